@@ -1,8 +1,10 @@
+use crate::clock::UnsetClock;
+use crate::command_policy::{CommandPolicy, DeviceModelCommandPolicy};
 use crate::executor::Executor;
 use crate::state::{
     AuthorizationRequested, BatterySwapEvent, ChargePointEffect, ChargePointEvent,
-    ChargePointState, ConnectorStatusChanged, HardwareCommand, PriorityChargingChange,
-    ReservationUpdate, ResetKind, SecurityEvent, SmartChargingNotification,
+    ChargePointState, ConnectorStatus, ConnectorStatusChanged, HardwareCommand,
+    PriorityChargingChange, ReservationUpdate, ResetKind, SecurityEvent, SmartChargingNotification,
     TransactionEventOccurred, TriggeredMonitor,
 };
 use crate::sync::{
@@ -35,6 +37,7 @@ enum Command {
 struct EffectSenders {
     commands: BroadcastSender<HardwareCommand>,
     status_notifications: BroadcastSender<ConnectorStatusChanged>,
+    station_status_notifications: BroadcastSender<ConnectorStatus>,
     transaction_events: BroadcastSender<TransactionEventOccurred>,
     authorization_requests: BroadcastSender<AuthorizationRequested>,
     security_events: BroadcastSender<SecurityEvent>,
@@ -74,6 +77,7 @@ pub struct ChargePointActor {
     state: WatchReceiver<ChargePointState>,
     commands: BroadcastSender<HardwareCommand>,
     status_notifications: BroadcastSender<ConnectorStatusChanged>,
+    station_status_notifications: BroadcastSender<ConnectorStatus>,
     transaction_events: BroadcastSender<TransactionEventOccurred>,
     authorization_requests: BroadcastSender<AuthorizationRequested>,
     security_events: BroadcastSender<SecurityEvent>,
@@ -88,6 +92,9 @@ pub struct ChargePointActor {
     // reach it that doesn't route through `ResetHandler`'s per-protocol-version implementations.
     boot_reason_recorder:
         Arc<BlockingMutex<CriticalSectionRawMutex, RefCell<Option<BootReasonRecorder>>>>,
+    // The same kind of settable hook, for the same reason: every inbound handler consults it, and
+    // every one of them already carries the actor. See `set_command_policy`.
+    command_policy: Arc<BlockingMutex<CriticalSectionRawMutex, RefCell<Arc<dyn CommandPolicy>>>>,
 }
 
 impl ChargePointActor {
@@ -141,6 +148,7 @@ impl ChargePointActor {
         let (updates, state_receiver) = watch_channel(state.clone());
         let commands = broadcast_channel();
         let status_notifications = broadcast_channel();
+        let station_status_notifications = broadcast_channel();
         let transaction_events = broadcast_channel();
         let authorization_requests = broadcast_channel();
         let security_events = broadcast_channel();
@@ -152,6 +160,7 @@ impl ChargePointActor {
         let effects = EffectSenders {
             commands: commands.clone(),
             status_notifications: status_notifications.clone(),
+            station_status_notifications: station_status_notifications.clone(),
             transaction_events: transaction_events.clone(),
             authorization_requests: authorization_requests.clone(),
             security_events: security_events.clone(),
@@ -174,6 +183,7 @@ impl ChargePointActor {
             state: state_receiver,
             commands,
             status_notifications,
+            station_status_notifications,
             transaction_events,
             authorization_requests,
             security_events,
@@ -183,6 +193,9 @@ impl ChargePointActor {
             battery_swap_events,
             smart_charging_notifications,
             boot_reason_recorder: Arc::new(BlockingMutex::new(RefCell::new(None))),
+            command_policy: Arc::new(BlockingMutex::new(RefCell::new(Arc::new(
+                DeviceModelCommandPolicy::new(UnsetClock),
+            )))),
         }
     }
 
@@ -245,6 +258,13 @@ impl ChargePointActor {
         self.status_notifications.subscribe()
     }
 
+    /// Subscribes to every change in the charge point's own status, reported to a 1.6J CSMS via
+    /// StatusNotification on `connectorId` 0 (see
+    /// [`crate::availability::StatusNotifier::notify_station_status`]).
+    pub fn subscribe_station_status_notifications(&self) -> BroadcastReceiver<ConnectorStatus> {
+        self.station_status_notifications.subscribe()
+    }
+
     /// Subscribes to every transaction lifecycle event (started/updated/ended), reported to the
     /// CSMS via TransactionEvent by the Transactions functional block (see
     /// [`crate::transactions::run_transaction_events`]).
@@ -339,6 +359,21 @@ impl ChargePointActor {
     pub(crate) fn boot_reason_recorder(&self) -> Option<BootReasonRecorder> {
         self.boot_reason_recorder.lock(|cell| cell.borrow().clone())
     }
+
+    /// Installs `policy`, consulted by every inbound CSMS handler before it acts - see
+    /// [`crate::command_policy`]. Replaces the one installed before it; an actor starts with a
+    /// [`DeviceModelCommandPolicy`] on an [`UnsetClock`], so the device model's
+    /// `CommandPolicyCtrlr` rules apply from the first command, and the time-based ones refuse
+    /// until a policy with a real clock is installed.
+    pub fn set_command_policy(&self, policy: Arc<dyn CommandPolicy>) {
+        self.command_policy.lock(|cell| *cell.borrow_mut() = policy);
+    }
+
+    /// The policy installed via [`Self::set_command_policy`]. `pub(crate)` - intended for
+    /// [`crate::command_policy::screen`] only.
+    pub(crate) fn command_policy(&self) -> Arc<dyn CommandPolicy> {
+        self.command_policy.lock(|cell| cell.borrow().clone())
+    }
 }
 
 async fn run(
@@ -389,6 +424,9 @@ async fn run(
                     ChargePointEffect::StatusNotification(changed) => {
                         effects.status_notifications.send(changed);
                     }
+                    ChargePointEffect::StationStatusNotification(status) => {
+                        effects.station_status_notifications.send(status);
+                    }
                     ChargePointEffect::TransactionEvent(occurred) => {
                         effects.transaction_events.send(occurred);
                     }
@@ -464,6 +502,7 @@ mod logging_tests {
         let effects = EffectSenders {
             commands: broadcast_channel(),
             status_notifications: broadcast_channel(),
+            station_status_notifications: broadcast_channel(),
             transaction_events: broadcast_channel(),
             authorization_requests: broadcast_channel(),
             security_events: broadcast_channel(),
@@ -171,6 +171,8 @@ mod tests {
                         limit_reached: None,
                         energy_start_wh: None,
                         elapsed_secs: None,
+                        ended_meter_samples: Vec::new(),
+                        start_meter_sample: None,
                     },
                 }),
             ]
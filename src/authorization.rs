@@ -312,6 +312,7 @@ pub enum ClearCacheOutcome {
     Accepted,
     /// Caching is disabled on this charge point (`AuthCacheCtrlr`/`Enabled` is false), so there is
     /// no cache to clear. OCPP's own guidance for a charge point without an authorization cache.
+    /// Also the answer when the [command policy](crate::command_policy) refuses the request.
     Rejected,
 }
 
@@ -326,6 +327,9 @@ pub async fn handle_clear_cache(actor: &ChargePointActor) -> ClearCacheOutcome {
     if !boolean_variable(&state, "AuthCacheCtrlr", "Enabled", true) {
         return ClearCacheOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "ClearCache") {
+        return ClearCacheOutcome::Rejected;
+    }
     let _ = actor
         .send(ChargePointEvent::AuthorizationCacheCleared)
         .await;
@@ -4,8 +4,8 @@
 
 use crate::actor::ChargePointActor;
 use crate::state::{
-    ChargePointEvent, ChargePointState, Component, ConnectorEvent, ConnectorState, ConnectorStatus,
-    ConnectorStatusChanged, EvseEvent, Variable, VariableAttributeType,
+    ChargePointEvent, ChargePointState, Component, ConnectorState, ConnectorStatus,
+    ConnectorStatusChanged, Variable, VariableAttributeType,
 };
 use crate::sync::BroadcastReceiver;
 use alloc::boxed::Box;
@@ -21,6 +21,7 @@ pub use self::ocpp_1_6::{Ocpp1_6ChangeAvailabilityHandler, Ocpp1_6StatusNotifier
 pub use self::ocpp_2_0_1::Ocpp2_0_1StatusNotifier;
 #[cfg(feature = "ocpp_2_1")]
 pub use self::ocpp_2_1::Ocpp2_1StatusNotifier;
+pub use crate::state::AvailabilityTarget;
 
 /// Reports a connector's status to the CSMS via StatusNotification. Implemented per protocol
 /// version (see the `ocpp_2_1`/`ocpp_1_6` modules), mirroring
@@ -43,6 +44,16 @@ pub trait StatusNotifier {
         status: ConnectorStatus,
         connector_state: ConnectorState,
     ) -> Result<(), Self::Error>;
+
+    /// Reports the charge point's own status - see
+    /// [`ChargePointState::station_status`](crate::state::ChargePointState::station_status).
+    /// Only 1.6J has a message for this, a StatusNotification on `connectorId` 0, so the default
+    /// reports nothing: a 2.x CSMS learns the same change from the per-connector reports
+    /// [`Self::notify_status`] already gets for every connector it covers.
+    async fn notify_station_status(&self, status: ConnectorStatus) -> Result<(), Self::Error> {
+        let _ = status;
+        Ok(())
+    }
 }
 
 /// Wraps any [`StatusNotifier`] to only forward a call when `status` actually differs from the
@@ -122,6 +133,12 @@ impl<N: StatusNotifier + Sync> StatusNotifier for DedupedStatusNotifier<N> {
             .lock(|cache| cache.borrow_mut().insert((evse_id, connector_id), status));
         Ok(())
     }
+
+    /// Forwarded without dedup: the state machine only reports the station's status when it
+    /// actually changes, so there are no internal transitions to filter out.
+    async fn notify_station_status(&self, status: ConnectorStatus) -> Result<(), Self::Error> {
+        self.inner.notify_station_status(status).await
+    }
 }
 
 /// The `(Component, Variable)` of `ChargingStation.MinimumStatusDuration`.
@@ -225,11 +242,7 @@ impl<N, B> MinimumStatusDurationNotifier<N, B> {
     fn current_status(&self, evse_id: usize, connector_id: usize) -> Option<ConnectorStatus> {
         self.actor
             .state()
-            .evses
-            .get(evse_id)?
-            .connectors
-            .get(connector_id)
-            .map(ConnectorState::availability_status)
+            .reported_connector_status(evse_id, connector_id)
     }
 }
 
@@ -286,27 +299,12 @@ where
             .notify_status(evse_id, connector_id, status, connector_state)
             .await
     }
-}
 
-/// The scope of a CSMS-initiated `ChangeAvailability` request - OCPP's optional `evse`/
-/// `connectorId` addressing collapsed to one of the three levels the internal state model
-/// tracks availability at.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum AvailabilityTarget {
-    /// The request addresses the whole charge point.
-    ChargePoint,
-    /// The request addresses one EVSE and every connector on it.
-    Evse {
-        /// The targeted EVSE's index.
-        evse_id: usize,
-    },
-    /// The request addresses a single connector.
-    Connector {
-        /// The targeted connector's EVSE index.
-        evse_id: usize,
-        /// The targeted connector's index within its EVSE.
-        connector_id: usize,
-    },
+    /// Forwarded at once: `MinimumStatusDuration` is about a bouncing connector, and the
+    /// station's status only moves on a `ChangeAvailability`, a fault, or registration.
+    async fn notify_station_status(&self, status: ConnectorStatus) -> Result<(), Self::Error> {
+        self.inner.notify_station_status(status).await
+    }
 }
 
 /// Forwards to the wrapped notifier, so a version adapter that owns a cache (and therefore cannot
@@ -327,18 +325,28 @@ impl<N: StatusNotifier + Send + Sync> StatusNotifier for alloc::sync::Arc<N> {
             .notify_status(evse_id, connector_id, status, connector_state)
             .await
     }
+
+    async fn notify_station_status(
+        &self,
+        status: crate::state::ConnectorStatus,
+    ) -> Result<(), Self::Error> {
+        (**self).notify_station_status(status).await
+    }
 }
 
-/// The outcome of a CSMS-initiated `ChangeAvailability` request, matching (a subset of) OCPP's
-/// `ChangeAvailabilityStatusEnum`. `Scheduled` - deferring the change until an in-progress
-/// transaction ends - isn't modeled: `SetUnavailable` takes effect immediately regardless of an
-/// active transaction (see `docs/ROADMAP.md` §7).
+/// The outcome of a CSMS-initiated `ChangeAvailability` request, matching OCPP's
+/// `ChangeAvailabilityStatusEnum`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChangeAvailabilityOutcome {
     /// The request addressed a real EVSE/connector and was applied.
     Accepted,
-    /// The request addressed an EVSE/connector index that doesn't exist.
+    /// The request addressed an EVSE/connector index that doesn't exist, or the
+    /// [command policy](crate::command_policy) refused it.
     Rejected,
+    /// A change to inoperative arrived while a transaction was in progress inside its scope, and
+    /// will be applied once that transaction ends (G03.FR.05) - see
+    /// [`ChargePointState::scheduled_unavailability`].
+    Scheduled,
 }
 
 /// Handles a CSMS-initiated `ChangeAvailability` request against `actor`: rejects a `target`
@@ -347,72 +355,58 @@ pub enum ChangeAvailabilityOutcome {
 /// (charge-point-wide, EVSE, or connector) and accepts. Availability changes apply
 /// synchronously within the actor (unlike e.g. `UnlockConnector`, no hardware round-trip is
 /// needed), so this doesn't need to wait for a confirming state change.
+///
+/// # A transaction in progress
+///
+/// A change to inoperative whose scope has a transaction running is not applied: it is recorded
+/// and answered [`ChangeAvailabilityOutcome::Scheduled`], and the state machine applies it when
+/// the last such transaction ends (G03.FR.05) - a driver mid-session is never cut off by it. A
+/// change to operative is never deferred, since it interrupts nothing, and it cancels whatever
+/// was scheduled for its scope: the CSMS's latest word on a scope is the one that stands.
 #[tracing::instrument(skip_all, fields(?target, available))]
 pub async fn handle_change_availability_request(
     actor: &ChargePointActor,
     target: AvailabilityTarget,
     available: bool,
 ) -> ChangeAvailabilityOutcome {
-    let event = match target {
-        AvailabilityTarget::ChargePoint => availability_event(available),
+    let state = actor.state();
+    match target {
+        AvailabilityTarget::ChargePoint => {}
         AvailabilityTarget::Evse { evse_id } => {
-            if actor.state().evses.get(evse_id).is_none() {
+            if state.evses.get(evse_id).is_none() {
                 tracing::warn!("refusing ChangeAvailability: no such EVSE");
                 return ChangeAvailabilityOutcome::Rejected;
             }
-            ChargePointEvent::Evse {
-                evse_id,
-                event: evse_availability_event(available),
-            }
         }
         AvailabilityTarget::Connector {
             evse_id,
             connector_id,
         } => {
-            let connector_exists = actor
-                .state()
+            let connector_exists = state
                 .evses
                 .get(evse_id)
                 .is_some_and(|evse| evse.connectors.get(connector_id).is_some());
             if !connector_exists {
                 return ChangeAvailabilityOutcome::Rejected;
             }
-            ChargePointEvent::Evse {
-                evse_id,
-                event: EvseEvent::Connector {
-                    connector_id,
-                    event: connector_availability_event(available),
-                },
-            }
         }
-    };
-
-    let _ = actor.send(event).await;
-    ChangeAvailabilityOutcome::Accepted
-}
-
-fn availability_event(available: bool) -> ChargePointEvent {
-    if available {
-        ChargePointEvent::SetAvailable
-    } else {
-        ChargePointEvent::SetUnavailable
     }
-}
-
-fn evse_availability_event(available: bool) -> EvseEvent {
-    if available {
-        EvseEvent::SetAvailable
-    } else {
-        EvseEvent::SetUnavailable
+    if crate::command_policy::refuses(actor, "ChangeAvailability") {
+        return ChangeAvailabilityOutcome::Rejected;
     }
-}
 
-fn connector_availability_event(available: bool) -> ConnectorEvent {
     if available {
-        ConnectorEvent::SetAvailable
-    } else {
-        ConnectorEvent::SetUnavailable
+        let _ = actor
+            .send(ChargePointEvent::ScheduledUnavailabilityCancelled { target })
+            .await;
+    } else if state.transaction_in_progress(target) {
+        let _ = actor
+            .send(ChargePointEvent::UnavailabilityScheduled { target })
+            .await;
+        return ChangeAvailabilityOutcome::Scheduled;
     }
+    let _ = actor.send(target.availability_event(available)).await;
+    ChangeAvailabilityOutcome::Accepted
 }
 
 /// Registers this charge point's inbound `ChangeAvailability` handling with the CSMS
@@ -439,11 +433,13 @@ pub fn all_connector_statuses(state: &ChargePointState) -> Vec<ConnectorStatusCh
             evse.connectors
                 .iter()
                 .enumerate()
-                .map(move |(connector_id, connector)| ConnectorStatusChanged {
-                    evse_id,
-                    connector_id,
-                    status: connector.availability_status(),
-                    connector_state: *connector,
+                .filter_map(move |(connector_id, connector)| {
+                    Some(ConnectorStatusChanged {
+                        evse_id,
+                        connector_id,
+                        status: state.reported_connector_status(evse_id, connector_id)?,
+                        connector_state: *connector,
+                    })
                 })
         })
         .collect()
@@ -465,16 +461,26 @@ pub fn all_connector_statuses(state: &ChargePointState) -> Vec<ConnectorStatusCh
 /// this function is not called on every reconnect - see
 /// [`resynchronise_after_outage`].
 ///
+/// The charge point's own status goes first, through [`StatusNotifier::notify_station_status`] -
+/// 1.6J's `connectorId` 0, which a 1.6J CSMS equally knows nothing about after a boot.
+///
 /// Errors are logged per connector and do not stop the sweep: a CSMS that rejects one status is
 /// no reason to leave it ignorant of the rest.
 #[tracing::instrument(skip_all, fields(connectors))]
-pub async fn report_all_connector_statuses<N: StatusNotifier>(
+pub async fn report_all_connector_statuses<N: StatusNotifier + Sync>(
     actor: &ChargePointActor,
     notifier: &N,
 ) {
-    let statuses = all_connector_statuses(&actor.state());
+    let state = actor.state();
+    let statuses = all_connector_statuses(&state);
     tracing::Span::current().record("connectors", statuses.len());
     tracing::debug!("reporting every connector's status to the CSMS");
+    if let Err(err) = notifier.notify_station_status(state.station_status()).await {
+        tracing::warn!(
+            error = %err,
+            "the charge point's own status could not be resynchronised"
+        );
+    }
     for changed in statuses {
         if let Err(err) = notifier
             .notify_status(
@@ -538,7 +544,7 @@ pub fn offline_threshold_secs(actor: &ChargePointActor, fallback: u64) -> u64 {
 ///
 /// `offline_for_secs` is how long the CSMS was unreachable. See
 /// [`crate::connection::reregister_on_reconnect`] for where that is measured and how precisely.
-pub async fn resynchronise_after_outage<N: StatusNotifier>(
+pub async fn resynchronise_after_outage<N: StatusNotifier + Sync>(
     actor: &ChargePointActor,
     notifier: &N,
     offline_for_secs: u64,
@@ -843,6 +849,75 @@ mod tests {
         );
     }
 
+    /// Records only the station-level reports - 1.6J's `connectorId` 0.
+    struct StationRecordingNotifier {
+        seen: std::sync::Mutex<Vec<ConnectorStatus>>,
+    }
+
+    #[async_trait::async_trait]
+    impl StatusNotifier for StationRecordingNotifier {
+        type Error = core::convert::Infallible;
+
+        async fn notify_status(
+            &self,
+            _evse_id: usize,
+            _connector_id: usize,
+            _status: ConnectorStatus,
+            _connector_state: ConnectorState,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        async fn notify_station_status(&self, status: ConnectorStatus) -> Result<(), Self::Error> {
+            self.seen.lock().unwrap().push(status);
+            Ok(())
+        }
+    }
+
+    /// The sweep reports what each connector reports, not what it is on its own: a connector
+    /// inside an EVSE made unavailable is `Unavailable` to the CSMS, and the station's own status
+    /// goes out too for 1.6J's `connectorId` 0.
+    #[tokio::test]
+    async fn a_full_sweep_reports_an_out_of_service_evse_through_its_connectors_and_the_station() {
+        use crate::actor::ChargePointActor;
+        use crate::executor::TokioExecutor;
+
+        let actor = ChargePointActor::spawn([1, 1], &TokioExecutor);
+        actor
+            .send(ChargePointEvent::Evse {
+                evse_id: 1,
+                event: EvseEvent::SetUnavailable,
+            })
+            .await
+            .unwrap();
+
+        let (seen_tx, mut seen_rx) = watch::channel(Vec::new());
+        report_all_connector_statuses(&actor, &RecordingStatusNotifier { seen: seen_tx }).await;
+        let seen = seen_rx.borrow_and_update().clone();
+        assert_eq!(
+            seen,
+            alloc::vec![
+                (0, 0, ConnectorStatus::Available, ConnectorState::Available),
+                (
+                    1,
+                    0,
+                    ConnectorStatus::Unavailable,
+                    ConnectorState::Available
+                ),
+            ]
+        );
+
+        let station = StationRecordingNotifier {
+            seen: std::sync::Mutex::new(Vec::new()),
+        };
+        report_all_connector_statuses(&actor, &station).await;
+        // Still `Booting` - no BootNotification has been accepted in this test.
+        assert_eq!(
+            *station.seen.lock().unwrap(),
+            alloc::vec![ConnectorStatus::Unavailable]
+        );
+    }
+
     /// B04's split, as a pure decision. OCPP words it as *exceeds* the threshold, so an outage
     /// exactly equal to it takes the short path.
     #[test]
@@ -1215,7 +1290,47 @@ mod change_availability_tests {
     };
     use crate::actor::ChargePointActor;
     use crate::executor::TokioExecutor;
-    use crate::state::{ConnectorState, EvseStatus, LifecycleState};
+    use crate::state::{
+        ChargePointEvent, ConnectorEvent, ConnectorState, EvseEvent, EvseStatus, IdToken,
+        IdTokenKind, LifecycleState, StopReason,
+    };
+
+    async fn send(actor: &ChargePointActor, event: ConnectorEvent) {
+        let _ = actor
+            .send(ChargePointEvent::Evse {
+                evse_id: 0,
+                event: EvseEvent::Connector {
+                    connector_id: 0,
+                    event,
+                },
+            })
+            .await;
+    }
+
+    /// Drives connector 0 of a fresh actor to `Charging` with a transaction running.
+    async fn charging_actor() -> ChargePointActor {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let id_token = IdToken {
+            value: "04A224B2".into(),
+            kind: IdTokenKind::ISO14443,
+        };
+        for event in [
+            ConnectorEvent::CableConnected,
+            ConnectorEvent::LockConfirmed,
+            ConnectorEvent::IdTokenPresented(id_token.clone()),
+            ConnectorEvent::ChargingAuthorized(id_token),
+            ConnectorEvent::ContactorClosed,
+        ] {
+            send(&actor, event).await;
+        }
+        assert!(actor.state().evses[0].transactions[0].is_some());
+        actor
+    }
+
+    const CONNECTOR: AvailabilityTarget = AvailabilityTarget::Connector {
+        evse_id: 0,
+        connector_id: 0,
+    };
 
     #[tokio::test]
     async fn making_the_charge_point_unavailable_sets_the_lifecycle_state() {
@@ -1310,6 +1425,61 @@ mod change_availability_tests {
 
         assert_eq!(outcome, ChangeAvailabilityOutcome::Rejected);
     }
+
+    // G03.FR.05: inoperative during a transaction is Scheduled, and applied once it has ended.
+    #[tokio::test]
+    async fn making_a_charging_connector_unavailable_waits_for_the_transaction_to_end() {
+        let actor = charging_actor().await;
+
+        let outcome = handle_change_availability_request(&actor, CONNECTOR, false).await;
+
+        assert_eq!(outcome, ChangeAvailabilityOutcome::Scheduled);
+        assert_eq!(actor.state().scheduled_unavailability, [CONNECTOR]);
+        assert_ne!(
+            actor.state().evses[0].connectors[0],
+            ConnectorState::Unavailable
+        );
+
+        send(&actor, ConnectorEvent::ChargingStopped(StopReason::Local)).await;
+        send(&actor, ConnectorEvent::ContactorOpened).await;
+
+        assert!(actor.state().evses[0].transactions[0].is_none());
+        assert!(actor.state().scheduled_unavailability.is_empty());
+        assert_eq!(
+            actor.state().evses[0].connectors[0],
+            ConnectorState::Unavailable
+        );
+    }
+
+    #[tokio::test]
+    async fn a_whole_charge_point_change_is_scheduled_behind_any_transaction() {
+        let actor = charging_actor().await;
+
+        let outcome =
+            handle_change_availability_request(&actor, AvailabilityTarget::ChargePoint, false)
+                .await;
+
+        assert_eq!(outcome, ChangeAvailabilityOutcome::Scheduled);
+        assert_ne!(actor.state().lifecycle, LifecycleState::Unavailable);
+    }
+
+    #[tokio::test]
+    async fn making_a_connector_operative_cancels_its_scheduled_change() {
+        let actor = charging_actor().await;
+        handle_change_availability_request(&actor, CONNECTOR, false).await;
+
+        let outcome = handle_change_availability_request(&actor, CONNECTOR, true).await;
+        assert_eq!(outcome, ChangeAvailabilityOutcome::Accepted);
+        assert!(actor.state().scheduled_unavailability.is_empty());
+
+        send(&actor, ConnectorEvent::ChargingStopped(StopReason::Local)).await;
+        send(&actor, ConnectorEvent::ContactorOpened).await;
+
+        assert_ne!(
+            actor.state().evses[0].connectors[0],
+            ConnectorState::Unavailable
+        );
+    }
 }
 
 #[cfg(feature = "ocpp_2_1")]
@@ -1513,6 +1683,7 @@ mod ocpp_2_1 {
         match outcome {
             ChangeAvailabilityOutcome::Accepted => ChangeAvailabilityStatusEnum::Accepted,
             ChangeAvailabilityOutcome::Rejected => ChangeAvailabilityStatusEnum::Rejected,
+            ChangeAvailabilityOutcome::Scheduled => ChangeAvailabilityStatusEnum::Scheduled,
         }
     }
 
@@ -1623,6 +1794,10 @@ mod ocpp_2_1 {
                 map_outcome(ChangeAvailabilityOutcome::Rejected),
                 ChangeAvailabilityStatusEnum::Rejected
             );
+            assert_eq!(
+                map_outcome(ChangeAvailabilityOutcome::Scheduled),
+                ChangeAvailabilityStatusEnum::Scheduled
+            );
         }
     }
 }
@@ -1820,6 +1995,7 @@ mod ocpp_2_0_1 {
         match outcome {
             ChangeAvailabilityOutcome::Accepted => ChangeAvailabilityStatusEnum::Accepted,
             ChangeAvailabilityOutcome::Rejected => ChangeAvailabilityStatusEnum::Rejected,
+            ChangeAvailabilityOutcome::Scheduled => ChangeAvailabilityStatusEnum::Scheduled,
         }
     }
 
@@ -1930,6 +2106,10 @@ mod ocpp_2_0_1 {
                 map_outcome(ChangeAvailabilityOutcome::Rejected),
                 ChangeAvailabilityStatusEnum::Rejected
             );
+            assert_eq!(
+                map_outcome(ChangeAvailabilityOutcome::Scheduled),
+                ChangeAvailabilityStatusEnum::Scheduled
+            );
         }
     }
 }
@@ -1951,7 +2131,7 @@ mod ocpp_2_0_1 {
 #[cfg(feature = "ocpp_1_6")]
 mod ocpp_1_6 {
     use crate::availability::StatusNotifier;
-    use crate::state::ConnectorState;
+    use crate::state::{ConnectorState, ConnectorStatus};
     use crate::topology::flatten_ocpp_1_6_connector_id;
     use crate::wire::v16::StatusNotificationRequest;
     use crate::wire::v16::common::{ErrorCode, StatusNotificationRequestStatus};
@@ -1994,6 +2174,34 @@ mod ocpp_1_6 {
         }
     }
 
+    /// The 1.6J status to report for a connector whose derived `status` may not be its own:
+    /// when its EVSE or the whole charge point is out of service, `status` says so and wins -
+    /// `connector_state` would describe a connector a driver can't use as `Available`. Otherwise
+    /// [`map_status`]'s richer reading of `connector_state` stands.
+    pub(super) fn wire_status(
+        status: ConnectorStatus,
+        connector_state: ConnectorState,
+    ) -> StatusNotificationRequestStatus {
+        if status == connector_state.availability_status() {
+            return map_status(connector_state);
+        }
+        map_station_status(status)
+    }
+
+    /// Maps a status that carries no connector detail - the charge point's own, on `connectorId`
+    /// 0, or an EVSE's or the station's showing through on a connector. 1.6J allows only
+    /// `Available`, `Unavailable` and `Faulted` on `connectorId` 0; the other two values can't
+    /// arise from a [`crate::state::LifecycleState`] and are mapped for completeness.
+    pub(super) fn map_station_status(status: ConnectorStatus) -> StatusNotificationRequestStatus {
+        match status {
+            ConnectorStatus::Available => StatusNotificationRequestStatus::Available,
+            ConnectorStatus::Occupied => StatusNotificationRequestStatus::Preparing,
+            ConnectorStatus::Reserved => StatusNotificationRequestStatus::Reserved,
+            ConnectorStatus::Unavailable => StatusNotificationRequestStatus::Unavailable,
+            ConnectorStatus::Faulted => StatusNotificationRequestStatus::Faulted,
+        }
+    }
+
     /// Wraps an `OCPP1_6Client` with the charge point's connector topology, needed to translate
     /// this crate's `(evse_id, connector_id)` addressing into 1.6J's flat `connectorId` - see
     /// this module's docs. `connector_counts` is each EVSE's connector count, in `evse_id`
@@ -2043,7 +2251,7 @@ mod ocpp_1_6 {
             &self,
             evse_id: usize,
             connector_id: usize,
-            _status: crate::state::ConnectorStatus,
+            status: ConnectorStatus,
             connector_state: ConnectorState,
         ) -> Result<(), Self::Error> {
             let Some(connector_id) =
@@ -2059,7 +2267,23 @@ mod ocpp_1_6 {
                     connector_id,
                     error_code: ErrorCode::NoError,
                     info: None,
-                    status: map_status(connector_state),
+                    status: wire_status(status, connector_state),
+                    timestamp: None,
+                    vendor_error_code: None,
+                    vendor_id: None,
+                })
+                .await?;
+            Ok(())
+        }
+
+        /// `connectorId` 0: the charge point itself, which 1.6J reports like a connector.
+        async fn notify_station_status(&self, status: ConnectorStatus) -> Result<(), Self::Error> {
+            self.client
+                .send_status_notification(StatusNotificationRequest {
+                    connector_id: 0,
+                    error_code: ErrorCode::NoError,
+                    info: None,
+                    status: map_station_status(status),
                     timestamp: None,
                     vendor_error_code: None,
                     vendor_id: None,
@@ -2139,6 +2363,39 @@ mod ocpp_1_6 {
             );
         }
 
+        #[test]
+        fn a_connector_inside_an_out_of_service_evse_reports_the_evses_status() {
+            assert_eq!(
+                wire_status(ConnectorStatus::Unavailable, ConnectorState::Available),
+                StatusNotificationRequestStatus::Unavailable
+            );
+            assert_eq!(
+                wire_status(ConnectorStatus::Faulted, ConnectorState::Charging),
+                StatusNotificationRequestStatus::Faulted
+            );
+            // Its own status is still read at 1.6J's full granularity when nothing overrides it.
+            assert_eq!(
+                wire_status(ConnectorStatus::Occupied, ConnectorState::SuspendedEv),
+                StatusNotificationRequestStatus::SuspendedEV
+            );
+        }
+
+        #[test]
+        fn every_station_status_maps_to_a_connector_zero_status() {
+            assert_eq!(
+                map_station_status(ConnectorStatus::Available),
+                StatusNotificationRequestStatus::Available
+            );
+            assert_eq!(
+                map_station_status(ConnectorStatus::Unavailable),
+                StatusNotificationRequestStatus::Unavailable
+            );
+            assert_eq!(
+                map_station_status(ConnectorStatus::Faulted),
+                StatusNotificationRequestStatus::Faulted
+            );
+        }
+
         #[test]
         fn ocpp1_6_status_notifier_implements_status_notifier() {
             fn assert_impl<T: StatusNotifier>() {}
@@ -2186,6 +2443,7 @@ mod ocpp_1_6 {
         match outcome {
             ChangeAvailabilityOutcome::Accepted => ChangeAvailabilityResponseStatus::Accepted,
             ChangeAvailabilityOutcome::Rejected => ChangeAvailabilityResponseStatus::Rejected,
+            ChangeAvailabilityOutcome::Scheduled => ChangeAvailabilityResponseStatus::Scheduled,
         }
     }
 
@@ -2288,6 +2546,10 @@ mod ocpp_1_6 {
                 map_outcome(ChangeAvailabilityOutcome::Rejected),
                 ChangeAvailabilityResponseStatus::Rejected
             );
+            assert_eq!(
+                map_outcome(ChangeAvailabilityOutcome::Scheduled),
+                ChangeAvailabilityResponseStatus::Scheduled
+            );
         }
 
         #[test]
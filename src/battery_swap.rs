@@ -60,6 +60,9 @@ pub async fn handle_request_battery_swap<S: BatterySwapStation>(
     if !crate::refusal::capability_present(&state.capabilities, "RequestBatterySwap") {
         return RequestBatterySwapOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "RequestBatterySwap") {
+        return RequestBatterySwapOutcome::Rejected;
+    }
 
     let _ = actor
         .send(ChargePointEvent::BatterySwapRequested(PendingBatterySwap {
@@ -49,15 +49,16 @@ use crate::periodic_event_stream::{
 };
 use crate::persistence::{
     AuthorizationCacheStore, BootReasonStore, DeviceModelStore, NetworkProfileSnapshotStore,
-    QueueStore, TransactionStore, flush_and_persist_security_event_queue,
-    flush_and_persist_status_notification_queue, flush_and_persist_transaction_event_queue,
-    restore_authorization_cache, restore_device_model, restore_network_profiles,
-    restore_security_event_queue, restore_security_log, restore_status_notification_queue,
-    restore_transaction_event_queue, restore_transactions, run_authorization_cache_persistence,
-    run_device_model_persistence, run_network_profile_persistence,
-    run_persisted_security_event_queue, run_persisted_status_notification_queue,
-    run_persisted_transaction_event_queue, run_security_log_persistence,
-    run_transaction_persistence,
+    QueueStore, ScheduledUnavailabilityStore, TransactionStore,
+    flush_and_persist_security_event_queue, flush_and_persist_status_notification_queue,
+    flush_and_persist_transaction_event_queue, restore_authorization_cache, restore_device_model,
+    restore_network_profiles, restore_scheduled_unavailability, restore_security_event_queue,
+    restore_security_log, restore_status_notification_queue, restore_transaction_event_queue,
+    restore_transactions, run_authorization_cache_persistence, run_device_model_persistence,
+    run_network_profile_persistence, run_persisted_security_event_queue,
+    run_persisted_status_notification_queue, run_persisted_transaction_event_queue,
+    run_scheduled_unavailability_persistence, run_security_log_clears,
+    run_security_log_persistence, run_transaction_persistence,
 };
 // Split out from the `crate::persistence` import above (C4.2): each of these backs exactly one
 // feature-gated registration method below, so importing it unconditionally would leave an unused
@@ -83,9 +84,9 @@ use crate::reservation::{CancelReservationHandler, ReserveNowHandler};
 use crate::reset::ResetHandler;
 use crate::security::{SecurityEventNotifier, report_security_event};
 use crate::state::{
-    AuthorizationRequested, BootReasonCause, ChargePointEvent, Component, ConnectorStatusChanged,
-    DeviceModelEvent, SecurityEvent, SecurityEventType, TransactionEventOccurred, Variable,
-    VariableAttributeType,
+    AuthorizationRequested, BootReasonCause, ChargePointEvent, Component, ConnectorStatus,
+    ConnectorStatusChanged, DeviceModelEvent, SecurityEvent, SecurityEventType,
+    TransactionEventOccurred, Variable, VariableAttributeType,
 };
 use crate::sync::BroadcastReceiver;
 #[cfg(feature = "tariff-cost")]
@@ -135,6 +136,9 @@ pub struct ChargePointBuilder<T, X> {
     // is consulted to keep every advertisement surface in sync with what the hardware declares.
     capabilities: Capabilities,
     status_changes: Option<BroadcastReceiver<ConnectorStatusChanged>>,
+    // Taken alongside `status_changes` by whichever status registration runs - see
+    // `Self::forward_station_statuses`.
+    station_status_changes: Option<BroadcastReceiver<ConnectorStatus>>,
     transaction_events: Option<BroadcastReceiver<TransactionEventOccurred>>,
     authorization_requests: Option<BroadcastReceiver<AuthorizationRequested>>,
     security_events: Option<BroadcastReceiver<SecurityEvent>>,
@@ -173,10 +177,20 @@ pub struct ChargePointBuilder<T, X> {
     // the life of the process once `provisioning` reads it, including every reconnect's resend -
     // see `crate::connection::reregister_on_reconnect`'s docs for why it's never re-read.
     boot_reason: Option<BootReasonCause>,
-    // Set by `boot_reason_persistence`, consumed by `provisioning` once the CSMS accepts
-    // registration - see `BootReasonClearer`'s docs for why this is type-erased rather than a
-    // generic `BootReasonStore<S>` field (which would force `S` onto `ChargePointBuilder` itself).
-    boot_reason_clearer: Option<Arc<dyn BootReasonClearer>>,
+    // Pushed to by `boot_reason_persistence` and `firmware_update_persistence`, consumed by
+    // `provisioning` once the CSMS accepts registration - see `BootReasonClearer`'s docs for why
+    // these are type-erased rather than generic store fields (which would force each store's `S`
+    // onto `ChargePointBuilder` itself).
+    boot_reason_clearers: Vec<Arc<dyn BootReasonClearer>>,
+    // Set by `firmware_update_persistence`: the update the previous boot rebooted to finish, if
+    // any. Read by `provisioning` (it decides the boot reason) and taken by `firmware_updates`,
+    // which reports how that update ended.
+    #[cfg(feature = "firmware-management")]
+    pending_firmware_update: Option<crate::persistence::PendingFirmwareUpdate>,
+    // Set by `firmware_update_persistence`, handed by `firmware_updates` to the worker so it can
+    // record the next update before the reboot that finishes it.
+    #[cfg(feature = "firmware-management")]
+    firmware_update_recorder: Option<Arc<dyn crate::firmware::PendingUpdateRecorder>>,
 }
 
 /// One registered offline queue's flush, type-erased so [`ChargePointBuilder`] can hold flushes
@@ -204,6 +218,17 @@ impl<S: crate::hardware::Storage + Send + Sync> BootReasonClearer for BootReason
     }
 }
 
+// A pending firmware update is what makes a boot's reason `FirmwareUpdate`, so it is cleared on
+// exactly the same terms as a persisted boot reason.
+#[async_trait::async_trait]
+impl<S: crate::hardware::Storage + Send + Sync> BootReasonClearer
+    for crate::persistence::FirmwareUpdateStore<S>
+{
+    async fn clear(&self) {
+        crate::persistence::FirmwareUpdateStore::clear(self).await;
+    }
+}
+
 impl<T, X: Executor> ChargePointBuilder<T, X> {
     /// Starts the hardware and takes the four functional-block subscriptions (status,
     /// transaction, authorization, security) before doing so, so that events fired during
@@ -264,6 +289,7 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         // during `start()` (e.g. a connector that's already occupied at boot) are buffered rather
         // than lost.
         let status_changes = runtime.subscribe_status_notifications();
+        let station_status_changes = runtime.subscribe_station_status_notifications();
         let transaction_events = runtime.subscribe_transaction_events();
         let authorization_requests = runtime.subscribe_authorization_requests();
         let security_events = runtime.subscribe_security_events();
@@ -324,6 +350,7 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
             model_name,
             capabilities,
             status_changes: Some(status_changes),
+            station_status_changes: Some(station_status_changes),
             transaction_events: Some(transaction_events),
             authorization_requests: Some(authorization_requests),
             security_events: Some(security_events),
@@ -333,7 +360,11 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
             queue_flushes: Vec::new(),
             offline_queue_capacity: crate::offline_queue::DEFAULT_CAPACITY,
             boot_reason: None,
-            boot_reason_clearer: None,
+            boot_reason_clearers: Vec::new(),
+            #[cfg(feature = "firmware-management")]
+            pending_firmware_update: None,
+            #[cfg(feature = "firmware-management")]
+            firmware_update_recorder: None,
         })
     }
 
@@ -485,6 +516,10 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
     /// runtime without a reboot. Unlike [`Self::transaction_events`], this reports readings taken
     /// with no transaction running at all, which is the whole point of the message.
     ///
+    /// A 1.6J notifier also keeps each boundary's reading for `StopTxnAlignedData`, and makes
+    /// `AlignedDataCtrlr.TxEndedMeasurands` writable here - see
+    /// [`crate::meter_values::advertise_aligned_stop_data_support`].
+    ///
     /// `backoff`/`clock` are caller-supplied for the same no_std reason [`Self::provisioning`]'s
     /// are.
     pub async fn meter_values<N, B, K>(self, csms: &N, backoff: B, clock: K) -> Self
@@ -494,6 +529,9 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         K: crate::clock::Clock + Send + Sync + 'static,
     {
         let actor = self.runtime.actor();
+        if csms.keeps_aligned_stop_data() {
+            crate::meter_values::advertise_aligned_stop_data_support(&actor).await;
+        }
         let notifier = csms.clone();
         self.executor.spawn(Box::pin(async move {
             crate::meter_values::run_aligned_meter_values(&notifier, &backoff, &clock, &actor)
@@ -730,7 +768,7 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         let store = Arc::new(BootReasonStore::new(storage));
 
         self.boot_reason = store.load().await;
-        self.boot_reason_clearer = Some(store.clone());
+        self.boot_reason_clearers.push(store.clone());
 
         let recorder_store = store.clone();
         self.runtime.actor().set_boot_reason_recorder(Arc::new(
@@ -746,6 +784,38 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         self
     }
 
+    /// Installs `policy` as the [`crate::command_policy::CommandPolicy`] every inbound CSMS
+    /// handler consults before acting, in place of the built-in
+    /// [`crate::command_policy::DeviceModelCommandPolicy`] on an unset clock.
+    ///
+    /// The usual call passes the built-in policy with a real clock -
+    /// `DeviceModelCommandPolicy::new(SystemClock)` - which is what lets its maintenance-window
+    /// and confirmation rules accept anything at all; see [`Self::command_policy_rules`] for
+    /// configuring it. Registration order is irrelevant: the policy is read per command.
+    pub fn command_policy<P>(self, policy: P) -> Self
+    where
+        P: crate::command_policy::CommandPolicy + 'static,
+    {
+        self.runtime.actor().set_command_policy(Arc::new(policy));
+        self
+    }
+
+    /// Registers `rules` in the device model's `CommandPolicyCtrlr` component, where the built-in
+    /// [`crate::command_policy::DeviceModelCommandPolicy`] reads them - see
+    /// [`crate::command_policy::CommandPolicyRules`]. Equivalent to a hardware binding sending
+    /// [`crate::command_policy::CommandPolicyRules::device_model_events`] itself. Each variable
+    /// counts against the device model's `max_variables`.
+    pub async fn command_policy_rules(
+        self,
+        rules: crate::command_policy::CommandPolicyRules,
+    ) -> Self {
+        let actor = self.runtime.actor();
+        for event in rules.device_model_events() {
+            let _ = actor.send(ChargePointEvent::DeviceModel(event)).await;
+        }
+        self
+    }
+
     /// Registers the Provisioning functional block: retries BootNotification (via
     /// [`ChargePointRuntime::register_until_accepted`]) until the CSMS accepts registration,
     /// lands the accepted Heartbeat interval into the `OCPPCommCtrlr`/`HeartbeatInterval` device
@@ -768,6 +838,15 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
     {
         let vendor_name = self.vendor_name.as_str();
         let model_name = self.model_name.as_str();
+        // The reboot that finished a firmware update went through the ordinary `Reset` path, which
+        // recorded `RemoteReset` for it - but the reason the CSMS wants is the update.
+        #[cfg(feature = "firmware-management")]
+        let boot_reason = if self.pending_firmware_update.is_some() {
+            Some(BootReasonCause::FirmwareUpdate)
+        } else {
+            self.boot_reason
+        };
+        #[cfg(not(feature = "firmware-management"))]
         let boot_reason = self.boot_reason;
         let outcome = self
             .runtime
@@ -782,11 +861,11 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
             .await;
 
         // The BootNotification carrying `boot_reason` has now been accepted - clear the
-        // persisted cause (if `boot_reason_persistence` registered one) so a *future* uncommanded
-        // restart doesn't wrongly keep reporting it. See
+        // persisted cause (if `boot_reason_persistence` or `firmware_update_persistence`
+        // registered one) so a *future* uncommanded restart doesn't wrongly keep reporting it. See
         // `crate::persistence::BootReasonStore::clear`'s docs for why this only happens now,
         // after acceptance, rather than before sending.
-        if let Some(clearer) = &self.boot_reason_clearer {
+        for clearer in &self.boot_reason_clearers {
             clearer.clear().await;
         }
 
@@ -873,6 +952,26 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         self
     }
 
+    /// Spawns the loop that ends each `InvalidMessages` rate-limiting interval, folding whatever
+    /// invalid CSMS messages arrived during it into one security-log entry (see
+    /// [`crate::invalid_messages`]).
+    ///
+    /// Counting needs nothing registered - every block that refuses a message counts it - so a
+    /// station that omits this still publishes `SecurityCtrlr.InvalidMessages` and still logs the
+    /// first invalid message. What it loses is every report after that one. `interval_secs` is
+    /// the most often a flood can reach the security log.
+    pub fn invalid_message_summaries<B>(self, backoff: B, interval_secs: u32) -> Self
+    where
+        B: crate::provisioning::Backoff + Send + Sync + 'static,
+    {
+        let actor = self.runtime.actor();
+        self.executor.spawn(Box::pin(async move {
+            crate::invalid_messages::run_invalid_message_summaries(&actor, &backoff, interval_secs)
+                .await;
+        }));
+        self
+    }
+
     /// Spawns the sweep that enforces a `maxTime` transaction limit
     /// (`docs/OCPP-2.1-COMPLIANCE-ROADMAP.md` CV21, **E16.FR.09**).
     ///
@@ -913,6 +1012,31 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         self
     }
 
+    /// Spawns the loop that paces transaction meter data to `SampledDataCtrlr.TxUpdatedInterval`
+    /// and samples it every `SampledDataCtrlr.TxEndedInterval` for the `Ended` event (J02).
+    ///
+    /// Without it, every reading the binding pushes goes out in its own `TransactionEvent` - fine
+    /// for a meter sampled once a minute, a flood for one sampled once a second. Both intervals
+    /// stay read-only until this is called, so a station that omits it cannot accept a setting
+    /// that would silence its readings. See
+    /// [`crate::transactions::run_transaction_meter_sampling`] for how the two interact with
+    /// `seqNo` and with a change at runtime.
+    ///
+    /// `backoff`/`clock` are caller-supplied for the same no_std reason [`Self::meter_values`]'
+    /// are; `clock` stamps the readings an `Ended` event reports.
+    pub async fn transaction_meter_sampling<B, K>(self, backoff: B, clock: K) -> Self
+    where
+        B: Backoff + Send + Sync + 'static,
+        K: crate::clock::Clock + Send + Sync + 'static,
+    {
+        crate::transactions::advertise_meter_sampling_support(&self.runtime.actor()).await;
+        let actor = self.runtime.actor();
+        self.executor.spawn(Box::pin(async move {
+            crate::transactions::run_transaction_meter_sampling(&actor, &backoff, &clock).await;
+        }));
+        self
+    }
+
     /// Reports every connector's current status when the CSMS's picture of this charge point can
     /// no longer be trusted (`docs/OCPP-2.1-COMPLIANCE-ROADMAP.md` CV5).
     ///
@@ -1026,6 +1150,8 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
             self.runtime.actor(),
             backoff,
         ));
+        self.forward_station_statuses(csms, status_notifier.clone())
+            .await;
         let forwarder_queue = status_queue.clone();
         let forwarder_notifier = status_notifier.clone();
         let overflow_actor = self.runtime.actor();
@@ -1128,6 +1254,8 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
             self.runtime.actor(),
             backoff,
         ));
+        self.forward_station_statuses(csms, status_notifier.clone())
+            .await;
         let forwarder_queue = status_queue.clone();
         let forwarder_store = store.clone();
         let forwarder_notifier = status_notifier.clone();
@@ -2276,6 +2404,35 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         self
     }
 
+    /// Registers durable scheduled availability changes (G03.FR.05): recovers every
+    /// `ChangeAvailability` to inoperative that was answered `Scheduled` and still waiting on a
+    /// transaction when the charge point last lost power, then persists every subsequent change
+    /// to that set through `storage` for the life of the process.
+    ///
+    /// A recovered change is applied at once, since the transaction it waited on was ended by
+    /// the power cut - see [`crate::persistence::restore_scheduled_unavailability`]'s docs. The
+    /// resulting `StatusNotification`s go out through [`Self::status_notifications`] like any
+    /// other.
+    ///
+    /// Independent of every other `*_persistence` method, same as
+    /// [`Self::local_authorization_list_persistence`]. `storage` may be
+    /// [`crate::hardware::NoStorage`]. Register this before [`Self::availability_control`] is
+    /// reachable by a CSMS request, for the same race-avoidance reason.
+    pub async fn scheduled_unavailability_persistence<S>(self, storage: S) -> Self
+    where
+        S: crate::hardware::Storage + Send + Sync + 'static,
+    {
+        let store = Arc::new(ScheduledUnavailabilityStore::new(storage));
+        restore_scheduled_unavailability(&self.runtime.actor(), &store).await;
+
+        let state_changes = self.runtime.actor().subscribe();
+        self.executor.spawn(Box::pin(async move {
+            run_scheduled_unavailability_persistence(state_changes, &store).await;
+        }));
+
+        self
+    }
+
     /// Registers durable device model attribute state (`docs/PRODUCTION-ROADMAP.md` §7.2, E2.3):
     /// recovers whatever `persistent`-flagged attribute values survived from before the charge
     /// point last lost power, then persists every subsequent change through `storage` for the
@@ -2314,8 +2471,15 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
     ///
     /// `log` is the caller's handle onto the live log - hold on to it (it is cheap to clone behind
     /// the [`Arc`] this takes) to read the history back, and to clear it via
-    /// [`crate::persistence::clear_security_log`]. It also fixes the bound on how much the log
-    /// retains; see [`crate::security::SecurityEventLog::with_capacity`].
+    /// [`crate::persistence::clear_security_log`]. It also fixes the bounds on how much the log
+    /// retains; see [`crate::security::SecurityEventLog::with_capacity`] and
+    /// [`with_max_bytes`](crate::security::SecurityEventLog::with_max_bytes).
+    ///
+    /// Also carries out clears the CSMS (`SecurityLogCtrlr.Clear`) or a technician
+    /// ([`crate::maintenance::MaintenanceCommand::ClearSecurityLog`]) ask for - see
+    /// [`crate::persistence::run_security_log_clears`]. The CSMS's variable is registered here,
+    /// not by default, since nothing else acts on it - see
+    /// [`crate::persistence::security_log_clear_registration`].
     ///
     /// Independent of [`Self::security_events`]/[`Self::security_events_persisted`] and of every
     /// other `*_persistence` method: the log records every event whether or not it is ever
@@ -2350,9 +2514,23 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         // follows, nor race a write against the same key.
         restore_security_log(&log, &store).await;
 
+        let store = Arc::new(store);
+        let (clear_log, clear_store) = (log.clone(), store.clone());
         self.executor.spawn(Box::pin(async move {
             run_security_log_persistence(events, &log, &store, &clock).await;
         }));
+        let actor = self.runtime.actor();
+        self.executor.spawn(Box::pin(async move {
+            run_security_log_clears(&actor, &clear_log, &clear_store).await;
+        }));
+        // After the spawn, so the variable is never writable without something to act on it.
+        let _ = self
+            .runtime
+            .actor()
+            .send(ChargePointEvent::DeviceModel(
+                crate::persistence::security_log_clear_registration(),
+            ))
+            .await;
 
         self
     }
@@ -2402,6 +2580,72 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         self
     }
 
+    /// Registers durable firmware-update state (`docs/PRODUCTION-ROADMAP.md` B3.2): loads the
+    /// update the previous boot rebooted to finish (if any), so [`Self::provisioning`] sends
+    /// `BootNotification.reason` `FirmwareUpdate` and [`Self::firmware_updates`] can report
+    /// whether it ended `Installed` or `InstallVerificationFailed`, then has that block record
+    /// every future update the same way before its reboot.
+    ///
+    /// Call this **before** [`Self::provisioning`] and [`Self::firmware_updates`], for the reason
+    /// [`Self::boot_reason_persistence`] gives. The marker is cleared once the CSMS accepts
+    /// registration, exactly as the persisted boot reason is. `storage` may be
+    /// [`crate::hardware::NoStorage`], in which case an update that needs a reboot ends at
+    /// `InstallRebooting`, as it does without this call.
+    ///
+    /// Only present when the `firmware-management` Cargo feature is enabled (C4.2).
+    #[cfg(feature = "firmware-management")]
+    pub async fn firmware_update_persistence<S>(mut self, storage: S) -> Self
+    where
+        S: crate::hardware::Storage + Send + Sync + 'static,
+    {
+        let store = Arc::new(crate::persistence::FirmwareUpdateStore::new(storage));
+
+        self.pending_firmware_update = store.load().await;
+        self.boot_reason_clearers.push(store.clone());
+        self.firmware_update_recorder = Some(store);
+
+        self
+    }
+
+    /// Spawns the supervisor that decides whether an A/B trial image stays (see
+    /// [`crate::hardware::AbFirmwareInstaller::supervise_trial_boot`]): confirmed once the CSMS
+    /// accepts a `BootNotification`, reverted and rebooted out of if the installer's rollback
+    /// window closes first. Does nothing on a boot that is not a trial.
+    ///
+    /// Call this **before** [`Self::provisioning`]: that method does not return until registration
+    /// is accepted, so a supervisor spawned after it could never see an image that fails to
+    /// register. Pass the same installer (behind an `Arc`) to [`Self::firmware_updates`].
+    ///
+    /// Only present when the `firmware-management` Cargo feature is enabled (C4.2).
+    #[cfg(feature = "firmware-management")]
+    pub fn trial_firmware_supervision<I, S, B, M>(
+        self,
+        installer: Arc<crate::hardware::AbFirmwareInstaller<I, S>>,
+        backoff: B,
+        monotonic: M,
+    ) -> Self
+    where
+        I: crate::hardware::FirmwareImage + Send + Sync + 'static,
+        S: crate::hardware::FirmwareSlots + Send + Sync + 'static,
+        B: Backoff + Send + Sync + 'static,
+        M: MonotonicClock + Send + Sync + 'static,
+    {
+        let actor = self.runtime.actor();
+        self.executor.spawn(Box::pin(async move {
+            match installer
+                .supervise_trial_boot(&actor, &backoff, &monotonic)
+                .await
+            {
+                Ok(outcome) => tracing::info!(?outcome, "trial firmware supervision finished"),
+                Err(err) => tracing::warn!(
+                    error = %err,
+                    "supervising the trial firmware failed; the bootloader will revert it"
+                ),
+            }
+        }));
+        self
+    }
+
     /// Registers the Firmware Management block (`docs/PRODUCTION-ROADMAP.md` B3.2/B3.3): inbound
     /// `UpdateFirmware` (and, for a 1.6J `csms`, the Security Whitepaper's `SignedUpdateFirmware`,
     /// see [`crate::firmware::SignedUpdateFirmwareHandler`]), and the worker that downloads,
@@ -2414,10 +2658,15 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
     /// receives signed updates) - which is why, like [`Self::log_uploads`], this is builder-only:
     /// `setup()` has no way to receive any of them.
     ///
+    /// If [`Self::firmware_update_persistence`] found an update the previous boot rebooted to
+    /// finish, this first reports how it ended (see [`crate::firmware::report_pending_update`]),
+    /// so call it after [`Self::provisioning`] - nothing may be sent before registration is
+    /// accepted.
+    ///
     /// Only present when the `firmware-management` Cargo feature is enabled (C4.2).
     #[cfg(feature = "firmware-management")]
     pub async fn firmware_updates<N, F, I, K, B, V>(
-        self,
+        mut self,
         csms: &N,
         transfer: F,
         installer: I,
@@ -2440,7 +2689,11 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         V: crate::hardware::FirmwareVerifier + Send + Sync + 'static,
     {
         let updates = crate::firmware::FirmwareUpdateQueue::new();
-        let state = Arc::new(crate::firmware::FirmwareUpdateState::new());
+        let state = Arc::new(match self.firmware_update_recorder.clone() {
+            Some(recorder) => crate::firmware::FirmwareUpdateState::with_recorder(recorder),
+            None => crate::firmware::FirmwareUpdateState::new(),
+        });
+        let pending = self.pending_firmware_update.take();
         csms.register_update_firmware_handler(self.runtime.actor(), updates.clone(), state.clone())
             .await;
         csms.register_signed_update_firmware_handler(
@@ -2453,6 +2706,17 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         let actor = self.runtime.actor();
         let notifier = csms.clone();
         self.executor.spawn(Box::pin(async move {
+            if let Some(pending) = pending {
+                let running_version = installer.running_version();
+                crate::firmware::report_pending_update(
+                    &actor,
+                    &state,
+                    &notifier,
+                    &pending,
+                    running_version.as_deref(),
+                )
+                .await;
+            }
             crate::firmware::run_firmware_updates(
                 &actor, updates, &state, &transfer, &installer, &notifier, &clock, &backoff,
                 &verifier,
@@ -2900,6 +3164,63 @@ impl<T, X: Executor> ChargePointBuilder<T, X> {
         Self::warn_if_taken(self.status_changes.take(), "status_notifications")
     }
 
+    /// Forwards the charge point's own status changes - 1.6J's `connectorId` 0 - through
+    /// `notifier`, on an offline queue of their own, gated on registration and flushed on
+    /// reconnect the same way the per-connector one is. A no-op once a status registration has
+    /// already taken the subscription.
+    ///
+    /// Never persisted, even by [`Self::status_notifications_persisted`]: the sweep that follows
+    /// the next accepted BootNotification reports the station's status afresh (B01.FR.05), so a
+    /// queued one lost to a power cut is superseded rather than missed.
+    async fn forward_station_statuses<N, M>(&mut self, csms: &N, notifier: Arc<M>)
+    where
+        N: ReconnectHandler,
+        M: StatusNotifier + Send + Sync + 'static,
+    {
+        let Some(station_changes) = self.station_status_changes.take() else {
+            return;
+        };
+        let queue = Arc::new(
+            OfflineQueue::with_capacity(self.offline_queue_capacity)
+                .gated_on_registration(self.runtime.actor()),
+        );
+        let forwarder_queue = queue.clone();
+        let forwarder_notifier = notifier.clone();
+        let overflow_actor = self.runtime.actor();
+        self.executor.spawn(Box::pin(async move {
+            run_with_offline_queue(
+                station_changes,
+                &forwarder_queue,
+                move |status| {
+                    let notifier = forwarder_notifier.clone();
+                    async move { notifier.notify_station_status(status).await }
+                },
+                move |_dropped| {
+                    let actor = overflow_actor.clone();
+                    async move { report_memory_exhaustion(&actor).await }
+                },
+            )
+            .await;
+        }));
+        let flush: QueueFlush = Arc::new(move || {
+            let queue = queue.clone();
+            let notifier = notifier.clone();
+            Box::pin(async move {
+                crate::offline_queue::flush_offline_queue(&queue, move |status| {
+                    let notifier = notifier.clone();
+                    async move { notifier.notify_station_status(status).await }
+                })
+                .await;
+            })
+        });
+        self.queue_flushes.push(flush.clone());
+        csms.register_reconnect_handler(move || {
+            let flush = flush.clone();
+            async move { flush().await }
+        })
+        .await;
+    }
+
     /// Takes the transaction-event subscription captured in [`Self::start`], or `None` if an
     /// earlier call already consumed it (see [`Self::take_status_changes`]).
     fn take_transaction_events(&mut self) -> Option<BroadcastReceiver<TransactionEventOccurred>> {
@@ -4823,6 +5144,8 @@ mod tests {
                     limit_reached: None,
                     energy_start_wh: None,
                     elapsed_secs: None,
+                    ended_meter_samples: Vec::new(),
+                    start_meter_sample: None,
                 },
                 started_at: None,
                 meter_start: None,
@@ -4937,6 +5260,49 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn registering_scheduled_unavailability_persistence_applies_a_recovered_change_at_boot() {
+        use crate::availability::AvailabilityTarget;
+        use crate::persistence::ScheduledUnavailabilityStore;
+        use crate::state::ConnectorState;
+
+        let storage = Arc::new(crate::hardware::InMemoryStorage::new());
+        ScheduledUnavailabilityStore::new(storage.clone())
+            .save(&[AvailabilityTarget::Connector {
+                evse_id: 0,
+                connector_id: 0,
+            }])
+            .await;
+
+        let charge_point = super::test_support::IdleTestChargePoint {
+            evses: [TestEvse {
+                connectors: [TestConnector {
+                    locked: Arc::new(AtomicBool::new(false)),
+                    lock_succeeds: true,
+                }],
+            }],
+        };
+        let runtime = ChargePointBuilder::start(charge_point, TokioExecutor)
+            .await
+            .unwrap()
+            .scheduled_unavailability_persistence(storage.clone())
+            .await
+            .build();
+        accept_registration(&runtime).await;
+
+        assert_eq!(
+            runtime.state().evses[0].connectors[0],
+            ConnectorState::Unavailable
+        );
+        assert!(runtime.state().scheduled_unavailability.is_empty());
+        assert!(
+            ScheduledUnavailabilityStore::new(storage)
+                .load()
+                .await
+                .is_empty()
+        );
+    }
+
     #[tokio::test]
     async fn registering_device_model_persistence_recovers_a_persistent_attribute_at_boot() {
         use crate::persistence::{DeviceModelStore, PersistedDeviceModelAttribute};
@@ -5103,6 +5469,60 @@ mod tests {
         assert_eq!(BootReasonStore::new(storage).load().await, None);
     }
 
+    #[cfg(feature = "firmware-management")]
+    #[tokio::test]
+    async fn a_pending_firmware_update_reports_the_firmware_update_boot_reason_and_then_clears_it()
+    {
+        use crate::persistence::{
+            BootReasonStore, FIRMWARE_UPDATE_SCHEMA_VERSION, FirmwareUpdateStore,
+            PendingFirmwareUpdate,
+        };
+
+        let storage = Arc::new(crate::hardware::InMemoryStorage::new());
+        // Storage as the previous boot left it: the firmware worker wrote its marker, then the
+        // `Reset` path it rebooted through recorded `RemoteReset`.
+        FirmwareUpdateStore::new(storage.clone())
+            .save(&PendingFirmwareUpdate {
+                schema_version: FIRMWARE_UPDATE_SCHEMA_VERSION,
+                request_id: Some(3),
+                previous_version: Some("1.0.0".into()),
+                expected_version: Some("1.1.0".into()),
+                started_at: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            })
+            .await;
+        BootReasonStore::new(storage.clone())
+            .save(BootReasonCause::RemoteReset)
+            .await;
+
+        let (charge_point, _locked) = test_charge_point(true);
+        let csms = RecordingReasonCsms {
+            outcome: BootNotificationOutcome {
+                status: RegistrationStatus::Accepted,
+                interval_secs: 60,
+                current_time: None,
+            },
+            seen_reasons: Arc::new(std::sync::Mutex::new(alloc::vec::Vec::new())),
+        };
+
+        ChargePointBuilder::start(charge_point, TokioExecutor)
+            .await
+            .unwrap()
+            .boot_reason_persistence(storage.clone())
+            .await
+            .firmware_update_persistence(storage.clone())
+            .await
+            .provisioning(&csms, TokioBackoff, crate::clock::SystemMonotonicClock)
+            .await
+            .build();
+
+        assert_eq!(
+            *csms.seen_reasons.lock().unwrap(),
+            alloc::vec![Some(BootReasonCause::FirmwareUpdate)]
+        );
+        assert_eq!(FirmwareUpdateStore::new(storage.clone()).load().await, None);
+        assert_eq!(BootReasonStore::new(storage).load().await, None);
+    }
+
     #[tokio::test]
     async fn a_reset_recorded_before_reboot_is_reported_as_the_cause_on_the_next_boot() {
         use crate::persistence::BootReasonStore;
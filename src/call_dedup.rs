@@ -0,0 +1,684 @@
+//! Deduplicating inbound CSMS CALLs by message id: a CALL whose id this charge point has already
+//! seen is answered with the response it got the first time, never dispatched again.
+//!
+//! # Why at the transport
+//!
+//! `ocpp-client` dispatches every inbound CALL to its registered handler unconditionally, and its
+//! `Client::on` callback never sees the message id (see [`crate::replay_protection`], item 3). The
+//! one place both the id of a CALL and the id of the response to it are visible is the transport
+//! underneath the client, so that is where this module sits: [`deduplicate_calls`] wraps a
+//! transport's two halves, the read half checking each CALL's id against [`SeenCalls`] and the
+//! write half recording the CALLRESULT or CALLERROR the client answers it with.
+//!
+//! # What a duplicate gets
+//!
+//! - **Answered already**: the cached response, resent verbatim. The handler does not run again.
+//! - **Still being handled**: nothing. The original's response carries the same id, so it answers
+//!   both.
+//!
+//! Either way the duplicate never reaches the client. A CSMS that lost a response and retried
+//! under the same id - which OCPP-J permits - gets the answer it missed, and the command's effect
+//! happens once. A duplicate whose action or payload differs from the first CALL under that id
+//! is no retry: it is also reported as [`AttemptedReplayAttacks`], since a CSMS has no legitimate
+//! reason to reuse an id for a different message. It still gets the cached response, so a replay
+//! learns nothing it did not already have.
+//!
+//! # Bounds
+//!
+//! [`SeenCalls`] remembers at most [`DEFAULT_SEEN_CALLS_CAPACITY`] calls, for at most
+//! [`DEFAULT_SEEN_CALLS_WINDOW_SECS`], whichever runs out first (G2.2's bounded-memory stance).
+//! An id seen again after it has been forgotten is a fresh CALL. The window is measured on a
+//! [`MonotonicClock`], so a wall-clock correction cannot expire every entry at once, or keep one
+//! alive forever. A full record makes room by forgetting its oldest answered call, and forgets a
+//! call still being handled only when every call it holds is one.
+//!
+//! # Which connections
+//!
+//! Every connection `crate::connect_and_setup` makes is dialled through
+//! `crate::network_switch::ConnectionTarget` (private) and wrapped, the first included, sharing
+//! one [`SeenCalls`] so a retry after a reconnect is still recognised. The first connection
+//! exists before the actor does, so a replay that arrives before `setup` returns is answered
+//! from cache at once and reported once the actor is attached - see `ReplayReporter`
+//! (private). An integrator who builds a `Client` over their own transport with
+//! `Client::from_transport` wraps it with [`deduplicate_calls`] to cover every connection.
+//!
+//! [`AttemptedReplayAttacks`]: crate::state::SecurityEventType::AttemptedReplayAttacks
+
+use crate::actor::ChargePointActor;
+use crate::clock::{MonotonicClock, MonotonicInstant};
+use crate::security::report_security_event;
+use crate::state::{SecurityEvent, SecurityEventType};
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::time::Duration;
+use embassy_sync::blocking_mutex::Mutex as BlockingMutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::mutex::Mutex;
+use ocpp_client::{TransportError, TransportEvent, TransportSink, TransportStream};
+use serde_json::Value;
+
+/// Default [`SeenCalls`] capacity. A CSMS has at most one CALL in flight to a charge point at a
+/// time (OCPP-J §4.1.1), so this covers far more history than a retry ever reaches back for.
+pub const DEFAULT_SEEN_CALLS_CAPACITY: usize = 32;
+
+/// Default [`SeenCalls`] window, in seconds: ten times the 30 s message timeout OCPP suggests, so
+/// a CSMS that retries after its own timeout, or after a reconnect, is still recognised.
+pub const DEFAULT_SEEN_CALLS_WINDOW_SECS: u64 = 300;
+
+/// What [`SeenCalls::observe_call`] made of an inbound CALL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallVerdict {
+    /// A message id not seen within the window: dispatch it.
+    Fresh,
+    /// A message id already seen within the window: do not dispatch it.
+    Duplicate {
+        /// The response the first CALL was answered with, or `None` while it is still being
+        /// handled.
+        cached_response: Option<String>,
+        /// Whether the action or payload differs from the first CALL's.
+        body_differs: bool,
+    },
+}
+
+struct SeenCall {
+    id: String,
+    action: String,
+    payload: Value,
+    seen_at: MonotonicInstant,
+    response: Option<String>,
+}
+
+/// The bounded, time-windowed record of inbound CALL ids [`deduplicate_calls`] consults - see the
+/// module docs.
+///
+/// Shared between a transport's two halves, and across redials, through an [`Arc`]; internally
+/// synchronized by the same blocking mutex [`crate::replay_protection::ReplayGuard`] uses.
+pub struct SeenCalls<M> {
+    clock: M,
+    capacity: usize,
+    window: Duration,
+    calls: BlockingMutex<CriticalSectionRawMutex, RefCell<VecDeque<SeenCall>>>,
+}
+
+impl<M: MonotonicClock> SeenCalls<M> {
+    /// An empty record with the default capacity and window, timed by `clock`.
+    pub fn new(clock: M) -> Self {
+        Self::with_limits(
+            clock,
+            DEFAULT_SEEN_CALLS_CAPACITY,
+            Duration::from_secs(DEFAULT_SEEN_CALLS_WINDOW_SECS),
+        )
+    }
+
+    /// An empty record holding at most `capacity` calls (clamped to at least 1) for at most
+    /// `window`, timed by `clock`.
+    pub fn with_limits(clock: M, capacity: usize, window: Duration) -> Self {
+        Self {
+            clock,
+            capacity: capacity.max(1),
+            window,
+            calls: BlockingMutex::new(RefCell::new(VecDeque::new())),
+        }
+    }
+
+    /// Checks an inbound CALL against the record, remembering it if it is [`CallVerdict::Fresh`].
+    pub fn observe_call(&self, id: &str, action: &str, payload: &Value) -> CallVerdict {
+        let now = self.clock.now();
+        self.calls.lock(|calls| {
+            let mut calls = calls.borrow_mut();
+            calls.retain(|call| now.duration_since(call.seen_at) < self.window);
+            if let Some(call) = calls.iter().find(|call| call.id == id) {
+                return CallVerdict::Duplicate {
+                    cached_response: call.response.clone(),
+                    body_differs: call.action != action || call.payload != *payload,
+                };
+            }
+            if calls.len() >= self.capacity {
+                // An answered call can still be retried, but one still being handled is what a
+                // retry arriving right now is most likely to repeat.
+                match calls.iter().position(|call| call.response.is_some()) {
+                    Some(answered) => {
+                        calls.remove(answered);
+                    }
+                    None => {
+                        calls.pop_front();
+                    }
+                }
+            }
+            calls.push_back(SeenCall {
+                id: id.into(),
+                action: action.into(),
+                payload: payload.clone(),
+                seen_at: now,
+                response: None,
+            });
+            CallVerdict::Fresh
+        })
+    }
+
+    /// Records `frame` as the response to the CALL with message id `id`. Ignored for an id not in
+    /// the record, and for one already answered: the first response is the one a duplicate gets.
+    pub fn observe_response(&self, id: &str, frame: &str) {
+        self.calls.lock(|calls| {
+            if let Some(call) = calls
+                .borrow_mut()
+                .iter_mut()
+                .find(|call| call.id == id && call.response.is_none())
+            {
+                call.response = Some(frame.into());
+            }
+        });
+    }
+}
+
+/// `frame`'s message id, action and payload, if it is an OCPP-J CALL. Anything else - including
+/// a malformed frame, which [`crate::invalid_messages`] counts - is not this module's concern.
+fn parse_call(frame: &str) -> Option<(String, String, Value)> {
+    let Value::Array(mut items) = serde_json::from_str(frame).ok()? else {
+        return None;
+    };
+    if items.len() != 4 || items[0].as_u64() != Some(2) {
+        return None;
+    }
+    let payload = items.pop()?;
+    let action = items.pop()?;
+    let id = items.pop()?;
+    match (id, action) {
+        (Value::String(id), Value::String(action)) => Some((id, action, payload)),
+        _ => None,
+    }
+}
+
+/// The message id `frame` answers, if it is a CALLRESULT or CALLERROR.
+fn response_id(frame: &str) -> Option<String> {
+    // Cheap pre-check: this runs on every outbound frame, and the client's own CALLs are not
+    // responses.
+    let head = frame.trim_start().strip_prefix('[')?.trim_start();
+    if !(head.starts_with('3') || head.starts_with('4')) {
+        return None;
+    }
+    let Value::Array(items) = serde_json::from_str(frame).ok()? else {
+        return None;
+    };
+    matches!(items.first().and_then(Value::as_u64), Some(3 | 4))
+        .then(|| items.get(1)?.as_str().map(Into::into))
+        .flatten()
+}
+
+type SharedSink = Arc<Mutex<CriticalSectionRawMutex, Box<dyn TransportSink>>>;
+
+/// How many replay reports a [`ReplayReporter`] holds while no actor is attached. Each one is a
+/// CSMS reusing a message id within seconds of connecting, so more than a handful says nothing
+/// the first few did not; the rest are still logged.
+const MAX_HELD_REPLAY_REPORTS: usize = 8;
+
+#[derive(Default)]
+struct ReporterState {
+    actor: Option<ChargePointActor>,
+    held: Vec<SecurityEvent>,
+}
+
+/// The actor a [`DeduplicatingStream`] reports a replayed CALL to, attachable after the transport
+/// it watches was built: the first connection `crate::connect_and_setup` dials exists before the
+/// actor does, since the actor is built from the client riding on it. A replay seen before
+/// [`Self::attach`] is answered from cache at once and held, up to [`MAX_HELD_REPLAY_REPORTS`],
+/// until the stream's first frame after it.
+#[derive(Clone)]
+pub(crate) struct ReplayReporter(
+    Arc<BlockingMutex<CriticalSectionRawMutex, RefCell<ReporterState>>>,
+);
+
+impl ReplayReporter {
+    /// A reporter with no actor attached yet.
+    pub(crate) fn new() -> Self {
+        Self(Arc::new(BlockingMutex::new(RefCell::new(
+            ReporterState::default(),
+        ))))
+    }
+
+    /// Reports to `actor` from now on, and delivers what was held before on the next frame.
+    pub(crate) fn attach(&self, actor: ChargePointActor) {
+        self.0.lock(|state| state.borrow_mut().actor = Some(actor));
+    }
+
+    /// Reports `event`, along with anything held before an actor was attached - or, with no actor
+    /// yet, holds it. `None` delivers only what is held.
+    async fn report(&self, event: Option<SecurityEvent>) {
+        let due = self.0.lock(|state| {
+            let mut state = state.borrow_mut();
+            let state = &mut *state;
+            state.held.extend(event);
+            match &state.actor {
+                Some(actor) if !state.held.is_empty() => {
+                    Some((actor.clone(), core::mem::take(&mut state.held)))
+                }
+                Some(_) => None,
+                None => {
+                    if state.held.len() > MAX_HELD_REPLAY_REPORTS {
+                        state.held.pop();
+                        tracing::warn!(
+                            "too many replays before the actor was attached - not reported"
+                        );
+                    }
+                    None
+                }
+            }
+        });
+        if let Some((actor, held)) = due {
+            for event in held {
+                report_security_event(&actor, event).await;
+            }
+        }
+    }
+}
+
+/// Wraps a transport's two halves so duplicate inbound CALLs are answered from `seen` rather than
+/// dispatched again, reporting a duplicate with a different body to `actor` - see the module
+/// docs. The halves share `sink`, since the read half has to send a cached response itself.
+pub fn deduplicate_calls<M>(
+    sink: Box<dyn TransportSink>,
+    stream: Box<dyn TransportStream>,
+    seen: Arc<SeenCalls<M>>,
+    actor: ChargePointActor,
+) -> (Box<dyn TransportSink>, Box<dyn TransportStream>)
+where
+    M: MonotonicClock + Send + Sync + 'static,
+{
+    let reporter = ReplayReporter::new();
+    reporter.attach(actor);
+    deduplicate_calls_reporting_to(sink, stream, seen, reporter)
+}
+
+/// [`deduplicate_calls`], reporting to whichever actor `reporter` holds when a replay arrives.
+pub(crate) fn deduplicate_calls_reporting_to<M>(
+    sink: Box<dyn TransportSink>,
+    stream: Box<dyn TransportStream>,
+    seen: Arc<SeenCalls<M>>,
+    reporter: ReplayReporter,
+) -> (Box<dyn TransportSink>, Box<dyn TransportStream>)
+where
+    M: MonotonicClock + Send + Sync + 'static,
+{
+    let sink: SharedSink = Arc::new(Mutex::new(sink));
+    (
+        Box::new(DeduplicatingSink {
+            sink: sink.clone(),
+            seen: seen.clone(),
+        }),
+        Box::new(DeduplicatingStream {
+            inner: stream,
+            sink,
+            seen,
+            reporter,
+        }),
+    )
+}
+
+/// The read half of [`deduplicate_calls`].
+pub struct DeduplicatingStream<M> {
+    inner: Box<dyn TransportStream>,
+    sink: SharedSink,
+    seen: Arc<SeenCalls<M>>,
+    reporter: ReplayReporter,
+}
+
+impl<M: MonotonicClock + Send + Sync> TransportStream for DeduplicatingStream<M> {
+    fn recv<'a>(
+        &'a mut self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<TransportEvent>, TransportError>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            loop {
+                let event = self.inner.recv().await?;
+                // Anything held from before the actor was attached goes out now that the
+                // connection is evidently still carrying traffic.
+                self.reporter.report(None).await;
+                let Some(TransportEvent::Frame(text)) = &event else {
+                    return Ok(event);
+                };
+                let Some((id, action, payload)) = parse_call(text) else {
+                    return Ok(event);
+                };
+                let CallVerdict::Duplicate {
+                    cached_response,
+                    body_differs,
+                } = self.seen.observe_call(&id, &action, &payload)
+                else {
+                    return Ok(event);
+                };
+                // Still cancel-safe in the sense `TransportStream::recv` asks for: the duplicate
+                // is never handed to the client either way, so a cancellation below loses at
+                // most the resend of an answer the CSMS already had once.
+                tracing::warn!(
+                    message_id = id.as_str(),
+                    action = action.as_str(),
+                    body_differs,
+                    "the CSMS repeated a CALL message id - answering from cache"
+                );
+                if body_differs {
+                    self.reporter
+                        .report(Some(SecurityEvent {
+                            event_type: SecurityEventType::AttemptedReplayAttacks,
+                            tech_info: Some(alloc::format!(
+                                "{action} CALL reused message id {id} with a different payload"
+                            )),
+                        }))
+                        .await;
+                }
+                if let Some(response) = cached_response
+                    && let Err(err) = self.sink.lock().await.send(response).await
+                {
+                    tracing::warn!(error = %err, "resending a cached response failed");
+                }
+            }
+        })
+    }
+}
+
+/// The write half of [`deduplicate_calls`].
+pub struct DeduplicatingSink<M> {
+    sink: SharedSink,
+    seen: Arc<SeenCalls<M>>,
+}
+
+impl<M: MonotonicClock + Send + Sync> TransportSink for DeduplicatingSink<M> {
+    fn send<'a>(
+        &'a mut self,
+        frame: String,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+        Box::pin(async move {
+            if let Some(id) = response_id(&frame) {
+                self.seen.observe_response(&id, &frame);
+            }
+            self.sink.lock().await.send(frame).await
+        })
+    }
+
+    fn ping<'a>(
+        &'a mut self,
+        payload: Vec<u8>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+        Box::pin(async move { self.sink.lock().await.ping(payload).await })
+    }
+
+    fn pong<'a>(
+        &'a mut self,
+        payload: Vec<u8>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+        Box::pin(async move { self.sink.lock().await.pong(payload).await })
+    }
+
+    fn close<'a>(
+        &'a mut self,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+        Box::pin(async move { self.sink.lock().await.close().await })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::TokioExecutor;
+    use alloc::string::ToString;
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    /// A [`MonotonicClock`] tests move by hand, in whole seconds.
+    #[derive(Default)]
+    struct SteppedClock(AtomicU64);
+
+    impl SteppedClock {
+        fn advance(&self, secs: u64) {
+            self.0.fetch_add(secs * 1_000_000_000, Ordering::SeqCst);
+        }
+    }
+
+    impl MonotonicClock for SteppedClock {
+        fn now(&self) -> MonotonicInstant {
+            MonotonicInstant::from_ticks(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    impl MonotonicClock for Arc<SteppedClock> {
+        fn now(&self) -> MonotonicInstant {
+            self.as_ref().now()
+        }
+    }
+
+    /// A [`TransportSink`] recording what it was asked to send where the test can still see it.
+    #[derive(Clone, Default)]
+    struct RecordingSink(Arc<std::sync::Mutex<Vec<String>>>);
+
+    impl TransportSink for RecordingSink {
+        fn send<'a>(
+            &'a mut self,
+            frame: String,
+        ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+            self.0.lock().unwrap().push(frame);
+            Box::pin(async { Ok(()) })
+        }
+
+        fn ping<'a>(
+            &'a mut self,
+            _payload: Vec<u8>,
+        ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn pong<'a>(
+            &'a mut self,
+            _payload: Vec<u8>,
+        ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn close<'a>(
+            &'a mut self,
+        ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    /// A [`TransportStream`] that yields one frame per `recv`, then `None`.
+    struct ScriptedStream(Vec<String>);
+
+    impl TransportStream for ScriptedStream {
+        fn recv<'a>(
+            &'a mut self,
+        ) -> Pin<Box<dyn Future<Output = Result<Option<TransportEvent>, TransportError>> + Send + 'a>>
+        {
+            Box::pin(async move {
+                Ok((!self.0.is_empty()).then(|| TransportEvent::Frame(self.0.remove(0))))
+            })
+        }
+    }
+
+    fn reset(kind: &str) -> Value {
+        serde_json::json!({ "type": kind })
+    }
+
+    #[test]
+    fn a_repeated_id_is_a_duplicate_carrying_the_first_response() {
+        let seen = SeenCalls::new(SteppedClock::default());
+
+        assert_eq!(
+            seen.observe_call("m-1", "Reset", &reset("Immediate")),
+            CallVerdict::Fresh
+        );
+        assert_eq!(
+            seen.observe_call("m-1", "Reset", &reset("Immediate")),
+            CallVerdict::Duplicate {
+                cached_response: None,
+                body_differs: false
+            }
+        );
+        seen.observe_response("m-1", r#"[3,"m-1",{"status":"Accepted"}]"#);
+        seen.observe_response("m-1", r#"[3,"m-1",{"status":"Rejected"}]"#);
+        assert_eq!(
+            seen.observe_call("m-1", "Reset", &reset("OnIdle")),
+            CallVerdict::Duplicate {
+                cached_response: Some(r#"[3,"m-1",{"status":"Accepted"}]"#.into()),
+                body_differs: true
+            }
+        );
+        assert_eq!(
+            seen.observe_call("m-2", "Reset", &reset("Immediate")),
+            CallVerdict::Fresh
+        );
+    }
+
+    #[test]
+    fn an_id_is_forgotten_once_the_window_or_the_capacity_runs_out() {
+        let clock = Arc::new(SteppedClock::default());
+        let seen = SeenCalls::with_limits(clock.clone(), 2, Duration::from_secs(60));
+
+        seen.observe_call("m-1", "Heartbeat", &Value::Null);
+        clock.advance(60);
+        assert_eq!(
+            seen.observe_call("m-1", "Heartbeat", &Value::Null),
+            CallVerdict::Fresh
+        );
+
+        seen.observe_call("m-2", "Heartbeat", &Value::Null);
+        seen.observe_call("m-3", "Heartbeat", &Value::Null);
+        assert_eq!(
+            seen.observe_call("m-1", "Heartbeat", &Value::Null),
+            CallVerdict::Fresh
+        );
+    }
+
+    #[test]
+    fn a_full_record_forgets_an_answered_call_before_one_still_being_handled() {
+        let seen = SeenCalls::with_limits(SteppedClock::default(), 2, Duration::from_secs(60));
+
+        seen.observe_call("m-1", "Reset", &reset("Immediate"));
+        seen.observe_call("m-2", "Heartbeat", &Value::Null);
+        seen.observe_response("m-2", r#"[3,"m-2",{}]"#);
+        seen.observe_call("m-3", "Heartbeat", &Value::Null);
+
+        assert_eq!(
+            seen.observe_call("m-1", "Reset", &reset("Immediate")),
+            CallVerdict::Duplicate {
+                cached_response: None,
+                body_differs: false
+            }
+        );
+        assert_eq!(
+            seen.observe_call("m-2", "Heartbeat", &Value::Null),
+            CallVerdict::Fresh
+        );
+    }
+
+    #[test]
+    fn only_a_call_envelope_is_parsed_as_a_call() {
+        assert_eq!(
+            parse_call(r#"[2,"m-1","Reset",{"type":"Immediate"}]"#),
+            Some(("m-1".into(), "Reset".into(), reset("Immediate")))
+        );
+        for not_a_call in [r#"[3,"m-1",{}]"#, r#"[2,"m-1","Reset"]"#, "garbage"] {
+            assert_eq!(parse_call(not_a_call), None, "{not_a_call}");
+        }
+        assert_eq!(response_id(r#"[3,"m-1",{}]"#), Some("m-1".to_string()));
+        assert_eq!(
+            response_id(r#" [ 4,"m-2","NotImplemented","",{}]"#),
+            Some("m-2".to_string())
+        );
+        assert_eq!(response_id(r#"[2,"m-3","Heartbeat",{}]"#), None);
+    }
+
+    #[tokio::test]
+    async fn a_duplicate_call_is_answered_from_cache_and_never_reaches_the_client() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let wire = RecordingSink::default();
+        let call = r#"[2,"m-1","Reset",{"type":"Immediate"}]"#;
+        let (mut sink, mut stream) = deduplicate_calls(
+            Box::new(wire.clone()),
+            Box::new(ScriptedStream(alloc::vec![call.into(), call.into()])),
+            Arc::new(SeenCalls::new(SteppedClock::default())),
+            actor,
+        );
+
+        assert!(matches!(
+            stream.recv().await.unwrap(),
+            Some(TransportEvent::Frame(text)) if text == call
+        ));
+        sink.send(r#"[3,"m-1",{"status":"Accepted"}]"#.into())
+            .await
+            .unwrap();
+        assert!(stream.recv().await.unwrap().is_none());
+
+        assert_eq!(
+            *wire.0.lock().unwrap(),
+            alloc::vec![
+                r#"[3,"m-1",{"status":"Accepted"}]"#.to_string(),
+                r#"[3,"m-1",{"status":"Accepted"}]"#.to_string(),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_reused_id_with_a_different_payload_is_reported_as_a_replay() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let mut security_events = actor.subscribe_security_events();
+        let (_sink, mut stream) = deduplicate_calls(
+            Box::new(RecordingSink::default()),
+            Box::new(ScriptedStream(alloc::vec![
+                r#"[2,"m-1","Reset",{"type":"OnIdle"}]"#.into(),
+                r#"[2,"m-1","Reset",{"type":"OnIdle"}]"#.into(),
+                r#"[2,"m-1","Reset",{"type":"Immediate"}]"#.into(),
+            ])),
+            Arc::new(SeenCalls::new(SteppedClock::default())),
+            actor,
+        );
+
+        assert!(stream.recv().await.unwrap().is_some());
+        assert!(stream.recv().await.unwrap().is_none());
+
+        let raised = security_events.recv().await.unwrap();
+        assert_eq!(raised.event_type, SecurityEventType::AttemptedReplayAttacks);
+        assert_eq!(
+            raised.tech_info.as_deref(),
+            Some("Reset CALL reused message id m-1 with a different payload")
+        );
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), security_events.recv())
+                .await
+                .is_err(),
+            "an identical retry is not a replay"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_replay_seen_before_the_actor_exists_is_reported_once_it_is_attached() {
+        let reporter = ReplayReporter::new();
+        let (_sink, mut stream) = deduplicate_calls_reporting_to(
+            Box::new(RecordingSink::default()),
+            Box::new(ScriptedStream(alloc::vec![
+                r#"[2,"m-1","Reset",{"type":"OnIdle"}]"#.into(),
+                r#"[2,"m-1","Reset",{"type":"Immediate"}]"#.into(),
+                r#"[2,"m-2","Heartbeat",{}]"#.into(),
+            ])),
+            Arc::new(SeenCalls::new(SteppedClock::default())),
+            reporter.clone(),
+        );
+        assert!(stream.recv().await.unwrap().is_some());
+        assert!(stream.recv().await.unwrap().is_some());
+
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let mut security_events = actor.subscribe_security_events();
+        reporter.attach(actor);
+        assert!(stream.recv().await.unwrap().is_none());
+
+        let raised = security_events.recv().await.unwrap();
+        assert_eq!(raised.event_type, SecurityEventType::AttemptedReplayAttacks);
+        assert_eq!(
+            raised.tech_info.as_deref(),
+            Some("Reset CALL reused message id m-1 with a different payload")
+        );
+    }
+}
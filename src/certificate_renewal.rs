@@ -30,13 +30,15 @@
 //!
 //! # Where the expiry comes from
 //!
-//! This crate does no X.509 parsing (`CLAUDE.md`'s "no crypto dependency"), so it cannot compute
-//! a certificate's expiry from its PEM bytes. [`crate::hardware::CertificateStore::expires_at`]
+//! A certificate's expiry is in its X.509 `notAfter`, which only a store that parses certificates
+//! can read - [`crate::hardware::StoredCertificates`] with the `certificate-parsing` feature, or an
+//! integrator's own. [`crate::hardware::CertificateStore::expires_at`]
 //! is the honest hook for it: `None` is not "never expires" or "far away", it is "this store
 //! cannot say", and [`crate::certificate_renewal::run_certificate_renewal`] skips a slot it gets `None` for rather than
 //! guessing - the same stance [`crate::clock::is_synchronized`]'s callers take for an unset RTC.
-//! An integrator who wants ahead-of-expiry renewal must override that method (directly, or by
-//! feeding a value learned some other way, e.g. out of band alongside a `CertificateSigned`).
+//! An integrator with a store of their own who wants ahead-of-expiry renewal must override that
+//! method (directly, or by feeding a value learned some other way, e.g. out of band alongside a
+//! `CertificateSigned`).
 //!
 //! # The clock
 //!
@@ -1614,7 +1616,7 @@ mod tests {
         renewals
             .stash_backup(
                 CertificateSigningPurpose::ChargingStationCertificate,
-                "old-working-pem".into(),
+                crate::test_certificates::CHARGING_STATION.into(),
             )
             .await;
         let certificate_store =
@@ -1634,7 +1636,7 @@ mod tests {
                 .certificate_chain_pem(crate::hardware::CertificateUse::ChargingStation)
                 .await
                 .unwrap(),
-            Some("old-working-pem".into())
+            Some(crate::test_certificates::CHARGING_STATION.into())
         );
         // The backup is consumed - a second discard finds nothing left to restore.
         assert!(
@@ -94,6 +94,9 @@ pub async fn handle_get_certificate_status<O: OcspChecker>(
     if !crate::refusal::capability_present(&actor.state().capabilities, "GetCertificateStatus") {
         return CertificateStatusOutcome::Failed;
     }
+    if crate::command_policy::refuses(actor, "GetCertificateStatus") {
+        return CertificateStatusOutcome::Failed;
+    }
 
     match checker.check(certificate).await {
         Ok(result) => {
@@ -195,10 +198,12 @@ pub async fn handle_get_certificate_chain_status<O: OcspChecker, C: Clock>(
     clock: &C,
     requests: &[ChainStatusRequest],
 ) -> Vec<ChainCertificateStatus> {
+    // A policy refusal answers the way an absent capability does: `Failed` for every entry, since
+    // the response has no top-level status to carry it.
     let capable = crate::refusal::capability_present(
         &actor.state().capabilities,
         "GetCertificateChainStatus",
-    );
+    ) && !crate::command_policy::refuses(actor, "GetCertificateChainStatus");
 
     let mut results = Vec::with_capacity(requests.len());
     for request in requests {
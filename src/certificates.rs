@@ -42,6 +42,7 @@
 //! so a renewal CSR can be signed with the same key).
 
 use alloc::boxed::Box;
+use alloc::format;
 use alloc::vec::Vec;
 use core::cell::Cell;
 use core::cell::RefCell;
@@ -53,6 +54,7 @@ use crate::hardware::{
     CertificateHashData, CertificateStore, CertificateUse, DeleteCertificateOutcome,
     InstallCertificateOutcome, InstalledCertificate,
 };
+use crate::replay_protection::ReplayGuard;
 use crate::state::{SecurityEvent, SecurityEventType};
 
 pub mod csr;
@@ -241,6 +243,10 @@ pub struct PendingSignRequests {
     // `CriticalSectionRawMutex` is the same primitive the rest of the crate uses for shared
     // interior mutability under `no_std` (see `ChargePointActor::boot_reason_recorder`).
     next_request_id: BlockingMutex<CriticalSectionRawMutex, Cell<i64>>,
+    // Fingerprints of the chains this charge point has already installed, so the same chain
+    // arriving again is recognised as a replay - see `handle_certificate_signed`. A fingerprint
+    // rather than the chain itself because a chain is up to 10 000 characters.
+    delivered: ReplayGuard<u64>,
 }
 
 impl PendingSignRequests {
@@ -249,6 +255,7 @@ impl PendingSignRequests {
         Self {
             slots: BlockingMutex::new(RefCell::new([None, None])),
             next_request_id: BlockingMutex::new(Cell::new(1)),
+            delivered: ReplayGuard::new(),
         }
     }
 
@@ -396,6 +403,17 @@ impl PendingSignRequests {
     }
 }
 
+/// FNV-1a over `chain`: enough to tell one delivered chain from another, and a collision costs
+/// no more than a spurious report (see [`crate::replay_protection`]).
+fn chain_fingerprint(chain: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in chain.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
 impl Default for PendingSignRequests {
     fn default() -> Self {
         Self::new()
@@ -439,6 +457,14 @@ pub fn record_sign_certificate_sent(
 /// rather than discovering it half-way through an install. The pending request is deliberately
 /// **not** cleared: the CSR is still outstanding, so the resend discipline above may yet ask
 /// again and get a chain that fits.
+///
+/// # A chain delivered twice
+///
+/// A chain identical to one this charge point already installed is reported as
+/// [`SecurityEventType::AttemptedReplayAttacks`] - in place of the generic unsolicited report
+/// when nothing is pending, since that is the more specific finding. It is otherwise decided
+/// exactly as before: a CSMS answering a fresh CSR with an old chain still reaches the store,
+/// which is what refuses a chain for a key it no longer holds.
 #[tracing::instrument(skip_all)]
 pub async fn handle_certificate_signed<S: CertificateStore>(
     actor: &ChargePointActor,
@@ -451,21 +477,42 @@ pub async fn handle_certificate_signed<S: CertificateStore>(
     if !crate::refusal::capability_present(&actor.state().capabilities, "CertificateSigned") {
         return CertificateSignedOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "CertificateSigned") {
+        return CertificateSignedOutcome::Rejected;
+    }
 
-    if !pending.matches(purpose, request_id) {
-        tracing::warn!(
-            ?purpose,
-            ?request_id,
-            "received a CertificateSigned that does not match any outstanding SignCertificate"
-        );
+    let fingerprint = chain_fingerprint(certificate_chain);
+    let replayed = pending.delivered.contains(&fingerprint);
+    if replayed {
         crate::security::report_security_event(
             actor,
             SecurityEvent {
-                event_type: SecurityEventType::InvalidChargingStationCertificate,
-                tech_info: Some("unsolicited CertificateSigned".into()),
+                event_type: SecurityEventType::AttemptedReplayAttacks,
+                tech_info: Some(format!(
+                    "CertificateSigned repeated an already installed {purpose:?} chain \
+                     (fingerprint {fingerprint:016x}, requestId {request_id:?})"
+                )),
             },
         )
         .await;
+    }
+
+    if !pending.matches(purpose, request_id) {
+        tracing::warn!(
+            ?purpose,
+            ?request_id,
+            "received a CertificateSigned that does not match any outstanding SignCertificate"
+        );
+        if !replayed {
+            crate::security::report_security_event(
+                actor,
+                SecurityEvent {
+                    event_type: SecurityEventType::InvalidChargingStationCertificate,
+                    tech_info: Some("unsolicited CertificateSigned".into()),
+                },
+            )
+            .await;
+        }
         return CertificateSignedOutcome::Rejected;
     }
 
@@ -490,7 +537,12 @@ pub async fn handle_certificate_signed<S: CertificateStore>(
         .install(purpose.certificate_use(), certificate_chain)
         .await
     {
-        Ok(InstallCertificateOutcome::Accepted) => CertificateSignedOutcome::Accepted,
+        Ok(InstallCertificateOutcome::Accepted) => {
+            if !replayed {
+                pending.delivered.record(fingerprint);
+            }
+            CertificateSignedOutcome::Accepted
+        }
         Ok(_) => {
             tracing::warn!(?purpose, "the certificate store refused the signed chain");
             crate::security::report_security_event(
@@ -533,6 +585,9 @@ pub async fn handle_install_certificate<S: CertificateStore>(
     if !crate::refusal::capability_present(&actor.state().capabilities, "InstallCertificate") {
         return InstallCertificateOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "InstallCertificate") {
+        return InstallCertificateOutcome::Rejected;
+    }
     if !use_for.is_installable() {
         tracing::warn!(
             ?use_for,
@@ -566,6 +621,9 @@ pub async fn handle_delete_certificate<S: CertificateStore>(
     if !crate::refusal::capability_present(&actor.state().capabilities, "DeleteCertificate") {
         return DeleteCertificateOutcome::NotFound;
     }
+    if crate::command_policy::refuses(actor, "DeleteCertificate") {
+        return DeleteCertificateOutcome::NotFound;
+    }
     match store.delete(hash_data).await {
         Ok(outcome) => outcome,
         Err(err) => {
@@ -592,6 +650,9 @@ pub async fn handle_get_installed_certificate_ids<S: CertificateStore>(
     ) {
         return Vec::new();
     }
+    if crate::command_policy::refuses(actor, "GetInstalledCertificateIds") {
+        return Vec::new();
+    }
     match store.installed(uses).await {
         Ok(installed) => installed,
         Err(err) => {
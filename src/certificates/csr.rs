@@ -4,7 +4,7 @@
 //!
 //! This crate carries no crypto dependency, deliberately and consistently - see
 //! [`crate::hardware::CertificateStore`]'s module docs for why
-//! [`crate::hardware::StoredCertificates`] does no X.509 parsing, and
+//! [`crate::hardware::StoredCertificates`] parses X.509 only behind an optional feature, and
 //! [`crate::hardware::SoftwareCrypto`]'s docs for why asymmetric key generation
 //! and signing are delegated to the integrator rather than pulled in as a dependency here. This
 //! module draws the same line, but a CSR needs three things and only two of them are asymmetric
@@ -364,7 +364,7 @@ pub async fn build_signed_csr<K: KeyStore>(
 // dependency any more than it has a crypto one. Standard alphabet, `=` padding, 64-column wrap
 // (RFC 7468 §2's recommendation and what every CSR-consuming tool expects).
 
-fn base64_encode(data: &[u8]) -> String {
+pub(crate) fn base64_encode(data: &[u8]) -> String {
     const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
     let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
     for chunk in data.chunks(3) {
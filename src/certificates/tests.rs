@@ -6,6 +6,7 @@ use crate::hardware::{
     Capabilities, HashAlgorithm, InMemoryStorage, NoCertificateStore, StoredCertificates,
 };
 use crate::state::ChargePointEvent;
+use crate::test_certificates::CHARGING_STATION;
 use alloc::boxed::Box;
 use alloc::string::ToString;
 use alloc::sync::Arc;
@@ -142,8 +143,8 @@ async fn a_charge_point_without_the_capability_refuses_in_the_right_shape_per_me
 async fn the_default_store_refuses_an_install_it_could_not_later_address() {
     let actor = actor_with_certificates().await;
 
-    // `StoredCertificates` does no X.509 parsing, so it cannot compute the hash a CSMS would
-    // delete this certificate by - and one it cannot address is one it cannot delete. Refusing is
+    // Without a certificate it can parse, `StoredCertificates` cannot compute the hash a CSMS
+    // would delete this one by - and one it cannot address is one it cannot delete. Refusing is
     // more honest than storing something unreachable.
     assert_eq!(
         handle_install_certificate(
@@ -274,6 +275,54 @@ async fn a_solicited_certificate_signed_is_installed_in_the_right_slot() {
     assert_eq!(second, CertificateSignedOutcome::Rejected);
 }
 
+#[tokio::test]
+async fn a_chain_delivered_twice_is_reported_as_a_replay_instead_of_as_unsolicited() {
+    let actor = actor_with_certificates().await;
+    let mut security_events = actor.subscribe_security_events();
+    let store = AcceptingStore::default();
+    let pending = PendingSignRequests::new();
+    pending.record_sent(
+        CertificateSigningPurpose::ChargingStationCertificate,
+        Some(3),
+    );
+    let chain = "-----BEGIN CERTIFICATE-----";
+
+    let first = handle_certificate_signed(
+        &actor,
+        &store,
+        &pending,
+        CertificateSigningPurpose::ChargingStationCertificate,
+        Some(3),
+        chain,
+    )
+    .await;
+    assert_eq!(first, CertificateSignedOutcome::Accepted);
+
+    let second = handle_certificate_signed(
+        &actor,
+        &store,
+        &pending,
+        CertificateSigningPurpose::ChargingStationCertificate,
+        Some(3),
+        chain,
+    )
+    .await;
+
+    assert_eq!(second, CertificateSignedOutcome::Rejected);
+    let event = security_events.recv().await.unwrap();
+    assert_eq!(event.event_type, SecurityEventType::AttemptedReplayAttacks);
+    assert!(event.tech_info.unwrap().contains("requestId Some(3)"));
+    assert!(
+        tokio::time::timeout(
+            core::time::Duration::from_millis(50),
+            security_events.recv()
+        )
+        .await
+        .is_err(),
+        "the replay report stands in for the unsolicited one"
+    );
+}
+
 #[tokio::test]
 async fn pending_for_one_purpose_does_not_authorize_the_other() {
     let actor = actor_with_certificates().await;
@@ -363,7 +412,7 @@ async fn a_signed_charging_station_certificate_is_installed_into_the_real_store(
         &pending,
         CertificateSigningPurpose::ChargingStationCertificate,
         None,
-        "-----BEGIN CERTIFICATE-----\nleaf\n-----END CERTIFICATE-----",
+        CHARGING_STATION,
     )
     .await;
 
@@ -373,7 +422,7 @@ async fn a_signed_charging_station_certificate_is_installed_into_the_real_store(
             .certificate_chain_pem(CertificateUse::ChargingStation)
             .await
             .unwrap(),
-        Some("-----BEGIN CERTIFICATE-----\nleaf\n-----END CERTIFICATE-----".to_string())
+        Some(CHARGING_STATION.to_string())
     );
 }
 
@@ -848,7 +897,7 @@ async fn a_chain_larger_than_max_certificate_chain_size_is_refused() {
                 instance: None,
             },
             attribute_type: VariableAttributeType::Actual,
-            value: "64".into(),
+            value: CHARGING_STATION.len().to_string(),
         }],
         &crate::hardware::NoKeyStore,
     )
@@ -860,7 +909,7 @@ async fn a_chain_larger_than_max_certificate_chain_size_is_refused() {
         &pending,
         CertificateSigningPurpose::ChargingStationCertificate,
         Some(1),
-        &"x".repeat(65),
+        &format!("{CHARGING_STATION}\n"),
     )
     .await;
 
@@ -889,9 +938,98 @@ async fn a_chain_larger_than_max_certificate_chain_size_is_refused() {
             &pending,
             CertificateSigningPurpose::ChargingStationCertificate,
             Some(1),
-            &"x".repeat(64),
+            CHARGING_STATION,
         )
         .await,
         CertificateSignedOutcome::Accepted
     );
 }
+
+/// B4.3 end to end with nothing faked: a [`SoftKeyStore`](crate::hardware::SoftKeyStore) over the
+/// real [`RustCryptoBackend`](crate::hardware::RustCryptoBackend) generates the key and signs the
+/// CSR, a local test CA issues a certificate for it, and that certificate is installed through
+/// `CertificateSigned` into the real store.
+#[cfg(feature = "software-crypto")]
+#[tokio::test]
+async fn a_csr_signed_by_a_test_ca_is_installed_through_certificate_signed() {
+    use crate::hardware::{KeyStore, RustCryptoBackend, SignatureAlgorithm, SoftKeyStore};
+    use p256::ecdsa::signature::Verifier;
+    use p256::pkcs8::DecodePublicKey;
+    use x509_cert::builder::{Builder, CertificateBuilder, Profile};
+    use x509_cert::der::{DecodePem, Encode, EncodePem, pem::LineEnding};
+    use x509_cert::name::Name;
+    use x509_cert::request::CertReq;
+    use x509_cert::serial_number::SerialNumber;
+    use x509_cert::time::Validity;
+
+    let actor = actor_with_certificates().await;
+    let key_store = SoftKeyStore::new(InMemoryStorage::new(), RustCryptoBackend::with_os_rng());
+    let key = key_store
+        .generate_key_pair(SignatureAlgorithm::EcdsaP256Sha256)
+        .await
+        .unwrap();
+    let csr_pem = build_signed_csr(
+        &key_store,
+        &key,
+        &CsrSubject::new("CP-0001").with_organization_name("Example CPO"),
+    )
+    .await
+    .unwrap();
+
+    // The CA's side: the CSR parses, and its signature verifies against the key it carries.
+    let csr = CertReq::from_pem(csr_pem.as_bytes()).unwrap();
+    let requested_key =
+        p256::ecdsa::VerifyingKey::from_public_key_der(&csr.info.public_key.to_der().unwrap())
+            .unwrap();
+    requested_key
+        .verify(
+            &csr.info.to_der().unwrap(),
+            &p256::ecdsa::Signature::from_der(csr.signature.raw_bytes()).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(csr.info.public_key.to_der().unwrap(), key.public_key.bytes);
+
+    let ca_key = p256::ecdsa::SigningKey::random(&mut rand_core::OsRng);
+    let certificate = CertificateBuilder::new(
+        Profile::Leaf {
+            issuer: "CN=Test CSMS CA".parse::<Name>().unwrap(),
+            enable_key_agreement: false,
+            enable_key_encipherment: false,
+        },
+        SerialNumber::from(1u32),
+        Validity::from_now(core::time::Duration::from_secs(3600)).unwrap(),
+        csr.info.subject.clone(),
+        csr.info.public_key.clone(),
+        &ca_key,
+    )
+    .unwrap()
+    .build::<p256::ecdsa::DerSignature>()
+    .unwrap();
+    let certificate_pem = certificate.to_pem(LineEnding::LF).unwrap();
+
+    let store = store();
+    let pending = PendingSignRequests::new();
+    pending.record_sent(
+        CertificateSigningPurpose::ChargingStationCertificate,
+        Some(7),
+    );
+    assert_eq!(
+        handle_certificate_signed(
+            &actor,
+            &store,
+            &pending,
+            CertificateSigningPurpose::ChargingStationCertificate,
+            Some(7),
+            &certificate_pem,
+        )
+        .await,
+        CertificateSignedOutcome::Accepted
+    );
+    assert_eq!(
+        store
+            .certificate_chain_pem(CertificateUse::ChargingStation)
+            .await
+            .unwrap(),
+        Some(certificate_pem)
+    );
+}
@@ -34,6 +34,19 @@ impl Clock for SystemClock {
     }
 }
 
+/// A [`Clock`] for a component that can use one but was not given one: it always reads the Unix
+/// epoch, which [`is_synchronized`] reports as unsynchronized, so the component takes its
+/// "clock not set" path rather than judging anything by a made-up date. The default clock of
+/// [`StoredCertificates`](crate::hardware::StoredCertificates).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnsetClock;
+
+impl Clock for UnsetClock {
+    fn now(&self) -> DateTime<Utc> {
+        DateTime::UNIX_EPOCH
+    }
+}
+
 /// The earliest wall-clock time treated as plausibly real by [`is_synchronized`]. Hardware
 /// without an RTC ([`crate::hardware::Capabilities::has_rtc`] is `false`)
 /// conventionally boots reading the Unix epoch or some other fixed, implausible date rather than
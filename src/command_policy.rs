@@ -0,0 +1,827 @@
+//! Operator-configurable limits on what the CSMS may command: a [`CommandPolicy`] every inbound
+//! handler consults before it acts (`docs/THREAT-MODEL.md` §4.8).
+//!
+//! # Why this exists
+//!
+//! OCPP hands the CSMS broad authority, and this crate holds only a couple of fixed lines against
+//! it (no downgrade to cleartext, no unsolicited certificate). An operator often wants more, and
+//! wants them to be *theirs* rather than this crate's: no `Reset` while a driver is charging, no
+//! `SetNetworkProfile` outside the night-time maintenance window, no charging profile above what
+//! the site's supply contract allows. None of those is a protocol rule, so none of them can be
+//! hard-coded here - but all of them have the same shape: look at the command and the charge
+//! point's state, then say yes or no before anything happens.
+//!
+//! # How a refusal reaches the CSMS
+//!
+//! A policy only decides *whether*; each handler decides *how* to say no, following the same
+//! rule [`crate::refusal`]'s decision table records for absent capabilities: where the response
+//! schema has a status that can say no, the refusal is that status (`Rejected`, or the nearest
+//! thing the message has) in an ordinary CALLRESULT; where it has none, the refusal is a
+//! CALLERROR, built by [`crate::refusal::ocpp_2_1_refused_by_policy`] and its siblings with
+//! `SecurityError` - the code OCPP reserves for "refused for a security reason", which is what an
+//! operator's policy is. Every refusal is logged at `warn!` with the policy's reason, since the
+//! CSMS only ever sees the bare status.
+//!
+//! The command a policy sees is named after the OCPP 2.x message the protocol-agnostic handler
+//! implements ([`InboundCommand::action`]), so one rule covers every protocol version: a 1.6J
+//! `RemoteStartTransaction` is screened as `RequestStartTransaction`, `ChangeConfiguration` as
+//! `SetVariables`, `GetConfiguration` as `GetVariables`, `GetDiagnostics` as `GetLog` and
+//! `SignedUpdateFirmware` as `UpdateFirmware`.
+//!
+//! # The built-in policy
+//!
+//! Every actor starts with a [`DeviceModelCommandPolicy`], which reads its rules from the
+//! vendor-specific `CommandPolicyCtrlr` component of the device model - see
+//! [`CommandPolicyRules`] for the variables and what each does. Nothing is registered there by
+//! default, and an absent variable is a rule that is off, so a charge point that never configures
+//! a policy behaves exactly as before. The rules are registered `ReadOnly`: a CSMS can read the
+//! policy it is held to, but a policy the CSMS could switch off with `SetVariables` would not
+//! hold the line it exists for.
+//!
+//! An integrator with rules the device model cannot express implements [`CommandPolicy`]
+//! directly and installs it with [`crate::ChargePointBuilder::command_policy`].
+
+use crate::actor::ChargePointActor;
+use crate::clock::{Clock, is_synchronized};
+use crate::firmware::FirmwareUpdateRequest;
+use crate::state::{
+    ChargePointState, ChargingProfile, ChargingProfileScope, ChargingRateUnit, Component,
+    DeviceModel, DeviceModelEvent, NetworkConnectionProfile, ResetKind, ResetTarget, Variable,
+    VariableAttribute, VariableAttributeType, VariableCharacteristics, VariableDataType,
+    VariableMutability,
+};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use chrono::{DateTime, Timelike, Utc};
+use core::cell::RefCell;
+use core::fmt;
+use embassy_sync::blocking_mutex::Mutex as BlockingMutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+
+/// The vendor-specific component [`DeviceModelCommandPolicy`] reads its rules from.
+pub const COMMAND_POLICY_COMPONENT: &str = "CommandPolicyCtrlr";
+
+/// An inbound CSMS command, as a [`CommandPolicy`] sees it: which message it is, and - for the
+/// commands operators most often want to constrain - what it asks for.
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum InboundCommand<'a> {
+    /// `Reset` (1.6J: `Reset`, `Hard` as `Immediate`, `Soft` as `OnIdle`).
+    Reset {
+        /// What is to be reset.
+        target: ResetTarget,
+        /// When.
+        kind: ResetKind,
+    },
+    /// `SetNetworkProfile`.
+    SetNetworkProfile {
+        /// The configuration slot the profile is for.
+        slot: i32,
+        /// The profile itself.
+        profile: &'a NetworkConnectionProfile,
+    },
+    /// `SetChargingProfile`.
+    SetChargingProfile {
+        /// Where the profile is to be installed.
+        scope: ChargingProfileScope,
+        /// The profile itself.
+        profile: &'a ChargingProfile,
+    },
+    /// `UpdateFirmware` (and 1.6J's `SignedUpdateFirmware`).
+    UpdateFirmware {
+        /// The update requested.
+        request: &'a FirmwareUpdateRequest,
+    },
+    /// Any other command, by message name - see the module docs for how names are chosen.
+    Other {
+        /// The OCPP message name.
+        action: &'static str,
+    },
+}
+
+impl InboundCommand<'_> {
+    /// The OCPP message name this command is screened under.
+    pub fn action(&self) -> &'static str {
+        match self {
+            Self::Reset { .. } => "Reset",
+            Self::SetNetworkProfile { .. } => "SetNetworkProfile",
+            Self::SetChargingProfile { .. } => "SetChargingProfile",
+            Self::UpdateFirmware { .. } => "UpdateFirmware",
+            Self::Other { action } => action,
+        }
+    }
+}
+
+/// A [`CommandPolicy`]'s answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyDecision {
+    /// Let the handler act on the command as it normally would.
+    Accept,
+    /// Refuse the command before the handler acts on it.
+    Refuse {
+        /// Why, for the operator reading the log. Never sent to the CSMS.
+        reason: String,
+    },
+}
+
+/// Decides, before any handler acts, whether this charge point will carry out a CSMS command.
+///
+/// Called synchronously on the handler's task, with a snapshot of the state the handler is about
+/// to act on, so it must not block. A policy that needs memory between commands - a second
+/// confirmation, a rate - keeps it behind interior mutability, as [`DeviceModelCommandPolicy`]
+/// does.
+pub trait CommandPolicy: Send + Sync {
+    /// Evaluates `command` against `state`.
+    fn evaluate(&self, command: &InboundCommand<'_>, state: &ChargePointState) -> PolicyDecision;
+}
+
+/// A command the installed [`CommandPolicy`] refused - see [`screen`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandRefused {
+    /// The message name, as [`InboundCommand::action`] gave it.
+    pub action: &'static str,
+    /// The policy's reason.
+    pub reason: String,
+}
+
+/// Consults `actor`'s installed [`CommandPolicy`] about `command`, logging a refusal at `warn!`
+/// with its reason. The one entry point every handler goes through.
+pub fn screen(
+    actor: &ChargePointActor,
+    command: &InboundCommand<'_>,
+) -> Result<(), CommandRefused> {
+    match actor.command_policy().evaluate(command, &actor.state()) {
+        PolicyDecision::Accept => Ok(()),
+        PolicyDecision::Refuse { reason } => {
+            let action = command.action();
+            tracing::warn!(
+                action,
+                reason = reason.as_str(),
+                "refusing a CSMS command: the command policy does not allow it"
+            );
+            Err(CommandRefused { action, reason })
+        }
+    }
+}
+
+/// [`screen`] for an [`InboundCommand::Other`] - the form a handler with nothing but the message
+/// name to offer uses.
+pub fn screen_action(actor: &ChargePointActor, action: &'static str) -> Result<(), CommandRefused> {
+    screen(actor, &InboundCommand::Other { action })
+}
+
+/// Whether the installed policy refuses `action`. Reads like the
+/// [`crate::refusal::capability_present`] check it usually sits beside, for handlers that refuse
+/// through a status rather than a CALLERROR.
+pub fn refuses(actor: &ChargePointActor, action: &'static str) -> bool {
+    screen_action(actor, action).is_err()
+}
+
+/// A daily UTC time-of-day window, `HH:MM-HH:MM`, start inclusive and end exclusive. A window
+/// whose end is before its start runs past midnight (`22:00-04:00`); one whose end equals its
+/// start is empty and never open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenanceWindow {
+    start_minute: u16,
+    end_minute: u16,
+}
+
+impl MaintenanceWindow {
+    /// Parses `HH:MM-HH:MM`. `None` for anything else, including an hour past 23 or a minute
+    /// past 59.
+    pub fn parse(value: &str) -> Option<Self> {
+        let (start, end) = value.trim().split_once('-')?;
+        Some(Self {
+            start_minute: parse_minute_of_day(start)?,
+            end_minute: parse_minute_of_day(end)?,
+        })
+    }
+
+    /// Whether `now` falls inside the window.
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        // Bounded by 1440, so the narrowing can't truncate.
+        let minute = (now.time().num_seconds_from_midnight() / 60) as u16;
+        if self.start_minute <= self.end_minute {
+            (self.start_minute..self.end_minute).contains(&minute)
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
+    }
+}
+
+impl fmt::Display for MaintenanceWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}-{:02}:{:02}",
+            self.start_minute / 60,
+            self.start_minute % 60,
+            self.end_minute / 60,
+            self.end_minute % 60
+        )
+    }
+}
+
+fn parse_minute_of_day(value: &str) -> Option<u16> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+/// The declarative rules [`DeviceModelCommandPolicy`] enforces, one `CommandPolicyCtrlr`
+/// variable each. Every rule defaults to off.
+///
+/// | Variable | Type | Rule |
+/// |---|---|---|
+/// | `RefuseResetDuringTransaction` | boolean | refuse a `Reset` whose target has a transaction in progress |
+/// | `NetworkProfileWindow` | string, `HH:MM-HH:MM` UTC | refuse `SetNetworkProfile` outside the window |
+/// | `MaxChargingProfileAmps` | decimal, A | refuse a `SetChargingProfile` with any period above it |
+/// | `MaxChargingProfileWatts` | decimal, W | the same, for schedules in watts |
+/// | `FirmwareUpdateConfirmation` | integer, s | refuse an `UpdateFirmware` unless the same location was sent within the last this-many seconds |
+///
+/// `0` turns a numeric rule off, as it does for every numeric variable in this crate. A window
+/// that does not parse is treated as never open - a restriction the operator asked for and
+/// mistyped should fail closed, not silently vanish.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommandPolicyRules {
+    /// `RefuseResetDuringTransaction`.
+    pub refuse_reset_during_transaction: bool,
+    /// `NetworkProfileWindow`.
+    pub network_profile_window: Option<MaintenanceWindow>,
+    /// `MaxChargingProfileAmps`.
+    pub max_charging_profile_amps: Option<f64>,
+    /// `MaxChargingProfileWatts`.
+    pub max_charging_profile_watts: Option<f64>,
+    /// `FirmwareUpdateConfirmation`, in seconds.
+    pub firmware_update_confirmation_secs: Option<u32>,
+}
+
+impl CommandPolicyRules {
+    /// Reads the rules out of `model`'s `CommandPolicyCtrlr` component. Read fresh per command
+    /// rather than cached, so the policy a CSMS reads with `GetVariables` is the policy enforced.
+    pub fn from_device_model(model: &DeviceModel) -> Self {
+        let read = |name: &str| {
+            model
+                .get(&policy_component(), &policy_variable(name))
+                .and_then(|definition| definition.attribute(VariableAttributeType::Actual))
+                .map(|attribute| attribute.value.clone())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            refuse_reset_during_transaction: read("RefuseResetDuringTransaction")
+                .is_some_and(|value| value.eq_ignore_ascii_case("true")),
+            network_profile_window: read("NetworkProfileWindow").map(|value| {
+                MaintenanceWindow::parse(&value).unwrap_or_else(|| {
+                    tracing::warn!(
+                        value = value.as_str(),
+                        "CommandPolicyCtrlr.NetworkProfileWindow is not HH:MM-HH:MM - treating the window as never open"
+                    );
+                    MaintenanceWindow {
+                        start_minute: 0,
+                        end_minute: 0,
+                    }
+                })
+            }),
+            max_charging_profile_amps: read("MaxChargingProfileAmps")
+                .and_then(|value| value.parse::<f64>().ok())
+                .filter(|limit| *limit > 0.0),
+            max_charging_profile_watts: read("MaxChargingProfileWatts")
+                .and_then(|value| value.parse::<f64>().ok())
+                .filter(|limit| *limit > 0.0),
+            firmware_update_confirmation_secs: read("FirmwareUpdateConfirmation")
+                .and_then(|value| value.parse::<u32>().ok())
+                .filter(|secs| *secs != 0),
+        }
+    }
+
+    /// The `CommandPolicyCtrlr` registrations that express these rules, for a hardware binding to
+    /// send at startup (or [`crate::ChargePointBuilder::command_policy_rules`] to send for it).
+    /// All five variables are registered, the ones that are off with their "off" value, so a
+    /// CSMS reading the component sees the whole policy. Registered `ReadOnly` - see the module
+    /// docs.
+    pub fn device_model_events(&self) -> Vec<DeviceModelEvent> {
+        let register = |name: &str, data_type, unit: Option<&str>, value: String| {
+            DeviceModelEvent::VariableRegistered {
+                component: policy_component(),
+                variable: policy_variable(name),
+                characteristics: VariableCharacteristics {
+                    data_type,
+                    unit: unit.map(Into::into),
+                    min_limit: None,
+                    max_limit: None,
+                    values_list: None,
+                    supports_monitoring: false,
+                },
+                attributes: alloc::vec![VariableAttribute {
+                    attribute_type: VariableAttributeType::Actual,
+                    value,
+                    mutability: VariableMutability::ReadOnly,
+                    persistent: true,
+                    constant: false,
+                    requires_reboot: false,
+                }],
+            }
+        };
+        alloc::vec![
+            register(
+                "RefuseResetDuringTransaction",
+                VariableDataType::Boolean,
+                None,
+                self.refuse_reset_during_transaction.to_string(),
+            ),
+            register(
+                "NetworkProfileWindow",
+                VariableDataType::String,
+                None,
+                self.network_profile_window
+                    .map(|window| window.to_string())
+                    .unwrap_or_default(),
+            ),
+            register(
+                "MaxChargingProfileAmps",
+                VariableDataType::Decimal,
+                Some("A"),
+                self.max_charging_profile_amps.unwrap_or(0.0).to_string(),
+            ),
+            register(
+                "MaxChargingProfileWatts",
+                VariableDataType::Decimal,
+                Some("W"),
+                self.max_charging_profile_watts.unwrap_or(0.0).to_string(),
+            ),
+            register(
+                "FirmwareUpdateConfirmation",
+                VariableDataType::Integer,
+                Some("s"),
+                self.firmware_update_confirmation_secs
+                    .unwrap_or(0)
+                    .to_string(),
+            ),
+        ]
+    }
+}
+
+fn policy_component() -> Component {
+    Component {
+        name: COMMAND_POLICY_COMPONENT.into(),
+        instance: None,
+        evse: None,
+    }
+}
+
+fn policy_variable(name: &str) -> Variable {
+    Variable {
+        name: name.into(),
+        instance: None,
+    }
+}
+
+/// The built-in [`CommandPolicy`]: enforces the [`CommandPolicyRules`] in the device model, and
+/// accepts everything they do not mention.
+///
+/// `clock` judges the two time-based rules, the maintenance window and the confirmation window.
+/// While it is not [synchronized](is_synchronized), both refuse: a window that cannot be placed
+/// in time cannot be said to be open. The actor's default instance uses
+/// [`UnsetClock`](crate::clock::UnsetClock), so an integrator configuring either rule installs
+/// their own through [`crate::ChargePointBuilder::command_policy`].
+pub struct DeviceModelCommandPolicy<K> {
+    clock: K,
+    pending_firmware_update: BlockingMutex<CriticalSectionRawMutex, RefCell<PendingFirmwareUpdate>>,
+}
+
+/// The `UpdateFirmware` awaiting its confirmation: the location asked for, and when.
+type PendingFirmwareUpdate = Option<(String, DateTime<Utc>)>;
+
+impl<K: Clock> DeviceModelCommandPolicy<K> {
+    /// A policy judging time by `clock`.
+    pub fn new(clock: K) -> Self {
+        Self {
+            clock,
+            pending_firmware_update: BlockingMutex::new(RefCell::new(None)),
+        }
+    }
+
+    fn firmware_update_confirmed(
+        &self,
+        request: &FirmwareUpdateRequest,
+        window_secs: u32,
+    ) -> PolicyDecision {
+        let now = self.clock.now();
+        if !is_synchronized(&now) {
+            return refuse("the clock is not synchronized, so a confirmation cannot be timed");
+        }
+        let confirmed = self.pending_firmware_update.lock(|pending| {
+            let mut pending = pending.borrow_mut();
+            let confirmed = pending.as_ref().is_some_and(|(location, asked_at)| {
+                *location == request.location
+                    && (now - *asked_at).num_seconds() <= i64::from(window_secs)
+            });
+            *pending = if confirmed {
+                None
+            } else {
+                Some((request.location.clone(), now))
+            };
+            confirmed
+        });
+        if confirmed {
+            PolicyDecision::Accept
+        } else {
+            refuse(&alloc::format!(
+                "UpdateFirmware needs confirming: send it again within {window_secs}s"
+            ))
+        }
+    }
+}
+
+impl<K: Clock + Send + Sync> CommandPolicy for DeviceModelCommandPolicy<K> {
+    fn evaluate(&self, command: &InboundCommand<'_>, state: &ChargePointState) -> PolicyDecision {
+        let rules = CommandPolicyRules::from_device_model(&state.device_model);
+        match command {
+            InboundCommand::Reset { target, .. }
+                if rules.refuse_reset_during_transaction
+                    && transaction_in_progress(state, *target) =>
+            {
+                refuse("a transaction is in progress")
+            }
+            InboundCommand::SetNetworkProfile { .. } => match rules.network_profile_window {
+                None => PolicyDecision::Accept,
+                Some(window) => {
+                    let now = self.clock.now();
+                    if !is_synchronized(&now) {
+                        refuse(
+                            "the clock is not synchronized, so the maintenance window cannot be placed",
+                        )
+                    } else if window.contains(now) {
+                        PolicyDecision::Accept
+                    } else {
+                        refuse(&alloc::format!(
+                            "outside the {window} UTC maintenance window"
+                        ))
+                    }
+                }
+            },
+            InboundCommand::SetChargingProfile { profile, .. } => {
+                match highest_limit_over_cap(profile, &rules) {
+                    Some((limit, cap)) => refuse(&alloc::format!(
+                        "a period's limit of {limit} is above the configured cap of {cap}"
+                    )),
+                    None => PolicyDecision::Accept,
+                }
+            }
+            InboundCommand::UpdateFirmware { request } => {
+                match rules.firmware_update_confirmation_secs {
+                    Some(window_secs) => self.firmware_update_confirmed(request, window_secs),
+                    None => PolicyDecision::Accept,
+                }
+            }
+            _ => PolicyDecision::Accept,
+        }
+    }
+}
+
+fn refuse(reason: &str) -> PolicyDecision {
+    PolicyDecision::Refuse {
+        reason: reason.into(),
+    }
+}
+
+/// Whether any connector `target` covers has a transaction.
+fn transaction_in_progress(state: &ChargePointState, target: ResetTarget) -> bool {
+    let evse_in_progress = |evse_id: usize| {
+        state
+            .evses
+            .get(evse_id)
+            .is_some_and(|evse| evse.transactions.iter().any(Option::is_some))
+    };
+    match target {
+        ResetTarget::ChargePoint => (0..state.evses.len()).any(evse_in_progress),
+        ResetTarget::Evse { evse_id } => evse_in_progress(evse_id),
+    }
+}
+
+/// The first period limit in `profile` above the cap for its schedule's unit, with that cap.
+fn highest_limit_over_cap(
+    profile: &ChargingProfile,
+    rules: &CommandPolicyRules,
+) -> Option<(f64, f64)> {
+    profile.schedules.iter().find_map(|schedule| {
+        let cap = match schedule.rate_unit {
+            ChargingRateUnit::Amps => rules.max_charging_profile_amps,
+            ChargingRateUnit::Watts => rules.max_charging_profile_watts,
+        }?;
+        schedule
+            .periods
+            .iter()
+            .map(|period| period.limit)
+            .find(|limit| *limit > cap)
+            .map(|limit| (limit, cap))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CommandPolicy, CommandPolicyRules, DeviceModelCommandPolicy, InboundCommand,
+        MaintenanceWindow, PolicyDecision,
+    };
+    use crate::actor::ChargePointActor;
+    use crate::clock::Clock;
+    use crate::executor::TokioExecutor;
+    use crate::firmware::FirmwareUpdateRequest;
+    use crate::reset::{ResetOutcome, handle_reset};
+    use crate::state::{
+        ChargePointEvent, ChargingProfile, ChargingProfileId, ChargingProfileKind,
+        ChargingProfilePurpose, ChargingProfileScope, ChargingRateUnit, ChargingSchedule,
+        ChargingSchedulePeriod, ConnectorEvent, EvseEvent, IdToken, IdTokenKind,
+        NetworkConnectionProfile, NetworkInterface, NetworkTransport, ResetKind, ResetTarget,
+    };
+    use alloc::sync::Arc;
+    use chrono::{DateTime, TimeDelta, Utc};
+    use core::cell::Cell;
+
+    /// A [`Clock`] tests can move forward.
+    struct SteppedClock(embassy_sync::blocking_mutex::CriticalSectionMutex<Cell<DateTime<Utc>>>);
+
+    impl SteppedClock {
+        fn at(timestamp: i64) -> Self {
+            Self(embassy_sync::blocking_mutex::CriticalSectionMutex::new(
+                Cell::new(DateTime::from_timestamp(timestamp, 0).unwrap()),
+            ))
+        }
+
+        fn advance(&self, secs: i64) {
+            self.0
+                .lock(|now| now.set(now.get() + TimeDelta::seconds(secs)));
+        }
+    }
+
+    impl Clock for SteppedClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.0.lock(Cell::get)
+        }
+    }
+
+    impl Clock for Arc<SteppedClock> {
+        fn now(&self) -> DateTime<Utc> {
+            self.as_ref().now()
+        }
+    }
+
+    // 2027-01-15 02:00:00 UTC.
+    const NIGHT: i64 = 1_799_978_400;
+
+    async fn actor_with(rules: CommandPolicyRules) -> ChargePointActor {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        for event in rules.device_model_events() {
+            actor
+                .send(ChargePointEvent::DeviceModel(event))
+                .await
+                .unwrap();
+        }
+        actor
+    }
+
+    fn firmware_request(location: &str) -> FirmwareUpdateRequest {
+        FirmwareUpdateRequest {
+            request_id: Some(1),
+            location: location.into(),
+            retrieve_at: None,
+            install_at: None,
+            signature: None,
+            signing_certificate: None,
+            retries: 0,
+            retry_interval_secs: 0,
+        }
+    }
+
+    fn amp_profile(limit: f64) -> ChargingProfile {
+        ChargingProfile {
+            id: ChargingProfileId(1),
+            stack_level: 0,
+            purpose: ChargingProfilePurpose::TxDefault,
+            kind: ChargingProfileKind::Absolute,
+            recurrency: None,
+            valid_from: None,
+            valid_to: None,
+            transaction_id: None,
+            schedules: alloc::vec![ChargingSchedule {
+                id: 1,
+                start_schedule: None,
+                duration_secs: None,
+                rate_unit: ChargingRateUnit::Amps,
+                min_charging_rate: None,
+                periods: alloc::vec![ChargingSchedulePeriod {
+                    start_period_secs: 0,
+                    limit,
+                    number_phases: None,
+                }],
+            }],
+            dyn_update_interval_secs: None,
+            dyn_update_time: None,
+        }
+    }
+
+    #[test]
+    fn a_window_is_start_inclusive_and_end_exclusive() {
+        let window = MaintenanceWindow::parse("01:00-03:00").unwrap();
+        let at = |secs: i64| DateTime::from_timestamp(NIGHT - 2 * 3600 + secs, 0).unwrap();
+
+        assert!(!window.contains(at(3600 - 60)));
+        assert!(window.contains(at(3600)));
+        assert!(window.contains(at(3 * 3600 - 60)));
+        assert!(!window.contains(at(3 * 3600)));
+    }
+
+    #[test]
+    fn a_window_ending_before_it_starts_runs_past_midnight() {
+        let window = MaintenanceWindow::parse("22:00-04:00").unwrap();
+        let midnight = NIGHT - 2 * 3600;
+        let at = |secs: i64| DateTime::from_timestamp(midnight + secs, 0).unwrap();
+
+        assert!(window.contains(at(0)));
+        assert!(window.contains(at(23 * 3600)));
+        assert!(!window.contains(at(12 * 3600)));
+        assert_eq!(window.to_string(), "22:00-04:00");
+    }
+
+    #[test]
+    fn a_malformed_window_does_not_parse() {
+        for value in ["", "01:00", "24:00-01:00", "01:60-02:00", "1-2"] {
+            assert_eq!(MaintenanceWindow::parse(value), None, "{value}");
+        }
+    }
+
+    #[tokio::test]
+    async fn with_no_rules_configured_everything_is_accepted() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let policy = DeviceModelCommandPolicy::new(SteppedClock::at(NIGHT));
+
+        assert_eq!(
+            policy.evaluate(&InboundCommand::Other { action: "Reset" }, &actor.state()),
+            PolicyDecision::Accept
+        );
+        assert_eq!(
+            policy.evaluate(
+                &InboundCommand::UpdateFirmware {
+                    request: &firmware_request("https://example.com/fw.bin")
+                },
+                &actor.state()
+            ),
+            PolicyDecision::Accept
+        );
+    }
+
+    #[tokio::test]
+    async fn a_reset_during_a_transaction_is_rejected_when_the_rule_is_on() {
+        let actor = actor_with(CommandPolicyRules {
+            refuse_reset_during_transaction: true,
+            ..CommandPolicyRules::default()
+        })
+        .await;
+        for event in [
+            ConnectorEvent::CableConnected,
+            ConnectorEvent::LockConfirmed,
+            ConnectorEvent::RemoteStartRequested {
+                id_token: IdToken {
+                    value: "04A224B2".into(),
+                    kind: IdTokenKind::ISO14443,
+                },
+                remote_start_id: None,
+            },
+            ConnectorEvent::ContactorClosed,
+        ] {
+            actor
+                .send(ChargePointEvent::Evse {
+                    evse_id: 0,
+                    event: EvseEvent::Connector {
+                        connector_id: 0,
+                        event,
+                    },
+                })
+                .await
+                .unwrap();
+        }
+
+        let outcome = handle_reset(&actor, ResetTarget::ChargePoint, ResetKind::OnIdle).await;
+
+        assert_eq!(outcome, ResetOutcome::Rejected);
+        assert!(actor.state().pending_reset.is_none());
+    }
+
+    #[tokio::test]
+    async fn a_reset_while_idle_is_still_accepted_when_the_rule_is_on() {
+        let actor = actor_with(CommandPolicyRules {
+            refuse_reset_during_transaction: true,
+            ..CommandPolicyRules::default()
+        })
+        .await;
+
+        let outcome = handle_reset(&actor, ResetTarget::ChargePoint, ResetKind::Immediate).await;
+
+        assert_eq!(outcome, ResetOutcome::Accepted);
+    }
+
+    #[tokio::test]
+    async fn a_charging_profile_above_the_cap_is_refused() {
+        let actor = actor_with(CommandPolicyRules {
+            max_charging_profile_amps: Some(16.0),
+            ..CommandPolicyRules::default()
+        })
+        .await;
+        let policy = DeviceModelCommandPolicy::new(SteppedClock::at(NIGHT));
+        let evaluate = |limit| {
+            policy.evaluate(
+                &InboundCommand::SetChargingProfile {
+                    scope: ChargingProfileScope::ChargePoint,
+                    profile: &amp_profile(limit),
+                },
+                &actor.state(),
+            )
+        };
+
+        assert_eq!(evaluate(16.0), PolicyDecision::Accept);
+        assert!(matches!(evaluate(32.0), PolicyDecision::Refuse { .. }));
+    }
+
+    #[tokio::test]
+    async fn a_network_profile_is_only_accepted_inside_the_window() {
+        let actor = actor_with(CommandPolicyRules {
+            network_profile_window: MaintenanceWindow::parse("01:00-03:00"),
+            ..CommandPolicyRules::default()
+        })
+        .await;
+        let clock = Arc::new(SteppedClock::at(NIGHT));
+        let policy = DeviceModelCommandPolicy::new(clock.clone());
+        let profile = NetworkConnectionProfile {
+            csms_url: "wss://operator.example/ocpp".into(),
+            interface: NetworkInterface::Any,
+            transport: NetworkTransport::Json,
+            security_profile: 2,
+            message_timeout_secs: 30,
+            identity: None,
+        };
+        let command = InboundCommand::SetNetworkProfile {
+            slot: 1,
+            profile: &profile,
+        };
+
+        assert_eq!(
+            policy.evaluate(&command, &actor.state()),
+            PolicyDecision::Accept
+        );
+        clock.advance(3600);
+        assert!(matches!(
+            policy.evaluate(&command, &actor.state()),
+            PolicyDecision::Refuse { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_firmware_update_is_accepted_only_once_confirmed_within_the_window() {
+        let actor = actor_with(CommandPolicyRules {
+            firmware_update_confirmation_secs: Some(60),
+            ..CommandPolicyRules::default()
+        })
+        .await;
+        let clock = Arc::new(SteppedClock::at(NIGHT));
+        let policy = DeviceModelCommandPolicy::new(clock.clone());
+        let request = firmware_request("https://example.com/fw.bin");
+        let other = firmware_request("https://example.com/other.bin");
+        let evaluate =
+            |request| policy.evaluate(&InboundCommand::UpdateFirmware { request }, &actor.state());
+
+        assert!(matches!(evaluate(&request), PolicyDecision::Refuse { .. }));
+        clock.advance(30);
+        assert_eq!(evaluate(&request), PolicyDecision::Accept);
+
+        // A confirmation is used up, a late one starts over, and a different location is not one.
+        assert!(matches!(evaluate(&request), PolicyDecision::Refuse { .. }));
+        clock.advance(61);
+        assert!(matches!(evaluate(&request), PolicyDecision::Refuse { .. }));
+        assert!(matches!(evaluate(&other), PolicyDecision::Refuse { .. }));
+        assert!(matches!(evaluate(&request), PolicyDecision::Refuse { .. }));
+    }
+
+    #[tokio::test]
+    async fn an_installed_policy_refuses_through_the_handlers_status() {
+        struct RefuseEverything;
+        impl CommandPolicy for RefuseEverything {
+            fn evaluate(
+                &self,
+                _command: &InboundCommand<'_>,
+                _state: &crate::state::ChargePointState,
+            ) -> PolicyDecision {
+                PolicyDecision::Refuse {
+                    reason: "test".into(),
+                }
+            }
+        }
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        actor.set_command_policy(Arc::new(RefuseEverything));
+
+        let outcome = handle_reset(&actor, ResetTarget::ChargePoint, ResetKind::Immediate).await;
+
+        assert_eq!(outcome, ResetOutcome::Rejected);
+    }
+}
@@ -1,9 +1,9 @@
 //! Live `ocpp-client` WebSocket wiring, this crate's std/tokio "batteries included" entry point.
 //! [`setup`](crate::setup) itself only takes an already-connected CSMS client -
-//! [`connect_and_setup`] closes that gap by dialing `address` with `ocpp_client::connect` first
-//! (offering whichever OCPP versions `versions` names, or every version compiled into this
-//! build if `None` - the CSMS picks one via the WebSocket subprotocol handshake, per RFC 6455),
-//! then handing the negotiated client straight to `setup`. Embedded targets, or std users who
+//! [`connect_and_setup`] closes that gap by dialing `address` first (offering whichever OCPP
+//! versions `versions` names, or every version compiled into this build if `None` - the CSMS
+//! accepts one via the WebSocket subprotocol handshake, per RFC 6455), then handing the negotiated
+//! client straight to `setup`. Embedded targets, or std users who
 //! need a non-WebSocket transport, still construct their own client and call
 //! [`setup`](crate::setup) directly.
 //!
@@ -19,9 +19,9 @@
 //!
 //! What each version gets is therefore *not* identical, and the differences are real rather than
 //! oversights - see [`connect_and_setup`]'s own docs for the per-version list. `versions` orders
-//! what this charge point offers (A3): the CSMS picks from that list, so putting one version in
-//! it forces that version, and the default offers every version compiled into the build,
-//! newest first.
+//! what this charge point offers (A3): each is offered in turn and the first the CSMS accepts is
+//! used, so putting one version in it forces that version, and the default offers every version
+//! compiled into the build, newest first.
 
 use crate::ChargePointRuntime;
 use crate::builder::ChargePointBuilder;
@@ -32,7 +32,9 @@ use crate::network_switch::ConnectionTarget;
 use crate::payload_limit::PayloadLimits;
 use crate::provisioning::Backoff;
 use crate::state::{Component, DeviceModel, Variable, VariableAttributeType};
+use alloc::boxed::Box;
 use core::fmt;
+use core::time::Duration;
 use ocpp_client::{ConnectOptions, NegotiatedClient, OcppVersion};
 
 /// A CSMS address with any `user:password@` userinfo stripped, for logging.
@@ -73,7 +75,7 @@ pub enum ConnectAndSetupError<S> {
     Connect(Box<dyn std::error::Error + Send + Sync>),
     /// The CSMS negotiated a real OCPP version over the WebSocket handshake that this build was
     /// not compiled with support for. Only reachable if `versions` names a version whose Cargo
-    /// feature is off - which `ocpp_client::connect` would not offer in the first place - so this
+    /// feature is off - which this crate would not offer in the first place - so this
     /// is a defensive case rather than one a correctly-configured charge point meets.
     ///
     /// Before A1/A2 this was the *normal* outcome of negotiating 1.6J or 2.0.1, because only 2.1
@@ -99,18 +101,40 @@ impl<S: fmt::Display> fmt::Display for ConnectAndSetupError<S> {
 
 impl<S: fmt::Debug + fmt::Display> std::error::Error for ConnectAndSetupError<S> {}
 
+#[cfg(feature = "certificates")]
+impl<S> ConnectAndSetupError<S> {
+    /// The security event a failed dial amounts to, if it failed in TLS (an invalid CSMS
+    /// certificate, a CSMS that could not prove it holds its key, a protocol version or cipher
+    /// suite this charge point refuses) - see [`crate::trust_store::tls_failure_event`].
+    ///
+    /// The initial dial happens before there is a charge-point actor to raise it on, so
+    /// [`connect_and_setup`] cannot raise it itself the way a redial does. A caller that retries
+    /// should keep it and raise it through [`crate::security::report_security_event`] on the
+    /// runtime the first successful attempt returns, so it reaches the security log and the CSMS
+    /// like any other event recorded while offline.
+    pub fn security_event(&self) -> Option<crate::state::SecurityEvent> {
+        match self {
+            Self::Connect(error) => crate::trust_store::tls_failure_event(&**error),
+            Self::UnsupportedNegotiatedVersion(_) | Self::Start(_) => None,
+        }
+    }
+}
+
 /// Dials `address` over WebSocket, offering `versions` (or every version compiled into this
-/// build, if `None`) and letting the CSMS pick one (via `ocpp_client::connect`), then runs
+/// build, newest first, if `None`) one at a time until the CSMS accepts one, then runs
 /// [`setup`](crate::setup) against the resulting client if it negotiated OCPP 2.1 - the only
 /// version `setup()` can currently drive end-to-end (see this module's docs). The "batteries
 /// included" entry point for std/tokio users who don't need a custom transport.
 ///
-/// `payload_limits` (`None` for [`PayloadLimits::default`]) sets the inbound-frame ceiling every
-/// *redial* is guarded with (F5.2) - see [`crate::payload_limit`] for exactly what that covers.
-/// It does **not** cover the initial dial this function performs itself: `ocpp_client::connect`
-/// builds that connection's transport internally and exposes no hook to guard it, which is a real
-/// upstream gap this crate cannot close without duplicating transport/handshake logic
-/// `CLAUDE.md` reserves to `ocpp-client`.
+/// Every connection, the first and each redial, goes through the same network-switching target, so
+/// each one deduplicates repeated CSMS CALLs ([`crate::call_dedup`]) and is guarded by the
+/// inbound-frame ceiling `payload_limits` sets (`None` for [`PayloadLimits::default`], F5.2 - see
+/// [`crate::payload_limit`] for exactly what that covers). That is why versions are offered one
+/// at a time rather than negotiated in one handshake - see `dial_through` (private).
+///
+/// A redial that fails in TLS raises the matching security event (`InvalidCsmsCertificate` and
+/// friends) on the session's actor - see [`crate::trust_store`]. A failed initial dial has no
+/// session to raise it on; [`ConnectAndSetupError::security_event`] hands it back instead.
 pub async fn connect_and_setup<T, E, C, X, B>(
     charge_point: T,
     address: &str,
@@ -136,32 +160,39 @@ where
     // device-model variables, so what a CSMS reads there matches what the connection does.
     let options = options.unwrap_or_else(connection_options_from_device_model);
     // A9: the redial target has to exist before the connection does, because it *is* the
-    // connection's reconnector. Which version it redials is filled in below, once the CSMS has
-    // picked one.
+    // connection's reconnector - and it dials the first connection too, so every connection gets
+    // the same transport wrappers.
     let target = ConnectionTarget::new(address, &options);
-    // F5.2: configures the ceiling every redial through `target` is guarded with - see this
-    // function's own docs for why the *initial* dial just below is not covered.
+    // F5.2: configures the ceiling every connection through `target` is guarded with, the first
+    // included.
     target.set_max_inbound_frame_bytes(payload_limits.unwrap_or_default().max_inbound_frame_bytes);
     tracing::info!(
         endpoint = %redacted_endpoint(address),
         offered_versions = ?versions,
         "dialing the CSMS"
     );
-    let negotiated =
-        match ocpp_client::connect(address, versions, Some(target.install(options))).await {
-            Ok(negotiated) => negotiated,
-            Err(error) => {
-                // The single most-asked question during commissioning is "why won't it connect", and
-                // until now this path answered it only by returning an error to a caller that
-                // typically just propagates it.
+    let negotiated = match dial_through(&target, versions, &options).await {
+        Ok(negotiated) => negotiated,
+        Err(error) => {
+            // The single most-asked question during commissioning is "why won't it connect", and
+            // until now this path answered it only by returning an error to a caller that
+            // typically just propagates it.
+            tracing::warn!(
+                endpoint = %redacted_endpoint(address),
+                %error,
+                "the CSMS dial failed"
+            );
+            let error = ConnectAndSetupError::Connect(error);
+            #[cfg(feature = "certificates")]
+            if let Some(event) = error.security_event() {
                 tracing::warn!(
-                    endpoint = %redacted_endpoint(address),
-                    %error,
-                    "the CSMS dial failed"
+                    event = ?event.event_type,
+                    "the CSMS dial failed in TLS; the error carries the security event to raise"
                 );
-                return Err(ConnectAndSetupError::Connect(error));
             }
-        };
+            return Err(error);
+        }
+    };
 
     // `SystemMonotonicClock`/`SystemClock` (std-backed) rather than caller-supplied parameters -
     // this function is already std/tokio-only "batteries included" (it dials a real WebSocket),
@@ -173,24 +204,80 @@ where
     match negotiated {
         NegotiatedClient::V2_1(client) => {
             tracing::info!(version = "2.1", "the CSMS negotiated an OCPP version");
-            target.set_version(OcppVersion::V2_1);
             setup_ocpp_2_1(charge_point, client, executor, backoff, target).await
         }
         #[cfg(feature = "ocpp_2_0_1")]
         NegotiatedClient::V2_0_1(client) => {
             tracing::info!(version = "2.0.1", "the CSMS negotiated an OCPP version");
-            target.set_version(OcppVersion::V2_0_1);
             setup_ocpp_2_0_1(charge_point, client, executor, backoff, Some(target)).await
         }
         #[cfg(feature = "ocpp_1_6")]
         NegotiatedClient::V1_6(client) => {
             tracing::info!(version = "1.6J", "the CSMS negotiated an OCPP version");
-            target.set_version(OcppVersion::V1_6);
             setup_ocpp_1_6(charge_point, client, executor, backoff, target).await
         }
     }
 }
 
+/// The per-call timeout `ocpp-client` falls back to when `ConnectOptions::timeout` is `None`. Not
+/// exported by it, so mirrored here for [`dial_through`], which builds its `Client` itself.
+const OCPP_CLIENT_DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Dials the session's first connection through `target`, leaving `target` as its reconnector and
+/// recording the version it speaks.
+///
+/// `ocpp-client` negotiates among several versions only inside `ocpp_client::connect`, which
+/// builds the transport itself and exposes no hook to wrap it - and a first connection built
+/// there would dispatch the CSMS's CALLs without [`crate::call_dedup`] or
+/// [`crate::payload_limit`]. So each offered version is dialled through `target` in turn, in
+/// `versions`' order, and the first one the CSMS accepts is the one spoken. A dial that fails
+/// below the WebSocket handshake (the TCP connection, TLS) would fail the same way for every
+/// version, so it ends the attempt rather than being repeated.
+async fn dial_through(
+    target: &alloc::sync::Arc<ConnectionTarget>,
+    versions: Option<&[OcppVersion]>,
+    options: &ConnectOptions<'_>,
+) -> Result<NegotiatedClient, Box<dyn std::error::Error + Send + Sync>> {
+    let compiled_in = [
+        OcppVersion::V2_1,
+        #[cfg(feature = "ocpp_2_0_1")]
+        OcppVersion::V2_0_1,
+        #[cfg(feature = "ocpp_1_6")]
+        OcppVersion::V1_6,
+    ];
+    let timeout = options.timeout.unwrap_or(OCPP_CLIENT_DEFAULT_TIMEOUT);
+    let mut first_error = None;
+    for &version in versions.unwrap_or(&compiled_in) {
+        target.set_version(version);
+        match target.connect(options, timeout).await {
+            Ok(negotiated) => return Ok(negotiated),
+            Err(error) => {
+                let below_the_handshake = is_below_the_handshake(&*error);
+                tracing::debug!(?version, %error, "the CSMS did not accept an offered version");
+                first_error.get_or_insert(error);
+                if below_the_handshake {
+                    break;
+                }
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| "no OCPP version was offered".into()))
+}
+
+/// Whether `error` came from the socket or TLS underneath the WebSocket handshake rather than
+/// from the handshake itself - every such failure surfaces as an [`std::io::Error`] somewhere in
+/// the chain, whether `ocpp-client` returned it directly or `tungstenite` wrapped it.
+fn is_below_the_handshake(error: &(dyn std::error::Error + 'static)) -> bool {
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if error.is::<std::io::Error>() {
+            return true;
+        }
+        current = error.source();
+    }
+    false
+}
+
 /// Runs a full OCPP 2.1 session against `client`, adding the network-profile switching
 /// [`setup`](crate::setup) itself cannot register - it takes no redial target, because a caller
 /// who built their own client owns their own transport.
@@ -349,8 +436,6 @@ fn ocpp_comm_ctrlr_secs(model: &DeviceModel, variable: &str, default: u64) -> u6
 /// `ConnectOptions` is configuring the transport deliberately, and silently overriding their
 /// timeout with a device-model default would be the opposite of helpful.
 fn connection_options_from_device_model<'a>() -> ConnectOptions<'a> {
-    use core::time::Duration;
-
     let model = DeviceModel::new();
     let minimum = ocpp_comm_ctrlr_secs(&model, "RetryBackOffWaitMinimum", 1);
     let repeats = ocpp_comm_ctrlr_secs(&model, "RetryBackOffRepeatTimes", 5).min(16);
@@ -454,7 +539,9 @@ where
         // `ocpp-client` 0.2.0 generated no 2.0.1 action for `SecurityEventNotification` at all;
         // D1 added it upstream and 0.2.2 carries it.
         .security_events(&client)
-        .await;
+        .await
+        // The same one-a-minute ceiling `setup()` puts on `InvalidMessages` reports.
+        .invalid_message_summaries(backoff.clone(), 60);
 
     // C3.1: the same capability gating `setup()` applies - an absent capability means no handler,
     // so the CSMS gets `NotImplemented` rather than a handler backed by hardware that can't.
@@ -530,6 +617,7 @@ where
     let transactions = alloc::sync::Arc::new(crate::transactions::Ocpp1_6TransactionNotifier::new(
         client.clone(),
         counts.clone(),
+        builder.actor(),
     ));
     let remote =
         crate::remote_control::Ocpp1_6RemoteControlHandler::new(client.clone(), counts.clone());
@@ -537,8 +625,11 @@ where
         crate::remote_control::Ocpp1_6TriggerMessageHandler::new(client.clone(), counts.clone());
     let availability =
         crate::availability::Ocpp1_6ChangeAvailabilityHandler::new(client.clone(), counts.clone());
-    let meter =
-        crate::meter_values::Ocpp1_6MeterValuesNotifier::new(client.clone(), counts.clone());
+    let meter = crate::meter_values::Ocpp1_6MeterValuesNotifier::new(
+        client.clone(),
+        counts.clone(),
+        builder.actor(),
+    );
 
     let mut builder = builder
         .provisioning(&client, backoff.clone(), SystemMonotonicClock)
@@ -562,7 +653,10 @@ where
         .configuration(&client)
         .await
         .meter_values(&meter, backoff.clone(), SystemClock)
-        .await;
+        .await
+        // Version-independent: a redial's malformed and undecodable frames are counted on 1.6J
+        // too, so its reports get the same one-a-minute ceiling `setup()` gives 2.x.
+        .invalid_message_summaries(backoff.clone(), 60);
 
     let capabilities = builder.capabilities();
     if capabilities.reservation {
@@ -656,6 +750,21 @@ mod tests {
             "a registered WebSocketPingInterval of 0 must mean no unsolicited traffic"
         );
     }
+
+    /// A refused socket fails every version alike, whether or not a WebSocket library wrapped it;
+    /// a refused subprotocol is the one failure the next version could get past.
+    #[test]
+    fn only_a_socket_failure_ends_the_version_offers() {
+        let refused = || std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        let wrapped: Box<dyn std::error::Error + Send + Sync> =
+            Box::new(tokio_tungstenite::tungstenite::Error::Io(refused()));
+        let rejected: Box<dyn std::error::Error + Send + Sync> =
+            "No OCPP protocol negotiated".into();
+
+        assert!(is_below_the_handshake(&refused()));
+        assert!(is_below_the_handshake(&*wrapped));
+        assert!(!is_below_the_handshake(&*rejected));
+    }
 }
 
 #[cfg(test)]
@@ -155,6 +155,8 @@ mod ocpp_2_1 {
         if !crate::refusal::capability_present(&actor.state().capabilities, "CostUpdated") {
             return Err(crate::refusal::ocpp_2_1_not_supported("CostUpdated"));
         }
+        crate::command_policy::screen_action(actor, "CostUpdated")
+            .map_err(|refused| crate::refusal::ocpp_2_1_refused_by_policy(&refused))?;
         if let Some(transaction_id) = parse_transaction_id(request) {
             handle_cost_updated(actor, transaction_id, request.total_cost).await;
         }
@@ -255,6 +257,8 @@ mod ocpp_2_0_1 {
         if !crate::refusal::capability_present(&actor.state().capabilities, "CostUpdated") {
             return Err(crate::refusal::ocpp_2_0_1_not_supported("CostUpdated"));
         }
+        crate::command_policy::screen_action(actor, "CostUpdated")
+            .map_err(|refused| crate::refusal::ocpp_2_0_1_refused_by_policy(&refused))?;
         if let Some(transaction_id) = parse_transaction_id(request) {
             handle_cost_updated(actor, transaction_id, request.total_cost).await;
         }
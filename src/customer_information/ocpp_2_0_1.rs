@@ -87,14 +87,19 @@ impl Ocpp2_0_1CustomerInformationHandler {
 impl CustomerInformationHandler for Ocpp2_0_1CustomerInformationHandler {
     async fn register_customer_information_handler(
         &self,
-        _actor: ChargePointActor,
+        actor: ChargePointActor,
         queue: CustomerInformationQueue,
     ) {
         self.client
             .on_customer_information(move |request: CustomerInformationRequest, _client| {
+                let actor = actor.clone();
                 let queue = queue.clone();
                 async move {
-                    let outcome = handle_customer_information(&queue, map_request(&request));
+                    let outcome = if crate::command_policy::refuses(&actor, "CustomerInformation") {
+                        CustomerInformationOutcome::Rejected
+                    } else {
+                        handle_customer_information(&queue, map_request(&request))
+                    };
                     Ok(CustomerInformationResponse {
                         custom_data: None,
                         status: map_outcome(outcome),
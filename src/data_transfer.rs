@@ -26,6 +26,7 @@
 //! `docs/ROADMAP.md` §16. 1.6J is unaffected: its `data` is a real `Option<String>` and this
 //! block's 1.6J adapter has always carried the payload.
 
+use crate::actor::ChargePointActor;
 use alloc::boxed::Box;
 use alloc::string::String;
 
@@ -94,12 +95,31 @@ pub trait DataTransferHandler {
 /// [`crate::setup`] - see this module's docs for why.
 #[async_trait::async_trait]
 pub trait DataTransferRegistrar {
-    /// Registers `handler` to answer every future CSMS-initiated `DataTransfer`.
-    async fn register_data_transfer_handler<H>(&self, handler: H)
+    /// Registers `handler` to answer every future CSMS-initiated `DataTransfer` that `actor`'s
+    /// [command policy](crate::command_policy) allows; one it refuses is answered `Rejected`
+    /// without reaching `handler`.
+    async fn register_data_transfer_handler<H>(&self, actor: ChargePointActor, handler: H)
     where
         H: DataTransferHandler + Clone + Send + Sync + 'static;
 }
 
+/// Screens `message` against `actor`'s command policy, then hands it to `handler`. Shared by every
+/// version's registration.
+#[cfg(any(feature = "ocpp_2_1", feature = "ocpp_2_0_1", feature = "ocpp_1_6"))]
+async fn screen_and_handle<H: DataTransferHandler>(
+    actor: &ChargePointActor,
+    handler: &H,
+    message: DataTransferMessage,
+) -> DataTransferResult {
+    if crate::command_policy::refuses(actor, "DataTransfer") {
+        return DataTransferResult {
+            outcome: DataTransferOutcome::Rejected,
+            data: None,
+        };
+    }
+    handler.handle_data_transfer(message).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -154,8 +174,9 @@ mod tests {
 mod ocpp_2_1 {
     use super::{
         DataTransferHandler, DataTransferMessage, DataTransferOutcome, DataTransferRegistrar,
-        DataTransferResult, DataTransferSender,
+        DataTransferResult, DataTransferSender, screen_and_handle,
     };
+    use crate::actor::ChargePointActor;
     use crate::wire::v21::common::DataTransferStatusEnum;
     use crate::wire::v21::{DataTransferRequest, DataTransferResponse};
     use alloc::boxed::Box;
@@ -227,15 +248,16 @@ mod ocpp_2_1 {
 
     #[async_trait::async_trait]
     impl DataTransferRegistrar for OCPP2_1Client {
-        async fn register_data_transfer_handler<H>(&self, handler: H)
+        async fn register_data_transfer_handler<H>(&self, actor: ChargePointActor, handler: H)
         where
             H: DataTransferHandler + Clone + Send + Sync + 'static,
         {
             self.on_data_transfer(move |request, _client| {
+                let actor = actor.clone();
                 let handler = handler.clone();
                 async move {
                     let message = map_request(&request);
-                    let result = handler.handle_data_transfer(message).await;
+                    let result = screen_and_handle(&actor, &handler, message).await;
                     Ok(DataTransferResponse {
                         custom_data: None,
                         // Can't carry `result.data` - see this module's top-level docs.
@@ -328,8 +350,9 @@ mod ocpp_2_1 {
 mod ocpp_2_0_1 {
     use super::{
         DataTransferHandler, DataTransferMessage, DataTransferOutcome, DataTransferRegistrar,
-        DataTransferResult, DataTransferSender,
+        DataTransferResult, DataTransferSender, screen_and_handle,
     };
+    use crate::actor::ChargePointActor;
     use crate::wire::v201::common::DataTransferStatusEnum;
     use crate::wire::v201::{DataTransferRequest, DataTransferResponse};
     use alloc::boxed::Box;
@@ -392,15 +415,16 @@ mod ocpp_2_0_1 {
 
     #[async_trait::async_trait]
     impl DataTransferRegistrar for OCPP2_0_1Client {
-        async fn register_data_transfer_handler<H>(&self, handler: H)
+        async fn register_data_transfer_handler<H>(&self, actor: ChargePointActor, handler: H)
         where
             H: DataTransferHandler + Clone + Send + Sync + 'static,
         {
             self.on_data_transfer(move |request, _client| {
+                let actor = actor.clone();
                 let handler = handler.clone();
                 async move {
                     let message = map_request(&request);
-                    let result = handler.handle_data_transfer(message).await;
+                    let result = screen_and_handle(&actor, &handler, message).await;
                     Ok(DataTransferResponse {
                         custom_data: None,
                         data: None,
@@ -496,8 +520,9 @@ mod ocpp_2_0_1 {
 mod ocpp_1_6 {
     use super::{
         DataTransferHandler, DataTransferMessage, DataTransferOutcome, DataTransferRegistrar,
-        DataTransferResult, DataTransferSender,
+        DataTransferResult, DataTransferSender, screen_and_handle,
     };
+    use crate::actor::ChargePointActor;
     use crate::wire::v16::common::DataTransferResponseStatus;
     use crate::wire::v16::{DataTransferRequest, DataTransferResponse};
     use alloc::boxed::Box;
@@ -563,15 +588,16 @@ mod ocpp_1_6 {
 
     #[async_trait::async_trait]
     impl DataTransferRegistrar for OCPP1_6Client {
-        async fn register_data_transfer_handler<H>(&self, handler: H)
+        async fn register_data_transfer_handler<H>(&self, actor: ChargePointActor, handler: H)
         where
             H: DataTransferHandler + Clone + Send + Sync + 'static,
         {
             self.on_data_transfer(move |request, _client| {
+                let actor = actor.clone();
                 let handler = handler.clone();
                 async move {
                     let message = map_request(&request);
-                    let result = handler.handle_data_transfer(message).await;
+                    let result = screen_and_handle(&actor, &handler, message).await;
                     Ok(DataTransferResponse {
                         data: result.data,
                         status: map_outcome_to_status(result.outcome),
@@ -122,6 +122,9 @@ pub async fn handle_set_der_control(
     if !crate::refusal::capability_present(&state.capabilities, "SetDERControl") {
         return SetDERControlOutcome::NotSupported;
     }
+    if crate::command_policy::refuses(actor, "SetDERControl") {
+        return SetDERControlOutcome::NotSupported;
+    }
     let mut trial = state.der_controls.clone();
     if let Err(rejection) = trial.install(control.clone()) {
         tracing::warn!(?rejection, "refusing a DER control");
@@ -158,6 +161,9 @@ pub async fn handle_clear_der_control(
     if !crate::refusal::capability_present(&state.capabilities, "ClearDERControl") {
         return QueryOutcome::NotSupported;
     }
+    if crate::command_policy::refuses(actor, "ClearDERControl") {
+        return QueryOutcome::NotSupported;
+    }
     if state.der_controls.matching(&query).is_empty() {
         return QueryOutcome::NotFound;
     }
@@ -178,6 +184,9 @@ pub fn handle_get_der_control(
     if !crate::refusal::capability_present(&state.capabilities, "GetDERControl") {
         return (QueryOutcome::NotSupported, Vec::new());
     }
+    if crate::command_policy::refuses(actor, "GetDERControl") {
+        return (QueryOutcome::NotSupported, Vec::new());
+    }
     let matched: Vec<InstalledDERControl> = state
         .der_controls
         .matching(query)
@@ -238,6 +247,9 @@ pub async fn handle_afrr_signal(
     if !crate::refusal::capability_present(&state.capabilities, "AFRRSignal") {
         return AfrrSignalOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "AFRRSignal") {
+        return AfrrSignalOutcome::Rejected;
+    }
     let _ = actor
         .send(ChargePointEvent::AfrrSignalReceived { signal, timestamp })
         .await;
@@ -271,6 +283,9 @@ pub async fn handle_notify_allowed_energy_transfer(
     if !crate::refusal::capability_present(&state.capabilities, "NotifyAllowedEnergyTransfer") {
         return NotifyAllowedEnergyTransferOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "NotifyAllowedEnergyTransfer") {
+        return NotifyAllowedEnergyTransferOutcome::Rejected;
+    }
     if transaction_id.parse::<u64>().is_err() {
         return NotifyAllowedEnergyTransferOutcome::Rejected;
     }
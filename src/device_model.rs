@@ -985,6 +985,47 @@ pub fn capability_gate_events(capabilities: &Capabilities) -> Vec<ChargePointEve
         .collect()
 }
 
+/// Re-registers `variable` with its `Actual` attribute flipped from `ReadOnly` to `ReadWrite` -
+/// how a loop that starts acting on a variable registered with `honoured: false` lifts that
+/// narrowing (CV14). Characteristics and the current value are kept, so a value already restored
+/// from persistence survives. A no-op for an unregistered or already-writable variable.
+pub(crate) async fn make_actual_writable(
+    actor: &ChargePointActor,
+    component: Component,
+    variable: Variable,
+) {
+    let Some(mut definition) = actor
+        .state()
+        .device_model
+        .get(&component, &variable)
+        .cloned()
+    else {
+        return;
+    };
+    let mut changed = false;
+    for attribute in &mut definition.attributes {
+        if attribute.attribute_type == VariableAttributeType::Actual
+            && attribute.mutability == VariableMutability::ReadOnly
+        {
+            attribute.mutability = VariableMutability::ReadWrite;
+            changed = true;
+        }
+    }
+    if !changed {
+        return;
+    }
+    let _ = actor
+        .send(ChargePointEvent::DeviceModel(
+            DeviceModelEvent::VariableRegistered {
+                component,
+                variable,
+                characteristics: definition.characteristics,
+                attributes: definition.attributes,
+            },
+        ))
+        .await;
+}
+
 /// One requested attribute in a `GetVariables` request: which component/variable/attribute-type
 /// to read (OCPP `GetVariableData`, minus wire-only bookkeeping fields).
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1002,7 +1043,8 @@ pub struct GetVariableRequest {
 pub enum GetVariableOutcome {
     /// The attribute exists and is readable; carries its current value.
     Accepted(String),
-    /// The attribute exists, but is `WriteOnly` - there's nothing to read back.
+    /// The attribute exists, but is `WriteOnly` - there's nothing to read back. Also every
+    /// item's answer when the [command policy](crate::command_policy) refuses the request.
     Rejected,
     /// `component` isn't registered in the device model at all.
     UnknownComponent,
@@ -1020,6 +1062,10 @@ pub fn handle_get_variables(
     actor: &ChargePointActor,
     requests: Vec<GetVariableRequest>,
 ) -> Vec<GetVariableOutcome> {
+    // GetVariablesResponse has no top-level status, so a refusal is every item's own `Rejected`.
+    if crate::command_policy::refuses(actor, "GetVariables") {
+        return alloc::vec![GetVariableOutcome::Rejected; requests.len()];
+    }
     let state = actor.state();
     requests
         .iter()
@@ -1112,7 +1158,8 @@ pub enum SetVariableOutcome {
     /// The attribute was written.
     Accepted,
     /// The attribute exists, but is `ReadOnly` or `constant` - it can never be written by
-    /// `SetVariables`.
+    /// `SetVariables`. Also every item's answer when the [command policy](crate::command_policy)
+    /// refuses the request.
     Rejected,
     /// `component` isn't registered in the device model at all.
     UnknownComponent,
@@ -1143,6 +1190,9 @@ pub async fn handle_set_variables<K: KeyStore>(
     requests: Vec<SetVariableRequest>,
     key_store: &K,
 ) -> Vec<SetVariableOutcome> {
+    if crate::command_policy::refuses(actor, "SetVariables") {
+        return alloc::vec![SetVariableOutcome::Rejected; requests.len()];
+    }
     let mut outcomes = Vec::with_capacity(requests.len());
     for request in requests {
         outcomes.push(resolve_and_apply_set(actor, &request, key_store).await);
@@ -1221,6 +1271,20 @@ async fn resolve_and_apply_set<K: KeyStore>(
         );
         return SetVariableOutcome::Rejected;
     }
+    // An action rather than a setting - see its entry in `DEFAULT_VARIABLES`. `false` asks for
+    // nothing and is accepted as the no-op it is.
+    if request.component.name == crate::persistence::SECURITY_LOG_COMPONENT
+        && request.variable.name == "Clear"
+    {
+        if request.value == "true" {
+            let _ = actor
+                .send(ChargePointEvent::SecurityLogClearRequested {
+                    requested_by: "the CSMS".into(),
+                })
+                .await;
+        }
+        return SetVariableOutcome::Accepted;
+    }
     let requires_reboot = attribute.requires_reboot;
 
     let _ = actor
@@ -1447,7 +1511,9 @@ mod tests {
     };
     use crate::actor::ChargePointActor;
     use crate::executor::TokioExecutor;
-    use crate::hardware::{InMemoryStorage, NoKeyStore, SoftKeyStore, SoftwareCrypto};
+    use crate::hardware::{
+        Capabilities, InMemoryStorage, NoKeyStore, SoftKeyStore, SoftwareCrypto,
+    };
     use crate::state::{
         ChargePointEvent, Component, DeviceModelEvent, Variable, VariableAttribute,
         VariableAttributeType, VariableCharacteristics, VariableDataType, VariableMutability,
@@ -2282,11 +2348,63 @@ mod tests {
         }
     }
 
+    /// `SecurityLogCtrlr.Clear` is an action: `true` asks for a clear, nothing is stored, and the
+    /// write-only value never reads back. Until something registers it to act on the clear, there
+    /// is nothing to write (B05.FR.09).
+    #[tokio::test]
+    async fn writing_the_security_log_clear_requests_a_clear_without_storing_anything() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let set = |value: &str| SetVariableRequest {
+            component: component(crate::persistence::SECURITY_LOG_COMPONENT),
+            variable: variable("Clear"),
+            attribute_type: VariableAttributeType::Actual,
+            value: value.into(),
+        };
+
+        assert_eq!(
+            handle_set_variables(&actor, alloc::vec![set("true")], &NoKeyStore).await,
+            alloc::vec![SetVariableOutcome::UnknownComponent]
+        );
+        assert_eq!(actor.state().pending_security_log_clear, None);
+
+        actor
+            .send(ChargePointEvent::DeviceModel(
+                crate::persistence::security_log_clear_registration(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            handle_set_variables(&actor, alloc::vec![set("false")], &NoKeyStore).await,
+            alloc::vec![SetVariableOutcome::Accepted]
+        );
+        assert_eq!(actor.state().pending_security_log_clear, None);
+
+        assert_eq!(
+            handle_set_variables(&actor, alloc::vec![set("true")], &NoKeyStore).await,
+            alloc::vec![SetVariableOutcome::Accepted]
+        );
+        let state = actor.state();
+        assert_eq!(
+            state.pending_security_log_clear.as_deref(),
+            Some("the CSMS")
+        );
+        assert_eq!(
+            state
+                .device_model
+                .get(
+                    &component(crate::persistence::SECURITY_LOG_COMPONENT),
+                    &variable("Clear")
+                )
+                .and_then(|definition| definition.attribute(VariableAttributeType::Actual))
+                .map(|attribute| attribute.value.as_str()),
+            Some("false")
+        );
+    }
+
     /// A `MemberList` must be a subset of its `values_list`, element by element - so one bad
     /// member rejects the whole write rather than being silently dropped. Exercised against
-    /// [`validate_value`] directly because the two variables that carry a `values_list` today
-    /// (`TxStartPoint`/`TxStopPoint`) are registered read-only under CV2.1, so a handler-level
-    /// test would be rejected before it reached the value check.
+    /// [`validate_value`] directly, so the rule is pinned independently of whichever variables
+    /// happen to carry a `values_list`.
     #[test]
     fn a_member_list_is_checked_element_by_element_against_its_allowed_values() {
         let characteristics = VariableCharacteristics {
@@ -2312,6 +2430,60 @@ mod tests {
         assert!(validate_value(&characteristics, "").is_ok());
     }
 
+    /// CV2.2/B05.FR.09: the three points only a hardware signal can mark are refused until the
+    /// binding declares it raises the signal, and then accepted for both transaction points.
+    #[tokio::test]
+    async fn signalled_tx_points_are_accepted_only_once_the_binding_declares_the_signal() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let set = |variable_name: &'static str, value: &'static str| SetVariableRequest {
+            component: component("TxCtrlr"),
+            variable: variable(variable_name),
+            attribute_type: VariableAttributeType::Actual,
+            value: value.into(),
+        };
+        let requests = || {
+            alloc::vec![
+                set("TxStartPoint", "ParkingBayOccupancy"),
+                set("TxStartPoint", "EnergyTransfer"),
+                set("TxStopPoint", "DataSigned"),
+                set("TxStopPoint", "EVConnected,PowerPathClosed"),
+                set("TxStartPoint", "Authorized,Teleported"),
+            ]
+        };
+
+        assert_eq!(
+            handle_set_variables(&actor, requests(), &NoKeyStore).await,
+            alloc::vec![
+                SetVariableOutcome::Rejected,
+                SetVariableOutcome::Rejected,
+                SetVariableOutcome::Rejected,
+                SetVariableOutcome::Accepted,
+                SetVariableOutcome::Rejected,
+            ]
+        );
+
+        actor
+            .send(ChargePointEvent::CapabilitiesDeclared(
+                Capabilities::default()
+                    .with_parking_bay_sensor(true)
+                    .with_energy_transfer_detection(true)
+                    .with_signed_meter_values(true),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            handle_set_variables(&actor, requests(), &NoKeyStore).await,
+            alloc::vec![
+                SetVariableOutcome::Accepted,
+                SetVariableOutcome::Accepted,
+                SetVariableOutcome::Accepted,
+                SetVariableOutcome::Accepted,
+                SetVariableOutcome::Rejected,
+            ]
+        );
+    }
+
     /// CV2.1/B05.FR.09: a variable this build does not act on must be *refused*, not accepted and
     /// ignored. `AuthCtrlr.LocalPreAuthorize` is a good example - OCPP defines it as writable, an
     /// operator setting it would reasonably believe the station now starts sessions from its local
@@ -2886,9 +3058,11 @@ mod ocpp_2_1 {
                         &request,
                     ) {
                         return Err(crate::message_limits::ocpp_2_1_too_large(
+                            &actor,
                             "GetVariables",
                             violation,
-                        ));
+                        )
+                        .await);
                     }
                     let parsed: Vec<GetVariableRequest> = request
                         .get_variable_data
@@ -2933,9 +3107,11 @@ mod ocpp_2_1 {
                         &request,
                     ) {
                         return Err(crate::message_limits::ocpp_2_1_too_large(
+                            &actor,
                             "SetVariables",
                             violation,
-                        ));
+                        )
+                        .await);
                     }
                     let parsed: Vec<SetVariableRequest> = request
                         .set_variable_data
@@ -3247,9 +3423,11 @@ mod ocpp_2_0_1 {
                         &request,
                     ) {
                         return Err(crate::message_limits::ocpp_2_0_1_too_large(
+                            &actor,
                             "GetVariables",
                             violation,
-                        ));
+                        )
+                        .await);
                     }
                     let parsed: Vec<GetVariableRequest> = request
                         .get_variable_data
@@ -3294,9 +3472,11 @@ mod ocpp_2_0_1 {
                         &request,
                     ) {
                         return Err(crate::message_limits::ocpp_2_0_1_too_large(
+                            &actor,
                             "SetVariables",
                             violation,
-                        ));
+                        )
+                        .await);
                     }
                     let parsed: Vec<SetVariableRequest> = request
                         .set_variable_data
@@ -3418,7 +3598,9 @@ mod ocpp_2_0_1 {
 /// entry, so it's left as a real gap rather than modeled incorrectly. A standard key that isn't in
 /// the table - or any non-standard/vendor key - still falls back to the dotted convention,
 /// degrading to `unknownKey`/`NotSupported` exactly as before rather than breaking; extend the
-/// table by adding a `StandardKeyAlias` entry as more of the device model grows in.
+/// table by adding a `StandardKeyAlias` entry as more of the device model grows in. One entry is
+/// not a 1.6J standard key at all: `StopTransactionDataInterval` names 2.x's
+/// `SampledDataCtrlr.TxEndedInterval`, which 1.6J has no key for.
 ///
 /// `GetConfiguration` is answered directly against the device model - it needs the `readonly` bit
 /// [`crate::device_model::GetVariableOutcome`] doesn't carry, and its "return everything" shape
@@ -3634,6 +3816,16 @@ mod ocpp_1_6 {
             variable: "TxEndedMeasurands",
             instance: None,
         },
+        // Not in 1.6J's Appendix: 1.6J samples `StopTxnSampledData` at `MeterValueSampleInterval`,
+        // which 2.x split into `TxUpdatedInterval` and `TxEndedInterval`. This key gives a 1.6J
+        // CSMS the second half of that split under a name in its own style, since the flat
+        // dotted key is not one it would think to look for.
+        StandardKeyAlias {
+            key: "StopTransactionDataInterval",
+            component: "SampledDataCtrlr",
+            variable: "TxEndedInterval",
+            instance: None,
+        },
         // dm_components_vars.csv:79 - 1.6's clock-aligned measurand list.
         StandardKeyAlias {
             key: "MeterValuesAlignedData",
@@ -3999,6 +4191,10 @@ mod ocpp_1_6 {
             self.on_get_configuration(move |request, _client| {
                 let actor = actor.clone();
                 async move {
+                    // Screened under its 2.x counterpart's name, as `ChangeConfiguration` is (through
+                    // `handle_set_variables`). No status field to refuse through, so a CALLERROR.
+                    crate::command_policy::screen_action(&actor, "GetVariables")
+                        .map_err(|refused| crate::refusal::ocpp_1_6_refused_by_policy(&refused))?;
                     let state = actor.state();
                     let (configuration_key, unknown_key) =
                         resolve_get_configuration(&state, request.key.as_deref());
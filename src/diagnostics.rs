@@ -160,7 +160,7 @@ pub fn render_security_log(entries: &[crate::security::SecurityLogEntry]) -> all
 /// `<kind>-<timestamp>.log`, with the timestamp in a filename-safe basic ISO form. Named from the
 /// charge point's clock rather than a counter so two uploads never collide at a destination that
 /// is holding files from a whole fleet.
-fn log_filename<C: Clock>(clock: &C, log_kind: LogKind) -> String {
+pub(crate) fn log_filename<C: Clock>(clock: &C, log_kind: LogKind) -> String {
     let kind = match log_kind {
         LogKind::Security => "security",
         LogKind::Diagnostics => "diagnostics",
@@ -308,6 +308,9 @@ pub async fn handle_get_log<C: Clock>(
     if !crate::refusal::capability_present(&actor.state().capabilities, "GetLog") {
         return GetLogOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "GetLog") {
+        return GetLogOutcome::Rejected;
+    }
     if request.remote_location.is_empty() {
         return GetLogOutcome::Rejected;
     }
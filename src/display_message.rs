@@ -86,6 +86,9 @@ pub async fn handle_set_display_message(
     if !crate::refusal::capability_present(&state.capabilities, "SetDisplayMessage") {
         return SetDisplayMessageOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "SetDisplayMessage") {
+        return SetDisplayMessageOutcome::Rejected;
+    }
     if !format_supported(message.message.format, supported_formats) {
         return SetDisplayMessageOutcome::NotSupportedMessageFormat;
     }
@@ -143,6 +146,9 @@ pub async fn handle_clear_display_message(
     if !crate::refusal::capability_present(&state.capabilities, "ClearDisplayMessage") {
         return ClearDisplayMessageOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "ClearDisplayMessage") {
+        return ClearDisplayMessageOutcome::Rejected;
+    }
     if state.display_messages.get(id).is_none() {
         return ClearDisplayMessageOutcome::Unknown;
     }
@@ -203,6 +209,10 @@ pub fn handle_get_display_messages(
     actor: &ChargePointActor,
     filter: &DisplayMessageFilter,
 ) -> GetDisplayMessagesOutcome {
+    // `Unknown` is also the nearest this status has to a refusal.
+    if crate::command_policy::refuses(actor, "GetDisplayMessages") {
+        return GetDisplayMessagesOutcome::Unknown;
+    }
     let state = actor.state();
     let matched: Vec<DisplayedMessage> = state
         .display_messages
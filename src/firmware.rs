@@ -14,6 +14,8 @@
 //!                      DownloadFailed                              InstallationFailed
 //!                                                                           ↓
 //!                                                                    InstallRebooting
+//!                                                                           ↓ (next boot)
+//!                                                        Installed / InstallVerificationFailed
 //! ```
 //!
 //! Like the log upload in [`crate::diagnostics`], the work happens on a worker task rather than in
@@ -42,18 +44,31 @@
 //! same as before B3.3. See [`crate::hardware::FirmwareVerifier`]'s docs for where the line
 //! between this crate and the integrator is drawn and why.
 //!
+//! # Finishing an update after the reboot
+//!
+//! An install that needs a restart leaves `InstallRebooting` as the last thing the old firmware
+//! can say. Before asking for the reboot, the worker writes a [`PendingFirmwareUpdate`] marker
+//! through a [`PendingUpdateRecorder`] (in practice [`crate::persistence::FirmwareUpdateStore`]):
+//! the request id, the version being replaced and the version being installed, as far as
+//! [`crate::hardware::FirmwareInstaller`] knows them. The next boot finds it, sends its
+//! `BootNotification` with reason `FirmwareUpdate`, and once registered reports how the update
+//! ended through [`report_pending_update`]: `Installed` (raising the `FirmwareUpdated` security
+//! event) if the charge point came up running the installed version, `InstallVerificationFailed`
+//! if it came up running something else - most often the old image, a bootloader having rolled
+//! back.
+//!
 //! # What this block does not do yet
 //!
-//! Reporting `Installed` *after* a reboot remains open - it needs a marker that survives the
-//! restart, the same shape as [`crate::persistence::BootReasonStore`]. Pre-download certificate
-//! chain validation (answering 2.x's `InvalidCertificate`/`RevokedCertificate`, or 1.6J's
-//! `SignedUpdateFirmware` equivalents, synchronously in the accept/reject response) is also not
-//! done: that response goes out before any download starts, and
+//! Pre-download certificate chain validation (answering 2.x's
+//! `InvalidCertificate`/`RevokedCertificate`, or 1.6J's `SignedUpdateFirmware` equivalents,
+//! synchronously in the accept/reject response) is not done: that response goes out before any
+//! download starts, and
 //! [`crate::hardware::FirmwareVerifier`] is scoped to checking the *downloaded image*, not to
 //! chain-validating a certificate the charge point has not yet fetched anything to check it
 //! against.
 
 use alloc::boxed::Box;
+use alloc::format;
 use alloc::string::String;
 use chrono::{DateTime, Utc};
 use core::cell::RefCell;
@@ -64,9 +79,12 @@ use crate::actor::ChargePointActor;
 use crate::clock::Clock;
 use crate::hardware::{
     FileTransfer, FirmwareInstallOutcome, FirmwareInstaller, FirmwareVerificationOutcome,
-    FirmwareVerifier, TransferProgress,
+    FirmwareVerifier, Storage, TransferProgress,
 };
+pub use crate::persistence::PendingFirmwareUpdate;
+use crate::persistence::{FIRMWARE_UPDATE_SCHEMA_VERSION, FirmwareUpdateStore};
 use crate::provisioning::Backoff;
+use crate::replay_protection::ReplayGuard;
 use crate::security::report_security_event;
 use crate::state::{ChargePointEvent, EvseEvent, SecurityEvent, SecurityEventType};
 use crate::sync::Chan;
@@ -152,6 +170,10 @@ pub enum FirmwareStatus {
     InstallationFailed,
     /// The image is staged and the charge point is about to restart to run it (L01.FR.15).
     InstallRebooting,
+    /// The charge point restarted after `InstallRebooting` but is not running the version that
+    /// was installed - typically a bootloader rolled back to the previous image. Only ever
+    /// reported on the boot after an update, by [`report_pending_update`].
+    InstallVerificationFailed,
     /// The downloaded image's signature was checked against `signingCertificate` and verified
     /// (B3.3). Only ever reported for a signed update, immediately before installation resumes.
     SignatureVerified,
@@ -230,11 +252,39 @@ pub trait SignedUpdateFirmwareHandler {
     }
 }
 
+/// Durably records the update a reboot is about to finish, so the next boot can report how it
+/// ended - see the module docs. Implemented for [`FirmwareUpdateStore`]; type-erased so
+/// [`FirmwareUpdateState`] can hold one without becoming generic over its storage.
+#[async_trait::async_trait]
+pub trait PendingUpdateRecorder: Send + Sync {
+    /// Writes `update` before the reboot. Returns whether it reached storage.
+    async fn record(&self, update: &PendingFirmwareUpdate) -> bool;
+}
+
+#[async_trait::async_trait]
+impl<S: Storage + Send + Sync> PendingUpdateRecorder for FirmwareUpdateStore<S> {
+    async fn record(&self, update: &PendingFirmwareUpdate) -> bool {
+        self.save(update).await
+    }
+}
+
 /// Tracks which update is current and what was last reported, so a superseded update can be
 /// dropped and a `TriggerMessage` can be answered (L01.FR.25/26).
-#[derive(Debug)]
 pub struct FirmwareUpdateState {
     inner: BlockingMutex<CriticalSectionRawMutex, RefCell<FirmwareUpdateInner>>,
+    recorder: Option<alloc::sync::Arc<dyn PendingUpdateRecorder>>,
+    // Request ids of updates that reached `Installed`, so an `UpdateFirmware` naming one again
+    // is reported as a replay - see `handle_update_firmware`.
+    installed: ReplayGuard<i64>,
+}
+
+impl core::fmt::Debug for FirmwareUpdateState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FirmwareUpdateState")
+            .field("inner", &self.inner)
+            .field("records_pending_updates", &self.recorder.is_some())
+            .finish()
+    }
 }
 
 #[derive(Debug)]
@@ -254,6 +304,8 @@ impl Default for FirmwareUpdateState {
                 last_status: FirmwareStatus::Idle,
                 last_request_id: None,
             })),
+            recorder: None,
+            installed: ReplayGuard::new(),
         }
     }
 }
@@ -264,6 +316,15 @@ impl FirmwareUpdateState {
         Self::default()
     }
 
+    /// A state with no update in progress that records a [`PendingFirmwareUpdate`] through
+    /// `recorder` before every reboot an install asks for.
+    pub fn with_recorder(recorder: alloc::sync::Arc<dyn PendingUpdateRecorder>) -> Self {
+        Self {
+            recorder: Some(recorder),
+            ..Self::default()
+        }
+    }
+
     /// What a `TriggerMessage` for `FirmwareStatusNotification` should report: `Idle` once the
     /// last update finished installing (L01.FR.25), otherwise the last status sent (L01.FR.26).
     ///
@@ -294,6 +355,12 @@ impl FirmwareUpdateState {
     }
 
     fn record(&self, request_id: Option<i64>, status: FirmwareStatus) {
+        if status == FirmwareStatus::Installed
+            && let Some(request_id) = request_id
+            && !self.installed.contains(&request_id)
+        {
+            self.installed.record(request_id);
+        }
         self.inner.lock(|inner| {
             let mut inner = inner.borrow_mut();
             inner.last_status = status;
@@ -302,6 +369,7 @@ impl FirmwareUpdateState {
                 status,
                 FirmwareStatus::Installed
                     | FirmwareStatus::InstallationFailed
+                    | FirmwareStatus::InstallVerificationFailed
                     | FirmwareStatus::DownloadFailed
                     | FirmwareStatus::InvalidSignature
             ) {
@@ -333,6 +401,11 @@ impl Default for FirmwareUpdateQueue {
 }
 
 /// Decides what to answer an `UpdateFirmware` with, and queues the update if accepted.
+///
+/// A `requestId` that `state` already saw reach `Installed` is additionally reported as
+/// [`SecurityEventType::AttemptedReplayAttacks`]: an old request coming back, which would
+/// otherwise quietly reinstall firmware the station already runs. The answer is unchanged - see
+/// [`crate::replay_protection`] for why.
 #[tracing::instrument(skip_all)]
 pub async fn handle_update_firmware(
     actor: &ChargePointActor,
@@ -346,6 +419,26 @@ pub async fn handle_update_firmware(
     if request.location.is_empty() {
         return UpdateFirmwareOutcome::Rejected;
     }
+    let command = crate::command_policy::InboundCommand::UpdateFirmware { request: &request };
+    if crate::command_policy::screen(actor, &command).is_err() {
+        return UpdateFirmwareOutcome::Rejected;
+    }
+    if let Some(request_id) = request.request_id
+        && state.installed.contains(&request_id)
+    {
+        report_security_event(
+            actor,
+            SecurityEvent {
+                event_type: SecurityEventType::AttemptedReplayAttacks,
+                tech_info: Some(format!(
+                    "UpdateFirmware repeated requestId {request_id} after it was installed \
+                     (location {})",
+                    request.location
+                )),
+            },
+        )
+        .await;
+    }
 
     let (ticket, superseded) = state.claim();
     updates.channel.send((ticket, request));
@@ -515,8 +608,22 @@ async fn run_one_update<T, I, N, C, B, V>(
                 set_all_evses(actor, EvseEvent::SetAvailable).await;
             }
             report(FirmwareStatus::Installed).await;
+            report_firmware_updated(actor).await;
         }
         Ok(FirmwareInstallOutcome::RebootRequired) => {
+            // The marker goes down first: once the reboot is under way nothing can write it, and
+            // without it the next boot cannot tell how this update ended.
+            if let Some(recorder) = &state.recorder {
+                recorder
+                    .record(&PendingFirmwareUpdate {
+                        schema_version: FIRMWARE_UPDATE_SCHEMA_VERSION,
+                        request_id: request.request_id,
+                        previous_version: installer.running_version(),
+                        expected_version: installer.installed_version(),
+                        started_at: clock.now(),
+                    })
+                    .await;
+            }
             // L01.FR.15: told *before* the reboot, because afterwards there is no process left to
             // tell anyone. Availability is deliberately left withheld - the charge point is about
             // to restart, and re-opening it for a session that a reboot would kill is worse than
@@ -544,6 +651,73 @@ async fn run_one_update<T, I, N, C, B, V>(
     }
 }
 
+/// Reports how the update `pending` describes ended, on the first boot after the reboot it asked
+/// for: `Installed` if `running_version` is the version that was installed, or failing that no
+/// longer the one it replaced; `InstallVerificationFailed` otherwise. With no versions to compare
+/// the reboot itself is taken as success - the same assumption `InstallRebooting` always left a
+/// CSMS to make.
+///
+/// Call this once the `BootNotification` has been accepted: OCPP allows nothing else to be sent
+/// before then.
+pub async fn report_pending_update<N: FirmwareStatusNotifier>(
+    actor: &ChargePointActor,
+    state: &FirmwareUpdateState,
+    notifier: &N,
+    pending: &PendingFirmwareUpdate,
+    running_version: Option<&str>,
+) {
+    let status = post_reboot_status(pending, running_version);
+    if status == FirmwareStatus::InstallVerificationFailed {
+        tracing::warn!(
+            running = running_version,
+            expected = pending.expected_version.as_deref(),
+            "the charge point did not come up running the firmware it installed"
+        );
+    }
+    state.record(pending.request_id, status);
+    if let Err(err) = notifier
+        .notify_firmware_status(pending.request_id, status)
+        .await
+    {
+        tracing::warn!(error = %err, ?status, "failed to report a firmware status");
+    }
+    if status == FirmwareStatus::Installed {
+        report_firmware_updated(actor).await;
+    }
+}
+
+fn post_reboot_status(
+    pending: &PendingFirmwareUpdate,
+    running_version: Option<&str>,
+) -> FirmwareStatus {
+    let installed = match (
+        running_version,
+        pending.expected_version.as_deref(),
+        pending.previous_version.as_deref(),
+    ) {
+        (Some(running), Some(expected), _) => running == expected,
+        (Some(running), None, Some(previous)) => running != previous,
+        _ => true,
+    };
+    if installed {
+        FirmwareStatus::Installed
+    } else {
+        FirmwareStatus::InstallVerificationFailed
+    }
+}
+
+/// Raises the `FirmwareUpdated` security event the spec lists for a completed update.
+async fn report_firmware_updated(actor: &ChargePointActor) {
+    report_security_event(
+        actor,
+        SecurityEvent {
+            event_type: SecurityEventType::FirmwareUpdated,
+            tech_info: None,
+        },
+    )
+    .await;
+}
+
 /// Whether `at` is still in the future by the charge point's clock.
 ///
 /// An unsynchronized clock treats every schedule as **due now** rather than waiting: a charge
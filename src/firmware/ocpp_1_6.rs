@@ -103,6 +103,9 @@ fn map_request(request: &UpdateFirmwareRequest) -> FirmwareUpdateRequest {
 /// - `InvalidSignature` → `DownloadFailed`: same reachability note. `DownloadFailed` is the
 ///   nearest true meaning 1.6J has for "this image cannot be used" - the closest existing status
 ///   is not `InstallationFailed`, which would claim an installation was attempted.
+/// - `InstallVerificationFailed` → `InstallationFailed`: an installation *was* attempted, and the
+///   charge point is not running the image it installed - which is what a 1.6J CSMS takes
+///   `InstallationFailed` to mean.
 pub(super) fn wire_status(status: FirmwareStatus) -> FirmwareStatusNotificationRequestStatus {
     match status {
         FirmwareStatus::Idle | FirmwareStatus::DownloadScheduled => {
@@ -119,7 +122,7 @@ pub(super) fn wire_status(status: FirmwareStatus) -> FirmwareStatusNotificationR
             FirmwareStatusNotificationRequestStatus::Installing
         }
         FirmwareStatus::Installed => FirmwareStatusNotificationRequestStatus::Installed,
-        FirmwareStatus::InstallationFailed => {
+        FirmwareStatus::InstallationFailed | FirmwareStatus::InstallVerificationFailed => {
             FirmwareStatusNotificationRequestStatus::InstallationFailed
         }
     }
@@ -178,6 +181,9 @@ pub(super) fn signed_wire_status(
         FirmwareStatus::InstallRebooting => {
             SignedFirmwareStatusNotificationRequestStatus::InstallRebooting
         }
+        FirmwareStatus::InstallVerificationFailed => {
+            SignedFirmwareStatusNotificationRequestStatus::InstallVerificationFailed
+        }
         FirmwareStatus::SignatureVerified => {
             SignedFirmwareStatusNotificationRequestStatus::SignatureVerified
         }
@@ -404,6 +410,11 @@ mod tests {
             wire_status(FirmwareStatus::InvalidSignature),
             FirmwareStatusNotificationRequestStatus::DownloadFailed
         );
+        // The image was installed but is not what came back up after the reboot.
+        assert_eq!(
+            wire_status(FirmwareStatus::InstallVerificationFailed),
+            FirmwareStatusNotificationRequestStatus::InstallationFailed
+        );
     }
 
     #[test]
@@ -517,6 +528,10 @@ mod tests {
                 FirmwareStatus::InstallRebooting,
                 SignedFirmwareStatusNotificationRequestStatus::InstallRebooting,
             ),
+            (
+                FirmwareStatus::InstallVerificationFailed,
+                SignedFirmwareStatusNotificationRequestStatus::InstallVerificationFailed,
+            ),
             (
                 FirmwareStatus::SignatureVerified,
                 SignedFirmwareStatusNotificationRequestStatus::SignatureVerified,
@@ -69,6 +69,7 @@ pub(super) fn wire_status(status: FirmwareStatus) -> FirmwareStatusEnum {
         FirmwareStatus::Installed => FirmwareStatusEnum::Installed,
         FirmwareStatus::InstallationFailed => FirmwareStatusEnum::InstallationFailed,
         FirmwareStatus::InstallRebooting => FirmwareStatusEnum::InstallRebooting,
+        FirmwareStatus::InstallVerificationFailed => FirmwareStatusEnum::InstallVerificationFailed,
         // B3.3.
         FirmwareStatus::SignatureVerified => FirmwareStatusEnum::SignatureVerified,
         FirmwareStatus::InvalidSignature => FirmwareStatusEnum::InvalidSignature,
@@ -272,6 +273,10 @@ mod tests {
                 FirmwareStatus::InstallRebooting,
                 FirmwareStatusEnum::InstallRebooting,
             ),
+            (
+                FirmwareStatus::InstallVerificationFailed,
+                FirmwareStatusEnum::InstallVerificationFailed,
+            ),
             (
                 FirmwareStatus::SignatureVerified,
                 FirmwareStatusEnum::SignatureVerified,
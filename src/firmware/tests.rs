@@ -72,6 +72,8 @@ impl FileTransfer for FakeTransfer {
 struct FakeInstaller {
     outcome: StdMutex<Result<FirmwareInstallOutcome, ()>>,
     installs: StdMutex<u32>,
+    running_version: Option<String>,
+    installed_version: Option<String>,
 }
 
 impl FakeInstaller {
@@ -79,8 +81,16 @@ impl FakeInstaller {
         Self {
             outcome: StdMutex::new(outcome),
             installs: StdMutex::new(0),
+            running_version: None,
+            installed_version: None,
         }
     }
+
+    fn with_versions(mut self, running: &str, installed: &str) -> Self {
+        self.running_version = Some(running.into());
+        self.installed_version = Some(installed.into());
+        self
+    }
 }
 
 #[async_trait::async_trait]
@@ -91,6 +101,14 @@ impl FirmwareInstaller for FakeInstaller {
         *self.installs.lock().unwrap() += 1;
         (*self.outcome.lock().unwrap()).map_err(|()| NoFirmwareInstallerError)
     }
+
+    fn running_version(&self) -> Option<String> {
+        self.running_version.clone()
+    }
+
+    fn installed_version(&self) -> Option<String> {
+        self.installed_version.clone()
+    }
 }
 
 struct FakeVerifier {
@@ -493,6 +511,134 @@ async fn a_reboot_is_announced_before_it_happens_because_afterwards_nothing_can_
     );
 }
 
+#[tokio::test]
+async fn a_reboot_records_the_pending_update_before_it_happens() {
+    use crate::hardware::InMemoryStorage;
+    use crate::persistence::FirmwareUpdateStore;
+
+    let actor = actor_with_firmware().await;
+    let updates = FirmwareUpdateQueue::new();
+    let storage = Arc::new(InMemoryStorage::new());
+    let state = Arc::new(FirmwareUpdateState::with_recorder(Arc::new(
+        FirmwareUpdateStore::new(storage.clone()),
+    )));
+
+    handle_update_firmware(&actor, &updates, &state, request()).await;
+    run(
+        &actor,
+        updates,
+        state,
+        Arc::new(FakeTransfer::default()),
+        Arc::new(
+            FakeInstaller::new(Ok(FirmwareInstallOutcome::RebootRequired))
+                .with_versions("1.0.0", "1.1.0"),
+        ),
+        Arc::new(RecordingNotifier::default()),
+        FixedClock(at(0)),
+    )
+    .await;
+
+    assert_eq!(
+        FirmwareUpdateStore::new(storage).load().await,
+        Some(PendingFirmwareUpdate {
+            schema_version: FIRMWARE_UPDATE_SCHEMA_VERSION,
+            request_id: Some(9),
+            previous_version: Some("1.0.0".into()),
+            expected_version: Some("1.1.0".into()),
+            started_at: at(0),
+        })
+    );
+}
+
+fn pending(previous: Option<&str>, expected: Option<&str>) -> PendingFirmwareUpdate {
+    PendingFirmwareUpdate {
+        schema_version: FIRMWARE_UPDATE_SCHEMA_VERSION,
+        request_id: Some(9),
+        previous_version: previous.map(Into::into),
+        expected_version: expected.map(Into::into),
+        started_at: at(0),
+    }
+}
+
+#[tokio::test]
+async fn coming_back_up_on_the_installed_version_reports_installed_and_firmware_updated() {
+    let actor = actor_with_firmware().await;
+    let mut security_events = actor.subscribe_security_events();
+    let state = FirmwareUpdateState::new();
+    let notifier = RecordingNotifier::default();
+
+    report_pending_update(
+        &actor,
+        &state,
+        &notifier,
+        &pending(Some("1.0.0"), Some("1.1.0")),
+        Some("1.1.0"),
+    )
+    .await;
+
+    assert_eq!(
+        *notifier.seen.lock().unwrap(),
+        alloc::vec![(Some(9), FirmwareStatus::Installed)]
+    );
+    // The update is finished, so a trigger now reports Idle (L01.FR.25).
+    assert_eq!(state.triggered_status(), (None, FirmwareStatus::Idle));
+    let event = security_events.recv().await.unwrap();
+    assert_eq!(event.event_type, SecurityEventType::FirmwareUpdated);
+}
+
+#[tokio::test]
+async fn coming_back_up_on_the_previous_version_reports_install_verification_failed() {
+    let actor = actor_with_firmware().await;
+    let state = FirmwareUpdateState::new();
+    let notifier = RecordingNotifier::default();
+
+    // A bootloader that rolled back: the charge point is running what it ran before the update.
+    report_pending_update(
+        &actor,
+        &state,
+        &notifier,
+        &pending(Some("1.0.0"), Some("1.1.0")),
+        Some("1.0.0"),
+    )
+    .await;
+
+    assert_eq!(
+        *notifier.seen.lock().unwrap(),
+        alloc::vec![(Some(9), FirmwareStatus::InstallVerificationFailed)]
+    );
+    assert_eq!(
+        state.triggered_status(),
+        (Some(9), FirmwareStatus::InstallVerificationFailed)
+    );
+}
+
+#[test]
+fn the_post_reboot_verdict_uses_whichever_versions_are_known() {
+    // The installed version is the strongest evidence: anything else running is a failure.
+    assert_eq!(
+        post_reboot_status(&pending(None, Some("1.1.0")), Some("1.2.0")),
+        FirmwareStatus::InstallVerificationFailed
+    );
+    // Without it, no longer running the replaced version is the next best.
+    assert_eq!(
+        post_reboot_status(&pending(Some("1.0.0"), None), Some("1.1.0")),
+        FirmwareStatus::Installed
+    );
+    assert_eq!(
+        post_reboot_status(&pending(Some("1.0.0"), None), Some("1.0.0")),
+        FirmwareStatus::InstallVerificationFailed
+    );
+    // Nothing to compare: the reboot itself is taken as success.
+    assert_eq!(
+        post_reboot_status(&pending(Some("1.0.0"), Some("1.1.0")), None),
+        FirmwareStatus::Installed
+    );
+    assert_eq!(
+        post_reboot_status(&pending(None, None), Some("1.1.0")),
+        FirmwareStatus::Installed
+    );
+}
+
 #[tokio::test]
 async fn a_failed_install_reports_it_and_puts_the_charge_point_back_in_service() {
     let actor = actor_with_firmware().await;
@@ -670,6 +816,22 @@ fn a_triggered_status_is_idle_only_once_the_last_update_finished_installing() {
     assert_eq!(state.triggered_status(), (None, FirmwareStatus::Idle));
 }
 
+#[tokio::test]
+async fn repeating_the_request_id_of_an_installed_update_is_reported_but_still_accepted() {
+    let actor = actor_with_firmware().await;
+    let mut security_events = actor.subscribe_security_events();
+    let updates = FirmwareUpdateQueue::new();
+    let state = FirmwareUpdateState::new();
+    state.record(Some(9), FirmwareStatus::Installed);
+
+    let outcome = handle_update_firmware(&actor, &updates, &state, request()).await;
+
+    assert_eq!(outcome, UpdateFirmwareOutcome::Accepted);
+    let event = security_events.recv().await.unwrap();
+    assert_eq!(event.event_type, SecurityEventType::AttemptedReplayAttacks);
+    assert!(event.tech_info.unwrap().contains("requestId 9"));
+}
+
 // --- B3.3: signature verification ------------------------------------------------------------
 
 #[tokio::test]
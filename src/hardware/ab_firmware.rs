@@ -0,0 +1,442 @@
+//! A [`FirmwareInstaller`] for boards with two firmware slots and a bootloader that can boot one
+//! of them on trial - the A/B scheme (MCUboot's test/confirm, U-Boot's `bootcount`, RAUC's
+//! mark-good/mark-bad) most charge point controllers already ship with.
+//!
+//! # The shape of an A/B update
+//!
+//! The running image is never touched. [`AbFirmwareInstaller::install`] copies the downloaded
+//! image into the **inactive** slot and marks it pending; the reboot that follows boots it on
+//! trial. Whether it stays is decided by [`AbFirmwareInstaller::supervise_trial_boot`] on that
+//! boot: the first `BootNotification` the CSMS accepts confirms it, and a
+//! [rollback window](AbFirmwareInstaller::with_rollback_window_secs) that closes first reverts it
+//! and reboots back into the image that was running before.
+//!
+//! Registration is the bar because it is the one thing a charge point is useless without: an image
+//! that boots, drives the contactors and then never reaches its CSMS has still broken the station,
+//! and it can no longer be updated remotely to fix that. The bootloader remains the backstop for an
+//! image that does not even get that far - one that crashes or hangs before the window closes is
+//! reverted by the next reset, because it was never confirmed.
+//!
+//! On the boot after a rollback, `crate::firmware` sees the pending-update marker and the old
+//! version running, and reports `InstallVerificationFailed` - see that module's docs.
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use core::time::Duration;
+
+use super::firmware::{FirmwareImage, FirmwareInstallOutcome, FirmwareInstaller};
+use crate::actor::ChargePointActor;
+use crate::clock::MonotonicClock;
+use crate::provisioning::Backoff;
+use crate::state::{ChargePointEvent, RegistrationStatus, ResetKind, ResetTarget};
+
+/// How long a trial image has to get its first `BootNotification` accepted before it is reverted,
+/// unless [`AbFirmwareInstaller::with_rollback_window_secs`] says otherwise.
+///
+/// Ten minutes: long enough to ride out a CSMS that answers `Pending` for a while or a cellular
+/// modem that is slow to attach, short enough that a broken image does not keep a station offline
+/// for long.
+pub const DEFAULT_ROLLBACK_WINDOW_SECS: u32 = 600;
+
+/// How many bytes of the image are copied per read/write.
+const COPY_CHUNK_BYTES: usize = 1024;
+
+/// How often [`AbFirmwareInstaller::supervise_trial_boot`] checks the registration status.
+const TRIAL_POLL_SECS: u32 = 1;
+
+/// Whether the running image is confirmed or still on trial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlotState {
+    /// The running image is permanent: the bootloader will keep booting it.
+    Confirmed,
+    /// The running image was booted on trial from a pending mark and has not been confirmed. The
+    /// next reset goes back to the other slot unless [`FirmwareSlots::confirm`] is called first.
+    Trial,
+}
+
+/// A pair of firmware slots and the bootloader state that decides which one boots - the primitives
+/// [`AbFirmwareInstaller`] needs, and nothing more.
+///
+/// Implemented by the integrator over their flash driver and bootloader. Every method that talks
+/// to flash or to the bootloader is fallible and must report a failure as an `Err` rather than
+/// panicking, like every other hardware binding in this crate.
+#[async_trait::async_trait]
+pub trait FirmwareSlots {
+    /// The error type returned by a failed slot operation.
+    type Error: core::error::Error + Send + Sync + 'static;
+
+    /// Writes `data` into the inactive slot at `offset`. A write at offset `0` starts a new image:
+    /// the implementation prepares the slot (erasing it, on NOR flash) as it needs to. Writes
+    /// arrive in order and never overlap.
+    async fn write_inactive(&self, offset: u64, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Marks the image in the inactive slot to be booted, on trial, by the next reset.
+    async fn mark_pending(&self) -> Result<(), Self::Error>;
+
+    /// Whether the running image is confirmed or on trial.
+    async fn state(&self) -> Result<SlotState, Self::Error>;
+
+    /// Makes the running trial image permanent.
+    async fn confirm(&self) -> Result<(), Self::Error>;
+
+    /// Makes the next reset boot the confirmed image: undoes a pending mark, or abandons the
+    /// running trial image.
+    async fn revert(&self) -> Result<(), Self::Error>;
+
+    /// The version of the running image, if the slot can tell. Forwarded as
+    /// [`FirmwareInstaller::running_version`].
+    fn running_version(&self) -> Option<String> {
+        None
+    }
+
+    /// The version of the image in the inactive slot, if the slot can tell. Forwarded as
+    /// [`FirmwareInstaller::installed_version`].
+    fn inactive_version(&self) -> Option<String> {
+        None
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: FirmwareSlots + Send + Sync + ?Sized> FirmwareSlots for alloc::sync::Arc<T> {
+    type Error = T::Error;
+
+    async fn write_inactive(&self, offset: u64, data: &[u8]) -> Result<(), Self::Error> {
+        (**self).write_inactive(offset, data).await
+    }
+
+    async fn mark_pending(&self) -> Result<(), Self::Error> {
+        (**self).mark_pending().await
+    }
+
+    async fn state(&self) -> Result<SlotState, Self::Error> {
+        (**self).state().await
+    }
+
+    async fn confirm(&self) -> Result<(), Self::Error> {
+        (**self).confirm().await
+    }
+
+    async fn revert(&self) -> Result<(), Self::Error> {
+        (**self).revert().await
+    }
+
+    fn running_version(&self) -> Option<String> {
+        (**self).running_version()
+    }
+
+    fn inactive_version(&self) -> Option<String> {
+        (**self).inactive_version()
+    }
+}
+
+/// The error type of [`AbFirmwareInstaller`].
+#[derive(Debug)]
+pub enum AbFirmwareInstallerError<I, S> {
+    /// The downloaded image could not be read.
+    Image(I),
+    /// A slot operation failed.
+    Slots(S),
+    /// The downloaded image is empty. Marking an empty slot pending would brick the trial boot,
+    /// so the install is refused instead.
+    EmptyImage,
+}
+
+impl<I: core::fmt::Display, S: core::fmt::Display> core::fmt::Display
+    for AbFirmwareInstallerError<I, S>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Image(error) => write!(f, "reading the firmware image failed: {error}"),
+            Self::Slots(error) => write!(f, "firmware slot operation failed: {error}"),
+            Self::EmptyImage => f.write_str("the firmware image is empty"),
+        }
+    }
+}
+
+impl<I, S> core::error::Error for AbFirmwareInstallerError<I, S>
+where
+    I: core::fmt::Debug + core::fmt::Display,
+    S: core::fmt::Debug + core::fmt::Display,
+{
+}
+
+/// What [`AbFirmwareInstaller::supervise_trial_boot`] decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrialOutcome {
+    /// The running image was already confirmed; there was nothing to decide.
+    NotOnTrial,
+    /// The CSMS accepted registration within the window and the trial image is now permanent.
+    Confirmed,
+    /// The window closed first: the trial image was reverted and a reboot requested.
+    RolledBack,
+}
+
+/// A [`FirmwareInstaller`] over a [`FirmwareSlots`] pair - see the module docs.
+#[derive(Debug)]
+pub struct AbFirmwareInstaller<I, S> {
+    image: I,
+    slots: S,
+    rollback_window_secs: u32,
+}
+
+impl<I, S> AbFirmwareInstaller<I, S>
+where
+    I: FirmwareImage,
+    S: FirmwareSlots,
+{
+    /// An installer copying from `image` (where `FileTransfer` put the download) into `slots`,
+    /// with a [`DEFAULT_ROLLBACK_WINDOW_SECS`] rollback window.
+    pub fn new(image: I, slots: S) -> Self {
+        Self {
+            image,
+            slots,
+            rollback_window_secs: DEFAULT_ROLLBACK_WINDOW_SECS,
+        }
+    }
+
+    /// How long a trial image has to get registration accepted before it is reverted.
+    pub fn with_rollback_window_secs(mut self, secs: u32) -> Self {
+        self.rollback_window_secs = secs;
+        self
+    }
+
+    /// Decides the fate of a trial image, on the boot it is running in: waits for the CSMS to
+    /// accept a `BootNotification` and confirms it, or, once the rollback window closes without
+    /// that, reverts it and requests a reboot back into the previous image.
+    ///
+    /// The reboot goes through the ordinary `Reset` path as an `OnIdle` reset, so a session a
+    /// driver started offline in the meantime finishes before the charge point restarts. Returns
+    /// straight away with [`TrialOutcome::NotOnTrial`] on any boot that is not a trial.
+    ///
+    /// A slot error is returned as-is. An unconfirmed image is still reverted by the bootloader on
+    /// the next reset, so a failure here never makes a bad image permanent.
+    pub async fn supervise_trial_boot<B, M>(
+        &self,
+        actor: &ChargePointActor,
+        backoff: &B,
+        monotonic: &M,
+    ) -> Result<TrialOutcome, S::Error>
+    where
+        B: Backoff,
+        M: MonotonicClock,
+    {
+        if self.slots.state().await? != SlotState::Trial {
+            return Ok(TrialOutcome::NotOnTrial);
+        }
+        let window = Duration::from_secs(u64::from(self.rollback_window_secs));
+        let started = monotonic.now();
+        loop {
+            if actor.state().registration == Some(RegistrationStatus::Accepted) {
+                self.slots.confirm().await?;
+                return Ok(TrialOutcome::Confirmed);
+            }
+            if monotonic.now().duration_since(started) >= window {
+                tracing::warn!(
+                    window_secs = self.rollback_window_secs,
+                    "the trial firmware never registered with the CSMS; rolling back"
+                );
+                self.slots.revert().await?;
+                let _ = actor
+                    .send(ChargePointEvent::ResetRequested {
+                        target: ResetTarget::ChargePoint,
+                        kind: ResetKind::OnIdle,
+                    })
+                    .await;
+                return Ok(TrialOutcome::RolledBack);
+            }
+            backoff.wait(TRIAL_POLL_SECS).await;
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<I, S> FirmwareInstaller for AbFirmwareInstaller<I, S>
+where
+    I: FirmwareImage + Send + Sync,
+    S: FirmwareSlots + Send + Sync,
+    I::Error: core::fmt::Debug,
+    S::Error: core::fmt::Debug,
+{
+    type Error = AbFirmwareInstallerError<I::Error, S::Error>;
+
+    /// Copies the image into the inactive slot and marks it pending. Always
+    /// [`FirmwareInstallOutcome::RebootRequired`] on success: the new image only runs after a
+    /// reset.
+    async fn install(&self) -> Result<FirmwareInstallOutcome, Self::Error> {
+        let mut buf = vec![0u8; COPY_CHUNK_BYTES];
+        let mut offset = 0u64;
+        loop {
+            let read = self
+                .image
+                .read_image(offset, &mut buf)
+                .await
+                .map_err(AbFirmwareInstallerError::Image)?;
+            let Some(chunk) = buf.get(..read).filter(|chunk| !chunk.is_empty()) else {
+                break;
+            };
+            self.slots
+                .write_inactive(offset, chunk)
+                .await
+                .map_err(AbFirmwareInstallerError::Slots)?;
+            offset += read as u64;
+        }
+        if offset == 0 {
+            return Err(AbFirmwareInstallerError::EmptyImage);
+        }
+        self.slots
+            .mark_pending()
+            .await
+            .map_err(AbFirmwareInstallerError::Slots)?;
+        Ok(FirmwareInstallOutcome::RebootRequired)
+    }
+
+    fn running_version(&self) -> Option<String> {
+        self.slots.running_version()
+    }
+
+    fn installed_version(&self) -> Option<String> {
+        self.slots.inactive_version()
+    }
+}
+
+/// A `std`-only in-memory [`FirmwareSlots`] pair with a simulated bootloader, for tests and for
+/// exercising an update end to end on a desktop. [`Self::reboot`] stands in for the reset: it
+/// boots a pending image on trial, and sends an unconfirmed trial image back to the other slot,
+/// as an A/B bootloader does.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct InMemoryFirmwareSlots {
+    inner: std::sync::Mutex<SlotPair>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct SlotPair {
+    images: [alloc::vec::Vec<u8>; 2],
+    versions: [Option<String>; 2],
+    active: usize,
+    pending: bool,
+    trial: bool,
+}
+
+#[cfg(feature = "std")]
+impl InMemoryFirmwareSlots {
+    /// A pair whose active slot holds `image`, confirmed, reporting `version`; the other slot is
+    /// empty.
+    pub fn new(image: &[u8], version: &str) -> Self {
+        Self {
+            inner: std::sync::Mutex::new(SlotPair {
+                images: [image.into(), alloc::vec::Vec::new()],
+                versions: [Some(version.into()), None],
+                active: 0,
+                pending: false,
+                trial: false,
+            }),
+        }
+    }
+
+    /// Sets the version the image in the inactive slot reports - what a real slot would read
+    /// from the image header.
+    pub fn set_inactive_version(&self, version: &str) {
+        let mut pair = self.lock();
+        let inactive = 1 - pair.active;
+        pair.versions[inactive] = Some(version.into());
+    }
+
+    /// The image the running slot holds.
+    pub fn running_image(&self) -> alloc::vec::Vec<u8> {
+        let pair = self.lock();
+        pair.images[pair.active].clone()
+    }
+
+    /// Simulates a reset: an unconfirmed trial image is abandoned for the other slot; otherwise a
+    /// pending image is booted on trial.
+    pub fn reboot(&self) {
+        let mut pair = self.lock();
+        if pair.trial {
+            pair.active = 1 - pair.active;
+            pair.trial = false;
+        } else if pair.pending {
+            pair.active = 1 - pair.active;
+            pair.pending = false;
+            pair.trial = true;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SlotPair> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// The error type of [`InMemoryFirmwareSlots`]. Its operations never fail, so this can never be
+/// constructed - the same arrangement as [`InMemoryStorageError`](super::InMemoryStorageError).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InMemoryFirmwareSlotsError {}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for InMemoryFirmwareSlotsError {
+    fn fmt(&self, _formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for InMemoryFirmwareSlotsError {}
+
+#[cfg(feature = "std")]
+#[async_trait::async_trait]
+impl FirmwareSlots for InMemoryFirmwareSlots {
+    type Error = InMemoryFirmwareSlotsError;
+
+    async fn write_inactive(&self, offset: u64, data: &[u8]) -> Result<(), Self::Error> {
+        let mut pair = self.lock();
+        let inactive = 1 - pair.active;
+        let image = &mut pair.images[inactive];
+        if offset == 0 {
+            image.clear();
+        }
+        image.extend_from_slice(data);
+        Ok(())
+    }
+
+    async fn mark_pending(&self) -> Result<(), Self::Error> {
+        self.lock().pending = true;
+        Ok(())
+    }
+
+    async fn state(&self) -> Result<SlotState, Self::Error> {
+        Ok(if self.lock().trial {
+            SlotState::Trial
+        } else {
+            SlotState::Confirmed
+        })
+    }
+
+    async fn confirm(&self) -> Result<(), Self::Error> {
+        self.lock().trial = false;
+        Ok(())
+    }
+
+    async fn revert(&self) -> Result<(), Self::Error> {
+        // A trial image is left marked as such: the next `reboot` is what abandons it.
+        self.lock().pending = false;
+        Ok(())
+    }
+
+    fn running_version(&self) -> Option<String> {
+        let pair = self.lock();
+        pair.versions[pair.active].clone()
+    }
+
+    fn inactive_version(&self) -> Option<String> {
+        let pair = self.lock();
+        pair.versions[1 - pair.active].clone()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests;
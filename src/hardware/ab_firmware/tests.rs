@@ -0,0 +1,177 @@
+//! Tests for the A/B firmware installer, driven end to end through [`InMemoryFirmwareSlots`].
+
+use super::*;
+use crate::clock::MonotonicInstant;
+use crate::executor::TokioExecutor;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+const OLD_IMAGE: &[u8] = b"firmware 1.0.0";
+
+/// An image spanning several copy chunks, with a short last one.
+fn new_image() -> Vec<u8> {
+    (0..COPY_CHUNK_BYTES * 2 + 17)
+        .map(|i| (i % 251) as u8)
+        .collect()
+}
+
+struct InMemoryImage(Vec<u8>);
+
+#[async_trait::async_trait]
+impl FirmwareImage for InMemoryImage {
+    type Error = InMemoryFirmwareSlotsError;
+
+    async fn read_image(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let rest = self.0.get(offset as usize..).unwrap_or_default();
+        let len = rest.len().min(buf.len());
+        buf[..len].copy_from_slice(&rest[..len]);
+        Ok(len)
+    }
+}
+
+struct InstantBackoff;
+
+#[async_trait::async_trait]
+impl Backoff for InstantBackoff {
+    async fn wait(&self, _seconds: u32) {
+        tokio::task::yield_now().await;
+    }
+}
+
+/// A monotonic clock that moves on a second every time it is read.
+#[derive(Default)]
+struct SteppingClock(AtomicU64);
+
+impl MonotonicClock for SteppingClock {
+    fn now(&self) -> MonotonicInstant {
+        let secs = self.0.fetch_add(1, Ordering::SeqCst);
+        MonotonicInstant::from_ticks(secs * 1_000_000_000)
+    }
+}
+
+fn installer(
+    image: Vec<u8>,
+) -> (
+    AbFirmwareInstaller<InMemoryImage, Arc<InMemoryFirmwareSlots>>,
+    Arc<InMemoryFirmwareSlots>,
+) {
+    let slots = Arc::new(InMemoryFirmwareSlots::new(OLD_IMAGE, "1.0.0"));
+    (
+        AbFirmwareInstaller::new(InMemoryImage(image), slots.clone()),
+        slots,
+    )
+}
+
+/// Installs the new image and reboots into it, leaving it on trial.
+async fn boot_on_trial(
+    installer: &AbFirmwareInstaller<InMemoryImage, Arc<InMemoryFirmwareSlots>>,
+    slots: &InMemoryFirmwareSlots,
+) {
+    assert_eq!(
+        installer.install().await.unwrap(),
+        FirmwareInstallOutcome::RebootRequired
+    );
+    slots.reboot();
+    assert_eq!(slots.state().await.unwrap(), SlotState::Trial);
+}
+
+#[tokio::test]
+async fn installing_writes_the_inactive_slot_and_the_next_boot_runs_it_on_trial() {
+    let (installer, slots) = installer(new_image());
+
+    assert_eq!(
+        installer.install().await.unwrap(),
+        FirmwareInstallOutcome::RebootRequired
+    );
+    // Nothing changes for the running image until the reset.
+    assert_eq!(slots.running_image(), OLD_IMAGE);
+    assert_eq!(slots.state().await.unwrap(), SlotState::Confirmed);
+
+    slots.reboot();
+    assert_eq!(slots.running_image(), new_image());
+    assert_eq!(slots.state().await.unwrap(), SlotState::Trial);
+}
+
+#[tokio::test]
+async fn an_empty_image_is_refused_and_nothing_is_marked_pending() {
+    let (installer, slots) = installer(Vec::new());
+
+    assert!(matches!(
+        installer.install().await,
+        Err(AbFirmwareInstallerError::EmptyImage)
+    ));
+    slots.reboot();
+    assert_eq!(slots.running_image(), OLD_IMAGE);
+    assert_eq!(slots.state().await.unwrap(), SlotState::Confirmed);
+}
+
+#[tokio::test]
+async fn the_slot_versions_are_what_the_firmware_worker_records() {
+    let (installer, slots) = installer(new_image());
+    slots.set_inactive_version("1.1.0");
+
+    assert_eq!(installer.running_version().as_deref(), Some("1.0.0"));
+    assert_eq!(installer.installed_version().as_deref(), Some("1.1.0"));
+}
+
+#[tokio::test]
+async fn an_accepted_registration_confirms_the_trial_image() {
+    let (installer, slots) = installer(new_image());
+    boot_on_trial(&installer, &slots).await;
+    let actor = ChargePointActor::spawn([1], &TokioExecutor);
+    let _ = actor
+        .send(ChargePointEvent::RegistrationStatusReceived(
+            RegistrationStatus::Accepted,
+        ))
+        .await;
+
+    let outcome = installer
+        .supervise_trial_boot(&actor, &InstantBackoff, &SteppingClock::default())
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, TrialOutcome::Confirmed);
+    assert_eq!(slots.state().await.unwrap(), SlotState::Confirmed);
+    // Confirmed means permanent: a later reset keeps it.
+    slots.reboot();
+    assert_eq!(slots.running_image(), new_image());
+}
+
+#[tokio::test]
+async fn a_trial_image_that_never_registers_is_rolled_back_once_the_window_closes() {
+    let (installer, slots) = installer(new_image());
+    let installer = installer.with_rollback_window_secs(5);
+    boot_on_trial(&installer, &slots).await;
+    let actor = ChargePointActor::spawn([1], &TokioExecutor);
+    // The CSMS answers, but never with Accepted - still not good enough to keep the image.
+    let _ = actor
+        .send(ChargePointEvent::RegistrationStatusReceived(
+            RegistrationStatus::Pending,
+        ))
+        .await;
+
+    let outcome = installer
+        .supervise_trial_boot(&actor, &InstantBackoff, &SteppingClock::default())
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, TrialOutcome::RolledBack);
+    slots.reboot();
+    assert_eq!(slots.running_image(), OLD_IMAGE);
+    assert_eq!(slots.state().await.unwrap(), SlotState::Confirmed);
+    assert_eq!(slots.running_version().as_deref(), Some("1.0.0"));
+}
+
+#[tokio::test]
+async fn a_confirmed_boot_has_nothing_to_supervise() {
+    let (installer, _slots) = installer(new_image());
+    let actor = ChargePointActor::spawn([1], &TokioExecutor);
+
+    let outcome = installer
+        .supervise_trial_boot(&actor, &InstantBackoff, &SteppingClock::default())
+        .await
+        .unwrap();
+
+    assert_eq!(outcome, TrialOutcome::NotOnTrial);
+}
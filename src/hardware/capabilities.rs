@@ -120,6 +120,28 @@ pub struct Capabilities {
     /// CSMS-only network allowlist) as OCSP-capable, and would deny OCSP checking to one that
     /// *can* reach a responder but keeps no certificates of its own.
     pub ocsp_checking: bool,
+    /// The hardware binding raises [`ConnectorEvent::ParkingBayOccupied`] and
+    /// [`ConnectorEvent::ParkingBayVacated`] from a bay sensor, so `TxCtrlr.TxStartPoint`/
+    /// `TxStopPoint` accept `ParkingBayOccupancy` (CV2.2). Without the sensor the point could never
+    /// be observed, and accepting it would be the silent lie B05.FR.09 forbids.
+    ///
+    /// [`ConnectorEvent::ParkingBayOccupied`]: crate::state::ConnectorEvent::ParkingBayOccupied
+    /// [`ConnectorEvent::ParkingBayVacated`]: crate::state::ConnectorEvent::ParkingBayVacated
+    pub parking_bay_sensor: bool,
+    /// The hardware binding raises [`ConnectorEvent::EnergyTransferStarted`] and
+    /// [`ConnectorEvent::EnergyTransferStopped`] from a measurement of current actually flowing,
+    /// distinct from the contactor being closed, so the transaction points accept `EnergyTransfer`
+    /// (CV2.2).
+    ///
+    /// [`ConnectorEvent::EnergyTransferStarted`]: crate::state::ConnectorEvent::EnergyTransferStarted
+    /// [`ConnectorEvent::EnergyTransferStopped`]: crate::state::ConnectorEvent::EnergyTransferStopped
+    pub energy_transfer_detection: bool,
+    /// The meter signs its readings and the hardware binding passes the signature on in
+    /// [`ConnectorEvent::MeterValueSampled`], so the transaction points accept `DataSigned`
+    /// (CV2.2).
+    ///
+    /// [`ConnectorEvent::MeterValueSampled`]: crate::state::ConnectorEvent::MeterValueSampled
+    pub signed_meter_values: bool,
 }
 
 impl Default for Capabilities {
@@ -154,6 +176,9 @@ impl Default for Capabilities {
             certificates: false,
             key_storage: false,
             ocsp_checking: false,
+            parking_bay_sensor: false,
+            energy_transfer_detection: false,
+            signed_meter_values: false,
         }
     }
 }
@@ -818,6 +843,27 @@ impl Capabilities {
         self.ocsp_checking = enabled;
         self
     }
+
+    /// Sets `parking_bay_sensor` - see that field.
+    #[must_use]
+    pub fn with_parking_bay_sensor(mut self, enabled: bool) -> Self {
+        self.parking_bay_sensor = enabled;
+        self
+    }
+
+    /// Sets `energy_transfer_detection` - see that field.
+    #[must_use]
+    pub fn with_energy_transfer_detection(mut self, enabled: bool) -> Self {
+        self.energy_transfer_detection = enabled;
+        self
+    }
+
+    /// Sets `signed_meter_values` - see that field.
+    #[must_use]
+    pub fn with_signed_meter_values(mut self, enabled: bool) -> Self {
+        self.signed_meter_values = enabled;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -880,6 +926,9 @@ mod tests {
         assert!(!capabilities.certificates);
         assert!(!capabilities.key_storage);
         assert!(!capabilities.ocsp_checking);
+        assert!(!capabilities.parking_bay_sensor);
+        assert!(!capabilities.energy_transfer_detection);
+        assert!(!capabilities.signed_meter_values);
     }
 
     #[test]
@@ -25,6 +25,9 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use chrono::{DateTime, Utc};
 
+#[cfg(feature = "certificate-parsing")]
+mod parsing;
+
 /// What a certificate is *for*, spanning OCPP's two overlapping enums
 /// (`InstallCertificateUseEnum` for what may be installed, `GetCertificateIdUseEnum` for what may
 /// be listed - the latter adds the charge point's own chain, which is obtained by signing rather
@@ -79,8 +82,9 @@ pub enum HashAlgorithm {
 /// a CSMS that asks to delete a certificate names it this way, and any local id would have to be
 /// mapped back to these fields anyway.
 ///
-/// The hashes are computed by whoever parses the certificate - the integrator - because computing
-/// them means parsing X.509 and hashing DER, which needs crypto this crate does not have.
+/// The hashes are computed by whoever parses the certificate: [`StoredCertificates`] itself with
+/// the `certificate-parsing` feature, otherwise the integrator, through
+/// [`StoredCertificates::install_with_hash`] or a store of their own.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CertificateHashData {
     /// The algorithm the two hashes below were computed with.
@@ -154,11 +158,11 @@ pub enum DeleteCertificateOutcome {
 /// | [`expires_at`](Self::expires_at) | `None` | **No ahead-of-expiry renewal.** [`crate::certificate_renewal`] skips the slot entirely, so the certificate expires mid-service instead of being replaced early. |
 ///
 /// The dividing line is whether the implementation can *parse X.509*: all three defaulted methods
-/// need something that reads a certificate's contents, and this crate has no parser (see the
-/// module docs). They are defaulted rather than split into a second trait because a store that
-/// implements them is still one store - an integrator should not have to wire up two objects, and
-/// the crate should not have to ask "did you also bring the parsing half?" at every call site.
-/// [`StoredCertificates`] overrides the first two and not the third, for exactly this reason.
+/// need something that reads a certificate's contents. They are defaulted rather than split into
+/// a second trait because a store that implements them is still one store - an integrator should
+/// not have to wire up two objects, and the crate should not have to ask "did you also bring the
+/// parsing half?" at every call site. [`StoredCertificates`] overrides the first two always and
+/// the third only with the `certificate-parsing` feature, for exactly this reason.
 #[async_trait::async_trait]
 pub trait CertificateStore {
     /// The error type returned by a failed store operation.
@@ -252,11 +256,10 @@ pub trait CertificateStore {
     /// whatever parses certificates on its behalf) knows.
     ///
     /// Additive (default `Ok(None)`), for the same reason [`Self::certificate_chain_pem`] is:
-    /// this crate does no X.509 parsing (see the module docs), so it cannot compute an expiry
-    /// from PEM bytes on its own - only an implementor that parses the certificate (or that
-    /// learned the expiry out of band, e.g. alongside the CSMS's `CertificateSigned`) can answer
-    /// this honestly. [`StoredCertificates`] does not override it, for exactly that reason: it
-    /// has no parser either.
+    /// an expiry cannot be had from PEM bytes without parsing them, so only an implementor that
+    /// parses the certificate (or that learned the expiry out of band, e.g. alongside the CSMS's
+    /// `CertificateSigned`) can answer this honestly. [`StoredCertificates`] overrides it with the
+    /// `certificate-parsing` feature and not without, for exactly that reason.
     ///
     /// **`None` here does not mean "never expires" or "far away".** It means "this store cannot
     /// say", and [`crate::certificate_renewal`] (F3.2) treats it exactly that way - skipping the
@@ -387,9 +390,16 @@ impl CertificateStore for NoCertificateStore {
 ///
 /// Bounded by `max_certificates`, for the reason every other collection here is (G2.2): a store a
 /// remote peer can grow without limit is not a bound.
-pub struct StoredCertificates<S> {
+///
+/// With the `certificate-parsing` feature it also parses what it is given - see
+/// [`CertificateStore::install`]'s impl below - and `clock` ([`Self::with_clock`]) is what it
+/// judges expiry by. Without one, it reads [`UnsetClock`](crate::clock::UnsetClock) and never
+/// refuses a certificate as expired.
+pub struct StoredCertificates<S, C = crate::clock::UnsetClock> {
     storage: S,
     max_certificates: usize,
+    #[cfg_attr(not(feature = "certificate-parsing"), allow(dead_code))]
+    clock: C,
 }
 
 /// The key the whole certificate index is written under - one snapshot, like the local
@@ -496,6 +506,23 @@ impl<S: crate::hardware::Storage> StoredCertificates<S> {
         Self {
             storage,
             max_certificates: max_certificates.max(1),
+            clock: crate::clock::UnsetClock,
+        }
+    }
+}
+
+impl<S: crate::hardware::Storage, C> StoredCertificates<S, C> {
+    /// Judges certificate expiry by `clock` rather than not at all.
+    ///
+    /// Only consulted with the `certificate-parsing` feature, and only once
+    /// [`crate::clock::is_synchronized`] says the reading is real: a charge point that does not
+    /// know the date must not refuse a root as expired, any more than
+    /// [`crate::certificate_renewal`] renews one early.
+    pub fn with_clock<T: crate::clock::Clock>(self, clock: T) -> StoredCertificates<S, T> {
+        StoredCertificates {
+            storage: self.storage,
+            max_certificates: self.max_certificates,
+            clock,
         }
     }
 
@@ -549,7 +576,11 @@ impl core::fmt::Display for StoredCertificatesError {
 impl core::error::Error for StoredCertificatesError {}
 
 #[async_trait::async_trait]
-impl<S: crate::hardware::Storage + Send + Sync> CertificateStore for StoredCertificates<S> {
+impl<S, C> CertificateStore for StoredCertificates<S, C>
+where
+    S: crate::hardware::Storage + Send + Sync,
+    C: crate::clock::Clock + Send + Sync,
+{
     type Error = StoredCertificatesError;
 
     async fn install(
@@ -557,6 +588,12 @@ impl<S: crate::hardware::Storage + Send + Sync> CertificateStore for StoredCerti
         use_for: CertificateUse,
         certificate: &str,
     ) -> Result<InstallCertificateOutcome, Self::Error> {
+        // With a parser, every certificate gets the hash data a CSMS would compute itself - see
+        // `install_parsed`. What follows is the parser-less fallback.
+        #[cfg(feature = "certificate-parsing")]
+        return Ok(self.install_parsed(use_for, certificate).await);
+
+        #[cfg(not(feature = "certificate-parsing"))]
         if use_for.is_installable() {
             // A CSMS-pushed root (`InstallCertificate`). This store does no X.509 parsing - it
             // has no crypto - so it cannot compute the hashes a CSMS would later address the
@@ -576,6 +613,7 @@ impl<S: crate::hardware::Storage + Send + Sync> CertificateStore for StoredCerti
         // this store can give it a self-computed, honestly-labelled stand-in identity instead of
         // refusing outright. See `install_own_certificate` for exactly what that identity is and
         // is not.
+        #[cfg(not(feature = "certificate-parsing"))]
         Ok(self.install_own_certificate(use_for, certificate).await)
     }
 
@@ -661,15 +699,34 @@ impl<S: crate::hardware::Storage + Send + Sync> CertificateStore for StoredCerti
             .map(|entry| entry.pem)
             .collect())
     }
+
+    #[cfg(feature = "certificate-parsing")]
+    async fn expires_at(
+        &self,
+        use_for: CertificateUse,
+    ) -> Result<Option<DateTime<Utc>>, Self::Error> {
+        // Parsed from the stored PEM on every call rather than persisted beside it: a store
+        // written before this feature was enabled has no expiry to read back, and this is called
+        // once per renewal sweep, not per message.
+        Ok(self
+            .certificate_chain_pem(use_for)
+            .await?
+            .and_then(|pem| parsing::parse_chain(&pem))
+            .and_then(|chain| parsing::expires_at(&chain)))
+    }
 }
 
-impl<S: crate::hardware::Storage + Send + Sync> StoredCertificates<S> {
+impl<S, C> StoredCertificates<S, C>
+where
+    S: crate::hardware::Storage + Send + Sync,
+    C: crate::clock::Clock + Send + Sync,
+{
     /// Stores `certificate` with hashes the caller has already computed.
     ///
     /// The way in for an integrator that *can* parse X.509 but has no secure element: they supply
     /// the hash data, this store keeps it. Separate from
-    /// [`CertificateStore::install`] because the trait method takes only a PEM, and this store
-    /// cannot derive the hashes from one.
+    /// [`CertificateStore::install`] because the trait method takes only a PEM, and without the
+    /// `certificate-parsing` feature this store cannot derive the hashes from one.
     ///
     /// **No `is_installable` gate.** That check exists to stop a *CSMS* from pushing the charge
     /// point's own certificate through `InstallCertificate` - and it is enforced there, at the
@@ -717,13 +774,68 @@ impl<S: crate::hardware::Storage + Send + Sync> StoredCertificates<S> {
         }
     }
 
+    /// [`CertificateStore::install`] with the `certificate-parsing` feature: parses `certificate`
+    /// and stores it under the hash data a CSMS computes from the same bytes.
+    ///
+    /// `Rejected` for a certificate that does not parse, a chain out of order, or one with a
+    /// certificate past its `notAfter` by a synchronized clock ([`Self::with_clock`]). The
+    /// issuer's key, which `issuerKeyHash` needs, comes from the certificate itself for a root,
+    /// otherwise from the rest of the chain or from a certificate already installed; a CSMS-pushed
+    /// certificate whose issuer is in none of those places is `Rejected` too, since hash data
+    /// without it would not be the hash data the CSMS deletes by. The charge point's own
+    /// certificate falls back to [`Self::install_own_certificate`]'s stand-in instead, for the
+    /// reason that method gives.
+    #[cfg(feature = "certificate-parsing")]
+    async fn install_parsed(
+        &self,
+        use_for: CertificateUse,
+        certificate: &str,
+    ) -> InstallCertificateOutcome {
+        let Some(chain) = parsing::parse_chain(certificate) else {
+            tracing::warn!(?use_for, "refusing a certificate that does not parse");
+            return InstallCertificateOutcome::Rejected;
+        };
+        let now = self.clock.now();
+        if crate::clock::is_synchronized(&now)
+            && parsing::expires_at(&chain).is_none_or(|expires_at| expires_at < now)
+        {
+            tracing::warn!(?use_for, "refusing an expired certificate");
+            return InstallCertificateOutcome::Rejected;
+        }
+
+        let installed: Vec<_> = self
+            .load()
+            .await
+            .entries
+            .iter()
+            .filter_map(|entry| parsing::parse_chain(&entry.pem))
+            .flatten()
+            .collect();
+        let hash_data = parsing::issuer_of(&chain, &installed)
+            .and_then(|issuer| parsing::hash_data(&chain[0], issuer));
+        match hash_data {
+            Some(hash_data) => {
+                self.install_with_hash(use_for, certificate, hash_data)
+                    .await
+            }
+            None if !use_for.is_installable() => {
+                self.install_own_certificate(use_for, certificate).await
+            }
+            None => {
+                tracing::warn!(?use_for, "refusing a certificate whose issuer is not known");
+                InstallCertificateOutcome::Rejected
+            }
+        }
+    }
+
     /// Stores the charge point's own certificate (`ChargingStation`/`V2gCertificateChain`) - the
     /// branch of [`CertificateStore::install`] that is not a CSMS-pushed root, reached from
     /// [`crate::certificates::handle_certificate_signed`] answering a `CertificateSigned`.
     ///
-    /// **The hash this reports is not a real X.509 issuer hash.** This store has no X.509 parser
-    /// (see the module docs), so it cannot derive `issuerNameHash`/`issuerKeyHash` from
-    /// `certificate` the way a CSMS would from parsing the same bytes. What it *can* do - the one
+    /// **The hash this reports is not a real X.509 issuer hash.** Without the
+    /// `certificate-parsing` feature this store cannot derive `issuerNameHash`/`issuerKeyHash`
+    /// from `certificate` the way a CSMS would from parsing the same bytes - and with it, not when
+    /// the issuer's certificate is nowhere it can look. What it *can* do - the one
     /// hashing exception this crate makes, the same one `crate::certificates::csr` (CSR digests)
     /// and `crate::mutual_tls` (TLS handshake digests) already rely on - is hash the PEM bytes
     /// with SHA-256 and use that as a stable stand-in identity for
@@ -733,8 +845,8 @@ impl<S: crate::hardware::Storage + Send + Sync> StoredCertificates<S> {
     /// single-slotted (`certificate_chain_pem`'s docs), so the only party that will ever ask "is
     /// this the one I mean" is this same charge point, comparing against a value it produced
     /// itself. A CSMS listing it via `GetInstalledCertificateIds` sees this stand-in, not a
-    /// spec-compliant issuer hash - a genuinely compliant one still needs the X.509 parser this
-    /// crate does not carry, the same honest limitation `install`'s root branch documents.
+    /// spec-compliant issuer hash - a genuinely compliant one needs the parser and the issuer,
+    /// the same honest limitation `install`'s root branch documents.
     async fn install_own_certificate(
         &self,
         use_for: CertificateUse,
@@ -757,10 +869,19 @@ impl<S: crate::hardware::Storage + Send + Sync> StoredCertificates<S> {
 
 /// Whether `entry` is the certificate `hash_data` addresses. All four fields must match: the
 /// serial number alone is only unique per issuer.
+///
+/// Compared without regard to case: they are hexadecimal, and a CSMS that computed them itself
+/// may well write them in capitals where this store writes lowercase.
 fn matches_hash(entry: &PersistedCertificate, hash_data: &CertificateHashData) -> bool {
-    entry.serial_number == hash_data.serial_number
-        && entry.issuer_name_hash == hash_data.issuer_name_hash
-        && entry.issuer_key_hash == hash_data.issuer_key_hash
+    entry
+        .serial_number
+        .eq_ignore_ascii_case(&hash_data.serial_number)
+        && entry
+            .issuer_name_hash
+            .eq_ignore_ascii_case(&hash_data.issuer_name_hash)
+        && entry
+            .issuer_key_hash
+            .eq_ignore_ascii_case(&hash_data.issuer_key_hash)
 }
 
 /// Hex-encodes `bytes`, lowercase - just enough formatting for
@@ -816,6 +937,7 @@ mod tests {
     }
 
     use crate::hardware::InMemoryStorage;
+    use crate::test_certificates::CHARGING_STATION;
     use alloc::string::ToString;
     use alloc::sync::Arc;
 
@@ -945,17 +1067,15 @@ mod tests {
     async fn install_stores_the_charge_points_own_certificate_with_a_self_computed_stand_in_hash() {
         // The plain trait method, as `crate::certificates::handle_certificate_signed` calls it -
         // no hash data available at all, only the raw PEM from `CertificateSigned`. Unlike a
-        // CSMS-pushed root (still `Rejected`: no crypto to compute the hash a CSMS would expect),
-        // the charge point's own certificate gets a self-computed stand-in instead of a refusal -
-        // see `install_own_certificate`'s docs for exactly what that stand-in is and is not.
+        // CSMS-pushed root (still `Rejected` when this store cannot compute the hash a CSMS would
+        // expect), the charge point's own certificate gets a self-computed stand-in instead of a
+        // refusal - see `install_own_certificate`'s docs for exactly what that stand-in is and is
+        // not. With a parser this is the certificate whose issuer is nowhere in the store.
         let store = store();
 
         assert_eq!(
             store
-                .install(
-                    CertificateUse::ChargingStation,
-                    "-----BEGIN CERTIFICATE-----\nleaf\n-----END CERTIFICATE-----",
-                )
+                .install(CertificateUse::ChargingStation, CHARGING_STATION)
                 .await,
             Ok(InstallCertificateOutcome::Accepted)
         );
@@ -965,7 +1085,7 @@ mod tests {
                 .certificate_chain_pem(CertificateUse::ChargingStation)
                 .await
                 .unwrap(),
-            Some("-----BEGIN CERTIFICATE-----\nleaf\n-----END CERTIFICATE-----".to_string())
+            Some(CHARGING_STATION.to_string())
         );
         let installed = store
             .installed(&[CertificateUse::ChargingStation])
@@ -1117,9 +1237,9 @@ mod tests {
 
     #[tokio::test]
     async fn the_default_expires_at_honestly_reports_that_it_does_not_know() {
-        // F3.2: no store here can parse X.509, so `None` (not a fabricated far-future date) is
-        // the only honest answer - `crate::certificate_renewal` relies on this to skip a slot it
-        // has no expiry for rather than guessing.
+        // F3.2: with nothing installed, or no X.509 parser, `None` (not a fabricated far-future
+        // date) is the only honest answer - `crate::certificate_renewal` relies on this to skip a
+        // slot it has no expiry for rather than guessing.
         assert_eq!(
             store().expires_at(CertificateUse::CsmsRoot).await.unwrap(),
             None
@@ -1146,4 +1266,187 @@ mod tests {
                 .is_empty()
         );
     }
+
+    #[cfg(feature = "certificate-parsing")]
+    mod parsed {
+        use super::*;
+        use crate::test_certificates::{CSMS_ROOT, EXPIRED_ROOT};
+        use alloc::format;
+        use chrono::TimeZone;
+
+        /// What `openssl ocsp -sha256 -issuer CSMS_ROOT -cert CHARGING_STATION -req_text` reports,
+        /// so the hash data is checked against a computation this crate did not do.
+        fn charging_station_hash() -> CertificateHashData {
+            CertificateHashData {
+                hash_algorithm: HashAlgorithm::Sha256,
+                issuer_name_hash:
+                    "DE19356FAC053593714166ECCCD75B5DC45E16626994827846A6BDFA97DD858D".to_string(),
+                issuer_key_hash: "F92C19891237F13A8655530653B114102452FEB0EC9F51C0D9BE2452DCAA0F8D"
+                    .to_string(),
+                serial_number: "AB01".to_string(),
+            }
+        }
+
+        struct FixedClock(DateTime<Utc>);
+
+        impl crate::clock::Clock for FixedClock {
+            fn now(&self) -> DateTime<Utc> {
+                self.0
+            }
+        }
+
+        fn at(year: i32) -> FixedClock {
+            FixedClock(Utc.with_ymd_and_hms(year, 6, 1, 0, 0, 0).unwrap())
+        }
+
+        #[tokio::test]
+        async fn a_root_is_installed_under_the_hash_data_a_csms_computes_and_deleted_by_it() {
+            let store = store();
+            assert_eq!(
+                store.install(CertificateUse::CsmsRoot, CSMS_ROOT).await,
+                Ok(InstallCertificateOutcome::Accepted)
+            );
+
+            // A root is its own issuer, so its name and key hashes are the ones its children get.
+            let installed = store.installed(&[]).await.unwrap();
+            assert_eq!(installed.len(), 1);
+            let hash_data = &installed[0].hash_data;
+            assert!(
+                hash_data
+                    .issuer_name_hash
+                    .eq_ignore_ascii_case(&charging_station_hash().issuer_name_hash)
+            );
+            assert!(
+                hash_data
+                    .issuer_key_hash
+                    .eq_ignore_ascii_case(&charging_station_hash().issuer_key_hash)
+            );
+
+            // The CSMS deletes by what it computed, in whatever case it writes hex.
+            let mut upper = hash_data.clone();
+            upper.issuer_name_hash.make_ascii_uppercase();
+            upper.issuer_key_hash.make_ascii_uppercase();
+            upper.serial_number.make_ascii_uppercase();
+            assert_eq!(
+                store.delete(&upper).await.unwrap(),
+                DeleteCertificateOutcome::Accepted
+            );
+        }
+
+        #[tokio::test]
+        async fn a_certificate_takes_its_issuers_key_hash_from_an_installed_root() {
+            let store = store();
+            store
+                .install(CertificateUse::CsmsRoot, CSMS_ROOT)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                store
+                    .install(CertificateUse::ChargingStation, CHARGING_STATION)
+                    .await,
+                Ok(InstallCertificateOutcome::Accepted)
+            );
+            assert_eq!(
+                store.delete(&charging_station_hash()).await.unwrap(),
+                DeleteCertificateOutcome::Accepted
+            );
+        }
+
+        #[tokio::test]
+        async fn a_certificate_takes_its_issuers_key_hash_from_the_rest_of_its_chain() {
+            let store = store();
+            let chain = format!("{CHARGING_STATION}{CSMS_ROOT}");
+
+            assert_eq!(
+                store.install(CertificateUse::ChargingStation, &chain).await,
+                Ok(InstallCertificateOutcome::Accepted)
+            );
+            let installed = store.installed(&[]).await.unwrap();
+            assert_eq!(installed[0].hash_data.serial_number, "ab01");
+            assert_eq!(
+                store
+                    .certificate_chain_pem(CertificateUse::ChargingStation)
+                    .await,
+                Ok(Some(chain))
+            );
+        }
+
+        #[tokio::test]
+        async fn a_chain_out_of_order_is_rejected() {
+            assert_eq!(
+                store()
+                    .install(
+                        CertificateUse::ChargingStation,
+                        &format!("{CSMS_ROOT}{CHARGING_STATION}")
+                    )
+                    .await,
+                Ok(InstallCertificateOutcome::Rejected)
+            );
+        }
+
+        #[tokio::test]
+        async fn a_csms_pushed_certificate_whose_issuer_is_unknown_is_rejected() {
+            // Without the issuer's key there is no `issuerKeyHash` the CSMS would recognise, so
+            // the certificate could be listed but never deleted.
+            let store = store();
+            assert_eq!(
+                store
+                    .install(CertificateUse::V2gRoot, CHARGING_STATION)
+                    .await,
+                Ok(InstallCertificateOutcome::Rejected)
+            );
+            assert!(store.installed(&[]).await.unwrap().is_empty());
+        }
+
+        #[tokio::test]
+        async fn an_expired_certificate_is_rejected_once_the_clock_is_known() {
+            let store = store().with_clock(at(2030));
+            assert_eq!(
+                store.install(CertificateUse::CsmsRoot, EXPIRED_ROOT).await,
+                Ok(InstallCertificateOutcome::Rejected)
+            );
+            assert_eq!(
+                store.install(CertificateUse::CsmsRoot, CSMS_ROOT).await,
+                Ok(InstallCertificateOutcome::Accepted)
+            );
+        }
+
+        #[tokio::test]
+        async fn an_unsynchronized_clock_never_rejects_a_certificate_as_expired() {
+            // The same stance `crate::certificate_renewal` takes: a charge point that does not
+            // know the date must not judge a certificate by it.
+            assert_eq!(
+                store()
+                    .with_clock(at(1970))
+                    .install(CertificateUse::CsmsRoot, EXPIRED_ROOT)
+                    .await,
+                Ok(InstallCertificateOutcome::Accepted)
+            );
+            assert_eq!(
+                store()
+                    .install(CertificateUse::CsmsRoot, EXPIRED_ROOT)
+                    .await,
+                Ok(InstallCertificateOutcome::Accepted)
+            );
+        }
+
+        #[tokio::test]
+        async fn expires_at_is_the_earliest_not_after_on_the_installed_chain() {
+            let store = store();
+            store
+                .install(
+                    CertificateUse::ChargingStation,
+                    &format!("{CHARGING_STATION}{CSMS_ROOT}"),
+                )
+                .await
+                .unwrap();
+
+            // Both were issued for the same term, so this is each one's `notAfter`.
+            assert_eq!(
+                store.expires_at(CertificateUse::ChargingStation).await,
+                Ok(Some(Utc.with_ymd_and_hms(2126, 9, 23, 8, 5, 7).unwrap()))
+            );
+        }
+    }
 }
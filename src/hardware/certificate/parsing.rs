@@ -0,0 +1,125 @@
+//! The X.509 half of [`StoredCertificates`](super::StoredCertificates), behind the
+//! `certificate-parsing` feature: turning an installed PEM into the [`CertificateHashData`] a CSMS
+//! addresses it by, and into the expiry [`crate::certificate_renewal`] schedules against.
+//!
+//! The hash data follows OCPP's `CertificateHashDataType` to the letter, since a CSMS computes
+//! the same fields from the same bytes and `DeleteCertificate` only works if the two agree:
+//!
+//! - `issuerNameHash` is the SHA-256 of the DER of the certificate's `issuer` field;
+//! - `issuerKeyHash` is the SHA-256 of the issuer's public key - the contents of its
+//!   `subjectPublicKey` BIT STRING, without tag or length (the same value OCSP's `CertID` uses);
+//! - `serialNumber` is the serial in hexadecimal, without a `0x` prefix or leading zeroes.
+//!
+//! Hashes are SHA-256 via [`crate::certificates::csr::sha256`], the digest this crate already
+//! carries, so the feature needs no hashing crate of its own.
+
+use super::{CertificateHashData, HashAlgorithm, hex_encode};
+use alloc::string::String;
+use alloc::vec::Vec;
+use chrono::{DateTime, Utc};
+use x509_cert::Certificate;
+use x509_cert::der::Encode;
+
+/// Parses `pem` as a certificate chain, leaf (or root) first.
+///
+/// `None` for anything that is not one or more well-formed certificates in issuing order - each
+/// one's `issuer` the `subject` of the one after it. A chain out of order is refused rather than
+/// sorted: it is what a CSMS sends when it has concatenated the wrong files, and what a TLS peer
+/// would reject later anyway.
+pub(super) fn parse_chain(pem: &str) -> Option<Vec<Certificate>> {
+    // `load_pem_chain` indexes one byte before the end of its input, so text that is empty once
+    // its trailing line breaks are gone panics it rather than failing to parse.
+    if pem.trim().is_empty() {
+        return None;
+    }
+    let chain = Certificate::load_pem_chain(pem.as_bytes()).ok()?;
+    let in_order = chain
+        .windows(2)
+        .all(|pair| pair[0].tbs_certificate.issuer == pair[1].tbs_certificate.subject);
+    (!chain.is_empty() && in_order).then_some(chain)
+}
+
+/// When `chain` stops being usable: the earliest `notAfter` on it, since a chain is only as valid
+/// as its shortest-lived link.
+pub(super) fn expires_at(chain: &[Certificate]) -> Option<DateTime<Utc>> {
+    chain
+        .iter()
+        .map(|certificate| {
+            let not_after = certificate
+                .tbs_certificate
+                .validity
+                .not_after
+                .to_unix_duration();
+            DateTime::from_timestamp(i64::try_from(not_after.as_secs()).ok()?, 0)
+        })
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .min()
+}
+
+/// The certificate that issued `chain`'s first entry, looked for where it can be: the entry
+/// itself if it is self-issued (a root), the next one on the chain, or any of `installed`.
+pub(super) fn issuer_of<'a>(
+    chain: &'a [Certificate],
+    installed: &'a [Certificate],
+) -> Option<&'a Certificate> {
+    let first = chain.first()?;
+    let issuer = &first.tbs_certificate.issuer;
+    if *issuer == first.tbs_certificate.subject {
+        return Some(first);
+    }
+    chain
+        .iter()
+        .skip(1)
+        .chain(installed)
+        .find(|candidate| candidate.tbs_certificate.subject == *issuer)
+}
+
+/// OCPP's hash data for `certificate`, issued by `issuer` - see the module docs.
+pub(super) fn hash_data(
+    certificate: &Certificate,
+    issuer: &Certificate,
+) -> Option<CertificateHashData> {
+    let issuer_name = certificate.tbs_certificate.issuer.to_der().ok()?;
+    let issuer_key = issuer
+        .tbs_certificate
+        .subject_public_key_info
+        .subject_public_key
+        .raw_bytes();
+    Some(CertificateHashData {
+        hash_algorithm: HashAlgorithm::Sha256,
+        issuer_name_hash: hex_encode(&crate::certificates::csr::sha256(&issuer_name)),
+        issuer_key_hash: hex_encode(&crate::certificates::csr::sha256(issuer_key)),
+        serial_number: serial_number(certificate.tbs_certificate.serial_number.as_bytes()),
+    })
+}
+
+/// `bytes` (a DER `INTEGER`'s contents) as OCPP's serial number string.
+fn serial_number(bytes: &[u8]) -> String {
+    let hex = hex_encode(bytes);
+    match hex.trim_start_matches('0') {
+        "" => String::from("0"),
+        trimmed => String::from(trimmed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_serial_number_loses_its_sign_byte_and_leading_zeroes_but_not_its_value() {
+        assert_eq!(serial_number(&[0x00, 0x8f, 0x01]), "8f01");
+        assert_eq!(serial_number(&[0x01, 0x00]), "100");
+        assert_eq!(serial_number(&[0x00]), "0");
+    }
+
+    #[test]
+    fn text_that_is_not_a_certificate_does_not_parse() {
+        assert!(parse_chain("").is_none());
+        assert!(parse_chain("\r\n").is_none());
+        assert!(
+            parse_chain("-----BEGIN CERTIFICATE-----\nleaf\n-----END CERTIFICATE-----").is_none()
+        );
+    }
+}
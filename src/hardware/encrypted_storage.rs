@@ -0,0 +1,709 @@
+//! [`EncryptedStorage`]: authenticated encryption at rest for any [`Storage`], closing the gap
+//! `docs/THREAT-MODEL.md` §4.6 describes - transaction records carrying `IdToken`s, the security
+//! log and [`SoftKeyStore`](crate::hardware::SoftKeyStore)'s private keys otherwise land on the
+//! medium in the clear.
+//!
+//! # Record format
+//!
+//! Every value is sealed with XChaCha20-Poly1305 and written as
+//! `magic "OCPE" (4) | format (1) | generation (4, LE) | nonce (24) | ciphertext | tag (16)`.
+//! The associated data is the first nine header bytes followed by the logical key name, so:
+//!
+//! - a record copied from one key to another (slot swapping: moving a transaction record under
+//!   the security log's key, or an old key-store index over a new one) fails to authenticate;
+//! - the generation a record claims cannot be edited to point it at a different key.
+//!
+//! The nonce is synthetic: an HMAC-SHA256 over the key name and the value, under a key derived
+//! alongside the encryption key. Nothing here needs a random number generator, which a
+//! microcontroller target may not have, and a nonce can never repeat for two different values.
+//! The cost is that writing the same value under the same key twice produces the same bytes -
+//! which reveals that the value did not change, something the write pattern reveals anyway.
+//!
+//! # Keys, generations and rotation
+//!
+//! The AEAD key is never stored. It is derived with HKDF-SHA256 from a secret a
+//! [`StorageSecret`] hands out per *generation*: [`DeviceSecret`] for a device-unique value (an
+//! OTP fuse bank, a SoC's unique-ID-derived key), [`KeyStoreSecret`] for a credential held in a
+//! [`KeyStore`] - a secure element's data zone, where it has one. Writes always use the
+//! [`EncryptedStorage::with_generation`] in force; reads use whichever generation the record was
+//! written under, so rotating is: provision the new generation's secret, raise the generation,
+//! then call [`EncryptedStorage::migrate`] for each key this charge point persists. Once every
+//! key reports [`StorageMigration::AlreadyCurrent`], the old generation's secret can be retired.
+//!
+//! # Migrating from plaintext
+//!
+//! A charge point that has already been running with a plain [`Storage`] has records without
+//! the header. By default those are refused ([`EncryptedStorageError::Plaintext`]): accepting a
+//! headerless record means accepting one an attacker with the flash wrote, so it is opt-in.
+//! [`EncryptedStorage::with_plaintext_migration`] reads them as-is, and [`EncryptedStorage::migrate`]
+//! rewrites them sealed. Turn it off again in the firmware release after the one that migrated.
+//!
+//! # Composing with [`AtomicStorage`](crate::hardware::AtomicStorage)
+//!
+//! Wrap it, `EncryptedStorage::new(AtomicStorage::new(flash), secret)`: the A/B slots and CRC
+//! then protect the ciphertext, so a torn write still falls back to the previous record, and a
+//! record that is complete but tampered with fails authentication rather than being returned.
+//! The other order works too, but binds each ciphertext to a physical slot name and reports a
+//! tampered slot as the storage error [`AtomicStorage`](crate::hardware::AtomicStorage) then
+//! skips past.
+//!
+//! # What this does not protect
+//!
+//! Anything in RAM, and the secret itself: a [`DeviceSecret`] read out of the same flash as the
+//! records it seals protects nothing. Nor does it stop a record being rolled back to an older,
+//! validly sealed value of the *same* key - that needs a monotonic counter this crate cannot
+//! assume the hardware has.
+
+use super::key_storage::KeyStore;
+use super::storage::Storage;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+const MAGIC: [u8; 4] = *b"OCPE";
+const FORMAT: u8 = 1;
+/// `magic(4) + format(1) + generation(4)` - the part of the header bound in as associated data.
+const PREFIX_LEN: usize = 9;
+const NONCE_LEN: usize = 24;
+const HEADER_LEN: usize = PREFIX_LEN + NONCE_LEN;
+const TAG_LEN: usize = 16;
+const HKDF_SALT: &[u8] = b"ocpp-charge-point/encrypted-storage";
+
+/// The shortest secret [`EncryptedStorage`] derives a key from: 128 bits. HKDF stretches a
+/// secret to the key size, but cannot add entropy it never had.
+pub const MIN_STORAGE_SECRET_LEN: usize = 16;
+
+/// Where [`EncryptedStorage`] gets the secret its keys are derived from, one per generation - see
+/// the module docs.
+#[async_trait::async_trait]
+pub trait StorageSecret {
+    /// The error type returned when the secret cannot be read.
+    type Error: core::error::Error + Send + Sync + 'static;
+
+    /// The secret for `generation`, or `Ok(None)` if this source has none for it (never
+    /// provisioned, or retired after a rotation).
+    async fn secret(&self, generation: u32) -> Result<Option<Vec<u8>>, Self::Error>;
+}
+
+#[async_trait::async_trait]
+impl<T: StorageSecret + Send + Sync + ?Sized> StorageSecret for alloc::sync::Arc<T> {
+    type Error = T::Error;
+
+    async fn secret(&self, generation: u32) -> Result<Option<Vec<u8>>, Self::Error> {
+        (**self).secret(generation).await
+    }
+}
+
+/// A [`StorageSecret`] over one fixed, device-unique value.
+///
+/// Every generation gets the same secret, and the generation number alone tells the derived keys
+/// apart. Rotating therefore re-keys the records but cannot help once the device secret itself is
+/// known; use [`KeyStoreSecret`] where that matters.
+#[derive(Clone)]
+pub struct DeviceSecret {
+    secret: Vec<u8>,
+}
+
+impl DeviceSecret {
+    /// Wraps `secret`, which must be at least [`MIN_STORAGE_SECRET_LEN`] bytes for
+    /// [`EncryptedStorage`] to use it.
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+}
+
+/// Deliberately prints nothing of the secret.
+impl core::fmt::Debug for DeviceSecret {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DeviceSecret").finish_non_exhaustive()
+    }
+}
+
+/// The error type of [`DeviceSecret`]. Reading a secret held in memory cannot fail, so this can
+/// never be constructed; it exists only to give [`StorageSecret::Error`] a concrete, documented
+/// type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceSecretError {}
+
+impl core::fmt::Display for DeviceSecretError {
+    fn fmt(&self, _formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match *self {}
+    }
+}
+
+impl core::error::Error for DeviceSecretError {}
+
+#[async_trait::async_trait]
+impl StorageSecret for DeviceSecret {
+    type Error = DeviceSecretError;
+
+    async fn secret(&self, _generation: u32) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(Some(self.secret.clone()))
+    }
+}
+
+/// A [`StorageSecret`] that reads each generation's secret from a [`KeyStore`] credential
+/// labelled `"{label}.{generation}"` (`"storage-key.0"`, `"storage-key.1"`, ... by default).
+///
+/// The integrator provisions them with [`KeyStore::store_credential`] - typically at the factory
+/// for generation 0, and ahead of raising the generation for each rotation - and retires one with
+/// [`KeyStore::delete_credential`] once [`EncryptedStorage::migrate`] has moved everything off it.
+/// Do not back this with a [`SoftKeyStore`](crate::hardware::SoftKeyStore) over the same
+/// [`Storage`] it is meant to protect: the secret would sit in the clear next to the records.
+#[derive(Debug, Clone)]
+pub struct KeyStoreSecret<K> {
+    key_store: K,
+    label: String,
+}
+
+impl<K: KeyStore> KeyStoreSecret<K> {
+    /// Reads secrets from `key_store` under the `"storage-key"` label prefix.
+    pub fn new(key_store: K) -> Self {
+        Self::with_label(key_store, "storage-key")
+    }
+
+    /// Reads secrets from `key_store` under `label` as the prefix instead.
+    pub fn with_label(key_store: K, label: impl Into<String>) -> Self {
+        Self {
+            key_store,
+            label: label.into(),
+        }
+    }
+
+    /// The credential label generation `generation`'s secret is read from.
+    pub fn credential_label(&self, generation: u32) -> String {
+        format!("{}.{generation}", self.label)
+    }
+}
+
+#[async_trait::async_trait]
+impl<K: KeyStore + Send + Sync> StorageSecret for KeyStoreSecret<K> {
+    type Error = K::Error;
+
+    async fn secret(&self, generation: u32) -> Result<Option<Vec<u8>>, Self::Error> {
+        let credential = self
+            .key_store
+            .load_credential(&self.credential_label(generation))
+            .await?;
+        Ok(credential.map(String::into_bytes))
+    }
+}
+
+/// The error type of [`EncryptedStorage`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum EncryptedStorageError<S, K> {
+    /// The wrapped [`Storage`] failed.
+    Storage(S),
+    /// The [`StorageSecret`] failed to produce a secret.
+    Secret(K),
+    /// The [`StorageSecret`] has no secret for this generation - the one being written, or the
+    /// one a stored record was sealed under.
+    MissingSecret {
+        /// The generation asked for.
+        generation: u32,
+    },
+    /// The secret is shorter than [`MIN_STORAGE_SECRET_LEN`].
+    SecretTooShort,
+    /// A stored record failed to authenticate: it was altered, truncated, written under a
+    /// different key name, sealed with a different secret, or is in a format this build does not
+    /// know. Never returned as data, whatever the cause.
+    Unauthenticated,
+    /// A stored record has no encryption header, and
+    /// [`EncryptedStorage::with_plaintext_migration`] is off.
+    Plaintext,
+}
+
+impl<S: core::fmt::Display, K: core::fmt::Display> core::fmt::Display
+    for EncryptedStorageError<S, K>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Storage(error) => write!(f, "storage failed: {error}"),
+            Self::Secret(error) => write!(f, "could not read the storage secret: {error}"),
+            Self::MissingSecret { generation } => {
+                write!(f, "no storage secret for generation {generation}")
+            }
+            Self::SecretTooShort => f.write_str("the storage secret is too short"),
+            Self::Unauthenticated => f.write_str("a stored record failed to authenticate"),
+            Self::Plaintext => f.write_str("a stored record is not encrypted"),
+        }
+    }
+}
+
+impl<S: core::fmt::Debug + core::fmt::Display, K: core::fmt::Debug + core::fmt::Display>
+    core::error::Error for EncryptedStorageError<S, K>
+{
+}
+
+/// What [`EncryptedStorage::migrate`] did with one key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageMigration {
+    /// Nothing is stored under the key.
+    Absent,
+    /// The record was already sealed under the current generation; nothing was written.
+    AlreadyCurrent,
+    /// The record was plaintext or sealed under an older generation, and has been rewritten under
+    /// the current one.
+    Migrated,
+}
+
+/// A [`Storage`] that seals every value with authenticated encryption before it reaches the
+/// wrapped store - see the module docs for the format, key handling, rotation and the plaintext
+/// migration path.
+///
+/// Keys (the names) are passed through unencrypted: [`Storage`] implementations address records
+/// by them, and this crate's are fixed strings that say nothing a firmware image does not.
+pub struct EncryptedStorage<S, K> {
+    inner: S,
+    secret: K,
+    generation: u32,
+    plaintext_migration: bool,
+}
+
+/// The two keys derived from one generation's secret.
+struct DerivedKeys {
+    cipher: XChaCha20Poly1305,
+    nonce_key: [u8; 32],
+}
+
+impl<S: Storage, K: StorageSecret> EncryptedStorage<S, K> {
+    /// Seals values written to `inner` with keys derived from `secret`, at generation 0 and with
+    /// plaintext records refused.
+    pub fn new(inner: S, secret: K) -> Self {
+        Self {
+            inner,
+            secret,
+            generation: 0,
+            plaintext_migration: false,
+        }
+    }
+
+    /// Writes under `generation` instead. Records sealed under any other generation stay
+    /// readable for as long as `secret` still has that generation's secret.
+    pub fn with_generation(mut self, generation: u32) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    /// Reads records without an encryption header as plaintext rather than refusing them, so a
+    /// charge point that ran without encryption can [`Self::migrate`] its records.
+    pub fn with_plaintext_migration(mut self) -> Self {
+        self.plaintext_migration = true;
+        self
+    }
+
+    /// Gives back the wrapped store.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Rewrites `key`'s record under the current generation if it is plaintext or sealed under
+    /// an older one. A record that fails to authenticate is reported, not overwritten.
+    pub async fn migrate(
+        &self,
+        key: &str,
+    ) -> Result<StorageMigration, EncryptedStorageError<S::Error, K::Error>> {
+        let Some(stored) = self
+            .inner
+            .get(key)
+            .await
+            .map_err(EncryptedStorageError::Storage)?
+        else {
+            return Ok(StorageMigration::Absent);
+        };
+        if sealed_generation(&stored) == Some(self.generation) {
+            self.open(key, &stored).await?;
+            return Ok(StorageMigration::AlreadyCurrent);
+        }
+        let value = self.open(key, &stored).await?;
+        self.inner
+            .set(key, &self.seal(key, &value).await?)
+            .await
+            .map_err(EncryptedStorageError::Storage)?;
+        Ok(StorageMigration::Migrated)
+    }
+
+    async fn derive(
+        &self,
+        generation: u32,
+    ) -> Result<DerivedKeys, EncryptedStorageError<S::Error, K::Error>> {
+        let secret = self
+            .secret
+            .secret(generation)
+            .await
+            .map_err(EncryptedStorageError::Secret)?
+            .ok_or(EncryptedStorageError::MissingSecret { generation })?;
+        if secret.len() < MIN_STORAGE_SECRET_LEN {
+            return Err(EncryptedStorageError::SecretTooShort);
+        }
+        let mut okm = [0u8; 64];
+        hkdf::Hkdf::<Sha256>::new(Some(HKDF_SALT), &secret)
+            .expand_multi_info(&[b"keys", &generation.to_le_bytes()], &mut okm)
+            .map_err(|_| EncryptedStorageError::SecretTooShort)?;
+        let mut nonce_key = [0u8; 32];
+        nonce_key.copy_from_slice(&okm[32..]);
+        Ok(DerivedKeys {
+            cipher: XChaCha20Poly1305::new(okm[..32].into()),
+            nonce_key,
+        })
+    }
+
+    async fn seal(
+        &self,
+        key: &str,
+        value: &[u8],
+    ) -> Result<Vec<u8>, EncryptedStorageError<S::Error, K::Error>> {
+        let keys = self.derive(self.generation).await?;
+        let mut record = Vec::with_capacity(HEADER_LEN + value.len() + TAG_LEN);
+        record.extend_from_slice(&MAGIC);
+        record.push(FORMAT);
+        record.extend_from_slice(&self.generation.to_le_bytes());
+
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&keys.nonce_key)
+            .map_err(|_| EncryptedStorageError::SecretTooShort)?;
+        mac.update(&(key.len() as u32).to_le_bytes());
+        mac.update(key.as_bytes());
+        mac.update(value);
+        let nonce = mac.finalize().into_bytes();
+        let nonce = XNonce::from_slice(&nonce[..NONCE_LEN]);
+        record.extend_from_slice(nonce);
+
+        let aad = associated_data(&record[..PREFIX_LEN], key);
+        let ciphertext = keys
+            .cipher
+            .encrypt(
+                nonce,
+                Payload {
+                    msg: value,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| EncryptedStorageError::Unauthenticated)?;
+        record.extend_from_slice(&ciphertext);
+        Ok(record)
+    }
+
+    async fn open(
+        &self,
+        key: &str,
+        stored: &[u8],
+    ) -> Result<Vec<u8>, EncryptedStorageError<S::Error, K::Error>> {
+        if !stored.starts_with(&MAGIC) {
+            return if self.plaintext_migration {
+                Ok(stored.to_vec())
+            } else {
+                Err(EncryptedStorageError::Plaintext)
+            };
+        }
+        let generation = sealed_generation(stored).ok_or(EncryptedStorageError::Unauthenticated)?;
+        if stored.len() < HEADER_LEN + TAG_LEN {
+            return Err(EncryptedStorageError::Unauthenticated);
+        }
+        let keys = self.derive(generation).await?;
+        let aad = associated_data(&stored[..PREFIX_LEN], key);
+        keys.cipher
+            .decrypt(
+                XNonce::from_slice(&stored[PREFIX_LEN..HEADER_LEN]),
+                Payload {
+                    msg: &stored[HEADER_LEN..],
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| EncryptedStorageError::Unauthenticated)
+    }
+}
+
+/// The generation a sealed record names, or `None` if it is not one this build can open.
+fn sealed_generation(stored: &[u8]) -> Option<u32> {
+    if stored.len() < PREFIX_LEN || stored[..4] != MAGIC || stored[4] != FORMAT {
+        return None;
+    }
+    Some(u32::from_le_bytes(stored[5..PREFIX_LEN].try_into().ok()?))
+}
+
+fn associated_data(prefix: &[u8], key: &str) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(prefix.len() + key.len());
+    aad.extend_from_slice(prefix);
+    aad.extend_from_slice(key.as_bytes());
+    aad
+}
+
+#[async_trait::async_trait]
+impl<S: Storage + Send + Sync, K: StorageSecret + Send + Sync> Storage for EncryptedStorage<S, K> {
+    type Error = EncryptedStorageError<S::Error, K::Error>;
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        match self
+            .inner
+            .get(key)
+            .await
+            .map_err(EncryptedStorageError::Storage)?
+        {
+            Some(stored) => self.open(key, &stored).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn set(&self, key: &str, value: &[u8]) -> Result<(), Self::Error> {
+        let record = self.seal(key, value).await?;
+        self.inner
+            .set(key, &record)
+            .await
+            .map_err(EncryptedStorageError::Storage)
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), Self::Error> {
+        self.inner
+            .remove(key)
+            .await
+            .map_err(EncryptedStorageError::Storage)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::hardware::{
+        AtomicStorage, InMemoryStorage, InMemoryStorageError, PublicKey, SignatureAlgorithm,
+        SoftKeyStore, SoftwareCrypto,
+    };
+    use alloc::sync::Arc;
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn sealed(inner: Arc<InMemoryStorage>) -> EncryptedStorage<Arc<InMemoryStorage>, DeviceSecret> {
+        EncryptedStorage::new(inner, DeviceSecret::new(SECRET))
+    }
+
+    #[tokio::test]
+    async fn a_value_round_trips_and_never_reaches_the_medium_in_the_clear() {
+        let medium = Arc::new(InMemoryStorage::new());
+        let storage = sealed(medium.clone());
+
+        storage
+            .set("transaction", b"idToken=DEADBEEF")
+            .await
+            .unwrap();
+
+        let raw = medium.get("transaction").await.unwrap().unwrap();
+        assert!(!raw.windows(8).any(|window| window == b"DEADBEEF"));
+        assert_eq!(
+            storage.get("transaction").await.unwrap(),
+            Some(b"idToken=DEADBEEF".to_vec())
+        );
+        storage.remove("transaction").await.unwrap();
+        assert_eq!(storage.get("transaction").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn a_record_moved_to_another_key_fails_to_authenticate() {
+        let medium = Arc::new(InMemoryStorage::new());
+        let storage = sealed(medium.clone());
+        storage.set("keys", b"old index").await.unwrap();
+
+        let raw = medium.get("keys").await.unwrap().unwrap();
+        medium.set("credentials", &raw).await.unwrap();
+
+        assert_eq!(
+            storage.get("credentials").await,
+            Err(EncryptedStorageError::Unauthenticated)
+        );
+    }
+
+    #[tokio::test]
+    async fn a_tampered_or_foreign_record_fails_to_authenticate() {
+        let medium = Arc::new(InMemoryStorage::new());
+        let storage = sealed(medium.clone());
+        storage.set("key", b"value").await.unwrap();
+
+        let mut raw = medium.get("key").await.unwrap().unwrap();
+        *raw.last_mut().unwrap() ^= 1;
+        medium.set("key", &raw).await.unwrap();
+        assert_eq!(
+            storage.get("key").await,
+            Err(EncryptedStorageError::Unauthenticated)
+        );
+
+        storage.set("key", b"value").await.unwrap();
+        let other =
+            EncryptedStorage::new(medium, DeviceSecret::new(&b"another secret, 16+ bytes"[..]));
+        assert_eq!(
+            other.get("key").await,
+            Err(EncryptedStorageError::Unauthenticated)
+        );
+    }
+
+    #[tokio::test]
+    async fn a_short_secret_is_refused() {
+        let storage =
+            EncryptedStorage::new(InMemoryStorage::new(), DeviceSecret::new(&b"short"[..]));
+        assert_eq!(
+            storage.set("key", b"value").await,
+            Err(EncryptedStorageError::SecretTooShort)
+        );
+    }
+
+    #[tokio::test]
+    async fn plaintext_records_are_refused_unless_migration_is_on() {
+        let medium = Arc::new(InMemoryStorage::new());
+        medium
+            .set("transaction", b"{\"plain\":true}")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sealed(medium.clone()).get("transaction").await,
+            Err(EncryptedStorageError::Plaintext)
+        );
+
+        let migrating = sealed(medium.clone()).with_plaintext_migration();
+        assert_eq!(
+            migrating.get("transaction").await.unwrap(),
+            Some(b"{\"plain\":true}".to_vec())
+        );
+        assert_eq!(
+            migrating.migrate("transaction").await,
+            Ok(StorageMigration::Migrated)
+        );
+        assert_eq!(
+            migrating.migrate("absent").await,
+            Ok(StorageMigration::Absent)
+        );
+
+        let raw = medium.get("transaction").await.unwrap().unwrap();
+        assert!(raw.starts_with(&MAGIC));
+        assert_eq!(
+            sealed(medium).get("transaction").await.unwrap(),
+            Some(b"{\"plain\":true}".to_vec())
+        );
+    }
+
+    /// Only lets a test reach [`SoftKeyStore`]'s credentials; no key pair is ever generated.
+    struct NoCrypto;
+
+    impl SoftwareCrypto for NoCrypto {
+        type Error = InMemoryStorageError;
+
+        fn generate_key_pair(
+            &self,
+            _algorithm: SignatureAlgorithm,
+        ) -> Result<(Vec<u8>, PublicKey), Self::Error> {
+            unreachable!("these tests never generate a key pair")
+        }
+
+        fn sign(
+            &self,
+            _algorithm: SignatureAlgorithm,
+            _private_key: &[u8],
+            _digest: &[u8],
+        ) -> Result<Vec<u8>, Self::Error> {
+            unreachable!("these tests never sign")
+        }
+
+        fn supported_algorithms(&self) -> &[SignatureAlgorithm] {
+            &[]
+        }
+    }
+
+    #[tokio::test]
+    async fn rotating_re_keys_records_and_retires_the_old_secret() {
+        let key_store = Arc::new(SoftKeyStore::new(InMemoryStorage::new(), NoCrypto));
+        key_store
+            .store_credential("storage-key.0", "generation zero secret")
+            .await
+            .unwrap();
+        let medium = Arc::new(InMemoryStorage::new());
+        let storage = EncryptedStorage::new(medium.clone(), KeyStoreSecret::new(key_store.clone()));
+        storage.set("security-log", b"events").await.unwrap();
+
+        key_store
+            .store_credential("storage-key.1", "generation one secret!")
+            .await
+            .unwrap();
+        let rotated = EncryptedStorage::new(medium.clone(), KeyStoreSecret::new(key_store.clone()))
+            .with_generation(1);
+        assert_eq!(
+            rotated.get("security-log").await.unwrap(),
+            Some(b"events".to_vec())
+        );
+        assert_eq!(
+            rotated.migrate("security-log").await,
+            Ok(StorageMigration::Migrated)
+        );
+        assert_eq!(
+            rotated.migrate("security-log").await,
+            Ok(StorageMigration::AlreadyCurrent)
+        );
+
+        key_store.delete_credential("storage-key.0").await.unwrap();
+        assert_eq!(
+            rotated.get("security-log").await.unwrap(),
+            Some(b"events".to_vec())
+        );
+        assert_eq!(
+            storage.set("other", b"value").await,
+            Err(EncryptedStorageError::MissingSecret { generation: 0 })
+        );
+    }
+
+    /// A store whose writes can be armed to stop halfway, as a power cut would leave them.
+    #[derive(Default)]
+    struct TornWrites {
+        inner: InMemoryStorage,
+        tear_next: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl Storage for TornWrites {
+        type Error = InMemoryStorageError;
+
+        async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+            self.inner.get(key).await
+        }
+
+        async fn set(&self, key: &str, value: &[u8]) -> Result<(), Self::Error> {
+            if self
+                .tear_next
+                .swap(false, std::sync::atomic::Ordering::SeqCst)
+            {
+                self.inner.set(key, &value[..value.len() / 2]).await
+            } else {
+                self.inner.set(key, value).await
+            }
+        }
+
+        async fn remove(&self, key: &str) -> Result<(), Self::Error> {
+            self.inner.remove(key).await
+        }
+    }
+
+    #[tokio::test]
+    async fn a_torn_write_under_atomic_storage_still_reads_the_previous_value() {
+        let medium = Arc::new(TornWrites::default());
+        let storage = EncryptedStorage::new(
+            AtomicStorage::new(medium.clone()),
+            DeviceSecret::new(SECRET),
+        );
+        storage.set("transaction", b"before").await.unwrap();
+
+        medium
+            .tear_next
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+        storage
+            .set("transaction", b"after the power cut")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage.get("transaction").await.unwrap(),
+            Some(b"before".to_vec())
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! [`FileSink`]: a [`DownloadSink`] that writes each download to a file, for std targets whose
+//! firmware images live on a filesystem, and the [`FirmwareImage`] that reads the finished one
+//! back.
+
+use super::file_transfer::DownloadSink;
+use super::firmware::FirmwareImage;
+use alloc::string::{String, ToString};
+use core::cell::RefCell;
+use embassy_sync::blocking_mutex::Mutex as BlockingMutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// A [`DownloadSink`] writing to one file on a filesystem.
+///
+/// Data goes to `<path>.part` and is renamed over `path` only by [`DownloadSink::finish`], after
+/// an `fsync` - so `path` is either absent, the previous complete download, or the new complete
+/// one, never a partial file. A download that fails keeps its `.part` for the next attempt to
+/// resume.
+///
+/// What it resumes is remembered in memory only: after a restart, the first download starts
+/// `<path>.part` over. A restarted charge point has forgotten the `UpdateFirmware` that was in
+/// progress anyway, and a `.part` of unknown origin is not worth trusting.
+pub struct FileSink {
+    path: PathBuf,
+    partial: BlockingMutex<CriticalSectionRawMutex, RefCell<Partial>>,
+}
+
+#[derive(Default)]
+struct Partial {
+    /// The URL whose download `<path>.part` holds the start of.
+    url: Option<String>,
+    /// Open between `begin` and `finish`.
+    file: Option<File>,
+}
+
+impl FileSink {
+    /// A sink that writes downloads to `path`. Nothing is touched until the first download.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            partial: BlockingMutex::new(RefCell::new(Partial::default())),
+        }
+    }
+
+    /// Where a finished download ends up.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn part_path(&self) -> PathBuf {
+        let mut part = self.path.as_os_str().to_owned();
+        part.push(".part");
+        PathBuf::from(part)
+    }
+}
+
+impl core::fmt::Debug for FileSink {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FileSink")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl DownloadSink for FileSink {
+    type Error = io::Error;
+
+    async fn resume_offset(&self, url: &str) -> Result<u64, Self::Error> {
+        if !self
+            .partial
+            .lock(|partial| partial.borrow().url.as_deref() == Some(url))
+        {
+            return Ok(0);
+        }
+        match fs::metadata(self.part_path()) {
+            Ok(metadata) => Ok(metadata.len()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(0),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn begin(&self, url: &str, offset: u64, _total: Option<u64>) -> Result<(), Self::Error> {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(offset == 0)
+            .open(self.part_path())?;
+        if offset > 0 {
+            file.set_len(offset)?;
+            file.seek(SeekFrom::Start(offset))?;
+        }
+        self.partial.lock(|partial| {
+            *partial.borrow_mut() = Partial {
+                url: Some(url.to_string()),
+                file: Some(file),
+            };
+        });
+        Ok(())
+    }
+
+    async fn write(&self, chunk: &[u8]) -> Result<(), Self::Error> {
+        self.partial
+            .lock(|partial| match partial.borrow_mut().file.as_mut() {
+                Some(file) => file.write_all(chunk),
+                None => Err(io::Error::other("a download wrote before it began")),
+            })
+    }
+
+    async fn finish(&self) -> Result<(), Self::Error> {
+        let file = self
+            .partial
+            .lock(|partial| partial.borrow_mut().file.take())
+            .ok_or_else(|| io::Error::other("a download finished before it began"))?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(self.part_path(), &self.path)?;
+        // The rename is only durable once the directory holding it is; see `FileStorage`.
+        #[cfg(unix)]
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            File::open(parent)?.sync_all()?;
+        }
+        self.partial
+            .lock(|partial| *partial.borrow_mut() = Partial::default());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl FirmwareImage for FileSink {
+    type Error = io::Error;
+
+    async fn read_image(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        file.read(buf)
+    }
+}
@@ -0,0 +1,398 @@
+//! A `std`-only [`Storage`] backed by a directory of files, one file per key.
+//!
+//! Every std integrator needs this, and the part each one gets subtly wrong is not the mapping
+//! from keys to files - it is meeting [`Storage`]'s "durable by the time `set` returns" contract.
+//! A plain `fs::write` is neither durable (the bytes sit in the page cache) nor safe against a
+//! power cut (the file is truncated before it is rewritten, so a cut in between leaves it empty).
+//! [`FileStorage`] does the whole sequence: write a temporary file, `fsync` it, `rename` it over
+//! the target, then `fsync` the directory so the rename itself is on disk.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use super::Storage;
+
+/// Filename prefix for a write in progress. `~` is never left unescaped by [`escape_key`], so no
+/// key's file can ever start with it and a leftover temporary file can never be mistaken for a
+/// value (or collide with one).
+const TEMP_PREFIX: &str = "~tmp-";
+
+/// The longest escaped filename [`FileStorage`] will create, temporary prefix included. 255 bytes
+/// is the per-component limit on every filesystem a charge point is likely to run (ext4, f2fs,
+/// FAT with long names, NTFS); refusing a key up front is better than a `set` that fails on one
+/// filesystem and not another.
+const MAX_FILE_NAME_LEN: usize = 255;
+
+/// A [`Storage`] that maps each key to one file under a root directory.
+///
+/// # Durability
+///
+/// [`Storage::set`] returns `Ok` only once the value is on disk: the bytes are written to a
+/// temporary file in the same directory, that file is `fsync`ed, renamed over the key's file, and
+/// the directory itself is then `fsync`ed so the rename survives a power cut too. `remove` likewise
+/// `fsync`s the directory after unlinking. On a POSIX filesystem the `rename` is atomic, so a cut
+/// at any point leaves either the old value or the new one - but that is a property of the
+/// filesystem, not something this type relies on: wrap it in
+/// [`AtomicStorage`](crate::hardware::AtomicStorage) like any other backend, exactly as
+/// [`Storage`]'s docs ask.
+///
+/// A temporary file left behind by a cut mid-`set` is never read back as a value, and
+/// [`FileStorage::open`] deletes any it finds.
+///
+/// # Key mapping
+///
+/// Keys are flattened into a single directory, with every byte outside `[A-Za-z0-9_-.]` written as
+/// `%XX` (so `ocpp-cp/txn/1/1` becomes `ocpp-cp%2Ftxn%2F1%2F1`). A leading `.` is escaped too, so
+/// no key can name `.`, `..` or a hidden file, and no key can reach outside the root directory
+/// whatever it contains. A key whose escaped name would exceed 255 bytes is refused with
+/// [`FileStorageError::KeyTooLong`] rather than truncated.
+///
+/// # Blocking
+///
+/// Every operation is plain blocking `std::fs` I/O, run on the calling task. The records this
+/// crate persists are small (a transaction record, a queue snapshot), so that is a few
+/// milliseconds on the flash a charge point has, and it keeps this type usable under any
+/// executor rather than only Tokio. An integrator whose storage medium is slow enough to matter
+/// can wrap it in their own `spawn_blocking` adapter.
+///
+/// # Concurrency
+///
+/// Like [`Storage`] itself, a single writer per key is assumed - true of every caller in this
+/// crate. Writes to different keys through the same (or a cloned) `FileStorage` are independent.
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+/// The error type of [`FileStorage`].
+#[derive(Debug)]
+pub enum FileStorageError {
+    /// The escaped key would exceed the filesystem's filename length limit.
+    KeyTooLong,
+    /// The underlying filesystem operation failed.
+    Io(io::Error),
+}
+
+impl core::fmt::Display for FileStorageError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::KeyTooLong => f.write_str("storage key is too long to map to a file name"),
+            Self::Io(error) => write!(f, "file storage I/O failed: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for FileStorageError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::KeyTooLong => None,
+            Self::Io(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for FileStorageError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl FileStorage {
+    /// Opens (creating it if needed) a store rooted at `root`, and deletes any temporary file an
+    /// interrupted `set` left behind.
+    ///
+    /// The directory should be dedicated to this store: every file in it is either a key's value
+    /// or a temporary file this type is free to delete.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, FileStorageError> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        for entry in fs::read_dir(&root)? {
+            let entry = entry?;
+            if entry.file_name().to_string_lossy().starts_with(TEMP_PREFIX) {
+                fs::remove_file(entry.path())?;
+            }
+        }
+        Ok(Self { root })
+    }
+
+    /// The directory this store keeps its files in.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn key_path(&self, key: &str) -> Result<PathBuf, FileStorageError> {
+        let name = escape_key(key);
+        if name.len() + TEMP_PREFIX.len() > MAX_FILE_NAME_LEN {
+            return Err(FileStorageError::KeyTooLong);
+        }
+        Ok(self.root.join(name))
+    }
+
+    /// `fsync`s the root directory, so a `rename`/unlink already done in it is itself durable.
+    /// Only meaningful (and only possible - a directory cannot be opened as a `File` elsewhere)
+    /// on Unix; Windows has no equivalent and NTFS journals the metadata change itself.
+    fn sync_root(&self) -> io::Result<()> {
+        #[cfg(unix)]
+        File::open(&self.root)?.sync_all()?;
+        Ok(())
+    }
+}
+
+/// Maps a storage key to a single, safe file name - see [`FileStorage`]'s "Key mapping".
+fn escape_key(key: &str) -> String {
+    // An empty key still needs a file of its own; `%` alone can never come out of escaping a
+    // non-empty key, since every escape is exactly three characters.
+    if key.is_empty() {
+        return String::from("%");
+    }
+    let mut name = String::with_capacity(key.len());
+    for (index, byte) in key.bytes().enumerate() {
+        let unreserved = byte.is_ascii_alphanumeric()
+            || byte == b'_'
+            || byte == b'-'
+            || (byte == b'.' && index > 0);
+        if unreserved {
+            name.push(char::from(byte));
+        } else {
+            name.push('%');
+            name.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+            name.push(char::from(HEX_DIGITS[usize::from(byte & 0x0F)]));
+        }
+    }
+    name
+}
+
+const HEX_DIGITS: [u8; 16] = *b"0123456789ABCDEF";
+
+#[async_trait::async_trait]
+impl Storage for FileStorage {
+    type Error = FileStorageError;
+
+    async fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Self::Error> {
+        match fs::read(self.key_path(key)?) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    async fn set(&self, key: &str, value: &[u8]) -> Result<(), Self::Error> {
+        let path = self.key_path(key)?;
+        let temp_path = self
+            .root
+            .join(alloc::format!("{TEMP_PREFIX}{}", escape_key(key)));
+        let written = (|| {
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&temp_path)?;
+            file.write_all(value)?;
+            file.sync_all()?;
+            fs::rename(&temp_path, &path)
+        })();
+        if let Err(error) = written {
+            // Best effort: a leftover is harmless (never read, deleted by the next `open`), so
+            // failing to clean it up must not mask the error that actually matters.
+            let _ = fs::remove_file(&temp_path);
+            return Err(error.into());
+        }
+        self.sync_root()?;
+        Ok(())
+    }
+
+    async fn remove(&self, key: &str) -> Result<(), Self::Error> {
+        match fs::remove_file(self.key_path(key)?) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(error.into()),
+        }
+        self.sync_root()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hardware::AtomicStorage;
+
+    /// A fresh, empty directory under the system temp dir, removed again on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(alloc::format!(
+                "ocpp-cp-file-storage-{name}-{}",
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&path);
+            Self(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn file_storage_round_trips_a_value() {
+        let dir = TempDir::new("round-trip");
+        let storage = FileStorage::open(&dir.0).unwrap();
+        assert_eq!(storage.get("key").await.unwrap(), None);
+
+        storage.set("key", b"value").await.unwrap();
+        assert_eq!(storage.get("key").await.unwrap(), Some(b"value".to_vec()));
+
+        storage.set("key", b"updated").await.unwrap();
+        assert_eq!(storage.get("key").await.unwrap(), Some(b"updated".to_vec()));
+
+        storage.remove("key").await.unwrap();
+        assert_eq!(storage.get("key").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn file_storage_remove_of_missing_key_is_not_an_error() {
+        let dir = TempDir::new("remove-missing");
+        let storage = FileStorage::open(&dir.0).unwrap();
+        storage.remove("missing").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_value_survives_reopening_the_directory() {
+        let dir = TempDir::new("reopen");
+        FileStorage::open(&dir.0)
+            .unwrap()
+            .set("ocpp-cp/txn/1/1", b"record")
+            .await
+            .unwrap();
+
+        let reopened = FileStorage::open(&dir.0).unwrap();
+        assert_eq!(
+            reopened.get("ocpp-cp/txn/1/1").await.unwrap(),
+            Some(b"record".to_vec())
+        );
+    }
+
+    #[test]
+    fn keys_are_escaped_into_a_single_flat_file_name() {
+        assert_eq!(escape_key("ocpp-cp/txn/1/1"), "ocpp-cp%2Ftxn%2F1%2F1");
+        assert_eq!(escape_key("key.a"), "key.a");
+        assert_eq!(escape_key(".."), "%2E.");
+        assert_eq!(escape_key("."), "%2E");
+        assert_eq!(escape_key(""), "%");
+        assert_eq!(escape_key("~tmp-key"), "%7Etmp-key");
+        assert_eq!(escape_key("a\\b"), "a%5Cb");
+    }
+
+    #[tokio::test]
+    async fn no_key_escapes_the_root_directory() {
+        let dir = TempDir::new("traversal");
+        let storage = FileStorage::open(dir.0.join("root")).unwrap();
+        for key in ["../outside", "/etc/passwd", "..", ".", "a/../../b"] {
+            storage.set(key, b"value").await.unwrap();
+            assert_eq!(storage.get(key).await.unwrap(), Some(b"value".to_vec()));
+        }
+        let entries: Vec<_> = fs::read_dir(&dir.0).unwrap().collect();
+        assert_eq!(
+            entries.len(),
+            1,
+            "only the root directory itself may exist beside it"
+        );
+    }
+
+    #[tokio::test]
+    async fn distinct_keys_never_share_a_file() {
+        let dir = TempDir::new("distinct");
+        let storage = FileStorage::open(&dir.0).unwrap();
+        // Pairs that a naive "replace `/` with `_`" mapping would collide.
+        storage.set("a/b", b"slash").await.unwrap();
+        storage.set("a_b", b"underscore").await.unwrap();
+        storage.set("a%2Fb", b"literal escape").await.unwrap();
+        storage.set("", b"empty").await.unwrap();
+        storage.set("%", b"percent").await.unwrap();
+
+        assert_eq!(storage.get("a/b").await.unwrap(), Some(b"slash".to_vec()));
+        assert_eq!(
+            storage.get("a_b").await.unwrap(),
+            Some(b"underscore".to_vec())
+        );
+        assert_eq!(
+            storage.get("a%2Fb").await.unwrap(),
+            Some(b"literal escape".to_vec())
+        );
+        assert_eq!(storage.get("").await.unwrap(), Some(b"empty".to_vec()));
+        assert_eq!(storage.get("%").await.unwrap(), Some(b"percent".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn an_over_long_key_is_refused_rather_than_truncated() {
+        let dir = TempDir::new("long-key");
+        let storage = FileStorage::open(&dir.0).unwrap();
+        let key = "k".repeat(MAX_FILE_NAME_LEN);
+        assert!(matches!(
+            storage.set(&key, b"value").await,
+            Err(FileStorageError::KeyTooLong)
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_temporary_file_left_by_an_interrupted_set_is_ignored_and_cleaned_up() {
+        let dir = TempDir::new("leftover");
+        let storage = FileStorage::open(&dir.0).unwrap();
+        storage.set("key", b"committed").await.unwrap();
+
+        // A cut after the temporary file was written but before the rename: the new bytes exist
+        // on disk, just not under the key's name.
+        let leftover = dir.0.join(alloc::format!("{TEMP_PREFIX}key"));
+        fs::write(&leftover, b"half-writ").unwrap();
+
+        let reopened = FileStorage::open(&dir.0).unwrap();
+        assert_eq!(
+            reopened.get("key").await.unwrap(),
+            Some(b"committed".to_vec())
+        );
+        assert!(
+            !leftover.exists(),
+            "open must delete the stale temporary file"
+        );
+    }
+
+    #[tokio::test]
+    async fn atomic_storage_over_file_storage_survives_a_torn_slot_file() {
+        let dir = TempDir::new("torn");
+        let storage = AtomicStorage::new(FileStorage::open(&dir.0).unwrap());
+        storage.set("key", b"first value").await.unwrap();
+        storage
+            .set("key", b"second value, longer than first")
+            .await
+            .unwrap();
+
+        // Tear whichever slot file holds the latest record, the way a medium without an atomic
+        // rename (or a bit-rotted sector) would leave it.
+        let latest = ["key.a", "key.b"]
+            .into_iter()
+            .map(|slot| dir.0.join(escape_key(slot)))
+            .max_by_key(|path| fs::metadata(path).map(|m| m.len()).unwrap_or(0))
+            .unwrap();
+        let bytes = fs::read(&latest).unwrap();
+        fs::write(&latest, &bytes[..bytes.len() / 2]).unwrap();
+
+        let reopened = AtomicStorage::new(FileStorage::open(&dir.0).unwrap());
+        assert_eq!(
+            reopened.get("key").await.unwrap(),
+            Some(b"first value".to_vec()),
+            "a torn slot must not be observable - the previous complete value must win"
+        );
+        reopened.set("key", b"third value").await.unwrap();
+        assert_eq!(
+            reopened.get("key").await.unwrap(),
+            Some(b"third value".to_vec())
+        );
+    }
+}
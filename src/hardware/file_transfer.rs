@@ -24,6 +24,14 @@
 //!
 //! Upload is the asymmetric one, because the two things a charge point uploads have different
 //! owners - see [`UploadSource`].
+//!
+//! # Ready-made backends
+//!
+//! Hardware surface or not, HTTP and FTP are common enough that this crate ships backends for
+//! them: `HttpFileTransfer` and `FtpFileTransfer`, behind the `http-file-transfer` and
+//! `ftp-file-transfer` features. Each speaks its protocol and leaves the two integrator-owned ends
+//! to [`DownloadSink`] (where a downloaded image goes) and [`LogSource`] (where a diagnostics log
+//! comes from); [`SchemeRouter`] puts both behind the one transfer a builder registration takes.
 
 use alloc::boxed::Box;
 use alloc::string::String;
@@ -281,6 +289,239 @@ impl FileTransfer for NoFileTransfer {
     }
 }
 
+/// Two [`FileTransfer`]s behind one, chosen per transfer by the URL's scheme.
+///
+/// [`crate::ChargePointBuilder::log_uploads`] and
+/// [`crate::ChargePointBuilder::firmware_updates`] each take a single transfer, but a CSMS may hand
+/// out `https://` for one request and `ftp://` for the next. URLs whose scheme is in `schemes` go
+/// to `first`; everything else goes to `second`, which is then the one to fail on a scheme
+/// neither supports. Nest routers for a third backend.
+#[derive(Debug, Clone)]
+pub struct SchemeRouter<A, B> {
+    schemes: &'static [&'static str],
+    first: A,
+    second: B,
+}
+
+impl<A, B> SchemeRouter<A, B> {
+    /// Sends URLs with any of `schemes` (compared case-insensitively) to `first`, and the rest to
+    /// `second`.
+    pub fn new(schemes: &'static [&'static str], first: A, second: B) -> Self {
+        Self {
+            schemes,
+            first,
+            second,
+        }
+    }
+
+    fn routes_to_first(&self, url: &str) -> bool {
+        url.split_once(':').is_some_and(|(scheme, _)| {
+            self.schemes
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(scheme))
+        })
+    }
+}
+
+/// The error of whichever backend a [`SchemeRouter`] sent the transfer to.
+#[derive(Debug)]
+pub enum SchemeRouterError<A, B> {
+    /// The first backend's error.
+    First(A),
+    /// The second backend's error.
+    Second(B),
+}
+
+impl<A: core::fmt::Display, B: core::fmt::Display> core::fmt::Display for SchemeRouterError<A, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::First(error) => error.fmt(f),
+            Self::Second(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<A, B> core::error::Error for SchemeRouterError<A, B>
+where
+    A: core::error::Error + 'static,
+    B: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::First(error) => error.source(),
+            Self::Second(error) => error.source(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<A, B> FileTransfer for SchemeRouter<A, B>
+where
+    A: FileTransfer + Send + Sync,
+    B: FileTransfer + Send + Sync,
+{
+    type Error = SchemeRouterError<A::Error, B::Error>;
+
+    async fn download(
+        &self,
+        url: &str,
+        progress: &TransferProgress<'_>,
+    ) -> Result<(), Self::Error> {
+        if self.routes_to_first(url) {
+            self.first
+                .download(url, progress)
+                .await
+                .map_err(SchemeRouterError::First)
+        } else {
+            self.second
+                .download(url, progress)
+                .await
+                .map_err(SchemeRouterError::Second)
+        }
+    }
+
+    async fn upload(
+        &self,
+        url: &str,
+        source: UploadSource<'_>,
+        progress: &TransferProgress<'_>,
+    ) -> Result<(), Self::Error> {
+        if self.routes_to_first(url) {
+            self.first
+                .upload(url, source, progress)
+                .await
+                .map_err(SchemeRouterError::First)
+        } else {
+            self.second
+                .upload(url, source, progress)
+                .await
+                .map_err(SchemeRouterError::Second)
+        }
+    }
+}
+
+/// Where a [`FileTransfer`] implementation that this crate ships puts the bytes it downloads.
+///
+/// The crate's own transfer backends (such as `HttpFileTransfer`, behind the
+/// `http-file-transfer` feature) know how to speak to a server but not where a firmware image
+/// belongs on a given board - a file, a raw flash partition, the inactive slot of an A/B pair.
+/// This is the seam between the two. Implement it once for your storage and any backend can
+/// stream into it.
+///
+/// # Resuming
+///
+/// [`Self::resume_offset`] is how an interrupted download continues where it stopped rather
+/// than starting over. OCPP's `retries` are the caller's (see [`FileTransfer`]), so a retry is a
+/// fresh call to [`FileTransfer::download`]; a sink that remembers what it already holds lets that
+/// call ask the server for only the rest. A sink that cannot resume returns `0` and every attempt
+/// starts from the beginning, which is slower but still correct.
+///
+/// # Completeness
+///
+/// Nothing a sink holds is a complete image until [`Self::finish`] has returned. A download that
+/// fails or is cancelled stops calling the sink mid-stream, and a sink must not let a later reader
+/// mistake that partial content for the real thing - the same promise [`FileTransfer`] makes about
+/// cancellation.
+#[async_trait::async_trait]
+pub trait DownloadSink {
+    /// The error type returned when the sink cannot accept data.
+    type Error: core::error::Error + Send + Sync + 'static;
+
+    /// How many bytes of `url` this sink already holds from an earlier attempt that did not
+    /// finish. `0` if it holds none, or holds a partial download of a different URL.
+    async fn resume_offset(&self, url: &str) -> Result<u64, Self::Error>;
+
+    /// Prepares to receive `url` starting at byte `offset`, discarding anything held beyond it.
+    ///
+    /// `offset` is either `0` (start over) or the value [`Self::resume_offset`] returned: a server
+    /// may decline to resume, and then the download starts again from the beginning. `total` is
+    /// the full size of the file when the server said.
+    async fn begin(&self, url: &str, offset: u64, total: Option<u64>) -> Result<(), Self::Error>;
+
+    /// Appends `chunk` to what has been received so far.
+    async fn write(&self, chunk: &[u8]) -> Result<(), Self::Error>;
+
+    /// Marks the download complete. Only after this returns may the content be treated as a whole
+    /// file.
+    async fn finish(&self) -> Result<(), Self::Error>;
+}
+
+#[async_trait::async_trait]
+impl<T: DownloadSink + Send + Sync + ?Sized> DownloadSink for alloc::sync::Arc<T> {
+    type Error = T::Error;
+
+    async fn resume_offset(&self, url: &str) -> Result<u64, Self::Error> {
+        (**self).resume_offset(url).await
+    }
+
+    async fn begin(&self, url: &str, offset: u64, total: Option<u64>) -> Result<(), Self::Error> {
+        (**self).begin(url, offset, total).await
+    }
+
+    async fn write(&self, chunk: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(chunk).await
+    }
+
+    async fn finish(&self) -> Result<(), Self::Error> {
+        (**self).finish().await
+    }
+}
+
+/// Where a [`FileTransfer`] implementation that this crate ships reads an
+/// [`UploadSource::Local`] log from.
+///
+/// The counterpart of [`DownloadSink`] for the other direction: the backend owns the protocol,
+/// the integrator owns the log. Reads are by offset rather than through a stream so a backend can
+/// send the same log again - after a redirect, or on the caller's next retry - without the
+/// integrator having to rewind anything.
+#[async_trait::async_trait]
+pub trait LogSource {
+    /// The error type returned when a log cannot be read.
+    type Error: core::error::Error + Send + Sync + 'static;
+
+    /// The length of `kind`'s log in bytes, or `None` if this charge point keeps no such log.
+    ///
+    /// Asked once per upload, before anything is sent, so the length can go in the request
+    /// header. A log that grows while it is being uploaded is uploaded as it was when this was
+    /// called.
+    async fn log_len(&self, kind: LogKind) -> Result<Option<u64>, Self::Error>;
+
+    /// Reads up to `buf.len()` bytes of `kind`'s log starting at `offset`, returning how many were
+    /// read. `0` means the log ends at `offset`.
+    async fn read_log(
+        &self,
+        kind: LogKind,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+}
+
+/// A [`LogSource`] for a charge point that keeps no logs of its own.
+///
+/// Every log is absent, so an upload of one fails - which is what OCPP expects of a charge point
+/// asked for a log it does not have. The security log is unaffected: this crate owns it and hands
+/// it over as [`UploadSource::Bytes`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoLogSource;
+
+#[async_trait::async_trait]
+impl LogSource for NoLogSource {
+    type Error = core::convert::Infallible;
+
+    async fn log_len(&self, _kind: LogKind) -> Result<Option<u64>, Self::Error> {
+        Ok(None)
+    }
+
+    async fn read_log(
+        &self,
+        _kind: LogKind,
+        _offset: u64,
+        _buf: &mut [u8],
+    ) -> Result<usize, Self::Error> {
+        Ok(0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
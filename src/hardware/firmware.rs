@@ -8,6 +8,7 @@
 //! means the Diagnostics block (B5.1) never has to see a trait it would never call.
 
 use alloc::boxed::Box;
+use alloc::string::String;
 
 /// What happened when the integrator installed a firmware image.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -36,6 +37,15 @@ pub enum FirmwareInstallOutcome {
 /// Fallible, and expected to be: an image may be corrupt, a partition may be unwritable, a
 /// bootloader may refuse it. A failure is reported to the CSMS as `InstallationFailed` rather than
 /// panicking - a charge point that dies mid-update is a truck roll.
+///
+/// # Versions
+///
+/// [`Self::running_version`] and [`Self::installed_version`] are how an update that needed a
+/// reboot is judged once the charge point is back: the worker records both before rebooting, and
+/// the next boot reports `Installed` only if the version it comes up running is the one that was
+/// installed (or, failing that, is no longer the one that was replaced) - otherwise
+/// `InstallVerificationFailed`, the bootloader having evidently rolled back. Both default to
+/// `None`, in which case the reboot itself is taken as success, as it always was.
 #[async_trait::async_trait]
 pub trait FirmwareInstaller {
     /// The error type returned by a failed installation.
@@ -43,6 +53,17 @@ pub trait FirmwareInstaller {
 
     /// Installs the most recently downloaded firmware image.
     async fn install(&self) -> Result<FirmwareInstallOutcome, Self::Error>;
+
+    /// The version of the firmware running right now, if known.
+    fn running_version(&self) -> Option<String> {
+        None
+    }
+
+    /// The version the image [`Self::install`] last staged will run as, if known. Only asked
+    /// after an install that returned [`FirmwareInstallOutcome::RebootRequired`].
+    fn installed_version(&self) -> Option<String> {
+        None
+    }
 }
 
 #[async_trait::async_trait]
@@ -52,6 +73,14 @@ impl<T: FirmwareInstaller + Send + Sync + ?Sized> FirmwareInstaller for alloc::s
     async fn install(&self) -> Result<FirmwareInstallOutcome, Self::Error> {
         (**self).install().await
     }
+
+    fn running_version(&self) -> Option<String> {
+        (**self).running_version()
+    }
+
+    fn installed_version(&self) -> Option<String> {
+        (**self).installed_version()
+    }
 }
 
 /// A [`FirmwareInstaller`] for charge points that cannot be updated over the air, mirroring
@@ -115,17 +144,22 @@ pub enum FirmwareVerificationOutcome {
 /// exactly the bytes this crate has deliberately never received. The implementor of this trait is
 /// the same implementor that streamed the image somewhere in
 /// [`FileTransfer::download`](crate::hardware::FileTransfer::download), so it is the only party
-/// that can hash it.
+/// that can hash it - or read it back a chunk at a time, through [`FirmwareImage`], for something
+/// else to.
+///
+/// Verifying also needs two things the core of this crate carries no dependency for (see
+/// [`crate::hardware::CertificateStore`]'s and [`crate::hardware::SoftwareCrypto`]'s module docs
+/// for the same stance): parsing the X.509 `signing_certificate` and checking it chains to a
+/// trusted root, and the asymmetric signature check itself. An implementor with a crypto library
+/// and trust store of their own (commonly the same one behind their
+/// [`CertificateStore`](crate::hardware::CertificateStore)) does both with those. One without
+/// turns on the `signed-firmware` feature and uses
+/// [`SignedFirmwareVerifier`](crate::hardware::SignedFirmwareVerifier), which reads the image
+/// back through a [`FirmwareImage`].
 ///
-/// Verifying also needs two things this crate has never carried a dependency for and does not
-/// start now (see [`crate::hardware::CertificateStore`]'s and
-/// [`crate::hardware::SoftwareCrypto`]'s module docs for the same stance): parsing the X.509
-/// `signing_certificate` and checking it chains to a trusted root, and the asymmetric signature
-/// check itself. Both are the implementor's to do, with whatever crypto library and trust store
-/// (commonly the same one behind their [`CertificateStore`](crate::hardware::CertificateStore))
-/// they already have for TLS. This crate's contribution is calling this trait at the right point
-/// in the state machine - after download, strictly before install - and turning the answer into
-/// the protocol-correct `FirmwareStatusNotification` and `SecurityEventNotification`.
+/// Either way, this crate's contribution is calling this trait at the right point in the state
+/// machine - after download, strictly before install - and turning the answer into the
+/// protocol-correct `FirmwareStatusNotification` and `SecurityEventNotification`.
 ///
 /// # Error handling and the fail-safe default
 ///
@@ -207,6 +241,32 @@ impl<T: FirmwareVerifier + Send + Sync + ?Sized> FirmwareVerifier for alloc::syn
     }
 }
 
+/// Reads back the most recently downloaded firmware image, for a verifier that hashes the image
+/// itself rather than leaving that to the integrator - see
+/// [`SignedFirmwareVerifier`](crate::hardware::SignedFirmwareVerifier).
+///
+/// The read-side counterpart of [`DownloadSink`](crate::hardware::DownloadSink): whatever that
+/// wrote the image to is what this reads it from, one caller-sized chunk at a time so the image
+/// never has to fit in RAM. [`FileSink`](crate::hardware::FileSink) implements both.
+#[async_trait::async_trait]
+pub trait FirmwareImage {
+    /// The error type returned when the image cannot be read.
+    type Error: core::error::Error + Send + Sync + 'static;
+
+    /// Reads up to `buf.len()` bytes of the image starting at `offset`, returning how many were
+    /// read. `0` means the end of the image.
+    async fn read_image(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+#[async_trait::async_trait]
+impl<T: FirmwareImage + Send + Sync + ?Sized> FirmwareImage for alloc::sync::Arc<T> {
+    type Error = T::Error;
+
+    async fn read_image(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        (**self).read_image(offset, buf).await
+    }
+}
+
 /// A [`FirmwareVerifier`] for charge points with no signature-verification capability, mirroring
 /// [`NoFirmwareInstaller`].
 ///
@@ -0,0 +1,471 @@
+//! [`SignedFirmwareVerifier`]: a ready-made [`FirmwareVerifier`] that checks a signed firmware
+//! update itself (`docs/PRODUCTION-ROADMAP.md` B3.3), for integrators with no crypto stack of
+//! their own to do it with.
+//!
+//! Pure Rust and `no_std` + `alloc` - the RustCrypto crates behind the `signed-firmware` feature -
+//! so it runs on the same microcontrollers as the rest of this crate. The image is read back
+//! through [`FirmwareImage`] a chunk at a time and never held in memory whole.
+//!
+//! # What is checked
+//!
+//! - The `signature` is an RSA (PKCS#1 v1.5, 2048 bits or more) or ECDSA (P-256 or P-384)
+//!   signature over the SHA-256 of the image, made with the key in the `signingCertificate`. An
+//!   ECDSA signature may be DER (what `openssl dgst -sign` writes) or the fixed-size `r || s`.
+//! - The `signingCertificate` chains to a [`CertificateUse::ManufacturerRoot`] installed in the
+//!   [`CertificateStore`]. The PEM may carry intermediates after the signing certificate, as the
+//!   Security Whitepaper allows.
+//! - Every certificate on that chain is within its validity period by the [`Clock`]. An
+//!   intermediate is a CA (`basicConstraints`), may sign certificates (`keyUsage`) and respects its
+//!   path length. The signing certificate may sign data and, if it says what it is for
+//!   (`extendedKeyUsage`), code signing is among it.
+//! - No certificate on the chain has a critical extension this verifier does not understand.
+//!
+//! # What is not
+//!
+//! Revocation - OCPP gives a charge point no CRL or OCSP responder for firmware signers - and
+//! name or policy constraints. Roots are trusted as installed: they are the trust anchors, and
+//! `InstallCertificate` is already where a CSMS decides what is one.
+//!
+//! A clock that has not been set yet ([`crate::clock::is_synchronized`]) puts every certificate
+//! outside its validity period, so nothing verifies until it is. That is the fail-safe direction:
+//! the CSMS can retry the update once the charge point knows what time it is.
+
+use super::certificate::{CertificateStore, CertificateUse};
+use super::firmware::{FirmwareImage, FirmwareVerificationOutcome, FirmwareVerifier};
+use crate::clock::Clock;
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use base64ct::{Base64, Encoding};
+use rsa::pkcs1::DecodeRsaPublicKey;
+use rsa::{Pkcs1v15Sign, RsaPublicKey};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use x509_cert::Certificate;
+use x509_cert::der::Encode;
+use x509_cert::der::asn1::ObjectIdentifier;
+use x509_cert::ext::pkix::{BasicConstraints, ExtendedKeyUsage, KeyUsage};
+
+/// How many intermediates may sit between a signing certificate and its root. Real firmware
+/// signing chains have zero or one; the bound keeps a hostile `signingCertificate` from making the
+/// search expensive.
+const MAX_INTERMEDIATES: usize = 4;
+
+/// How much of the image is read, and hashed, at a time.
+const IMAGE_CHUNK: usize = 1024;
+
+/// RSA keys shorter than this many bytes are refused outright, whatever they signed.
+const MIN_RSA_KEY_BYTES: usize = 2048 / 8;
+
+const RSA_ENCRYPTION: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+const SHA256_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const SHA384_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12");
+const SHA512_WITH_RSA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13");
+const EC_PUBLIC_KEY: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const SECP256R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+const SECP384R1: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.34");
+const ECDSA_WITH_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const ECDSA_WITH_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const ECDSA_WITH_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.4");
+const BASIC_CONSTRAINTS: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.19");
+const KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.15");
+const EXTENDED_KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.37");
+const CODE_SIGNING: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.3");
+const ANY_EXTENDED_KEY_USAGE: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.5.29.37.0");
+
+/// A [`FirmwareVerifier`] that does the whole check in this crate - see the module docs for
+/// exactly what it checks.
+///
+/// `image` reads back what the [`FileTransfer`](crate::hardware::FileTransfer) downloaded (a
+/// [`FileSink`](crate::hardware::FileSink) is both), `certificates` holds the manufacturer roots,
+/// and `clock` decides whether a certificate has expired.
+pub struct SignedFirmwareVerifier<I, S, C> {
+    image: I,
+    certificates: S,
+    clock: C,
+}
+
+impl<I, S, C> SignedFirmwareVerifier<I, S, C> {
+    /// A verifier reading the image from `image` and trusting the
+    /// [`CertificateUse::ManufacturerRoot`]s in `certificates`.
+    pub fn new(image: I, certificates: S, clock: C) -> Self {
+        Self {
+            image,
+            certificates,
+            clock,
+        }
+    }
+}
+
+impl<I, S, C> core::fmt::Debug for SignedFirmwareVerifier<I, S, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SignedFirmwareVerifier")
+            .finish_non_exhaustive()
+    }
+}
+
+/// Why a [`SignedFirmwareVerifier`] could not reach a verdict. Anything it *could* judge - a
+/// malformed certificate, an untrusted one, a signature that does not verify - is a
+/// [`FirmwareVerificationOutcome`] instead.
+#[derive(Debug)]
+pub enum SignedFirmwareVerifierError<I, S> {
+    /// The downloaded image could not be read back.
+    Image(I),
+    /// The manufacturer roots could not be read from the certificate store.
+    CertificateStore(S),
+    /// The signing certificate chains to a trusted root, but its key is neither RSA nor ECDSA on
+    /// P-256 or P-384.
+    UnsupportedKey,
+}
+
+impl<I: core::fmt::Display, S: core::fmt::Display> core::fmt::Display
+    for SignedFirmwareVerifierError<I, S>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Image(error) => write!(f, "could not read the firmware image: {error}"),
+            Self::CertificateStore(error) => {
+                write!(f, "could not read the manufacturer roots: {error}")
+            }
+            Self::UnsupportedKey => {
+                f.write_str("the signing certificate's key is not RSA, P-256 or P-384")
+            }
+        }
+    }
+}
+
+impl<I, S> core::error::Error for SignedFirmwareVerifierError<I, S>
+where
+    I: core::error::Error + 'static,
+    S: core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Image(error) => Some(error),
+            Self::CertificateStore(error) => Some(error),
+            Self::UnsupportedKey => None,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<I, S, C> FirmwareVerifier for SignedFirmwareVerifier<I, S, C>
+where
+    I: FirmwareImage + Send + Sync,
+    S: CertificateStore + Send + Sync,
+    C: Clock + Send + Sync,
+{
+    type Error = SignedFirmwareVerifierError<I::Error, S::Error>;
+
+    async fn verify(
+        &self,
+        signing_certificate: Option<&str>,
+        signature: Option<&str>,
+    ) -> Result<FirmwareVerificationOutcome, Self::Error> {
+        // Without a certificate there is no key to check against and nothing to trust; a bare
+        // signature proves only that someone signed something.
+        let Some(presented) =
+            signing_certificate.and_then(|pem| Certificate::load_pem_chain(pem.as_bytes()).ok())
+        else {
+            return Ok(FirmwareVerificationOutcome::InvalidSigningCertificate);
+        };
+        let Some((signer, intermediates)) = presented.split_first() else {
+            return Ok(FirmwareVerificationOutcome::InvalidSigningCertificate);
+        };
+
+        let roots = self.manufacturer_roots().await?;
+        let now = self.clock.now().timestamp();
+        if !chains_to_root(signer, intermediates, &roots, now) {
+            return Ok(FirmwareVerificationOutcome::InvalidSigningCertificate);
+        }
+        let key = SignerKey::of(signer).ok_or(SignedFirmwareVerifierError::UnsupportedKey)?;
+
+        let Some(signature) = signature.and_then(decode_base64) else {
+            return Ok(FirmwareVerificationOutcome::InvalidSignature);
+        };
+        let digest = self.image_digest().await?;
+        Ok(if key.verifies(HashFunction::Sha256, &digest, &signature) {
+            FirmwareVerificationOutcome::Valid
+        } else {
+            FirmwareVerificationOutcome::InvalidSignature
+        })
+    }
+}
+
+impl<I, S, C> SignedFirmwareVerifier<I, S, C>
+where
+    I: FirmwareImage + Send + Sync,
+    S: CertificateStore + Send + Sync,
+{
+    /// Every installed manufacturer root this verifier can parse. One that does not parse is
+    /// skipped rather than failing the whole check: it cannot vouch for anything either way.
+    async fn manufacturer_roots(
+        &self,
+    ) -> Result<Vec<Certificate>, SignedFirmwareVerifierError<I::Error, S::Error>> {
+        let pems = self
+            .certificates
+            .all_certificate_chain_pems(CertificateUse::ManufacturerRoot)
+            .await
+            .map_err(SignedFirmwareVerifierError::CertificateStore)?;
+        let mut roots = Vec::new();
+        for pem in pems {
+            match Certificate::load_pem_chain(pem.as_bytes()) {
+                Ok(certificates) => roots.extend(certificates),
+                Err(error) => {
+                    tracing::warn!(%error, "skipping a manufacturer root that does not parse");
+                }
+            }
+        }
+        Ok(roots)
+    }
+
+    /// The SHA-256 of the downloaded image.
+    async fn image_digest(
+        &self,
+    ) -> Result<Vec<u8>, SignedFirmwareVerifierError<I::Error, S::Error>> {
+        let mut hasher = Sha256::new();
+        let mut buf = vec![0; IMAGE_CHUNK];
+        let mut offset = 0;
+        loop {
+            let read = self
+                .image
+                .read_image(offset, &mut buf)
+                .await
+                .map_err(SignedFirmwareVerifierError::Image)?
+                .min(buf.len());
+            if read == 0 {
+                return Ok(hasher.finalize().to_vec());
+            }
+            hasher.update(&buf[..read]);
+            offset += read as u64;
+        }
+    }
+}
+
+/// Whether `signer` chains, through some of `intermediates`, to one of `roots` - every link
+/// signed by the next, every certificate on the way valid at `now` and allowed to do what the
+/// chain uses it for.
+fn chains_to_root(
+    signer: &Certificate,
+    intermediates: &[Certificate],
+    roots: &[Certificate],
+    now: i64,
+) -> bool {
+    if !is_current(signer, now) || !understands_critical_extensions(signer) || !may_sign(signer) {
+        return false;
+    }
+    let mut current = signer;
+    let mut used = Vec::new();
+    for below in 0..=MAX_INTERMEDIATES {
+        if roots
+            .iter()
+            .any(|root| is_current(root, now) && issued(root, current))
+        {
+            return true;
+        }
+        let next = intermediates.iter().enumerate().find(|(index, ca)| {
+            !used.contains(index)
+                && is_current(ca, now)
+                && understands_critical_extensions(ca)
+                && may_issue(ca, below)
+                && issued(ca, current)
+        });
+        let Some((index, ca)) = next else {
+            return false;
+        };
+        used.push(index);
+        current = ca;
+    }
+    false
+}
+
+/// Whether `now` (Unix seconds) falls within `certificate`'s validity period.
+fn is_current(certificate: &Certificate, now: i64) -> bool {
+    let validity = &certificate.tbs_certificate.validity;
+    let not_before = validity.not_before.to_unix_duration().as_secs();
+    let not_after = validity.not_after.to_unix_duration().as_secs();
+    u64::try_from(now).is_ok_and(|now| not_before <= now && now <= not_after)
+}
+
+/// RFC 5280 §4.2: a certificate with a critical extension the verifier does not recognise must be
+/// rejected - the issuer said whoever cannot honour it must not trust the certificate.
+fn understands_critical_extensions(certificate: &Certificate) -> bool {
+    certificate
+        .tbs_certificate
+        .extensions
+        .as_deref()
+        .unwrap_or(&[])
+        .iter()
+        .filter(|extension| extension.critical)
+        .all(|extension| {
+            [BASIC_CONSTRAINTS, KEY_USAGE, EXTENDED_KEY_USAGE].contains(&extension.extn_id)
+        })
+}
+
+/// Whether `ca` may issue a certificate with `below` intermediates already under it.
+fn may_issue(ca: &Certificate, below: usize) -> bool {
+    let tbs = &ca.tbs_certificate;
+    let Ok(Some((_, constraints))) = tbs.get::<BasicConstraints>() else {
+        return false;
+    };
+    let key_usage_allows = match tbs.get::<KeyUsage>() {
+        Ok(Some((_, usage))) => usage.key_cert_sign(),
+        Ok(None) => true,
+        Err(_) => false,
+    };
+    constraints.ca
+        && constraints
+            .path_len_constraint
+            .is_none_or(|limit| below <= usize::from(limit))
+        && key_usage_allows
+}
+
+/// Whether the signing certificate may sign a firmware image.
+fn may_sign(signer: &Certificate) -> bool {
+    let tbs = &signer.tbs_certificate;
+    let key_usage_allows = match tbs.get::<KeyUsage>() {
+        Ok(Some((_, usage))) => usage.digital_signature(),
+        Ok(None) => true,
+        Err(_) => false,
+    };
+    let extended_key_usage_allows = match tbs.get::<ExtendedKeyUsage>() {
+        Ok(Some((_, usage))) => usage
+            .0
+            .iter()
+            .any(|purpose| *purpose == CODE_SIGNING || *purpose == ANY_EXTENDED_KEY_USAGE),
+        Ok(None) => true,
+        Err(_) => false,
+    };
+    key_usage_allows && extended_key_usage_allows
+}
+
+/// Whether `issuer` issued `certificate`: the names match and `issuer`'s key verifies
+/// `certificate`'s signature.
+fn issued(issuer: &Certificate, certificate: &Certificate) -> bool {
+    if certificate.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+        return false;
+    }
+    let Some(key) = SignerKey::of(issuer) else {
+        return false;
+    };
+    let algorithm = certificate.signature_algorithm.oid;
+    let (family, hash) = match algorithm {
+        SHA256_WITH_RSA => (KeyFamily::Rsa, HashFunction::Sha256),
+        SHA384_WITH_RSA => (KeyFamily::Rsa, HashFunction::Sha384),
+        SHA512_WITH_RSA => (KeyFamily::Rsa, HashFunction::Sha512),
+        ECDSA_WITH_SHA256 => (KeyFamily::Ecdsa, HashFunction::Sha256),
+        ECDSA_WITH_SHA384 => (KeyFamily::Ecdsa, HashFunction::Sha384),
+        ECDSA_WITH_SHA512 => (KeyFamily::Ecdsa, HashFunction::Sha512),
+        _ => return false,
+    };
+    if key.family() != family {
+        return false;
+    }
+    let (Ok(signed), Some(signature)) = (
+        certificate.tbs_certificate.to_der(),
+        certificate.signature.as_bytes(),
+    ) else {
+        return false;
+    };
+    key.verifies(hash, &hash.digest(&signed), signature)
+}
+
+/// Decodes OCPP's base64 `signature`, tolerating the line breaks some CSMSs wrap it with.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let compact: Vec<u8> = text
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect();
+    Base64::decode_vec(core::str::from_utf8(&compact).ok()?).ok()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyFamily {
+    Rsa,
+    Ecdsa,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum HashFunction {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashFunction {
+    fn digest(self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+            Self::Sha384 => Sha384::digest(data).to_vec(),
+            Self::Sha512 => Sha512::digest(data).to_vec(),
+        }
+    }
+}
+
+/// A certificate's public key, in the forms this verifier can check signatures with.
+enum SignerKey {
+    Rsa(RsaPublicKey),
+    P256(p256::ecdsa::VerifyingKey),
+    P384(p384::ecdsa::VerifyingKey),
+}
+
+impl SignerKey {
+    /// `certificate`'s key, or `None` if it is of a kind (or size) this verifier does not accept.
+    fn of(certificate: &Certificate) -> Option<Self> {
+        let info = &certificate.tbs_certificate.subject_public_key_info;
+        let bits = info.subject_public_key.as_bytes()?;
+        match info.algorithm.oid {
+            RSA_ENCRYPTION => RsaPublicKey::from_pkcs1_der(bits)
+                .ok()
+                .filter(|key| rsa::traits::PublicKeyParts::size(key) >= MIN_RSA_KEY_BYTES)
+                .map(Self::Rsa),
+            EC_PUBLIC_KEY => match info
+                .algorithm
+                .parameters
+                .as_ref()?
+                .decode_as::<ObjectIdentifier>()
+                .ok()?
+            {
+                SECP256R1 => p256::ecdsa::VerifyingKey::from_sec1_bytes(bits)
+                    .ok()
+                    .map(Self::P256),
+                SECP384R1 => p384::ecdsa::VerifyingKey::from_sec1_bytes(bits)
+                    .ok()
+                    .map(Self::P384),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn family(&self) -> KeyFamily {
+        match self {
+            Self::Rsa(_) => KeyFamily::Rsa,
+            Self::P256(_) | Self::P384(_) => KeyFamily::Ecdsa,
+        }
+    }
+
+    /// Whether `signature` is this key's signature over the data `prehash` is the `hash` of.
+    fn verifies(&self, hash: HashFunction, prehash: &[u8], signature: &[u8]) -> bool {
+        use p256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        match self {
+            Self::Rsa(key) => {
+                let scheme = match hash {
+                    HashFunction::Sha256 => Pkcs1v15Sign::new::<Sha256>(),
+                    HashFunction::Sha384 => Pkcs1v15Sign::new::<Sha384>(),
+                    HashFunction::Sha512 => Pkcs1v15Sign::new::<Sha512>(),
+                };
+                key.verify(scheme, prehash, signature).is_ok()
+            }
+            Self::P256(key) => p256::ecdsa::Signature::from_der(signature)
+                .or_else(|_| p256::ecdsa::Signature::from_slice(signature))
+                .is_ok_and(|signature| key.verify_prehash(prehash, &signature).is_ok()),
+            Self::P384(key) => p384::ecdsa::Signature::from_der(signature)
+                .or_else(|_| p384::ecdsa::Signature::from_slice(signature))
+                .is_ok_and(|signature| key.verify_prehash(prehash, &signature).is_ok()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
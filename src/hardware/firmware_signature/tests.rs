@@ -0,0 +1,370 @@
+//! Tests for [`SignedFirmwareVerifier`], against a small PKI made with `openssl` for the purpose:
+//! an RSA manufacturer root, a P-384 intermediate under it, a P-256 signer under that and an RSA
+//! signer directly under the root, each valid until 2126 and each signature over [`IMAGE`].
+
+use super::*;
+use crate::hardware::{
+    CertificateHashData, HashAlgorithm, InMemoryStorage, StoredCertificates,
+    StoredCertificatesError,
+};
+use alloc::string::String;
+use alloc::sync::Arc;
+use chrono::{DateTime, TimeZone, Utc};
+
+const IMAGE: &[u8] = b"firmware image 2.0.1 for the test charge point\n";
+
+/// The manufacturer root (RSA 2048, self-signed).
+const ROOT: &str = "-----BEGIN CERTIFICATE-----\n\
+MIIDNTCCAh2gAwIBAgIUJnng72goFI4VKHE1OwGw4qUn0WcwDQYJKoZIhvcNAQEL\n\
+BQAwITEfMB0GA1UEAwwWVGVzdCBNYW51ZmFjdHVyZXIgUm9vdDAgFw0yNjEwMTcw\n\
+NTE3MzJaGA8yMTI2MDkyMzA1MTczMlowITEfMB0GA1UEAwwWVGVzdCBNYW51ZmFj\n\
+dHVyZXIgUm9vdDCCASIwDQYJKoZIhvcNAQEBBQADggEPADCCAQoCggEBALs7FLdR\n\
+lMl8OTATZaaF15/0M9QkDD7yqWM/a96YtvGMCiiYpWoDMX4QHoV+MZ9YHiKldosl\n\
+4CWgHsIqRJ356t2c68J2Fwpn8WtpafRSENFeKG9OMEB+3yqaltm5iYaFpDEYZNkn\n\
+6iX2bJYWNE5IiLGqrR9vL0FrrVHZi1l16E0AfxCc7dy0DinNBKJP3V7AqDU41Dtc\n\
+vZ8rY6TAk7IYjcvxinpyp2HVX038juhtYvs4L1HUYyu4+y3J0S4RRKPOmpsxf/jL\n\
+UOALngj7yyj5Y74hZguA0L6C279n2ZtafapvsihjCVacqG7U1YEZCHUkEU0PnaK0\n\
+k38fHS+A61JC1x8CAwEAAaNjMGEwHQYDVR0OBBYEFDJ/es+ZFSOZWSm7QYEXqtL+\n\
+H0yNMB8GA1UdIwQYMBaAFDJ/es+ZFSOZWSm7QYEXqtL+H0yNMA8GA1UdEwEB/wQF\n\
+MAMBAf8wDgYDVR0PAQH/BAQDAgEGMA0GCSqGSIb3DQEBCwUAA4IBAQAJJ070Cf1n\n\
+wjnZl1wKtM91QjhYkWx0egx98E6iwdc0abVY+HOJ07AOUrmEtl39gp5GgwTmJz7C\n\
++L2dZYQEUBC9hwlRYwz2Udk+0LhSFvwgSLqQ6wcryQzH4vlw2SB9xQmT8q7nqnQg\n\
++r7XmIR8YaYoNcuh85VanOQ9u2u95LtYYEGJL3rj+A/kMLpIoifTwwYksmTtjLqy\n\
+NOFt5F1dcFOgzFY4rtnpT/zf5+5tOZvfrLUGjmBddqhxW95UDIcQeAB2USM76+Wg\n\
+gcB33qs1L9fudsSX5YmfxpopY4Zg+NGEjb/yJVrdJQwKDx+vtNkTlZVBiwZsYFCV\n\
+MM2YeIM+nkTh\n\
+-----END CERTIFICATE-----\n";
+
+/// A firmware signing CA (P-384) the root issued.
+const INTERMEDIATE: &str = "-----BEGIN CERTIFICATE-----\n\
+MIICiTCCAXGgAwIBAgIUEgllZonecKJFEplsWPKerEPwgAcwDQYJKoZIhvcNAQEL\n\
+BQAwITEfMB0GA1UEAwwWVGVzdCBNYW51ZmFjdHVyZXIgUm9vdDAgFw0yNjEwMTcw\n\
+NTE3MzJaGA8yMTI2MDkyMzA1MTczMlowIzEhMB8GA1UEAwwYVGVzdCBGaXJtd2Fy\n\
+ZSBTaWduaW5nIENBMHYwEAYHKoZIzj0CAQYFK4EEACIDYgAEOX+f9tlvxH60vp0v\n\
+EchgAMmWL4xBXsvsgQAxzoUxad9mPHBCpBx98lImejxcgmZXSDAt/wiDAQvLnZsx\n\
+1cREAQcp16Zq8BIAHgCIz3aMpjc+j7Uv9hawoF7cv4hEjfeXo2MwYTAPBgNVHRMB\n\
+Af8EBTADAQH/MA4GA1UdDwEB/wQEAwIBBjAdBgNVHQ4EFgQU/NFAro5CDYVzjcGQ\n\
+yR2DJlyuZSQwHwYDVR0jBBgwFoAUMn96z5kVI5lZKbtBgReq0v4fTI0wDQYJKoZI\n\
+hvcNAQELBQADggEBAGe6UIIUwHkdcL3ZT9RHzVYm1yO/L1DnJ1WS6Y4AJ2bKHvEI\n\
+9CQSxjYZeYHcsZD/1mFFKXcX53vfc9KQIw60rO+Lzs4JQj0wY2LkU48MbLD0fFw4\n\
+yR0/XGPl1QcalGjBSS5roYsd6ISpkBKLmTae0v5nSLECLGjj/g+QfIV+ROpuZZBI\n\
+iGaASo0O+WQfj1dYAj5uJhSh3v8+ai5KIdUvHg+23rsP5D0K4pvwLmt9clQnGu3R\n\
+L6ao1LC/ZYQ+YgHECqiQ3SY/0bf4DjsJUlzt6sTmsDIE7Jy9w4+4LWdCdHF5nmRx\n\
+j14Q1pkvEaPlBERqnGfdVX5pjO7w88cLBwx/VAc=\n\
+-----END CERTIFICATE-----\n";
+
+/// A firmware signer (P-256, `codeSigning`) the intermediate issued.
+const EC_SIGNER: &str = "-----BEGIN CERTIFICATE-----\n\
+MIIB3jCCAWSgAwIBAgIUYvALFz8y9erSxrarT7Tle6NcCWwwCgYIKoZIzj0EAwMw\n\
+IzEhMB8GA1UEAwwYVGVzdCBGaXJtd2FyZSBTaWduaW5nIENBMCAXDTI2MTAxNzA1\n\
+MTczMloYDzIxMjYwOTIzMDUxNzMyWjAiMSAwHgYDVQQDDBdUZXN0IEZpcm13YXJl\n\
+IFNpZ25lciBFQzBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABKgBK//cWYh1lmiX\n\
+7q5h8WpkH13JGnCM0hUL+o407a5oUvEah0rQ2jSNfVZXw4ozz4wBodEVtqOpIuGs\n\
+xUA2b2qjdTBzMAwGA1UdEwEB/wQCMAAwDgYDVR0PAQH/BAQDAgeAMBMGA1UdJQQM\n\
+MAoGCCsGAQUFBwMDMB0GA1UdDgQWBBRYDPQopZDrntpkfKJJJOrPGtkgAzAfBgNV\n\
+HSMEGDAWgBT80UCujkINhXONwZDJHYMmXK5lJDAKBggqhkjOPQQDAwNoADBlAjBA\n\
+I0oH9ArDZALIJMGSKeVkRj+yu4inSJV5cS3LebPQ/HYWux5IFi7+7Ki9KqiuyRgC\n\
+MQDC7LvgeHSlkh90zPLs+HxIAmQFHyH+ZEM+YEe5mO7bAope3a5P/ib8+HrwI0wC\n\
+SYA=\n\
+-----END CERTIFICATE-----\n";
+const EC_SIGNATURE: &str = "MEYCIQCLQfzte6K4Gg7sn6Q0MqbbxPVrEh9h7mjUoozOfOvB7AIhAKgmAGjc1PHtCpHXR+NMuQJcMK+OBtw74bxVC47gEUg1";
+
+/// A firmware signer (RSA 2048, `codeSigning`) the root issued directly.
+const RSA_SIGNER: &str = "-----BEGIN CERTIFICATE-----\n\
+MIIDSTCCAjGgAwIBAgIUEgllZonecKJFEplsWPKerEPwgAgwDQYJKoZIhvcNAQEL\n\
+BQAwITEfMB0GA1UEAwwWVGVzdCBNYW51ZmFjdHVyZXIgUm9vdDAgFw0yNjEwMTcw\n\
+NTE3MzJaGA8yMTI2MDkyMzA1MTczMlowIzEhMB8GA1UEAwwYVGVzdCBGaXJtd2Fy\n\
+ZSBTaWduZXIgUlNBMIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEAod9P\n\
+81RLSchxC3njAWr4WTjtHM033iljPafnhZ+8VdHz4beWyVFBSmSHjGA14A+WQtr7\n\
+k517zfGtFjZkfTpqdS0IXUX15yFbfNvyl54dRpR+44TkHE3GsFcLgE1IETCubSYN\n\
+GX3MPGu4U+EX6WxFeMlBt2wG3taKLYF8OWvap4UV3FGuUJlsWk5bHxsecfi49gr+\n\
+z0gcGoYNO4X284P8ODdqd2TwXWQ5WVu4ugK3U04aIDqMnrur8XSIuG9FucfhGAZl\n\
+9ufA9bCU4Zo6fPzsf2nJule+HUgDirML/Gaik4xrXAnd4ibuSI+xCLFFe2/MYf0v\n\
+CV+O/vZUYxYegEBixwIDAQABo3UwczAMBgNVHRMBAf8EAjAAMA4GA1UdDwEB/wQE\n\
+AwIHgDATBgNVHSUEDDAKBggrBgEFBQcDAzAdBgNVHQ4EFgQUJ3sONNPSX+zSnMN7\n\
+rKafy7JDPoEwHwYDVR0jBBgwFoAUMn96z5kVI5lZKbtBgReq0v4fTI0wDQYJKoZI\n\
+hvcNAQELBQADggEBAJA0UzFJj4mI4LbQ0TR8qbnwjRNTIMC+3uAPgUb0YHNFKgem\n\
+cIT0+k7AmE8NbX5quOtZgnjN3WoBndZMfVWUkrNA66WUuWJEG2mW0TAeY5Tb1R9x\n\
+Sqn/VTDa++UGbu5VovXzQVNf7l2S3KKuzXHGDsQ4JefrMVHX0DIYdbdmobbhbfji\n\
+PJummogb4/OUtvr9UqmMP4q6ql/+9fOWASsqpVOzKxlYVIthlOtMpG7yx+Aupqqb\n\
+VUC+kZB1rEWfoQA5iELpXX7PZO60LAb1sTxsTqWSOALv8n4apt7k4yY3rRChiIg8\n\
+aBbGruvJ3bz0ph6VrKrhKEe33pxc2R0fplEFz8o=\n\
+-----END CERTIFICATE-----\n";
+const RSA_SIGNATURE: &str = "ShSOv+hq+1TKYu6n+9PVm7rUCUDlfPm0cBOIsyTsuFIhCP0sOermzBjQcIH5svVT3hYNMGicL4zz8IuGHcNd5pZeUtUimFx9/Yd/SDQjLvi/6tv4kTl06Hh0OfHNdPBp50jVHtgukZ+I4M3KAEFN+Y3xxKQXnu3MqI0+9wqke09NslusjMYAJTHNMwwzyPFjIPzs5vpRf7XSbL6PBseyZVUbSunJaKqRNBJFjiwABFI3UrvVh7qJUEz39CGWZdB10oCj/kK7fOt0d3et/t0OjL2aXOMo6U0Daql7deMcz34NMXhdiLSffHLdYM8P9UOo1uVMinF0YikqjdJjUSV1eA==";
+
+/// A self-signed CA with the root's name but its own key.
+const IMPOSTOR_ROOT: &str = "-----BEGIN CERTIFICATE-----\n\
+MIIBmjCCAT+gAwIBAgIUWdBTrA/e89wK+fWqFEOqaUikZhIwCgYIKoZIzj0EAwIw\n\
+ITEfMB0GA1UEAwwWVGVzdCBNYW51ZmFjdHVyZXIgUm9vdDAgFw0yNjEwMTcwNTE3\n\
+MzJaGA8yMTI2MDkyMzA1MTczMlowITEfMB0GA1UEAwwWVGVzdCBNYW51ZmFjdHVy\n\
+ZXIgUm9vdDBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABL5edBsrhsLfo1FSfJz+\n\
+cY50DGCkgQufDApWrxNea04wRiOoYzXxKj6UT6CGgdh0HYsveP+xMUE4li4ZqVnM\n\
+JIyjUzBRMB0GA1UdDgQWBBRCBHnbak9l8HP3RlZKOxe4BbaK0zAfBgNVHSMEGDAW\n\
+gBRCBHnbak9l8HP3RlZKOxe4BbaK0zAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49\n\
+BAMCA0kAMEYCIQCMoRGl+QOGZqiUUJyibCWVWHOZPW4vx+p6ujaZM4LSSAIhAMMR\n\
+xGEs2A3npUus5Sexd1jqNN6Xx0oKOrfaPPN8Bo+I\n\
+-----END CERTIFICATE-----\n";
+
+/// A signer the impostor issued, named like [`EC_SIGNER`].
+const IMPOSTOR_SIGNER: &str = "-----BEGIN CERTIFICATE-----\n\
+MIIBvDCCAWKgAwIBAgIUYFwjv6NrFz0zOAigqmPQ15NLcAQwCgYIKoZIzj0EAwIw\n\
+ITEfMB0GA1UEAwwWVGVzdCBNYW51ZmFjdHVyZXIgUm9vdDAgFw0yNjEwMTcwNTE3\n\
+MzNaGA8yMTI2MDkyMzA1MTczM1owIjEgMB4GA1UEAwwXVGVzdCBGaXJtd2FyZSBT\n\
+aWduZXIgRUMwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAARpOq4mpVTzVDyaAap8\n\
+TKTXKsvJu+ZV5mq/JcyrVpCC8HSz2iq4dcS6wk/ucVgN6y+yjik6xv8c3h8PuoG3\n\
+cH00o3UwczAMBgNVHRMBAf8EAjAAMA4GA1UdDwEB/wQEAwIHgDATBgNVHSUEDDAK\n\
+BggrBgEFBQcDAzAdBgNVHQ4EFgQU8zKz5aygLIJsib1gHxQjBXIfmhkwHwYDVR0j\n\
+BBgwFoAUQgR522pPZfBz90ZWSjsXuAW2itMwCgYIKoZIzj0EAwIDSAAwRQIgTvAl\n\
+nNhm75u+gDtf+l0XwSrAwvNIe1Hi2P4/PsXIstUCIQC/bCbyP0RWjlvSbo2RGoUc\n\
+/YkP+SFeB0OaLrYAnZFnIQ==\n\
+-----END CERTIFICATE-----\n";
+const IMPOSTOR_SIGNATURE: &str = "MEUCIQCMcXbrjZuf2qA7rxne/gBiQf23SNdQOsDp2t50oP7R5wIgCbCLJN5n3S3nfgaEd4NBB3mt0gnKkT5UP7jJIIVwJJA=";
+
+/// A signer that [`RSA_SIGNER`] - not a CA - issued.
+const SUB_SIGNER: &str = "-----BEGIN CERTIFICATE-----\n\
+MIICgDCCAWigAwIBAgIUPBjOwkUedxdO2NlGTtBeOtypAUgwDQYJKoZIhvcNAQEL\n\
+BQAwIzEhMB8GA1UEAwwYVGVzdCBGaXJtd2FyZSBTaWduZXIgUlNBMCAXDTI2MTAx\n\
+NzA1MTczM1oYDzIxMjYwOTIzMDUxNzMzWjAjMSEwHwYDVQQDDBhUZXN0IEZpcm13\n\
+YXJlIFNpZ25lciBTdWIwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAARd5Ij+L7Np\n\
+DnYB2D3JxKHc24aMLWM2pCWUhKJTyxN/1GBEs1z5zKsthTPfEBPpSr7MMJ258T0z\n\
+VeYe5SVKC4Yzo3UwczAMBgNVHRMBAf8EAjAAMA4GA1UdDwEB/wQEAwIHgDATBgNV\n\
+HSUEDDAKBggrBgEFBQcDAzAdBgNVHQ4EFgQUSUF3AYHHSkgfJJURAPmWeb9W3vQw\n\
+HwYDVR0jBBgwFoAUJ3sONNPSX+zSnMN7rKafy7JDPoEwDQYJKoZIhvcNAQELBQAD\n\
+ggEBAERrZfcAOGoqMFHA7ONGS30l7jb7IjqAJLhOD2MQppjwdPrmZ3FU55T8/2bg\n\
++tCaNz5LUoFNVfPwjRsCRe3pgZgozhqmH1CoIxokMDyHTziVrbtO4uc2JZQ1d8LW\n\
+a3QjfjYESa0jc67tQ63R5CMbzK4ScCTXP4TLYVvC9Dh8BfgkLdd5ifrz/KzSfEyB\n\
+PSVwN+1ReRBTYB0ZF/BAP80/dPQ2d1VlQlAWIOfesXRWpOYga2SrTKd9hQiu+6n9\n\
+4mAYg1XFnNjRnVitm51Z7H14Di2r4EcPptkE1pBQswYF8O2w80j8KUdss7I+HwYW\n\
+YJ+vQsLfbEuWR9GjMy1ZgvpJ6yE=\n\
+-----END CERTIFICATE-----\n";
+const SUB_SIGNATURE: &str = "MEUCIDHL8mQ9T72ddIJeO0c5AWdZlw9PBM+KPZ16ryJP+P79AiEAsy7nBFMVYj1tErGSDrbfw6PEwIJQ9oZypIN3kYhN1hk=";
+
+/// A firmware image held in memory, read back a few bytes at a time so the chunked hashing is
+/// exercised.
+struct InMemoryImage(&'static [u8]);
+
+#[async_trait::async_trait]
+impl FirmwareImage for InMemoryImage {
+    type Error = core::convert::Infallible;
+
+    async fn read_image(&self, offset: u64, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let start = usize::try_from(offset).unwrap().min(self.0.len());
+        let read = (self.0.len() - start).min(buf.len()).min(7);
+        buf[..read].copy_from_slice(&self.0[start..start + read]);
+        Ok(read)
+    }
+}
+
+struct FixedClock(DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+fn today() -> FixedClock {
+    FixedClock(Utc.with_ymd_and_hms(2030, 6, 1, 12, 0, 0).unwrap())
+}
+
+async fn store_with(
+    use_for: CertificateUse,
+    pem: &str,
+) -> StoredCertificates<Arc<InMemoryStorage>> {
+    let store = StoredCertificates::new(Arc::new(InMemoryStorage::new()));
+    store
+        .install_with_hash(
+            use_for,
+            pem,
+            CertificateHashData {
+                hash_algorithm: HashAlgorithm::Sha256,
+                issuer_name_hash: String::from("00"),
+                issuer_key_hash: String::from("00"),
+                serial_number: String::from("01"),
+            },
+        )
+        .await;
+    store
+}
+
+type Verifier =
+    SignedFirmwareVerifier<InMemoryImage, StoredCertificates<Arc<InMemoryStorage>>, FixedClock>;
+
+async fn verifier(image: &'static [u8], clock: FixedClock) -> Verifier {
+    SignedFirmwareVerifier::new(
+        InMemoryImage(image),
+        store_with(CertificateUse::ManufacturerRoot, ROOT).await,
+        clock,
+    )
+}
+
+async fn verify(
+    verifier: &Verifier,
+    certificate: &str,
+    signature: &str,
+) -> Result<
+    FirmwareVerificationOutcome,
+    SignedFirmwareVerifierError<core::convert::Infallible, StoredCertificatesError>,
+> {
+    verifier.verify(Some(certificate), Some(signature)).await
+}
+
+#[tokio::test]
+async fn an_ecdsa_signature_through_an_intermediate_in_the_signing_certificate_is_valid() {
+    let verifier = verifier(IMAGE, today()).await;
+    let chain = [EC_SIGNER, INTERMEDIATE].concat();
+
+    assert_eq!(
+        verify(&verifier, &chain, EC_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::Valid
+    );
+}
+
+#[tokio::test]
+async fn an_rsa_signature_by_a_signer_the_root_issued_directly_is_valid() {
+    let verifier = verifier(IMAGE, today()).await;
+
+    assert_eq!(
+        verify(&verifier, RSA_SIGNER, RSA_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::Valid
+    );
+}
+
+#[tokio::test]
+async fn a_signature_wrapped_across_lines_still_decodes() {
+    let verifier = verifier(IMAGE, today()).await;
+    let (head, tail) = RSA_SIGNATURE.split_at(64);
+    let wrapped = [head, "\r\n", tail].concat();
+
+    assert_eq!(
+        verify(&verifier, RSA_SIGNER, &wrapped).await.unwrap(),
+        FirmwareVerificationOutcome::Valid
+    );
+}
+
+#[tokio::test]
+async fn an_altered_image_is_an_invalid_signature() {
+    let verifier = verifier(
+        b"firmware image 2.0.1 for the test charge point, patched\n",
+        today(),
+    )
+    .await;
+    let chain = [EC_SIGNER, INTERMEDIATE].concat();
+
+    assert_eq!(
+        verify(&verifier, &chain, EC_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSignature
+    );
+    assert_eq!(
+        verify(&verifier, RSA_SIGNER, RSA_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSignature
+    );
+}
+
+#[tokio::test]
+async fn another_signers_signature_or_none_at_all_is_an_invalid_signature() {
+    let verifier = verifier(IMAGE, today()).await;
+
+    assert_eq!(
+        verify(&verifier, RSA_SIGNER, EC_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSignature
+    );
+    assert_eq!(
+        verify(&verifier, RSA_SIGNER, "not base64!").await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSignature
+    );
+    assert_eq!(
+        verifier.verify(Some(RSA_SIGNER), None).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSignature
+    );
+}
+
+#[tokio::test]
+async fn a_signer_whose_intermediate_is_missing_does_not_chain() {
+    let verifier = verifier(IMAGE, today()).await;
+
+    assert_eq!(
+        verify(&verifier, EC_SIGNER, EC_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSigningCertificate
+    );
+}
+
+#[tokio::test]
+async fn a_signer_under_a_root_that_only_shares_the_manufacturer_roots_name_is_untrusted() {
+    let verifier = verifier(IMAGE, today()).await;
+    let chain = [IMPOSTOR_SIGNER, IMPOSTOR_ROOT].concat();
+
+    assert_eq!(
+        verify(&verifier, &chain, IMPOSTOR_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSigningCertificate
+    );
+}
+
+#[tokio::test]
+async fn a_certificate_issued_by_a_non_ca_is_untrusted_even_though_the_issuer_chains() {
+    let verifier = verifier(IMAGE, today()).await;
+    let chain = [SUB_SIGNER, RSA_SIGNER].concat();
+
+    assert_eq!(
+        verify(&verifier, &chain, SUB_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSigningCertificate
+    );
+}
+
+#[tokio::test]
+async fn a_chain_outside_its_validity_period_is_untrusted() {
+    let expired = verifier(
+        IMAGE,
+        FixedClock(Utc.with_ymd_and_hms(2200, 1, 1, 0, 0, 0).unwrap()),
+    )
+    .await;
+    let unset_clock = verifier(IMAGE, FixedClock(DateTime::UNIX_EPOCH)).await;
+
+    assert_eq!(
+        verify(&expired, RSA_SIGNER, RSA_SIGNATURE).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSigningCertificate
+    );
+    assert_eq!(
+        verify(&unset_clock, RSA_SIGNER, RSA_SIGNATURE)
+            .await
+            .unwrap(),
+        FirmwareVerificationOutcome::InvalidSigningCertificate
+    );
+}
+
+#[tokio::test]
+async fn only_a_manufacturer_root_is_trusted_for_firmware() {
+    let verifier = SignedFirmwareVerifier::new(
+        InMemoryImage(IMAGE),
+        store_with(CertificateUse::CsmsRoot, ROOT).await,
+        today(),
+    );
+
+    assert_eq!(
+        verifier
+            .verify(Some(RSA_SIGNER), Some(RSA_SIGNATURE))
+            .await
+            .unwrap(),
+        FirmwareVerificationOutcome::InvalidSigningCertificate
+    );
+}
+
+#[tokio::test]
+async fn a_missing_or_unparseable_signing_certificate_is_untrusted() {
+    let verifier = verifier(IMAGE, today()).await;
+
+    assert_eq!(
+        verifier.verify(None, Some(RSA_SIGNATURE)).await.unwrap(),
+        FirmwareVerificationOutcome::InvalidSigningCertificate
+    );
+    assert_eq!(
+        verify(
+            &verifier,
+            "-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n",
+            RSA_SIGNATURE
+        )
+        .await
+        .unwrap(),
+        FirmwareVerificationOutcome::InvalidSigningCertificate
+    );
+}
@@ -0,0 +1,622 @@
+use super::*;
+use crate::hardware::transfer_net::test_tls;
+use crate::hardware::{FileSink, SchemeRouter, SchemeRouterError};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::TlsAcceptor;
+
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!(
+            "ocpp-cp-ftp-file-transfer-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// What the stand-in server supports, so each test can take away the piece it is about.
+#[derive(Clone, Copy)]
+struct Options {
+    /// Accepts `AUTH TLS`.
+    tls: bool,
+    /// Accepts `EPSV`; without it, only `PASV`.
+    epsv: bool,
+    /// Accepts `REST`.
+    rest: bool,
+    /// Aborts the first `RETR` after this many bytes, with a `426`.
+    cut_first_retr_at: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            tls: false,
+            epsv: true,
+            rest: true,
+            cut_first_retr_at: None,
+        }
+    }
+}
+
+/// A stand-in FTP server: just enough of RFC 959 (plus `EPSV`, `SIZE`, `REST` and RFC 4217's
+/// explicit TLS) to be the far end of every path [`FtpFileTransfer`] takes, over an in-memory file
+/// table.
+struct FtpStandIn {
+    port: u16,
+    files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    commands: Arc<Mutex<Vec<String>>>,
+}
+
+impl FtpStandIn {
+    async fn start(options: Options, files: &[(&str, &[u8])]) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let files = Arc::new(Mutex::new(
+            files
+                .iter()
+                .map(|(name, content)| (name.to_string(), content.to_vec()))
+                .collect(),
+        ));
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let retrievals = Arc::new(AtomicUsize::new(0));
+        {
+            let files = files.clone();
+            let commands = commands.clone();
+            tokio::spawn(async move {
+                while let Ok((socket, _)) = listener.accept().await {
+                    tokio::spawn(serve(
+                        socket,
+                        options,
+                        files.clone(),
+                        commands.clone(),
+                        retrievals.clone(),
+                    ));
+                }
+            });
+        }
+        Self {
+            port,
+            files,
+            commands,
+        }
+    }
+
+    fn url(&self, scheme: &str, userinfo: &str, path: &str) -> String {
+        format!("{scheme}://{userinfo}127.0.0.1:{}/{path}", self.port)
+    }
+
+    fn file(&self, name: &str) -> Option<Vec<u8>> {
+        self.files.lock().unwrap().get(name).cloned()
+    }
+
+    fn commands(&self) -> Vec<String> {
+        self.commands.lock().unwrap().clone()
+    }
+}
+
+type Control = BufReader<Box<dyn Stream>>;
+
+async fn say(control: &mut Control, line: &str) {
+    control
+        .write_all(format!("{line}\r\n").as_bytes())
+        .await
+        .unwrap();
+    control.flush().await.unwrap();
+}
+
+async fn data_stream(listener: TcpListener, tls: bool) -> Box<dyn Stream> {
+    let (tcp, _) = listener.accept().await.unwrap();
+    if tls {
+        Box::new(
+            TlsAcceptor::from(test_tls::server_config())
+                .accept(tcp)
+                .await
+                .unwrap(),
+        )
+    } else {
+        Box::new(tcp)
+    }
+}
+
+fn size_of(files: &Mutex<HashMap<String, Vec<u8>>>, name: &str) -> Option<usize> {
+    files.lock().unwrap().get(name).map(Vec::len)
+}
+
+async fn serve(
+    socket: TcpStream,
+    options: Options,
+    files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    commands: Arc<Mutex<Vec<String>>>,
+    retrievals: Arc<AtomicUsize>,
+) {
+    let mut control: Control = BufReader::new(Box::new(socket));
+    // A multi-line greeting, so every session exercises the continuation handling.
+    say(&mut control, "220-stand-in FTP server").await;
+    say(&mut control, "220 ready").await;
+    let mut protected = false;
+    let mut rest = 0;
+    let mut passive: Option<TcpListener> = None;
+
+    loop {
+        let mut line = String::new();
+        if control.read_line(&mut line).await.unwrap_or(0) == 0 {
+            return;
+        }
+        let line = line.trim_end().to_string();
+        commands.lock().unwrap().push(line.clone());
+        let (command, argument) = line.split_once(' ').unwrap_or((&line, ""));
+
+        match command {
+            "AUTH" if options.tls && argument == "TLS" => {
+                say(&mut control, "234 proceed with negotiation").await;
+                let plain =
+                    core::mem::replace(&mut control, BufReader::new(Box::new(tokio::io::empty())))
+                        .into_inner();
+                let tls = TlsAcceptor::from(test_tls::server_config())
+                    .accept(plain)
+                    .await
+                    .unwrap();
+                control = BufReader::new(Box::new(tls));
+            }
+            "PBSZ" => say(&mut control, "200 PBSZ=0").await,
+            "PROT" => {
+                protected = argument == "P";
+                say(&mut control, "200 protection level set").await;
+            }
+            "USER" => say(&mut control, "331 password required").await,
+            "PASS" => say(&mut control, "230 logged in").await,
+            "TYPE" => say(&mut control, "200 type set").await,
+            "SIZE" => match size_of(&files, argument) {
+                Some(size) => say(&mut control, &format!("213 {size}")).await,
+                None => say(&mut control, "550 no such file").await,
+            },
+            "REST" if options.rest => {
+                rest = argument.parse().unwrap();
+                say(&mut control, "350 restarting").await;
+            }
+            "EPSV" if options.epsv => {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                let port = listener.local_addr().unwrap().port();
+                passive = Some(listener);
+                say(
+                    &mut control,
+                    &format!("229 Entering Extended Passive Mode (|||{port}|)"),
+                )
+                .await;
+            }
+            "PASV" => {
+                let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+                let port = listener.local_addr().unwrap().port();
+                passive = Some(listener);
+                // An address the client must not use - a NAT'd server's internal one.
+                say(
+                    &mut control,
+                    &format!(
+                        "227 Entering Passive Mode (10,9,8,7,{},{})",
+                        port >> 8,
+                        port & 0xFF
+                    ),
+                )
+                .await;
+            }
+            "RETR" => {
+                let content = files.lock().unwrap().get(argument).cloned();
+                let Some(content) = content else {
+                    say(&mut control, "550 no such file").await;
+                    continue;
+                };
+                let listener = passive.take().unwrap();
+                say(&mut control, "150 opening data connection").await;
+                let mut data = data_stream(listener, protected).await;
+                let start = core::mem::take(&mut rest) as usize;
+                let end = match options.cut_first_retr_at {
+                    Some(cut) if retrievals.fetch_add(1, Ordering::SeqCst) == 0 => cut,
+                    _ => content.len(),
+                };
+                data.write_all(&content[start..end]).await.unwrap();
+                data.shutdown().await.unwrap();
+                drop(data);
+                if end < content.len() {
+                    say(&mut control, "426 connection closed; transfer aborted").await;
+                } else {
+                    say(&mut control, "226 transfer complete").await;
+                }
+            }
+            "STOR" => {
+                let listener = passive.take().unwrap();
+                say(&mut control, "150 opening data connection").await;
+                let mut data = data_stream(listener, protected).await;
+                let mut content = Vec::new();
+                data.read_to_end(&mut content).await.unwrap();
+                files.lock().unwrap().insert(argument.to_string(), content);
+                say(&mut control, "226 transfer complete").await;
+            }
+            "QUIT" => {
+                say(&mut control, "221 goodbye").await;
+                return;
+            }
+            _ => say(&mut control, "502 command not implemented").await,
+        }
+    }
+}
+
+fn firmware() -> Vec<u8> {
+    (0..40_000u32).map(|i| (i * 13 % 251) as u8).collect()
+}
+
+fn transfer(dir: &TempDir) -> FtpFileTransfer<FileSink> {
+    FtpFileTransfer::new(FileSink::new(dir.0.join("firmware.bin")))
+        .with_io_timeout(Duration::from_secs(5))
+}
+
+#[tokio::test]
+async fn downloads_over_ftp_logging_in_with_the_urls_credentials() {
+    let image = firmware();
+    let server = FtpStandIn::start(Options::default(), &[("fw/image.bin", &image)]).await;
+    let dir = TempDir::new("download");
+    let transfer = transfer(&dir);
+    let reports = Mutex::new(Vec::new());
+    let record = |report| reports.lock().unwrap().push(report);
+
+    transfer
+        .download(
+            &server.url("ftp", "op%40site:p%3Ass@", "fw/image.bin"),
+            &TransferProgress::new(&record),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(fs::read(dir.0.join("firmware.bin")).unwrap(), image);
+    assert_eq!(
+        reports.into_inner().unwrap().last().unwrap().percent(),
+        Some(100)
+    );
+    let commands = server.commands();
+    // Decoded before sending - the server must see the account, not its URL escaping.
+    assert!(commands.contains(&"USER op@site".to_string()));
+    assert!(commands.contains(&"PASS p:ss".to_string()));
+    assert!(commands.contains(&"TYPE I".to_string()));
+}
+
+#[tokio::test]
+async fn a_url_without_credentials_logs_in_anonymously() {
+    let server = FtpStandIn::start(Options::default(), &[("fw.bin", b"image")]).await;
+    let dir = TempDir::new("anonymous");
+    let transfer = transfer(&dir);
+
+    transfer
+        .download(
+            &server.url("ftp", "", "fw.bin"),
+            &TransferProgress::ignored(),
+        )
+        .await
+        .unwrap();
+
+    assert!(server.commands().contains(&"USER anonymous".to_string()));
+}
+
+#[tokio::test]
+async fn a_retry_after_an_aborted_transfer_resumes_with_rest() {
+    let image = firmware();
+    let cut = 25_000;
+    let server = FtpStandIn::start(
+        Options {
+            cut_first_retr_at: Some(cut),
+            ..Options::default()
+        },
+        &[("fw.bin", &image)],
+    )
+    .await;
+    let dir = TempDir::new("resume");
+    let transfer = transfer(&dir);
+    let url = server.url("ftp", "", "fw.bin");
+
+    let first = transfer.download(&url, &TransferProgress::ignored()).await;
+    assert!(
+        matches!(
+            first,
+            Err(FtpFileTransferError::Rejected {
+                command: "RETR",
+                code: 426
+            })
+        ),
+        "{first:?}"
+    );
+    assert!(!dir.0.join("firmware.bin").exists());
+
+    transfer
+        .download(&url, &TransferProgress::ignored())
+        .await
+        .unwrap();
+
+    assert_eq!(fs::read(dir.0.join("firmware.bin")).unwrap(), image);
+    assert!(server.commands().contains(&format!("REST {cut}")));
+}
+
+#[tokio::test]
+async fn a_server_without_rest_gets_the_whole_file_fetched_again() {
+    let image = firmware();
+    let server = FtpStandIn::start(
+        Options {
+            rest: false,
+            cut_first_retr_at: Some(10_000),
+            ..Options::default()
+        },
+        &[("fw.bin", &image)],
+    )
+    .await;
+    let dir = TempDir::new("no-rest");
+    let transfer = transfer(&dir);
+    let url = server.url("ftp", "", "fw.bin");
+
+    assert!(
+        transfer
+            .download(&url, &TransferProgress::ignored())
+            .await
+            .is_err()
+    );
+    transfer
+        .download(&url, &TransferProgress::ignored())
+        .await
+        .unwrap();
+
+    assert_eq!(fs::read(dir.0.join("firmware.bin")).unwrap(), image);
+}
+
+#[tokio::test]
+async fn pasv_uses_the_control_connections_address_not_the_announced_one() {
+    // The stand-in announces 10.9.8.7, which nothing in this test listens on. Reaching the file
+    // at all proves the data connection went to the control connection's peer.
+    let server = FtpStandIn::start(
+        Options {
+            epsv: false,
+            ..Options::default()
+        },
+        &[("fw.bin", b"image")],
+    )
+    .await;
+    let dir = TempDir::new("pasv");
+    let transfer = transfer(&dir);
+
+    transfer
+        .download(
+            &server.url("ftp", "", "fw.bin"),
+            &TransferProgress::ignored(),
+        )
+        .await
+        .unwrap();
+
+    assert!(server.commands().contains(&"PASV".to_string()));
+    assert_eq!(fs::read(dir.0.join("firmware.bin")).unwrap(), b"image");
+}
+
+#[tokio::test]
+async fn a_missing_file_is_reported_by_its_reply_code_without_producing_a_file() {
+    let server = FtpStandIn::start(Options::default(), &[]).await;
+    let dir = TempDir::new("missing");
+    let transfer = transfer(&dir);
+
+    let result = transfer
+        .download(
+            &server.url("ftp", "", "missing.bin"),
+            &TransferProgress::ignored(),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(FtpFileTransferError::Rejected {
+            command: "RETR",
+            code: 550
+        })
+    ));
+    assert!(!dir.0.join("firmware.bin").exists());
+}
+
+#[tokio::test]
+async fn an_upload_to_a_directory_is_named_after_the_log() {
+    let server = FtpStandIn::start(Options::default(), &[]).await;
+    let dir = TempDir::new("upload-dir");
+    let transfer = transfer(&dir);
+
+    transfer
+        .upload(
+            &server.url("ftp", "user:pass@", "logs/"),
+            UploadSource::Bytes(b"security log contents"),
+            &TransferProgress::ignored(),
+        )
+        .await
+        .unwrap();
+
+    let stored = server
+        .commands()
+        .into_iter()
+        .find_map(|command| command.strip_prefix("STOR ").map(str::to_string))
+        .unwrap();
+    assert!(stored.starts_with("logs/security-"), "{stored}");
+    assert!(stored.ends_with(".log"), "{stored}");
+    assert_eq!(server.file(&stored).unwrap(), b"security log contents");
+}
+
+struct DiagnosticsLog(Vec<u8>);
+
+#[async_trait::async_trait]
+impl LogSource for DiagnosticsLog {
+    type Error = io::Error;
+
+    async fn log_len(&self, kind: LogKind) -> Result<Option<u64>, Self::Error> {
+        Ok((kind == LogKind::Diagnostics).then_some(self.0.len() as u64))
+    }
+
+    async fn read_log(
+        &self,
+        _kind: LogKind,
+        offset: u64,
+        buf: &mut [u8],
+    ) -> Result<usize, Self::Error> {
+        let rest = &self.0[offset as usize..];
+        let read = rest.len().min(buf.len());
+        buf[..read].copy_from_slice(&rest[..read]);
+        Ok(read)
+    }
+}
+
+#[tokio::test]
+async fn uploads_a_local_log_to_the_named_path() {
+    let log: Vec<u8> = (0..30_000u32).map(|i| b'a' + (i % 26) as u8).collect();
+    let server = FtpStandIn::start(Options::default(), &[]).await;
+    let dir = TempDir::new("upload-local");
+    let transfer = transfer(&dir).with_log_source(DiagnosticsLog(log.clone()));
+
+    transfer
+        .upload(
+            &server.url("ftp", "", "incoming/cp-42.log"),
+            UploadSource::Local(LogKind::Diagnostics),
+            &TransferProgress::ignored(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(server.file("incoming/cp-42.log").unwrap(), log);
+}
+
+#[tokio::test]
+async fn ftps_protects_both_connections_with_explicit_tls() {
+    let image = firmware();
+    let server = FtpStandIn::start(
+        Options {
+            tls: true,
+            ..Options::default()
+        },
+        &[("fw.bin", &image)],
+    )
+    .await;
+    let dir = TempDir::new("ftps");
+    let transfer = transfer(&dir).with_tls_config(test_tls::client_config());
+
+    transfer
+        .download(
+            &server.url("ftps", "user:secret@", "fw.bin"),
+            &TransferProgress::ignored(),
+        )
+        .await
+        .unwrap();
+    transfer
+        .upload(
+            &server.url("ftps", "user:secret@", "up.log"),
+            UploadSource::Bytes(b"over tls"),
+            &TransferProgress::ignored(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(fs::read(dir.0.join("firmware.bin")).unwrap(), image);
+    assert_eq!(server.file("up.log").unwrap(), b"over tls");
+    let commands = server.commands();
+    let auth = commands.iter().position(|c| c == "AUTH TLS").unwrap();
+    let user = commands.iter().position(|c| c == "USER user").unwrap();
+    assert!(
+        auth < user,
+        "credentials must not cross the wire before TLS"
+    );
+    assert!(commands.contains(&"PROT P".to_string()));
+}
+
+#[tokio::test]
+async fn ftps_against_a_server_that_cannot_do_tls_fails_rather_than_falling_back() {
+    let server = FtpStandIn::start(Options::default(), &[("fw.bin", b"image")]).await;
+    let dir = TempDir::new("ftps-refused");
+    let transfer = transfer(&dir).with_tls_config(test_tls::client_config());
+
+    let result = transfer
+        .download(
+            &server.url("ftps", "user:secret@", "fw.bin"),
+            &TransferProgress::ignored(),
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(FtpFileTransferError::Rejected {
+            command: "AUTH",
+            code: 502
+        })
+    ));
+    assert!(!server.commands().iter().any(|c| c.starts_with("PASS")));
+}
+
+#[tokio::test]
+async fn a_url_that_decodes_to_a_second_command_is_refused() {
+    let dir = TempDir::new("injection");
+    let transfer = transfer(&dir);
+
+    let result = transfer
+        .download(
+            "ftp://127.0.0.1:9/fw.bin%0D%0ADELE%20other.bin",
+            &TransferProgress::ignored(),
+        )
+        .await;
+
+    assert!(matches!(result, Err(FtpFileTransferError::InvalidUrl)));
+}
+
+#[tokio::test]
+async fn a_router_sends_ftp_urls_to_the_ftp_backend_and_the_rest_elsewhere() {
+    fn usable_by_the_builder<F: FileTransfer + Send + Sync + 'static>(_: &F) {}
+
+    let server = FtpStandIn::start(Options::default(), &[("fw.bin", b"image")]).await;
+    let dir = TempDir::new("router");
+    let sink = Arc::new(FileSink::new(dir.0.join("firmware.bin")));
+    let router = SchemeRouter::new(
+        &["ftp", "ftps"],
+        FtpFileTransfer::new(sink.clone()).with_io_timeout(Duration::from_secs(5)),
+        crate::hardware::NoFileTransfer,
+    );
+    usable_by_the_builder(&router);
+
+    router
+        .download(
+            &server.url("FTP", "", "fw.bin"),
+            &TransferProgress::ignored(),
+        )
+        .await
+        .unwrap();
+    let elsewhere = router
+        .download(
+            "https://example.invalid/fw.bin",
+            &TransferProgress::ignored(),
+        )
+        .await;
+
+    assert_eq!(fs::read(sink.path()).unwrap(), b"image");
+    assert!(matches!(elsewhere, Err(SchemeRouterError::Second(_))));
+}
+
+#[test]
+fn passive_replies_parse_to_their_port() {
+    assert_eq!(
+        parse_epsv("Entering Extended Passive Mode (|||6446|)"),
+        Some(6446)
+    );
+    assert_eq!(
+        parse_pasv("Entering Passive Mode (192,168,1,2,25,46)"),
+        Some(25 * 256 + 46)
+    );
+    assert_eq!(parse_pasv("Entering Passive Mode (192,168,1,2,25)"), None);
+    assert_eq!(parse_epsv("Entering Extended Passive Mode"), None);
+}
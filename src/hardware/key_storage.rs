@@ -364,7 +364,10 @@ impl KeyStore for NoKeyStore {
 /// software key-storage fallback still needs *something* to actually generate a keypair and
 /// produce a signature, so [`SoftKeyStore`] takes that something as a type parameter rather than
 /// reaching for a crypto crate this crate would then force on every consumer (including ones with
-/// a real secure element and no use for software crypto at all).
+/// a real secure element and no use for software crypto at all). The `software-crypto` feature
+/// ships one anyway, for everyone else:
+/// [`RustCryptoBackend`](crate::hardware::RustCryptoBackend), pure Rust and opt-out like any
+/// other feature.
 pub trait SoftwareCrypto {
     /// The error type returned by a failed crypto operation.
     type Error: core::error::Error + Send + Sync + 'static;
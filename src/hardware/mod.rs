@@ -6,6 +6,7 @@
 //! protocol handling, state machines, transaction lifecycle, and networking are this crate's own
 //! responsibility, not something an integrator needs to touch.
 
+mod ab_firmware;
 #[cfg(feature = "battery-swap")]
 mod battery_swap;
 mod capabilities;
@@ -16,21 +17,44 @@ mod command_receiver;
 mod connector;
 mod display;
 mod electrical;
+#[cfg(feature = "encrypted-storage")]
+mod encrypted_storage;
 mod event_sender;
 mod evse;
 #[cfg(test)]
 mod fault_injection;
+#[cfg(feature = "std")]
+mod file_sink;
+#[cfg(feature = "std")]
+mod file_storage;
 mod file_transfer;
 mod firmware;
 mod firmware_publisher;
+#[cfg(feature = "signed-firmware")]
+mod firmware_signature;
+mod flash_storage;
+#[cfg(feature = "ftp-file-transfer")]
+mod ftp_file_transfer;
+#[cfg(feature = "http-file-transfer")]
+mod http_file_transfer;
 mod iso15118;
 mod key_storage;
 mod ocsp;
 #[cfg(feature = "payment")]
 mod payment_terminal;
+#[cfg(feature = "software-crypto")]
+mod rust_crypto;
 mod storage;
+#[cfg(any(feature = "http-file-transfer", feature = "ftp-file-transfer"))]
+mod transfer_net;
 mod watchdog;
 
+pub use self::ab_firmware::{
+    AbFirmwareInstaller, AbFirmwareInstallerError, DEFAULT_ROLLBACK_WINDOW_SECS, FirmwareSlots,
+    SlotState, TrialOutcome,
+};
+#[cfg(feature = "std")]
+pub use self::ab_firmware::{InMemoryFirmwareSlots, InMemoryFirmwareSlotsError};
 #[cfg(feature = "battery-swap")]
 pub use self::battery_swap::{BatterySwapStation, NoBatterySwapStation, NoBatterySwapStationError};
 pub use self::capabilities::{
@@ -47,19 +71,36 @@ pub use self::command_executor::execute_hardware_command;
 pub use self::command_receiver::HardwareCommandReceiver;
 pub use self::connector::Connector;
 pub use self::display::{Display, NoDisplay, NoDisplayError};
+#[cfg(feature = "encrypted-storage")]
+pub use self::encrypted_storage::{
+    DeviceSecret, DeviceSecretError, EncryptedStorage, EncryptedStorageError, KeyStoreSecret,
+    MIN_STORAGE_SECRET_LEN, StorageMigration, StorageSecret,
+};
 pub use self::event_sender::HardwareEventSender;
 pub use self::evse::Evse;
+#[cfg(feature = "std")]
+pub use self::file_sink::FileSink;
+#[cfg(feature = "std")]
+pub use self::file_storage::{FileStorage, FileStorageError};
 pub use self::file_transfer::{
-    FileTransfer, LogKind, NoFileTransfer, NoFileTransferError, TransferProgress, TransferReport,
-    UploadSource,
+    DownloadSink, FileTransfer, LogKind, LogSource, NoFileTransfer, NoFileTransferError,
+    NoLogSource, SchemeRouter, SchemeRouterError, TransferProgress, TransferReport, UploadSource,
 };
 pub use self::firmware::{
-    FirmwareInstallOutcome, FirmwareInstaller, FirmwareVerificationOutcome, FirmwareVerifier,
-    NoFirmwareInstaller, NoFirmwareInstallerError, NoFirmwareVerifier, NoFirmwareVerifierError,
+    FirmwareImage, FirmwareInstallOutcome, FirmwareInstaller, FirmwareVerificationOutcome,
+    FirmwareVerifier, NoFirmwareInstaller, NoFirmwareInstallerError, NoFirmwareVerifier,
+    NoFirmwareVerifierError,
 };
 pub use self::firmware_publisher::{
     FirmwarePublisher, NoFirmwarePublisher, NoFirmwarePublisherError,
 };
+#[cfg(feature = "signed-firmware")]
+pub use self::firmware_signature::{SignedFirmwareVerifier, SignedFirmwareVerifierError};
+pub use self::flash_storage::{Flash, FlashStorage, FlashStorageError};
+#[cfg(feature = "ftp-file-transfer")]
+pub use self::ftp_file_transfer::{FtpFileTransfer, FtpFileTransferError};
+#[cfg(feature = "http-file-transfer")]
+pub use self::http_file_transfer::{HttpFileTransfer, HttpFileTransferError, UploadMethod};
 pub use self::iso15118::{
     Iso15118CertificateAction, Iso15118CertificateRequest, Iso15118CertificateResult,
     Iso15118CertificateStatus, Iso15118Controller, NoIso15118Controller, NoIso15118ControllerError,
@@ -77,6 +118,8 @@ pub use self::payment_terminal::{
     MerchantIdentity, NoPaymentTerminal, NoPaymentTerminalError, PaymentTerminal,
     PaymentTerminalInfo, PaymentTerminalStatus,
 };
+#[cfg(feature = "software-crypto")]
+pub use self::rust_crypto::{RustCryptoBackend, RustCryptoBackendError};
 pub use self::storage::{AtomicStorage, NoStorage, NoStorageError, Storage};
 #[cfg(feature = "std")]
 pub use self::storage::{InMemoryStorage, InMemoryStorageError};
@@ -0,0 +1,289 @@
+//! [`RustCryptoBackend`]: a ready-made [`SoftwareCrypto`] so [`SoftKeyStore`] works without an
+//! integrator writing their own key generation and signing.
+//!
+//! Pure Rust and `no_std` + `alloc` - the RustCrypto `p256`, `p384` and `rsa` crates behind the
+//! `software-crypto` feature - so it builds for the same targets as the rest of this crate. The
+//! one thing it cannot supply on a microcontroller is entropy, so the caller hands in a function
+//! that produces a cryptographically secure RNG (a TRNG peripheral driver, typically); std targets
+//! can use [`RustCryptoBackend::with_os_rng`].
+//!
+//! # Encodings
+//!
+//! Chosen so the keys it generates plug straight into the rest of this crate:
+//!
+//! - **Public keys** are `SubjectPublicKeyInfo` DER, the encoding
+//!   [`build_signed_csr`](crate::certificates::build_signed_csr) embeds verbatim and
+//!   [`crate::mutual_tls`] hands to rustls.
+//! - **Signatures** are over the digest [`KeyStore::sign`](crate::hardware::KeyStore::sign) is
+//!   given, not over a message: ECDSA as a DER `Ecdsa-Sig-Value` (what X.509 and TLS carry), RSA
+//!   as PKCS#1 v1.5 with the SHA-256 `DigestInfo`.
+//! - **Private keys**, which only [`SoftKeyStore`] ever sees, are the raw big-endian scalar for
+//!   ECDSA and PKCS#1 `RSAPrivateKey` DER for RSA.
+//!
+//! # What this does not change
+//!
+//! A key this backend generates is still a key in [`Storage`](crate::hardware::Storage):
+//! [`SoftKeyStore`] keeps reporting [`KeyStoreBacking::Software`](crate::hardware::KeyStoreBacking::Software),
+//! and the key is exactly as safe as that medium - wrap it in
+//! [`EncryptedStorage`](crate::hardware::EncryptedStorage) where the flash can be read out.
+//!
+//! RSA signing uses `rsa`'s blinded private-key operation, but that crate's Marvin timing side
+//! channel (RUSTSEC-2023-0071) has no fixed release; prefer an ECDSA key where the CSMS's CA
+//! accepts one.
+
+#[cfg(doc)]
+use super::key_storage::SoftKeyStore;
+use super::key_storage::{PublicKey, SignatureAlgorithm, SoftwareCrypto};
+use alloc::vec::Vec;
+use p256::ecdsa::signature::hazmat::PrehashSigner;
+use p256::pkcs8::EncodePublicKey;
+use rand_core::CryptoRngCore;
+use rsa::pkcs1::{DecodeRsaPrivateKey, EncodeRsaPrivateKey};
+use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use sha2::Sha256;
+
+const SUPPORTED_ALGORITHMS: &[SignatureAlgorithm] = &[
+    SignatureAlgorithm::EcdsaP256Sha256,
+    SignatureAlgorithm::EcdsaP384Sha384,
+    SignatureAlgorithm::Rsa2048Sha256,
+    SignatureAlgorithm::Rsa3072Sha256,
+];
+
+/// The error type of [`RustCryptoBackend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RustCryptoBackendError {
+    /// Key generation failed - for RSA, the RNG could not produce a usable prime.
+    KeyGeneration,
+    /// A public key could not be encoded as `SubjectPublicKeyInfo`, or a private key as PKCS#1.
+    Encoding,
+    /// The private key handed to [`SoftwareCrypto::sign`] does not decode for its algorithm.
+    MalformedPrivateKey,
+    /// Signing failed, including a digest of a length the algorithm cannot sign.
+    Signing,
+}
+
+impl core::fmt::Display for RustCryptoBackendError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::KeyGeneration => f.write_str("key generation failed"),
+            Self::Encoding => f.write_str("could not encode the generated key"),
+            Self::MalformedPrivateKey => f.write_str("the stored private key does not decode"),
+            Self::Signing => f.write_str("signing failed"),
+        }
+    }
+}
+
+impl core::error::Error for RustCryptoBackendError {}
+
+/// A pure-Rust [`SoftwareCrypto`] for ECDSA P-256/P-384 and RSA 2048/3072 - see the module docs.
+///
+/// `rng` is called once per operation that needs randomness (every key generation, and every RSA
+/// signature for blinding) and must return a cryptographically secure generator. ECDSA
+/// signatures are deterministic (RFC 6979) and do not call it.
+#[derive(Debug, Clone)]
+pub struct RustCryptoBackend<F> {
+    rng: F,
+}
+
+impl<F, R> RustCryptoBackend<F>
+where
+    F: Fn() -> R,
+    R: CryptoRngCore,
+{
+    /// A backend drawing its randomness from whatever `rng` returns.
+    pub fn new(rng: F) -> Self {
+        Self { rng }
+    }
+}
+
+#[cfg(feature = "std")]
+impl RustCryptoBackend<fn() -> rand_core::OsRng> {
+    /// A backend drawing its randomness from the operating system.
+    pub fn with_os_rng() -> Self {
+        Self {
+            rng: || rand_core::OsRng,
+        }
+    }
+}
+
+fn rsa_key_pair(
+    rng: &mut impl CryptoRngCore,
+    bits: usize,
+) -> Result<(Vec<u8>, Vec<u8>), RustCryptoBackendError> {
+    let private_key =
+        RsaPrivateKey::new(rng, bits).map_err(|_| RustCryptoBackendError::KeyGeneration)?;
+    let public_key = private_key
+        .to_public_key()
+        .to_public_key_der()
+        .map_err(|_| RustCryptoBackendError::Encoding)?;
+    let private_key = private_key
+        .to_pkcs1_der()
+        .map_err(|_| RustCryptoBackendError::Encoding)?;
+    Ok((private_key.as_bytes().to_vec(), public_key.into_vec()))
+}
+
+impl<F, R> SoftwareCrypto for RustCryptoBackend<F>
+where
+    F: Fn() -> R,
+    R: CryptoRngCore,
+{
+    type Error = RustCryptoBackendError;
+
+    fn generate_key_pair(
+        &self,
+        algorithm: SignatureAlgorithm,
+    ) -> Result<(Vec<u8>, PublicKey), Self::Error> {
+        let mut rng = (self.rng)();
+        let (private_key, public_key) = match algorithm {
+            SignatureAlgorithm::EcdsaP256Sha256 => {
+                let key = p256::ecdsa::SigningKey::random(&mut rng);
+                let public_key = p256::PublicKey::from(key.verifying_key())
+                    .to_public_key_der()
+                    .map_err(|_| RustCryptoBackendError::Encoding)?;
+                (key.to_bytes().to_vec(), public_key.into_vec())
+            }
+            SignatureAlgorithm::EcdsaP384Sha384 => {
+                let key = p384::ecdsa::SigningKey::random(&mut rng);
+                let public_key = p384::PublicKey::from(key.verifying_key())
+                    .to_public_key_der()
+                    .map_err(|_| RustCryptoBackendError::Encoding)?;
+                (key.to_bytes().to_vec(), public_key.into_vec())
+            }
+            SignatureAlgorithm::Rsa2048Sha256 => rsa_key_pair(&mut rng, 2048)?,
+            SignatureAlgorithm::Rsa3072Sha256 => rsa_key_pair(&mut rng, 3072)?,
+        };
+        Ok((
+            private_key,
+            PublicKey {
+                algorithm,
+                bytes: public_key,
+            },
+        ))
+    }
+
+    fn sign(
+        &self,
+        algorithm: SignatureAlgorithm,
+        private_key: &[u8],
+        digest: &[u8],
+    ) -> Result<Vec<u8>, Self::Error> {
+        match algorithm {
+            SignatureAlgorithm::EcdsaP256Sha256 => {
+                let key = p256::ecdsa::SigningKey::from_slice(private_key)
+                    .map_err(|_| RustCryptoBackendError::MalformedPrivateKey)?;
+                let signature: p256::ecdsa::Signature = key
+                    .sign_prehash(digest)
+                    .map_err(|_| RustCryptoBackendError::Signing)?;
+                Ok(signature.to_der().as_bytes().to_vec())
+            }
+            SignatureAlgorithm::EcdsaP384Sha384 => {
+                let key = p384::ecdsa::SigningKey::from_slice(private_key)
+                    .map_err(|_| RustCryptoBackendError::MalformedPrivateKey)?;
+                let signature: p384::ecdsa::Signature = key
+                    .sign_prehash(digest)
+                    .map_err(|_| RustCryptoBackendError::Signing)?;
+                Ok(signature.to_der().as_bytes().to_vec())
+            }
+            SignatureAlgorithm::Rsa2048Sha256 | SignatureAlgorithm::Rsa3072Sha256 => {
+                let key = RsaPrivateKey::from_pkcs1_der(private_key)
+                    .map_err(|_| RustCryptoBackendError::MalformedPrivateKey)?;
+                key.sign_with_rng(&mut (self.rng)(), Pkcs1v15Sign::new::<Sha256>(), digest)
+                    .map_err(|_| RustCryptoBackendError::Signing)
+            }
+        }
+    }
+
+    fn supported_algorithms(&self) -> &[SignatureAlgorithm] {
+        SUPPORTED_ALGORITHMS
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::hardware::{InMemoryStorage, KeyStore, SoftKeyStore};
+    use p256::ecdsa::signature::hazmat::PrehashVerifier;
+    use p256::pkcs8::DecodePublicKey;
+
+    fn digest(message: &[u8]) -> Vec<u8> {
+        crate::certificates::csr::sha256(message)
+    }
+
+    #[tokio::test]
+    async fn an_ecdsa_p256_signature_verifies_against_the_published_key() {
+        let key_store = SoftKeyStore::new(InMemoryStorage::new(), RustCryptoBackend::with_os_rng());
+        let key = key_store
+            .generate_key_pair(SignatureAlgorithm::EcdsaP256Sha256)
+            .await
+            .unwrap();
+        let signature = key_store
+            .sign(&key.handle, &digest(b"a CSR"))
+            .await
+            .unwrap();
+
+        let verifying_key =
+            p256::ecdsa::VerifyingKey::from_public_key_der(&key.public_key.bytes).unwrap();
+        let signature = p256::ecdsa::Signature::from_der(&signature).unwrap();
+        verifying_key
+            .verify_prehash(&digest(b"a CSR"), &signature)
+            .unwrap();
+        assert!(
+            verifying_key
+                .verify_prehash(&digest(b"another CSR"), &signature)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn an_ecdsa_p384_signature_verifies_against_the_published_key() {
+        let backend = RustCryptoBackend::with_os_rng();
+        let (private_key, public_key) = backend
+            .generate_key_pair(SignatureAlgorithm::EcdsaP384Sha384)
+            .unwrap();
+        let digest = [7u8; 48];
+        let signature = backend
+            .sign(SignatureAlgorithm::EcdsaP384Sha384, &private_key, &digest)
+            .unwrap();
+
+        let verifying_key =
+            p384::ecdsa::VerifyingKey::from_public_key_der(&public_key.bytes).unwrap();
+        let signature = p384::ecdsa::Signature::from_der(&signature).unwrap();
+        verifying_key.verify_prehash(&digest, &signature).unwrap();
+    }
+
+    #[test]
+    fn an_rsa_signature_verifies_against_the_published_key() {
+        let backend = RustCryptoBackend::with_os_rng();
+        let (private_key, public_key) = backend
+            .generate_key_pair(SignatureAlgorithm::Rsa2048Sha256)
+            .unwrap();
+        let signature = backend
+            .sign(
+                SignatureAlgorithm::Rsa2048Sha256,
+                &private_key,
+                &digest(b"a TLS handshake"),
+            )
+            .unwrap();
+
+        let verifying_key = rsa::RsaPublicKey::from_public_key_der(&public_key.bytes).unwrap();
+        verifying_key
+            .verify(
+                Pkcs1v15Sign::new::<Sha256>(),
+                &digest(b"a TLS handshake"),
+                &signature,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn a_private_key_for_another_algorithm_is_refused_rather_than_misread() {
+        let backend = RustCryptoBackend::with_os_rng();
+        let (p256_key, _) = backend
+            .generate_key_pair(SignatureAlgorithm::EcdsaP256Sha256)
+            .unwrap();
+        assert_eq!(
+            backend.sign(SignatureAlgorithm::Rsa2048Sha256, &p256_key, &[0; 32]),
+            Err(RustCryptoBackendError::MalformedPrivateKey)
+        );
+    }
+}
@@ -45,7 +45,8 @@ use alloc::vec::Vec;
 ///   the simple thing; do not build a journal underneath one.
 ///
 /// Nothing here requires a filesystem, a transaction log, or ordering guarantees between
-/// different keys.
+/// different keys. A std target with a filesystem can use
+/// [`FileStorage`](crate::hardware::FileStorage) rather than writing its own.
 #[async_trait::async_trait]
 pub trait Storage {
     /// The error type returned by a failed storage operation.
@@ -245,6 +246,7 @@ impl Storage for InMemoryStorage {
 ///   still needs the underlying [`Storage`] impl to place `.a`/`.b` pairs (and distinct keys
 ///   generally) in a way that a torn write to one cannot clobber another - e.g. separate erase
 ///   sectors, or a wear-levelling flash filesystem that already provides that isolation.
+///   [`FlashStorage`](crate::hardware::FlashStorage) is that layer for bare NOR flash.
 /// - **Detecting corruption that leaves a record's bytes complete and checksum-valid but wrong.**
 ///   The CRC32 catches torn/partial writes and bit-level flash glitches; it cannot catch, say, the
 ///   wrong (but complete and self-consistent) value being written due to a logic bug upstream.
@@ -355,7 +357,16 @@ fn seq_is_later(candidate: u64, than: u64) -> bool {
 /// avoids pulling in a `crc`/`crc32fast` dependency or a 1 KiB static table for a check that runs
 /// once per write on an embedded target.
 fn crc32(data: &[u8]) -> u32 {
-    let mut crc: u32 = 0xFFFF_FFFF;
+    !crc32_update(CRC32_INIT, data)
+}
+
+/// The register value a CRC32 starts from; the finished checksum is the bitwise NOT of the
+/// register after the last [`crc32_update`].
+pub(super) const CRC32_INIT: u32 = 0xFFFF_FFFF;
+
+/// Feeds `data` into a running CRC32 register, so a record too large to hold in RAM at once can
+/// be checksummed a chunk at a time ([`crate::hardware::FlashStorage`] reads flash that way).
+pub(super) fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
     for &byte in data {
         crc ^= u32::from(byte);
         for _ in 0..8 {
@@ -363,7 +374,7 @@ fn crc32(data: &[u8]) -> u32 {
             crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
         }
     }
-    !crc
+    crc
 }
 
 impl<S: Storage> AtomicStorage<S> {
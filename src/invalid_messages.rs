@@ -0,0 +1,430 @@
+//! Counting and reporting invalid inbound CSMS messages - OCPP's `InvalidMessages` security event.
+//!
+//! # What counts
+//!
+//! Three kinds, one [`InvalidMessageKind`] each:
+//!
+//! - **Malformed** - a frame that is not an OCPP-J message at all. `ocpp-client`'s read loop
+//!   drops these with a `warn!` and no reply, so without this module nothing but a log line
+//!   records them.
+//! - **Undecodable** - a CALL whose payload does not match its action's schema: a missing
+//!   required field, a wrong type, an enumeration value OCPP does not define. `ocpp-client`
+//!   answers it with a `NotImplemented` CALLERROR (`"Action 'Failed to parse payload for
+//!   <action>' is not implemented"`) before any handler in this crate runs.
+//! - **Over a message limit** - a CALL that decoded, but carried more than the
+//!   `ItemsPerMessage`/`BytesPerMessage` this charge point declares. Refused by
+//!   [`crate::message_limits`], which reports it here.
+//!
+//! A CALL for an action this build does not implement is **not** counted: answering
+//! `NotImplemented` for a block the hardware does not declare is ordinary traffic (`C3.1`), not a
+//! CSMS misbehaving.
+//!
+//! # Where each is seen, and on which connections
+//!
+//! Limit refusals are counted in the handler that refuses them, so every connection is covered.
+//!
+//! The other two are only visible on the wire, and counted only once there is an actor to count
+//! against - so on every redial, but not on the first connection: either `ocpp_client::connect`
+//! builds it with no hook, or `crate::network_switch::ConnectionTarget` (private) dials it before
+//! the actor it would report to exists. Every redial through that target wraps its transport in
+//! [`InvalidMessageStream`] (malformed frames, inbound) and [`InvalidMessageSink`] (the
+//! decode-failure CALLERRORs `ocpp-client` sends back, outbound). An integrator who builds a
+//! `Client` over their own transport can wrap it the same way.
+//!
+//! # Rate limiting
+//!
+//! `InvalidMessages` is the one security event a remote party can raise at will, and every raised
+//! event lands in the bounded security log (A04.FR.04). One entry per message would let a CSMS -
+//! or anyone impersonating one - flush that log with noise. So the first invalid message after a
+//! quiet interval is reported at once, and everything after it is folded into one summary per
+//! interval by [`run_invalid_message_summaries`] (see [`crate::state::InvalidMessageTally`]).
+//! The count itself is never rate-limited: `SecurityCtrlr.InvalidMessages` in the device model
+//! holds every message counted since boot, readable with one `GetVariables`.
+
+use crate::actor::ChargePointActor;
+use crate::state::{ChargePointEvent, InvalidMessage, InvalidMessageKind};
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use ocpp_client::{TransportError, TransportEvent, TransportSink, TransportStream};
+
+/// The description `ocpp-client` gives the CALLERROR it answers an undecodable CALL with, either
+/// side of the action name. It comes out this way because the decode-failure text is passed
+/// through the same `not_implemented` constructor an unknown action uses, which wraps it again.
+const DECODE_FAILURE_PREFIX: &str = "Action 'Failed to parse payload for ";
+const DECODE_FAILURE_SUFFIX: &str = "' is not implemented";
+
+/// Counts `message` against this charge point, raising `InvalidMessages` if the rate limit
+/// allows it (see the module docs). The one entry point for recording an invalid message, in the
+/// same way [`crate::security::report_security_event`] is for security events.
+pub async fn report_invalid_message(actor: &ChargePointActor, message: InvalidMessage) {
+    tracing::warn!(
+        kind = ?message.kind,
+        action = message.action.as_deref(),
+        "the CSMS sent an invalid message"
+    );
+    let _ = actor
+        .send(ChargePointEvent::InvalidMessageReceived(message))
+        .await;
+}
+
+/// Ends one rate-limiting interval every `interval_secs`, so whatever was counted during it goes
+/// out as a single `InvalidMessages` summary - see [`crate::state::InvalidMessageTally`].
+///
+/// Runs forever. Without it, the first invalid message is still reported and every one is still
+/// counted, but nothing after the first ever reaches the security log.
+pub async fn run_invalid_message_summaries<B>(
+    actor: &ChargePointActor,
+    backoff: &B,
+    interval_secs: u32,
+) where
+    B: crate::provisioning::Backoff,
+{
+    loop {
+        backoff.wait(interval_secs.max(1)).await;
+        let _ = actor.send(ChargePointEvent::InvalidMessageSummaryDue).await;
+    }
+}
+
+/// Whether `frame` is something `ocpp-client`'s read loop would drop before dispatching it: not
+/// JSON, not an array, no integer message type id, or a CALL that is not
+/// `[2, "<uniqueId>", "<action>", <payload>]`.
+fn is_malformed(frame: &str) -> bool {
+    let Ok(serde_json::Value::Array(items)) = serde_json::from_str::<serde_json::Value>(frame)
+    else {
+        return true;
+    };
+    match items.first().and_then(serde_json::Value::as_u64) {
+        None => true,
+        Some(2) => !(items.len() == 4 && items[1].is_string() && items[2].is_string()),
+        Some(_) => false,
+    }
+}
+
+/// The action an outbound CALLERROR says failed to decode, if `frame` is one.
+fn undecodable_action(frame: &str) -> Option<String> {
+    // Cheap pre-check: this runs on every outbound frame, and nearly all of them are not this.
+    if !frame.contains(DECODE_FAILURE_PREFIX) {
+        return None;
+    }
+    let serde_json::Value::Array(items) = serde_json::from_str(frame).ok()? else {
+        return None;
+    };
+    if items.first().and_then(serde_json::Value::as_u64) != Some(4) {
+        return None;
+    }
+    items
+        .get(3)?
+        .as_str()?
+        .strip_prefix(DECODE_FAILURE_PREFIX)?
+        .strip_suffix(DECODE_FAILURE_SUFFIX)
+        .map(Into::into)
+}
+
+/// Wraps an [`ocpp_client::TransportStream`], counting each inbound frame that is not a valid
+/// OCPP-J message - see the module docs.
+///
+/// The frame is still forwarded: `ocpp-client` drops it itself, and a wrapper that only observes
+/// cannot change what the client does with a connection.
+pub struct InvalidMessageStream {
+    inner: Box<dyn TransportStream>,
+    actor: ChargePointActor,
+}
+
+impl InvalidMessageStream {
+    /// Wraps `inner`, reporting malformed frames to `actor`.
+    pub fn new(inner: Box<dyn TransportStream>, actor: ChargePointActor) -> Self {
+        Self { inner, actor }
+    }
+}
+
+impl TransportStream for InvalidMessageStream {
+    fn recv<'a>(
+        &'a mut self,
+    ) -> Pin<Box<dyn Future<Output = Result<Option<TransportEvent>, TransportError>> + Send + 'a>>
+    {
+        Box::pin(async move {
+            let event = self.inner.recv().await?;
+            // Still cancel-safe: the only await after `inner.recv` is for a frame `ocpp-client`
+            // would drop anyway, so a cancellation there loses nothing the client would have used.
+            if let Some(TransportEvent::Frame(text)) = &event
+                && is_malformed(text)
+            {
+                report_invalid_message(
+                    &self.actor,
+                    InvalidMessage {
+                        kind: InvalidMessageKind::Malformed,
+                        action: None,
+                    },
+                )
+                .await;
+            }
+            Ok(event)
+        })
+    }
+}
+
+/// Wraps an [`ocpp_client::TransportSink`], counting each CALL `ocpp-client` answers with a
+/// decode-failure CALLERROR - see the module docs for why the reply, rather than the CALL, is
+/// where this is visible.
+pub struct InvalidMessageSink {
+    inner: Box<dyn TransportSink>,
+    actor: ChargePointActor,
+}
+
+impl InvalidMessageSink {
+    /// Wraps `inner`, reporting undecodable CALLs to `actor`.
+    pub fn new(inner: Box<dyn TransportSink>, actor: ChargePointActor) -> Self {
+        Self { inner, actor }
+    }
+}
+
+impl TransportSink for InvalidMessageSink {
+    fn send<'a>(
+        &'a mut self,
+        frame: String,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+        Box::pin(async move {
+            if let Some(action) = undecodable_action(&frame) {
+                report_invalid_message(
+                    &self.actor,
+                    InvalidMessage {
+                        kind: InvalidMessageKind::Undecodable,
+                        action: Some(action),
+                    },
+                )
+                .await;
+            }
+            self.inner.send(frame).await
+        })
+    }
+
+    fn ping<'a>(
+        &'a mut self,
+        payload: Vec<u8>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+        self.inner.ping(payload)
+    }
+
+    fn pong<'a>(
+        &'a mut self,
+        payload: Vec<u8>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+        self.inner.pong(payload)
+    }
+
+    fn close<'a>(
+        &'a mut self,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+        self.inner.close()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::TokioExecutor;
+    use crate::state::{Component, SecurityEventType, Variable, VariableAttributeType};
+    use alloc::string::ToString;
+
+    /// A [`TransportSink`] that records what it was asked to send.
+    #[derive(Default)]
+    struct RecordingSink {
+        sent: Vec<String>,
+    }
+
+    impl TransportSink for RecordingSink {
+        fn send<'a>(
+            &'a mut self,
+            frame: String,
+        ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+            self.sent.push(frame);
+            Box::pin(async { Ok(()) })
+        }
+
+        fn ping<'a>(
+            &'a mut self,
+            _payload: Vec<u8>,
+        ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn pong<'a>(
+            &'a mut self,
+            _payload: Vec<u8>,
+        ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn close<'a>(
+            &'a mut self,
+        ) -> Pin<Box<dyn Future<Output = Result<(), TransportError>> + Send + 'a>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    /// A [`TransportStream`] that yields one frame per `recv`, then `None`.
+    struct ScriptedStream(Vec<String>);
+
+    impl TransportStream for ScriptedStream {
+        fn recv<'a>(
+            &'a mut self,
+        ) -> Pin<Box<dyn Future<Output = Result<Option<TransportEvent>, TransportError>> + Send + 'a>>
+        {
+            Box::pin(async move {
+                Ok((!self.0.is_empty()).then(|| TransportEvent::Frame(self.0.remove(0))))
+            })
+        }
+    }
+
+    fn invalid_message_count(actor: &ChargePointActor) -> String {
+        actor
+            .state()
+            .device_model
+            .get(
+                &Component {
+                    name: "SecurityCtrlr".into(),
+                    instance: None,
+                    evse: None,
+                },
+                &Variable {
+                    name: "InvalidMessages".into(),
+                    instance: None,
+                },
+            )
+            .and_then(|definition| definition.attribute(VariableAttributeType::Actual))
+            .map(|attribute| attribute.value.clone())
+            .expect("SecurityCtrlr.InvalidMessages is registered by default")
+    }
+
+    #[test]
+    fn only_frames_ocpp_client_would_drop_are_malformed() {
+        for malformed in [
+            "not json at all { [ garbage",
+            r#"{"action":"Reset"}"#,
+            r#"["2","id","Reset",{}]"#,
+            r#"[2,"id","Reset"]"#,
+            r#"[2,17,"Reset",{}]"#,
+            "[]",
+        ] {
+            assert!(is_malformed(malformed), "{malformed}");
+        }
+        for well_formed in [
+            r#"[2,"id","Reset",{"type":"Immediate"}]"#,
+            // The envelope is fine; whether the payload decodes is the sink's to observe.
+            r#"[2,"id","Reset",{}]"#,
+            r#"[3,"id",{}]"#,
+            r#"[4,"id","NotImplemented","",{}]"#,
+        ] {
+            assert!(!is_malformed(well_formed), "{well_formed}");
+        }
+    }
+
+    #[test]
+    fn only_a_decode_failure_callerror_names_an_undecodable_action() {
+        assert_eq!(
+            undecodable_action(
+                r#"[4,"m-1","NotImplemented","Action 'Failed to parse payload for GetVariables' is not implemented",{}]"#
+            ),
+            Some("GetVariables".to_string())
+        );
+        // A refusal for an action this build does not implement is not the CSMS misbehaving.
+        assert_eq!(
+            undecodable_action(
+                r#"[4,"m-1","NotImplemented","Action 'ReserveNow' is not implemented",{}]"#
+            ),
+            None
+        );
+        // Nor is a CALL of our own that happens to carry the text somewhere in its payload.
+        assert_eq!(
+            undecodable_action(
+                r#"[2,"m-2","DataTransfer",{"data":"Action 'Failed to parse payload for X' is not implemented"}]"#
+            ),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn an_undecodable_call_is_counted_and_its_callerror_still_goes_out() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let mut security_events = actor.subscribe_security_events();
+        let mut sink = InvalidMessageSink::new(Box::new(RecordingSink::default()), actor.clone());
+
+        let callerror = r#"[4,"m-1","NotImplemented","Action 'Failed to parse payload for GetVariables' is not implemented",{}]"#;
+        sink.send(callerror.into()).await.unwrap();
+        sink.send(r#"[3,"m-2",{}]"#.into()).await.unwrap();
+
+        assert_eq!(invalid_message_count(&actor), "1");
+        let raised = security_events.recv().await.unwrap();
+        assert_eq!(raised.event_type, SecurityEventType::InvalidMessages);
+        assert_eq!(
+            raised.tech_info.as_deref(),
+            Some("GetVariables carried a payload that does not match its schema")
+        );
+    }
+
+    #[tokio::test]
+    async fn a_malformed_frame_is_counted_and_still_forwarded() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let mut stream = InvalidMessageStream::new(
+            Box::new(ScriptedStream(alloc::vec![
+                "garbage".to_string(),
+                r#"[2,"id","Heartbeat",{}]"#.to_string(),
+            ])),
+            actor.clone(),
+        );
+
+        assert!(matches!(
+            stream.recv().await.unwrap(),
+            Some(TransportEvent::Frame(text)) if text == "garbage"
+        ));
+        assert!(stream.recv().await.unwrap().is_some());
+        assert_eq!(invalid_message_count(&actor), "1");
+    }
+
+    #[tokio::test]
+    async fn a_flood_inside_one_interval_reaches_the_security_log_as_one_summary() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let mut security_events = actor.subscribe_security_events();
+        for _ in 0..25 {
+            report_invalid_message(
+                &actor,
+                InvalidMessage {
+                    kind: InvalidMessageKind::Malformed,
+                    action: None,
+                },
+            )
+            .await;
+        }
+        actor
+            .send(ChargePointEvent::InvalidMessageSummaryDue)
+            .await
+            .unwrap();
+
+        let first = security_events.recv().await.unwrap();
+        let summary = security_events.recv().await.unwrap();
+        assert_eq!(
+            first.tech_info.as_deref(),
+            Some("received a frame that is not a valid OCPP-J message")
+        );
+        assert!(
+            summary
+                .tech_info
+                .as_deref()
+                .is_some_and(|info| info.starts_with("24 more invalid messages")),
+            "{summary:?}"
+        );
+        assert!(
+            tokio::time::timeout(
+                core::time::Duration::from_millis(100),
+                security_events.recv()
+            )
+            .await
+            .is_err(),
+            "25 invalid messages must cost the security log two entries, not 25"
+        );
+        assert_eq!(invalid_message_count(&actor), "25");
+    }
+}
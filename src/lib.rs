@@ -37,6 +37,9 @@ mod basic_auth_credential;
 #[cfg(feature = "battery-swap")]
 pub mod battery_swap;
 mod builder;
+/// Answering a repeated inbound CALL message id from cache instead of dispatching it again, at the
+/// transport. See [`call_dedup`]'s own docs for which connections it covers.
+pub mod call_dedup;
 /// `GetCertificateStatus`/`GetCertificateChainStatus` (OCPP 2.0.1/2.1, `docs/PRODUCTION-ROADMAP.md`
 /// B4.4): OCSP status checking, runtime-gated by [`hardware::Capabilities::ocsp_checking`] - see
 /// [`certificate_status`]'s own docs for why this is a separate capability from
@@ -46,6 +49,9 @@ pub mod certificate_renewal;
 pub mod certificate_status;
 pub mod certificates;
 pub mod clock;
+/// Operator-configurable limits on what the CSMS may command, consulted by every inbound handler
+/// before it acts. See [`command_policy`]'s own docs.
+pub mod command_policy;
 // `connect_and_setup` hands its negotiated client straight to `setup()`, so it needs the same
 // feature list `setup()` itself does - see the `setup` module gate below.
 #[cfg(all(
@@ -80,6 +86,9 @@ pub mod firmware;
 pub mod hardware;
 #[cfg(feature = "ocpp_1_6")]
 mod id_tag;
+/// Counting and rate-limited reporting of invalid inbound CSMS messages (`InvalidMessages`). See
+/// [`invalid_messages`]'s own docs for what counts, and on which connections each kind is seen.
+pub mod invalid_messages;
 /// ISO 15118 Plug & Charge: `Get15118EVCertificate` (2.0.1/2.1 only, `docs/PRODUCTION-ROADMAP.md`
 /// B4.5). See [`iso15118`]'s own docs for the hardware boundary and why this is
 /// charge-point-initiated with no `ChargePointBuilder` registration.
@@ -88,6 +97,9 @@ pub mod iso15118;
 pub mod keepalive;
 #[cfg(feature = "local-auth-list")]
 pub mod local_authorization_list;
+/// Local maintenance login for on-site technicians, with lockout, session timeout, and
+/// `MaintenanceLogin*` security events. See [`maintenance`]'s own docs.
+pub mod maintenance;
 pub mod message_limits;
 pub mod meter_values;
 /// Security profile 3 (mutual TLS): builds a `rustls::ClientConfig` presenting this charge
@@ -166,6 +178,8 @@ pub mod sync;
 /// [`tariff`]'s own docs for why this is 2.1-only and stores/reports rather than computes a cost.
 #[cfg(feature = "tariff-cost")]
 pub mod tariff;
+#[cfg(test)]
+mod test_certificates;
 #[cfg(feature = "ocpp_1_6")]
 mod topology;
 #[cfg(test)]
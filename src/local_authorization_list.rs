@@ -77,6 +77,9 @@ pub async fn handle_send_local_list(
     if !crate::refusal::capability_present(&state.capabilities, "SendLocalList") {
         return SendLocalListOutcome::NotSupported;
     }
+    if crate::command_policy::refuses(actor, "SendLocalList") {
+        return SendLocalListOutcome::NotSupported;
+    }
     let current = state.local_authorization_list;
 
     let max_entries = current.max_entries;
@@ -544,9 +547,11 @@ mod ocpp_2_1 {
                         &request,
                     ) {
                         return Err(crate::message_limits::ocpp_2_1_too_large(
+                            &actor,
                             "SendLocalList",
                             violation,
-                        ));
+                        )
+                        .await);
                     }
                     let update = parse_update(&request);
                     let outcome =
@@ -576,6 +581,8 @@ mod ocpp_2_1 {
                 "GetLocalListVersion",
             ));
         }
+        crate::command_policy::screen_action(actor, "GetLocalListVersion")
+            .map_err(|refused| crate::refusal::ocpp_2_1_refused_by_policy(&refused))?;
         Ok(GetLocalListVersionResponse {
             custom_data: None,
             version_number: handle_get_local_list_version(actor),
@@ -850,9 +857,11 @@ mod ocpp_2_0_1 {
                         &request,
                     ) {
                         return Err(crate::message_limits::ocpp_2_0_1_too_large(
+                            &actor,
                             "SendLocalList",
                             violation,
-                        ));
+                        )
+                        .await);
                     }
                     let update = parse_update(&request);
                     let outcome =
@@ -877,6 +886,8 @@ mod ocpp_2_0_1 {
                 "GetLocalListVersion",
             ));
         }
+        crate::command_policy::screen_action(actor, "GetLocalListVersion")
+            .map_err(|refused| crate::refusal::ocpp_2_0_1_refused_by_policy(&refused))?;
         Ok(GetLocalListVersionResponse {
             custom_data: None,
             version_number: handle_get_local_list_version(actor),
@@ -1155,6 +1166,8 @@ mod ocpp_1_6 {
                 "GetLocalListVersion",
             ));
         }
+        crate::command_policy::screen_action(actor, "GetLocalListVersion")
+            .map_err(|refused| crate::refusal::ocpp_1_6_refused_by_policy(&refused))?;
         Ok(GetLocalListVersionResponse {
             list_version: handle_get_local_list_version(actor),
         })
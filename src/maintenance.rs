@@ -0,0 +1,881 @@
+//! Local maintenance login: a technician on site - at the display's keypad, on the service port,
+//! or with an RFID service card - logs in and, while logged in, runs a small set of privileged
+//! local commands ([`MaintenanceCommand`]). Every login attempt is reported as a
+//! `MaintenanceLoginAccepted`/`MaintenanceLoginFailed` security event, both of which OCPP classes
+//! as critical, so they reach the CSMS as well as the security log.
+//!
+//! # What the crate owns, and what the integrator does
+//!
+//! The integrator owns the interface the technician types into and the credentials it checks
+//! against - a list of PIN hashes in flash, an LDAP bind through the service port, a whitelist of
+//! service cards - behind [`MaintenanceCredentialVerifier`]. This module owns everything around
+//! that decision, which is where a hand-rolled maintenance interface usually goes wrong:
+//!
+//! - **Lockout.** [`MaintenancePolicy::max_failed_attempts`] refusals in a row lock the interface
+//!   for [`MaintenancePolicy::lockout_secs`]. A locked interface refuses without consulting the
+//!   verifier, so a four-digit PIN cannot be enumerated at keypad speed. An attempt holds its
+//!   place against the limit from before the verifier is asked, so attempts made side by side -
+//!   two service-port connections, say - cannot between them get more guesses checked than one
+//!   attempt at a time would.
+//! - **Session timeout.** A session left idle for [`MaintenancePolicy::session_timeout_secs`]
+//!   ends on its own - the technician who walks away without logging out does not leave the
+//!   charge point unlocked for the next person at the keypad. Each command run restarts the
+//!   clock. Measured on a [`MonotonicClock`], so a `SetSystemTime` mid-session neither ends it
+//!   early nor extends it.
+//! - **Audit.** The techInfo follows OCPP's recommended `{'user': '...', 'origin': '...'}` shape,
+//!   with the user name escaped so it cannot forge a field and cut short to keep the whole within
+//!   OCPP's 255 bytes. A refused PIN or password is never echoed into it; a refused service card
+//!   is named only by [`IdToken::redacted_value`].
+//! - **Routing.** [`MaintenanceCommand`]s go through the same [`ChargePointEvent`]s a hardware
+//!   binding or the CSMS would raise, so a technician's unlock is held to exactly the rules a
+//!   CSMS `UnlockConnector` is - it cannot release a cable mid-transaction.
+//!
+//! Attempts made while locked out are logged but not reported individually: the failure that
+//! started the lockout was, and reporting each one after it would let whoever is at the keypad
+//! flush the bounded security log (A04.FR.04) by holding a key down.
+//!
+//! Nothing here is spawned by [`crate::ChargePointBuilder`]: the integrator constructs a
+//! [`MaintenanceSessions`] and calls it from whatever task drives their maintenance UI.
+
+use crate::actor::ChargePointActor;
+use crate::clock::{MonotonicClock, MonotonicInstant};
+use crate::remote_control::{UnlockOutcome, handle_unlock_request};
+use crate::security::report_security_event;
+use crate::state::{
+    ChargePointEvent, ConnectorEvent, EvseEvent, IdToken, SecurityEvent, SecurityEventType,
+};
+use alloc::boxed::Box;
+use alloc::string::String;
+use core::cell::RefCell;
+use core::time::Duration;
+use embassy_sync::blocking_mutex::Mutex as BlockingMutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+
+/// What a technician presented to log in.
+///
+/// `Debug` is implemented by hand so a PIN or password never reaches a log or a panic message -
+/// see [`crate::security_profile::BasicAuthPassword`] for the same reasoning.
+#[derive(Clone, PartialEq, Eq)]
+pub enum MaintenanceCredential {
+    /// A PIN entered on the display's keypad.
+    Pin(String),
+    /// A password entered over the service port.
+    Password(String),
+    /// An RFID service card. Which cards are service cards rather than drivers' cards is the
+    /// verifier's decision, not this crate's.
+    ServiceCard(IdToken),
+}
+
+impl core::fmt::Debug for MaintenanceCredential {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Pin(_) => f.write_str("Pin(<redacted>)"),
+            Self::Password(_) => f.write_str("Password(<redacted>)"),
+            Self::ServiceCard(id_token) => f
+                .debug_tuple("ServiceCard")
+                .field(&id_token.redacted_value())
+                .finish(),
+        }
+    }
+}
+
+/// Where a maintenance login attempt came from - the `origin` of its security event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceOrigin {
+    /// The charge point's own display and keypad.
+    Display,
+    /// A wired service port (serial console, local Ethernet).
+    ServicePort,
+    /// The RFID reader, presented a service card.
+    CardReader,
+}
+
+impl MaintenanceOrigin {
+    /// The name reported as the event's `origin`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Display => "Display",
+            Self::ServicePort => "ServicePort",
+            Self::CardReader => "CardReader",
+        }
+    }
+}
+
+/// OCPP's `techInfo` bound, in bytes.
+const MAX_TECH_INFO_BYTES: usize = 255;
+
+/// The `techInfo` a login attempt is reported with: OCPP's recommended
+/// `{'user': '...', 'origin': '...'}`, plus how long the interface is now locked for if this
+/// attempt locked it.
+///
+/// The user name comes from the integrator's verifier - or, for a refused card, from whoever
+/// presented it - so it is escaped as [`char::escape_debug`] does: a `'` or `\` in it cannot close
+/// the quotes and forge a field. It is also the one part cut short if the whole would exceed OCPP's
+/// 255 bytes, at a character boundary, so the event keeps its shape and its origin instead of
+/// losing `techInfo` altogether.
+fn login_tech_info(
+    user: &str,
+    origin: MaintenanceOrigin,
+    locked_out_for_secs: Option<u32>,
+) -> String {
+    let head = "{'user': '";
+    let tail = match locked_out_for_secs {
+        Some(secs) => alloc::format!(
+            "', 'origin': '{}', 'lockedOutFor': '{secs}s'}}",
+            origin.as_str()
+        ),
+        None => alloc::format!("', 'origin': '{}'}}", origin.as_str()),
+    };
+    let budget = MAX_TECH_INFO_BYTES - head.len() - tail.len();
+    let mut quoted = String::new();
+    for escaped in user.chars().map(char::escape_debug) {
+        let len: usize = escaped.clone().map(char::len_utf8).sum();
+        if quoted.len() + len > budget {
+            break;
+        }
+        quoted.extend(escaped);
+    }
+    alloc::format!("{head}{quoted}{tail}")
+}
+
+/// Decides whether a [`MaintenanceCredential`] logs a technician in. Implemented by the
+/// integrator, mirroring [`crate::authorization::Authorizer`] for drivers.
+#[async_trait::async_trait]
+pub trait MaintenanceCredentialVerifier {
+    /// The error type returned if the check itself could not be made (e.g. the credential store
+    /// is unreadable) - distinct from a refusal, which is `Ok(None)`. [`MaintenanceSessions`]
+    /// treats an error as a refusal: a maintenance interface that cannot check a credential must
+    /// not open.
+    type Error: core::error::Error + Send + Sync + 'static;
+
+    /// Checks `credential`, presented at `origin`, returning the technician's name if it is
+    /// accepted.
+    async fn verify(
+        &self,
+        credential: &MaintenanceCredential,
+        origin: MaintenanceOrigin,
+    ) -> Result<Option<String>, Self::Error>;
+}
+
+/// Lockout and timeout settings for [`MaintenanceSessions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaintenancePolicy {
+    /// How many refused attempts in a row lock the interface. `0` is treated as `1`.
+    pub max_failed_attempts: u32,
+    /// How long the interface stays locked once it is.
+    pub lockout_secs: u32,
+    /// How long a session may sit without a command before it ends on its own.
+    pub session_timeout_secs: u32,
+}
+
+impl Default for MaintenancePolicy {
+    /// Five attempts, then five minutes locked; sessions end after ten idle minutes.
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: 5,
+            lockout_secs: 300,
+            session_timeout_secs: 600,
+        }
+    }
+}
+
+/// A privileged local operation, available only inside a maintenance session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceCommand {
+    /// Release a connector's cable, via [`handle_unlock_request`] - refused while a transaction
+    /// is running on it, exactly as a CSMS `UnlockConnector` would be.
+    UnlockConnector {
+        /// The EVSE's index.
+        evse_id: usize,
+        /// The connector's index within that EVSE.
+        connector_id: usize,
+    },
+    /// Clear a charge-point-wide fault ([`ChargePointEvent::FaultCleared`]).
+    ClearFault,
+    /// Clear an EVSE-wide fault ([`EvseEvent::FaultCleared`]).
+    ClearEvseFault {
+        /// The EVSE's index.
+        evse_id: usize,
+    },
+    /// Clear one connector's fault ([`ConnectorEvent::FaultCleared`]).
+    ClearConnectorFault {
+        /// The EVSE's index.
+        evse_id: usize,
+        /// The connector's index within that EVSE.
+        connector_id: usize,
+    },
+    /// Clear the security log ([`ChargePointEvent::SecurityLogClearRequested`]), which raises
+    /// `SecurityLogWasCleared` naming the technician. Carried out by
+    /// [`crate::persistence::run_security_log_clears`], so [`MaintenanceOutcome::Applied`] means
+    /// requested rather than done.
+    ClearSecurityLog,
+}
+
+/// What a [`MaintenanceCommand`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceOutcome {
+    /// The command's event was applied. Clearing a fault is still subject to the state machine:
+    /// a connector whose contactor has not confirmed open stays faulted.
+    Applied,
+    /// How an [`MaintenanceCommand::UnlockConnector`] ended.
+    Unlock(UnlockOutcome),
+    /// The command named an EVSE or connector this charge point does not have.
+    UnknownTarget,
+}
+
+/// Why a login or a command was refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceError {
+    /// The verifier refused the credential, or could not check it - or so many attempts were
+    /// already being checked that another could have taken the interface past its lockout.
+    Refused,
+    /// Too many refusals in a row; nothing is checked until the lockout ends.
+    LockedOut {
+        /// Whole seconds until it does, rounded up.
+        retry_after_secs: u32,
+    },
+    /// No technician is logged in.
+    NotLoggedIn,
+    /// The session sat idle past [`MaintenancePolicy::session_timeout_secs`] and has ended.
+    SessionExpired,
+}
+
+/// The logged-in technician.
+#[derive(Debug, Clone)]
+struct Session {
+    user: String,
+    origin: MaintenanceOrigin,
+    last_activity: MonotonicInstant,
+}
+
+#[derive(Debug, Default)]
+struct SessionState {
+    /// Refusals since the last acceptance or lockout.
+    consecutive_failures: u32,
+    /// Attempts the verifier is still checking, each of which may yet be a refusal.
+    attempts_in_flight: u32,
+    /// When the current lockout began, if one is running.
+    locked_since: Option<MonotonicInstant>,
+    session: Option<Session>,
+}
+
+/// A login attempt's place against the lockout while the verifier checks it - see
+/// [`MaintenanceSessions::log_in`]. Given back on drop too, so an attempt whose future is dropped
+/// mid-check does not hold its place for good.
+struct AttemptInFlight<'a> {
+    state: &'a BlockingMutex<CriticalSectionRawMutex, RefCell<SessionState>>,
+}
+
+impl AttemptInFlight<'_> {
+    /// Gives the place back and records the outcome under the same lock, so no other attempt can
+    /// reserve in between and miss the failure this one is about to count.
+    fn settle<R>(self, record: impl FnOnce(&mut SessionState) -> R) -> R {
+        let outcome = self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.attempts_in_flight = state.attempts_in_flight.saturating_sub(1);
+            record(&mut state)
+        });
+        core::mem::forget(self);
+        outcome
+    }
+}
+
+impl Drop for AttemptInFlight<'_> {
+    fn drop(&mut self) {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.attempts_in_flight = state.attempts_in_flight.saturating_sub(1);
+        });
+    }
+}
+
+/// The maintenance interface's login state: who is logged in, since when, and how close the
+/// interface is to locking. See this module's docs.
+pub struct MaintenanceSessions<V, M> {
+    actor: ChargePointActor,
+    verifier: V,
+    clock: M,
+    policy: MaintenancePolicy,
+    state: BlockingMutex<CriticalSectionRawMutex, RefCell<SessionState>>,
+}
+
+impl<V: MaintenanceCredentialVerifier, M: MonotonicClock> MaintenanceSessions<V, M> {
+    /// A maintenance interface for `actor`, checking credentials with `verifier` and timing
+    /// lockouts and sessions on `clock`, under [`MaintenancePolicy::default`].
+    pub fn new(actor: ChargePointActor, verifier: V, clock: M) -> Self {
+        Self {
+            actor,
+            verifier,
+            clock,
+            policy: MaintenancePolicy::default(),
+            state: BlockingMutex::new(RefCell::new(SessionState::default())),
+        }
+    }
+
+    /// Replaces the lockout and timeout settings.
+    pub fn with_policy(mut self, policy: MaintenancePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Attempts a login, reporting the outcome as a security event. A successful login replaces
+    /// any session already open.
+    #[tracing::instrument(skip_all, fields(origin = origin.as_str()))]
+    pub async fn log_in(
+        &self,
+        credential: &MaintenanceCredential,
+        origin: MaintenanceOrigin,
+    ) -> Result<(), MaintenanceError> {
+        let lockout = Duration::from_secs(self.policy.lockout_secs.into());
+        let max_failed_attempts = self.policy.max_failed_attempts.max(1);
+        let now = self.clock.now();
+        // Checked and reserved under one lock, before the verifier is awaited: counting the
+        // attempt only once it comes back would let every attempt made meanwhile see the same
+        // count, and get checked however many of them there were.
+        let reserved = self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            if let Some(locked_since) = state.locked_since {
+                let elapsed = now.duration_since(locked_since);
+                if elapsed < lockout {
+                    return Err(Some(lockout - elapsed));
+                }
+                state.locked_since = None;
+            }
+            if state
+                .consecutive_failures
+                .saturating_add(state.attempts_in_flight)
+                >= max_failed_attempts
+            {
+                return Err(None);
+            }
+            state.attempts_in_flight += 1;
+            Ok(())
+        });
+        match reserved {
+            Ok(()) => {}
+            Err(Some(remaining)) => {
+                tracing::warn!("maintenance login attempted while locked out; not checked");
+                let retry_after_secs =
+                    remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+                return Err(MaintenanceError::LockedOut {
+                    retry_after_secs: u32::try_from(retry_after_secs).unwrap_or(u32::MAX),
+                });
+            }
+            Err(None) => {
+                tracing::warn!(
+                    "maintenance login attempted while the attempts already being checked could \
+                     lock the interface; not checked"
+                );
+                return Err(MaintenanceError::Refused);
+            }
+        }
+        let attempt = AttemptInFlight { state: &self.state };
+
+        let user = match self.verifier.verify(credential, origin).await {
+            Ok(user) => user,
+            Err(error) => {
+                tracing::warn!(%error, "maintenance credential could not be checked; refusing");
+                None
+            }
+        };
+        let now = self.clock.now();
+
+        let Some(user) = user else {
+            let locked = attempt.settle(|state| {
+                state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+                if state.consecutive_failures < max_failed_attempts {
+                    return false;
+                }
+                state.consecutive_failures = 0;
+                state.locked_since = Some(now);
+                true
+            });
+            let who = match credential {
+                MaintenanceCredential::ServiceCard(id_token) => id_token.redacted_value(),
+                MaintenanceCredential::Pin(_) | MaintenanceCredential::Password(_) => {
+                    "unknown".into()
+                }
+            };
+            if locked {
+                tracing::warn!(
+                    attempts = max_failed_attempts,
+                    "maintenance interface locked after repeated failed logins"
+                );
+            }
+            let tech_info =
+                login_tech_info(&who, origin, locked.then_some(self.policy.lockout_secs));
+            report_security_event(
+                &self.actor,
+                SecurityEvent {
+                    event_type: SecurityEventType::MaintenanceLoginFailed,
+                    tech_info: Some(tech_info),
+                },
+            )
+            .await;
+            return Err(if locked {
+                MaintenanceError::LockedOut {
+                    retry_after_secs: self.policy.lockout_secs,
+                }
+            } else {
+                MaintenanceError::Refused
+            });
+        };
+
+        tracing::info!(%user, "maintenance login accepted");
+        let tech_info = login_tech_info(&user, origin, None);
+        attempt.settle(|state| {
+            state.consecutive_failures = 0;
+            state.session = Some(Session {
+                user,
+                origin,
+                last_activity: now,
+            });
+        });
+        report_security_event(
+            &self.actor,
+            SecurityEvent {
+                event_type: SecurityEventType::MaintenanceLoginAccepted,
+                tech_info: Some(tech_info),
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Ends the current session, if there is one. OCPP has no logout event, so this is logged
+    /// rather than reported.
+    pub fn log_out(&self) {
+        if let Some(session) = self.state.lock(|state| state.borrow_mut().session.take()) {
+            tracing::info!(user = %session.user, "maintenance session ended by logout");
+        }
+    }
+
+    /// The logged-in technician's name and where they logged in from, or `None` if nobody is -
+    /// including a session that has just timed out, which this ends.
+    pub fn logged_in(&self) -> Option<(String, MaintenanceOrigin)> {
+        self.current_session(false)
+            .ok()
+            .map(|session| (session.user, session.origin))
+    }
+
+    /// Runs `command` on behalf of the logged-in technician, restarting the session's idle
+    /// timeout.
+    #[tracing::instrument(skip_all, fields(command = ?command))]
+    pub async fn execute(
+        &self,
+        command: MaintenanceCommand,
+    ) -> Result<MaintenanceOutcome, MaintenanceError> {
+        let session = self.current_session(true)?;
+        tracing::info!(user = %session.user, "running maintenance command");
+
+        let event = match command {
+            MaintenanceCommand::UnlockConnector {
+                evse_id,
+                connector_id,
+            } => {
+                let outcome = handle_unlock_request(&self.actor, evse_id, connector_id).await;
+                return Ok(MaintenanceOutcome::Unlock(outcome));
+            }
+            MaintenanceCommand::ClearFault => ChargePointEvent::FaultCleared,
+            MaintenanceCommand::ClearSecurityLog => ChargePointEvent::SecurityLogClearRequested {
+                requested_by: alloc::format!("maintenance user {}", session.user),
+            },
+            MaintenanceCommand::ClearEvseFault { evse_id } => {
+                if self.actor.state().evses.get(evse_id).is_none() {
+                    return Ok(MaintenanceOutcome::UnknownTarget);
+                }
+                ChargePointEvent::Evse {
+                    evse_id,
+                    event: EvseEvent::FaultCleared,
+                }
+            }
+            MaintenanceCommand::ClearConnectorFault {
+                evse_id,
+                connector_id,
+            } => {
+                let exists = self
+                    .actor
+                    .state()
+                    .evses
+                    .get(evse_id)
+                    .is_some_and(|evse| connector_id < evse.connectors.len());
+                if !exists {
+                    return Ok(MaintenanceOutcome::UnknownTarget);
+                }
+                ChargePointEvent::Evse {
+                    evse_id,
+                    event: EvseEvent::Connector {
+                        connector_id,
+                        event: ConnectorEvent::FaultCleared,
+                    },
+                }
+            }
+        };
+        let _ = self.actor.send(event).await;
+        Ok(MaintenanceOutcome::Applied)
+    }
+
+    /// The open session, ending it if it has idled out. `touch` restarts its idle timeout.
+    fn current_session(&self, touch: bool) -> Result<Session, MaintenanceError> {
+        let timeout = Duration::from_secs(self.policy.session_timeout_secs.into());
+        let now = self.clock.now();
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            let session = state
+                .session
+                .as_mut()
+                .ok_or(MaintenanceError::NotLoggedIn)?;
+            if now.duration_since(session.last_activity) >= timeout {
+                tracing::info!(user = %session.user, "maintenance session timed out");
+                state.session = None;
+                return Err(MaintenanceError::SessionExpired);
+            }
+            if touch {
+                session.last_activity = now;
+            }
+            Ok(session.clone())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::TokioExecutor;
+    use crate::state::{ConnectorState, IdTokenKind};
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    /// Accepts PIN `2468` as `alice`; refuses everything else.
+    struct FakeVerifier;
+
+    #[derive(Debug)]
+    struct Unreachable;
+
+    impl core::fmt::Display for Unreachable {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.write_str("unreachable")
+        }
+    }
+
+    impl core::error::Error for Unreachable {}
+
+    #[async_trait::async_trait]
+    impl MaintenanceCredentialVerifier for FakeVerifier {
+        type Error = Unreachable;
+
+        async fn verify(
+            &self,
+            credential: &MaintenanceCredential,
+            _origin: MaintenanceOrigin,
+        ) -> Result<Option<String>, Self::Error> {
+            Ok(match credential {
+                MaintenanceCredential::Pin(pin) if pin == "2468" => Some("alice".into()),
+                _ => None,
+            })
+        }
+    }
+
+    /// A monotonic clock the test advances by hand.
+    #[derive(Default)]
+    struct FakeMonotonicClock(AtomicU64);
+
+    impl FakeMonotonicClock {
+        fn advance_secs(&self, secs: u64) {
+            self.0.fetch_add(secs * 1_000_000_000, Ordering::SeqCst);
+        }
+    }
+
+    impl MonotonicClock for &FakeMonotonicClock {
+        fn now(&self) -> MonotonicInstant {
+            MonotonicInstant::from_ticks(self.0.load(Ordering::SeqCst))
+        }
+    }
+
+    fn pin(pin: &str) -> MaintenanceCredential {
+        MaintenanceCredential::Pin(pin.into())
+    }
+
+    #[tokio::test]
+    async fn logins_are_reported_and_repeated_failures_lock_the_interface() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let mut security_events = actor.subscribe_security_events();
+        let clock = FakeMonotonicClock::default();
+        let sessions =
+            MaintenanceSessions::new(actor, FakeVerifier, &clock).with_policy(MaintenancePolicy {
+                max_failed_attempts: 3,
+                lockout_secs: 60,
+                session_timeout_secs: 600,
+            });
+
+        for _ in 0..2 {
+            assert_eq!(
+                sessions
+                    .log_in(&pin("0000"), MaintenanceOrigin::Display)
+                    .await,
+                Err(MaintenanceError::Refused)
+            );
+        }
+        assert_eq!(
+            sessions
+                .log_in(&pin("0000"), MaintenanceOrigin::Display)
+                .await,
+            Err(MaintenanceError::LockedOut {
+                retry_after_secs: 60
+            })
+        );
+        // Locked: even the right PIN is not checked, and the attempt is not reported.
+        clock.advance_secs(30);
+        assert_eq!(
+            sessions
+                .log_in(&pin("2468"), MaintenanceOrigin::Display)
+                .await,
+            Err(MaintenanceError::LockedOut {
+                retry_after_secs: 30
+            })
+        );
+        clock.advance_secs(30);
+        assert_eq!(
+            sessions
+                .log_in(&pin("2468"), MaintenanceOrigin::Display)
+                .await,
+            Ok(())
+        );
+        assert_eq!(
+            sessions.logged_in(),
+            Some(("alice".into(), MaintenanceOrigin::Display))
+        );
+
+        let mut raised = alloc::vec::Vec::new();
+        for _ in 0..4 {
+            let event = security_events.recv().await.unwrap();
+            raised.push((event.event_type, event.tech_info.unwrap()));
+        }
+        assert_eq!(
+            raised,
+            alloc::vec![
+                (
+                    SecurityEventType::MaintenanceLoginFailed,
+                    "{'user': 'unknown', 'origin': 'Display'}".into()
+                ),
+                (
+                    SecurityEventType::MaintenanceLoginFailed,
+                    "{'user': 'unknown', 'origin': 'Display'}".into()
+                ),
+                (
+                    SecurityEventType::MaintenanceLoginFailed,
+                    "{'user': 'unknown', 'origin': 'Display', 'lockedOutFor': '60s'}".into()
+                ),
+                (
+                    SecurityEventType::MaintenanceLoginAccepted,
+                    "{'user': 'alice', 'origin': 'Display'}".into()
+                ),
+            ]
+        );
+        assert!(SecurityEventType::MaintenanceLoginFailed.is_critical());
+    }
+
+    /// Refuses everything, counting how many credentials it was asked about and yielding before
+    /// it answers - so attempts started together are all in flight at once.
+    #[derive(Default)]
+    struct SlowRefusingVerifier(core::sync::atomic::AtomicU32);
+
+    #[async_trait::async_trait]
+    impl MaintenanceCredentialVerifier for SlowRefusingVerifier {
+        type Error = Unreachable;
+
+        async fn verify(
+            &self,
+            _credential: &MaintenanceCredential,
+            _origin: MaintenanceOrigin,
+        ) -> Result<Option<String>, Self::Error> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn attempts_made_side_by_side_get_no_more_guesses_checked_than_the_limit() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let clock = FakeMonotonicClock::default();
+        let sessions = MaintenanceSessions::new(actor, SlowRefusingVerifier::default(), &clock)
+            .with_policy(MaintenancePolicy {
+                max_failed_attempts: 3,
+                lockout_secs: 60,
+                session_timeout_secs: 600,
+            });
+        let wrong = pin("0000");
+        let attempt = || sessions.log_in(&wrong, MaintenanceOrigin::ServicePort);
+
+        let outcomes = tokio::join!(attempt(), attempt(), attempt(), attempt(), attempt());
+
+        assert_eq!(sessions.verifier.0.load(Ordering::SeqCst), 3);
+        // Which of the three checked attempts is the one that locks depends on the order they
+        // finish in; that exactly one does, does not.
+        let outcomes = [outcomes.0, outcomes.1, outcomes.2, outcomes.3, outcomes.4];
+        let locking = outcomes
+            .iter()
+            .filter(|outcome| {
+                **outcome
+                    == Err(MaintenanceError::LockedOut {
+                        retry_after_secs: 60,
+                    })
+            })
+            .count();
+        let refused = outcomes
+            .iter()
+            .filter(|outcome| **outcome == Err(MaintenanceError::Refused))
+            .count();
+        assert_eq!((locking, refused), (1, 4), "{outcomes:?}");
+        assert_eq!(
+            attempt().await,
+            Err(MaintenanceError::LockedOut {
+                retry_after_secs: 60
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn an_attempt_abandoned_mid_check_gives_its_place_back() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let clock = FakeMonotonicClock::default();
+        let sessions = MaintenanceSessions::new(actor, SlowRefusingVerifier::default(), &clock)
+            .with_policy(MaintenancePolicy {
+                max_failed_attempts: 1,
+                lockout_secs: 60,
+                session_timeout_secs: 600,
+            });
+
+        // Polled once - far enough to reserve and reach the verifier - then dropped.
+        let wrong = pin("0000");
+        tokio::select! {
+            biased;
+            outcome = sessions.log_in(&wrong, MaintenanceOrigin::Display) => {
+                panic!("checked without ever yielding: {outcome:?}")
+            }
+            () = core::future::ready(()) => {}
+        }
+
+        assert_eq!(
+            sessions.log_in(&wrong, MaintenanceOrigin::Display).await,
+            Err(MaintenanceError::LockedOut {
+                retry_after_secs: 60
+            })
+        );
+        assert_eq!(sessions.verifier.0.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn a_refused_service_card_is_reported_redacted() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let mut security_events = actor.subscribe_security_events();
+        let clock = FakeMonotonicClock::default();
+        let sessions = MaintenanceSessions::new(actor, FakeVerifier, &clock);
+        let card = IdToken {
+            value: "04A1B2C3D4".into(),
+            kind: IdTokenKind::ISO14443,
+        };
+
+        let credential = MaintenanceCredential::ServiceCard(card.clone());
+        assert_eq!(
+            sessions
+                .log_in(&credential, MaintenanceOrigin::CardReader)
+                .await,
+            Err(MaintenanceError::Refused)
+        );
+        let event = security_events.recv().await.unwrap();
+        assert_eq!(
+            event.tech_info,
+            Some(alloc::format!(
+                "{{'user': '{}', 'origin': 'CardReader'}}",
+                card.redacted_value()
+            ))
+        );
+        assert!(!alloc::format!("{:?}", pin("2468")).contains("2468"));
+    }
+
+    #[test]
+    fn a_user_name_cannot_forge_a_tech_info_field_or_push_it_past_the_bound() {
+        assert_eq!(
+            login_tech_info("bob', 'origin': 'CSMS", MaintenanceOrigin::Display, None),
+            r"{'user': 'bob\', \'origin\': \'CSMS', 'origin': 'Display'}"
+        );
+
+        let long = "é".repeat(200);
+        let tech_info = login_tech_info(&long, MaintenanceOrigin::ServicePort, Some(60));
+        assert!(tech_info.len() <= 255, "{} bytes", tech_info.len());
+        assert!(tech_info.starts_with("{'user': 'éé"));
+        assert!(tech_info.ends_with("', 'origin': 'ServicePort', 'lockedOutFor': '60s'}"));
+    }
+
+    #[tokio::test]
+    async fn commands_need_a_live_session_and_route_through_the_state_machine() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let clock = FakeMonotonicClock::default();
+        let sessions = MaintenanceSessions::new(actor.clone(), FakeVerifier, &clock);
+        let clear = MaintenanceCommand::ClearConnectorFault {
+            evse_id: 0,
+            connector_id: 0,
+        };
+        assert_eq!(
+            sessions.execute(clear).await,
+            Err(MaintenanceError::NotLoggedIn)
+        );
+
+        for event in [
+            ConnectorEvent::FaultDetected,
+            ConnectorEvent::ContactorOpened,
+        ] {
+            let _ = actor
+                .send(ChargePointEvent::Evse {
+                    evse_id: 0,
+                    event: EvseEvent::Connector {
+                        connector_id: 0,
+                        event,
+                    },
+                })
+                .await;
+        }
+        sessions
+            .log_in(&pin("2468"), MaintenanceOrigin::ServicePort)
+            .await
+            .unwrap();
+        assert_eq!(
+            sessions
+                .execute(MaintenanceCommand::ClearConnectorFault {
+                    evse_id: 0,
+                    connector_id: 3,
+                })
+                .await,
+            Ok(MaintenanceOutcome::UnknownTarget)
+        );
+
+        // Each command restarts the idle timeout, so two gaps just short of it keep the session.
+        clock.advance_secs(599);
+        assert_eq!(
+            sessions.execute(clear).await,
+            Ok(MaintenanceOutcome::Applied)
+        );
+        assert_eq!(
+            actor.state().evses[0].connectors[0],
+            ConnectorState::Unlocking
+        );
+        sessions
+            .execute(MaintenanceCommand::ClearSecurityLog)
+            .await
+            .unwrap();
+        assert_eq!(
+            actor.state().pending_security_log_clear.as_deref(),
+            Some("maintenance user alice")
+        );
+        clock.advance_secs(599);
+        assert!(sessions.logged_in().is_some());
+
+        clock.advance_secs(1);
+        assert_eq!(
+            sessions.execute(clear).await,
+            Err(MaintenanceError::SessionExpired)
+        );
+        assert_eq!(sessions.logged_in(), None);
+    }
+}
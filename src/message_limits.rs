@@ -160,12 +160,14 @@ pub fn check_message_size<T: serde::Serialize>(
 }
 
 /// Logs a refusal at `warn!` — degraded but handled: the charge point kept working and the
-/// operator needs to know why a CSMS request is bouncing.
+/// operator needs to know why a CSMS request is bouncing — and counts it as an invalid message
+/// (see [`crate::invalid_messages`]): a CSMS that ignores the limits it was told is one an
+/// operator wants to be able to spot.
 ///
 /// Gated on the versions that have a CALLERROR to carry it, so a `--no-default-features` build
 /// with no protocol version compiled in does not carry a function nothing can reach.
 #[cfg(any(feature = "ocpp_2_1", feature = "ocpp_2_0_1"))]
-fn warn_refused(action: &str, violation: LimitViolation) {
+async fn record_refusal(actor: &ChargePointActor, action: &str, violation: LimitViolation) {
     match violation {
         LimitViolation::TooManyItems { count, limit } => tracing::warn!(
             action,
@@ -180,17 +182,27 @@ fn warn_refused(action: &str, violation: LimitViolation) {
             "refusing a request larger than BytesPerMessage allows"
         ),
     }
+    crate::invalid_messages::report_invalid_message(
+        actor,
+        crate::state::InvalidMessage {
+            kind: crate::state::InvalidMessageKind::OverLimit,
+            action: Some(action.into()),
+        },
+    )
+    .await;
 }
 
 /// The OCPP 2.1 CALLERROR for `violation`: `OccurrenceConstraintViolation` for items,
-/// `FormatViolation` for bytes, exactly as B06.FR.16/.17 and B08.FR.17/.18 name them.
+/// `FormatViolation` for bytes, exactly as B06.FR.16/.17 and B08.FR.17/.18 name them. Counts the
+/// refusal against `actor` as an invalid message on the way.
 #[cfg(feature = "ocpp_2_1")]
-pub fn ocpp_2_1_too_large(
+pub async fn ocpp_2_1_too_large(
+    actor: &ChargePointActor,
     action: &str,
     violation: LimitViolation,
 ) -> ocpp_client::ocpp_2_1::OCPP2_1Error {
     use crate::wire::v21::RpcErrorCode;
-    warn_refused(action, violation);
+    record_refusal(actor, action, violation).await;
     ocpp_client::ocpp_2_1::OCPP2_1Error {
         code: match violation {
             LimitViolation::TooManyItems { .. } => RpcErrorCode::OccurrenceConstraintViolation,
@@ -203,12 +215,13 @@ pub fn ocpp_2_1_too_large(
 
 /// The OCPP 2.0.1 CALLERROR for `violation`. Mirrors [`ocpp_2_1_too_large`].
 #[cfg(feature = "ocpp_2_0_1")]
-pub fn ocpp_2_0_1_too_large(
+pub async fn ocpp_2_0_1_too_large(
+    actor: &ChargePointActor,
     action: &str,
     violation: LimitViolation,
 ) -> ocpp_client::ocpp_2_0_1::OCPP2_0_1Error {
     use crate::wire::v201::RpcErrorCode;
-    warn_refused(action, violation);
+    record_refusal(actor, action, violation).await;
     ocpp_client::ocpp_2_0_1::OCPP2_0_1Error {
         code: match violation {
             LimitViolation::TooManyItems { .. } => RpcErrorCode::OccurrenceConstraintViolation,
@@ -385,33 +398,40 @@ mod tests {
     }
 
     #[cfg(feature = "ocpp_2_1")]
-    #[test]
-    fn each_violation_maps_to_the_error_code_ocpp_names_for_it() {
+    #[tokio::test]
+    async fn each_violation_maps_to_the_error_code_ocpp_names_for_it() {
         use crate::wire::v21::RpcErrorCode;
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
 
         assert_eq!(
             ocpp_2_1_too_large(
+                &actor,
                 "GetVariables",
                 LimitViolation::TooManyItems {
                     count: 99,
                     limit: 50
                 }
             )
+            .await
             .code,
             RpcErrorCode::OccurrenceConstraintViolation,
             "B06.FR.16"
         );
         assert_eq!(
             ocpp_2_1_too_large(
+                &actor,
                 "GetVariables",
                 LimitViolation::TooManyBytes {
                     bytes: 9_000,
                     limit: 8_192
                 }
             )
+            .await
             .code,
             RpcErrorCode::FormatViolation,
             "B06.FR.17"
         );
+        // Both refusals are a CSMS ignoring limits it was told, and both are counted.
+        assert_eq!(actor.state().invalid_messages.total, 2);
     }
 }
@@ -21,11 +21,12 @@
 //! nothing, which is the correct behaviour rather than a silent no-op.
 //!
 //! The companion variable `SampledDataCtrlr`/`TxUpdatedInterval` governs how often *transaction*
-//! meter data is reported, and is **not** honoured yet: this crate never polls hardware for a
-//! reading - the integrator pushes them in via
-//! [`ConnectorEvent::MeterValueSampled`](crate::state::ConnectorEvent::MeterValueSampled) - so the
-//! sampling rate is the binding's choice today. Throttling what reaches the CSMS to that interval
-//! is real, contained work, and it is listed as outstanding rather than papered over here.
+//! meter data is reported, and belongs to the transactions block rather than this one: this crate
+//! never polls hardware for a reading - the integrator pushes them in via
+//! [`ConnectorEvent::MeterValueSampled`](crate::state::ConnectorEvent::MeterValueSampled), at
+//! whatever rate the meter produces them - so pacing them is a matter of holding readings back, not
+//! of fetching them. [`crate::transactions::run_transaction_meter_sampling`] does that, and samples
+//! `TxEndedInterval` for the `Ended` event beside it.
 
 use alloc::boxed::Box;
 use alloc::vec::Vec;
@@ -72,12 +73,25 @@ pub struct MeasurandSet {
     pub energy: bool,
     /// `Power.Active.Import`.
     pub power: bool,
-    /// `Current.Import`.
+    /// `Current.Import` - the total and each phase alike. OCPP's measurand lists name measurands,
+    /// not phases, so selecting one selects every phase the meter reports it on.
     pub current: bool,
-    /// `Voltage`.
+    /// `Voltage`, per phase as for `current`.
     pub voltage: bool,
     /// `SoC`.
     pub soc: bool,
+    /// `Energy.Active.Export.Register`.
+    pub energy_export: bool,
+    /// `Power.Active.Export`.
+    pub power_export: bool,
+    /// `Power.Reactive.Import`.
+    pub reactive_power: bool,
+    /// `Power.Factor`.
+    pub power_factor: bool,
+    /// `Frequency`.
+    pub frequency: bool,
+    /// `Temperature` - 1.6J only; see [`MeterSample::temperature_c`].
+    pub temperature: bool,
 }
 
 impl MeasurandSet {
@@ -88,6 +102,12 @@ impl MeasurandSet {
         current: true,
         voltage: true,
         soc: true,
+        energy_export: true,
+        power_export: true,
+        reactive_power: true,
+        power_factor: true,
+        frequency: true,
+        temperature: true,
     };
 
     /// The measurands named in a `MemberList` value.
@@ -105,6 +125,12 @@ impl MeasurandSet {
                 "Current.Import" => set.current = true,
                 "Voltage" => set.voltage = true,
                 "SoC" => set.soc = true,
+                "Energy.Active.Export.Register" => set.energy_export = true,
+                "Power.Active.Export" => set.power_export = true,
+                "Power.Reactive.Import" => set.reactive_power = true,
+                "Power.Factor" => set.power_factor = true,
+                "Frequency" => set.frequency = true,
+                "Temperature" => set.temperature = true,
                 _ => {}
             }
         }
@@ -119,7 +145,16 @@ impl MeasurandSet {
 
 /// Reads a `MemberList` device-model variable into a [`MeasurandSet`].
 pub fn measurand_set(actor: &ChargePointActor, component: &str, variable: &str) -> MeasurandSet {
-    let value = actor
+    MeasurandSet::from_member_list(&actual_value(actor, component, variable))
+}
+
+/// The `Actual` value of a device-model variable, or the empty string if it is not registered.
+fn actual_value(
+    actor: &ChargePointActor,
+    component: &str,
+    variable: &str,
+) -> alloc::string::String {
+    actor
         .state()
         .device_model
         .get(
@@ -135,8 +170,7 @@ pub fn measurand_set(actor: &ChargePointActor, component: &str, variable: &str)
         )
         .and_then(|definition| definition.attribute(VariableAttributeType::Actual))
         .map(|attribute| attribute.value.clone())
-        .unwrap_or_default();
-    MeasurandSet::from_member_list(&value)
+        .unwrap_or_default()
 }
 
 /// The measurands configured for one `TransactionEvent` shape (CV2.6).
@@ -157,6 +191,61 @@ pub fn aligned_measurands(actor: &ChargePointActor) -> MeasurandSet {
     measurand_set(actor, "AlignedDataCtrlr", "Measurands")
 }
 
+/// The measurands configured for the clock-aligned readings a transaction's end reports -
+/// `AlignedDataCtrlr.TxEndedMeasurands`, 1.6J's `StopTxnAlignedData` (CV2.6).
+pub fn aligned_ended_measurands(actor: &ChargePointActor) -> MeasurandSet {
+    measurand_set(actor, "AlignedDataCtrlr", "TxEndedMeasurands")
+}
+
+/// Whether, and how, signed meter readings are reported - `SampledDataCtrlr.SignReadings` and
+/// `SampledDataCtrlr.PublicKeyWithSignedMeterValue` (E11.FR.08, J02).
+///
+/// Only decides what is *sent*: the signature itself comes from the meter, through
+/// [`MeterSample::signed`], and a sample without one is reported unsigned whatever this says.
+/// Applies to the readings that open and close a transaction - the ones calibration law bills
+/// on - rather than every periodic one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SignedReadings {
+    /// `SignReadings`: whether a reading's signature is sent at all.
+    pub enabled: bool,
+    /// `PublicKeyWithSignedMeterValue`: how often the meter's public key goes with it.
+    pub public_key: PublicKeyWithSignedMeterValue,
+}
+
+/// OCPP's `PublicKeyWithSignedMeterValue` values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PublicKeyWithSignedMeterValue {
+    /// Never sent; the CSMS is expected to know the meter's key already.
+    #[default]
+    Never,
+    /// Sent with the first signed reading of each transaction.
+    OncePerTransaction,
+    /// Sent with every signed reading.
+    EveryMeterValue,
+}
+
+/// Reads [`SignedReadings`] from the device model, fresh per message like the measurand lists.
+/// A value that does not parse reads as OCPP's default - signing off, key never sent.
+pub fn signed_readings(actor: &ChargePointActor) -> SignedReadings {
+    SignedReadings {
+        enabled: actual_value(actor, "SampledDataCtrlr", "SignReadings")
+            .parse()
+            .unwrap_or(false),
+        public_key: match actual_value(actor, "SampledDataCtrlr", "PublicKeyWithSignedMeterValue")
+            .as_str()
+        {
+            "OncePerTransaction" => PublicKeyWithSignedMeterValue::OncePerTransaction,
+            "EveryMeterValue" => PublicKeyWithSignedMeterValue::EveryMeterValue,
+            _ => PublicKeyWithSignedMeterValue::Never,
+        },
+    }
+}
+
+#[cfg(any(feature = "ocpp_1_6", feature = "ocpp_2_0_1", feature = "ocpp_2_1"))]
+mod reading;
+#[cfg(any(feature = "ocpp_1_6", feature = "ocpp_2_0_1", feature = "ocpp_2_1"))]
+pub(crate) use self::reading::{Reading, ReadingContext, ReadingKind, ReadingPhase, readings};
+
 /// Reports a meter reading to the CSMS via standalone `MeterValues`. Implemented per protocol
 /// version, mirroring [`crate::availability::StatusNotifier`].
 #[async_trait::async_trait]
@@ -175,6 +264,14 @@ pub trait MeterValuesNotifier {
         connector_id: usize,
         sample: MeterSample,
     ) -> Result<(), Self::Error>;
+
+    /// Whether the protocol behind this notifier reports aligned readings in a transaction's
+    /// stop data - 1.6J's `StopTxnAlignedData`. When it does, [`run_aligned_meter_values`] also
+    /// keeps each boundary's reading on every running transaction; 2.x has no such field, so the
+    /// default is `false` and those readings are never recorded.
+    fn keeps_aligned_stop_data(&self) -> bool {
+        false
+    }
 }
 
 /// The `(Component, Variable)` this crate's device model uses for the clock-aligned data interval
@@ -230,7 +327,10 @@ pub fn secs_until_next_aligned(now: DateTime<Utc>, interval_secs: u32) -> u32 {
 }
 
 /// Sends a standalone `MeterValues` for every connector with a known reading, every
-/// `AlignedDataCtrlr`/`Interval` seconds, aligned to the wall clock - forever.
+/// `AlignedDataCtrlr`/`Interval` seconds, aligned to the wall clock - forever. When `notifier`
+/// [keeps aligned stop data](MeterValuesNotifier::keeps_aligned_stop_data) (1.6J), each boundary
+/// also keeps a reading on every running transaction for `StopTxnAlignedData` - see
+/// [`crate::state::EvseState::ended_meter_samples`].
 ///
 /// Reads the interval fresh on every cycle (see the module docs), and re-checks once a minute
 /// while it is `0`/absent rather than exiting, so the block can be enabled at runtime. A send failure is logged and does not stop the loop: the next aligned reading is
@@ -253,6 +353,9 @@ pub async fn run_aligned_meter_values<N: MeterValuesNotifier, B: Backoff, C: Clo
         backoff
             .wait(secs_until_next_aligned(clock.now(), interval_secs))
             .await;
+        if notifier.keeps_aligned_stop_data() {
+            keep_aligned_readings_for_transactions(actor, clock.now()).await;
+        }
         for (evse_id, connector_id, sample) in aligned_readings(actor) {
             if let Err(err) = notifier
                 .send_meter_values(evse_id, connector_id, sample)
@@ -269,6 +372,68 @@ pub async fn run_aligned_meter_values<N: MeterValuesNotifier, B: Backoff, C: Clo
     }
 }
 
+/// Makes `AlignedDataCtrlr.TxEndedMeasurands` writable, declaring that this station now reports
+/// aligned readings in a transaction's stop data - 1.6J's `StopTxnAlignedData`.
+///
+/// Registered read-only until then (CV14, `DefaultVariable::honoured`): only a notifier that
+/// [keeps aligned stop data](MeterValuesNotifier::keeps_aligned_stop_data) gives the measurand
+/// list anything to select from, and only while
+/// [`StateLimits::max_ended_meter_samples`](crate::state::StateLimits::max_ended_meter_samples) is
+/// above its default of `0` is there anywhere to keep the readings - so this does nothing
+/// otherwise. Called by
+/// [`ChargePointBuilder::meter_values`](crate::ChargePointBuilder::meter_values) as it spawns
+/// [`run_aligned_meter_values`] with such a notifier. Idempotent.
+pub async fn advertise_aligned_stop_data_support(actor: &ChargePointActor) {
+    if actor.state().max_ended_meter_samples == 0 {
+        return;
+    }
+    crate::device_model::make_actual_writable(
+        actor,
+        Component {
+            name: "AlignedDataCtrlr".into(),
+            instance: None,
+            evse: None,
+        },
+        Variable {
+            name: "TxEndedMeasurands".into(),
+            instance: None,
+        },
+    )
+    .await;
+}
+
+/// Asks every connector with a transaction running to keep its latest reading, stamped `now`,
+/// for that transaction's end - 1.6J's `StopTxnAlignedData`, which is sampled on the same
+/// boundaries as the standalone `MeterValues` beside it. Whether there is a reading to keep is
+/// the state machine's call; see
+/// [`ConnectorEvent::TransactionAlignedSampleDue`](crate::state::ConnectorEvent::TransactionAlignedSampleDue).
+async fn keep_aligned_readings_for_transactions(actor: &ChargePointActor, now: DateTime<Utc>) {
+    let running: Vec<(usize, usize)> = actor
+        .state()
+        .evses
+        .iter()
+        .enumerate()
+        .flat_map(|(evse_id, evse)| {
+            evse.transactions
+                .iter()
+                .enumerate()
+                .filter(|(_, transaction)| transaction.is_some())
+                .map(move |(connector_id, _)| (evse_id, connector_id))
+        })
+        .collect();
+    for (evse_id, connector_id) in running {
+        let _ = actor
+            .send(crate::state::ChargePointEvent::Evse {
+                evse_id,
+                event: crate::state::EvseEvent::Connector {
+                    connector_id,
+                    event: crate::state::ConnectorEvent::TransactionAlignedSampleDue(now),
+                },
+            })
+            .await;
+    }
+}
+
 /// Every connector's latest reading, as `(evse_id, connector_id, sample)`. Connectors whose
 /// hardware has never pushed one are skipped - reporting a reading this charge point does not have
 /// would mean inventing a number.
@@ -278,7 +443,7 @@ fn aligned_readings(actor: &ChargePointActor) -> Vec<(usize, usize, MeterSample)
     for (evse_id, evse) in state.evses.iter().enumerate() {
         for (connector_id, sample) in evse.latest_meter_samples.iter().enumerate() {
             if let Some(sample) = sample {
-                readings.push((evse_id, connector_id, *sample));
+                readings.push((evse_id, connector_id, sample.clone()));
             }
         }
     }
@@ -316,6 +481,18 @@ mod tests {
             .await;
     }
 
+    #[test]
+    fn every_measurand_a_sample_carries_can_be_named_in_a_member_list() {
+        assert_eq!(
+            MeasurandSet::from_member_list(
+                "Energy.Active.Import.Register,Power.Active.Import,Current.Import,Voltage,SoC,\
+                 Energy.Active.Export.Register,Power.Active.Export,Power.Reactive.Import,\
+                 Power.Factor,Frequency,Temperature"
+            ),
+            MeasurandSet::ALL
+        );
+    }
+
     #[test]
     fn a_quarter_hourly_interval_lands_on_the_quarter_hour() {
         assert_eq!(secs_until_next_aligned(at(10, 0, 0), 900), 900);
@@ -449,6 +626,31 @@ mod tests {
         );
     }
 
+    /// E11.FR.08: signing is off until the CSMS turns it on, and the key policy is read from the
+    /// same place, fresh per message.
+    #[tokio::test]
+    async fn signed_readings_follow_the_sampled_data_controller() {
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        assert_eq!(signed_readings(&actor), SignedReadings::default());
+
+        set_measurands(&actor, "SampledDataCtrlr", "SignReadings", "true").await;
+        set_measurands(
+            &actor,
+            "SampledDataCtrlr",
+            "PublicKeyWithSignedMeterValue",
+            "EveryMeterValue",
+        )
+        .await;
+
+        assert_eq!(
+            signed_readings(&actor),
+            SignedReadings {
+                enabled: true,
+                public_key: PublicKeyWithSignedMeterValue::EveryMeterValue,
+            }
+        );
+    }
+
     /// CV2.6/J02: standalone `MeterValues` reads `AlignedDataCtrlr.Measurands`, not
     /// `SampledDataCtrlr`'s - the two configure different messages from the same stored sample.
     #[tokio::test]
@@ -483,7 +685,8 @@ mod tests {
 
     /// CV2.6: the four lists a message kind can be configured with are writable, because this
     /// build acts on them (`DefaultVariable::honoured`). The `*Interval` variables next to them
-    /// are deliberately still refused - nothing drives them yet.
+    /// are refused until something drives them - see
+    /// `crate::transactions::advertise_meter_sampling_support`.
     #[tokio::test]
     async fn the_measurand_variables_are_writable_and_the_intervals_are_not() {
         let actor = ChargePointActor::spawn([1], &TokioExecutor);
@@ -525,6 +728,45 @@ mod tests {
         );
     }
 
+    /// `AlignedDataCtrlr.TxEndedMeasurands` selects from readings only a 1.6J aligned loop keeps,
+    /// so it is refused until [`advertise_aligned_stop_data_support`] says one is running.
+    #[tokio::test]
+    async fn aligned_stop_measurands_are_writable_only_once_advertised() {
+        let mutability = |actor: &ChargePointActor| {
+            actor
+                .state()
+                .device_model
+                .get(
+                    &Component {
+                        name: "AlignedDataCtrlr".into(),
+                        instance: None,
+                        evse: None,
+                    },
+                    &Variable {
+                        name: "TxEndedMeasurands".into(),
+                        instance: None,
+                    },
+                )
+                .and_then(|definition| definition.attribute(VariableAttributeType::Actual))
+                .map(|attribute| attribute.mutability)
+                .expect("a built-in default")
+        };
+
+        // With no room to keep the readings, there is nothing to select from.
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        advertise_aligned_stop_data_support(&actor).await;
+        assert_eq!(mutability(&actor), VariableMutability::ReadOnly);
+
+        let actor = ChargePointActor::spawn_with_limits(
+            [1],
+            &TokioExecutor,
+            crate::state::StateLimits::default().with_max_ended_meter_samples(12),
+        );
+        assert_eq!(mutability(&actor), VariableMutability::ReadOnly);
+        advertise_aligned_stop_data_support(&actor).await;
+        assert_eq!(mutability(&actor), VariableMutability::ReadWrite);
+    }
+
     #[tokio::test]
     async fn only_connectors_with_a_reading_are_reported() {
         let actor = ChargePointActor::spawn([2], &TokioExecutor);
@@ -576,6 +818,9 @@ mod tests {
     #[derive(Clone, Default)]
     struct RecordingNotifier {
         sent: alloc::sync::Arc<std::sync::Mutex<Vec<(usize, usize, i64)>>>,
+        /// What [`MeterValuesNotifier::keeps_aligned_stop_data`] answers - `true` stands in for
+        /// the 1.6J adapter.
+        keeps_stop_data: bool,
     }
 
     #[async_trait::async_trait]
@@ -594,6 +839,10 @@ mod tests {
                 .push((evse_id, connector_id, sample.energy_wh));
             Ok(())
         }
+
+        fn keeps_aligned_stop_data(&self) -> bool {
+            self.keeps_stop_data
+        }
     }
 
     /// A [`Backoff`] that records what it was asked to wait and returns immediately, so a loop
@@ -679,6 +928,97 @@ mod tests {
             "the loop should have waited until 10:15, 450 s away, not a whole interval"
         );
     }
+
+    /// Runs `notifier`'s aligned loop through one 10:15 boundary on a station whose only
+    /// connector has a transaction running at 4200 Wh.
+    async fn one_boundary_on_a_running_session(
+        notifier: RecordingNotifier,
+    ) -> alloc::sync::Arc<ChargePointActor> {
+        let actor = alloc::sync::Arc::new(ChargePointActor::spawn_with_limits(
+            [1],
+            &TokioExecutor,
+            crate::state::StateLimits::default().with_max_ended_meter_samples(12),
+        ));
+        let id_token = crate::state::IdToken {
+            value: "04A224B2".into(),
+            kind: crate::state::IdTokenKind::ISO14443,
+        };
+        for event in [
+            ConnectorEvent::CableConnected,
+            ConnectorEvent::LockConfirmed,
+            ConnectorEvent::IdTokenPresented(id_token.clone()),
+            ConnectorEvent::ChargingAuthorized(id_token),
+            ConnectorEvent::ContactorClosed,
+            ConnectorEvent::MeterValueSampled(MeterSample {
+                energy_wh: 4_200,
+                ..Default::default()
+            }),
+        ] {
+            let _ = actor
+                .send(ChargePointEvent::Evse {
+                    evse_id: 0,
+                    event: EvseEvent::Connector {
+                        connector_id: 0,
+                        event,
+                    },
+                })
+                .await;
+        }
+        set_interval(&actor, "900").await;
+
+        let task_actor = actor.clone();
+        let task = tokio::spawn(async move {
+            run_aligned_meter_values(
+                &notifier,
+                &RecordingBackoff::default(),
+                &FixedClock(at(10, 15, 0)),
+                &task_actor,
+            )
+            .await;
+        });
+        for _ in 0..20 {
+            tokio::task::yield_now().await;
+        }
+        task.abort();
+        assert!(
+            actor.state().evses[0].transactions[0].is_some(),
+            "the session is still running"
+        );
+        actor
+    }
+
+    /// 1.6J's `StopTxnAlignedData`: the boundary that sends a standalone reading also keeps one
+    /// on the running transaction, stamped with the loop's clock.
+    #[tokio::test]
+    async fn an_aligned_boundary_keeps_a_reading_on_the_running_transaction() {
+        let actor = one_boundary_on_a_running_session(RecordingNotifier {
+            keeps_stop_data: true,
+            ..Default::default()
+        })
+        .await;
+
+        let state = actor.state();
+        let kept = state.evses[0].ended_meter_samples[0]
+            .first()
+            .expect("the boundary kept a reading");
+        assert_eq!(kept.timestamp, at(10, 15, 0));
+        assert_eq!(kept.sample.energy_wh, 4_200);
+        assert!(kept.clock_aligned);
+    }
+
+    /// 2.x has nowhere to report aligned stop data, so its adapters keep nothing - the `Ended`
+    /// series stays free for the sampled readings it does report.
+    #[tokio::test]
+    async fn an_aligned_boundary_keeps_nothing_without_aligned_stop_data() {
+        let notifier = RecordingNotifier::default();
+        let actor = one_boundary_on_a_running_session(notifier.clone()).await;
+
+        assert!(actor.state().evses[0].ended_meter_samples[0].is_empty());
+        assert!(
+            !notifier.sent.lock().unwrap().is_empty(),
+            "the standalone reading is still sent"
+        );
+    }
 }
 
 /// OCPP 2.1's standalone `MeterValues`.
@@ -708,7 +1048,7 @@ mod ocpp_2_1 {
     ) -> Option<MeterValuesRequest> {
         let now = clock.now();
         let meter_value =
-            crate::transactions::ocpp_2_1::build_meter_values(Some(sample), now, measurands);
+            crate::transactions::ocpp_2_1::build_meter_values(Some(&sample), now, measurands);
         if meter_value.is_empty() {
             return None;
         }
@@ -949,7 +1289,7 @@ mod ocpp_2_0_1 {
     ) -> Option<MeterValuesRequest> {
         let now = clock.now();
         let meter_value =
-            crate::transactions::ocpp_2_0_1::build_meter_values(Some(sample), now, measurands);
+            crate::transactions::ocpp_2_0_1::build_meter_values(Some(&sample), now, measurands);
         if meter_value.is_empty() {
             return None;
         }
@@ -1092,7 +1432,8 @@ mod ocpp_2_0_1 {
 /// discarding it - and needs the charge point's topology to translate.
 #[cfg(feature = "ocpp_1_6")]
 mod ocpp_1_6 {
-    use super::MeterValuesNotifier;
+    use super::{MeasurandSet, MeterValuesNotifier, aligned_measurands};
+    use crate::actor::ChargePointActor;
     use crate::clock::{Clock, is_synchronized};
     use crate::state::MeterSample;
     use crate::topology::flatten_ocpp_1_6_connector_id;
@@ -1103,27 +1444,32 @@ mod ocpp_1_6 {
     use ocpp_client::ocpp_1_6::{OCPP1_6Client, OCPP1_6Error};
 
     /// Wraps an [`OCPP1_6Client`] with the connector topology its flat `connectorId` addressing
-    /// needs, and the [`Clock`] that stamps each reading - the same pairing
+    /// needs, the [`Clock`] that stamps each reading, and the [`ChargePointActor`] holding
+    /// `MeterValuesAlignedData` (`AlignedDataCtrlr.Measurands`) - the same pairing
     /// [`crate::transactions::Ocpp1_6TransactionNotifier`] uses.
     #[derive(Clone)]
     pub struct Ocpp1_6MeterValuesNotifier<C> {
         client: OCPP1_6Client,
         connector_counts: Vec<usize>,
         clock: C,
+        actor: ChargePointActor,
     }
 
     impl<C: Clock> Ocpp1_6MeterValuesNotifier<C> {
         /// Wraps `client`, resolving connector addresses against `connector_counts` (each EVSE's
-        /// connector count, in `evse_id` order) and stamping readings from `clock`.
+        /// connector count, in `evse_id` order), stamping readings from `clock` and selecting
+        /// their measurands from `actor`'s device model.
         pub fn with_clock(
             client: OCPP1_6Client,
             connector_counts: impl IntoIterator<Item = usize>,
             clock: C,
+            actor: ChargePointActor,
         ) -> Self {
             Self {
                 client,
                 connector_counts: connector_counts.into_iter().collect(),
                 clock,
+                actor,
             }
         }
     }
@@ -1134,20 +1480,23 @@ mod ocpp_1_6 {
         pub fn new(
             client: OCPP1_6Client,
             connector_counts: impl IntoIterator<Item = usize>,
+            actor: ChargePointActor,
         ) -> Self {
-            Self::with_clock(client, connector_counts, crate::clock::SystemClock)
+            Self::with_clock(client, connector_counts, crate::clock::SystemClock, actor)
         }
     }
 
     /// Builds the wire request, or `None` if the address doesn't exist in this topology (which
     /// would otherwise be reported against connector 0 - the charge point itself - and misattribute
-    /// the reading).
+    /// the reading). A request whose `meterValue` is empty - `measurands` selects nothing the
+    /// meter reports - is the caller's to drop, so the two cases log differently.
     fn build_meter_values_request<C: Clock>(
         clock: &C,
         connector_counts: &[usize],
         evse_id: usize,
         connector_id: usize,
         sample: MeterSample,
+        measurands: MeasurandSet,
     ) -> Option<MeterValuesRequest> {
         let connector_id = flatten_ocpp_1_6_connector_id(connector_counts, evse_id, connector_id)?;
         let now = clock.now();
@@ -1159,7 +1508,9 @@ mod ocpp_1_6 {
         }
         Some(MeterValuesRequest {
             connector_id,
-            meter_value: crate::transactions::ocpp_1_6::build_meter_values(sample, now),
+            meter_value: crate::transactions::ocpp_1_6::build_meter_values(
+                &sample, now, measurands,
+            ),
             // No transaction: that is exactly what makes this reading standalone. 1.6J's
             // `transactionId` is optional for precisely this case.
             transaction_id: None,
@@ -1182,6 +1533,7 @@ mod ocpp_1_6 {
                 evse_id,
                 connector_id,
                 sample,
+                aligned_measurands(&self.actor),
             ) else {
                 tracing::warn!(
                     evse_id,
@@ -1191,9 +1543,16 @@ mod ocpp_1_6 {
                 );
                 return Ok(());
             };
+            if request.meter_value.is_empty() {
+                return Ok(());
+            }
             self.client.send_meter_values(request).await?;
             Ok(())
         }
+
+        fn keeps_aligned_stop_data(&self) -> bool {
+            true
+        }
     }
 
     #[cfg(test)]
@@ -1224,6 +1583,7 @@ mod ocpp_1_6 {
                 1,
                 0,
                 MeterSample::default(),
+                MeasurandSet::ALL,
             )
             .expect("an addressable connector");
 
@@ -1245,7 +1605,8 @@ mod ocpp_1_6 {
                     &[2],
                     3,
                     0,
-                    MeterSample::default()
+                    MeterSample::default(),
+                    MeasurandSet::ALL,
                 )
                 .is_none()
             );
@@ -0,0 +1,261 @@
+//! How a [`MeterSample`] becomes the readings a wire adapter sends - version-neutral, but read
+//! only by the `ocpp_1_6`/`ocpp_2_0_1`/`ocpp_2_1` adapters in [`crate::transactions`], so compiled
+//! only alongside one of them.
+
+use alloc::vec::Vec;
+
+use super::{MeasurandSet, PublicKeyWithSignedMeterValue, SignedReadings};
+use crate::state::MeterSample;
+
+impl SignedReadings {
+    /// The signature to report with `sample` at the given point of `transaction`, and whether
+    /// its public key goes with it - `None` while signing is off or the meter did not sign.
+    ///
+    /// `OncePerTransaction` is answered from the transaction itself rather than from a record of
+    /// what was sent: the key goes with the start reading when that was signed, and otherwise
+    /// with the end reading, which is the first signed one the CSMS sees.
+    pub(crate) fn signature<'a>(
+        self,
+        sample: &'a MeterSample,
+        context: ReadingContext,
+        transaction: &crate::state::Transaction,
+    ) -> Option<(&'a crate::state::SignedMeterValue, bool)> {
+        let signed = sample.signed.as_deref().filter(|_| self.enabled)?;
+        let with_key = !signed.public_key.is_empty()
+            && match self.public_key {
+                PublicKeyWithSignedMeterValue::Never => false,
+                PublicKeyWithSignedMeterValue::EveryMeterValue => true,
+                PublicKeyWithSignedMeterValue::OncePerTransaction => match context {
+                    ReadingContext::TransactionEnd => transaction
+                        .start_meter_sample
+                        .as_ref()
+                        .is_none_or(|start| start.signed.is_none()),
+                    _ => true,
+                },
+            };
+        Some((signed, with_key))
+    }
+}
+
+/// Why a reading was taken - OCPP's `ReadingContext`, narrowed to the four this crate reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadingContext {
+    /// A reading taken during the session, or for clock-aligned data.
+    SamplePeriodic,
+    /// A reading reported because the CSMS asked for it with `TriggerMessage` (F06).
+    Trigger,
+    /// The reading a transaction started from.
+    TransactionBegin,
+    /// The reading a transaction ended on.
+    TransactionEnd,
+}
+
+/// One quantity a [`MeterSample`] reports, in the wire's own unit - see [`readings`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Reading {
+    pub(crate) kind: ReadingKind,
+    /// `None` for a figure covering every phase, as the non-phase fields of [`MeterSample`] are.
+    pub(crate) phase: Option<ReadingPhase>,
+    pub(crate) value: f64,
+}
+
+/// The measurands a [`MeterSample`] can carry, one per [`MeasurandSet`] member.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadingKind {
+    EnergyImport,
+    PowerImport,
+    CurrentImport,
+    Voltage,
+    Soc,
+    EnergyExport,
+    PowerExport,
+    ReactivePowerImport,
+    PowerFactor,
+    Frequency,
+    Temperature,
+}
+
+impl ReadingKind {
+    /// The OCPP standardized unit [`Reading::value`] is in, spelled as 2.x's
+    /// `UnitOfMeasure.unit` spells it. `None` for the power factor, which has none.
+    ///
+    /// Stated on every reading rather than left to the spec's default, which is `Wh` for
+    /// *everything* - a power reading with no unit is, read literally, an energy one.
+    pub(crate) fn unit(self) -> Option<&'static str> {
+        match self {
+            Self::EnergyImport | Self::EnergyExport => Some("Wh"),
+            Self::PowerImport | Self::PowerExport => Some("W"),
+            Self::ReactivePowerImport => Some("var"),
+            Self::CurrentImport => Some("A"),
+            Self::Voltage => Some("V"),
+            Self::Soc => Some("Percent"),
+            Self::Frequency => Some("Hz"),
+            Self::Temperature => Some("Celsius"),
+            Self::PowerFactor => None,
+        }
+    }
+}
+
+/// The phase a per-phase [`Reading`] was measured on: a line for current, line-to-neutral for
+/// voltage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReadingPhase {
+    L1,
+    L2,
+    L3,
+    L1N,
+    L2N,
+    L3N,
+}
+
+/// Every reading `sample` carries **and** `measurands` selects, in the order the wire adapters
+/// send them (J01, J02, CV2.6).
+///
+/// The two conditions are separate on purpose. What the hardware reported is a fact about this
+/// station; what `measurands` selects is what the CSMS asked to be told about. A measurand the
+/// CSMS did not ask for is dropped even when the meter produced it, and one it did ask for is still
+/// absent when the meter did not - inventing a reading to satisfy a configured list would be worse
+/// than omitting it.
+///
+/// Version-neutral, and shared by all three adapters' `sampled_values` in
+/// [`crate::transactions`]: a reading is a reading, and three mappings of the same fixed-point
+/// fields into wire units would be three places for a unit bug to hide. What differs per version -
+/// the enum spellings, 2.x having no `Temperature` - stays in the adapter.
+pub(crate) fn readings(sample: &MeterSample, measurands: MeasurandSet) -> Vec<Reading> {
+    const CURRENT_PHASES: [ReadingPhase; 3] =
+        [ReadingPhase::L1, ReadingPhase::L2, ReadingPhase::L3];
+    const VOLTAGE_PHASES: [ReadingPhase; 3] =
+        [ReadingPhase::L1N, ReadingPhase::L2N, ReadingPhase::L3N];
+
+    let mut readings = Vec::new();
+    let mut push = |selected: bool, kind, phase, value: Option<f64>| {
+        if let Some(value) = value.filter(|_| selected) {
+            readings.push(Reading { kind, phase, value });
+        }
+    };
+    push(
+        measurands.energy,
+        ReadingKind::EnergyImport,
+        None,
+        Some(sample.energy_wh as f64),
+    );
+    push(
+        measurands.power,
+        ReadingKind::PowerImport,
+        None,
+        sample.power_w.map(|w| w as f64),
+    );
+    push(
+        measurands.current,
+        ReadingKind::CurrentImport,
+        None,
+        sample.current_ma.map(|ma| ma as f64 / 1_000.0),
+    );
+    for (phase, current_ma) in CURRENT_PHASES.into_iter().zip(sample.phase_current_ma) {
+        push(
+            measurands.current,
+            ReadingKind::CurrentImport,
+            Some(phase),
+            current_ma.map(|ma| f64::from(ma) / 1_000.0),
+        );
+    }
+    push(
+        measurands.voltage,
+        ReadingKind::Voltage,
+        None,
+        sample.voltage_v.map(|v| v as f64),
+    );
+    for (phase, voltage_v) in VOLTAGE_PHASES.into_iter().zip(sample.phase_voltage_v) {
+        push(
+            measurands.voltage,
+            ReadingKind::Voltage,
+            Some(phase),
+            voltage_v.map(f64::from),
+        );
+    }
+    push(
+        measurands.soc,
+        ReadingKind::Soc,
+        None,
+        sample.soc_percent.map(f64::from),
+    );
+    push(
+        measurands.energy_export,
+        ReadingKind::EnergyExport,
+        None,
+        sample.energy_export_wh.map(|wh| wh as f64),
+    );
+    push(
+        measurands.power_export,
+        ReadingKind::PowerExport,
+        None,
+        sample.power_export_w.map(f64::from),
+    );
+    push(
+        measurands.reactive_power,
+        ReadingKind::ReactivePowerImport,
+        None,
+        sample.reactive_power_var.map(f64::from),
+    );
+    push(
+        measurands.power_factor,
+        ReadingKind::PowerFactor,
+        None,
+        sample
+            .power_factor_milli
+            .map(|milli| f64::from(milli) / 1_000.0),
+    );
+    push(
+        measurands.frequency,
+        ReadingKind::Frequency,
+        None,
+        sample
+            .frequency_millihertz
+            .map(|millihertz| f64::from(millihertz) / 1_000.0),
+    );
+    push(
+        measurands.temperature,
+        ReadingKind::Temperature,
+        None,
+        sample.temperature_c.map(f64::from),
+    );
+    readings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_selected_measurand_reports_every_phase_the_meter_measured_it_on() {
+        let sample = MeterSample {
+            energy_wh: 1_000,
+            current_ma: Some(48_000),
+            phase_current_ma: [Some(16_000), Some(16_000), Some(16_000)],
+            phase_voltage_v: [Some(230), Some(230), Some(230)],
+            ..Default::default()
+        };
+
+        let current = readings(
+            &sample,
+            MeasurandSet {
+                current: true,
+                ..MeasurandSet::default()
+            },
+        );
+
+        assert_eq!(
+            current
+                .iter()
+                .map(|reading| (reading.kind, reading.phase))
+                .collect::<Vec<_>>(),
+            alloc::vec![
+                (ReadingKind::CurrentImport, None),
+                (ReadingKind::CurrentImport, Some(ReadingPhase::L1)),
+                (ReadingKind::CurrentImport, Some(ReadingPhase::L2)),
+                (ReadingKind::CurrentImport, Some(ReadingPhase::L3)),
+            ]
+        );
+        assert_eq!(current[1].value, 16.0);
+    }
+}
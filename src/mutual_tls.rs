@@ -219,8 +219,7 @@ where
         CertifiedKey::new(chain, signing_key),
     ));
 
-    let config = ClientConfig::builder()
-        .with_root_certificates(root_store)
+    let config = crate::trust_store::csms_verifying_config_builder(root_store.into())
         .with_client_cert_resolver(resolver);
 
     tracing::info!(
@@ -760,13 +759,12 @@ mod tests {
         // cannot run profile 3, no matter what else is configured"). Installed the same way
         // `crate::certificates::handle_certificate_signed` does in production: the plain `install`
         // trait method, with only a raw PEM and no hash data.
-        let leaf = base64_encode_for_test(b"leaf-der-bytes");
         let certificate_store = StoredCertificates::new(Arc::new(InMemoryStorage::new()));
         assert_eq!(
             certificate_store
                 .install(
                     CertificateUse::ChargingStation,
-                    &format!("-----BEGIN CERTIFICATE-----\n{leaf}\n-----END CERTIFICATE-----\n"),
+                    crate::test_certificates::CHARGING_STATION,
                 )
                 .await,
             Ok(crate::hardware::InstallCertificateOutcome::Accepted)
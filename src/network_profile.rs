@@ -94,6 +94,13 @@ pub async fn handle_set_network_profile(
         );
         return SetNetworkProfileOutcome::Rejected;
     }
+    let command = crate::command_policy::InboundCommand::SetNetworkProfile {
+        slot,
+        profile: &profile,
+    };
+    if crate::command_policy::screen(actor, &command).is_err() {
+        return SetNetworkProfileOutcome::Rejected;
+    }
 
     // F4.2/F3.3: a network profile carries the security profile and the endpoint this charge
     // point will authenticate to, so writing one *is* a reconfiguration of security parameters -
@@ -55,7 +55,10 @@
 //! password instead of the origin's.
 
 use crate::actor::ChargePointActor;
+use crate::call_dedup::{ReplayReporter, SeenCalls, deduplicate_calls_reporting_to};
+use crate::clock::SystemMonotonicClock;
 use crate::hardware::KeyStore;
+use crate::invalid_messages::{InvalidMessageSink, InvalidMessageStream};
 use crate::network_profile::selected_profile;
 use crate::payload_limit::{PayloadLimits, SizeLimitedStream};
 use alloc::boxed::Box;
@@ -138,6 +141,15 @@ pub struct ConnectionTarget {
     username: Option<String>,
     password: Option<String>,
     timeout: Option<Duration>,
+    /// The inbound CALL ids every connection's transport deduplicates against - one record
+    /// across the first connection and every redial, so a CSMS retrying after a reconnect is
+    /// still recognised (`crate::call_dedup`).
+    seen_calls: Arc<SeenCalls<SystemMonotonicClock>>,
+    /// Where those transports report a replayed CALL. Shared rather than read from
+    /// `Inner::security_actor` at dial time because the first connection is dialled before the
+    /// actor exists - [`ConnectionTarget::attach_security_reporting`] fills it in for every
+    /// transport already built.
+    replay_reporter: ReplayReporter,
     inner: Mutex<Inner>,
 }
 
@@ -180,10 +192,17 @@ struct Inner {
     /// [`ConnectionTarget::set_max_inbound_frame_bytes`] and [`crate::payload_limit`] (F5.2).
     max_inbound_frame_bytes: usize,
     /// Where a redial's [`SizeLimitedStream`] reports a `MemoryExhaustion` security event when it
-    /// refuses an oversized frame. `None` only before [`ConnectionTarget::attach_security_reporting`]
-    /// has been called - unreachable for a redial in practice, since that happens once the actor
-    /// this target's `Client` belongs to exists, which is before any redial can occur.
+    /// refuses an oversized frame, and where its invalid-message wrappers count what they see
+    /// (`crate::invalid_messages`). `None` only before
+    /// [`ConnectionTarget::attach_security_reporting`] has been called - unreachable for a redial
+    /// in practice, since that happens once the actor this target's `Client` belongs to exists,
+    /// which is before any redial can occur.
     security_actor: Option<ChargePointActor>,
+    /// The kind of TLS failure the last failed redial was reported as (see
+    /// `ConnectionTarget::report_tls_failure`, private), so a CSMS that keeps failing the same way
+    /// is reported once rather than once per back-off. Cleared by a successful dial.
+    #[cfg(feature = "certificates")]
+    reported_tls_failure: Option<crate::state::SecurityEventType>,
     /// A `NetworkConfiguration[slot]` Basic-Auth credential (CV10), set by
     /// [`ConnectionTarget::attach_basic_auth_credential`]. `None` (the default) means "no
     /// rotation to apply" - a redial to the origin address uses `ConnectionTarget::password`
@@ -206,6 +225,8 @@ impl ConnectionTarget {
             username: options.username.map(ToString::to_string),
             password: options.password.map(ToString::to_string),
             timeout: options.timeout,
+            seen_calls: Arc::new(SeenCalls::new(SystemMonotonicClock)),
+            replay_reporter: ReplayReporter::new(),
             inner: Mutex::new(Inner {
                 active: address.to_string(),
                 fallback: None,
@@ -221,6 +242,8 @@ impl ConnectionTarget {
                 max_inbound_frame_bytes: crate::payload_limit::PayloadLimits::default()
                     .max_inbound_frame_bytes,
                 security_actor: None,
+                #[cfg(feature = "certificates")]
+                reported_tls_failure: None,
                 credential: None,
                 credential_failures: 0,
             }),
@@ -240,9 +263,12 @@ impl ConnectionTarget {
 
     /// Attaches the charge-point actor a redial's [`crate::payload_limit::SizeLimitedStream`]
     /// reports a `MemoryExhaustion` security event to when it refuses an oversized inbound frame
-    /// (F5.2). Set once the actor exists - see `Inner::security_actor` (private) for why every
-    /// reachable redial already has one by the time it dials.
+    /// (F5.2), that counts the malformed and undecodable CSMS messages a redial sees (see
+    /// [`crate::invalid_messages`]), and that every connection's repeated CALL ids are reported to
+    /// (see [`crate::call_dedup`]). Set once the actor exists - see `Inner::security_actor`
+    /// (private) for why every reachable redial already has one by the time it dials.
     pub fn attach_security_reporting(&self, actor: ChargePointActor) {
+        self.replay_reporter.attach(actor.clone());
         self.inner.lock().expect("target lock").security_actor = Some(actor);
     }
 
@@ -290,6 +316,62 @@ impl ConnectionTarget {
         options
     }
 
+    /// Dials the first connection through this target - the same wrapped transport every redial
+    /// gets ([`crate::payload_limit`], [`crate::call_dedup`]) - and builds the version's `Client`
+    /// over it, redialling through this target unless `options` opts out of reconnecting or
+    /// supplies its own reconnector (the same rule [`Self::install`] follows).
+    ///
+    /// Needs [`Self::set_version`] first: a bare transport is dialled for one version, not
+    /// negotiated. `timeout` is the per-call timeout, which the caller has to state: the one
+    /// `ocpp-client` falls back to is private to it - see `crate::connect_and_setup`.
+    // Gated like `crate::connect`, its only caller.
+    #[cfg(all(
+        feature = "std",
+        feature = "ocpp_2_1",
+        feature = "reservation",
+        feature = "local-auth-list",
+        feature = "tariff-cost",
+        feature = "periodic-event-stream",
+        feature = "smart-charging",
+        feature = "variable-monitoring"
+    ))]
+    pub(crate) async fn connect(
+        self: &Arc<Self>,
+        options: &ConnectOptions<'_>,
+        timeout: Duration,
+    ) -> Result<ocpp_client::NegotiatedClient, TransportError> {
+        use ocpp_client::{
+            Client, ClientConfig, NegotiatedClient, ReconnectBehavior, TokioExecutor, TokioTimer,
+        };
+
+        let (_, version) = self.dial_parameters()?;
+        let (sink, source) = self.dial().await?;
+        let mut config = ClientConfig::new(timeout).with_keepalive(options.keepalive);
+        if let ReconnectBehavior::Enabled(policy) = options.reconnect {
+            let reconnector: Box<dyn Reconnector> = match &options.reconnector {
+                Some(custom) => Box::new(SharedReconnector(custom.clone())),
+                None => Box::new(TargetReconnector(self.clone())),
+            };
+            config = config.with_reconnect(reconnector, policy);
+        }
+        let executor = Box::new(TokioExecutor);
+        let timer = Box::new(TokioTimer);
+        Ok(match version {
+            #[cfg(feature = "ocpp_1_6")]
+            OcppVersion::V1_6 => NegotiatedClient::V1_6(Client::from_transport_with_config(
+                sink, source, executor, timer, config,
+            )),
+            #[cfg(feature = "ocpp_2_0_1")]
+            OcppVersion::V2_0_1 => NegotiatedClient::V2_0_1(Client::from_transport_with_config(
+                sink, source, executor, timer, config,
+            )),
+            #[cfg(feature = "ocpp_2_1")]
+            OcppVersion::V2_1 => NegotiatedClient::V2_1(Client::from_transport_with_config(
+                sink, source, executor, timer, config,
+            )),
+        })
+    }
+
     /// Records the OCPP version the connection negotiated, which every redial must keep speaking -
     /// the client's handlers are bound to it.
     ///
@@ -422,6 +504,10 @@ impl ConnectionTarget {
             }
             inner.tls_config_failures = 0;
             inner.credential_failures = 0;
+            #[cfg(feature = "certificates")]
+            {
+                inner.reported_tls_failure = None;
+            }
             is_origin.then(|| inner.credential.clone()).flatten()
         };
         // CV10: a successful dial to the origin address proves whatever password it just used -
@@ -490,6 +576,34 @@ impl ConnectionTarget {
         rolled_back_address
     }
 
+    /// Raises the security event a failed dial's TLS handshake amounts to, if any - see
+    /// [`crate::trust_store`]'s module docs, "Reporting what a failed handshake amounts to". A
+    /// failure of the same kind as the last one reported is not raised again until a dial has
+    /// succeeded in between: a back-off loop against a CSMS serving an expired certificate would
+    /// otherwise fill the security log, and the offline queue behind it, with copies of one fact.
+    #[cfg(feature = "certificates")]
+    async fn report_tls_failure(&self, error: &TransportError) {
+        let Some(event) = crate::trust_store::tls_failure_event(&**error) else {
+            return;
+        };
+        let actor = {
+            let mut inner = self.inner.lock().expect("target lock");
+            if inner.reported_tls_failure.as_ref() == Some(&event.event_type) {
+                return;
+            }
+            inner.reported_tls_failure = Some(event.event_type.clone());
+            inner.security_actor.clone()
+        };
+        tracing::warn!(
+            event = ?event.event_type,
+            tech_info = event.tech_info.as_deref().unwrap_or_default(),
+            "the CSMS's TLS handshake failed"
+        );
+        if let Some(actor) = actor {
+            crate::security::report_security_event(&actor, event).await;
+        }
+    }
+
     /// Stages a new TLS client configuration - typically rebuilt from
     /// [`crate::trust_store::build_root_cert_store`] (plus, on security profile 3,
     /// [`crate::mutual_tls::client_config`]) after the installed `CsmsRoot` set changes - to take
@@ -592,11 +706,9 @@ impl ConnectionTarget {
         match websocket_transport(&address, version, Some(options)).await {
             Ok((sink, source)) => {
                 self.record_success(is_origin).await;
-                // F5.2: every redial's inbound stream is wrapped so an oversized frame is
-                // refused before `ocpp-client` ever deserializes it - see `crate::payload_limit`
-                // for exactly what this does and does not cover (in particular: not the very
-                // first connection, dialled through `ocpp_client::connect` itself, which exposes
-                // no such hook).
+                // F5.2: every connection's inbound stream through this target is wrapped so an
+                // oversized frame is refused before `ocpp-client` ever deserializes it - see
+                // `crate::payload_limit` for exactly what this does and does not cover.
                 let (max_inbound_frame_bytes, security_actor) = {
                     let inner = self.inner.lock().expect("target lock");
                     (inner.max_inbound_frame_bytes, inner.security_actor.clone())
@@ -606,11 +718,32 @@ impl ConnectionTarget {
                     PayloadLimits {
                         max_inbound_frame_bytes,
                     },
-                    security_actor,
+                    security_actor.clone(),
                 ));
-                Ok((sink, source))
+                // Deduplicated on every connection, the first included when `Self::connect` dials
+                // it, since `seen_calls` needs no actor and `replay_reporter` picks one up once it
+                // exists.
+                let (sink, source) = deduplicate_calls_reporting_to(
+                    sink,
+                    source,
+                    self.seen_calls.clone(),
+                    self.replay_reporter.clone(),
+                );
+                // Counted once the actor exists to count against - see `crate::invalid_messages`
+                // for why the first connection is not. Deduplication sits beneath the counting, so
+                // a cached response resent for a repeated CALL is not counted a second time
+                // (`crate::call_dedup`).
+                match security_actor {
+                    Some(actor) => Ok((
+                        Box::new(InvalidMessageSink::new(sink, actor.clone())),
+                        Box::new(InvalidMessageStream::new(source, actor)),
+                    )),
+                    None => Ok((sink, source)),
+                }
             }
             Err(error) => {
+                #[cfg(feature = "certificates")]
+                self.report_tls_failure(&error).await;
                 if let Some(rolled_back_to) = self.record_failure(is_origin).await {
                     tracing::warn!(
                         failed = %address,
@@ -658,6 +791,48 @@ fn jitter_seed(address: &str, username: Option<&str>) -> u64 {
     fnv1a(seed, &nanos.to_le_bytes())
 }
 
+/// A caller-supplied `ConnectOptions::reconnector`, boxed for `ClientConfig` - see
+/// `ConnectionTarget::connect`.
+#[cfg(all(
+    feature = "std",
+    feature = "ocpp_2_1",
+    feature = "reservation",
+    feature = "local-auth-list",
+    feature = "tariff-cost",
+    feature = "periodic-event-stream",
+    feature = "smart-charging",
+    feature = "variable-monitoring"
+))]
+struct SharedReconnector(Arc<dyn Reconnector>);
+
+#[cfg(all(
+    feature = "std",
+    feature = "ocpp_2_1",
+    feature = "reservation",
+    feature = "local-auth-list",
+    feature = "tariff-cost",
+    feature = "periodic-event-stream",
+    feature = "smart-charging",
+    feature = "variable-monitoring"
+))]
+impl Reconnector for SharedReconnector {
+    fn connect<'a>(
+        &'a self,
+    ) -> Pin<
+        Box<
+            dyn Future<
+                    Output = Result<
+                        (Box<dyn TransportSink>, Box<dyn TransportStream>),
+                        TransportError,
+                    >,
+                > + Send
+                + 'a,
+        >,
+    > {
+        self.0.connect()
+    }
+}
+
 /// Adapts [`ConnectionTarget`] to `ocpp-client`'s `Reconnector`.
 struct TargetReconnector(Arc<ConnectionTarget>);
 
@@ -1548,3 +1723,316 @@ mod tests {
         // `credential: Option<...>` defaulting to `None`.
     }
 }
+
+/// A redial that fails in TLS raises the security event the failure amounts to (see
+/// [`crate::trust_store`]), driven against a local stand-in CSMS: a real rustls server presenting
+/// an expired, a wrong-host or a stolen certificate, or offering only a cipher suite the client
+/// does not, or a canned reply from a legacy TLS stack that only speaks TLS 1.0 or picks a weak
+/// cipher suite regardless.
+#[cfg(all(test, feature = "certificates"))]
+mod tls_failure_tests {
+    use super::*;
+    use crate::executor::TokioExecutor;
+    use crate::state::{SecurityEvent, SecurityEventType};
+    use crate::sync::BroadcastReceiver;
+    use crate::test_certificates::{
+        CSMS_ROOT, CSMS_SERVER, CSMS_SERVER_EXPIRED, CSMS_SERVER_KEY, CSMS_SERVER_WRONG_HOST,
+        IMPOSTOR_KEY,
+    };
+    use ocpp_client::rustls::crypto::{CipherSuiteCommon, CryptoProvider};
+    use ocpp_client::rustls::pki_types::pem::PemObject;
+    use ocpp_client::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+    use ocpp_client::rustls::sign::{CertifiedKey, SingleCertAndKey};
+    use ocpp_client::rustls::{
+        CipherSuite, RootCertStore, ServerConfig, ServerConnection, SupportedCipherSuite,
+        Tls12CipherSuite,
+    };
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// What a legacy server that tops out at TLS 1.0 answers a TLS 1.2/1.3 `ClientHello` with:
+    /// a `ServerHello` for version 1.0 (`TLS_RSA_WITH_AES_128_CBC_SHA`, no extensions).
+    const TLS_1_0_SERVER_HELLO: &[u8] = &[
+        0x16, 0x03, 0x01, 0x00, 0x2a, // handshake record, 42 bytes
+        0x02, 0x00, 0x00, 0x26, // ServerHello, 38 bytes
+        0x03, 0x01, // TLS 1.0
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // random
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,    //
+        0x00, // no session id
+        0x00, 0x2f, // TLS_RSA_WITH_AES_128_CBC_SHA
+        0x00, // no compression
+    ];
+
+    /// A TLS 1.2 server insisting on a weak cipher suite the client never offered.
+    const UNOFFERED_CIPHER_SERVER_HELLO: &[u8] = &[
+        0x16, 0x03, 0x03, 0x00, 0x2a, // handshake record, 42 bytes
+        0x02, 0x00, 0x00, 0x26, // ServerHello, 38 bytes
+        0x03, 0x03, // TLS 1.2
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // random
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,    //
+        0x00, // no session id
+        0x00, 0x2f, // TLS_RSA_WITH_AES_128_CBC_SHA
+        0x00, // no compression
+    ];
+
+    /// A server's fatal `handshake_failure` alert - the generic refusal, which says nothing of why.
+    const HANDSHAKE_FAILURE_ALERT: &[u8] = &[0x15, 0x03, 0x03, 0x00, 0x02, 0x02, 0x28];
+
+    fn server_config(certificate: &str, key: &str) -> Arc<ServerConfig> {
+        let builder = ServerConfig::builder();
+        // Loaded without `with_single_cert`'s check that the key matches the certificate, so the
+        // impostor below can present a certificate it does not hold the key for.
+        let signing_key = builder
+            .crypto_provider()
+            .key_provider
+            .load_private_key(PrivateKeyDer::from_pem_slice(key.as_bytes()).unwrap())
+            .unwrap();
+        let chain = alloc::vec![CertificateDer::from_pem_slice(certificate.as_bytes()).unwrap()];
+        Arc::new(builder.with_no_client_auth().with_cert_resolver(Arc::new(
+            SingleCertAndKey::from(CertifiedKey::new(chain, signing_key)),
+        )))
+    }
+
+    /// A real rustls server that will only negotiate `TLS_RSA_WITH_AES_128_CBC_SHA` - a suite no
+    /// rustls client offers. rustls has no implementation of it, so the suite is one of its own
+    /// TLS 1.2 suites under that name; the handshake never gets far enough for the difference to
+    /// matter.
+    fn weak_cipher_server_config() -> Arc<ServerConfig> {
+        let provider = ServerConfig::builder().crypto_provider().clone();
+        let base = provider
+            .cipher_suites
+            .iter()
+            .find_map(|suite| match suite {
+                SupportedCipherSuite::Tls12(suite) => Some(*suite),
+                _ => None,
+            })
+            .expect("the default provider has TLS 1.2 suites");
+        let weak: &'static Tls12CipherSuite = Box::leak(Box::new(Tls12CipherSuite {
+            common: CipherSuiteCommon {
+                suite: CipherSuite::TLS_RSA_WITH_AES_128_CBC_SHA,
+                ..base.common
+            },
+            ..*base
+        }));
+        let provider = CryptoProvider {
+            cipher_suites: alloc::vec![SupportedCipherSuite::Tls12(weak)],
+            ..(*provider).clone()
+        };
+        let signing_key = provider
+            .key_provider
+            .load_private_key(PrivateKeyDer::from_pem_slice(CSMS_SERVER_KEY.as_bytes()).unwrap())
+            .unwrap();
+        let chain = alloc::vec![CertificateDer::from_pem_slice(CSMS_SERVER.as_bytes()).unwrap()];
+        Arc::new(
+            ServerConfig::builder_with_provider(Arc::new(provider))
+                .with_protocol_versions(&[&ocpp_client::rustls::version::TLS12])
+                .unwrap()
+                .with_no_client_auth()
+                .with_cert_resolver(Arc::new(SingleCertAndKey::from(CertifiedKey::new(
+                    chain,
+                    signing_key,
+                )))),
+        )
+    }
+
+    /// Accepts one connection on a local port and runs a TLS server handshake over it.
+    fn serve_tls(config: Arc<ServerConfig>) -> u16 {
+        serve_tls_reporting(config).0
+    }
+
+    /// [`serve_tls`], also handing back the error the server's side of the handshake failed
+    /// with, if it did.
+    fn serve_tls_reporting(
+        config: Arc<ServerConfig>,
+    ) -> (u16, std::sync::mpsc::Receiver<ocpp_client::rustls::Error>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (failed, failure) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut connection = ServerConnection::new(config).unwrap();
+            while connection.is_handshaking() {
+                if let Err(error) = connection.complete_io(&mut stream) {
+                    if let Some(error) = error
+                        .get_ref()
+                        .and_then(|error| error.downcast_ref::<ocpp_client::rustls::Error>())
+                    {
+                        let _ = failed.send(error.clone());
+                    }
+                    break;
+                }
+            }
+        });
+        (port, failure)
+    }
+
+    /// Accepts one connection on a local port and answers its `ClientHello` with `reply`.
+    fn serve_bytes(reply: &'static [u8]) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut client_hello = [0; 4096];
+            let _ = stream.read(&mut client_hello);
+            let _ = stream.write_all(reply);
+            // Held open until the client gives up, so it reads the reply rather than a reset.
+            let _ = stream.read_to_end(&mut alloc::vec::Vec::new());
+        });
+        port
+    }
+
+    /// A target redialling `localhost:port`, trusting [`CSMS_ROOT`] through the configuration
+    /// `crate::trust_store` builds, with security reporting attached.
+    fn target(port: u16) -> (Arc<ConnectionTarget>, BroadcastReceiver<SecurityEvent>) {
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(CertificateDer::from_pem_slice(CSMS_ROOT.as_bytes()).unwrap())
+            .unwrap();
+        let options = ConnectOptions {
+            tls_config: Some(crate::trust_store::server_only_client_config(Arc::new(
+                roots,
+            ))),
+            ..Default::default()
+        };
+        let target = ConnectionTarget::new(&alloc::format!("wss://localhost:{port}"), &options);
+        target.set_version(OcppVersion::V2_1);
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let events = actor.subscribe_security_events();
+        target.attach_security_reporting(actor);
+        (target, events)
+    }
+
+    /// Redials `port` once, expecting it to fail, and returns the security event it raised.
+    async fn raised_by_a_redial_to(port: u16) -> Option<SecurityEvent> {
+        let (target, mut events) = target(port);
+        assert!(target.dial().await.is_err());
+        tokio::time::timeout(Duration::from_millis(200), events.recv())
+            .await
+            .ok()
+            .map(|event| event.expect("the actor is still running"))
+    }
+
+    #[tokio::test]
+    async fn an_expired_csms_certificate_raises_invalid_csms_certificate() {
+        let port = serve_tls(server_config(CSMS_SERVER_EXPIRED, CSMS_SERVER_KEY));
+
+        let event = raised_by_a_redial_to(port)
+            .await
+            .expect("an event is raised");
+
+        assert_eq!(event.event_type, SecurityEventType::InvalidCsmsCertificate);
+        assert!(event.tech_info.unwrap().contains("expired"));
+    }
+
+    #[tokio::test]
+    async fn a_certificate_for_another_host_raises_invalid_csms_certificate() {
+        let port = serve_tls(server_config(CSMS_SERVER_WRONG_HOST, CSMS_SERVER_KEY));
+
+        let event = raised_by_a_redial_to(port)
+            .await
+            .expect("an event is raised");
+
+        assert_eq!(event.event_type, SecurityEventType::InvalidCsmsCertificate);
+        assert!(event.tech_info.unwrap().contains("csms.example"));
+    }
+
+    #[tokio::test]
+    async fn a_server_without_the_certificates_key_raises_csms_failed_to_authenticate() {
+        let port = serve_tls(server_config(CSMS_SERVER, IMPOSTOR_KEY));
+
+        let event = raised_by_a_redial_to(port)
+            .await
+            .expect("an event is raised");
+
+        assert_eq!(
+            event.event_type,
+            SecurityEventType::CsmsFailedToAuthenticate
+        );
+    }
+
+    #[tokio::test]
+    async fn a_tls_1_0_server_raises_invalid_tls_version() {
+        let port = serve_bytes(TLS_1_0_SERVER_HELLO);
+
+        let event = raised_by_a_redial_to(port)
+            .await
+            .expect("an event is raised");
+
+        assert_eq!(event.event_type, SecurityEventType::InvalidTlsVersion);
+    }
+
+    #[tokio::test]
+    async fn a_server_choosing_a_cipher_suite_it_was_not_offered_raises_invalid_tls_cipher_suite() {
+        let port = serve_bytes(UNOFFERED_CIPHER_SERVER_HELLO);
+
+        let event = raised_by_a_redial_to(port)
+            .await
+            .expect("an event is raised");
+
+        assert_eq!(event.event_type, SecurityEventType::InvalidTlsCipherSuite);
+    }
+
+    #[tokio::test]
+    async fn a_real_server_with_only_a_suite_the_client_rejects_fails_the_dial_raising_nothing() {
+        // A conforming server never picks a suite the client did not offer: finding none in
+        // common, it refuses with the generic `handshake_failure` alert before any `ServerHello`.
+        // That is all the client gets to see, and it does not say the suite was the reason, so
+        // the redial fails without a cipher-suite event - only a server picking the suite anyway,
+        // as above, is caught naming it.
+        let (port, server_failure) = serve_tls_reporting(weak_cipher_server_config());
+
+        assert_eq!(raised_by_a_redial_to(port).await, None);
+        assert_eq!(
+            server_failure.recv().unwrap(),
+            ocpp_client::rustls::Error::PeerIncompatible(
+                ocpp_client::rustls::PeerIncompatible::NoCipherSuitesInCommon
+            ),
+            "the suite is what the server refused over"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_generic_handshake_failure_alert_raises_nothing() {
+        // The server gave up without saying why - a cipher suite, a curve, a client certificate -
+        // so there is no specific event to name.
+        let port = serve_bytes(HANDSHAKE_FAILURE_ALERT);
+
+        assert_eq!(raised_by_a_redial_to(port).await, None);
+    }
+
+    #[tokio::test]
+    async fn a_handshake_that_succeeds_raises_nothing_even_when_the_upgrade_then_fails() {
+        // The stand-in completes the handshake and hangs up on the WebSocket upgrade: the redial
+        // fails, but not in TLS.
+        let port = serve_tls(server_config(CSMS_SERVER, CSMS_SERVER_KEY));
+
+        assert_eq!(raised_by_a_redial_to(port).await, None);
+    }
+
+    #[tokio::test]
+    async fn the_same_failure_is_reported_once_until_a_dial_succeeds() {
+        let (target, mut events) = target(serve_bytes(UNOFFERED_CIPHER_SERVER_HELLO));
+        assert!(target.dial().await.is_err());
+        let first = events.recv().await.unwrap();
+        assert_eq!(first.event_type, SecurityEventType::InvalidTlsCipherSuite);
+
+        // Same target, same failure again - e.g. the next back-off.
+        let port = serve_bytes(UNOFFERED_CIPHER_SERVER_HELLO);
+        target.switch_to(&alloc::format!("wss://localhost:{port}"));
+        assert!(target.dial().await.is_err());
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), events.recv())
+                .await
+                .is_err(),
+            "a repeat of the last reported failure must not be raised again"
+        );
+
+        // A success in between re-arms it.
+        target.record_success(false).await;
+        let port = serve_bytes(UNOFFERED_CIPHER_SERVER_HELLO);
+        target.switch_to(&alloc::format!("wss://localhost:{port}"));
+        assert!(target.dial().await.is_err());
+        let again = events.recv().await.unwrap();
+        assert_eq!(again.event_type, SecurityEventType::InvalidTlsCipherSuite);
+    }
+}
@@ -25,15 +25,16 @@
 //!    `ChargingProfile` is 56 KB by value - see D2.3) actually gets built. Both allocations happen
 //!    entirely inside `ocpp-client`, before any code in this crate executes at all.
 //!
-//! So for the two connection paths built by `ocpp_client::connect()` itself (the *initial* dial,
-//! and every version-specific `connect_1_6`/`connect_2_0_1`/`connect_2_1` convenience function),
-//! this crate has **no hook to intervene before either allocation**, and says so rather than
-//! pretending otherwise. Building one would mean re-implementing the WebSocket handshake and
-//! subprotocol negotiation this crate deliberately does not duplicate.
+//! So for the connections `ocpp-client` builds itself (`ocpp_client::connect()` and every
+//! version-specific `connect_1_6`/`connect_2_0_1`/`connect_2_1` convenience function), this crate
+//! has **no hook to intervene before either allocation**, and says so rather than pretending
+//! otherwise. Building one would mean re-implementing the WebSocket handshake and subprotocol
+//! negotiation this crate deliberately does not duplicate. `crate::connect_and_setup` dials its
+//! first connection that way whenever it offers more than one version.
 //!
-//! What this crate *does* control is `crate::network_switch::ConnectionTarget::dial` (private): every
-//! redial (after the very first connection - unavoidably dialled through `ocpp_client::connect`)
-//! goes through the public `ocpp_client::websocket_transport`, which hands back the raw
+//! What this crate *does* control is `crate::network_switch::ConnectionTarget::dial` (private):
+//! every redial of a `crate::connect_and_setup` session - and its first connection too, when
+//! exactly one version is offered - goes through the public `ocpp_client::websocket_transport`, which hands back the raw
 //! [`ocpp_client::TransportSink`]/[`ocpp_client::TransportStream`] halves before they are wired
 //! into a `Client`. [`crate::payload_limit::SizeLimitedStream`] wraps that stream: a text frame over the configured
 //! ceiling is refused and **never handed to `Client`**, so neither of `ocpp-client`'s
@@ -120,9 +121,9 @@ pub struct SizeLimitedStream {
 impl SizeLimitedStream {
     /// Wraps `inner`, refusing any frame over `limits.max_inbound_frame_bytes`. `actor`, if
     /// present, receives a `MemoryExhaustion` security event each time a frame is refused - pass
-    /// `None` for a transport with no charge-point actor yet (e.g. before the first connection
-    /// exists), though [`crate::network_switch::ConnectionTarget`] only wraps redials, which
-    /// always have one.
+    /// `None` for a transport with no charge-point actor yet - the first connection
+    /// [`crate::network_switch::ConnectionTarget`] dials, whose refusals are logged but not
+    /// reported.
     pub fn new(
         inner: Box<dyn TransportStream>,
         limits: PayloadLimits,
@@ -61,6 +61,9 @@ pub async fn handle_open_periodic_event_stream(
     if !crate::refusal::capability_present(&state.capabilities, "OpenPeriodicEventStream") {
         return OpenPeriodicEventStreamOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "OpenPeriodicEventStream") {
+        return OpenPeriodicEventStreamOutcome::Rejected;
+    }
     if state
         .variable_monitors
         .get(variable_monitoring_id)
@@ -110,6 +113,9 @@ pub async fn handle_adjust_periodic_event_stream(
     if !crate::refusal::capability_present(&state.capabilities, "AdjustPeriodicEventStream") {
         return AdjustPeriodicEventStreamOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "AdjustPeriodicEventStream") {
+        return AdjustPeriodicEventStreamOutcome::Rejected;
+    }
     if state.periodic_event_streams.get(id).is_none() {
         return AdjustPeriodicEventStreamOutcome::Rejected;
     }
@@ -823,6 +829,8 @@ pub mod ocpp_2_1 {
                 "ClosePeriodicEventStream",
             ));
         }
+        crate::command_policy::screen_action(actor, "ClosePeriodicEventStream")
+            .map_err(|refused| crate::refusal::ocpp_2_1_refused_by_policy(&refused))?;
         handle_close_periodic_event_stream(actor, PeriodicEventStreamId(request.id)).await;
         Ok(ClosePeriodicEventStreamResponse { custom_data: None })
     }
@@ -852,6 +860,8 @@ pub mod ocpp_2_1 {
                 "GetPeriodicEventStream",
             ));
         }
+        crate::command_policy::screen_action(actor, "GetPeriodicEventStream")
+            .map_err(|refused| crate::refusal::ocpp_2_1_refused_by_policy(&refused))?;
         let streams = handle_get_periodic_event_stream(actor);
         let constant_stream_data = if streams.is_empty() {
             None
@@ -48,14 +48,15 @@ use crate::hardware::{AtomicStorage, Storage};
 use crate::offline_queue::{OfflineQueue, flush_offline_queue};
 use crate::security::{SecurityEventLog, SecurityLogEntry};
 use crate::state::{
-    AuthorizationCacheEntry, BootReasonCause, ChargePointEvent, ChargePointState, ChargingProfile,
-    ChargingProfileId, ChargingProfileKind, ChargingProfilePurpose, ChargingProfileScope,
-    ChargingRateUnit, ChargingSchedule, ChargingSchedulePeriod, Component, ConnectorState,
-    ConnectorStatus, ConnectorStatusChanged, InstalledChargingProfile, LocalListEntry, MeterSample,
-    NetworkProfileSlot, RecoveredDeviceModelAttribute, RecoveredReservation, RecoveredTransaction,
-    RecurrencyKind, Reservation, SecurityEvent, SecurityEventType, Transaction,
-    TransactionEventKind, TransactionEventOccurred, TransactionId, TransactionUpdateReason,
-    Variable, VariableAttributeType,
+    AuthorizationCacheEntry, AvailabilityTarget, BootReasonCause, ChargePointEvent,
+    ChargePointState, ChargingProfile, ChargingProfileId, ChargingProfileKind,
+    ChargingProfilePurpose, ChargingProfileScope, ChargingRateUnit, ChargingSchedule,
+    ChargingSchedulePeriod, Component, ConnectorState, ConnectorStatus, ConnectorStatusChanged,
+    DeviceModelEvent, InstalledChargingProfile, LocalListEntry, MeterSample, NetworkProfileSlot,
+    RecoveredDeviceModelAttribute, RecoveredReservation, RecoveredTransaction, RecurrencyKind,
+    Reservation, SecurityEvent, SecurityEventType, Transaction, TransactionEventKind,
+    TransactionEventOccurred, TransactionId, TransactionUpdateReason, Variable, VariableAttribute,
+    VariableAttributeType, VariableCharacteristics, VariableDataType, VariableMutability,
 };
 use crate::sync::{BroadcastReceiver, WatchReceiver};
 
@@ -133,10 +134,19 @@ pub fn persistence_decision(
 ) -> PersistenceDecision {
     match occurred.kind {
         TransactionEventKind::Ended => PersistenceDecision::Clear,
+        // The cable arriving, or the EV dropping off the line, arriving or leaving, moves the
+        // charging state just as a plain charging-state change does. A signed reading is the one a
+        // recovered transaction's bill would rest on, so it is never held back by the threshold
+        // below.
         TransactionEventKind::Started
-        | TransactionEventKind::Updated(TransactionUpdateReason::ChargingStateChanged) => {
-            PersistenceDecision::Write
-        }
+        | TransactionEventKind::Updated(
+            TransactionUpdateReason::ChargingStateChanged
+            | TransactionUpdateReason::CablePluggedIn
+            | TransactionUpdateReason::EvCommunicationLost
+            | TransactionUpdateReason::EvDetected
+            | TransactionUpdateReason::EvDeparted
+            | TransactionUpdateReason::SignedDataReceived,
+        ) => PersistenceDecision::Write,
         // A rate change (CV18) moves `seq_no` and nothing else recoverable: no new energy, no new
         // charging state. An energy manager may move the limit every few minutes - a dynamic
         // profile every `dynUpdateInterval` - so writing here would put flash wear on a path that
@@ -145,13 +155,18 @@ pub fn persistence_decision(
         // neither changes what a recovered transaction is owed - the energy delivered is the
         // meter's business, and a limit a power cut interrupted is one the CSMS re-sends or the
         // driver re-enters.
+        // A triggered report and a tariff change (I12) move nothing recoverable either: the
+        // tariff goes with its transaction (see `crate::tariff`), and is re-sent by a CSMS that
+        // finds the transaction still running.
         TransactionEventKind::Updated(
             TransactionUpdateReason::ChargingRateChanged
             | TransactionUpdateReason::LimitSet
-            | TransactionUpdateReason::LimitReached(_),
+            | TransactionUpdateReason::LimitReached(_)
+            | TransactionUpdateReason::Trigger
+            | TransactionUpdateReason::TariffChanged,
         ) => PersistenceDecision::Skip,
         TransactionEventKind::Updated(TransactionUpdateReason::MeterValuePeriodic) => {
-            let Some(sample) = occurred.transaction.last_meter_sample else {
+            let Some(sample) = &occurred.transaction.last_meter_sample else {
                 return PersistenceDecision::Skip;
             };
             // Nothing recoverable is on record for this connector yet (a start whose write
@@ -160,7 +175,7 @@ pub fn persistence_decision(
             let Some(previous) = previous else {
                 return PersistenceDecision::Write;
             };
-            let Some(persisted) = previous.transaction.last_meter_sample else {
+            let Some(persisted) = &previous.transaction.last_meter_sample else {
                 return PersistenceDecision::Write;
             };
             // A meter that has gone backwards is a hardware glitch, not a reason to stop
@@ -435,8 +450,8 @@ fn next_record<C: Clock>(
         (_, None) => None,
     };
     let meter_start = previous
-        .and_then(|previous| previous.meter_start)
-        .or(occurred.transaction.last_meter_sample);
+        .and_then(|previous| previous.meter_start.clone())
+        .or_else(|| occurred.transaction.last_meter_sample.clone());
     PersistedTransaction {
         schema_version: SCHEMA_VERSION,
         evse_id: occurred.evse_id,
@@ -929,6 +944,14 @@ enum PersistedTransactionEventKind {
     UpdatedMeterValuePeriodic,
     Ended,
     UpdatedChargingRateChanged,
+    // Appended, so a queue written before these existed still reads back.
+    UpdatedEvCommunicationLost,
+    UpdatedSignedDataReceived,
+    UpdatedTrigger,
+    UpdatedTariffChanged,
+    UpdatedEvDetected,
+    UpdatedEvDeparted,
+    UpdatedCablePluggedIn,
 }
 
 impl From<TransactionEventKind> for PersistedTransactionEventKind {
@@ -952,6 +975,28 @@ impl From<TransactionEventKind> for PersistedTransactionEventKind {
             TransactionEventKind::Updated(
                 TransactionUpdateReason::LimitSet | TransactionUpdateReason::LimitReached(_),
             ) => Self::UpdatedChargingRateChanged,
+            // Each of these *is* queued offline, and its trigger reason is the whole of what it
+            // tells the CSMS - replayed under another, it would report something that did not
+            // happen.
+            TransactionEventKind::Updated(TransactionUpdateReason::EvCommunicationLost) => {
+                Self::UpdatedEvCommunicationLost
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::SignedDataReceived) => {
+                Self::UpdatedSignedDataReceived
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::Trigger) => Self::UpdatedTrigger,
+            TransactionEventKind::Updated(TransactionUpdateReason::TariffChanged) => {
+                Self::UpdatedTariffChanged
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::EvDetected) => {
+                Self::UpdatedEvDetected
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::EvDeparted) => {
+                Self::UpdatedEvDeparted
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::CablePluggedIn) => {
+                Self::UpdatedCablePluggedIn
+            }
             TransactionEventKind::Ended => Self::Ended,
         }
     }
@@ -970,6 +1015,27 @@ impl From<PersistedTransactionEventKind> for TransactionEventKind {
             PersistedTransactionEventKind::UpdatedChargingRateChanged => {
                 Self::Updated(TransactionUpdateReason::ChargingRateChanged)
             }
+            PersistedTransactionEventKind::UpdatedEvCommunicationLost => {
+                Self::Updated(TransactionUpdateReason::EvCommunicationLost)
+            }
+            PersistedTransactionEventKind::UpdatedSignedDataReceived => {
+                Self::Updated(TransactionUpdateReason::SignedDataReceived)
+            }
+            PersistedTransactionEventKind::UpdatedTrigger => {
+                Self::Updated(TransactionUpdateReason::Trigger)
+            }
+            PersistedTransactionEventKind::UpdatedTariffChanged => {
+                Self::Updated(TransactionUpdateReason::TariffChanged)
+            }
+            PersistedTransactionEventKind::UpdatedEvDetected => {
+                Self::Updated(TransactionUpdateReason::EvDetected)
+            }
+            PersistedTransactionEventKind::UpdatedEvDeparted => {
+                Self::Updated(TransactionUpdateReason::EvDeparted)
+            }
+            PersistedTransactionEventKind::UpdatedCablePluggedIn => {
+                Self::Updated(TransactionUpdateReason::CablePluggedIn)
+            }
             PersistedTransactionEventKind::Ended => Self::Ended,
         }
     }
@@ -2579,7 +2645,7 @@ pub async fn run_security_log_persistence<S: Storage, C: Clock>(
             event,
             recorded_at: crate::clock::is_synchronized(&now).then_some(now),
         });
-        if let Some(evicted) = evicted {
+        for evicted in evicted {
             tracing::warn!(
                 event_type = ?evicted.event.event_type,
                 "the security log is full; dropping its oldest entry to make room for a new one"
@@ -2598,13 +2664,24 @@ pub async fn run_security_log_persistence<S: Storage, C: Clock>(
 /// running) as its first new entry. That ordering is deliberate: the new log's first line then
 /// says how the previous history ended.
 ///
-/// Nothing in this crate calls this yet - clearing is a CSMS-initiated or maintenance action, and
-/// the blocks that would trigger it (`GetLog`, customer-information erasure) don't exist yet; see
-/// `docs/PRODUCTION-ROADMAP.md` §8.4 (F4.3) and B5.1/B5.5.
+/// This is the direct call, for an integrator clearing the log from code of their own. The CSMS
+/// and a logged-in technician go through
+/// [`ChargePointEvent::SecurityLogClearRequested`] instead, which [`run_security_log_clears`]
+/// carries out with this.
 pub async fn clear_security_log<S: Storage>(
     actor: &ChargePointActor,
     log: &SecurityEventLog,
     store: &SecurityLogStore<S>,
+) -> usize {
+    clear_security_log_reporting(actor, log, store, None).await
+}
+
+/// [`clear_security_log`], with `tech_info` on the `SecurityLogWasCleared` event it raises.
+async fn clear_security_log_reporting<S: Storage>(
+    actor: &ChargePointActor,
+    log: &SecurityEventLog,
+    store: &SecurityLogStore<S>,
+    tech_info: Option<String>,
 ) -> usize {
     let discarded = log.clear();
     store.clear().await;
@@ -2612,13 +2689,93 @@ pub async fn clear_security_log<S: Storage>(
         actor,
         SecurityEvent {
             event_type: SecurityEventType::SecurityLogWasCleared,
-            tech_info: None,
+            tech_info,
         },
     )
     .await;
     discarded
 }
 
+/// The vendor-specific component the CSMS clears the security log through - see
+/// [`security_log_clear_registration`].
+pub const SECURITY_LOG_COMPONENT: &str = "SecurityLogCtrlr";
+
+/// The `SecurityLogCtrlr.Clear` registration: OCPP defines `SecurityLogWasCleared` but no message
+/// that clears the log, so writing `true` here is the CSMS's way to ask. The write raises
+/// [`ChargePointEvent::SecurityLogClearRequested`] and is never stored - `crate::device_model`
+/// intercepts it before the generic write path - so the value reads back as nothing but its
+/// `false` placeholder.
+///
+/// Registered only beside [`run_security_log_clears`], by
+/// [`crate::ChargePointBuilder::security_log_persisted`]: on a station with nothing to carry the
+/// clear out, a write would be accepted and never acted on (B05.FR.09), so the variable is not
+/// there to write. Counts against the device model's `max_variables`.
+pub fn security_log_clear_registration() -> DeviceModelEvent {
+    DeviceModelEvent::VariableRegistered {
+        component: Component {
+            name: SECURITY_LOG_COMPONENT.into(),
+            instance: None,
+            evse: None,
+        },
+        variable: Variable {
+            name: "Clear".into(),
+            instance: None,
+        },
+        characteristics: VariableCharacteristics {
+            data_type: VariableDataType::Boolean,
+            unit: None,
+            min_limit: None,
+            max_limit: None,
+            values_list: None,
+            supports_monitoring: false,
+        },
+        attributes: alloc::vec![VariableAttribute {
+            attribute_type: VariableAttributeType::Actual,
+            value: "false".into(),
+            mutability: VariableMutability::WriteOnly,
+            persistent: false,
+            constant: false,
+            requires_reboot: false,
+        }],
+    }
+}
+
+/// Carries out every [`ChargePointEvent::SecurityLogClearRequested`] against `log` and `store`,
+/// forever: clears both through [`clear_security_log`]'s path, with the `SecurityLogWasCleared`
+/// event naming who asked and how much went, then marks the request done.
+///
+/// Runs beside [`run_security_log_persistence`] for the same log. A snapshot that task was midway
+/// through writing when the clear landed may briefly put the old entries back in storage, but
+/// never for long: the `SecurityLogWasCleared` event this raises is the next thing that task
+/// records, and the snapshot it writes for it is the cleared log.
+pub async fn run_security_log_clears<S: Storage>(
+    actor: &ChargePointActor,
+    log: &SecurityEventLog,
+    store: &SecurityLogStore<S>,
+) {
+    let mut updates = actor.subscribe();
+    loop {
+        let pending = updates.borrow().pending_security_log_clear.clone();
+        if let Some(requested_by) = pending {
+            let discarded = log.len();
+            tracing::info!(%requested_by, discarded, "clearing the security log");
+            clear_security_log_reporting(
+                actor,
+                log,
+                store,
+                Some(format!(
+                    "cleared by {requested_by}; {discarded} entries discarded"
+                )),
+            )
+            .await;
+            let _ = actor
+                .send(ChargePointEvent::SecurityLogClearCompleted)
+                .await;
+        }
+        updates.changed().await;
+    }
+}
+
 // --- local authorization list persistence (E2.4, docs/PRODUCTION-ROADMAP.md §7.2) ---
 
 /// The version stamped into every [`PersistedLocalAuthorizationList`] record. Independent of
@@ -3050,6 +3207,185 @@ fn active_reservations(state: &ChargePointState) -> Vec<PersistedReservationEntr
     entries
 }
 
+// --- scheduled unavailability persistence (G03.FR.05, docs/PRODUCTION-ROADMAP.md §7.2) ---
+//
+// What must survive a reboot so a `ChangeAvailability` answered `Scheduled` is still honoured:
+// the CSMS has been told the change will happen once the transaction ends, and a power cut is
+// one way for it to end. Without a record, the connector would come back `Available` and the
+// CSMS would never hear otherwise.
+
+/// The version stamped into every [`PersistedScheduledUnavailability`] record. Independent of
+/// the other `*_SCHEMA_VERSION`s for the same reason [`BOOT_REASON_SCHEMA_VERSION`] is.
+pub const SCHEDULED_UNAVAILABILITY_SCHEMA_VERSION: u32 = 1;
+
+/// The key the whole set of scheduled changes is written under - one whole-set snapshot, for the
+/// same reason [`RESERVATION_KEY`] is one.
+const SCHEDULED_UNAVAILABILITY_KEY: &str = "ocpp-cp/scheduled-unavailability";
+
+/// Every `ChangeAvailability` to inoperative still waiting on a transaction, as written to
+/// durable storage.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PersistedScheduledUnavailability {
+    /// The [`SCHEDULED_UNAVAILABILITY_SCHEMA_VERSION`] this record was written with.
+    pub schema_version: u32,
+    /// Every scheduled change, in the order it was accepted.
+    pub targets: Vec<AvailabilityTarget>,
+}
+
+/// Reads and writes [`ChargePointState::scheduled_unavailability`] through a [`Storage`].
+///
+/// Write policy: the same as [`ReservationStore`]'s - every change is written unconditionally.
+/// Each one comes from a discrete CSMS `ChangeAvailability` or a transaction ending, neither of
+/// which is anywhere near a meter cadence.
+#[derive(Debug, Clone)]
+pub struct ScheduledUnavailabilityStore<S> {
+    storage: S,
+}
+
+impl<S: Storage> ScheduledUnavailabilityStore<S> {
+    /// Creates a store over `storage`.
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Writes `targets` as the whole scheduled set, replacing whatever was there before -
+    /// clearing storage outright if `targets` is empty. Returns whether the write actually
+    /// reached storage.
+    pub async fn save(&self, targets: &[AvailabilityTarget]) -> bool {
+        if targets.is_empty() {
+            self.clear().await;
+            return true;
+        }
+        let record = PersistedScheduledUnavailability {
+            schema_version: SCHEDULED_UNAVAILABILITY_SCHEMA_VERSION,
+            targets: targets.to_vec(),
+        };
+        let Ok(encoded) = serde_json::to_vec(&record) else {
+            tracing::error!("failed to encode the scheduled availability changes for storage");
+            return false;
+        };
+        match self
+            .storage
+            .set(SCHEDULED_UNAVAILABILITY_KEY, &encoded)
+            .await
+        {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    "failed to persist the scheduled availability changes; continuing without \
+                     durability for them"
+                );
+                false
+            }
+        }
+    }
+
+    /// Removes the stored snapshot, if any. A missing snapshot is not an error.
+    pub async fn clear(&self) {
+        if let Err(err) = self.storage.remove(SCHEDULED_UNAVAILABILITY_KEY).await {
+            tracing::warn!(
+                error = %err,
+                "failed to clear the persisted scheduled availability changes"
+            );
+        }
+    }
+
+    /// Reads back every persisted scheduled change, or an empty `Vec` if there isn't a record,
+    /// it can't be read, or it was written by an incompatible
+    /// [`SCHEDULED_UNAVAILABILITY_SCHEMA_VERSION`].
+    pub async fn load(&self) -> Vec<AvailabilityTarget> {
+        let encoded = match self.storage.get(SCHEDULED_UNAVAILABILITY_KEY).await {
+            Ok(Some(encoded)) => encoded,
+            Ok(None) => return Vec::new(),
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    "failed to read the persisted scheduled availability changes; treating them \
+                     as absent"
+                );
+                return Vec::new();
+            }
+        };
+        let record: PersistedScheduledUnavailability = match serde_json::from_slice(&encoded) {
+            Ok(record) => record,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    "persisted scheduled availability changes could not be decoded; discarding \
+                     them"
+                );
+                return Vec::new();
+            }
+        };
+        if record.schema_version != SCHEDULED_UNAVAILABILITY_SCHEMA_VERSION {
+            tracing::warn!(
+                found = record.schema_version,
+                expected = SCHEDULED_UNAVAILABILITY_SCHEMA_VERSION,
+                "discarding persisted scheduled availability changes written by an incompatible \
+                 schema version"
+            );
+            return Vec::new();
+        }
+        record.targets
+    }
+}
+
+impl<S: Storage + Send + Sync> ScheduledUnavailabilityStore<AtomicStorage<S>> {
+    /// Creates a store over `storage`, wrapped in [`AtomicStorage`] for the same reason
+    /// [`TransactionStore::new_atomic`] does.
+    pub fn new_atomic(storage: S) -> Self {
+        ScheduledUnavailabilityStore::new(AtomicStorage::new(storage))
+    }
+}
+
+/// Recovers the scheduled availability changes from durable storage and hands them to the state
+/// machine as one [`ChargePointEvent::PersistedScheduledUnavailabilityRestored`]. Returns how
+/// many were recovered.
+///
+/// A recovered change whose scope has no transaction in progress is applied straight away, as
+/// part of the same event - see [`ChargePointState::scheduled_unavailability`]. A transaction
+/// interrupted by the power cut is closed out by [`restore_transactions`] rather than resumed, so
+/// in practice every recovered change is applied here, and the connector comes back inoperative
+/// as the CSMS was promised. Storage is then reconciled to hold only the changes still waiting,
+/// so an applied one isn't re-applied on every later boot.
+pub async fn restore_scheduled_unavailability<S: Storage>(
+    actor: &ChargePointActor,
+    store: &ScheduledUnavailabilityStore<S>,
+) -> usize {
+    let targets = store.load().await;
+    let recovered = targets.len();
+    if recovered > 0 {
+        tracing::info!(
+            count = recovered,
+            "recovering scheduled availability changes from durable storage"
+        );
+    }
+    let _ = actor
+        .send(ChargePointEvent::PersistedScheduledUnavailabilityRestored { targets })
+        .await;
+    store.save(&actor.state().scheduled_unavailability).await;
+    recovered
+}
+
+/// Persists [`ChargePointState::scheduled_unavailability`] through `store` for as long as
+/// `state_changes` keeps producing new values, writing whenever the set actually changes - see
+/// [`ScheduledUnavailabilityStore`]'s docs for the write policy.
+pub async fn run_scheduled_unavailability_persistence<S: Storage>(
+    mut state_changes: WatchReceiver<ChargePointState>,
+    store: &ScheduledUnavailabilityStore<S>,
+) {
+    let mut last: Vec<AvailabilityTarget> = Vec::new();
+    loop {
+        state_changes.changed().await;
+        let snapshot = state_changes.borrow().scheduled_unavailability.clone();
+        if snapshot != last {
+            store.save(&snapshot).await;
+            last = snapshot;
+        }
+    }
+}
+
 // --- device model attribute persistence (E2.3, docs/PRODUCTION-ROADMAP.md §7.2) ---
 
 /// The version stamped into every [`PersistedDeviceModel`] record.
@@ -3461,6 +3797,131 @@ impl<S: Storage + Send + Sync> BootReasonStore<AtomicStorage<S>> {
     }
 }
 
+// --- firmware update persistence (B3.2, docs/PRODUCTION-ROADMAP.md §7.2) ---
+//
+// What must survive the reboot an installed firmware image asks for, so the next boot can still
+// tell the CSMS how the update ended: `InstallRebooting` is the last status the old firmware can
+// send (L01.FR.15), and without a marker the new firmware comes up with no idea an update was
+// under way, let alone which request started it. Written by `crate::firmware`'s worker strictly
+// before the reboot, for the same reason `BootReasonStore` is - there is no "after" in which to
+// still write it.
+
+/// The version stamped into every [`PendingFirmwareUpdate`] record. Independent of the other
+/// `*_SCHEMA_VERSION`s for the same reason [`BOOT_REASON_SCHEMA_VERSION`] is.
+pub const FIRMWARE_UPDATE_SCHEMA_VERSION: u32 = 1;
+
+/// The key the pending firmware update is written under - one record, since at most one update
+/// can be waiting on a reboot at a time.
+const FIRMWARE_UPDATE_KEY: &str = "ocpp-cp/firmware-update";
+
+/// A firmware update whose outcome is only known once the charge point has rebooted into it, as
+/// written to durable storage.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PendingFirmwareUpdate {
+    /// The [`FIRMWARE_UPDATE_SCHEMA_VERSION`] this record was written with.
+    pub schema_version: u32,
+    /// The request id the update's status notifications carry (L01.FR.10). `None` for 1.6J,
+    /// whose `UpdateFirmware` has none.
+    pub request_id: Option<i64>,
+    /// The firmware version that was running when the update was installed, if the installer
+    /// knew it.
+    pub previous_version: Option<String>,
+    /// The firmware version the installed image should come up as, if the installer knew it.
+    pub expected_version: Option<String>,
+    /// When the reboot into the new image was requested.
+    pub started_at: DateTime<Utc>,
+}
+
+/// Reads and writes the [`PendingFirmwareUpdate`] marker through a [`Storage`].
+///
+/// Write policy: the same as [`BootReasonStore`]'s - one synchronous write, issued by the
+/// firmware worker before it asks for the reboot, and one clear once the next boot's
+/// `BootNotification` has been accepted.
+#[derive(Debug, Clone)]
+pub struct FirmwareUpdateStore<S> {
+    storage: S,
+}
+
+impl<S: Storage> FirmwareUpdateStore<S> {
+    /// Creates a store over `storage`.
+    pub fn new(storage: S) -> Self {
+        Self { storage }
+    }
+
+    /// Writes `update`, replacing whatever was previously recorded. Returns whether the write
+    /// actually reached storage - `false` means the next boot will not know an update was under
+    /// way, already logged.
+    pub async fn save(&self, update: &PendingFirmwareUpdate) -> bool {
+        let Ok(encoded) = serde_json::to_vec(update) else {
+            tracing::error!("failed to encode the pending firmware update for storage");
+            return false;
+        };
+        match self.storage.set(FIRMWARE_UPDATE_KEY, &encoded).await {
+            Ok(()) => true,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    "failed to persist the pending firmware update; its outcome will not be \
+                     reported after the reboot"
+                );
+                false
+            }
+        }
+    }
+
+    /// Removes the stored marker, if any. A missing record is not an error. Like
+    /// [`BootReasonStore::clear`], call this only once the `BootNotification` it changed has been
+    /// accepted.
+    pub async fn clear(&self) {
+        if let Err(err) = self.storage.remove(FIRMWARE_UPDATE_KEY).await {
+            tracing::warn!(error = %err, "failed to clear the pending firmware update");
+        }
+    }
+
+    /// Reads back the pending update, or `None` if there isn't one, it can't be read, or it was
+    /// written by an incompatible [`FIRMWARE_UPDATE_SCHEMA_VERSION`]. Does not clear it.
+    pub async fn load(&self) -> Option<PendingFirmwareUpdate> {
+        let encoded = match self.storage.get(FIRMWARE_UPDATE_KEY).await {
+            Ok(Some(encoded)) => encoded,
+            Ok(None) => return None,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    "failed to read the pending firmware update; treating it as absent"
+                );
+                return None;
+            }
+        };
+        let record: PendingFirmwareUpdate = match serde_json::from_slice(&encoded) {
+            Ok(record) => record,
+            Err(err) => {
+                tracing::warn!(
+                    error = %err,
+                    "a pending firmware update could not be decoded; discarding it"
+                );
+                return None;
+            }
+        };
+        if record.schema_version != FIRMWARE_UPDATE_SCHEMA_VERSION {
+            tracing::warn!(
+                found = record.schema_version,
+                expected = FIRMWARE_UPDATE_SCHEMA_VERSION,
+                "discarding a pending firmware update written by an incompatible schema version"
+            );
+            return None;
+        }
+        Some(record)
+    }
+}
+
+impl<S: Storage + Send + Sync> FirmwareUpdateStore<AtomicStorage<S>> {
+    /// Creates a store over `storage`, wrapped in [`AtomicStorage`] for the same reason
+    /// [`TransactionStore::new_atomic`] does.
+    pub fn new_atomic(storage: S) -> Self {
+        FirmwareUpdateStore::new(AtomicStorage::new(storage))
+    }
+}
+
 #[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
@@ -3506,6 +3967,8 @@ mod tests {
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         }
     }
 
@@ -5081,6 +5544,42 @@ mod tests {
         assert_eq!(event.event_type, SecurityEventType::SecurityLogWasCleared);
     }
 
+    #[tokio::test]
+    async fn a_requested_clear_is_carried_out_and_names_who_asked() {
+        use crate::executor::TokioExecutor;
+
+        let actor = ChargePointActor::spawn([1], &TokioExecutor);
+        let mut reported = actor.subscribe_security_events();
+        let store = alloc::sync::Arc::new(SecurityLogStore::new(InMemoryStorage::new()));
+        let log = alloc::sync::Arc::new(SecurityEventLog::new());
+        log.record(log_entry(None));
+        log.record(log_entry(None));
+        store.save(&log.entries()).await;
+
+        let (task_actor, task_log, task_store) = (actor.clone(), log.clone(), store.clone());
+        tokio::spawn(async move {
+            run_security_log_clears(&task_actor, &task_log, &task_store).await;
+        });
+        let _ = actor
+            .send(ChargePointEvent::SecurityLogClearRequested {
+                requested_by: "the CSMS".into(),
+            })
+            .await;
+
+        let event = reported.recv().await.unwrap();
+        assert_eq!(event.event_type, SecurityEventType::SecurityLogWasCleared);
+        assert_eq!(
+            event.tech_info.as_deref(),
+            Some("cleared by the CSMS; 2 entries discarded")
+        );
+        assert!(log.is_empty());
+        assert_eq!(store.load().await, alloc::vec![]);
+        let mut updates = actor.subscribe();
+        while updates.borrow().pending_security_log_clear.is_some() {
+            updates.changed().await;
+        }
+    }
+
     #[tokio::test]
     async fn a_charge_point_without_storage_still_keeps_an_in_memory_security_log() {
         let store = SecurityLogStore::new(NoStorage);
@@ -5375,6 +5874,111 @@ mod tests {
         );
     }
 
+    // --- scheduled unavailability persistence (G03.FR.05) ---
+
+    const SCHEDULED_CONNECTOR: AvailabilityTarget = AvailabilityTarget::Connector {
+        evse_id: 0,
+        connector_id: 0,
+    };
+
+    #[tokio::test]
+    async fn a_scheduled_unavailability_set_round_trips_through_storage() {
+        let store = ScheduledUnavailabilityStore::new(InMemoryStorage::new());
+        assert_eq!(store.load().await, Vec::new());
+
+        assert!(store.save(&[SCHEDULED_CONNECTOR]).await);
+        assert_eq!(store.load().await, alloc::vec![SCHEDULED_CONNECTOR]);
+
+        assert!(store.save(&[]).await);
+        assert_eq!(store.load().await, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn a_scheduled_unavailability_set_from_an_incompatible_schema_version_is_discarded() {
+        let storage = InMemoryStorage::new();
+        storage
+            .set(
+                SCHEDULED_UNAVAILABILITY_KEY,
+                &serde_json::to_vec(&PersistedScheduledUnavailability {
+                    schema_version: SCHEDULED_UNAVAILABILITY_SCHEMA_VERSION + 1,
+                    targets: alloc::vec![SCHEDULED_CONNECTOR],
+                })
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let store = ScheduledUnavailabilityStore::new(storage);
+        assert_eq!(store.load().await, Vec::new());
+    }
+
+    /// The end-to-end guarantee: a change answered `Scheduled` is still honoured when the
+    /// transaction it waited on is ended by a power cut rather than by the driver, so the
+    /// connector doesn't come back `Available` against what the CSMS was told.
+    #[tokio::test]
+    async fn a_change_scheduled_behind_a_transaction_cut_by_a_power_loss_applies_after_reboot() {
+        let storage = alloc::sync::Arc::new(InMemoryStorage::new());
+        let store = ScheduledUnavailabilityStore::new(storage.clone());
+
+        let executor = crate::executor::TokioExecutor;
+        let before = ChargePointActor::spawn([1], &executor);
+        let state_changes = before.subscribe();
+        let persistence_store = ScheduledUnavailabilityStore::new(storage.clone());
+        tokio::spawn(async move {
+            run_scheduled_unavailability_persistence(state_changes, &persistence_store).await;
+        });
+        drive_a_charging_transaction(&before, 1_000).await;
+        let _ = before
+            .send(ChargePointEvent::UnavailabilityScheduled {
+                target: SCHEDULED_CONNECTOR,
+            })
+            .await;
+        for _ in 0..20 {
+            if !store.load().await.is_empty() {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(store.load().await, alloc::vec![SCHEDULED_CONNECTOR]);
+
+        // --- the cut.
+        drop(before);
+
+        // --- after the reboot.
+        let after = ChargePointActor::spawn([1], &executor);
+        let recovered = restore_scheduled_unavailability(&after, &store).await;
+
+        assert_eq!(recovered, 1);
+        assert_eq!(
+            after.state().evses[0].connectors[0],
+            ConnectorState::Unavailable
+        );
+        assert!(after.state().scheduled_unavailability.is_empty());
+        // Applied, so storage no longer holds it for the next boot.
+        assert_eq!(store.load().await, Vec::new());
+    }
+
+    #[tokio::test]
+    async fn restoring_a_scheduled_change_for_a_connector_this_charge_point_lacks_skips_it() {
+        let store = ScheduledUnavailabilityStore::new(InMemoryStorage::new());
+        store
+            .save(&[AvailabilityTarget::Connector {
+                evse_id: 3,
+                connector_id: 0,
+            }])
+            .await;
+
+        let executor = crate::executor::TokioExecutor;
+        let actor = ChargePointActor::spawn([1], &executor);
+        restore_scheduled_unavailability(&actor, &store).await;
+
+        assert_eq!(
+            actor.state().evses[0].connectors[0],
+            ConnectorState::Available
+        );
+        assert_eq!(store.load().await, Vec::new());
+    }
+
     // --- device model attribute persistence (E2.3) ---
 
     fn persistent_component() -> Component {
@@ -5630,4 +6234,43 @@ mod tests {
         assert!(store.save(BootReasonCause::RemoteReset).await);
         assert_eq!(store.load().await, None);
     }
+
+    fn pending_update(request_id: i64) -> PendingFirmwareUpdate {
+        PendingFirmwareUpdate {
+            schema_version: FIRMWARE_UPDATE_SCHEMA_VERSION,
+            request_id: Some(request_id),
+            previous_version: Some("1.0.0".into()),
+            expected_version: Some("1.1.0".into()),
+            started_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn a_pending_firmware_update_round_trips_through_storage() {
+        let store = FirmwareUpdateStore::new(InMemoryStorage::new());
+        assert_eq!(store.load().await, None);
+
+        assert!(store.save(&pending_update(7)).await);
+        assert_eq!(store.load().await, Some(pending_update(7)));
+
+        assert!(store.save(&pending_update(8)).await);
+        assert_eq!(store.load().await, Some(pending_update(8)));
+
+        store.clear().await;
+        assert_eq!(store.load().await, None);
+    }
+
+    #[tokio::test]
+    async fn a_pending_firmware_update_from_an_incompatible_schema_version_is_discarded() {
+        let storage = InMemoryStorage::new();
+        let mut record = pending_update(7);
+        record.schema_version = FIRMWARE_UPDATE_SCHEMA_VERSION + 1;
+        storage
+            .set(FIRMWARE_UPDATE_KEY, &serde_json::to_vec(&record).unwrap())
+            .await
+            .unwrap();
+
+        let store = FirmwareUpdateStore::new(storage);
+        assert_eq!(store.load().await, None);
+    }
 }
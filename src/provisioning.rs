@@ -1410,14 +1410,14 @@ mod ocpp_2_1 {
     /// crash) - maps to `Unknown` rather than `PowerUp`: this crate cannot currently tell those
     /// apart from a clean power-up without an explicit hardware-supplied signal (out of scope
     /// here - see `docs/ROADMAP.md`), and `Unknown` is the only variant that doesn't overclaim
-    /// knowledge it doesn't have. `ApplicationReset`/`LocalReset`/`FirmwareUpdate`/`Watchdog`/
-    /// `Triggered` are likewise never produced - nothing in this crate today commands any of
-    /// those causes.
+    /// knowledge it doesn't have. `ApplicationReset`/`LocalReset`/`Watchdog`/`Triggered` are
+    /// likewise never produced - nothing in this crate today commands any of those causes.
     pub(super) fn map_reason(reason: Option<BootReasonCause>) -> BootReasonEnum {
         match reason {
             None => BootReasonEnum::Unknown,
             Some(BootReasonCause::RemoteReset) => BootReasonEnum::RemoteReset,
             Some(BootReasonCause::ScheduledReset) => BootReasonEnum::ScheduledReset,
+            Some(BootReasonCause::FirmwareUpdate) => BootReasonEnum::FirmwareUpdate,
         }
     }
 
@@ -1520,6 +1520,10 @@ mod ocpp_2_1 {
                 map_reason(Some(BootReasonCause::ScheduledReset)),
                 BootReasonEnum::ScheduledReset
             );
+            assert_eq!(
+                map_reason(Some(BootReasonCause::FirmwareUpdate)),
+                BootReasonEnum::FirmwareUpdate
+            );
         }
 
         #[test]
@@ -1563,6 +1567,7 @@ mod ocpp_2_0_1 {
             None => BootReasonEnum::Unknown,
             Some(BootReasonCause::RemoteReset) => BootReasonEnum::RemoteReset,
             Some(BootReasonCause::ScheduledReset) => BootReasonEnum::ScheduledReset,
+            Some(BootReasonCause::FirmwareUpdate) => BootReasonEnum::FirmwareUpdate,
         }
     }
 
@@ -1666,6 +1671,10 @@ mod ocpp_2_0_1 {
                 map_reason(Some(BootReasonCause::ScheduledReset)),
                 BootReasonEnum::ScheduledReset
             );
+            assert_eq!(
+                map_reason(Some(BootReasonCause::FirmwareUpdate)),
+                BootReasonEnum::FirmwareUpdate
+            );
         }
 
         #[test]
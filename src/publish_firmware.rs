@@ -379,6 +379,9 @@ pub async fn handle_publish_firmware(
     if !crate::refusal::capability_present(&actor.state().capabilities, "PublishFirmware") {
         return PublishFirmwareOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "PublishFirmware") {
+        return PublishFirmwareOutcome::Rejected;
+    }
     if request.location.is_empty() || request.checksum.is_empty() {
         return PublishFirmwareOutcome::Rejected;
     }
@@ -413,6 +416,13 @@ pub async fn handle_unpublish_firmware<F: FirmwarePublisher>(
         // is simply true.
         return UnpublishFirmwareOutcome::NoFirmware;
     }
+    if crate::command_policy::refuses(actor, "UnpublishFirmware") {
+        // Not a synthetic refusal (C5's `RefusalShape::CallResultStatus` doesn't require one
+        // specific variant, only that the answer come through the status field) - a charge point
+        // with no publishing capability genuinely has never published anything, so `NoFirmware`
+        // is simply true.
+        return UnpublishFirmwareOutcome::NoFirmware;
+    }
     if state.is_download_ongoing(checksum) {
         return UnpublishFirmwareOutcome::DownloadOngoing;
     }
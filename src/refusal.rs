@@ -373,6 +373,60 @@ pub fn ocpp_1_6_not_supported(action: &str) -> ocpp_client::ocpp_1_6::OCPP1_6Err
     }
 }
 
+/// Builds the OCPP 2.1 CALLERROR a message with no status field must answer with when the
+/// installed [`crate::command_policy::CommandPolicy`] refuses it: `RpcErrorCode::SecurityError`
+/// rather than `NotSupported`, since the charge point does support the message - it is declining
+/// this one on its operator's behalf. See [`crate::command_policy`]'s module docs.
+#[cfg(feature = "ocpp_2_1")]
+pub fn ocpp_2_1_refused_by_policy(
+    refused: &crate::command_policy::CommandRefused,
+) -> ocpp_client::ocpp_2_1::OCPP2_1Error {
+    use crate::wire::v21::RpcErrorCode;
+    ocpp_client::ocpp_2_1::OCPP2_1Error {
+        code: RpcErrorCode::SecurityError,
+        description: policy_refusal_description(refused),
+        details: Default::default(),
+    }
+}
+
+/// The OCPP 2.0.1 counterpart of [`ocpp_2_1_refused_by_policy`].
+#[cfg(feature = "ocpp_2_0_1")]
+pub fn ocpp_2_0_1_refused_by_policy(
+    refused: &crate::command_policy::CommandRefused,
+) -> ocpp_client::ocpp_2_0_1::OCPP2_0_1Error {
+    use crate::wire::v201::RpcErrorCode;
+    ocpp_client::ocpp_2_0_1::OCPP2_0_1Error {
+        code: RpcErrorCode::SecurityError,
+        description: policy_refusal_description(refused),
+        details: Default::default(),
+    }
+}
+
+/// The OCPP 1.6J counterpart of [`ocpp_2_1_refused_by_policy`].
+#[cfg(feature = "ocpp_1_6")]
+pub fn ocpp_1_6_refused_by_policy(
+    refused: &crate::command_policy::CommandRefused,
+) -> ocpp_client::ocpp_1_6::OCPP1_6Error {
+    use crate::wire::v16::RpcErrorCode;
+    ocpp_client::ocpp_1_6::OCPP1_6Error {
+        code: RpcErrorCode::SecurityError,
+        description: policy_refusal_description(refused),
+        details: Default::default(),
+    }
+}
+
+/// Names the message, not the reason: the reason is the operator's, and goes to the log (see
+/// [`crate::command_policy::screen`]) rather than to whoever sent the command.
+#[cfg(any(feature = "ocpp_2_1", feature = "ocpp_2_0_1", feature = "ocpp_1_6"))]
+fn policy_refusal_description(
+    refused: &crate::command_policy::CommandRefused,
+) -> alloc::string::String {
+    alloc::format!(
+        "{} refused: this charge point's command policy does not allow it",
+        refused.action
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
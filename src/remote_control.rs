@@ -12,7 +12,6 @@ use crate::state::{
 };
 use alloc::boxed::Box;
 use alloc::format;
-use alloc::vec;
 use alloc::vec::Vec;
 
 #[cfg(feature = "ocpp_1_6")]
@@ -86,6 +85,42 @@ pub async fn handle_unlock_request(
     }
 }
 
+/// Same as [`handle_unlock_request`], but additionally reports
+/// [`SecurityEventType::AttemptedReplayAttacks`] when an unlock is refused because the connector
+/// is in a session *and* `guard` recorded an earlier successful unlock of that same connector - an
+/// old `UnlockConnector` replayed against whoever is charging there now. See
+/// [`crate::replay_protection`] for why this key is only trusted in combination with a session
+/// in progress. Never changes the returned outcome.
+#[tracing::instrument(skip_all)]
+pub async fn handle_unlock_request_with_replay_guard(
+    actor: &ChargePointActor,
+    guard: &ReplayGuard<(usize, usize)>,
+    evse_id: usize,
+    connector_id: usize,
+) -> UnlockOutcome {
+    let outcome = handle_unlock_request(actor, evse_id, connector_id).await;
+    match outcome {
+        UnlockOutcome::Unlocked if !guard.contains(&(evse_id, connector_id)) => {
+            guard.record((evse_id, connector_id));
+        }
+        UnlockOutcome::OngoingAuthorizedTransaction if guard.contains(&(evse_id, connector_id)) => {
+            report_security_event(
+                actor,
+                SecurityEvent {
+                    event_type: SecurityEventType::AttemptedReplayAttacks,
+                    tech_info: Some(format!(
+                        "UnlockConnector for EVSE {evse_id} connector {connector_id}, unlocked \
+                         before, repeated during a session"
+                    )),
+                },
+            )
+            .await;
+        }
+        _ => {}
+    }
+    outcome
+}
+
 /// Registers this charge point's inbound `UnlockConnector` handling with the CSMS connection.
 /// Implemented per protocol version (see the `ocpp_2_1` module), mirroring
 /// [`crate::availability::StatusNotifier`] but for an inbound CSMS-initiated call rather than
@@ -93,7 +128,8 @@ pub async fn handle_unlock_request(
 #[async_trait::async_trait]
 pub trait UnlockConnectorHandler {
     /// Registers an `UnlockConnector` handler with the CSMS connection that dispatches incoming
-    /// requests to [`handle_unlock_request`] against `actor`.
+    /// requests to [`handle_unlock_request_with_replay_guard`] against `actor`, keyed on a guard
+    /// private to this registration.
     async fn register_unlock_connector_handler(&self, actor: ChargePointActor);
 }
 
@@ -154,7 +190,7 @@ pub enum RequestStartTransactionOutcome {
     /// first and authorizes afterwards - and carries no `transactionId`, because none exists yet.
     AcceptedPendingAuthorization,
     /// No connector could take the request - see [`find_start_target`] for the conditions
-    /// F01.FR.21-.24 name.
+    /// F01.FR.21-.24 name - or the [`crate::command_policy`] refused the command.
     Rejected,
 }
 
@@ -192,6 +228,9 @@ pub async fn handle_request_start_transaction(
         );
         return RequestStartTransactionOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "RequestStartTransaction") {
+        return RequestStartTransactionOutcome::Rejected;
+    }
     // F01.FR.21-.24 / F02.FR.23-.26 (CV7): the rejection conditions OCPP names, checked
     // explicitly so each produces a `Rejected` for the reason the spec gives rather than as a
     // side effect of no connector happening to be latched.
@@ -475,6 +514,11 @@ fn can_start_here(
     if evse.status != crate::state::EvseStatus::Available {
         return false;
     }
+    // FR.23 for a connector a `Scheduled` change is waiting to take out of service: it already
+    // reports `Unavailable`, and a session started on it would only push the change back.
+    if state.unavailability_pending(evse_id, connector_id) {
+        return false;
+    }
     match evse.connectors[connector_id] {
         // `Reserved` is here because FR.21/.22 are rules about *whose* reservation it is, and they
         // say nothing unless a matching identifier can get as far as the comparison below.
@@ -526,17 +570,60 @@ fn reservation_admits(
 #[async_trait::async_trait]
 pub trait RequestStartTransactionHandler {
     /// Registers a `RequestStartTransaction` handler with the CSMS connection that dispatches
-    /// incoming requests to [`handle_request_start_transaction`] against `actor`.
+    /// incoming requests to [`handle_request_start_transaction_with_replay_guard`] against
+    /// `actor`, keyed on a guard private to this registration.
     async fn register_request_start_transaction_handler(&self, actor: ChargePointActor);
 }
 
+/// Same as [`handle_request_start_transaction`], but additionally reports
+/// [`SecurityEventType::AttemptedReplayAttacks`] when `remote_start_id` is one `guard` already
+/// recorded an accepted start for. The CSMS chooses a fresh `remoteStartId` for every request
+/// (F01.FR.25 quotes it back on the transaction to tell sessions apart), so seeing one twice means
+/// an old request came back. Never changes the returned outcome; a request with no
+/// `remote_start_id` (1.6J) has nothing to key on and is passed straight through.
+#[tracing::instrument(skip_all)]
+pub async fn handle_request_start_transaction_with_replay_guard(
+    actor: &ChargePointActor,
+    guard: &ReplayGuard<i64>,
+    evse_id: Option<usize>,
+    id_token: IdToken,
+    group_id_token: Option<IdToken>,
+    remote_start_id: Option<i64>,
+) -> RequestStartTransactionOutcome {
+    if let Some(remote_start_id) = remote_start_id
+        && guard.contains(&remote_start_id)
+    {
+        report_security_event(
+            actor,
+            SecurityEvent {
+                event_type: SecurityEventType::AttemptedReplayAttacks,
+                tech_info: Some(format!(
+                    "RequestStartTransaction repeated remoteStartId {remote_start_id}"
+                )),
+            },
+        )
+        .await;
+    }
+    let outcome =
+        handle_request_start_transaction(actor, evse_id, id_token, group_id_token, remote_start_id)
+            .await;
+    if let Some(remote_start_id) = remote_start_id
+        && outcome != RequestStartTransactionOutcome::Rejected
+        && !guard.contains(&remote_start_id)
+    {
+        guard.record(remote_start_id);
+    }
+    outcome
+}
+
 /// The outcome of a CSMS-initiated `RequestStopTransaction` request, matching (a subset of)
 /// OCPP's `RequestStartStopStatusEnum`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RequestStopTransactionOutcome {
     /// The transaction was stopped.
     Accepted,
-    /// `transaction_id` was unknown, or its connector isn't currently `Charging`.
+    /// `transaction_id` was unknown, its connector isn't currently `Charging`, or the
+    /// [`crate::command_policy`] refused the command.
     Rejected,
 }
 
@@ -559,6 +646,9 @@ pub async fn handle_request_stop_transaction(
         );
         return RequestStopTransactionOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "RequestStopTransaction") {
+        return RequestStopTransactionOutcome::Rejected;
+    }
     let Some((evse_id, connector_id)) = find_transaction(&state, transaction_id) else {
         tracing::warn!("refusing RequestStopTransaction: no such transaction is running here");
         return RequestStopTransactionOutcome::Rejected;
@@ -600,9 +690,8 @@ fn find_transaction(
 /// Same as [`handle_request_stop_transaction`], but additionally reports
 /// [`SecurityEventType::AttemptedReplayAttacks`] when `transaction_id` is being asked to stop
 /// again after `guard` already recorded a successful stop for it - see
-/// [`crate::replay_protection`] for why `TransactionId` is the one CSMS-initiated remote-control
-/// key this crate currently treats as strong enough evidence of a replay, and why the report
-/// never changes the returned outcome.
+/// [`crate::replay_protection`] for why `TransactionId` is strong enough evidence of a replay,
+/// and why the report never changes the returned outcome.
 #[tracing::instrument(skip_all)]
 pub async fn handle_request_stop_transaction_with_replay_guard(
     actor: &ChargePointActor,
@@ -643,11 +732,12 @@ pub trait RequestStopTransactionHandler {
 
 /// A CSMS-initiated `TriggerMessage` request to (re-)send a specific outbound message - the
 /// subset of OCPP's `MessageTriggerEnumType` this crate can currently fulfil, each backed by an
-/// outbound trait it already has (`HeartbeatSender`, `StatusNotifier`). Everything else
-/// (`BootNotification` - needs hardware vendor/model this module has no access to;
-/// `MeterValues`/`TransactionEvent` - needs a "resend current snapshot" capability neither
-/// functional block has yet; firmware/log/certificate triggers, `CustomTrigger` - no supporting
-/// functional block exists at all, §1/§12) has no internal representation to construct here.
+/// outbound trait it already has (`HeartbeatSender`, `StatusNotifier`) or, for
+/// `TransactionEvent`, by the transaction pipeline itself. Everything else (`BootNotification` -
+/// needs hardware vendor/model this module has no access to; 2.x's standalone `MeterValues` -
+/// needs a "resend the latest reading" capability that functional block does not have yet;
+/// firmware/log/certificate triggers, `CustomTrigger` - no supporting functional block exists at
+/// all, §1/§12) has no internal representation to construct here.
 /// All three versions can now reach this from the network - see
 /// [`TriggerMessageHandler`] and the `ocpp_1_6`/`ocpp_2_0_1`/`ocpp_2_1` adapters below. (An
 /// earlier revision of these docs said the 2.1 wire types did not exist; they do, in the
@@ -660,6 +750,11 @@ pub enum TriggerableMessage {
     /// Re-sends `StatusNotification` for the addressed connector(s) - see
     /// [`crate::availability::AvailabilityTarget`] for what each variant covers.
     StatusNotification(AvailabilityTarget),
+    /// Reports the transaction running on each addressed connector as a
+    /// `TransactionEvent(Updated)` with `triggerReason = Trigger` - on 1.6J, the `MeterValues` its
+    /// `TriggerMessage(MeterValues)` asks for (F06). Sent through the actor rather than a notifier
+    /// so the report takes its `seqNo` from the transaction and queues offline like any other.
+    TransactionEvent(AvailabilityTarget),
 }
 
 /// The outcome of a CSMS-initiated `TriggerMessage` request, matching (a subset of) OCPP's
@@ -670,7 +765,8 @@ pub enum TriggerableMessage {
 pub enum TriggerMessageOutcome {
     /// The charge point attempted to (re-)send the message.
     Accepted,
-    /// The requested message addressed an EVSE/connector that doesn't exist.
+    /// The requested message addressed an EVSE/connector that doesn't exist, or the
+    /// [`crate::command_policy`] refused the command.
     Rejected,
 }
 
@@ -688,6 +784,9 @@ pub async fn handle_trigger_message<N>(
 where
     N: HeartbeatSender + StatusNotifier,
 {
+    if crate::command_policy::refuses(actor, "TriggerMessage") {
+        return TriggerMessageOutcome::Rejected;
+    }
     match message {
         TriggerableMessage::Heartbeat => {
             // The `currentTime` this triggered heartbeat's response carries isn't evaluated for
@@ -703,15 +802,92 @@ where
         TriggerableMessage::StatusNotification(target) => {
             trigger_status_notification(actor, notifier, target).await
         }
+        TriggerableMessage::TransactionEvent(target) => {
+            trigger_transaction_event(actor, target).await
+        }
     }
 }
 
+/// Asks each addressed connector with a transaction to report it. An address with no
+/// transaction on it is still `Accepted`: the charge point did what was asked of it, and there
+/// was nothing to report.
+async fn trigger_transaction_event(
+    actor: &ChargePointActor,
+    target: AvailabilityTarget,
+) -> TriggerMessageOutcome {
+    let state = actor.state();
+    let with_transaction = |evse_id: usize, connector_id: usize| {
+        state
+            .evses
+            .get(evse_id)
+            .and_then(|evse| evse.transactions.get(connector_id))
+            .is_some_and(Option::is_some)
+            .then_some((evse_id, connector_id))
+    };
+    let addressed = match target {
+        AvailabilityTarget::ChargePoint => state
+            .evses
+            .iter()
+            .enumerate()
+            .flat_map(|(evse_id, evse)| {
+                (0..evse.connectors.len()).map(move |connector_id| (evse_id, connector_id))
+            })
+            .filter_map(|(evse_id, connector_id)| with_transaction(evse_id, connector_id))
+            .collect::<Vec<_>>(),
+        AvailabilityTarget::Evse { evse_id } => {
+            let Some(evse) = state.evses.get(evse_id) else {
+                return TriggerMessageOutcome::Rejected;
+            };
+            (0..evse.connectors.len())
+                .filter_map(|connector_id| with_transaction(evse_id, connector_id))
+                .collect()
+        }
+        AvailabilityTarget::Connector {
+            evse_id,
+            connector_id,
+        } => {
+            if state
+                .evses
+                .get(evse_id)
+                .and_then(|evse| evse.connectors.get(connector_id))
+                .is_none()
+            {
+                return TriggerMessageOutcome::Rejected;
+            }
+            with_transaction(evse_id, connector_id)
+                .into_iter()
+                .collect()
+        }
+    };
+
+    for (evse_id, connector_id) in addressed {
+        let _ = actor
+            .send(ChargePointEvent::Evse {
+                evse_id,
+                event: EvseEvent::Connector {
+                    connector_id,
+                    event: ConnectorEvent::TransactionEventTriggered,
+                },
+            })
+            .await;
+    }
+
+    TriggerMessageOutcome::Accepted
+}
+
 async fn trigger_status_notification<N: StatusNotifier>(
     actor: &ChargePointActor,
     notifier: &N,
     target: AvailabilityTarget,
 ) -> TriggerMessageOutcome {
     let state = actor.state();
+    // The status a connector *reports*, which its EVSE and the station can override - the same
+    // figure the CSMS was last told unprompted, so a triggered resend cannot contradict it.
+    let reported = |evse_id: usize, connector_id: usize, connector: ConnectorState| {
+        state
+            .reported_connector_status(evse_id, connector_id)
+            .map(|status| (evse_id, connector_id, status, connector))
+    };
     let addressed = match target {
         AvailabilityTarget::ChargePoint => state
             .evses
@@ -721,13 +897,8 @@ async fn trigger_status_notification<N: StatusNotifier>(
                 evse.connectors
                     .iter()
                     .enumerate()
-                    .map(move |(connector_id, connector)| {
-                        (
-                            evse_id,
-                            connector_id,
-                            connector.availability_status(),
-                            *connector,
-                        )
+                    .filter_map(move |(connector_id, connector)| {
+                        reported(evse_id, connector_id, *connector)
                     })
             })
             .collect::<Vec<_>>(),
@@ -738,14 +909,7 @@ async fn trigger_status_notification<N: StatusNotifier>(
             evse.connectors
                 .iter()
                 .enumerate()
-                .map(|(connector_id, connector)| {
-                    (
-                        evse_id,
-                        connector_id,
-                        connector.availability_status(),
-                        *connector,
-                    )
-                })
+                .filter_map(|(connector_id, connector)| reported(evse_id, connector_id, *connector))
                 .collect()
         }
         AvailabilityTarget::Connector {
@@ -759,12 +923,9 @@ async fn trigger_status_notification<N: StatusNotifier>(
             else {
                 return TriggerMessageOutcome::Rejected;
             };
-            vec![(
-                evse_id,
-                connector_id,
-                connector.availability_status(),
-                *connector,
-            )]
+            reported(evse_id, connector_id, *connector)
+                .into_iter()
+                .collect()
         }
     };
 
@@ -807,8 +968,9 @@ mod tests {
     use super::{
         RequestStartTransactionOutcome, RequestStopTransactionOutcome, TriggerMessageOutcome,
         TriggerableMessage, UnlockOutcome, handle_request_start_transaction,
-        handle_request_stop_transaction, handle_request_stop_transaction_with_replay_guard,
-        handle_trigger_message, handle_unlock_request,
+        handle_request_start_transaction_with_replay_guard, handle_request_stop_transaction,
+        handle_request_stop_transaction_with_replay_guard, handle_trigger_message,
+        handle_unlock_request, handle_unlock_request_with_replay_guard,
     };
     use crate::actor::ChargePointActor;
     use crate::availability::{AvailabilityTarget, StatusNotifier};
@@ -1293,6 +1455,29 @@ mod tests {
         );
     }
 
+    /// G03.FR.05: while a `Scheduled` change waits on one connector's transaction, the idle
+    /// connector beside it is already `Unavailable` to a CSMS start - accepting one would only
+    /// push the change back.
+    #[tokio::test]
+    async fn a_remote_start_inside_a_scheduled_unavailability_is_rejected() {
+        let actor = accepted_actor([2]).await;
+        lock_connector(&actor, 0, 0).await;
+        handle_request_start_transaction(&actor, Some(0), test_id_token(), None, None).await;
+        lock_connector(&actor, 0, 1).await;
+        actor
+            .send(ChargePointEvent::UnavailabilityScheduled {
+                target: crate::state::AvailabilityTarget::Evse { evse_id: 0 },
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            handle_request_start_transaction(&actor, Some(0), test_id_token(), None, None).await,
+            RequestStartTransactionOutcome::Rejected
+        );
+        assert_eq!(actor.state().evses[0].connectors[1], ConnectorState::Locked);
+    }
+
     /// F01.FR.21: a reservation is a promise to one driver. A CSMS start for a *different*
     /// identifier must not break it - while the same identifier is exactly who the connector was
     /// being held for.
@@ -1600,6 +1785,88 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn repeating_a_remote_start_id_reports_a_replay_attempt() {
+        let actor = accepted_actor([1]).await;
+        lock_connector(&actor, 0, 0).await;
+        let mut security_events = actor.subscribe_security_events();
+        let guard = ReplayGuard::new();
+
+        let first = handle_request_start_transaction_with_replay_guard(
+            &actor,
+            &guard,
+            Some(0),
+            test_id_token(),
+            None,
+            Some(7),
+        )
+        .await;
+        assert!(matches!(
+            first,
+            RequestStartTransactionOutcome::Accepted { .. }
+        ));
+
+        let second = handle_request_start_transaction_with_replay_guard(
+            &actor,
+            &guard,
+            Some(0),
+            test_id_token(),
+            None,
+            Some(7),
+        )
+        .await;
+
+        assert_eq!(second, RequestStartTransactionOutcome::Rejected);
+        let event = security_events.recv().await.unwrap();
+        assert_eq!(event.event_type, SecurityEventType::AttemptedReplayAttacks);
+        assert!(event.tech_info.unwrap().contains("remoteStartId 7"));
+    }
+
+    #[tokio::test]
+    async fn an_unlock_refused_during_a_session_reports_a_replay_only_after_an_earlier_unlock() {
+        let actor = locked_actor().await;
+        for event in [
+            ConnectorEvent::IdTokenPresented(test_id_token()),
+            ConnectorEvent::ChargingAuthorized(test_id_token()),
+        ] {
+            actor
+                .send(ChargePointEvent::Evse {
+                    evse_id: 0,
+                    event: EvseEvent::Connector {
+                        connector_id: 0,
+                        event,
+                    },
+                })
+                .await
+                .unwrap();
+        }
+        let mut security_events = actor.subscribe_security_events();
+        let guard = ReplayGuard::new();
+
+        // Nothing was unlocked through this guard yet: an ordinary refusal.
+        assert_eq!(
+            handle_unlock_request_with_replay_guard(&actor, &guard, 0, 0).await,
+            UnlockOutcome::OngoingAuthorizedTransaction
+        );
+        assert!(
+            tokio::time::timeout(
+                core::time::Duration::from_millis(50),
+                security_events.recv()
+            )
+            .await
+            .is_err(),
+            "a first refusal must not be reported as a replay"
+        );
+
+        guard.record((0, 0));
+        assert_eq!(
+            handle_unlock_request_with_replay_guard(&actor, &guard, 0, 0).await,
+            UnlockOutcome::OngoingAuthorizedTransaction
+        );
+        let event = security_events.recv().await.unwrap();
+        assert_eq!(event.event_type, SecurityEventType::AttemptedReplayAttacks);
+    }
+
     type Seen = (u32, Vec<(usize, usize, ConnectorStatus)>);
 
     struct RecordingNotifier {
@@ -1720,6 +1987,90 @@ mod tests {
         );
     }
 
+    /// A resend reports what the CSMS was told unprompted: a connector inside an out-of-service
+    /// EVSE is `Unavailable`, whatever its own state says.
+    #[tokio::test]
+    async fn triggering_a_status_notification_reports_the_status_the_evse_imposes() {
+        let actor = accepted_actor([1]).await;
+        actor
+            .send(ChargePointEvent::Evse {
+                evse_id: 0,
+                event: EvseEvent::SetUnavailable,
+            })
+            .await
+            .expect("the actor accepts events");
+        let (notifier, seen) = RecordingNotifier::new();
+
+        handle_trigger_message(
+            &actor,
+            &notifier,
+            TriggerableMessage::StatusNotification(AvailabilityTarget::Evse { evse_id: 0 }),
+        )
+        .await;
+
+        assert_eq!(
+            seen.borrow().1,
+            alloc::vec![(0, 0, ConnectorStatus::Unavailable)]
+        );
+    }
+
+    /// F06: the report goes through the transaction itself, so it is numbered as the next event
+    /// of that transaction and carries the reason the CSMS will look for.
+    #[tokio::test]
+    async fn triggering_a_transaction_event_reports_the_running_transaction() {
+        let actor = charging_actor().await;
+        let before = actor.state().evses[0].transactions[0]
+            .as_ref()
+            .map(|transaction| transaction.seq_no);
+        let mut events = actor.subscribe_transaction_events();
+        let (notifier, _) = RecordingNotifier::new();
+
+        let outcome = handle_trigger_message(
+            &actor,
+            &notifier,
+            TriggerableMessage::TransactionEvent(AvailabilityTarget::ChargePoint),
+        )
+        .await;
+
+        assert_eq!(outcome, TriggerMessageOutcome::Accepted);
+        let reported = events.recv().await.expect("the trigger is reported");
+        assert_eq!(
+            reported.kind,
+            crate::state::TransactionEventKind::Updated(
+                crate::state::TransactionUpdateReason::Trigger
+            )
+        );
+        assert_eq!(Some(reported.transaction.seq_no), before.map(|seq| seq + 1));
+    }
+
+    #[tokio::test]
+    async fn triggering_a_transaction_event_with_no_transaction_accepts_and_reports_nothing() {
+        let actor = accepted_actor([1]).await;
+        let (notifier, _) = RecordingNotifier::new();
+
+        let outcome = handle_trigger_message(
+            &actor,
+            &notifier,
+            TriggerableMessage::TransactionEvent(AvailabilityTarget::Connector {
+                evse_id: 0,
+                connector_id: 0,
+            }),
+        )
+        .await;
+
+        assert_eq!(outcome, TriggerMessageOutcome::Accepted);
+        assert!(actor.state().evses[0].transactions[0].is_none());
+        assert_eq!(
+            handle_trigger_message(
+                &actor,
+                &notifier,
+                TriggerableMessage::TransactionEvent(AvailabilityTarget::Evse { evse_id: 3 }),
+            )
+            .await,
+            TriggerMessageOutcome::Rejected
+        );
+    }
+
     #[tokio::test]
     async fn triggering_a_status_notification_for_an_unknown_connector_is_rejected() {
         let actor = accepted_actor([1]).await;
@@ -1789,6 +2140,9 @@ mod ocpp_2_1 {
             MessageTriggerEnum::StatusNotification => {
                 Some(TriggerableMessage::StatusNotification(target))
             }
+            MessageTriggerEnum::TransactionEvent => {
+                Some(TriggerableMessage::TransactionEvent(target))
+            }
             _ => None,
         }
     }
@@ -1879,7 +2233,7 @@ mod ocpp_2_1 {
         }
 
         #[test]
-        fn the_two_messages_this_crate_can_resend_map_and_the_rest_are_not_implemented() {
+        fn the_three_messages_this_crate_can_resend_map_and_the_rest_are_not_implemented() {
             assert_eq!(
                 triggerable_message(
                     &MessageTriggerEnum::Heartbeat,
@@ -1896,13 +2250,21 @@ mod ocpp_2_1 {
                     AvailabilityTarget::Evse { evse_id: 0 }
                 ))
             );
+            assert_eq!(
+                triggerable_message(
+                    &MessageTriggerEnum::TransactionEvent,
+                    AvailabilityTarget::Evse { evse_id: 0 }
+                ),
+                Some(TriggerableMessage::TransactionEvent(
+                    AvailabilityTarget::Evse { evse_id: 0 }
+                ))
+            );
             // Everything else needs a functional block this crate doesn't have. Reported as
             // NotImplemented rather than Rejected, which would claim the request was understood
             // and refused.
             for requested in [
                 MessageTriggerEnum::BootNotification,
                 MessageTriggerEnum::MeterValues,
-                MessageTriggerEnum::TransactionEvent,
                 MessageTriggerEnum::FirmwareStatusNotification,
                 MessageTriggerEnum::LogStatusNotification,
             ] {
@@ -1917,8 +2279,8 @@ mod ocpp_2_1 {
     use super::{
         RequestStartTransactionHandler, RequestStartTransactionOutcome,
         RequestStopTransactionHandler, RequestStopTransactionOutcome, UnlockConnectorHandler,
-        UnlockOutcome, handle_request_start_transaction,
-        handle_request_stop_transaction_with_replay_guard, handle_unlock_request,
+        UnlockOutcome, handle_request_start_transaction_with_replay_guard,
+        handle_request_stop_transaction_with_replay_guard, handle_unlock_request_with_replay_guard,
     };
     // Only used by the `std`-gated `impl TriggerMessageHandler for OCPP2_1Client` above (see its
     // cfg for why) and by `trigger_message_tests`.
@@ -1994,12 +2356,25 @@ mod ocpp_2_1 {
     #[async_trait::async_trait]
     impl UnlockConnectorHandler for OCPP2_1Client {
         async fn register_unlock_connector_handler(&self, actor: ChargePointActor) {
+            let guard = Arc::new(ReplayGuard::new());
             self.on_unlock_connector(move |request, _client| {
                 let actor = actor.clone();
+                let guard = guard.clone();
                 async move {
                     let outcome = match connector_address(&request) {
+                        // Screened here rather than in `handle_unlock_request`, which a local technician's
+                        // maintenance session also drives.
+                        _ if crate::command_policy::refuses(&actor, "UnlockConnector") => {
+                            UnlockOutcome::UnlockFailed
+                        }
                         Some((evse_id, connector_id)) => {
-                            handle_unlock_request(&actor, evse_id, connector_id).await
+                            handle_unlock_request_with_replay_guard(
+                                &actor,
+                                &guard,
+                                evse_id,
+                                connector_id,
+                            )
+                            .await
                         }
                         None => UnlockOutcome::UnknownConnector,
                     };
@@ -2051,13 +2426,16 @@ mod ocpp_2_1 {
     #[async_trait::async_trait]
     impl RequestStartTransactionHandler for OCPP2_1Client {
         async fn register_request_start_transaction_handler(&self, actor: ChargePointActor) {
+            let guard = Arc::new(ReplayGuard::new());
             self.on_request_start_transaction(move |request, _client| {
                 let actor = actor.clone();
+                let guard = guard.clone();
                 async move {
                     let outcome = match parse_evse_id(&request) {
                         Ok(evse_id) => {
-                            handle_request_start_transaction(
+                            handle_request_start_transaction_with_replay_guard(
                                 &actor,
+                                &guard,
                                 evse_id,
                                 map_id_token(&request.id_token),
                                 // F01.FR.22: consulted only against a reservation held for a
@@ -2318,6 +2696,9 @@ pub(crate) mod ocpp_2_0_1 {
             MessageTriggerEnum::StatusNotification => {
                 Some(TriggerableMessage::StatusNotification(target))
             }
+            MessageTriggerEnum::TransactionEvent => {
+                Some(TriggerableMessage::TransactionEvent(target))
+            }
             _ => None,
         }
     }
@@ -2405,7 +2786,7 @@ pub(crate) mod ocpp_2_0_1 {
         }
 
         #[test]
-        fn the_two_messages_this_crate_can_resend_map_and_the_rest_are_not_implemented() {
+        fn the_three_messages_this_crate_can_resend_map_and_the_rest_are_not_implemented() {
             assert_eq!(
                 triggerable_message(
                     &MessageTriggerEnum::Heartbeat,
@@ -2422,13 +2803,21 @@ pub(crate) mod ocpp_2_0_1 {
                     AvailabilityTarget::Evse { evse_id: 0 }
                 ))
             );
+            assert_eq!(
+                triggerable_message(
+                    &MessageTriggerEnum::TransactionEvent,
+                    AvailabilityTarget::Evse { evse_id: 0 }
+                ),
+                Some(TriggerableMessage::TransactionEvent(
+                    AvailabilityTarget::Evse { evse_id: 0 }
+                ))
+            );
             // Everything else needs a functional block this crate doesn't have. Reported as
             // NotImplemented rather than Rejected, which would claim the request was understood
             // and refused.
             for requested in [
                 MessageTriggerEnum::BootNotification,
                 MessageTriggerEnum::MeterValues,
-                MessageTriggerEnum::TransactionEvent,
                 MessageTriggerEnum::FirmwareStatusNotification,
                 MessageTriggerEnum::LogStatusNotification,
             ] {
@@ -2443,8 +2832,8 @@ pub(crate) mod ocpp_2_0_1 {
     use super::{
         RequestStartTransactionHandler, RequestStartTransactionOutcome,
         RequestStopTransactionHandler, RequestStopTransactionOutcome, UnlockConnectorHandler,
-        UnlockOutcome, handle_request_start_transaction,
-        handle_request_stop_transaction_with_replay_guard, handle_unlock_request,
+        UnlockOutcome, handle_request_start_transaction_with_replay_guard,
+        handle_request_stop_transaction_with_replay_guard, handle_unlock_request_with_replay_guard,
     };
     // Only used by the `std`-gated `impl TriggerMessageHandler for OCPP2_0_1Client` above (see
     // its cfg for why) and by `trigger_message_tests`.
@@ -2516,12 +2905,25 @@ pub(crate) mod ocpp_2_0_1 {
     #[async_trait::async_trait]
     impl UnlockConnectorHandler for OCPP2_0_1Client {
         async fn register_unlock_connector_handler(&self, actor: ChargePointActor) {
+            let guard = Arc::new(ReplayGuard::new());
             self.on_unlock_connector(move |request, _client| {
                 let actor = actor.clone();
+                let guard = guard.clone();
                 async move {
                     let outcome = match connector_address(&request) {
+                        // Screened here rather than in `handle_unlock_request`, which a local technician's
+                        // maintenance session also drives.
+                        _ if crate::command_policy::refuses(&actor, "UnlockConnector") => {
+                            UnlockOutcome::UnlockFailed
+                        }
                         Some((evse_id, connector_id)) => {
-                            handle_unlock_request(&actor, evse_id, connector_id).await
+                            handle_unlock_request_with_replay_guard(
+                                &actor,
+                                &guard,
+                                evse_id,
+                                connector_id,
+                            )
+                            .await
                         }
                         None => UnlockOutcome::UnknownConnector,
                     };
@@ -2569,13 +2971,16 @@ pub(crate) mod ocpp_2_0_1 {
     #[async_trait::async_trait]
     impl RequestStartTransactionHandler for OCPP2_0_1Client {
         async fn register_request_start_transaction_handler(&self, actor: ChargePointActor) {
+            let guard = Arc::new(ReplayGuard::new());
             self.on_request_start_transaction(move |request, _client| {
                 let actor = actor.clone();
+                let guard = guard.clone();
                 async move {
                     let outcome = match parse_evse_id(&request) {
                         Ok(evse_id) => {
-                            handle_request_start_transaction(
+                            handle_request_start_transaction_with_replay_guard(
                                 &actor,
+                                &guard,
                                 evse_id,
                                 map_id_token(&request.id_token),
                                 // F01.FR.22: consulted only against a reservation held for a
@@ -2819,7 +3224,7 @@ mod ocpp_1_6 {
         RequestStopTransactionOutcome, TriggerMessageHandler, TriggerMessageOutcome,
         TriggerableMessage, UnlockConnectorHandler, UnlockOutcome,
         handle_request_start_transaction, handle_request_stop_transaction_with_replay_guard,
-        handle_trigger_message, handle_unlock_request,
+        handle_trigger_message, handle_unlock_request_with_replay_guard,
     };
     use crate::actor::ChargePointActor;
     use crate::availability::{AvailabilityTarget, Ocpp1_6StatusNotifier};
@@ -2877,7 +3282,8 @@ mod ocpp_1_6 {
     /// 1.6J's `requestedMessage` onto this crate's [`TriggerableMessage`], or `None` for one no
     /// functional block here can fulfil - reported as `NotImplemented`, exactly as the 2.x
     /// adapters do. 1.6J's enum is smaller (six values, with `DiagnosticsStatusNotification` where
-    /// 2.x has the log/certificate triggers), but the two this crate can answer are the same two.
+    /// 2.x has the log/certificate triggers), but the three this crate can answer are the same
+    /// three - `MeterValues` standing in for the `TransactionEvent` 1.6J does not have.
     fn triggerable_message(
         requested: &RequestedMessage,
         target: AvailabilityTarget,
@@ -2887,6 +3293,9 @@ mod ocpp_1_6 {
             RequestedMessage::StatusNotification => {
                 Some(TriggerableMessage::StatusNotification(target))
             }
+            // 1.6J has no `TransactionEvent`; a transaction's state reaches its CSMS as
+            // `MeterValues`, which is what a triggered report projects to there.
+            RequestedMessage::MeterValues => Some(TriggerableMessage::TransactionEvent(target)),
             _ => None,
         }
     }
@@ -2897,8 +3306,8 @@ mod ocpp_1_6 {
     /// `TriggerMessage`. `ExtendedTriggerMessageRequestRequestedMessage` is a different wire enum
     /// from `TriggerMessageRequestRequestedMessage` (it adds `LogStatusNotification` and
     /// `SignChargePointCertificate`, and drops `DiagnosticsStatusNotification`), so this is its
-    /// own match rather than a shared one - but the two values this crate can actually fulfil are
-    /// the same two, and the Rust type system already keeps a value valid for one action from
+    /// own match rather than a shared one - but the three values this crate can actually fulfil
+    /// are the same three, and the Rust type system already keeps a value valid for one action from
     /// ever reaching the other's handler.
     fn extended_triggerable_message(
         requested: &ExtendedRequestedMessage,
@@ -2909,6 +3318,9 @@ mod ocpp_1_6 {
             ExtendedRequestedMessage::StatusNotification => {
                 Some(TriggerableMessage::StatusNotification(target))
             }
+            ExtendedRequestedMessage::MeterValues => {
+                Some(TriggerableMessage::TransactionEvent(target))
+            }
             _ => None,
         }
     }
@@ -3116,7 +3528,7 @@ mod ocpp_1_6 {
         }
 
         #[test]
-        fn the_two_messages_this_crate_can_resend_map_and_the_rest_are_not_implemented() {
+        fn the_three_messages_this_crate_can_resend_map_and_the_rest_are_not_implemented() {
             assert_eq!(
                 triggerable_message(
                     &RequestedMessage::Heartbeat,
@@ -3133,11 +3545,19 @@ mod ocpp_1_6 {
                     AvailabilityTarget::ChargePoint
                 ))
             );
+            assert_eq!(
+                triggerable_message(
+                    &RequestedMessage::MeterValues,
+                    AvailabilityTarget::ChargePoint
+                ),
+                Some(TriggerableMessage::TransactionEvent(
+                    AvailabilityTarget::ChargePoint
+                ))
+            );
             for requested in [
                 RequestedMessage::BootNotification,
                 RequestedMessage::DiagnosticsStatusNotification,
                 RequestedMessage::FirmwareStatusNotification,
-                RequestedMessage::MeterValues,
             ] {
                 assert_eq!(
                     triggerable_message(&requested, AvailabilityTarget::ChargePoint),
@@ -3147,7 +3567,7 @@ mod ocpp_1_6 {
         }
 
         #[test]
-        fn extended_trigger_message_maps_the_same_two_messages_and_rejects_the_rest() {
+        fn extended_trigger_message_maps_the_same_three_messages_and_rejects_the_rest() {
             assert_eq!(
                 extended_triggerable_message(
                     &ExtendedRequestedMessage::Heartbeat,
@@ -3164,6 +3584,15 @@ mod ocpp_1_6 {
                     AvailabilityTarget::ChargePoint
                 ))
             );
+            assert_eq!(
+                extended_triggerable_message(
+                    &ExtendedRequestedMessage::MeterValues,
+                    AvailabilityTarget::ChargePoint
+                ),
+                Some(TriggerableMessage::TransactionEvent(
+                    AvailabilityTarget::ChargePoint
+                ))
+            );
             // Distinct from `TriggerMessage`'s unsupported set: `ExtendedTriggerMessage` adds
             // `LogStatusNotification` and `SignChargePointCertificate`, and has no
             // `DiagnosticsStatusNotification` value at all - the two wire enums are different
@@ -3172,7 +3601,6 @@ mod ocpp_1_6 {
                 ExtendedRequestedMessage::BootNotification,
                 ExtendedRequestedMessage::LogStatusNotification,
                 ExtendedRequestedMessage::FirmwareStatusNotification,
-                ExtendedRequestedMessage::MeterValues,
                 ExtendedRequestedMessage::SignChargePointCertificate,
             ] {
                 assert_eq!(
@@ -3266,17 +3694,30 @@ mod ocpp_1_6 {
     impl UnlockConnectorHandler for Ocpp1_6RemoteControlHandler {
         async fn register_unlock_connector_handler(&self, actor: ChargePointActor) {
             let connector_counts = self.connector_counts.clone();
+            let guard = Arc::new(ReplayGuard::new());
             self.client
                 .on_unlock_connector(move |request, _client| {
                     let actor = actor.clone();
                     let connector_counts = connector_counts.clone();
+                    let guard = guard.clone();
                     async move {
                         let outcome = match unflatten_ocpp_1_6_connector_id(
                             &connector_counts,
                             request.connector_id,
                         ) {
+                            // Screened here rather than in `handle_unlock_request`, which a local technician's
+                            // maintenance session also drives.
+                            _ if crate::command_policy::refuses(&actor, "UnlockConnector") => {
+                                UnlockOutcome::UnlockFailed
+                            }
                             Some((evse_id, connector_id)) => {
-                                handle_unlock_request(&actor, evse_id, connector_id).await
+                                handle_unlock_request_with_replay_guard(
+                                    &actor,
+                                    &guard,
+                                    evse_id,
+                                    connector_id,
+                                )
+                                .await
                             }
                             None => UnlockOutcome::UnknownConnector,
                         };
@@ -27,11 +27,14 @@
 //!    whatever handler is registered for its action *unconditionally* - there is no tracking of
 //!    message ids already seen for incoming calls at all. Worse: `Client::on`'s callback
 //!    signature (`FnMut(A::Request, Client) -> Future<Output = Result<A::Response, E>>`) never
-//!    hands the registered handler the message id in the first place. That means this crate has
-//!    no way to see, and therefore no way to deduplicate, incoming CALL message ids through
-//!    `ocpp-client`'s current public API - full stop. **This is a gap that belongs in
-//!    `ocpp-client`** (it owns CALL/CALLRESULT correlation per `CLAUDE.md`), not something this
-//!    module can fake from above it. It is called out here rather than silently left uncovered.
+//!    hands the registered handler the message id in the first place. No handler can see it, so
+//!    nothing in this module can deduplicate on it. [`crate::call_dedup`] does, one layer down:
+//!    it wraps the transport beneath the client, where both a CALL's id and the response to it
+//!    are visible, and answers a repeated id from cache instead of letting it through. That
+//!    covers every redial, the first connection when `crate::connect_and_setup` offers exactly
+//!    one version, and any connection an integrator builds over their own transport - but not a
+//!    first connection `ocpp_client::connect` negotiates, which offers no hook (see that
+//!    module's docs).
 //! 4. **Replayed authorization/transaction *content*, independent of message id** - the case
 //!    this module actually addresses. A CSMS-initiated command whose effect is state-gated (it
 //!    only does something the *first* time, because the precondition state it needs has since
@@ -46,24 +49,43 @@
 //! [`crate::offline_queue::OfflineQueue`] resending its own contents after a reconnect is
 //! legitimate, expected replay at the transport-retry level, not an attack - and it is
 //! structurally impossible for it to reach this module's detection, because
-//! [`ReplayGuard`] is wired only into *inbound*, CSMS-initiated remote-control handlers
-//! (`RequestStopTransaction` today); the offline queue exists solely on the *outbound* reporting
-//! path (`TransactionEvent`, `SecurityEventNotification`, ...) and never touches it.
+//! every check below is wired only into *inbound*, CSMS-initiated command handlers; the offline
+//! queue exists solely on the *outbound* reporting path (`TransactionEvent`,
+//! `SecurityEventNotification`, ...) and never touches them.
 //!
-//! # Why `RequestStopTransaction`, specifically
+//! # What is guarded, and on which key
 //!
-//! The same technique - "did we already record success for this signature?" - was considered
-//! for `UnlockConnector` (keyed on `(evse_id, connector_id)`) and `RequestStartTransaction`
-//! (keyed on `(evse_id, idToken)`), but both keys can legitimately recur: a connector gets
-//! plugged, unlocked, and plugged in again; a fleet idToken starts a session on the same EVSE
-//! on a later, unrelated day. Reusing either key would report a normal repeat command as
-//! `AttemptedReplayAttacks`. [`crate::state::TransactionId`] has no such recurrence: it is a
-//! strictly monotonic counter that is never reused, even across a restart (see
-//! `recovery_restores_the_transaction_id_counter_so_ids_are_never_reused` in
-//! `src/state/charge_point_state.rs`), so "the CSMS asked to stop a transaction id that already
-//! stopped" is a much cleaner replay signal than either of the other two. This module still
-//! provides the generic [`ReplayGuard`] primitive so a future case with an equally
-//! non-recurring key can reuse it.
+//! Each check asks "did we already record success for this signature?", and is only as good as
+//! its key's refusal to recur legitimately:
+//!
+//! - **`RequestStopTransaction`**, keyed on [`crate::state::TransactionId`] - a strictly
+//!   monotonic counter that is never reused, even across a restart (see
+//!   `recovery_restores_the_transaction_id_counter_so_ids_are_never_reused` in
+//!   `src/state/charge_point_state.rs`). Reported when an already-stopped id is asked to stop
+//!   again. See [`crate::remote_control::handle_request_stop_transaction_with_replay_guard`].
+//! - **`RequestStartTransaction`**, keyed on `remoteStartId`, which the CSMS picks fresh for every
+//!   request so it can tell the resulting sessions apart (F01.FR.25). `(evse_id, idToken)` was
+//!   considered and rejected: a fleet token starts on the same EVSE again on an unrelated day.
+//!   1.6J carries no such id and is not guarded. See
+//!   [`crate::remote_control::handle_request_start_transaction_with_replay_guard`].
+//! - **`ReserveNow`**, keyed on the reservation id once a cable has honoured it -
+//!   [`crate::state::ChargePointState::consumed_reservations`]. Before that, the same id again is
+//!   a CSMS updating a reservation it still holds, not a replay. See
+//!   [`crate::reservation::handle_reserve_now`].
+//! - **`UnlockConnector`**, keyed on `(evse_id, connector_id)`, but only trusted when the unlock
+//!   is refused because a session is in progress there. A connector is unlocked after every
+//!   session, so the key alone recurs constantly; an earlier unlock of that connector arriving
+//!   again *while someone else is charging on it* is the case worth reporting. See
+//!   [`crate::remote_control::handle_unlock_request_with_replay_guard`].
+//! - **`CertificateSigned`**, keyed on a fingerprint of the delivered chain. A CSMS has no reason
+//!   to sign the same CSR twice, and every new CSR is over a fresh key. See
+//!   [`crate::certificates::handle_certificate_signed`].
+//! - **`UpdateFirmware`**, keyed on the `requestId` of an update that reached `Installed`. See
+//!   [`crate::firmware::handle_update_firmware`].
+//!
+//! Every guard is bounded - [`ReplayGuard`] by [`DEFAULT_REPLAY_GUARD_CAPACITY`], the consumed
+//! reservations by [`crate::state::MAX_CONSUMED_RESERVATIONS`] - and is lost on a restart, so a
+//! replay of something older than either goes unreported.
 //!
 //! # False positives are accepted; false rejections are not
 //!
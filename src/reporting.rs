@@ -138,9 +138,9 @@ pub enum ReportOutcome {
     /// [`ReportBase::SummaryInventory`]) without that meaning failure - see
     /// [`handle_get_base_report`]'s docs on why that case is `Accepted`, not `EmptyResultSet`.
     Accepted(Vec<ReportEntry>),
-    /// The charge point is temporarily unable to answer. Never produced by this crate's own
-    /// handlers today (a device model lookup can't fail) - modeled for wire completeness, so a
-    /// future hardware binding could report a real transient failure through this path.
+    /// The charge point will not answer. Produced by this crate's own handlers only when the
+    /// [command policy](crate::command_policy) refuses the request - a device model lookup can't
+    /// fail.
     Rejected,
     /// The requested report base/criteria aren't supported. Never produced by
     /// [`handle_get_base_report`] (this crate's [`ReportBase`] already covers all 3 values OCPP
@@ -188,13 +188,17 @@ fn to_report_entry(
 }
 
 /// Handles a CSMS-initiated `GetBaseReport` request against `actor`'s current device model.
-/// Always `Accepted` (possibly with zero entries, e.g. an unpopulated `SummaryInventory`) - all
-/// three `ReportBase` values OCPP defines are supported unconditionally, and a device model lookup
-/// can't fail, so `Rejected`/`NotSupported` are unreachable here (see [`ReportOutcome`]'s docs).
+/// `Accepted` (possibly with zero entries, e.g. an unpopulated `SummaryInventory`) unless the
+/// [command policy](crate::command_policy) refuses it - all three `ReportBase` values OCPP defines
+/// are supported unconditionally, and a device model lookup can't fail, so `NotSupported` is
+/// unreachable here (see [`ReportOutcome`]'s docs).
 /// Unlike [`handle_get_report`], an empty result is still `Accepted`, never `EmptyResultSet` - OCPP
 /// defines `EmptyResultSet` only for a *filtered* request producing nothing to report, and
 /// `GetBaseReport` takes no filter at all.
 pub fn handle_get_base_report(actor: &ChargePointActor, base: ReportBase) -> ReportOutcome {
+    if crate::command_policy::refuses(actor, "GetBaseReport") {
+        return ReportOutcome::Rejected;
+    }
     let state = actor.state();
     ReportOutcome::Accepted(report_base_entries(&state.device_model, base))
 }
@@ -263,12 +267,16 @@ fn filter_report_entries(
 /// Handles a CSMS-initiated `GetReport` request against `actor`'s current device model, applying
 /// `criteria`/`component_variable`, combined with OR semantics. `EmptyResultSet` if the
 /// combination matches nothing at all - the one case where this differs from
-/// [`handle_get_base_report`] (which never produces it).
+/// [`handle_get_base_report`] (which never produces it). `Rejected` if the
+/// [command policy](crate::command_policy) refuses it.
 pub fn handle_get_report(
     actor: &ChargePointActor,
     criteria: &[ReportComponentCriterion],
     component_variable: &[ReportComponentVariable],
 ) -> ReportOutcome {
+    if crate::command_policy::refuses(actor, "GetReport") {
+        return ReportOutcome::Rejected;
+    }
     let state = actor.state();
     let entries = filter_report_entries(&state.device_model, criteria, component_variable);
     if entries.is_empty() {
@@ -1362,9 +1370,11 @@ mod ocpp_2_1 {
                                 &request,
                             ) {
                                 return Err(crate::message_limits::ocpp_2_1_too_large(
+                                    &actor,
                                     "GetReport",
                                     violation,
-                                ));
+                                )
+                                .await);
                             }
                             let criteria: Vec<_> = request
                                 .component_criteria
@@ -1443,9 +1453,11 @@ mod ocpp_2_1 {
                             &request,
                         ) {
                             return Err(crate::message_limits::ocpp_2_1_too_large(
+                                &actor,
                                 "GetReport",
                                 violation,
-                            ));
+                            )
+                            .await);
                         }
                         let criteria: Vec<_> = request
                             .component_criteria
@@ -1915,9 +1927,11 @@ mod ocpp_2_0_1 {
                                 &request,
                             ) {
                                 return Err(crate::message_limits::ocpp_2_0_1_too_large(
+                                    &actor,
                                     "GetReport",
                                     violation,
-                                ));
+                                )
+                                .await);
                             }
                             let criteria: Vec<_> = request
                                 .component_criteria
@@ -1996,9 +2010,11 @@ mod ocpp_2_0_1 {
                             &request,
                         ) {
                             return Err(crate::message_limits::ocpp_2_0_1_too_large(
+                                &actor,
                                 "GetReport",
                                 violation,
-                            ));
+                            )
+                            .await);
                         }
                         let criteria: Vec<_> = request
                             .component_criteria
@@ -12,11 +12,13 @@
 
 use crate::actor::ChargePointActor;
 use crate::clock::Clock;
+use crate::security::report_security_event;
 use crate::state::{
     ChargePointEvent, ChargePointState, ConnectorEvent, ConnectorState, EvseEvent, IdToken,
-    Reservation, ReservationId, ReservationUpdate,
+    Reservation, ReservationId, ReservationUpdate, SecurityEvent, SecurityEventType,
 };
 use alloc::boxed::Box;
+use alloc::format;
 use chrono::{DateTime, Utc};
 
 #[cfg(feature = "ocpp_1_6")]
@@ -82,6 +84,11 @@ pub enum ReserveNowOutcome {
 /// `parse_expiry_date_time` (private to this module). `None` means a reservation that never expires, per
 /// [`Reservation`]'s docs; it no longer doubles as "the CSMS sent something unparseable", which
 /// the wire types now rule out.
+///
+/// A `reservation_id` already in [`ChargePointState::consumed_reservations`] names a reservation
+/// a cable has since honoured, and is additionally reported as
+/// [`SecurityEventType::AttemptedReplayAttacks`] - see [`crate::replay_protection`]. The request
+/// is still decided exactly as it would have been without the report.
 #[tracing::instrument(skip_all, fields(reservation_id = reservation_id.0, evse_id))]
 pub async fn handle_reserve_now(
     actor: &ChargePointActor,
@@ -92,6 +99,19 @@ pub async fn handle_reserve_now(
     expires_at: Option<DateTime<Utc>>,
 ) -> ReserveNowOutcome {
     let state = actor.state();
+    if state.consumed_reservations.contains(&reservation_id) {
+        report_security_event(
+            actor,
+            SecurityEvent {
+                event_type: SecurityEventType::AttemptedReplayAttacks,
+                tech_info: Some(format!(
+                    "ReserveNow repeated reservation {} after it was honoured",
+                    reservation_id.0
+                )),
+            },
+        )
+        .await;
+    }
     // C5 (docs/PRODUCTION-ROADMAP.md §5.5): the handler is registered whenever the
     // `reservation` Cargo feature is on, but the hardware may still declare the capability
     // runtime-absent - refuse via the same `Rejected` status a wire-level rejection already
@@ -99,6 +119,9 @@ pub async fn handle_reserve_now(
     if !crate::refusal::capability_present(&state.capabilities, "ReserveNow") {
         return ReserveNowOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "ReserveNow") {
+        return ReserveNowOutcome::Rejected;
+    }
     let Some(considered) = considered_connectors(&state, evse_id) else {
         return ReserveNowOutcome::Rejected;
     };
@@ -218,6 +241,9 @@ pub async fn handle_cancel_reservation(
     if !crate::refusal::capability_present(&state.capabilities, "CancelReservation") {
         return CancelReservationOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "CancelReservation") {
+        return CancelReservationOutcome::Rejected;
+    }
     let Some((evse_id, connector_id)) = find_reservation(&state, reservation_id) else {
         tracing::warn!("refusing CancelReservation: no such reservation is held here");
         return CancelReservationOutcome::Rejected;
@@ -383,7 +409,7 @@ mod tests {
     use crate::hardware::Capabilities;
     use crate::state::{
         ChargePointEvent, ConnectorEvent, ConnectorState, EvseEvent, IdToken, IdTokenKind,
-        ReservationId,
+        ReservationId, SecurityEventType,
     };
 
     fn test_id_token() -> IdToken {
@@ -812,6 +838,43 @@ mod tests {
     // `Rejected` status a normal CALLRESULT already uses, per `crate::refusal`'s decision table -
     // never let a `NotImplemented`/generic error leak out of a handler that's actually registered.
 
+    #[tokio::test]
+    async fn reserving_an_honoured_reservation_again_reports_a_replay_attempt() {
+        let actor = spawn_with_reservation([1]).await;
+        let mut security_events = actor.subscribe_security_events();
+        reserve_until(&actor, 7, None).await;
+        actor
+            .send(ChargePointEvent::Evse {
+                evse_id: 0,
+                event: EvseEvent::Connector {
+                    connector_id: 0,
+                    event: ConnectorEvent::CableConnected,
+                },
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            actor.state().consumed_reservations,
+            [ReservationId(7)],
+            "the cable arriving honours the reservation"
+        );
+
+        let outcome = handle_reserve_now(
+            &actor,
+            Some(0),
+            ReservationId(7),
+            test_id_token(),
+            None,
+            None,
+        )
+        .await;
+
+        assert_eq!(outcome, ReserveNowOutcome::Occupied);
+        let event = security_events.recv().await.unwrap();
+        assert_eq!(event.event_type, SecurityEventType::AttemptedReplayAttacks);
+        assert!(event.tech_info.unwrap().contains("reservation 7"));
+    }
+
     #[tokio::test]
     async fn reserve_now_is_rejected_when_the_reservation_capability_is_absent() {
         let actor = ChargePointActor::spawn([1], &TokioExecutor);
@@ -10,6 +10,7 @@
 //! `ocpp_2_1`/`ocpp_2_0_1`/`ocpp_1_6` submodules).
 
 use crate::actor::ChargePointActor;
+use crate::command_policy::InboundCommand;
 use crate::state::{ChargePointEvent, ChargePointState, ResetKind, ResetTarget};
 use alloc::boxed::Box;
 
@@ -52,6 +53,9 @@ pub async fn handle_reset(
     if !target_exists(&actor.state(), target) {
         return ResetOutcome::Rejected;
     }
+    if crate::command_policy::screen(actor, &InboundCommand::Reset { target, kind }).is_err() {
+        return ResetOutcome::Rejected;
+    }
 
     if let Some(recorder) = actor.boot_reason_recorder() {
         recorder(kind).await;
@@ -6,7 +6,7 @@ use crate::executor::Executor;
 use crate::hardware::{HardwareCommandReceiver, HardwareEventSender};
 use crate::provisioning::{Backoff, BootNotificationOutcome, BootNotifier};
 use crate::state::{
-    AuthorizationRequested, BootReasonCause, ChargePointEvent, ChargePointState,
+    AuthorizationRequested, BootReasonCause, ChargePointEvent, ChargePointState, ConnectorStatus,
     ConnectorStatusChanged, RegistrationStatus, SecurityEvent, TransactionEventOccurred,
 };
 use crate::sync::{BroadcastReceiver, WatchReceiver};
@@ -124,6 +124,13 @@ impl<T> ChargePointRuntime<T> {
         self.actor.subscribe_status_notifications()
     }
 
+    /// Subscribes to changes in the charge point's own status, which 1.6J reports on
+    /// `connectorId` 0. Subscribe before starting the hardware, for the same reason as
+    /// [`Self::subscribe_status_notifications`].
+    pub fn subscribe_station_status_notifications(&self) -> BroadcastReceiver<ConnectorStatus> {
+        self.actor.subscribe_station_status_notifications()
+    }
+
     /// Subscribes to transaction lifecycle events for the Transactions functional block.
     /// Subscribe before starting the hardware, for the same reason as
     /// [`Self::subscribe_status_notifications`].
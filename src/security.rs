@@ -163,6 +163,10 @@ mod wire_type_tests {
 /// (G2.2/G2.3) for the bounded-memory stance this follows.
 pub const DEFAULT_SECURITY_LOG_CAPACITY: usize = 50;
 
+/// What [`SecurityLogEntry::stored_size`] charges every entry on top of its variable-length text:
+/// the persisted record's field names, its timestamp, and the event type's name, rounded up.
+const SECURITY_LOG_ENTRY_OVERHEAD: usize = 128;
+
 /// One recorded security event, with the time it was recorded at.
 ///
 /// `recorded_at` is stamped by the [`Clock`](crate::clock::Clock) supplied to
@@ -185,6 +189,26 @@ pub struct SecurityLogEntry {
     pub recorded_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
+impl SecurityLogEntry {
+    /// Roughly how many bytes this entry occupies once persisted - what
+    /// [`SecurityEventLog::with_max_bytes`] budgets against. An overestimate by design: the
+    /// budget exists to keep the snapshot inside a flash partition, where running over is the
+    /// failure and running a little under is not.
+    pub fn stored_size(&self) -> usize {
+        let vendor_type = match &self.event.event_type {
+            SecurityEventType::Other(value) => value.len(),
+            _ => 0,
+        };
+        SECURITY_LOG_ENTRY_OVERHEAD
+            + vendor_type
+            + self
+                .event
+                .tech_info
+                .as_ref()
+                .map_or(0, alloc::string::String::len)
+    }
+}
+
 /// The charge point's security log: a bounded, oldest-first record of the security events raised
 /// on this charge point, independent of whether each one reached the CSMS.
 ///
@@ -208,6 +232,12 @@ pub struct SecurityLogEntry {
 /// nothing about whatever came after it. The evicted entry is handed back from [`Self::record`] so
 /// the caller can log that an audit-trail entry was lost.
 ///
+/// A count bound alone does not bound the snapshot [`crate::persistence::SecurityLogStore`]
+/// writes, because `techInfo` is free text of up to 255 bytes: fifty one-word entries and fifty
+/// full ones differ by more than ten times. [`Self::with_max_bytes`] adds a size bound on top, and
+/// entries rotate out oldest-first once *either* is reached - so the retention an integrator
+/// configures is whichever of "this many events" and "this much flash" comes first.
+///
 /// Durability is a separate concern layered on top - see
 /// [`crate::persistence::SecurityLogStore`]. This type is pure in-memory state and has no
 /// [`crate::hardware::Storage`] dependency of its own, so a charge point without durable storage
@@ -219,6 +249,7 @@ pub struct SecurityLogEntry {
 pub struct SecurityEventLog {
     entries: BlockingMutex<CriticalSectionRawMutex, RefCell<VecDeque<SecurityLogEntry>>>,
     capacity: usize,
+    max_bytes: Option<usize>,
 }
 
 impl SecurityEventLog {
@@ -234,26 +265,48 @@ impl SecurityEventLog {
         Self {
             entries: BlockingMutex::new(RefCell::new(VecDeque::new())),
             capacity: capacity.max(1),
+            max_bytes: None,
         }
     }
 
+    /// Additionally bounds the log to `max_bytes` of [`SecurityLogEntry::stored_size`] - see the
+    /// type's docs. The newest entry is always kept, even one larger than the whole budget on its
+    /// own: a log that refused the event it was just handed would be no log at all.
+    pub fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
     /// The maximum number of entries this log retains - see [`Self::with_capacity`].
     pub fn capacity(&self) -> usize {
         self.capacity
     }
 
-    /// Appends `entry`, evicting and returning the oldest entry if the log is already at capacity
-    /// (see the type's docs for why eviction is unconditional). Returns `None` when nothing was
-    /// evicted.
-    pub fn record(&self, entry: SecurityLogEntry) -> Option<SecurityLogEntry> {
+    /// The size bound set by [`Self::with_max_bytes`], if any.
+    pub fn max_bytes(&self) -> Option<usize> {
+        self.max_bytes
+    }
+
+    /// Appends `entry`, evicting the oldest entries until the log is back within its capacity and
+    /// size bound (see the type's docs for why eviction is unconditional). Returns what was
+    /// evicted, oldest first - empty when nothing was.
+    pub fn record(&self, entry: SecurityLogEntry) -> alloc::vec::Vec<SecurityLogEntry> {
         self.entries.lock(|entries| {
             let mut entries = entries.borrow_mut();
-            let evicted = if entries.len() >= self.capacity {
-                entries.pop_front()
-            } else {
-                None
-            };
+            let mut evicted = alloc::vec::Vec::new();
+            while entries.len() >= self.capacity {
+                evicted.extend(entries.pop_front());
+            }
             entries.push_back(entry);
+            if let Some(max_bytes) = self.max_bytes {
+                let mut stored: usize = entries.iter().map(SecurityLogEntry::stored_size).sum();
+                while stored > max_bytes && entries.len() > 1 {
+                    if let Some(oldest) = entries.pop_front() {
+                        stored -= oldest.stored_size();
+                        evicted.push(oldest);
+                    }
+                }
+            }
             evicted
         })
     }
@@ -292,12 +345,12 @@ impl SecurityEventLog {
     /// Appends every entry from `entries`, oldest first, through [`Self::record`] - so a log
     /// restored from durable storage after a reboot respects this log's capacity exactly as if the
     /// entries had been recorded one at a time while running. Returns how many entries the
-    /// capacity bound dropped (non-zero only if the persisted log is larger than this log's
-    /// capacity, e.g. because the capacity was lowered since the snapshot was written).
+    /// bounds dropped (non-zero only if the persisted log is larger than this log's bounds, e.g.
+    /// because they were lowered since the snapshot was written).
     pub fn restore(&self, entries: alloc::vec::Vec<SecurityLogEntry>) -> usize {
-        entries.into_iter().fold(0, |dropped, entry| {
-            dropped + usize::from(self.record(entry).is_some())
-        })
+        entries
+            .into_iter()
+            .fold(0, |dropped, entry| dropped + self.record(entry).len())
     }
 }
 
@@ -493,8 +546,8 @@ mod log_tests {
     #[test]
     fn entries_are_returned_oldest_first() {
         let log = SecurityEventLog::new();
-        assert_eq!(log.record(entry("first")), None);
-        assert_eq!(log.record(entry("second")), None);
+        assert_eq!(log.record(entry("first")), alloc::vec![]);
+        assert_eq!(log.record(entry("second")), alloc::vec![]);
         assert_eq!(tech_infos(&log), alloc::vec!["first", "second"]);
     }
 
@@ -505,7 +558,7 @@ mod log_tests {
         log.record(entry("second"));
         // Full at [first, second]: the third entry evicts the first, which is handed back so the
         // caller can log that an audit-trail entry was lost.
-        assert_eq!(log.record(entry("third")), Some(entry("first")));
+        assert_eq!(log.record(entry("third")), alloc::vec![entry("first")]);
         assert_eq!(tech_infos(&log), alloc::vec!["second", "third"]);
         assert_eq!(log.len(), 2);
     }
@@ -515,10 +568,31 @@ mod log_tests {
         let log = SecurityEventLog::with_capacity(0);
         assert_eq!(log.capacity(), 1);
         log.record(entry("first"));
-        assert_eq!(log.record(entry("second")), Some(entry("first")));
+        assert_eq!(log.record(entry("second")), alloc::vec![entry("first")]);
         assert_eq!(tech_infos(&log), alloc::vec!["second"]);
     }
 
+    #[test]
+    fn entries_rotate_out_once_the_size_bound_is_reached_whatever_the_count() {
+        let short = entry("a");
+        let log = SecurityEventLog::new().with_max_bytes(3 * short.stored_size());
+        for tech_info in ["a", "b", "c"] {
+            assert!(log.record(entry(tech_info)).is_empty());
+        }
+        // A long techInfo costs more than one short one, so it rotates out two.
+        let long = "x".repeat(short.stored_size());
+        assert_eq!(
+            log.record(entry(&long)),
+            alloc::vec![entry("a"), entry("b")]
+        );
+        assert_eq!(tech_infos(&log), alloc::vec!["c".into(), long.clone()]);
+
+        // An entry over the whole budget on its own is still kept - as the only one.
+        let huge = "y".repeat(4 * short.stored_size());
+        assert_eq!(log.record(entry(&huge)).len(), 2);
+        assert_eq!(tech_infos(&log), alloc::vec![huge]);
+    }
+
     #[test]
     fn clearing_empties_the_log_and_reports_how_many_entries_were_discarded() {
         let log = SecurityEventLog::new();
@@ -187,6 +187,9 @@ where
         // it would fire for whoever plugs in next. Swept every 5s, so the release lands within
         // 5s of `TxCtrlr.EVConnectionTimeOut`.
         .pending_remote_start_timeouts(backoff.clone(), monotonic, 5)
+        // At most one `InvalidMessages` entry a minute in the security log, however hard a CSMS
+        // floods it - the count in `SecurityCtrlr.InvalidMessages` stays exact regardless.
+        .invalid_message_summaries(backoff.clone(), 60)
         .transaction_events(&transactions)
         .await
         .authorization(&csms, clock.clone())
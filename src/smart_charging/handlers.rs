@@ -99,6 +99,16 @@ pub async fn handle_set_charging_profile(
         tracing::warn!(?rejection, "refusing a charging profile");
         return SetChargingProfileOutcome::Rejected(rejection_reason(&rejection));
     }
+    let command = crate::command_policy::InboundCommand::SetChargingProfile {
+        scope,
+        profile: &profile,
+    };
+    if crate::command_policy::screen(actor, &command).is_err() {
+        return SetChargingProfileOutcome::Rejected(SetChargingProfileRejection {
+            explanation: "this charge point's command policy does not allow it",
+            reason_code: None,
+        });
+    }
 
     let _ = actor
         .send(ChargePointEvent::ChargingProfileSet {
@@ -165,6 +175,9 @@ pub async fn handle_clear_charging_profile(
     if !crate::refusal::capability_present(&state.capabilities, "ClearChargingProfile") {
         return ClearChargingProfileOutcome::Unknown;
     }
+    if crate::command_policy::refuses(actor, "ClearChargingProfile") {
+        return ClearChargingProfileOutcome::Unknown;
+    }
     if state.charging_profiles.matching(&criteria).is_empty() {
         return ClearChargingProfileOutcome::Unknown;
     }
@@ -205,6 +218,9 @@ pub async fn handle_get_composite_schedule<C: Clock>(
     if !crate::refusal::capability_present(&state.capabilities, "GetCompositeSchedule") {
         return GetCompositeScheduleOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "GetCompositeSchedule") {
+        return GetCompositeScheduleOutcome::Rejected;
+    }
     let Some(evse) = state.evses.get(evse_id) else {
         return GetCompositeScheduleOutcome::Rejected;
     };
@@ -236,6 +252,9 @@ pub fn handle_get_charging_profiles(
     if !crate::refusal::capability_present(&state.capabilities, "GetChargingProfiles") {
         return Vec::new();
     }
+    if crate::command_policy::refuses(actor, "GetChargingProfiles") {
+        return Vec::new();
+    }
     state
         .charging_profiles
         .selected_by(query)
@@ -290,6 +309,9 @@ pub async fn handle_use_priority_charging(
     if !crate::refusal::capability_present(&state.capabilities, "UsePriorityCharging") {
         return UsePriorityChargingOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "UsePriorityCharging") {
+        return UsePriorityChargingOutcome::Rejected;
+    }
     let running = state.evses.iter().enumerate().find_map(|(evse_id, evse)| {
         evse.transactions
             .iter()
@@ -373,6 +395,9 @@ pub async fn handle_update_dynamic_schedule(
     if !crate::refusal::capability_present(&state.capabilities, "UpdateDynamicSchedule") {
         return UpdateDynamicScheduleOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "UpdateDynamicSchedule") {
+        return UpdateDynamicScheduleOutcome::Rejected;
+    }
     let addressable = state.charging_profiles.installed().iter().any(|installed| {
         installed.profile.id == profile_id
             && installed.profile.kind == crate::state::ChargingProfileKind::Dynamic
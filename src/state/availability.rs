@@ -0,0 +1,72 @@
+use crate::state::{ChargePointEvent, ConnectorEvent, EvseEvent};
+
+/// The scope of a CSMS-initiated `ChangeAvailability` request - OCPP's optional `evse`/
+/// `connectorId` addressing collapsed to one of the three levels the internal state model
+/// tracks availability at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum AvailabilityTarget {
+    /// The request addresses the whole charge point.
+    ChargePoint,
+    /// The request addresses one EVSE and every connector on it.
+    Evse {
+        /// The targeted EVSE's index.
+        evse_id: usize,
+    },
+    /// The request addresses a single connector.
+    Connector {
+        /// The targeted connector's EVSE index.
+        evse_id: usize,
+        /// The targeted connector's index within its EVSE.
+        connector_id: usize,
+    },
+}
+
+impl AvailabilityTarget {
+    /// The event that makes this target available (`available = true`) or unavailable, at the
+    /// matching level of the state model. A change applied at once and one applied later, once
+    /// a transaction ends, go through this same event - see
+    /// [`crate::state::ChargePointState::scheduled_unavailability`].
+    pub(crate) fn availability_event(self, available: bool) -> ChargePointEvent {
+        match (self, available) {
+            (Self::ChargePoint, true) => ChargePointEvent::SetAvailable,
+            (Self::ChargePoint, false) => ChargePointEvent::SetUnavailable,
+            (Self::Evse { evse_id }, available) => ChargePointEvent::Evse {
+                evse_id,
+                event: if available {
+                    EvseEvent::SetAvailable
+                } else {
+                    EvseEvent::SetUnavailable
+                },
+            },
+            (
+                Self::Connector {
+                    evse_id,
+                    connector_id,
+                },
+                available,
+            ) => ChargePointEvent::Evse {
+                evse_id,
+                event: EvseEvent::Connector {
+                    connector_id,
+                    event: if available {
+                        ConnectorEvent::SetAvailable
+                    } else {
+                        ConnectorEvent::SetUnavailable
+                    },
+                },
+            },
+        }
+    }
+
+    /// Whether `other` is this target or lies inside it: the charge point covers everything, an
+    /// EVSE covers itself and its own connectors, and a connector covers only itself.
+    pub(crate) fn covers(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::ChargePoint, _) => true,
+            (Self::Evse { evse_id }, Self::Evse { evse_id: other })
+            | (Self::Evse { evse_id }, Self::Connector { evse_id: other, .. }) => evse_id == other,
+            (Self::Evse { .. }, Self::ChargePoint) => false,
+            (Self::Connector { .. }, _) => self == other,
+        }
+    }
+}
@@ -4,8 +4,9 @@
 //! at all (`BootNotification.req` predates it), so this has no effect on a 1.6J connection - see
 //! `crate::provisioning`'s 1.6J adapter.
 //!
-//! There are only two causes this crate can currently produce, both originating from
-//! [`crate::reset::handle_reset`]: a CSMS-initiated `Reset`. Absence of a [`BootReasonCause`] (a
+//! There are only three causes this crate can currently produce: the two originating from
+//! [`crate::reset::handle_reset`] (a CSMS-initiated `Reset`), and the reboot a firmware update asks
+//! for to run the image it installed (`crate::firmware`). Absence of a [`BootReasonCause`] (a
 //! bare `None` wherever this type is used) means something else - a power cut, a watchdog
 //! reset, or a crash - caused the restart; see `crate::persistence::BootReasonStore`'s docs for
 //! how that's told apart from a commanded one, and `crate::provisioning`'s per-version
@@ -24,6 +25,12 @@ pub enum BootReasonCause {
     /// until the target had no transaction in progress. Maps to OCPP's
     /// `BootReasonEnum::ScheduledReset`.
     ScheduledReset,
+    /// The reboot a firmware update requested to start the image it installed. Not recorded via
+    /// [`crate::persistence::BootReasonStore`] but derived from the pending-update marker
+    /// `crate::persistence::FirmwareUpdateStore` keeps, which takes precedence over the
+    /// `RemoteReset` the same reboot's `Reset` path records. Maps to OCPP's
+    /// `BootReasonEnum::FirmwareUpdate`.
+    FirmwareUpdate,
 }
 
 impl From<super::ResetKind> for BootReasonCause {
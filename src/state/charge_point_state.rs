@@ -5,26 +5,29 @@ use chrono::{DateTime, Utc};
 
 use crate::clock::MonotonicInstant;
 use crate::hardware::Capabilities;
-use crate::state::connector_state::{ConnectorCommand, ConnectorPolicy, TxStartPoint, TxStopPoint};
+use crate::state::connector_state::{
+    ConnectorCommand, ConnectorPolicy, TxPoints, TxStartPoint, TxStopPoint,
+};
 use crate::state::device_model::{
     AVAILABILITY_COMPONENT_CHARGE_POINT, AVAILABILITY_COMPONENT_CONNECTOR,
     AVAILABILITY_COMPONENT_EVSE, AVAILABILITY_STATE_VARIABLE, CLOCK_COMPONENT,
     CLOCK_DATE_TIME_VARIABLE, NETWORK_CONFIGURATION_COMPONENT, PLUG_RETENTION_LOCK_COMPONENT,
-    PROBLEM_VARIABLE,
+    PROBLEM_VARIABLE, tx_start_stop_points,
 };
 use crate::state::{
-    AfrrSignal, AuthorizationCache, AuthorizationRequested, BatterySwapStore, ChargePointEffect,
-    ChargePointEvent, ChargingProfileScope, ChargingProfileStore, Component, ConnectorEvent,
-    ConnectorState, ConnectorStatus, ConnectorStatusChanged, DERControlStore, DeviceModel,
-    DeviceModelEvent, DisplayMessageStore, EventTrigger, EvseEvent, EvseState, EvseStatus,
-    ExternalChargingLimit, HardwareCommand, IdToken, LocalAuthorizationList, LocalListEntry,
-    MeterSample, NetworkProfileStore, PendingReset, PeriodicEventStreamStore, RegistrationStatus,
-    ReservationEndReason, ReservationUpdate, ResetKind, ResetTarget, SecurityEvent,
-    SecurityEventType, SmartChargingNotification, StateLimits, StopReason, TariffStore,
-    Transaction, TransactionChargingState, TransactionEventKind, TransactionEventOccurred,
-    TransactionId, TransactionUpdateReason, TriggeredMonitor, Variable, VariableAttribute,
-    VariableAttributeType, VariableCharacteristics, VariableDataType, VariableMonitorStore,
-    VariableMonitoringEvent, VariableMutability,
+    AfrrSignal, AuthorizationCache, AuthorizationRequested, AvailabilityTarget, BatterySwapStore,
+    ChargePointEffect, ChargePointEvent, ChargingProfileScope, ChargingProfileStore, Component,
+    ConnectorEvent, ConnectorState, ConnectorStatus, ConnectorStatusChanged, DERControlStore,
+    DeviceModel, DeviceModelEvent, DisplayMessageStore, EventTrigger, EvseEvent, EvseState,
+    EvseStatus, ExternalChargingLimit, HardwareCommand, IdToken, InvalidMessageTally,
+    LocalAuthorizationList, LocalListEntry, MeterSample, NetworkProfileStore, PendingReset,
+    PeriodicEventStreamStore, RegistrationStatus, ReservationEndReason, ReservationId,
+    ReservationUpdate, ResetKind, ResetTarget, SecurityEvent, SecurityEventType,
+    SmartChargingNotification, StateLimits, StopReason, TariffStore, Transaction,
+    TransactionChargingState, TransactionEventKind, TransactionEventOccurred, TransactionId,
+    TransactionUpdateReason, TriggeredMonitor, Variable, VariableAttribute, VariableAttributeType,
+    VariableCharacteristics, VariableDataType, VariableMonitorStore, VariableMonitoringEvent,
+    VariableMutability,
 };
 
 /// The wire value OCPP's `AvailabilityState` takes for `status` - the same
@@ -213,6 +216,55 @@ fn availability_precedence(status: ConnectorStatus) -> u8 {
     }
 }
 
+/// See [`ChargePointState::station_status`].
+fn station_status(lifecycle: LifecycleState) -> ConnectorStatus {
+    match lifecycle {
+        LifecycleState::Booting | LifecycleState::Unavailable => ConnectorStatus::Unavailable,
+        LifecycleState::Available => ConnectorStatus::Available,
+        LifecycleState::Faulted => ConnectorStatus::Faulted,
+    }
+}
+
+/// What a connector reports to the CSMS: its own status, unless its EVSE or the charge point as a
+/// whole is out of service, in which case the connector is too. The same "most restrictive wins"
+/// rule [`availability_precedence`] applies to an EVSE's connectors, applied down the hierarchy
+/// instead of up it - so a connector faulted in its own right still reports `Faulted` inside an
+/// EVSE that was merely made `Unavailable`.
+///
+/// `Booting` overrides nothing: no `StatusNotification` goes out before registration anyway, and
+/// the sweep that follows it (B01.FR.05) reports every connector as it then is.
+///
+/// `unavailability_pending` is [`ChargePointState::unavailability_pending`]: an idle connector
+/// inside a `Scheduled` change's scope reports `Unavailable` as if the change had already
+/// reached it.
+fn reported_connector_status(
+    lifecycle: LifecycleState,
+    evse: EvseStatus,
+    connector: ConnectorState,
+    unavailability_pending: bool,
+) -> ConnectorStatus {
+    let station = match lifecycle {
+        LifecycleState::Booting | LifecycleState::Available => ConnectorStatus::Available,
+        LifecycleState::Unavailable => ConnectorStatus::Unavailable,
+        LifecycleState::Faulted => ConnectorStatus::Faulted,
+    };
+    let evse = match evse {
+        EvseStatus::Available => ConnectorStatus::Available,
+        EvseStatus::Unavailable => ConnectorStatus::Unavailable,
+        EvseStatus::Faulted => ConnectorStatus::Faulted,
+    };
+    let scheduled = if unavailability_pending {
+        ConnectorStatus::Unavailable
+    } else {
+        ConnectorStatus::Available
+    };
+    let own = connector.availability_status();
+    [own, evse, station, scheduled]
+        .into_iter()
+        .max_by_key(|status| availability_precedence(*status))
+        .unwrap_or(own)
+}
+
 /// This charge point's best current estimate of the CSMS's clock, anchored to a
 /// [`MonotonicInstant`] so it can be advanced by elapsed real time without ever consulting a
 /// (possibly absent or unsynchronized) wall clock - see `crate::clock` and
@@ -314,8 +366,36 @@ pub struct ChargePointState {
     /// [`EvseState::local_generation_limit`] at station scope, and separate from the slot above
     /// for the reason given there (K27.FR.05).
     pub station_local_generation_limit: Option<ExternalChargingLimit>,
+    /// Invalid inbound CSMS messages counted since boot, and the rate limit on reporting them -
+    /// see [`InvalidMessageTally`] and [`crate::invalid_messages`].
+    pub invalid_messages: InvalidMessageTally,
+    /// A request to clear the security log that has not been carried out yet, naming who made
+    /// it. See [`ChargePointEvent::SecurityLogClearRequested`]. Held here rather than acted on
+    /// because the state machine does not own the log:
+    /// [`crate::persistence::run_security_log_clears`] does, and clears it once it sees this.
+    pub pending_security_log_clear: Option<String>,
+    /// The most recent reservations a cable arriving honoured, oldest first and at most
+    /// [`MAX_CONSUMED_RESERVATIONS`] of them. A reservation is spent once honoured, so a
+    /// `ReserveNow` naming one of these ids again is an old request coming back - see
+    /// [`crate::reservation::handle_reserve_now`].
+    pub consumed_reservations: Vec<ReservationId>,
+    /// Scopes the CSMS asked to take out of service while a transaction was running there, oldest
+    /// first, each waiting for its transaction to end (G03.FR.05). At most one entry per scope:
+    /// scheduling the same scope again keeps the one already waiting. See
+    /// [`ChargePointEvent::UnavailabilityScheduled`] and
+    /// [`crate::persistence::run_scheduled_unavailability_persistence`].
+    pub scheduled_unavailability: Vec<AvailabilityTarget>,
+    /// [`StateLimits::max_ended_meter_samples`], kept from construction: the series it bounds
+    /// lives in plain per-connector vectors ([`EvseState::ended_meter_samples`]) rather than a
+    /// store that could hold its own limit.
+    pub(crate) max_ended_meter_samples: usize,
 }
 
+/// How many honoured reservation ids [`ChargePointState::consumed_reservations`] remembers
+/// before evicting the oldest - the same bound [`crate::replay_protection::ReplayGuard`] uses
+/// by default, for the same reason.
+pub const MAX_CONSUMED_RESERVATIONS: usize = 16;
+
 /// The charge point's own lifecycle state, independent of any individual EVSE/connector's state.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LifecycleState {
@@ -389,6 +469,11 @@ impl ChargePointState {
             afrr_signal: None,
             station_external_charging_limit: None,
             station_local_generation_limit: None,
+            invalid_messages: InvalidMessageTally::default(),
+            pending_security_log_clear: None,
+            consumed_reservations: Vec::new(),
+            scheduled_unavailability: Vec::new(),
+            max_ended_meter_samples: limits.max_ended_meter_samples,
         }
     }
 
@@ -425,6 +510,8 @@ impl ChargePointState {
     /// state machines are designed to tolerate being handed events that don't currently apply.
     pub fn apply(&mut self, event: ChargePointEvent) -> Vec<ChargePointEffect> {
         let mut effects = Vec::new();
+        let reported_before = self.reported_before(&event);
+        let lifecycle_before = self.lifecycle;
         let changed = match event {
             ChargePointEvent::BootCompleted | ChargePointEvent::SetAvailable => {
                 set_if_changed(&mut self.lifecycle, LifecycleState::Available)
@@ -458,6 +545,33 @@ impl ChargePointState {
                 effects.push(ChargePointEffect::SecurityEventOccurred(event));
                 false
             }
+            ChargePointEvent::InvalidMessageReceived(message) => {
+                if let Some(event) = self.invalid_messages.record(message) {
+                    effects.push(ChargePointEffect::SecurityEventOccurred(event));
+                }
+                self.set_counter_variable(
+                    "SecurityCtrlr",
+                    "InvalidMessages",
+                    None,
+                    self.invalid_messages.total,
+                );
+                true
+            }
+            ChargePointEvent::InvalidMessageSummaryDue => {
+                if let Some(event) = self.invalid_messages.summarise() {
+                    effects.push(ChargePointEffect::SecurityEventOccurred(event));
+                }
+                false
+            }
+            ChargePointEvent::SecurityLogClearRequested { requested_by } => {
+                // A second request before the first is carried out asks for the same thing; the
+                // most recent requester is the one the `SecurityLogWasCleared` report names.
+                self.pending_security_log_clear = Some(requested_by);
+                true
+            }
+            ChargePointEvent::SecurityLogClearCompleted => {
+                self.pending_security_log_clear.take().is_some()
+            }
             ChargePointEvent::ResetRequested { target, kind } => {
                 self.pending_reset = Some(PendingReset { target, kind });
                 // `Immediate` kicks off the fail-safe stop right away, fanned out to every
@@ -477,6 +591,33 @@ impl ChargePointState {
                 }
                 true
             }
+            ChargePointEvent::UnavailabilityScheduled { target } => {
+                // Applied, if it already can be, by `check_scheduled_unavailability` below.
+                if self.scheduled_unavailability.contains(&target) {
+                    false
+                } else {
+                    self.scheduled_unavailability.push(target);
+                    true
+                }
+            }
+            ChargePointEvent::ScheduledUnavailabilityCancelled { target } => {
+                let before = self.scheduled_unavailability.len();
+                self.scheduled_unavailability
+                    .retain(|scheduled| !target.covers(*scheduled));
+                self.scheduled_unavailability.len() != before
+            }
+            ChargePointEvent::PersistedScheduledUnavailabilityRestored { targets } => {
+                let mut changed = false;
+                for target in targets {
+                    if self.availability_target_exists(target)
+                        && !self.scheduled_unavailability.contains(&target)
+                    {
+                        self.scheduled_unavailability.push(target);
+                        changed = true;
+                    }
+                }
+                changed
+            }
             ChargePointEvent::NetworkProfileSet { slot, profile } => {
                 let stored = self.network_profiles.set(slot, *profile);
                 if stored {
@@ -889,6 +1030,7 @@ impl ChargePointState {
                 restored_any
             }
             ChargePointEvent::CapabilitiesDeclared(capabilities) => {
+                self.declare_tx_start_stop_points(&capabilities);
                 set_if_changed(&mut self.capabilities, capabilities)
             }
             // CV1.5: the integrator's electrical declaration, projected onto the required
@@ -1028,9 +1170,18 @@ impl ChargePointState {
         // rather than at each of the several dozen mutation sites - which is what keeps it from
         // drifting when a new event variant lands. Costs one pass over the connectors; a charge
         // point has single digits of them.
+        //
+        // A scheduled change this event let through is applied first, so a connector it reaches
+        // is reported once, by its own transition, rather than first through the overlay
+        // `Self::unavailability_pending` puts on it while the change waits.
+        let changed = self.check_scheduled_unavailability(&mut effects) || changed;
         self.sync_availability_variables();
         self.sync_network_configuration_variables();
         self.sync_inventory_counters();
+        // The same reasoning for the StatusNotifications a station- or EVSE-level change owes
+        // each connector it covers: comparing what the connectors reported before and after
+        // catches them whichever event moved the level above.
+        self.report_fanned_out_statuses(&reported_before, lifecycle_before, &mut effects);
         if changed {
             effects.insert(0, ChargePointEffect::StateChanged);
         }
@@ -1038,6 +1189,121 @@ impl ChargePointState {
         effects
     }
 
+    /// The status the charge point as a whole reports: its [`LifecycleState`], collapsed to the
+    /// same values a connector reports. Not yet booted is not yet available - and the
+    /// `Unavailable` a `SetUnavailable` produces is the same thing to a CSMS, so both map to it.
+    ///
+    /// What 1.6J reports as `connectorId` 0; 2.x has no station-level `StatusNotification` and
+    /// sees this only through [`Self::reported_connector_status`].
+    pub fn station_status(&self) -> ConnectorStatus {
+        station_status(self.lifecycle)
+    }
+
+    /// The status `connector_id` on `evse_id` reports to the CSMS: its own, unless its EVSE or
+    /// the whole charge point is `Unavailable`/`Faulted`, which then shows through - or the
+    /// connector is idle inside a change still waiting on another connector's transaction (see
+    /// [`Self::unavailability_pending`]). `None` if the address doesn't name a connector this
+    /// charge point has.
+    ///
+    /// Every [`ChargePointEffect::StatusNotification`] carries this, and [`Self::apply`] reports
+    /// it for every connector it changes on, whichever level the change was made at.
+    pub fn reported_connector_status(
+        &self,
+        evse_id: usize,
+        connector_id: usize,
+    ) -> Option<ConnectorStatus> {
+        let evse = self.evses.get(evse_id)?;
+        let connector = evse.connectors.get(connector_id)?;
+        Some(reported_connector_status(
+            self.lifecycle,
+            evse.status,
+            *connector,
+            self.unavailability_pending(evse_id, connector_id),
+        ))
+    }
+
+    /// Whether `connector_id` on `evse_id` is idle inside the scope of a `ChangeAvailability`
+    /// answered `Scheduled` (G03.FR.05) - one still waiting on a transaction elsewhere in that
+    /// scope. Such a connector reports `Unavailable` and opens no new session: were it left
+    /// `Available`, every driver starting on it would push the change back again, and a busy site
+    /// could keep it pending indefinitely. `false` for an address that doesn't exist.
+    pub fn unavailability_pending(&self, evse_id: usize, connector_id: usize) -> bool {
+        let idle = self
+            .evses
+            .get(evse_id)
+            .and_then(|evse| evse.transactions.get(connector_id))
+            .is_some_and(Option::is_none);
+        idle && self.scheduled_unavailability.iter().any(|target| {
+            target.covers(AvailabilityTarget::Connector {
+                evse_id,
+                connector_id,
+            })
+        })
+    }
+
+    /// Whether a transaction is in progress anywhere inside `target` - what decides whether a
+    /// `ChangeAvailability` to inoperative is applied now or answered `Scheduled` (G03.FR.05).
+    /// `false` for a target that does not exist.
+    pub fn transaction_in_progress(&self, target: AvailabilityTarget) -> bool {
+        self.evses.iter().enumerate().any(|(evse_id, evse)| {
+            evse.transactions
+                .iter()
+                .enumerate()
+                .any(|(connector_id, transaction)| {
+                    transaction.is_some()
+                        && target.covers(AvailabilityTarget::Connector {
+                            evse_id,
+                            connector_id,
+                        })
+                })
+        })
+    }
+
+    /// Whether `target` addresses an EVSE/connector this charge point has.
+    fn availability_target_exists(&self, target: AvailabilityTarget) -> bool {
+        match target {
+            AvailabilityTarget::ChargePoint => true,
+            AvailabilityTarget::Evse { evse_id } => self.evses.get(evse_id).is_some(),
+            AvailabilityTarget::Connector {
+                evse_id,
+                connector_id,
+            } => self
+                .evses
+                .get(evse_id)
+                .is_some_and(|evse| evse.connectors.get(connector_id).is_some()),
+        }
+    }
+
+    /// Applies every entry of `scheduled_unavailability` whose scope no longer has a transaction
+    /// in progress, as the same `SetUnavailable` event an immediate `ChangeAvailability` sends -
+    /// so the `StatusNotification`s it produces are the ones an immediate change would have.
+    /// Called by every [`Self::apply`] once its event is handled, like
+    /// [`Self::check_pending_reset`], since the event that ends a transaction can be any of
+    /// several. Returns whether it applied any, for the caller's `StateChanged`.
+    fn check_scheduled_unavailability(&mut self, effects: &mut Vec<ChargePointEffect>) -> bool {
+        let mut applied = false;
+        while let Some(index) = self
+            .scheduled_unavailability
+            .iter()
+            .position(|target| !self.transaction_in_progress(*target))
+        {
+            let target = self.scheduled_unavailability.remove(index);
+            tracing::info!(
+                ?target,
+                "applying a scheduled ChangeAvailability now that its transaction has ended"
+            );
+            // The nested `apply` reports its own `StateChanged` first; the caller is about to be
+            // given one, so only what follows it is kept.
+            effects.extend(
+                self.apply(target.availability_event(false))
+                    .into_iter()
+                    .filter(|effect| !matches!(effect, ChargePointEffect::StateChanged)),
+            );
+            applied = true;
+        }
+        applied
+    }
+
     /// Reads a device-model variable's `Actual` value, or the empty string when it is absent -
     /// which every caller here treats as "not configured".
     fn string_variable(&self, component: &str, variable: &str) -> alloc::string::String {
@@ -1091,7 +1357,7 @@ impl ChargePointState {
     /// computation:
     ///
     /// - A **connector** reports exactly what its `StatusNotification` reports - the same
-    ///   [`ConnectorState::availability_status`] projection, so the two can never disagree.
+    ///   [`Self::reported_connector_status`] projection, so the two can never disagree.
     /// - An **EVSE** reports its own [`EvseStatus`] where that is decisive (`Faulted`,
     ///   `Unavailable` - both set by `ChangeAvailability` or a fault, and both meaning the EVSE is
     ///   out of service whatever its connectors say), and otherwise the busiest of its connectors.
@@ -1101,20 +1367,25 @@ impl ChargePointState {
     ///   up to the station would make a single charging connector on a 20-EVSE site report the
     ///   whole site as `Occupied`, which is not what a CSMS asking about the station means.
     fn sync_availability_variables(&mut self) {
-        let station = match self.lifecycle {
-            // Not yet booted is not yet available - and the `Unavailable` a `SetUnavailable`
-            // produces is the same thing to a CSMS, so both map to it.
-            LifecycleState::Booting | LifecycleState::Unavailable => ConnectorStatus::Unavailable,
-            LifecycleState::Available => ConnectorStatus::Available,
-            LifecycleState::Faulted => ConnectorStatus::Faulted,
-        };
-        self.set_availability_state(AVAILABILITY_COMPONENT_CHARGE_POINT, None, station);
+        self.set_availability_state(
+            AVAILABILITY_COMPONENT_CHARGE_POINT,
+            None,
+            self.station_status(),
+        );
 
         for evse_id in 0..self.evses.len() {
             let statuses: Vec<ConnectorStatus> = self.evses[evse_id]
                 .connectors
                 .iter()
-                .map(|connector| connector.availability_status())
+                .enumerate()
+                .map(|(connector_id, connector)| {
+                    reported_connector_status(
+                        self.lifecycle,
+                        self.evses[evse_id].status,
+                        *connector,
+                        self.unavailability_pending(evse_id, connector_id),
+                    )
+                })
                 .collect();
             for (connector_id, status) in statuses.iter().enumerate() {
                 self.set_availability_state(
@@ -1136,6 +1407,34 @@ impl ChargePointState {
         }
     }
 
+    /// Narrows or widens what `TxCtrlr.TxStartPoint`/`TxStopPoint` accept to the points this
+    /// station can observe (CV2.2) - the signalled ones only where `capabilities` say the binding
+    /// raises the signal, see [`tx_start_stop_points`]. The current value is kept.
+    fn declare_tx_start_stop_points(&mut self, capabilities: &Capabilities) {
+        let values = tx_start_stop_points(capabilities);
+        for name in ["TxStartPoint", "TxStopPoint"] {
+            let component = Component {
+                name: "TxCtrlr".into(),
+                instance: None,
+                evse: None,
+            };
+            let variable = Variable {
+                name: name.into(),
+                instance: None,
+            };
+            let Some(mut definition) = self.device_model.get(&component, &variable).cloned() else {
+                continue;
+            };
+            definition.characteristics.values_list = Some(values.clone());
+            self.device_model.register(
+                component,
+                variable,
+                definition.characteristics,
+                definition.attributes,
+            );
+        }
+    }
+
     /// Registers the electrical variables OCPP requires, from the integrator's declaration
     /// (`docs/OCPP-2.1-COMPLIANCE-ROADMAP.md` CV1.5).
     ///
@@ -1388,8 +1687,10 @@ impl ChargePointState {
     ///
     /// Like the availability sync, this writes through [`DeviceModel::set_attribute_value`]
     /// rather than raising a `DeviceModelEvent::AttributeValueSet`, so no variable monitor is
-    /// evaluated against these changes. That costs nothing today: all three are registered with
-    /// `supports_monitoring: false`, and `SetVariableMonitoring` refuses a variable that says so.
+    /// evaluated against these changes. That costs nothing today: all three - and
+    /// `SecurityCtrlr.InvalidMessages`, the one counter written from its own event arm rather than
+    /// from here - are registered with `supports_monitoring: false`, and `SetVariableMonitoring`
+    /// refuses a variable that says so.
     fn set_counter_variable(
         &mut self,
         component_name: &str,
@@ -1506,6 +1807,32 @@ impl ChargePointState {
         event: ConnectorEvent,
         effects: &mut Vec<ChargePointEffect>,
     ) -> bool {
+        // G03.FR.05: a connector already reporting `Unavailable` for a `Scheduled` change opens
+        // no session while the change waits - see `Self::unavailability_pending`. A card or a
+        // remote start is refused outright; an authorization already in flight when the change
+        // was scheduled is answered as a refusal, so the connector is not left `Authorizing`
+        // with nothing to end it. A cable cannot be refused, so it is the transaction start
+        // point that is held back instead, further down.
+        let unavailability_pending = self.unavailability_pending(evse_id, connector_id);
+        let event = match event {
+            ConnectorEvent::IdTokenPresented(_)
+            | ConnectorEvent::ContractCertificatePresented { .. }
+            | ConnectorEvent::RemoteStartRequested { .. }
+            | ConnectorEvent::RemoteStartPending(_)
+                if unavailability_pending =>
+            {
+                tracing::info!(
+                    evse_id,
+                    connector_id,
+                    "refusing a new session on a connector scheduled to become unavailable"
+                );
+                return false;
+            }
+            ConnectorEvent::ChargingAuthorized(_) if unavailability_pending => {
+                ConnectorEvent::AuthorizationDenied
+            }
+            event => event,
+        };
         // CV2.4: read once, before the mutable borrow, so the transition stays a pure function
         // of (state, event, policy). See `ConnectorPolicy`.
         let policy = ConnectorPolicy {
@@ -1514,10 +1841,10 @@ impl ChargePointState {
                 "StopTxOnEVSideDisconnect",
                 ConnectorPolicy::default().stop_tx_on_ev_side_disconnect,
             ),
-            tx_start_point: TxStartPoint::from_member_list(
+            tx_start_points: TxPoints::from_member_list(
                 &self.string_variable("TxCtrlr", "TxStartPoint"),
             ),
-            tx_stop_point: TxStopPoint::from_member_list(
+            tx_stop_points: TxPoints::from_member_list(
                 &self.string_variable("TxCtrlr", "TxStopPoint"),
             ),
             unlock_on_ev_side_disconnect: self.boolean_variable(
@@ -1545,6 +1872,17 @@ impl ChargePointState {
         // `TransactionLimit::supported`. Read here for the reason the policy above is: before the
         // mutable borrow.
         let supported_transaction_limits = self.string_variable("TxCtrlr", "SupportedLimits");
+        // J02: while `TxUpdatedInterval` is set, a reading is only recorded on arrival and
+        // reported when the sampling loop says the interval is up - see
+        // `ConnectorEvent::TransactionMeterValuesDue`. `0` (OCPP's own default), an unregistered
+        // variable or anything that does not parse leaves every reading reported as it arrives, as
+        // before the interval was honoured.
+        let max_ended_meter_samples = self.max_ended_meter_samples;
+        let report_each_sample = !matches!(
+            self.string_variable("SampledDataCtrlr", "TxUpdatedInterval")
+                .parse::<u32>(),
+            Ok(secs) if secs > 0
+        );
         let Some(evse) = self.evses.get_mut(evse_id) else {
             return false;
         };
@@ -1555,6 +1893,9 @@ impl ChargePointState {
         let stop_reason = match &event {
             ConnectorEvent::ChargingStopped(reason) => Some(*reason),
             ConnectorEvent::ResetRequested => Some(StopReason::Reset),
+            // E09: only ever recorded on the stop it causes, so a cable leaving an idle connector
+            // says nothing about any transaction.
+            ConnectorEvent::CableDisconnected => Some(StopReason::EVDisconnected),
             _ => None,
         };
         // Both presentations authorize an identifier; only the Plug & Charge one carries
@@ -1607,6 +1948,14 @@ impl ChargePointState {
                 }
             }
             ConnectorEvent::TransactionElapsed(secs) => EventKind::TransactionElapsed(*secs),
+            ConnectorEvent::TransactionMeterValuesDue => EventKind::TransactionMeterValuesDue,
+            ConnectorEvent::TransactionEventTriggered => EventKind::TransactionEventTriggered,
+            ConnectorEvent::TransactionEndedSampleDue(timestamp) => {
+                EventKind::TransactionEndedSampleDue(*timestamp)
+            }
+            ConnectorEvent::TransactionAlignedSampleDue(timestamp) => {
+                EventKind::TransactionAlignedSampleDue(*timestamp)
+            }
             _ => EventKind::Other,
         };
         // CV7: `Some(Some(..))` records a pending start, `Some(None)` clears one, `None` means
@@ -1630,7 +1979,23 @@ impl ChargePointState {
             _ => None,
         };
         let meter_sample = match &event {
-            ConnectorEvent::MeterValueSampled(sample) => Some(*sample),
+            ConnectorEvent::MeterValueSampled(sample) => Some(sample.clone()),
+            _ => None,
+        };
+        // CV2.2: the `TxStartPoint`/`TxStopPoint` members no connector transition stands for.
+        let session_signal = match &event {
+            ConnectorEvent::ParkingBayOccupied => Some(SessionSignal::ParkingBayOccupied),
+            ConnectorEvent::ParkingBayVacated => Some(SessionSignal::ParkingBayVacated),
+            ConnectorEvent::EnergyTransferStarted => Some(SessionSignal::EnergyTransferStarted),
+            ConnectorEvent::EnergyTransferStopped => Some(SessionSignal::EnergyTransferStopped),
+            ConnectorEvent::MeterValueSampled(sample) if sample.signed.is_some() => {
+                Some(SessionSignal::DataSigned)
+            }
+            // OCPP counts a cable leaving the EV as the EV's communication being lost - it is
+            // the E10 example the trigger reason is documented with.
+            ConnectorEvent::EvCommunicationLost | ConnectorEvent::CableDisconnected => {
+                Some(SessionSignal::EvCommunicationLost)
+            }
             _ => None,
         };
         let reservation_made = match &event {
@@ -1707,6 +2072,12 @@ impl ChargePointState {
                 *slot = None;
             }
         }
+        if let Some(id) = honoured {
+            if self.consumed_reservations.len() >= MAX_CONSUMED_RESERVATIONS {
+                self.consumed_reservations.remove(0);
+            }
+            self.consumed_reservations.push(ReservationId(id));
+        }
         if let Some(slot) = evse.honoured_reservations.get_mut(connector_id) {
             if honoured.is_some() {
                 *slot = honoured;
@@ -1731,6 +2102,7 @@ impl ChargePointState {
         {
             let from = transaction
                 .last_meter_sample
+                .as_ref()
                 .map(|sample| sample.energy_wh)
                 .unwrap_or_default();
             transaction.stop_at_energy_wh = Some(from + allowance);
@@ -1791,6 +2163,31 @@ impl ChargePointState {
             transaction.elapsed_secs = Some(secs);
             elapsed_reported = true;
         }
+        // J02 (`SampledDataCtrlr.TxEndedInterval`): the reading the connector last reported, kept
+        // against the instant the sampling loop supplied for the `Ended` event to carry. The
+        // connector's own latest reading rather than the transaction's, because the latter only
+        // moves while `Charging` and the interval samples the whole session. 1.6J's
+        // `StopTxnAlignedData` is kept the same way, on the wall-clock boundaries the standalone
+        // `MeterValues` are sent on.
+        let ended_sample_due = match event_kind {
+            EventKind::TransactionEndedSampleDue(timestamp) => Some((timestamp, false)),
+            EventKind::TransactionAlignedSampleDue(timestamp) => Some((timestamp, true)),
+            _ => None,
+        };
+        let mut ended_sample_recorded = false;
+        if let Some((timestamp, clock_aligned)) = ended_sample_due
+            && let Some(Some(sample)) = evse.latest_meter_samples.get(connector_id).cloned()
+            && let Some(Some(_)) = evse.transactions.get(connector_id)
+            && let Some(kept) = evse.ended_meter_samples.get_mut(connector_id)
+            && kept.len() < max_ended_meter_samples
+        {
+            kept.push(crate::state::TimestampedMeterSample {
+                timestamp,
+                sample,
+                clock_aligned,
+            });
+            ended_sample_recorded = true;
+        }
         // CV7/F02: the pending remote start's lifecycle. Recorded on request, dispatched the
         // moment the cable latches, cleared when the connector goes idle without ever being used
         // (a timeout sweep, a fault clearing) so it cannot fire for whoever plugs in next.
@@ -1932,7 +2329,12 @@ impl ChargePointState {
                 ConnectorStatusChanged {
                     evse_id,
                     connector_id,
-                    status: new_state.availability_status(),
+                    status: reported_connector_status(
+                        self.lifecycle,
+                        evse.status,
+                        new_state,
+                        unavailability_pending,
+                    ),
                     connector_state: new_state,
                 },
             ));
@@ -1974,12 +2376,27 @@ impl ChargePointState {
                 },
             ));
         }
+        // A transaction starting or ending on a signal moves no connector state, so
+        // `transition.changed` cannot be relied on to say the state did change.
+        let mut transaction_boundary = false;
+        // The connector's reading at this moment - what a transaction starting or ending here
+        // records as its start or stop value. A reading `DataSigned` arrives with is not yet
+        // recorded as the connector's latest, and is the very one that moment is about.
+        let reading_now = meter_sample.clone().or_else(|| {
+            evse.latest_meter_samples
+                .get(connector_id)
+                .cloned()
+                .flatten()
+        });
         if let Some(slot) = evse.transactions.get_mut(connector_id) {
-            if let Some((kind, transaction)) = advance_transaction(
+            if let Some((kind, mut transaction)) = advance_transaction(
                 slot,
                 &mut self.next_transaction_id,
-                previous_state,
-                new_state,
+                ConnectorMoment {
+                    previous_state,
+                    new_state,
+                    signal: session_signal,
+                },
                 stop_reason,
                 TransactionOrigin {
                     id_token: authorized_id_token,
@@ -1988,10 +2405,15 @@ impl ChargePointState {
                     // (F01.FR.01) - either way the transaction quotes the request that caused it.
                     remote_start_id: event_remote_start_id.or(held_remote_start_id),
                     reservation_id: active_reservation_id,
+                    meter_start: reading_now.clone(),
                 },
                 TransactionPoints {
-                    tx_start_point: policy.tx_start_point,
-                    tx_stop_point: policy.tx_stop_point,
+                    tx_start_points: if unavailability_pending {
+                        TxPoints::empty()
+                    } else {
+                        policy.tx_start_points
+                    },
+                    tx_stop_points: policy.tx_stop_points,
                 },
             ) {
                 // A new transaction must not inherit a previous one's running cost or driver
@@ -2000,6 +2422,7 @@ impl ChargePointState {
                     kind,
                     TransactionEventKind::Started | TransactionEventKind::Ended
                 ) {
+                    transaction_boundary = true;
                     if let Some(cost_slot) = evse.running_costs.get_mut(connector_id) {
                         *cost_slot = None;
                     }
@@ -2009,6 +2432,19 @@ impl ChargePointState {
                     if let Some(running_cost_slot) = evse.running_cost.get_mut(connector_id) {
                         *running_cost_slot = None;
                     }
+                    // J02: the series kept while it ran goes with the `Ended` event alone - see
+                    // `EvseState::ended_meter_samples` - and a new transaction starts without one.
+                    if let Some(kept) = evse.ended_meter_samples.get_mut(connector_id) {
+                        let kept = core::mem::take(kept);
+                        if kind == TransactionEventKind::Ended {
+                            transaction.ended_meter_samples = kept;
+                        }
+                    }
+                }
+                // The register at the stop point: the last `Charging` reading would miss
+                // whatever the meter counted while the session was suspended.
+                if kind == TransactionEventKind::Ended && reading_now.is_some() {
+                    transaction.last_meter_sample = reading_now;
                 }
                 effects.push(ChargePointEffect::TransactionEvent(
                     TransactionEventOccurred {
@@ -2020,8 +2456,13 @@ impl ChargePointState {
                     },
                 ));
             }
+            // A signed reading that just began the transaction already went out as its start
+            // value, on `Started`.
             if let Some(sample) = meter_sample
-                && let Some((kind, transaction)) = apply_meter_sample(slot, sample)
+                .clone()
+                .filter(|sample| !(transaction_boundary && sample.signed.is_some()))
+                && let Some((kind, transaction)) =
+                    apply_meter_sample(slot, sample, report_each_sample)
             {
                 effects.push(ChargePointEffect::TransactionEvent(
                     TransactionEventOccurred {
@@ -2105,6 +2546,43 @@ impl ChargePointState {
                 },
             ));
         }
+        // J02 (`SampledDataCtrlr.TxUpdatedInterval`): the interval is up, so the reading
+        // `apply_meter_sample` only recorded goes out now - under the same two conditions it would
+        // have gone out on arrival.
+        if matches!(event_kind, EventKind::TransactionMeterValuesDue)
+            && let Some(Some(transaction)) = evse.transactions.get_mut(connector_id)
+            && transaction.charging_state == TransactionChargingState::Charging
+            && transaction.last_meter_sample.is_some()
+        {
+            transaction.seq_no += 1;
+            effects.push(ChargePointEffect::TransactionEvent(
+                TransactionEventOccurred {
+                    evse_id,
+                    connector_id,
+                    kind: TransactionEventKind::Updated(
+                        TransactionUpdateReason::MeterValuePeriodic,
+                    ),
+                    transaction: transaction.clone(),
+                    offline: false,
+                },
+            ));
+        }
+        // F06: the CSMS asked where this transaction stands. Whatever it is doing, that is the
+        // answer - there is no condition under which a running transaction has nothing to say.
+        if matches!(event_kind, EventKind::TransactionEventTriggered)
+            && let Some(Some(transaction)) = evse.transactions.get_mut(connector_id)
+        {
+            transaction.seq_no += 1;
+            effects.push(ChargePointEffect::TransactionEvent(
+                TransactionEventOccurred {
+                    evse_id,
+                    connector_id,
+                    kind: TransactionEventKind::Updated(TransactionUpdateReason::Trigger),
+                    transaction: transaction.clone(),
+                    offline: false,
+                },
+            ));
+        }
         let limit_confirmed = confirmed_limit.is_some_and(|limit_ma| {
             let Some(slot) = evse.applied_charging_limits.get_mut(connector_id) else {
                 return false;
@@ -2135,6 +2613,21 @@ impl ChargePointState {
             }
             false
         });
+        // I12: the driver's new tariff is in force from here, and the CSMS hears it on the
+        // transaction it prices.
+        if tariff_recorded && let Some(Some(transaction)) = evse.transactions.get_mut(connector_id)
+        {
+            transaction.seq_no += 1;
+            effects.push(ChargePointEffect::TransactionEvent(
+                TransactionEventOccurred {
+                    evse_id,
+                    connector_id,
+                    kind: TransactionEventKind::Updated(TransactionUpdateReason::TariffChanged),
+                    transaction: transaction.clone(),
+                    offline: false,
+                },
+            ));
+        }
         let running_cost_recorded = running_cost_update.is_some_and(|(cost, total)| {
             if evse
                 .transactions
@@ -2214,7 +2707,9 @@ impl ChargePointState {
             // subscriber must see - the CSMS-facing snapshot, persistence, and the projection all
             // read what the transaction is running under (CV15).
             || limit_set
-            || elapsed_reported;
+            || elapsed_reported
+            || ended_sample_recorded
+            || transaction_boundary;
         // E05 (CV2.5): the last allowance ran out. Checked after the sample has been recorded, so
         // the stop is decided against the reading the CSMS will also see, and dispatched through
         // the ordinary stop path so the transaction ends exactly as any other does.
@@ -2228,6 +2723,7 @@ impl ChargePointState {
                     transaction.stop_at_energy_wh.is_some_and(|limit| {
                         transaction
                             .last_meter_sample
+                            .as_ref()
                             .is_some_and(|sample| sample.energy_wh >= limit)
                     })
                 });
@@ -2445,6 +2941,123 @@ impl ChargePointState {
         }
     }
 
+    /// What [`Self::report_fanned_out_statuses`] will need to compare against once `event` is
+    /// applied - taken before it is, and only as wide as what `event` can move.
+    fn reported_before(&self, event: &ChargePointEvent) -> ReportedBefore {
+        match event {
+            // The hot path - every meter sample arrives here - and also the narrowest: a connector
+            // event moves no EVSE or station level, so what it can change besides the connector's
+            // own transition is that connector's overlay (its transaction ending while a
+            // `Scheduled` change waits), and nothing on any other connector.
+            ChargePointEvent::Evse {
+                evse_id,
+                event: EvseEvent::Connector { connector_id, .. },
+            } => ReportedBefore::Connector {
+                evse_id: *evse_id,
+                connector_id: *connector_id,
+                status: self.reported_connector_status(*evse_id, *connector_id),
+            },
+            // What moves the station, an EVSE, the scheduled changes, or several connectors'
+            // transactions at once.
+            ChargePointEvent::BootCompleted
+            | ChargePointEvent::SetAvailable
+            | ChargePointEvent::SetUnavailable
+            | ChargePointEvent::FaultCleared
+            | ChargePointEvent::HardwareFault
+            | ChargePointEvent::ResetRequested { .. }
+            | ChargePointEvent::UnavailabilityScheduled { .. }
+            | ChargePointEvent::ScheduledUnavailabilityCancelled { .. }
+            | ChargePointEvent::PersistedScheduledUnavailabilityRestored { .. }
+            | ChargePointEvent::PersistedTransactionsRestored { .. }
+            | ChargePointEvent::PersistedReservationsRestored { .. }
+            | ChargePointEvent::Evse { .. } => ReportedBefore::Every(
+                self.evses
+                    .iter()
+                    .enumerate()
+                    .map(|(evse_id, evse)| {
+                        (0..evse.connectors.len())
+                            .filter_map(|connector_id| {
+                                self.reported_connector_status(evse_id, connector_id)
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            ),
+            _ => ReportedBefore::Unmoved,
+        }
+    }
+
+    /// Reports the connectors whose [`Self::reported_connector_status`] moved without a
+    /// transition of their own - an EVSE or the whole charge point going `Unavailable`/`Faulted`
+    /// or coming back - and, for 1.6J's `connectorId` 0, the charge point's own status.
+    ///
+    /// A connector whose own transition was already reported by this `apply` with the status it
+    /// now derives is skipped: a second report would be a duplicate on the wire. One reported
+    /// before its transaction ended under a `Scheduled` change is not - ending the transaction is
+    /// what took it out of service. The station report is skipped on leaving `Booting`, for the
+    /// reason [`reported_connector_status`] gives.
+    fn report_fanned_out_statuses(
+        &self,
+        reported_before: &ReportedBefore,
+        lifecycle_before: LifecycleState,
+        effects: &mut Vec<ChargePointEffect>,
+    ) {
+        let already_reported = |evse_id: usize, connector_id: usize, status: ConnectorStatus| {
+            effects.iter().rev().find_map(|effect| match effect {
+                ChargePointEffect::StatusNotification(changed)
+                    if changed.evse_id == evse_id && changed.connector_id == connector_id =>
+                {
+                    Some(changed.status == status)
+                }
+                _ => None,
+            }) == Some(true)
+        };
+        let moved = |evse_id: usize, connector_id: usize, before: Option<ConnectorStatus>| {
+            let connector = *self.evses.get(evse_id)?.connectors.get(connector_id)?;
+            let status = self.reported_connector_status(evse_id, connector_id)?;
+            (before != Some(status) && !already_reported(evse_id, connector_id, status)).then_some(
+                ChargePointEffect::StatusNotification(ConnectorStatusChanged {
+                    evse_id,
+                    connector_id,
+                    status,
+                    connector_state: connector,
+                }),
+            )
+        };
+        let fanned_out: Vec<ChargePointEffect> = match reported_before {
+            ReportedBefore::Unmoved => Vec::new(),
+            ReportedBefore::Connector {
+                evse_id,
+                connector_id,
+                status,
+            } => moved(*evse_id, *connector_id, *status)
+                .into_iter()
+                .collect(),
+            ReportedBefore::Every(statuses) => self
+                .evses
+                .iter()
+                .enumerate()
+                .flat_map(|(evse_id, evse)| {
+                    (0..evse.connectors.len()).map(move |connector_id| (evse_id, connector_id))
+                })
+                .filter_map(|(evse_id, connector_id)| {
+                    let before = statuses
+                        .get(evse_id)
+                        .and_then(|statuses| statuses.get(connector_id))
+                        .copied();
+                    moved(evse_id, connector_id, before)
+                })
+                .collect(),
+        };
+        effects.extend(fanned_out);
+
+        let status = station_status(self.lifecycle);
+        if lifecycle_before != LifecycleState::Booting && station_status(lifecycle_before) != status
+        {
+            effects.push(ChargePointEffect::StationStatusNotification(status));
+        }
+    }
+
     /// Cascades a hardware fault (`detected = true`) or its clearing (`detected = false`) from
     /// one EVSE down to every connector it owns, via the same `apply_connector_event` path a
     /// direct connector-level fault takes - so e.g. a shared-power-source failure forces every
@@ -2561,14 +3174,15 @@ impl ChargePointState {
     /// CV8's, `running_costs` is the CSMS's - so this reads whichever exists, preferring the local
     /// one exactly as those requirements order them.
     ///
-    /// # Suspend, never end
+    /// # Suspend here, end on the signal
     ///
     /// E16.FR.06 would have the transaction *end* rather than suspend when `TxCtrlr.TxStopPoint`
-    /// contains `EnergyTransfer`. It cannot here: [`TxStopPoint`] models the three points this
-    /// crate can observe and `EnergyTransfer` is not among them, so a `SetVariables` naming it is
-    /// `Rejected` by CV3's validation against the declared `values_list`. The station can
-    /// therefore never be configured into FR.06's branch, and FR.05 - suspend, report
-    /// `SuspendedEVSE` with the limit's own trigger reason - is the only answer it can give.
+    /// contains `EnergyTransfer`. This always takes FR.05's path - suspend, report
+    /// `SuspendedEVSE` with the limit's own trigger reason - and FR.06 follows from it: the zero
+    /// current commanded here stops the energy, the binding reports
+    /// [`ConnectorEvent::EnergyTransferStopped`], and that is the moment an `EnergyTransfer` stop
+    /// point ends the transaction at. Ending it here instead would end it on a command rather
+    /// than on energy having actually stopped.
     ///
     /// Suspending means *commanding* zero current, not merely recording a state: OCPP's
     /// `SuspendedEVSE` is a report, and a station that reported it while energy kept flowing
@@ -2601,10 +3215,11 @@ impl ChargePointState {
         let cost = local_cost.or(csms_cost);
         let delivered_wh = transaction
             .energy_start_wh
-            .zip(transaction.last_meter_sample)
+            .zip(transaction.last_meter_sample.as_ref())
             .map(|(start, sample)| (sample.energy_wh - start) as f64);
         let soc = transaction
             .last_meter_sample
+            .as_ref()
             .and_then(|sample| sample.soc_percent);
 
         let reached = None
@@ -2766,6 +3381,24 @@ impl ChargePointState {
     }
 }
 
+/// The connectors' reported statuses from before an event, as far as
+/// [`ChargePointState::report_fanned_out_statuses`] needs them - see
+/// [`ChargePointState::reported_before`].
+#[derive(Debug, Clone, PartialEq)]
+enum ReportedBefore {
+    /// The event cannot move what any connector reports except through that connector's own
+    /// transition, which reports itself.
+    Unmoved,
+    /// A connector event: the one connector whose report it can move, and what that was.
+    Connector {
+        evse_id: usize,
+        connector_id: usize,
+        status: Option<ConnectorStatus>,
+    },
+    /// Every connector's, indexed `[evse_id][connector_id]`.
+    Every(Vec<Vec<ConnectorStatus>>),
+}
+
 /// Which of the events `apply_connector_event` special-cases this one is - captured before
 /// `ConnectorState::apply` consumes it, because two later blocks still need to know (CV2.5).
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -2784,16 +3417,52 @@ enum EventKind {
     },
     /// How long the transaction has been running, from the clock-bearing sweep (E16.FR.09, CV21).
     TransactionElapsed(i64),
+    /// `TxUpdatedInterval` is up, from the clock-bearing sampling loop (J02).
+    TransactionMeterValuesDue,
+    /// The CSMS triggered a `TransactionEvent` (F06).
+    TransactionEventTriggered,
+    /// `TxEndedInterval` is up, from the same loop, stamped with its clock's reading (J02).
+    TransactionEndedSampleDue(DateTime<Utc>),
+    /// An `AlignedDataCtrlr.Interval` boundary, from the clock-aligned loop, stamped likewise.
+    TransactionAlignedSampleDue(DateTime<Utc>),
     Other,
 }
 
+/// The hardware signals that mark a moment in a session without necessarily moving the connector -
+/// a `TxCtrlr.TxStartPoint`/`TxStopPoint` (CV2.2), or the EV dropping off the line - captured
+/// before `ConnectorState::apply` consumes the event, for the reason [`EventKind`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SessionSignal {
+    /// [`ConnectorEvent::ParkingBayOccupied`].
+    ParkingBayOccupied,
+    /// [`ConnectorEvent::ParkingBayVacated`].
+    ParkingBayVacated,
+    /// [`ConnectorEvent::EnergyTransferStarted`].
+    EnergyTransferStarted,
+    /// [`ConnectorEvent::EnergyTransferStopped`].
+    EnergyTransferStopped,
+    /// A [`ConnectorEvent::MeterValueSampled`] whose reading carries the meter's signature.
+    DataSigned,
+    /// [`ConnectorEvent::EvCommunicationLost`], or the cable leaving the EV.
+    EvCommunicationLost,
+}
+
+/// What one connector event did, as `advance_transaction` sees it: the transition it made - often
+/// none at all - and the session signal it carried, if it was one (CV2.2).
+#[derive(Debug, Clone, Copy)]
+struct ConnectorMoment {
+    previous_state: ConnectorState,
+    new_state: ConnectorState,
+    signal: Option<SessionSignal>,
+}
+
 /// Where in a session a transaction begins and ends - `TxCtrlr.TxStartPoint`/`TxStopPoint`
 /// (CV2.2), paired because `advance_transaction` needs both on every call and neither means much
 /// without the other.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct TransactionPoints {
-    tx_start_point: TxStartPoint,
-    tx_stop_point: TxStopPoint,
+    tx_start_points: TxPoints<TxStartPoint>,
+    tx_stop_points: TxPoints<TxStopPoint>,
 }
 
 /// How a transaction came to exist - everything `advance_transaction` records on a new
@@ -2806,27 +3475,34 @@ struct TransactionOrigin {
     remote_start_id: Option<i64>,
     /// The reservation it consumed, if the connector had honoured one.
     reservation_id: Option<i64>,
+    /// The connector's latest reading, if it has one - see [`Transaction::start_meter_sample`].
+    meter_start: Option<MeterSample>,
 }
 
-/// Advances (or starts, or ends) the transaction in `slot` for a connector moving from
-/// `previous_state` to `new_state`, returning the TransactionEvent to report, if any.
-///
-/// `event_stop_reason` is the `StopReason` carried by the triggering
-/// `ConnectorEvent::ChargingStopped`/`ConnectorEvent::ResetRequested`, if that is what caused this
-/// transition. `origin` carries everything recorded on a *new* transaction about how it began -
-/// see [`TransactionOrigin`].
-/// Whether moving from `previous_state` to `new_state` is the moment `tx_start_point` names
-/// (CV2.2).
+/// Whether `moment` is the one `tx_start_point` names (CV2.2).
 ///
-/// Each point is one transition, and they are checked exclusively rather than cumulatively: with
-/// `PowerPathClosed` configured, locking the cable and authorizing both happen without a
-/// transaction existing, and the contactor closing is what creates it.
-fn starts_transaction(
-    previous_state: ConnectorState,
-    new_state: ConnectorState,
-    tx_start_point: TxStartPoint,
-) -> bool {
+/// Each point is one transition or one signal: with only `PowerPathClosed` configured, locking
+/// the cable and authorizing both happen without a transaction existing, and the contactor closing
+/// is what creates it. With several configured, `advance_transaction` asks this of each and starts
+/// the transaction on the first that answers yes.
+fn starts_transaction(moment: ConnectorMoment, tx_start_point: TxStartPoint) -> bool {
+    let ConnectorMoment {
+        previous_state,
+        new_state,
+        signal,
+    } = moment;
     match tx_start_point {
+        // The bay sensor saw a vehicle. Nothing about the connector need have moved yet, but a
+        // connector that is out of service cannot be the start of anything.
+        TxStartPoint::ParkingBayOccupancy => {
+            signal == Some(SessionSignal::ParkingBayOccupied)
+                && !matches!(
+                    new_state,
+                    ConnectorState::Unavailable
+                        | ConnectorState::Faulted
+                        | ConnectorState::FaultedSafe
+                )
+        }
         // The cable is in and latched. Everything after this - including an authorization that
         // is refused - happens inside a transaction the CSMS already knows about.
         TxStartPoint::EVConnected => {
@@ -2840,33 +3516,61 @@ fn starts_transaction(
                 ConnectorState::Authorizing | ConnectorState::Locked
             ) && new_state == ConnectorState::Starting
         }
+        // A signed reading, once the session is authorized. One taken before that belongs to no
+        // session yet - the meter signing its idle register is not a driver's start value.
+        TxStartPoint::DataSigned => {
+            signal == Some(SessionSignal::DataSigned)
+                && matches!(
+                    new_state,
+                    ConnectorState::Starting
+                        | ConnectorState::Charging
+                        | ConnectorState::SuspendedEv
+                        | ConnectorState::SuspendedEvse
+                )
+        }
         // The contactor closed, so energy can flow.
         TxStartPoint::PowerPathClosed => {
             previous_state == ConnectorState::Starting && new_state == ConnectorState::Charging
         }
+        // Energy does flow. Only believed of a connector whose contactor is closed and which is
+        // not suspended - anything else is a measurement this crate cannot reconcile.
+        TxStartPoint::EnergyTransfer => {
+            signal == Some(SessionSignal::EnergyTransferStarted)
+                && new_state == ConnectorState::Charging
+        }
     }
 }
 
-/// Whether moving from `previous_state` to `new_state` is the moment `tx_stop_point` names
-/// (CV2.2).
+/// Whether `moment` is the one `tx_stop_point` names for `transaction` (CV2.2).
 ///
-/// The counterpart of [`starts_transaction`], and checked the same way - exclusively, one
-/// transition per point. Which transitions those are is what makes each point real:
+/// The counterpart of [`starts_transaction`], and checked the same way - one moment per point,
+/// asked of each configured point, the first that answers yes ending the transaction. Which
+/// moments those are is what makes each point real:
 ///
 /// - **`Authorized`** - entering a stop. This crate's stop path is driven by an explicit
 ///   [`ConnectorEvent::ChargingStopped`] (or a `Reset`, or E05's revocation), and *that event
 ///   arriving* is the authorization for this session ending; there is no separate "the driver's
 ///   permission lapsed" signal to observe.
+/// - **`EnergyTransfer`** - [`ConnectorEvent::EnergyTransferStopped`], whatever stopped it.
 /// - **`PowerPathClosed`** - the contactor confirmed open. Both stopping states settle here, and
 ///   this is what this crate did unconditionally before `TxStopPoint` was honoured.
+/// - **`DataSigned`** - a signed reading once energy has stopped: while the stop settles, or
+///   afterwards on a transaction that was energised. A signed reading taken mid-charge is the
+///   meter's periodic output, not the session's end value.
 /// - **`EVConnected`** - the cable left the connector, which is the connector returning to
 ///   `Available` from `Connected`. The transaction therefore survives the whole unlock, which is
 ///   the point: it is billing for the bay being occupied.
+/// - **`ParkingBayOccupancy`** - [`ConnectorEvent::ParkingBayVacated`].
 fn ends_transaction(
-    previous_state: ConnectorState,
-    new_state: ConnectorState,
+    moment: ConnectorMoment,
+    transaction: &Transaction,
     tx_stop_point: TxStopPoint,
 ) -> bool {
+    let ConnectorMoment {
+        previous_state,
+        new_state,
+        signal,
+    } = moment;
     match tx_stop_point {
         TxStopPoint::Authorized => {
             matches!(
@@ -2880,14 +3584,31 @@ fn ends_transaction(
                 ConnectorState::Stopping | ConnectorState::StoppingLocked
             )
         }
+        TxStopPoint::EnergyTransfer => signal == Some(SessionSignal::EnergyTransferStopped),
         TxStopPoint::PowerPathClosed => {
             (previous_state == ConnectorState::Stopping && new_state == ConnectorState::Finishing)
                 || (previous_state == ConnectorState::StoppingLocked
                     && new_state == ConnectorState::Locked)
         }
+        TxStopPoint::DataSigned => {
+            signal == Some(SessionSignal::DataSigned)
+                && match new_state {
+                    ConnectorState::Stopping
+                    | ConnectorState::StoppingLocked
+                    | ConnectorState::Finishing => true,
+                    ConnectorState::Starting
+                    | ConnectorState::Charging
+                    | ConnectorState::SuspendedEv
+                    | ConnectorState::SuspendedEvse => false,
+                    // The connector is idle again, or was never anything else: only a transaction
+                    // that drew energy has an end value to sign.
+                    _ => transaction.energy_start_wh.is_some(),
+                }
+        }
         TxStopPoint::EVConnected => {
             previous_state == ConnectorState::Connected && new_state == ConnectorState::Available
         }
+        TxStopPoint::ParkingBayOccupancy => signal == Some(SessionSignal::ParkingBayVacated),
     }
 }
 
@@ -2897,27 +3618,45 @@ fn charging_state_for(state: ConnectorState) -> TransactionChargingState {
         ConnectorState::Charging => TransactionChargingState::Charging,
         ConnectorState::SuspendedEv => TransactionChargingState::SuspendedEV,
         ConnectorState::SuspendedEvse => TransactionChargingState::SuspendedEVSE,
+        // A transaction begun by the bay sensor, before any cable.
+        ConnectorState::Available | ConnectorState::Reserved => TransactionChargingState::Idle,
         _ => TransactionChargingState::EvConnected,
     }
 }
 
+/// Advances (or starts, or ends) the transaction in `slot` for a connector moving from
+/// `previous_state` to `new_state`, returning the TransactionEvent to report, if any.
+///
+/// `event_stop_reason` is the `StopReason` carried by the triggering
+/// `ConnectorEvent::ChargingStopped`/`ConnectorEvent::ResetRequested`/
+/// `ConnectorEvent::CableDisconnected`, if that is what caused this transition. `origin` carries
+/// everything recorded on a *new* transaction about how it began - see [`TransactionOrigin`].
 fn advance_transaction(
     slot: &mut Option<Transaction>,
     next_transaction_id: &mut u64,
-    previous_state: ConnectorState,
-    new_state: ConnectorState,
+    moment: ConnectorMoment,
     event_stop_reason: Option<StopReason>,
     origin: TransactionOrigin,
     points: TransactionPoints,
 ) -> Option<(TransactionEventKind, Transaction)> {
     let TransactionPoints {
-        tx_start_point,
-        tx_stop_point,
+        tx_start_points,
+        tx_stop_points,
     } = points;
-    // CV2.2: which transition begins a transaction is `TxCtrlr.TxStartPoint`, not a constant.
-    // Checked before the arms below because with an earlier start point the *same* transitions
-    // that used to begin one now merely update one that already exists.
-    if slot.is_none() && starts_transaction(previous_state, new_state, tx_start_point) {
+    let ConnectorMoment {
+        previous_state,
+        new_state,
+        signal,
+    } = moment;
+    // CV2.2: which transition begins a transaction is `TxCtrlr.TxStartPoint`, not a constant -
+    // any configured member's condition being met begins it. Checked before the arms below
+    // because with an earlier start point the *same* transitions that used to begin one now
+    // merely update one that already exists.
+    if slot.is_none()
+        && tx_start_points
+            .iter()
+            .any(|point| starts_transaction(moment, point))
+    {
         let id = TransactionId(*next_transaction_id);
         *next_transaction_id += 1;
         let transaction = Transaction {
@@ -2936,11 +3675,13 @@ fn advance_transaction(
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: origin.meter_start,
         };
         *slot = Some(transaction.clone());
         return Some((TransactionEventKind::Started, transaction));
     }
-    // Recorded the moment the stop begins, whatever `tx_stop_point` says: with a later stop point
+    // Recorded the moment the stop begins, whatever `tx_stop_points` says: with a later stop point
     // the transaction outlives this transition, and by the time it does end the event that caused
     // the stop is gone. This is why `stoppedReason` survives an `EVConnected` stop point at all.
     if matches!(
@@ -2956,10 +3697,15 @@ fn advance_transaction(
     {
         transaction.stop_reason = event_stop_reason;
     }
-    // CV2.2: which transition ends a transaction is `TxCtrlr.TxStopPoint`. Checked before the
-    // arms below for the same reason `starts_transaction` is: with an earlier stop point, a
-    // transition that used to merely update the transaction is now the one that closes it.
-    if ends_transaction(previous_state, new_state, tx_stop_point) {
+    // CV2.2: which transition ends a transaction is `TxCtrlr.TxStopPoint` - any configured
+    // member's condition ceasing to hold ends it. Checked before the arms below for the same
+    // reason `starts_transaction` is: with an earlier stop point, a transition that used to merely
+    // update the transaction is now the one that closes it.
+    if slot.as_ref().is_some_and(|transaction| {
+        tx_stop_points
+            .iter()
+            .any(|point| ends_transaction(moment, transaction, point))
+    }) {
         let mut transaction = slot.take()?;
         // The bay is only free once the cable is out; every earlier stop point leaves it in.
         transaction.charging_state = if new_state == ConnectorState::Available {
@@ -2967,17 +3713,45 @@ fn advance_transaction(
         } else {
             TransactionChargingState::EvConnected
         };
+        // A session nobody stopped, ended by the vehicle leaving - the cable out, or the bay
+        // empty - ended because the EV departed, and says so rather than `StopAuthorized`.
+        if (previous_state == ConnectorState::Connected && new_state == ConnectorState::Available)
+            || signal == Some(SessionSignal::ParkingBayVacated)
+        {
+            transaction
+                .stop_reason
+                .get_or_insert(StopReason::EVDisconnected);
+        }
         transaction.seq_no += 1;
         return Some((TransactionEventKind::Ended, transaction));
     }
-    match (previous_state, new_state) {
-        // Reached from `Authorizing` (a physically presented id token was authorized) or
-        // directly from `Locked` (a CSMS-initiated `RequestStartTransaction` - see
-        // `docs/ROADMAP.md` §6) - either way, entering `Starting` from elsewhere always begins a
-        // new transaction. Excludes `Starting` -> `Starting` (e.g. a meter sample applied while
-        // still `Starting`, which doesn't change connector state) - that must stay a no-op.
-
-        // Every move between "energy is flowing" and "energy is paused, by one side or the
+    // What a signal that moves no connector state is still worth telling the CSMS about.
+    let signal_reason = match signal {
+        Some(SessionSignal::ParkingBayOccupied) => Some(TransactionUpdateReason::EvDetected),
+        Some(SessionSignal::ParkingBayVacated) => Some(TransactionUpdateReason::EvDeparted),
+        // Only while the EV was meant to be drawing energy: a session already stopping has
+        // nothing left to lose.
+        Some(SessionSignal::EvCommunicationLost)
+            if matches!(
+                new_state,
+                ConnectorState::Starting
+                    | ConnectorState::Charging
+                    | ConnectorState::SuspendedEv
+                    | ConnectorState::SuspendedEvse
+            ) =>
+        {
+            Some(TransactionUpdateReason::EvCommunicationLost)
+        }
+        _ => None,
+    };
+    match (previous_state, new_state) {
+        // Reached from `Authorizing` (a physically presented id token was authorized) or
+        // directly from `Locked` (a CSMS-initiated `RequestStartTransaction` - see
+        // `docs/ROADMAP.md` §6) - either way, entering `Starting` from elsewhere always begins a
+        // new transaction. Excludes `Starting` -> `Starting` (e.g. a meter sample applied while
+        // still `Starting`, which doesn't change connector state) - that must stay a no-op.
+
+        // Every move between "energy is flowing" and "energy is paused, by one side or the
         // other" is a charging-state change on the same running transaction - which is how 2.x
         // expresses suspension at all (its connector status has no `SuspendedEV`; 1.6J's does,
         // reported separately by that version's StatusNotification adapter). Reaching `Charging`
@@ -3002,10 +3776,38 @@ fn advance_transaction(
             let transaction = slot.as_mut()?;
             transaction.charging_state = charging_state;
             transaction.seq_no += 1;
-            Some((
-                TransactionEventKind::Updated(TransactionUpdateReason::ChargingStateChanged),
-                transaction.clone(),
-            ))
+            // The EV dropping off the line is why it is now `SuspendedEV`, and OCPP has a reason
+            // for exactly that.
+            let reason = match signal_reason {
+                Some(TransactionUpdateReason::EvCommunicationLost) => {
+                    TransactionUpdateReason::EvCommunicationLost
+                }
+                _ => TransactionUpdateReason::ChargingStateChanged,
+            };
+            Some((TransactionEventKind::Updated(reason), transaction.clone()))
+        }
+        // The cable arrived on a transaction some earlier start point began, or left one whose stop
+        // point is still ahead. Either way the session is still running and the CSMS is owed the
+        // change in what it is doing. A cable leaving is the EV's communication being lost, as
+        // for `SessionSignal::EvCommunicationLost`; only the bay sensor reports EV arrival or
+        // departure.
+        (ConnectorState::Available | ConnectorState::Reserved, ConnectorState::Connected)
+        | (_, ConnectorState::Available)
+            if previous_state != new_state =>
+        {
+            let transaction = slot.as_mut()?;
+            let charging_state = charging_state_for(new_state);
+            if transaction.charging_state == charging_state {
+                return None;
+            }
+            transaction.charging_state = charging_state;
+            transaction.seq_no += 1;
+            let reason = if new_state == ConnectorState::Available {
+                TransactionUpdateReason::EvCommunicationLost
+            } else {
+                TransactionUpdateReason::CablePluggedIn
+            };
+            Some((TransactionEventKind::Updated(reason), transaction.clone()))
         }
         // A fault ends the transaction whatever `TxStopPoint` says: the charge point can no longer
         // observe the conditions a later stop point is waiting for, so honouring one here would
@@ -3016,6 +3818,16 @@ fn advance_transaction(
             transaction.seq_no += 1;
             Some((TransactionEventKind::Ended, transaction))
         }
+        // The vehicle came or went, or stopped communicating, and the connector had nowhere to
+        // move - the transaction is unchanged but for the CSMS knowing.
+        _ if previous_state == new_state && signal_reason.is_some() => {
+            let transaction = slot.as_mut()?;
+            transaction.seq_no += 1;
+            Some((
+                TransactionEventKind::Updated(signal_reason?),
+                transaction.clone(),
+            ))
+        }
         _ => None,
     }
 }
@@ -3023,11 +3835,32 @@ fn advance_transaction(
 /// Records a meter reading against the connector's active transaction, if it's currently
 /// `Charging` - meter values are only meaningful (and only reported) while energy is actually
 /// flowing.
+///
+/// `report` is false while `SampledDataCtrlr.TxUpdatedInterval` is set: the reading is recorded
+/// exactly as it otherwise would be, but reported later, by
+/// [`ConnectorEvent::TransactionMeterValuesDue`].
+///
+/// A signed reading is the exception to both: it is recorded whatever the transaction is doing
+/// and reported at once, as `SignedDataReceived`. A meter signs the readings a bill rests on, and
+/// those are rarely taken while energy is flowing - nor can the CSMS be handed one later that an
+/// unsigned reading has since replaced.
 fn apply_meter_sample(
     slot: &mut Option<Transaction>,
     sample: MeterSample,
+    report: bool,
 ) -> Option<(TransactionEventKind, Transaction)> {
     let transaction = slot.as_mut()?;
+    if sample.signed.is_some() {
+        if transaction.charging_state == TransactionChargingState::Charging {
+            transaction.energy_start_wh.get_or_insert(sample.energy_wh);
+        }
+        transaction.last_meter_sample = Some(sample);
+        transaction.seq_no += 1;
+        return Some((
+            TransactionEventKind::Updated(TransactionUpdateReason::SignedDataReceived),
+            transaction.clone(),
+        ));
+    }
     if transaction.charging_state != TransactionChargingState::Charging {
         return None;
     }
@@ -3036,6 +3869,9 @@ fn apply_meter_sample(
     // rather than wherever the meter's lifetime total happened to stand.
     transaction.energy_start_wh.get_or_insert(sample.energy_wh);
     transaction.last_meter_sample = Some(sample);
+    if !report {
+        return None;
+    }
     transaction.seq_no += 1;
     Some((
         TransactionEventKind::Updated(TransactionUpdateReason::MeterValuePeriodic),
@@ -3178,6 +4014,180 @@ mod tests {
         )));
     }
 
+    fn status_notifications(effects: &[ChargePointEffect]) -> Vec<ConnectorStatusChanged> {
+        effects
+            .iter()
+            .filter_map(|effect| match effect {
+                ChargePointEffect::StatusNotification(changed) => Some(*changed),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn accepted_state(connector_counts: impl IntoIterator<Item = usize>) -> ChargePointState {
+        let mut state = ChargePointState::new(connector_counts);
+        state.apply(ChargePointEvent::RegistrationStatusReceived(
+            RegistrationStatus::Accepted,
+        ));
+        state
+    }
+
+    // The gap `docs/ROADMAP.md` §7 recorded: an EVSE made unavailable left every connector on it
+    // reporting `Available`, so the CSMS kept offering a bay out of service.
+    #[test]
+    fn making_an_evse_unavailable_reports_each_of_its_connectors() {
+        let mut state = accepted_state([2, 1]);
+
+        let effects = state.apply(ChargePointEvent::Evse {
+            evse_id: 0,
+            event: EvseEvent::SetUnavailable,
+        });
+
+        assert_eq!(
+            status_notifications(&effects),
+            [0, 1].map(|connector_id| ConnectorStatusChanged {
+                evse_id: 0,
+                connector_id,
+                status: crate::state::ConnectorStatus::Unavailable,
+                connector_state: ConnectorState::Available,
+            })
+        );
+        assert_eq!(
+            state.reported_connector_status(1, 0),
+            Some(crate::state::ConnectorStatus::Available)
+        );
+
+        let effects = state.apply(ChargePointEvent::Evse {
+            evse_id: 0,
+            event: EvseEvent::SetAvailable,
+        });
+        assert_eq!(status_notifications(&effects).len(), 2);
+        assert_eq!(
+            state.reported_connector_status(0, 0),
+            Some(crate::state::ConnectorStatus::Available)
+        );
+    }
+
+    #[test]
+    fn making_the_charge_point_unavailable_reports_every_connector_and_the_station() {
+        let mut state = accepted_state([1, 1]);
+
+        let effects = state.apply(ChargePointEvent::SetUnavailable);
+
+        let reported: Vec<_> = status_notifications(&effects)
+            .iter()
+            .map(|changed| (changed.evse_id, changed.connector_id, changed.status))
+            .collect();
+        assert_eq!(
+            reported,
+            [
+                (0, 0, crate::state::ConnectorStatus::Unavailable),
+                (1, 0, crate::state::ConnectorStatus::Unavailable),
+            ]
+        );
+        assert!(
+            effects.contains(&ChargePointEffect::StationStatusNotification(
+                crate::state::ConnectorStatus::Unavailable
+            ))
+        );
+    }
+
+    // A connector's own transition under an out-of-service EVSE still reports, and reports the
+    // EVSE's status rather than its own.
+    #[test]
+    fn a_connector_transition_inside_an_unavailable_evse_reports_the_evses_status() {
+        let mut state = accepted_state([1]);
+        state.apply(ChargePointEvent::Evse {
+            evse_id: 0,
+            event: EvseEvent::SetUnavailable,
+        });
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::CableConnected);
+
+        assert_eq!(
+            status_notifications(&effects),
+            [ConnectorStatusChanged {
+                evse_id: 0,
+                connector_id: 0,
+                status: crate::state::ConnectorStatus::Unavailable,
+                connector_state: ConnectorState::Connected,
+            }]
+        );
+    }
+
+    #[test]
+    fn a_faulted_connector_keeps_reporting_faulted_inside_an_unavailable_evse() {
+        let mut state = accepted_state([2]);
+        state.apply(ChargePointEvent::Evse {
+            evse_id: 0,
+            event: EvseEvent::Connector {
+                connector_id: 1,
+                event: ConnectorEvent::FaultDetected,
+            },
+        });
+
+        let effects = state.apply(ChargePointEvent::Evse {
+            evse_id: 0,
+            event: EvseEvent::SetUnavailable,
+        });
+
+        let reported: Vec<_> = status_notifications(&effects)
+            .iter()
+            .map(|changed| changed.connector_id)
+            .collect();
+        assert_eq!(
+            reported,
+            [0],
+            "the faulted connector's report did not change"
+        );
+        assert_eq!(
+            state.reported_connector_status(0, 1),
+            Some(crate::state::ConnectorStatus::Faulted)
+        );
+    }
+
+    // The cascade already moves every connector itself; the fan-out must not report them twice.
+    #[test]
+    fn a_charge_point_fault_reports_each_connector_once() {
+        let mut state = accepted_state([1, 1]);
+
+        let effects = state.apply(ChargePointEvent::HardwareFault);
+
+        let reported: Vec<_> = status_notifications(&effects)
+            .iter()
+            .map(|changed| (changed.evse_id, changed.connector_id, changed.status))
+            .collect();
+        assert_eq!(
+            reported,
+            [
+                (0, 0, crate::state::ConnectorStatus::Faulted),
+                (1, 0, crate::state::ConnectorStatus::Faulted),
+            ]
+        );
+        assert!(
+            effects.contains(&ChargePointEffect::StationStatusNotification(
+                crate::state::ConnectorStatus::Faulted
+            ))
+        );
+    }
+
+    // B01.FR.05's sweep reports the station after registration, so leaving `Booting` doesn't.
+    #[test]
+    fn registration_does_not_report_the_station_status_itself() {
+        let mut state = ChargePointState::new([1]);
+
+        let effects = state.apply(ChargePointEvent::RegistrationStatusReceived(
+            RegistrationStatus::Accepted,
+        ));
+
+        assert!(
+            !effects
+                .iter()
+                .any(|effect| matches!(effect, ChargePointEffect::StationStatusNotification(_)))
+        );
+        assert!(status_notifications(&effects).is_empty());
+    }
+
     fn apply_connector_event(
         state: &mut ChargePointState,
         event: ConnectorEvent,
@@ -3615,6 +4625,8 @@ mod tests {
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         };
         assert_eq!(
             state.evses[0].transactions[0],
@@ -4062,6 +5074,8 @@ mod tests {
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         };
         assert_eq!(
             state.evses[0].transactions[0],
@@ -4105,6 +5119,8 @@ mod tests {
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         };
         assert_eq!(
             state.evses[0].transactions[0],
@@ -4121,6 +5137,43 @@ mod tests {
         )));
     }
 
+    /// The reading a transaction started from is the one the connector held when it started,
+    /// signature included - not the first taken once energy was already flowing.
+    #[test]
+    fn a_transaction_records_the_connectors_reading_at_its_start() {
+        let mut state = ChargePointState::new([1]);
+        let before = MeterSample {
+            energy_wh: 1_000,
+            signed: Some(alloc::boxed::Box::new(crate::state::SignedMeterValue {
+                signed_meter_data: "OCMF|begin".into(),
+                encoding_method: "OCMF".into(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(before.clone()),
+        );
+
+        plug_in_and_authorize(&mut state);
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingAuthorized(test_id_token()),
+        );
+        apply_connector_event(&mut state, ConnectorEvent::ContactorClosed);
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(MeterSample {
+                energy_wh: 1_200,
+                ..Default::default()
+            }),
+        );
+
+        let transaction = state.evses[0].transactions[0].as_ref().expect("running");
+        assert_eq!(transaction.start_meter_sample, Some(before));
+    }
+
     #[test]
     fn a_meter_reading_while_charging_updates_the_transaction_and_is_reported() {
         let mut state = ChargePointState::new([1]);
@@ -4135,7 +5188,10 @@ mod tests {
             energy_wh: 1_500,
             ..Default::default()
         };
-        let effects = apply_connector_event(&mut state, ConnectorEvent::MeterValueSampled(sample));
+        let effects = apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(sample.clone()),
+        );
 
         let expected_transaction = Transaction {
             id: TransactionId(0),
@@ -4143,7 +5199,7 @@ mod tests {
             charging_state: TransactionChargingState::Charging,
             stop_reason: None,
             seq_no: 2,
-            last_meter_sample: Some(sample),
+            last_meter_sample: Some(sample.clone()),
             priority_charging: false,
             remote_start_id: None,
             reservation_id: None,
@@ -4156,6 +5212,8 @@ mod tests {
             // must still measure from the session's real start.
             energy_start_wh: Some(sample.energy_wh),
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         };
         assert_eq!(
             state.evses[0].transactions[0],
@@ -4193,7 +5251,7 @@ mod tests {
         assert_eq!(
             state.evses[0].transactions[0]
                 .as_ref()
-                .map(|transaction| transaction.last_meter_sample),
+                .map(|transaction| transaction.last_meter_sample.clone()),
             Some(None)
         );
         assert!(
@@ -4224,7 +5282,9 @@ mod tests {
         // But the reading itself is kept: clock-aligned MeterValues (B1.1) are due whether or not
         // anything is charging, so a reading taken between sessions is exactly what they report.
         assert_eq!(
-            state.evses[0].latest_meter_samples[0].map(|sample| sample.energy_wh),
+            state.evses[0].latest_meter_samples[0]
+                .as_ref()
+                .map(|sample| sample.energy_wh),
             Some(1_500)
         );
         assert!(effects.contains(&ChargePointEffect::StateChanged));
@@ -4254,7 +5314,9 @@ mod tests {
 
         assert!(state.evses[0].transactions[0].is_none());
         assert_eq!(
-            state.evses[0].latest_meter_samples[0].map(|sample| sample.energy_wh),
+            state.evses[0].latest_meter_samples[0]
+                .as_ref()
+                .map(|sample| sample.energy_wh),
             Some(4_200),
             "the meter register does not reset when a session ends, and neither should the \
              reading standalone MeterValues reports"
@@ -4301,6 +5363,8 @@ mod tests {
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         };
         assert_eq!(state.evses[0].connectors[0], ConnectorState::Finishing);
         assert_eq!(state.evses[0].transactions[0], None);
@@ -4315,8 +5379,10 @@ mod tests {
         )));
     }
 
+    // G03.FR.05: a change to inoperative scheduled behind a transaction is applied by the event
+    // that ends it, and reported the same way an immediate change would be.
     #[test]
-    fn a_hardware_fault_during_charging_immediately_ends_the_transaction() {
+    fn a_scheduled_connector_unavailability_is_applied_and_reported_when_the_transaction_ends() {
         let mut state = ChargePointState::new([1]);
         plug_in_and_authorize(&mut state);
         apply_connector_event(
@@ -4324,77 +5390,311 @@ mod tests {
             ConnectorEvent::ChargingAuthorized(test_id_token()),
         );
         apply_connector_event(&mut state, ConnectorEvent::ContactorClosed);
+        let target = AvailabilityTarget::Connector {
+            evse_id: 0,
+            connector_id: 0,
+        };
+        state.apply(ChargePointEvent::UnavailabilityScheduled { target });
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+        assert_eq!(state.scheduled_unavailability, [target]);
 
-        let effects = apply_connector_event(&mut state, ConnectorEvent::FaultDetected);
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
 
-        let expected_transaction = Transaction {
-            id: TransactionId(0),
-            id_token: Some(test_id_token()),
-            charging_state: TransactionChargingState::Charging,
-            stop_reason: Some(StopReason::EmergencyStop),
-            seq_no: 2,
-            last_meter_sample: None,
-            priority_charging: false,
-            remote_start_id: None,
-            reservation_id: None,
-            stop_at_energy_wh: None,
-            limit: None,
-            csms_limit: None,
-            limit_reached: None,
-            energy_start_wh: None,
-            elapsed_secs: None,
-        };
-        assert_eq!(state.evses[0].transactions[0], None);
-        assert!(effects.contains(&ChargePointEffect::TransactionEvent(
-            TransactionEventOccurred {
+        assert!(state.scheduled_unavailability.is_empty());
+        assert_eq!(state.evses[0].connectors[0], ConnectorState::Unavailable);
+        assert_eq!(effects[0], ChargePointEffect::StateChanged);
+        assert!(effects.contains(&ChargePointEffect::StatusNotification(
+            ConnectorStatusChanged {
                 evse_id: 0,
                 connector_id: 0,
-                kind: TransactionEventKind::Ended,
-                transaction: expected_transaction,
-                offline: false,
+                status: crate::state::ConnectorStatus::Unavailable,
+                connector_state: ConnectorState::Unavailable,
             }
         )));
     }
 
+    /// G03.FR.05 at EVSE level: the idle connector beside the one charging is taken out of
+    /// service at once - reported `Unavailable` and refusing a driver - so a session started on
+    /// it after the `Scheduled` response cannot push the change back.
     #[test]
-    fn a_fault_with_no_active_transaction_reports_no_transaction_event() {
-        let mut state = ChargePointState::new([1]);
-
-        let effects = apply_connector_event(&mut state, ConnectorEvent::FaultDetected);
-
-        assert!(
-            !effects
-                .iter()
-                .any(|effect| matches!(effect, ChargePointEffect::TransactionEvent(_)))
-        );
-    }
-
-    #[test]
-    fn an_evse_fault_forces_every_connector_under_it_into_a_faulted_safe_state() {
-        let mut state = ChargePointState::new([3]);
-        apply_connector_event(&mut state, ConnectorEvent::CableConnected);
-
-        let effects = state.apply(ChargePointEvent::Evse {
-            evse_id: 0,
-            event: EvseEvent::FaultDetected,
-        });
-
-        assert_eq!(state.evses[0].status, EvseStatus::Faulted);
-        assert_eq!(state.evses[0].connectors[0], ConnectorState::Faulted);
-        assert_eq!(state.evses[0].connectors[1], ConnectorState::Faulted);
-        assert_eq!(state.evses[0].connectors[2], ConnectorState::Faulted);
-        for connector_id in 0..3 {
-            assert!(effects.contains(&ChargePointEffect::HardwareCommand(
-                HardwareCommand::OpenContactor {
-                    evse_id: 0,
-                    connector_id,
-                }
-            )));
-        }
-    }
-
-    #[test]
-    fn an_evse_fault_ends_active_transactions_on_every_connector_it_covers() {
+    fn a_scheduled_evse_unavailability_takes_its_idle_connectors_out_of_service_at_once() {
+        let mut state = ChargePointState::new([2]);
+        plug_in_and_authorize(&mut state);
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingAuthorized(test_id_token()),
+        );
+        apply_connector_event(&mut state, ConnectorEvent::ContactorClosed);
+        let second = |state: &mut ChargePointState, event| {
+            state.apply(ChargePointEvent::Evse {
+                evse_id: 0,
+                event: EvseEvent::Connector {
+                    connector_id: 1,
+                    event,
+                },
+            })
+        };
+
+        let effects = state.apply(ChargePointEvent::UnavailabilityScheduled {
+            target: AvailabilityTarget::Evse { evse_id: 0 },
+        });
+
+        assert!(effects.contains(&ChargePointEffect::StatusNotification(
+            ConnectorStatusChanged {
+                evse_id: 0,
+                connector_id: 1,
+                status: crate::state::ConnectorStatus::Unavailable,
+                connector_state: ConnectorState::Available,
+            }
+        )));
+        assert_eq!(
+            state.reported_connector_status(0, 0),
+            Some(crate::state::ConnectorStatus::Occupied),
+            "the transaction the change waits on carries on"
+        );
+
+        second(&mut state, ConnectorEvent::CableConnected);
+        second(&mut state, ConnectorEvent::LockConfirmed);
+        let effects = second(
+            &mut state,
+            ConnectorEvent::IdTokenPresented(test_id_token()),
+        );
+        assert!(
+            !effects
+                .iter()
+                .any(|effect| matches!(effect, ChargePointEffect::AuthorizationRequested(_))),
+            "a driver is not authorized onto a connector about to go out of service"
+        );
+        assert_eq!(state.evses[0].connectors[1], ConnectorState::Locked);
+        assert_eq!(state.evses[0].transactions[1], None);
+        assert_eq!(
+            state.reported_connector_status(0, 1),
+            Some(crate::state::ConnectorStatus::Unavailable)
+        );
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+        apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+
+        assert!(state.scheduled_unavailability.is_empty());
+        assert_eq!(state.evses[0].status, EvseStatus::Unavailable);
+    }
+
+    /// A connector whose transaction ends while the change still waits on the one beside it is
+    /// reported `Unavailable` by the event that ended it - the one thing a connector event can
+    /// move without a transition of its own.
+    #[test]
+    fn a_transaction_ending_under_a_scheduled_unavailability_reports_its_connector_unavailable() {
+        let mut state = ChargePointState::new([2]);
+        for connector_id in 0..2 {
+            for event in [
+                ConnectorEvent::CableConnected,
+                ConnectorEvent::LockConfirmed,
+                ConnectorEvent::IdTokenPresented(test_id_token()),
+                ConnectorEvent::ChargingAuthorized(test_id_token()),
+                ConnectorEvent::ContactorClosed,
+            ] {
+                state.apply(ChargePointEvent::Evse {
+                    evse_id: 0,
+                    event: EvseEvent::Connector {
+                        connector_id,
+                        event,
+                    },
+                });
+            }
+        }
+        state.apply(ChargePointEvent::UnavailabilityScheduled {
+            target: AvailabilityTarget::Evse { evse_id: 0 },
+        });
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+
+        let reports: Vec<_> = effects
+            .iter()
+            .filter_map(|effect| match effect {
+                ChargePointEffect::StatusNotification(changed) => Some(changed.status),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            reports.last(),
+            Some(&crate::state::ConnectorStatus::Unavailable)
+        );
+        assert_eq!(state.evses[0].status, EvseStatus::Available);
+        assert_eq!(
+            state.scheduled_unavailability,
+            [AvailabilityTarget::Evse { evse_id: 0 }]
+        );
+    }
+
+    /// A change scheduled on the connector itself reaches it through its own transition, and is
+    /// reported by that alone.
+    #[test]
+    fn a_scheduled_connector_unavailability_is_reported_once() {
+        let mut state = ChargePointState::new([1]);
+        plug_in_and_authorize(&mut state);
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingAuthorized(test_id_token()),
+        );
+        apply_connector_event(&mut state, ConnectorEvent::ContactorClosed);
+        state.apply(ChargePointEvent::UnavailabilityScheduled {
+            target: AvailabilityTarget::Connector {
+                evse_id: 0,
+                connector_id: 0,
+            },
+        });
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+
+        let unavailable = effects
+            .iter()
+            .filter(|effect| {
+                matches!(
+                    effect,
+                    ChargePointEffect::StatusNotification(changed)
+                        if changed.status == crate::state::ConnectorStatus::Unavailable
+                )
+            })
+            .count();
+        assert_eq!(unavailable, 1);
+        assert_eq!(
+            effects
+                .iter()
+                .filter(|effect| matches!(effect, ChargePointEffect::StateChanged))
+                .count(),
+            1
+        );
+    }
+
+    /// Cancelling the change gives the idle connector back as it was - nothing was done to the
+    /// connector itself, only to what it reports.
+    #[test]
+    fn cancelling_a_scheduled_unavailability_puts_its_idle_connectors_back_in_service() {
+        let mut state = ChargePointState::new([1, 1]);
+        plug_in_and_authorize(&mut state);
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingAuthorized(test_id_token()),
+        );
+        state.apply(ChargePointEvent::UnavailabilityScheduled {
+            target: AvailabilityTarget::ChargePoint,
+        });
+        assert_eq!(
+            state.reported_connector_status(1, 0),
+            Some(crate::state::ConnectorStatus::Unavailable)
+        );
+
+        let effects = state.apply(ChargePointEvent::ScheduledUnavailabilityCancelled {
+            target: AvailabilityTarget::ChargePoint,
+        });
+
+        assert!(effects.contains(&ChargePointEffect::StatusNotification(
+            ConnectorStatusChanged {
+                evse_id: 1,
+                connector_id: 0,
+                status: crate::state::ConnectorStatus::Available,
+                connector_state: ConnectorState::Available,
+            }
+        )));
+        assert!(!state.unavailability_pending(1, 0));
+    }
+
+    #[test]
+    fn a_hardware_fault_during_charging_immediately_ends_the_transaction() {
+        let mut state = ChargePointState::new([1]);
+        plug_in_and_authorize(&mut state);
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingAuthorized(test_id_token()),
+        );
+        apply_connector_event(&mut state, ConnectorEvent::ContactorClosed);
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::FaultDetected);
+
+        let expected_transaction = Transaction {
+            id: TransactionId(0),
+            id_token: Some(test_id_token()),
+            charging_state: TransactionChargingState::Charging,
+            stop_reason: Some(StopReason::EmergencyStop),
+            seq_no: 2,
+            last_meter_sample: None,
+            priority_charging: false,
+            remote_start_id: None,
+            reservation_id: None,
+            stop_at_energy_wh: None,
+            limit: None,
+            csms_limit: None,
+            limit_reached: None,
+            energy_start_wh: None,
+            elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
+        };
+        assert_eq!(state.evses[0].transactions[0], None);
+        assert!(effects.contains(&ChargePointEffect::TransactionEvent(
+            TransactionEventOccurred {
+                evse_id: 0,
+                connector_id: 0,
+                kind: TransactionEventKind::Ended,
+                transaction: expected_transaction,
+                offline: false,
+            }
+        )));
+    }
+
+    #[test]
+    fn a_fault_with_no_active_transaction_reports_no_transaction_event() {
+        let mut state = ChargePointState::new([1]);
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::FaultDetected);
+
+        assert!(
+            !effects
+                .iter()
+                .any(|effect| matches!(effect, ChargePointEffect::TransactionEvent(_)))
+        );
+    }
+
+    #[test]
+    fn an_evse_fault_forces_every_connector_under_it_into_a_faulted_safe_state() {
+        let mut state = ChargePointState::new([3]);
+        apply_connector_event(&mut state, ConnectorEvent::CableConnected);
+
+        let effects = state.apply(ChargePointEvent::Evse {
+            evse_id: 0,
+            event: EvseEvent::FaultDetected,
+        });
+
+        assert_eq!(state.evses[0].status, EvseStatus::Faulted);
+        assert_eq!(state.evses[0].connectors[0], ConnectorState::Faulted);
+        assert_eq!(state.evses[0].connectors[1], ConnectorState::Faulted);
+        assert_eq!(state.evses[0].connectors[2], ConnectorState::Faulted);
+        for connector_id in 0..3 {
+            assert!(effects.contains(&ChargePointEffect::HardwareCommand(
+                HardwareCommand::OpenContactor {
+                    evse_id: 0,
+                    connector_id,
+                }
+            )));
+        }
+    }
+
+    #[test]
+    fn an_evse_fault_ends_active_transactions_on_every_connector_it_covers() {
         let mut state = ChargePointState::new([2]);
         plug_in_and_authorize(&mut state);
         apply_connector_event(
@@ -4561,6 +5861,281 @@ mod tests {
         assert!(state.evses[0].pending_remote_starts[0].is_some());
     }
 
+    // --- J02: SampledDataCtrlr.TxUpdatedInterval / TxEndedInterval ---
+
+    fn set_sampled_data_interval(state: &mut ChargePointState, variable: &str, secs: &str) {
+        state.apply(ChargePointEvent::DeviceModel(
+            crate::state::DeviceModelEvent::AttributeValueSet {
+                component: Component {
+                    name: "SampledDataCtrlr".into(),
+                    instance: None,
+                    evse: None,
+                },
+                variable: crate::state::Variable {
+                    name: variable.into(),
+                    instance: None,
+                },
+                attribute_type: crate::state::VariableAttributeType::Actual,
+                value: secs.into(),
+            },
+        ));
+    }
+
+    fn periodic_reports(effects: &[ChargePointEffect]) -> Vec<&Transaction> {
+        effects
+            .iter()
+            .filter_map(|effect| match effect {
+                ChargePointEffect::TransactionEvent(TransactionEventOccurred {
+                    kind: TransactionEventKind::Updated(TransactionUpdateReason::MeterValuePeriodic),
+                    transaction,
+                    ..
+                }) => Some(transaction),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// J02: with `TxUpdatedInterval` set, a pushed reading is recorded but held back, and goes out
+    /// when the sampling loop says the interval is up - with the very next `seqNo`, because
+    /// nothing was sent in between.
+    #[test]
+    fn with_tx_updated_interval_set_a_reading_waits_for_the_interval() {
+        let mut state = charging_from(1_000);
+        let seq_no_before = state.evses[0].transactions[0].as_ref().unwrap().seq_no;
+        set_sampled_data_interval(&mut state, "TxUpdatedInterval", "60");
+
+        let effects = apply_connector_event(&mut state, sample_at(1_100));
+        assert!(periodic_reports(&effects).is_empty());
+        let effects = apply_connector_event(&mut state, sample_at(1_200));
+        assert!(periodic_reports(&effects).is_empty());
+        assert_eq!(
+            state.evses[0].transactions[0]
+                .as_ref()
+                .and_then(|transaction| transaction.last_meter_sample.clone())
+                .map(|sample| sample.energy_wh),
+            Some(1_200),
+            "held back from the CSMS, not from the state machine"
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::TransactionMeterValuesDue);
+
+        let reports = periodic_reports(&effects);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].seq_no, seq_no_before + 1);
+        assert_eq!(
+            reports[0]
+                .last_meter_sample
+                .as_ref()
+                .map(|sample| sample.energy_wh),
+            Some(1_200)
+        );
+    }
+
+    /// Unset (OCPP's default `0`), every reading is reported on arrival, as it always was.
+    #[test]
+    fn without_tx_updated_interval_every_reading_is_reported() {
+        let mut state = charging_from(1_000);
+
+        let effects = apply_connector_event(&mut state, sample_at(1_100));
+
+        assert_eq!(periodic_reports(&effects).len(), 1);
+    }
+
+    /// Unregistered - a charge point without the `SampledDataCtrlr` block - reads as unset, not
+    /// as an interval that never comes up.
+    #[test]
+    fn without_a_registered_tx_updated_interval_every_reading_is_reported() {
+        let mut state = charging_from(1_000);
+        assert!(state.device_model.remove_component(&Component {
+            name: "SampledDataCtrlr".into(),
+            instance: None,
+            evse: None,
+        }));
+
+        let effects = apply_connector_event(&mut state, sample_at(1_100));
+
+        assert_eq!(periodic_reports(&effects).len(), 1);
+    }
+
+    #[test]
+    fn a_due_report_with_nothing_charging_is_a_no_op() {
+        let mut state = ChargePointState::new([1]);
+        set_sampled_data_interval(&mut state, "TxUpdatedInterval", "60");
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::TransactionMeterValuesDue);
+
+        assert!(periodic_reports(&effects).is_empty());
+    }
+
+    /// J02: each `TxEndedInterval` reading is kept with the instant the loop supplied, and the
+    /// whole series rides on the `Ended` event.
+    #[test]
+    fn readings_sampled_for_the_ended_event_are_carried_on_it() {
+        let mut state = charging_from(1_000);
+        state.max_ended_meter_samples = 12;
+        let first = "2026-10-17T10:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let second = "2026-10-17T10:15:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        apply_connector_event(&mut state, ConnectorEvent::TransactionEndedSampleDue(first));
+        apply_connector_event(&mut state, sample_at(1_500));
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::TransactionEndedSampleDue(second),
+        );
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+
+        let ended = effects
+            .iter()
+            .find_map(|effect| match effect {
+                ChargePointEffect::TransactionEvent(TransactionEventOccurred {
+                    kind: TransactionEventKind::Ended,
+                    transaction,
+                    ..
+                }) => Some(transaction),
+                _ => None,
+            })
+            .expect("the transaction ended");
+        assert_eq!(
+            ended
+                .ended_meter_samples
+                .iter()
+                .map(|sampled| (sampled.timestamp, sampled.sample.energy_wh))
+                .collect::<Vec<_>>(),
+            vec![(first, 1_000), (second, 1_500)]
+        );
+    }
+
+    #[test]
+    fn an_ended_sample_with_no_transaction_running_is_a_no_op() {
+        let mut state = ChargePointState::new([1]);
+        apply_connector_event(&mut state, sample_at(1_000));
+        let now = "2026-10-17T10:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        let effects =
+            apply_connector_event(&mut state, ConnectorEvent::TransactionEndedSampleDue(now));
+
+        assert!(!effects.contains(&ChargePointEffect::StateChanged));
+    }
+
+    /// The series is bounded by `StateLimits::max_ended_meter_samples`, held against the
+    /// connector rather than the running transaction - so neither it nor an `Updated` snapshot of
+    /// it carries the readings - and starts empty for the next transaction.
+    #[test]
+    fn the_ended_series_is_bounded_and_kept_off_the_running_transaction() {
+        let mut state = charging_from(1_000);
+        state.max_ended_meter_samples = 2;
+        let first = "2026-10-17T10:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        for minutes in 0..3 {
+            apply_connector_event(
+                &mut state,
+                ConnectorEvent::TransactionEndedSampleDue(
+                    first + chrono::Duration::minutes(minutes),
+                ),
+            );
+        }
+        let effects = apply_connector_event(&mut state, sample_at(1_100));
+
+        assert_eq!(state.evses[0].ended_meter_samples[0].len(), 2);
+        assert!(periodic_reports(&effects)[0].ended_meter_samples.is_empty());
+        assert!(
+            state.evses[0].transactions[0]
+                .as_ref()
+                .is_some_and(|transaction| transaction.ended_meter_samples.is_empty())
+        );
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+
+        assert_eq!(ended(&effects).ended_meter_samples.len(), 2);
+        assert!(state.evses[0].ended_meter_samples[0].is_empty());
+    }
+
+    fn ended(effects: &[ChargePointEffect]) -> &Transaction {
+        effects
+            .iter()
+            .find_map(|effect| match effect {
+                ChargePointEffect::TransactionEvent(TransactionEventOccurred {
+                    kind: TransactionEventKind::Ended,
+                    transaction,
+                    ..
+                }) => Some(transaction),
+                _ => None,
+            })
+            .expect("the transaction ended")
+    }
+
+    /// 1.6J's `StopTxnAlignedData`: each clock-aligned boundary keeps the connector's reading
+    /// for the transaction's end, marked apart from the sampled series it is interleaved with.
+    #[test]
+    fn readings_kept_on_aligned_boundaries_are_carried_on_the_ended_event() {
+        let mut state = charging_from(1_000);
+        state.max_ended_meter_samples = 12;
+        let sampled = "2026-10-17T10:10:00Z".parse::<DateTime<Utc>>().unwrap();
+        let quarter = "2026-10-17T10:15:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::TransactionEndedSampleDue(sampled),
+        );
+        apply_connector_event(&mut state, sample_at(1_200));
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::TransactionAlignedSampleDue(quarter),
+        );
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+
+        assert_eq!(
+            ended(&effects)
+                .ended_meter_samples
+                .iter()
+                .map(|kept| (kept.timestamp, kept.sample.energy_wh, kept.clock_aligned))
+                .collect::<Vec<_>>(),
+            vec![(sampled, 1_000, false), (quarter, 1_200, true)]
+        );
+    }
+
+    /// The register the transaction stopped on is the connector's at the stop point, not the
+    /// last one read while `Charging`: the meter keeps counting while the EV has paused.
+    #[test]
+    fn the_ended_event_reports_the_register_at_the_stop_point() {
+        let mut state = charging_from(1_000);
+        apply_connector_event(&mut state, sample_at(1_500));
+        apply_connector_event(&mut state, ConnectorEvent::ChargingSuspendedByEv);
+        apply_connector_event(&mut state, sample_at(1_520));
+        assert_eq!(
+            transaction(&state)
+                .and_then(|transaction| transaction.last_meter_sample.as_ref())
+                .map(|s| s.energy_wh),
+            Some(1_500)
+        );
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+
+        assert_eq!(
+            ended(&effects)
+                .last_meter_sample
+                .as_ref()
+                .map(|s| s.energy_wh),
+            Some(1_520)
+        );
+    }
+
     // --- CV2.2: TxCtrlr.TxStartPoint ---
 
     fn set_string(state: &mut ChargePointState, component: &str, variable: &str, value: &str) {
@@ -4667,35 +6242,162 @@ mod tests {
         );
     }
 
-    /// OCPP models the start point as a set that must *all* hold, and the three points this
-    /// crate observes are strictly ordered along a session - so a set resolves to its latest
-    /// member.
+    /// OCPP does not rank the start points, so a set is not folded to one of them: the first
+    /// member whose condition is met begins the transaction, and the others then only update it.
     #[test]
-    fn a_set_of_start_points_resolves_to_the_latest_of_them() {
+    fn a_set_of_start_points_begins_the_transaction_at_the_first_one_met() {
         let mut state = ChargePointState::new([1]);
         set_string(
             &mut state,
             "TxCtrlr",
             "TxStartPoint",
-            "EVConnected,Authorized",
+            "PowerPathClosed,EVConnected",
         );
 
         apply_connector_event(&mut state, ConnectorEvent::CableConnected);
-        apply_connector_event(&mut state, ConnectorEvent::LockConfirmed);
+        let effects = apply_connector_event(&mut state, ConnectorEvent::LockConfirmed);
+        let started = state.evses[0].transactions[0]
+            .as_ref()
+            .expect("EVConnected is met first, and it is enough")
+            .id;
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::IdTokenPresented(test_id_token()),
+        );
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingAuthorized(test_id_token()),
+        );
+        let later = apply_connector_event(&mut state, ConnectorEvent::ContactorClosed);
+
+        assert_eq!(
+            state.evses[0].transactions[0]
+                .as_ref()
+                .map(|transaction| transaction.id),
+            Some(started),
+            "the contactor closing is a member too, but the transaction already exists"
+        );
+        let started_events = |effects: &[ChargePointEffect]| {
+            effects
+                .iter()
+                .filter(|effect| {
+                    matches!(
+                        effect,
+                        ChargePointEffect::TransactionEvent(occurred)
+                            if occurred.kind == TransactionEventKind::Started
+                    )
+                })
+                .count()
+        };
+        assert_eq!(started_events(&effects), 1);
+        assert_eq!(started_events(&later), 0);
+    }
+
+    fn signed_sample(energy_wh: i64, data: &str) -> MeterSample {
+        MeterSample {
+            energy_wh,
+            signed: Some(alloc::boxed::Box::new(crate::state::SignedMeterValue {
+                signed_meter_data: data.into(),
+                encoding_method: "OCMF".into(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    }
+
+    /// `ParkingBayOccupancy`: the garage bills from the moment the car stands in the bay, so the
+    /// transaction exists before any cable does - and the cable arriving is reported as the cable
+    /// being plugged in on that transaction, not as a new one.
+    #[test]
+    fn parking_bay_occupancy_starts_the_transaction_before_any_cable() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStartPoint", "ParkingBayOccupancy");
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ParkingBayOccupied);
+
+        assert!(effects.contains(&ChargePointEffect::StateChanged));
+        let started = state.evses[0].transactions[0]
+            .clone()
+            .expect("the bay sensor starts it");
+        assert_eq!(
+            started.charging_state,
+            crate::state::TransactionChargingState::Idle
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::CableConnected);
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            ChargePointEffect::TransactionEvent(occurred)
+                if occurred.kind
+                    == TransactionEventKind::Updated(TransactionUpdateReason::CablePluggedIn)
+                    && occurred.transaction.id == started.id
+                    && occurred.transaction.charging_state
+                        == crate::state::TransactionChargingState::EvConnected
+        )));
+    }
+
+    #[test]
+    fn a_bay_sensor_does_not_start_a_transaction_on_an_unavailable_connector() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStartPoint", "ParkingBayOccupancy");
+        apply_connector_event(&mut state, ConnectorEvent::SetUnavailable);
+
+        apply_connector_event(&mut state, ConnectorEvent::ParkingBayOccupied);
+
+        assert!(state.evses[0].transactions[0].is_none());
+    }
+
+    /// `EnergyTransfer`: the contactor closing is not enough - an EV that never draws produces no
+    /// transaction until current actually flows.
+    #[test]
+    fn energy_transfer_starts_the_transaction_when_current_flows() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStartPoint", "EnergyTransfer");
+        charging_connector(&mut state);
         assert!(
             state.evses[0].transactions[0].is_none(),
-            "EVConnected alone does not satisfy a set that also names Authorized"
+            "a closed contactor is not energy flowing"
+        );
+
+        apply_connector_event(&mut state, ConnectorEvent::EnergyTransferStarted);
+
+        let transaction = state.evses[0].transactions[0]
+            .as_ref()
+            .expect("the measured flow starts it");
+        assert_eq!(
+            transaction.charging_state,
+            crate::state::TransactionChargingState::Charging
         );
+    }
 
+    /// `DataSigned`: the transaction begins on the meter's signed start value, and quotes it as
+    /// its start reading - the figure a calibration-law audit checks against.
+    #[test]
+    fn data_signed_starts_the_transaction_on_the_signed_start_reading() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStartPoint", "DataSigned");
         apply_connector_event(
             &mut state,
-            ConnectorEvent::IdTokenPresented(test_id_token()),
+            ConnectorEvent::MeterValueSampled(signed_sample(900, "OCMF|idle")),
         );
+        plug_in_and_authorize(&mut state);
         apply_connector_event(
             &mut state,
             ConnectorEvent::ChargingAuthorized(test_id_token()),
         );
-        assert!(state.evses[0].transactions[0].is_some());
+        assert!(
+            state.evses[0].transactions[0].is_none(),
+            "neither the idle register's signature nor the authorization is the start"
+        );
+
+        let start = signed_sample(1_000, "OCMF|begin");
+        apply_connector_event(&mut state, ConnectorEvent::MeterValueSampled(start.clone()));
+
+        let transaction = state.evses[0].transactions[0]
+            .as_ref()
+            .expect("the signed reading starts it");
+        assert_eq!(transaction.start_meter_sample, Some(start));
     }
 
     // --- CV2.2: TxCtrlr.TxStopPoint ---
@@ -4815,11 +6517,136 @@ mod tests {
         );
     }
 
-    /// A stop point is a condition that *ceases* to hold, so a configured set resolves to its
-    /// **earliest** member - the opposite of `TxStartPoint`, and the one thing about this
-    /// variable worth getting wrong quietly.
+    /// `EnergyTransfer`: a suspension stops the energy as surely as a stop does, so under this
+    /// stop point it ends the transaction - E16.FR.06's case.
+    #[test]
+    fn energy_transfer_ends_the_transaction_when_current_stops() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStopPoint", "EnergyTransfer");
+        charging_connector(&mut state);
+
+        apply_connector_event(&mut state, ConnectorEvent::ChargingSuspendedByEvse);
+        assert!(
+            transaction(&state).is_some(),
+            "the suspension is reported by the binding's signal, not inferred"
+        );
+        let effects = apply_connector_event(&mut state, ConnectorEvent::EnergyTransferStopped);
+
+        assert!(transaction(&state).is_none());
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            ChargePointEffect::TransactionEvent(occurred)
+                if occurred.kind == TransactionEventKind::Ended
+        )));
+    }
+
+    /// `DataSigned`: the meter's periodic signed readings mid-charge are not the end value; the
+    /// one it signs once energy has stopped is.
+    #[test]
+    fn data_signed_ends_the_transaction_on_the_signed_reading_after_energy_stops() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStopPoint", "DataSigned");
+        charging_connector(&mut state);
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(signed_sample(1_500, "OCMF|periodic")),
+        );
+        assert!(transaction(&state).is_some(), "a mid-charge signature");
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+        apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+        apply_connector_event(&mut state, ConnectorEvent::UnlockConfirmed);
+        assert!(
+            transaction(&state).is_some(),
+            "the power path opening is not this stop point"
+        );
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(signed_sample(2_000, "OCMF|end")),
+        );
+
+        assert!(transaction(&state).is_none());
+    }
+
+    /// A set of stop points ends the transaction on whichever member lapses first in this
+    /// session. Here the meter signs its end value before the binding reports current stopping,
+    /// so `DataSigned` ends it - no order between the two is assumed.
+    #[test]
+    fn a_set_of_stop_points_ends_the_transaction_at_the_first_one_to_lapse() {
+        let mut state = ChargePointState::new([1]);
+        set_string(
+            &mut state,
+            "TxCtrlr",
+            "TxStopPoint",
+            "EnergyTransfer,DataSigned",
+        );
+        charging_connector(&mut state);
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+        apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+        assert!(
+            transaction(&state).is_some(),
+            "the power path opening is not a member"
+        );
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(signed_sample(2_000, "OCMF|end")),
+        );
+
+        assert!(transaction(&state).is_none());
+    }
+
+    /// `ParkingBayOccupancy`: the transaction outlives the cable, reporting the bay idle, and ends
+    /// when the car drives away.
+    #[test]
+    fn parking_bay_occupancy_keeps_the_transaction_open_until_the_bay_is_vacated() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStopPoint", "ParkingBayOccupancy");
+        charging_connector(&mut state);
+
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingStopped(StopReason::Local),
+        );
+        apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+        apply_connector_event(&mut state, ConnectorEvent::UnlockConfirmed);
+        apply_connector_event(&mut state, ConnectorEvent::CableDisconnected);
+        assert_eq!(
+            transaction(&state).map(|transaction| transaction.charging_state),
+            Some(crate::state::TransactionChargingState::Idle),
+            "the cable is gone but the car is still in the bay"
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ParkingBayVacated);
+
+        assert!(transaction(&state).is_none());
+        let ended = effects
+            .iter()
+            .find_map(|effect| match effect {
+                ChargePointEffect::TransactionEvent(occurred)
+                    if occurred.kind == TransactionEventKind::Ended =>
+                {
+                    Some(&occurred.transaction)
+                }
+                _ => None,
+            })
+            .expect("the bay emptying ends it under this stop point");
+        assert_eq!(ended.stop_reason, Some(StopReason::Local));
+    }
+
+    /// A stop point is a condition that *ceases* to hold, and the transaction cannot outlive any
+    /// configured one lapsing: the authorization ending is enough, with the cable still in.
     #[test]
-    fn a_set_of_stop_points_resolves_to_the_earliest_of_them() {
+    fn a_set_of_stop_points_ends_the_transaction_when_any_member_lapses() {
         let mut state = ChargePointState::new([1]);
         set_string(
             &mut state,
@@ -4836,7 +6663,7 @@ mod tests {
 
         assert!(
             transaction(&state).is_none(),
-            "Authorized is the earliest member and must win"
+            "Authorized lapsed, and EVConnected still holding does not keep it open"
         );
     }
 
@@ -4844,7 +6671,14 @@ mod tests {
     /// service, so nothing will ever observe a later condition lapsing.
     #[test]
     fn a_fault_ends_the_transaction_whatever_the_stop_point_is() {
-        for stop_point in ["Authorized", "PowerPathClosed", "EVConnected"] {
+        for stop_point in [
+            "Authorized",
+            "EnergyTransfer",
+            "PowerPathClosed",
+            "DataSigned",
+            "EVConnected",
+            "ParkingBayOccupancy",
+        ] {
             let mut state = ChargePointState::new([1]);
             set_string(&mut state, "TxCtrlr", "TxStopPoint", stop_point);
             charging_connector(&mut state);
@@ -5211,10 +7045,7 @@ mod tests {
     fn sample(energy_wh: i64) -> crate::state::MeterSample {
         crate::state::MeterSample {
             energy_wh,
-            power_w: None,
-            current_ma: None,
-            voltage_v: None,
-            soc_percent: None,
+            ..Default::default()
         }
     }
 
@@ -5350,6 +7181,240 @@ mod tests {
         );
     }
 
+    /// Every `Updated` report among `effects`, by reason and the charging state it carried.
+    fn updates(
+        effects: &[ChargePointEffect],
+    ) -> Vec<(
+        TransactionUpdateReason,
+        crate::state::TransactionChargingState,
+    )> {
+        effects
+            .iter()
+            .filter_map(|effect| match effect {
+                ChargePointEffect::TransactionEvent(TransactionEventOccurred {
+                    kind: TransactionEventKind::Updated(reason),
+                    transaction,
+                    ..
+                }) => Some((*reason, transaction.charging_state)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// E09: the cable leaving the EV is why the transaction stopped, and the CSMS is told so -
+    /// `stoppedReason = EVDisconnected`, which the 2.x adapters report as `EVDeparted`.
+    #[test]
+    fn an_ev_side_disconnect_records_why_the_transaction_stopped() {
+        let mut state = ChargePointState::new([1]);
+        charging_connector(&mut state);
+
+        apply_connector_event(&mut state, ConnectorEvent::CableDisconnected);
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ContactorOpened);
+
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            ChargePointEffect::TransactionEvent(TransactionEventOccurred {
+                kind: TransactionEventKind::Ended,
+                transaction,
+                ..
+            }) if transaction.stop_reason == Some(StopReason::EVDisconnected)
+        )));
+    }
+
+    /// E10: the suspension is the EV's communication being lost, and reported as that rather than
+    /// as a bare charging-state change.
+    #[test]
+    fn an_ev_side_suspension_is_reported_as_the_ev_communication_being_lost() {
+        let mut state = ChargePointState::new([1]);
+        charging_connector(&mut state);
+        set_boolean(&mut state, "TxCtrlr", "StopTxOnEVSideDisconnect", "false");
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::CableDisconnected);
+
+        assert_eq!(
+            updates(&effects),
+            alloc::vec![(
+                TransactionUpdateReason::EvCommunicationLost,
+                crate::state::TransactionChargingState::SuspendedEV
+            )]
+        );
+    }
+
+    /// ISO 15118 dropping, or the pilot in state E/F: charging pauses on the EV's side and the
+    /// CSMS hears why - and hears it again if it happens while the EV was already paused.
+    #[test]
+    fn losing_the_ev_suspends_charging_and_reports_the_loss() {
+        let mut state = ChargePointState::new([1]);
+        charging_connector(&mut state);
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::EvCommunicationLost);
+
+        assert_eq!(state.evses[0].connectors[0], ConnectorState::SuspendedEv);
+        assert_eq!(
+            updates(&effects),
+            alloc::vec![(
+                TransactionUpdateReason::EvCommunicationLost,
+                crate::state::TransactionChargingState::SuspendedEV
+            )]
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::EvCommunicationLost);
+        assert_eq!(
+            updates(&effects),
+            alloc::vec![(
+                TransactionUpdateReason::EvCommunicationLost,
+                crate::state::TransactionChargingState::SuspendedEV
+            )]
+        );
+    }
+
+    #[test]
+    fn losing_an_ev_with_no_transaction_reports_nothing() {
+        let mut state = ChargePointState::new([1]);
+        apply_connector_event(&mut state, ConnectorEvent::CableConnected);
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::EvCommunicationLost);
+
+        assert!(updates(&effects).is_empty());
+    }
+
+    /// A signed reading goes out as it arrives, even while `TxUpdatedInterval` holds unsigned
+    /// ones back and even once energy has stopped flowing.
+    #[test]
+    fn a_signed_reading_mid_session_is_reported_at_once() {
+        let mut state = ChargePointState::new([1]);
+        charging_connector(&mut state);
+        set_string(&mut state, "SampledDataCtrlr", "TxUpdatedInterval", "60");
+
+        let effects = apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(MeterSample {
+                energy_wh: 1_000,
+                ..Default::default()
+            }),
+        );
+        assert!(
+            updates(&effects).is_empty(),
+            "unsigned: held for the interval"
+        );
+
+        apply_connector_event(&mut state, ConnectorEvent::ChargingSuspendedByEv);
+        let effects = apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(signed_sample(1_200, "OCMF|{}")),
+        );
+
+        assert_eq!(
+            updates(&effects),
+            alloc::vec![(
+                TransactionUpdateReason::SignedDataReceived,
+                crate::state::TransactionChargingState::SuspendedEV
+            )]
+        );
+        assert_eq!(
+            transaction(&state)
+                .and_then(|transaction| transaction.last_meter_sample.as_ref())
+                .map(|sample| sample.energy_wh),
+            Some(1_200)
+        );
+    }
+
+    /// `DataSigned` start: the signed reading already went out as the start value on `Started`,
+    /// and is not reported a second time.
+    #[test]
+    fn a_signed_reading_that_starts_the_transaction_is_not_also_reported_as_an_update() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStartPoint", "DataSigned");
+        charging_connector(&mut state);
+
+        let effects = apply_connector_event(
+            &mut state,
+            ConnectorEvent::MeterValueSampled(signed_sample(1_000, "OCMF|{}")),
+        );
+
+        assert!(transaction(&state).is_some());
+        assert!(updates(&effects).is_empty());
+    }
+
+    /// F06: a triggered report says where the transaction stands, with the next `seqNo`.
+    #[test]
+    fn a_triggered_transaction_event_reports_the_transaction_as_it_stands() {
+        let mut state = ChargePointState::new([1]);
+        charging_connector(&mut state);
+        let seq_no = transaction(&state).map(|transaction| transaction.seq_no);
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::TransactionEventTriggered);
+
+        assert_eq!(
+            updates(&effects),
+            alloc::vec![(
+                TransactionUpdateReason::Trigger,
+                crate::state::TransactionChargingState::Charging
+            )]
+        );
+        assert_eq!(
+            transaction(&state).map(|transaction| transaction.seq_no),
+            seq_no.map(|seq_no| seq_no + 1)
+        );
+    }
+
+    /// CV2.2 around a cable session: the bay sensor seeing the vehicle leave, and then arrive, is
+    /// news about a transaction it neither starts nor ends.
+    #[test]
+    fn the_bay_sensor_reports_the_vehicle_leaving_and_arriving_during_a_session() {
+        let mut state = ChargePointState::new([1]);
+        charging_connector(&mut state);
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ParkingBayVacated);
+        assert_eq!(
+            updates(&effects),
+            alloc::vec![(
+                TransactionUpdateReason::EvDeparted,
+                crate::state::TransactionChargingState::Charging
+            )]
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ParkingBayOccupied);
+        assert_eq!(
+            updates(&effects),
+            alloc::vec![(
+                TransactionUpdateReason::EvDetected,
+                crate::state::TransactionChargingState::Charging
+            )]
+        );
+    }
+
+    /// A bay-billed session the cable leaves before the car does reports the EV's communication
+    /// lost - the car is still in the bay - and one the car ends by driving off ended because the
+    /// EV departed.
+    #[test]
+    fn a_bay_session_reports_the_cable_leaving_and_ends_when_the_vehicle_does() {
+        let mut state = ChargePointState::new([1]);
+        set_string(&mut state, "TxCtrlr", "TxStartPoint", "ParkingBayOccupancy");
+        set_string(&mut state, "TxCtrlr", "TxStopPoint", "ParkingBayOccupancy");
+        apply_connector_event(&mut state, ConnectorEvent::ParkingBayOccupied);
+        apply_connector_event(&mut state, ConnectorEvent::CableConnected);
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::CableDisconnected);
+        assert_eq!(
+            updates(&effects),
+            alloc::vec![(
+                TransactionUpdateReason::EvCommunicationLost,
+                crate::state::TransactionChargingState::Idle
+            )]
+        );
+
+        let effects = apply_connector_event(&mut state, ConnectorEvent::ParkingBayVacated);
+        assert!(effects.iter().any(|effect| matches!(
+            effect,
+            ChargePointEffect::TransactionEvent(TransactionEventOccurred {
+                kind: TransactionEventKind::Ended,
+                transaction,
+                ..
+            }) if transaction.stop_reason == Some(StopReason::EVDisconnected)
+        )));
+    }
+
     /// The retained-cable stop is still a stop: whatever the unlock setting, the transaction
     /// ends at the same point in the stop and reports the same thing. Only the cable differs.
     #[test]
@@ -6157,6 +8222,36 @@ mod tests {
         assert_eq!(state.evses[0].transaction_tariffs[0], None);
     }
 
+    /// I12: the transaction the new tariff prices hears about it, once.
+    #[test]
+    fn a_transaction_tariff_is_reported_on_the_transaction_it_prices() {
+        let mut state = ChargePointState::new([1]);
+        plug_in_and_authorize(&mut state);
+        apply_connector_event(
+            &mut state,
+            ConnectorEvent::ChargingAuthorized(test_id_token()),
+        );
+
+        let effects = apply_connector_event(
+            &mut state,
+            ConnectorEvent::TariffAssigned(alloc::boxed::Box::new(test_tariff("t1"))),
+        );
+
+        assert_eq!(
+            effects
+                .iter()
+                .filter(|effect| matches!(
+                    effect,
+                    ChargePointEffect::TransactionEvent(TransactionEventOccurred {
+                        kind: TransactionEventKind::Updated(TransactionUpdateReason::TariffChanged),
+                        ..
+                    })
+                ))
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn a_new_transaction_does_not_inherit_the_previous_ones_tariff() {
         let mut state = ChargePointState::new([1]);
@@ -6720,7 +8815,9 @@ mod tests {
         );
 
         assert_eq!(
-            state.evses[0].latest_meter_samples[0].map(|sample| sample.energy_wh),
+            state.evses[0].latest_meter_samples[0]
+                .as_ref()
+                .map(|sample| sample.energy_wh),
             Some(5_000)
         );
     }
@@ -6784,6 +8881,8 @@ mod tests {
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         }
     }
 
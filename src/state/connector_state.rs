@@ -71,71 +71,77 @@ pub(crate) struct ConnectorTransition {
     pub command: Option<ConnectorCommand>,
 }
 
-/// Where in a session OCPP says the transaction begins - `TxCtrlr.TxStartPoint`
+/// Where in a session OCPP says the transaction begins - one member of `TxCtrlr.TxStartPoint`
 /// (`docs/OCPP-2.1-COMPLIANCE-ROADMAP.md` CV2.2).
 ///
-/// OCPP models this as a *set*: the transaction starts once every configured condition holds. The
-/// three this charge point can observe are strictly ordered along a session, so "all conditions
-/// met" is simply the last of them - which is why this is an ordered enum and
-/// [`Self::from_member_list`] takes the maximum rather than tracking a set of flags.
+/// OCPP configures this as a *set*, and the transaction starts as soon as **any** configured
+/// member's condition is met - see [`TxPoints`]. The members are not ordered by the spec, so none
+/// is assumed here: each is checked on its own, and whichever happens first begins the
+/// transaction.
 ///
-/// The other three values OCPP defines are not here, and their absence is enforced rather than
-/// implied: `ParkingBayOccupancy` needs a bay sensor this crate has no binding for, `DataSigned`
-/// needs signed meter values it does not produce, and `EnergyTransfer` needs a "current is
-/// actually flowing" signal distinct from the contactor being closed. The variable's declared
-/// `values_list` is narrowed to the three below, so a `SetVariables` naming one of the others is
-/// `Rejected` by CV3's validation with a reason - rather than accepted and quietly treated as
-/// something else.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+/// Three of the points are transitions of the connector's own state machine. The other three are
+/// signals only the hardware binding can raise, because nothing else can observe them:
+/// [`ConnectorEvent::ParkingBayOccupied`] from a bay sensor,
+/// [`ConnectorEvent::EnergyTransferStarted`] from whatever measures current, and a
+/// [`ConnectorEvent::MeterValueSampled`] whose reading carries the meter's signature. Those three
+/// are only accepted once the binding declares it raises them - see
+/// [`Capabilities::parking_bay_sensor`](crate::hardware::Capabilities::parking_bay_sensor) and its
+/// two siblings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub(crate) enum TxStartPoint {
+    /// A vehicle occupied the parking bay - the transaction covers the whole time the bay is in
+    /// use, before any cable has been connected.
+    ParkingBayOccupancy,
     /// The cable is connected and locked - the transaction covers the whole time the bay is
     /// occupied, including any authorization attempt that fails.
     EVConnected,
     /// The presented identifier was authorized. OCPP's default, and this crate's.
     #[default]
     Authorized,
+    /// The meter signed a reading once the session was authorized - the transaction begins on the
+    /// reading a calibration-law audit will quote as its start.
+    DataSigned,
     /// The contactor closed - the transaction covers only the time energy could actually flow.
     PowerPathClosed,
+    /// Energy actually started flowing, which can be well after the contactor closed if the EV
+    /// takes its time drawing.
+    EnergyTransfer,
 }
 
-impl TxStartPoint {
-    /// Parses `TxCtrlr.TxStartPoint`'s `MemberList` value, taking the latest point named.
-    ///
-    /// An empty or unrecognised value falls back to the default rather than erroring: the value
-    /// reaching here has already passed CV3's validation against the declared `values_list`, so an
-    /// unparseable one means the device model was written by something other than `SetVariables`
-    /// (a hardware binding, a persisted restore), and a charge point is better off starting
-    /// transactions the conventional way than not at all.
-    pub fn from_member_list(value: &str) -> Self {
-        value
-            .split(',')
-            .filter_map(|member| match member.trim() {
-                "EVConnected" => Some(Self::EVConnected),
-                "Authorized" => Some(Self::Authorized),
-                "PowerPathClosed" => Some(Self::PowerPathClosed),
-                _ => None,
-            })
-            .max()
-            .unwrap_or_default()
+impl TxPoint for TxStartPoint {
+    const ALL: &'static [Self] = &[
+        Self::ParkingBayOccupancy,
+        Self::EVConnected,
+        Self::Authorized,
+        Self::DataSigned,
+        Self::PowerPathClosed,
+        Self::EnergyTransfer,
+    ];
+
+    fn parse(member: &str) -> Option<Self> {
+        match member {
+            "ParkingBayOccupancy" => Some(Self::ParkingBayOccupancy),
+            "EVConnected" => Some(Self::EVConnected),
+            "Authorized" => Some(Self::Authorized),
+            "DataSigned" => Some(Self::DataSigned),
+            "PowerPathClosed" => Some(Self::PowerPathClosed),
+            "EnergyTransfer" => Some(Self::EnergyTransfer),
+            _ => None,
+        }
     }
 }
 
-/// Where in a session OCPP says the transaction ends - `TxCtrlr.TxStopPoint`
+/// Where in a session OCPP says the transaction ends - one member of `TxCtrlr.TxStopPoint`
 /// (`docs/OCPP-2.1-COMPLIANCE-ROADMAP.md` CV2.2).
 ///
-/// **Stopping is not the mirror image of starting.** A start point is a condition that *begins* to
-/// hold, and OCPP starts the transaction once every configured one does - so a configured set
-/// resolves to its *latest* member (see [`TxStartPoint`]). A stop point is a condition that
-/// *ceases* to hold, and a transaction cannot outlive the first of its conditions to lapse - so a
-/// configured set resolves to its **earliest** member, which is why [`Self::from_member_list`]
-/// takes the minimum where its start-point counterpart takes the maximum. The variants are
-/// declared in the order they lapse along the end of a session, so `Ord` is that order.
+/// A stop point is a condition that *ceases* to hold, and the transaction ends as soon as **any**
+/// configured member's condition is no longer met - see [`TxPoints`]. As with [`TxStartPoint`],
+/// no order between the members is assumed.
 ///
-/// Each point is one transition of this crate's own state machine, because that is the only place
-/// where "the condition stopped holding" is observable here: the stop path is driven by an explicit
+/// Each point is one moment this crate can observe: a transition of its own state machine, or
+/// one of the hardware signals [`TxStartPoint`] lists. The stop path is driven by an explicit
 /// [`ConnectorEvent::ChargingStopped`] from the hardware binding rather than by polling each
-/// condition. The three below are exactly the three [`TxStartPoint`] supports, and the declared
-/// `values_list` is narrowed to them for the same reason - see [`TxStartPoint`]'s docs.
+/// condition, and the signals are pushed in the same way.
 ///
 /// One combination OCPP itself warns about is reachable here: a stop point the charge point can
 /// never observe lapsing leaves the transaction open forever. `EVConnected` on a station with
@@ -143,39 +149,136 @@ impl TxStartPoint {
 /// never released (see [`ConnectorState::StoppingLocked`]). OCPP places the responsibility for
 /// sensible start/stop combinations on the CSMS, and this crate does not second-guess a
 /// configuration it was told to honour.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub(crate) enum TxStopPoint {
     /// The authorization that permitted the session ended - the driver stopped it, the CSMS did,
-    /// or the identifier was revoked. The earliest of the three, and the one an operator billing
-    /// for time of use wants.
+    /// or the identifier was revoked. The one an operator billing for time of use wants.
     Authorized,
+    /// Energy stopped flowing. Lapses on a suspension as well as a stop, which is what makes
+    /// E16.FR.06's "end rather than suspend" reachable.
+    EnergyTransfer,
     /// The contactor confirmed open, so energy can no longer flow. This crate's default, and what
     /// it did before `TxStopPoint` was honoured at all.
     #[default]
     PowerPathClosed,
+    /// The meter signed its reading after energy stopped - the transaction ends on the reading a
+    /// calibration-law audit will quote as its end.
+    DataSigned,
     /// The cable left the connector, so the bay is free again - the transaction covers the whole
     /// time the connector was occupied, including the settling after energy stopped.
     EVConnected,
+    /// The vehicle left the parking bay - the transaction covers the time it stood there after
+    /// the cable was put away.
+    ParkingBayOccupancy,
+}
+
+impl TxPoint for TxStopPoint {
+    const ALL: &'static [Self] = &[
+        Self::Authorized,
+        Self::EnergyTransfer,
+        Self::PowerPathClosed,
+        Self::DataSigned,
+        Self::EVConnected,
+        Self::ParkingBayOccupancy,
+    ];
+
+    fn parse(member: &str) -> Option<Self> {
+        match member {
+            "Authorized" => Some(Self::Authorized),
+            "EnergyTransfer" => Some(Self::EnergyTransfer),
+            "PowerPathClosed" => Some(Self::PowerPathClosed),
+            "DataSigned" => Some(Self::DataSigned),
+            "EVConnected" => Some(Self::EVConnected),
+            "ParkingBayOccupancy" => Some(Self::ParkingBayOccupancy),
+            _ => None,
+        }
+    }
+}
+
+/// One of OCPP's `TxStartStopPointEnumType` members as [`TxPoints`] holds it: [`TxStartPoint`] or
+/// [`TxStopPoint`].
+pub(crate) trait TxPoint: Copy + PartialEq + Default + 'static {
+    /// Every member, each one's index being its bit in [`TxPoints`].
+    const ALL: &'static [Self];
+
+    /// The member one `MemberList` entry names, if it names one.
+    fn parse(member: &str) -> Option<Self>;
+}
+
+/// The configured members of `TxCtrlr.TxStartPoint` or `TxStopPoint` - a set, because OCPP does
+/// not rank them and neither does this crate. A transaction starts on the first start member whose
+/// condition is met and ends on the first stop member whose condition stops being met, whichever
+/// those turn out to be in a given session.
+///
+/// A bit set rather than a collection so that [`ConnectorPolicy`] stays `Copy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TxPoints<P> {
+    bits: u8,
+    point: core::marker::PhantomData<P>,
 }
 
-impl TxStopPoint {
-    /// Parses `TxCtrlr.TxStopPoint`'s `MemberList` value, taking the *earliest* point named - see
-    /// this type's docs for why the earliest and not the latest.
+impl<P: TxPoint> TxPoints<P> {
+    /// Parses the variable's `MemberList` value.
     ///
-    /// An empty or unrecognised value falls back to the default, for the reason
-    /// [`TxStartPoint::from_member_list`] gives: a charge point is better off ending transactions
-    /// the conventional way than never ending them.
+    /// An empty value, or one naming nothing recognised, falls back to the single default point
+    /// rather than erroring: the value reaching here has already passed CV3's validation against
+    /// the declared `values_list`, so an unparseable one means the device model was written by
+    /// something other than `SetVariables` (a hardware binding, a persisted restore), and a charge
+    /// point is better off starting and ending transactions the conventional way than not at all.
     pub fn from_member_list(value: &str) -> Self {
-        value
+        let points = value
             .split(',')
-            .filter_map(|member| match member.trim() {
-                "Authorized" => Some(Self::Authorized),
-                "PowerPathClosed" => Some(Self::PowerPathClosed),
-                "EVConnected" => Some(Self::EVConnected),
-                _ => None,
-            })
-            .min()
-            .unwrap_or_default()
+            .filter_map(|member| P::parse(member.trim()))
+            .fold(Self::empty(), Self::with);
+        if points.bits == 0 {
+            Self::default()
+        } else {
+            points
+        }
+    }
+
+    /// Whether `point` is one of the configured members.
+    pub fn contains(self, point: P) -> bool {
+        self.bits & Self::bit(point) != 0
+    }
+
+    /// The configured members, in declaration order - which carries no meaning beyond being
+    /// deterministic.
+    pub fn iter(self) -> impl Iterator<Item = P> {
+        P::ALL
+            .iter()
+            .copied()
+            .filter(move |point| self.contains(*point))
+    }
+
+    /// No members at all - what a connector that may not begin a transaction right now is given
+    /// as its start points.
+    pub fn empty() -> Self {
+        Self {
+            bits: 0,
+            point: core::marker::PhantomData,
+        }
+    }
+
+    fn with(self, point: P) -> Self {
+        Self {
+            bits: self.bits | Self::bit(point),
+            point: core::marker::PhantomData,
+        }
+    }
+
+    fn bit(point: P) -> u8 {
+        P::ALL
+            .iter()
+            .position(|member| *member == point)
+            .map_or(0, |index| 1 << index)
+    }
+}
+
+impl<P: TxPoint> Default for TxPoints<P> {
+    /// The single point OCPP (and this crate) defaults the variable to.
+    fn default() -> Self {
+        Self::empty().with(P::default())
     }
 }
 
@@ -194,10 +297,11 @@ pub(crate) struct ConnectorPolicy {
     /// `false`: the transaction is *suspended* instead and can resume if the cable comes back,
     /// which is what a driver who briefly unplugs to reseat a connector expects.
     pub stop_tx_on_ev_side_disconnect: bool,
-    /// `TxCtrlr.TxStartPoint` - where in the session the transaction begins (CV2.2).
-    pub tx_start_point: TxStartPoint,
-    /// `TxCtrlr.TxStopPoint` - where in the session the transaction ends (CV2.2).
-    pub tx_stop_point: TxStopPoint,
+    /// `TxCtrlr.TxStartPoint` - the points in the session at which the transaction may begin
+    /// (CV2.2).
+    pub tx_start_points: TxPoints<TxStartPoint>,
+    /// `TxCtrlr.TxStopPoint` - the points in the session at which the transaction may end (CV2.2).
+    pub tx_stop_points: TxPoints<TxStopPoint>,
     /// `OCPPCommCtrlr.UnlockOnEVSideDisconnect` - **E09.FR.02 vs E09.FR.03**.
     ///
     /// `true` (OCPP's default): the cable leaving the EV releases this station's end too, so the
@@ -232,8 +336,8 @@ impl Default for ConnectorPolicy {
     fn default() -> Self {
         Self {
             stop_tx_on_ev_side_disconnect: true,
-            tx_start_point: TxStartPoint::Authorized,
-            tx_stop_point: TxStopPoint::PowerPathClosed,
+            tx_start_points: TxPoints::default(),
+            tx_stop_points: TxPoints::default(),
             unlock_on_ev_side_disconnect: true,
             // OCPP's default, and the safe one: an identifier the CSMS has just refused should
             // not keep drawing energy nobody will be billed for.
@@ -359,6 +463,11 @@ impl ConnectorState {
             (Self::Charging | Self::SuspendedEvse, ConnectorEvent::ChargingSuspendedByEv) => {
                 (Self::SuspendedEv, None)
             }
+            // An EV that stopped communicating has stopped drawing energy too, and nothing but
+            // the EV coming back can resume it - the same pause as above, from the same side.
+            (Self::Charging | Self::SuspendedEvse, ConnectorEvent::EvCommunicationLost) => {
+                (Self::SuspendedEv, None)
+            }
             (Self::Charging | Self::SuspendedEv, ConnectorEvent::ChargingSuspendedByEvse) => {
                 (Self::SuspendedEvse, None)
             }
@@ -590,3 +699,51 @@ mod suspension_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod tx_point_tests {
+    use super::*;
+
+    #[test]
+    fn every_named_member_is_kept_and_none_is_folded_into_another() {
+        let start =
+            TxPoints::<TxStartPoint>::from_member_list("EnergyTransfer, ParkingBayOccupancy");
+        assert_eq!(
+            start.iter().collect::<alloc::vec::Vec<_>>(),
+            alloc::vec![
+                TxStartPoint::ParkingBayOccupancy,
+                TxStartPoint::EnergyTransfer
+            ]
+        );
+        assert!(!start.contains(TxStartPoint::Authorized));
+
+        let stop =
+            TxPoints::<TxStopPoint>::from_member_list("DataSigned,PowerPathClosed,EVConnected");
+        assert_eq!(
+            stop.iter().collect::<alloc::vec::Vec<_>>(),
+            alloc::vec![
+                TxStopPoint::PowerPathClosed,
+                TxStopPoint::DataSigned,
+                TxStopPoint::EVConnected
+            ]
+        );
+    }
+
+    #[test]
+    fn a_value_naming_nothing_recognised_falls_back_to_the_default_point() {
+        for value in ["", " ", "Teleported"] {
+            assert_eq!(
+                TxPoints::<TxStartPoint>::from_member_list(value)
+                    .iter()
+                    .collect::<alloc::vec::Vec<_>>(),
+                alloc::vec![TxStartPoint::Authorized]
+            );
+            assert_eq!(
+                TxPoints::<TxStopPoint>::from_member_list(value)
+                    .iter()
+                    .collect::<alloc::vec::Vec<_>>(),
+                alloc::vec![TxStopPoint::PowerPathClosed]
+            );
+        }
+    }
+}
@@ -3,6 +3,8 @@ use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
 
+use crate::hardware::Capabilities;
+
 /// A component in the protocol-version-independent Component/Variable device model (OCPP
 /// `ComponentType`), addressed the same way every other piece of this crate's state is: this
 /// crate's own `(evse_id, connector_id)` `usize` indices, never OCPP's wire `EVSE` type.
@@ -621,19 +623,27 @@ pub(crate) struct VariableBounds {
     pub values: Option<&'static [&'static str]>,
 }
 
-/// The measurands this firmware actually produces - the five fields of
-/// [`MeterSample`](crate::state::MeterSample), and so the only values the measurand variables
-/// accept (CV2.6).
+/// The measurands this firmware actually produces - one per quantity
+/// [`MeterSample`](crate::state::MeterSample) carries, and so the only values the measurand
+/// variables accept (CV2.6).
 ///
 /// Narrower than OCPP's `MeasurandEnumType` for the same reason `TX_START_STOP_POINTS` is narrower
 /// than its enum: accepting a measurand this crate cannot sample and then not sending it is the
-/// silent lie B05.FR.09 forbids.
+/// silent lie B05.FR.09 forbids. `Temperature` is the one entry only 1.6J reports; it stays
+/// listed because the same variables back 1.6J's `MeterValuesSampledData` keys, and a 2.x CSMS
+/// cannot name it anyway - it is not in 2.x's enum.
 const SUPPORTED_MEASURANDS: &[&str] = &[
     "Energy.Active.Import.Register",
     "Power.Active.Import",
     "Current.Import",
     "Voltage",
     "SoC",
+    "Energy.Active.Export.Register",
+    "Power.Active.Export",
+    "Power.Reactive.Import",
+    "Power.Factor",
+    "Frequency",
+    "Temperature",
 ];
 
 /// Every bound this crate can state on a built-in default - see [`VariableBounds`].
@@ -745,8 +755,8 @@ pub(crate) const VARIABLE_BOUNDS: &[VariableBounds] = &[
         values: None,
     },
     // The two transaction points are `MemberList`s over OCPP's `TxStartStopPointEnumType`. Their
-    // value sets are the reason a `SetVariables` naming a point this crate does not implement is
-    // *rejected* rather than stored (CV2.2) - see `TX_START_STOP_POINTS`.
+    // value sets are the reason a `SetVariables` naming something outside that enum is *rejected*
+    // rather than stored (CV2.2) - see `TX_START_STOP_POINTS`.
     VariableBounds {
         component: "SampledDataCtrlr",
         variable: "TxStartedMeasurands",
@@ -771,6 +781,15 @@ pub(crate) const VARIABLE_BOUNDS: &[VariableBounds] = &[
         max: None,
         values: Some(SUPPORTED_MEASURANDS),
     },
+    // OCPP's own three values; anything else would leave `signed_readings` guessing.
+    VariableBounds {
+        component: "SampledDataCtrlr",
+        variable: "PublicKeyWithSignedMeterValue",
+        instance: None,
+        min: None,
+        max: None,
+        values: Some(&["Never", "OncePerTransaction", "EveryMeterValue"]),
+    },
     VariableBounds {
         component: "AlignedDataCtrlr",
         variable: "Measurands",
@@ -805,21 +824,41 @@ pub(crate) const VARIABLE_BOUNDS: &[VariableBounds] = &[
     },
 ];
 
-/// The subset of OCPP's `TxStartStopPointEnumType` this charge point can actually observe, and so
-/// the only values `TxCtrlr.TxStartPoint` and `TxCtrlr.TxStopPoint` accept (CV2.2).
+/// The members of OCPP's `TxStartStopPointEnumType` every charge point can observe, and so the
+/// values `TxCtrlr.TxStartPoint` and `TxCtrlr.TxStopPoint` accept until the hardware binding
+/// declares more (CV2.2).
 ///
 /// Shared by both variables because the answer is the same for both: a point this charge point
 /// cannot see *begin* is equally one it cannot see *cease*.
 ///
-/// **Deliberately narrower than the enum OCPP defines.** `ParkingBayOccupancy` needs a bay sensor
-/// this crate has no binding for, `DataSigned` needs signed meter values it does not produce, and
-/// `EnergyTransfer` needs a "current is actually flowing" signal distinct from the contactor being
-/// closed. Declaring them would mean accepting a `SetVariables` and then starting transactions at
-/// some other point - the silent-lie failure mode B05.FR.09 forbids. Declaring only these three
-/// makes CV3's validation reject the rest with a reason, and `VariableCharacteristics::values_list`
-/// is exactly where a charge point is supposed to say what it accepts.
+/// **Deliberately narrower than the enum OCPP defines.** `ParkingBayOccupancy`, `EnergyTransfer`
+/// and `DataSigned` are marked by signals only the binding can raise - a bay sensor, a current
+/// measurement, a signing meter. Declaring them on a station that raises none would mean accepting
+/// a `SetVariables` and then never starting or ending a transaction there - the silent-lie failure
+/// mode B05.FR.09 forbids. [`tx_start_stop_points`] adds each one only for a binding whose
+/// [`Capabilities`] say it raises the signal.
 const TX_START_STOP_POINTS: &[&str] = &["EVConnected", "Authorized", "PowerPathClosed"];
 
+/// The values `TxCtrlr.TxStartPoint`/`TxStopPoint` accept on a station with `capabilities`:
+/// [`TX_START_STOP_POINTS`], plus each signalled point the binding declares it raises.
+pub(crate) fn tx_start_stop_points(capabilities: &Capabilities) -> Vec<String> {
+    let signalled = [
+        (capabilities.parking_bay_sensor, "ParkingBayOccupancy"),
+        (capabilities.signed_meter_values, "DataSigned"),
+        (capabilities.energy_transfer_detection, "EnergyTransfer"),
+    ];
+    TX_START_STOP_POINTS
+        .iter()
+        .copied()
+        .chain(
+            signalled
+                .into_iter()
+                .filter_map(|(declared, point)| declared.then_some(point)),
+        )
+        .map(String::from)
+        .collect()
+}
+
 /// The transaction limits *every* build enforces - the value `TxCtrlr.SupportedLimits` starts
 /// with (**E16**, CV15/CV21).
 ///
@@ -1218,6 +1257,9 @@ pub(crate) const DEFAULT_VARIABLES: &[DefaultVariable] = &[
         unit: Some("s"),
         value: "0",
         mutability: VariableMutability::ReadWrite,
+        // J02: read by `crate::transactions::run_transaction_meter_sampling`, and made writable
+        // by `advertise_meter_sampling_support` when that loop starts - not before, since a
+        // value with nothing driving it would silence readings rather than pace them.
         honoured: false,
         persistent: false,
     },
@@ -1253,9 +1295,37 @@ pub(crate) const DEFAULT_VARIABLES: &[DefaultVariable] = &[
         unit: Some("s"),
         value: "0",
         mutability: VariableMutability::ReadWrite,
+        // J02: read by `crate::transactions::run_transaction_meter_sampling`, and made writable
+        // by `advertise_meter_sampling_support` when that loop starts - not before, since a
+        // value with nothing driving it would silence readings rather than pace them.
         honoured: false,
         persistent: false,
     },
+    DefaultVariable {
+        component: "SampledDataCtrlr",
+        variable: "SignReadings",
+        instance: None,
+        data_type: VariableDataType::Boolean,
+        unit: None,
+        value: "false",
+        mutability: VariableMutability::ReadWrite,
+        // Read per transaction event by `crate::meter_values::signed_readings`: while `true`, the
+        // start and end readings carry the meter's signature if the hardware supplied one.
+        honoured: true,
+        persistent: false,
+    },
+    DefaultVariable {
+        component: "SampledDataCtrlr",
+        variable: "PublicKeyWithSignedMeterValue",
+        instance: None,
+        data_type: VariableDataType::OptionList,
+        unit: None,
+        value: "Never",
+        mutability: VariableMutability::ReadWrite,
+        // Read alongside `SignReadings`, and only meaningful while it is `true`.
+        honoured: true,
+        persistent: false,
+    },
     DefaultVariable {
         component: "AlignedDataCtrlr",
         variable: "Measurands",
@@ -1276,6 +1346,9 @@ pub(crate) const DEFAULT_VARIABLES: &[DefaultVariable] = &[
         unit: None,
         value: "Energy.Active.Import.Register",
         mutability: VariableMutability::ReadWrite,
+        // 1.6J's `StopTxnAlignedData`: read per `StopTransaction` by `aligned_ended_measurands`,
+        // and made writable by `advertise_aligned_stop_data_support` when a 1.6J aligned loop
+        // starts. 2.x never reports aligned readings in `Ended`, so there it stays read-only.
         honoured: false,
         persistent: false,
     },
@@ -1508,7 +1581,7 @@ pub(crate) const DEFAULT_VARIABLES: &[DefaultVariable] = &[
         // token is authorized (see `advance_transaction`), not on plug-in or on energy flow.
         value: "Authorized",
         mutability: VariableMutability::ReadWrite,
-        // CV2.2: read into `ConnectorPolicy::tx_start_point` and honoured by
+        // CV2.2: read into `ConnectorPolicy::tx_start_points` and honoured by
         // `advance_transaction`.
         honoured: true,
         persistent: false,
@@ -1525,7 +1598,7 @@ pub(crate) const DEFAULT_VARIABLES: &[DefaultVariable] = &[
         // unparseable value fall back honestly.
         value: "PowerPathClosed",
         mutability: VariableMutability::ReadWrite,
-        // CV2.2: read into `ConnectorPolicy::tx_stop_point` and honoured by `advance_transaction`.
+        // CV2.2: read into `ConnectorPolicy::tx_stop_points` and honoured by `advance_transaction`.
         honoured: true,
         persistent: false,
     },
@@ -1624,6 +1697,23 @@ pub(crate) const DEFAULT_VARIABLES: &[DefaultVariable] = &[
         honoured: false,
         persistent: false,
     },
+    // Not an OCPP-standard variable: how many inbound CSMS messages this charge point has counted
+    // as invalid since boot - malformed, not matching their schema, or over a declared message
+    // limit. Kept current by the `InvalidMessageReceived` arm of `ChargePointState::apply`, so an
+    // operator can spot a misbehaving CSMS with one `GetVariables` rather than by pulling the
+    // security log, whose `InvalidMessages` entries are rate-limited (see
+    // `crate::invalid_messages`). Not persistent: it describes this boot's connections.
+    DefaultVariable {
+        component: "SecurityCtrlr",
+        variable: "InvalidMessages",
+        instance: None,
+        data_type: VariableDataType::Integer,
+        unit: None,
+        value: "0",
+        mutability: VariableMutability::ReadOnly,
+        honoured: true,
+        persistent: false,
+    },
     // CV9 (A02.FR.17/A03.FR.17): the first back-off before a `SignCertificate` the CSMS accepted
     // but never answered is resent. 30 seconds because the two failure modes it sits between are
     // asymmetric: a CSMS that signs in three seconds costs nothing by being asked again at thirty,
@@ -5,16 +5,17 @@ use chrono::{DateTime, Utc};
 use crate::clock::MonotonicInstant;
 use crate::hardware::Capabilities;
 use crate::state::{
-    AuthorizationCacheEntry, AuthorizationStatus, BatterySwapEvent, ChargingLimitSource,
-    ChargingProfile, ChargingProfileCriteria, ChargingProfileId, ChargingProfileScope, Component,
-    ConnectorState, ConnectorStatus, ContractCertificate, DERControlQuery, DeviceModelEvent,
-    DisplayMessageId, DisplayedMessage, EVChargingNeeds, EVChargingScheduleReport,
-    ExternalChargingLimit, IdToken, InstalledChargingProfile, InstalledDERControl, LocalListEntry,
-    MeterSample, NetworkConnectionProfile, NetworkProfileSlot, PendingBatterySwap,
-    PeriodicEventStreamId, PeriodicEventStreamParams, RegistrationStatus, Reservation,
-    ReservationId, ResetKind, ResetTarget, SecurityEvent, SmartChargingNotification, StopReason,
-    Tariff, TariffClearCriteria, TariffScope, Transaction, TransactionId, TriggeredMonitor,
-    Variable, VariableAttributeType, VariableMonitorId, VariableMonitoringEvent,
+    AuthorizationCacheEntry, AuthorizationStatus, AvailabilityTarget, BatterySwapEvent,
+    ChargingLimitSource, ChargingProfile, ChargingProfileCriteria, ChargingProfileId,
+    ChargingProfileScope, Component, ConnectorState, ConnectorStatus, ContractCertificate,
+    DERControlQuery, DeviceModelEvent, DisplayMessageId, DisplayedMessage, EVChargingNeeds,
+    EVChargingScheduleReport, ExternalChargingLimit, IdToken, InstalledChargingProfile,
+    InstalledDERControl, InvalidMessage, LocalListEntry, MeterSample, NetworkConnectionProfile,
+    NetworkProfileSlot, PendingBatterySwap, PeriodicEventStreamId, PeriodicEventStreamParams,
+    RegistrationStatus, Reservation, ReservationId, ResetKind, ResetTarget, SecurityEvent,
+    SmartChargingNotification, StopReason, Tariff, TariffClearCriteria, TariffScope, Transaction,
+    TransactionId, TriggeredMonitor, Variable, VariableAttributeType, VariableMonitorId,
+    VariableMonitoringEvent,
 };
 
 /// An event applied to [`crate::state::ChargePointState`], driving its state machine forward.
@@ -55,6 +56,26 @@ pub enum ChargePointEvent {
     /// via [`crate::security::report_security_event`], not tied to connector/EVSE state. See
     /// `docs/ROADMAP.md` §1.
     SecurityEventOccurred(SecurityEvent),
+    /// An inbound CSMS message was malformed, did not match its schema, or exceeded a declared
+    /// message limit. Counted into [`crate::state::InvalidMessageTally`], which decides whether it
+    /// raises an `InvalidMessages` security event now or waits for the next
+    /// [`Self::InvalidMessageSummaryDue`]. Raised via
+    /// [`crate::invalid_messages::report_invalid_message`].
+    InvalidMessageReceived(InvalidMessage),
+    /// The rate-limiting interval for `InvalidMessages` reports ended - see
+    /// [`crate::invalid_messages::run_invalid_message_summaries`].
+    InvalidMessageSummaryDue,
+    /// Someone asked for the security log to be cleared: the CSMS, by writing `true` to
+    /// `SecurityLogCtrlr.Clear`, or a technician through
+    /// [`crate::maintenance::MaintenanceCommand::ClearSecurityLog`]. Recorded as
+    /// [`crate::state::ChargePointState::pending_security_log_clear`] until
+    /// [`crate::persistence::run_security_log_clears`] carries it out.
+    SecurityLogClearRequested {
+        /// Who asked, as the `SecurityLogWasCleared` event's `techInfo` will name them.
+        requested_by: String,
+    },
+    /// The pending security-log clear was carried out.
+    SecurityLogClearCompleted,
     /// The CSMS requested a `Reset` (OCPP `Reset`). Recorded as a
     /// [`crate::state::PendingReset`] and fulfilled - possibly immediately, possibly once
     /// `target` goes idle - as a [`HardwareCommand::Reboot`]. See `crate::reset` and
@@ -66,6 +87,29 @@ pub enum ChargePointEvent {
         /// idle first.
         kind: ResetKind,
     },
+    /// The CSMS asked for `target` to become inoperative while a transaction was in progress
+    /// there, and was answered `Scheduled` (OCPP `ChangeAvailability`, G03.FR.05). Recorded in
+    /// [`crate::state::ChargePointState::scheduled_unavailability`] and applied, as the
+    /// `SetUnavailable` the request would otherwise have sent at once, when `target` has no
+    /// transaction left. See `crate::availability`.
+    UnavailabilityScheduled {
+        /// The scope to take out of service.
+        target: AvailabilityTarget,
+    },
+    /// A later `ChangeAvailability` to operative superseded whatever was scheduled for `target`,
+    /// or for anything inside it.
+    ScheduledUnavailabilityCancelled {
+        /// The scope the operative request addressed.
+        target: AvailabilityTarget,
+    },
+    /// Scheduled availability changes recovered from durable storage at boot - see
+    /// [`crate::persistence::restore_scheduled_unavailability`]. Each is applied at once if its
+    /// target has no transaction in progress, which is the case whenever the transaction it was
+    /// waiting for ended with the power.
+    PersistedScheduledUnavailabilityRestored {
+        /// The recovered scopes, oldest first.
+        targets: Vec<AvailabilityTarget>,
+    },
     /// The CSMS wrote a network connection profile into a configuration slot (OCPP
     /// `SetNetworkProfile`). Stored for reporting and for a future connection attempt; it does
     /// **not** switch the live connection - see [`crate::state::NetworkProfileStore`].
@@ -467,7 +511,16 @@ impl ChargePointEvent {
             Self::RegistrationStatusReceived { .. } => "RegistrationStatusReceived",
             Self::LocalListUpdated { .. } => "LocalListUpdated",
             Self::SecurityEventOccurred { .. } => "SecurityEventOccurred",
+            Self::InvalidMessageReceived { .. } => "InvalidMessageReceived",
+            Self::InvalidMessageSummaryDue { .. } => "InvalidMessageSummaryDue",
+            Self::SecurityLogClearRequested { .. } => "SecurityLogClearRequested",
+            Self::SecurityLogClearCompleted => "SecurityLogClearCompleted",
             Self::ResetRequested { .. } => "ResetRequested",
+            Self::UnavailabilityScheduled { .. } => "UnavailabilityScheduled",
+            Self::ScheduledUnavailabilityCancelled { .. } => "ScheduledUnavailabilityCancelled",
+            Self::PersistedScheduledUnavailabilityRestored { .. } => {
+                "PersistedScheduledUnavailabilityRestored"
+            }
             Self::NetworkProfileSet { .. } => "NetworkProfileSet",
             Self::PersistedNetworkProfilesRestored { .. } => "PersistedNetworkProfilesRestored",
             Self::AuthorizationCached { .. } => "AuthorizationCached",
@@ -577,10 +630,19 @@ impl ConnectorEvent {
             Self::AuthorizationRevoked { .. } => "AuthorizationRevoked",
             Self::TransactionLimitSet { .. } => "TransactionLimitSet",
             Self::TransactionElapsed { .. } => "TransactionElapsed",
+            Self::TransactionMeterValuesDue => "TransactionMeterValuesDue",
+            Self::TransactionEndedSampleDue { .. } => "TransactionEndedSampleDue",
+            Self::TransactionAlignedSampleDue { .. } => "TransactionAlignedSampleDue",
             Self::ChargingStopped { .. } => "ChargingStopped",
             Self::ChargingSuspendedByEv { .. } => "ChargingSuspendedByEv",
             Self::ChargingSuspendedByEvse { .. } => "ChargingSuspendedByEvse",
             Self::ChargingResumed { .. } => "ChargingResumed",
+            Self::ParkingBayOccupied => "ParkingBayOccupied",
+            Self::ParkingBayVacated => "ParkingBayVacated",
+            Self::EnergyTransferStarted => "EnergyTransferStarted",
+            Self::EnergyTransferStopped => "EnergyTransferStopped",
+            Self::EvCommunicationLost => "EvCommunicationLost",
+            Self::TransactionEventTriggered => "TransactionEventTriggered",
             Self::MeterValueSampled { .. } => "MeterValueSampled",
             Self::Reserved { .. } => "Reserved",
             Self::ReservationCancelled { .. } => "ReservationCancelled",
@@ -763,6 +825,34 @@ pub enum ConnectorEvent {
     ///
     /// A no-op on a connector with no transaction running.
     TransactionElapsed(i64),
+    /// `SampledDataCtrlr.TxUpdatedInterval` has elapsed: report this connector's latest reading in
+    /// a `TransactionEvent(Updated)` with `triggerReason = MeterValuePeriodic` (J02).
+    ///
+    /// Raised by [`crate::transactions::run_transaction_meter_sampling`], which owns the clock.
+    /// While that interval is configured, a [`Self::MeterValueSampled`] only *records* the
+    /// reading - the hardware may sample once a second, and forwarding each one would flood the
+    /// CSMS - so this is the only thing that reports one. With it unset, every pushed reading is
+    /// reported as it arrives and this event is never raised.
+    ///
+    /// A no-op unless the connector's transaction is `Charging` and has a reading to report, the
+    /// same two conditions an unthrottled reading is reported under.
+    TransactionMeterValuesDue,
+    /// `SampledDataCtrlr.TxEndedInterval` has elapsed: keep this connector's latest reading,
+    /// stamped with the given instant, for the transaction's eventual `Ended` event (J02). See
+    /// [`crate::state::EvseState::ended_meter_samples`].
+    ///
+    /// Raised by [`crate::transactions::run_transaction_meter_sampling`], which supplies the
+    /// timestamp the clock-free state machine cannot. A no-op on a connector with no transaction
+    /// running, or no reading yet.
+    TransactionEndedSampleDue(DateTime<Utc>),
+    /// An `AlignedDataCtrlr.Interval` boundary has passed: keep this connector's latest reading,
+    /// stamped with the given instant, for the transaction's eventual end - 1.6J's
+    /// `StopTxnAlignedData`. See [`crate::state::EvseState::ended_meter_samples`].
+    ///
+    /// Raised by [`crate::meter_values::run_aligned_meter_values`] beside the standalone
+    /// `MeterValues` the same boundary sends. A no-op on a connector with no transaction running,
+    /// or no reading yet.
+    TransactionAlignedSampleDue(DateTime<Utc>),
     /// The authorization held against this connector is no longer wanted - the cable arrived and
     /// it was dispatched, or `TxCtrlr.EVConnectionTimeOut` expired without one. Covers both a held
     /// `RequestStartTransaction` (F02.FR.07/.08) and a card presented before the cable
@@ -786,9 +876,39 @@ pub enum ConnectorEvent {
     /// Energy is flowing again after a suspension from either side. A no-op on a connector that
     /// isn't suspended.
     ChargingResumed,
+    /// A vehicle now stands in the parking bay this connector serves, from the binding's bay
+    /// sensor. Moves nothing about the connector; it is the moment `TxCtrlr.TxStartPoint`'s
+    /// `ParkingBayOccupancy` names (CV2.2). A binding that raises it declares
+    /// [`Capabilities::parking_bay_sensor`](crate::hardware::Capabilities::parking_bay_sensor),
+    /// which is what lets a CSMS configure that point.
+    ParkingBayOccupied,
+    /// The vehicle left the parking bay this connector serves - the moment
+    /// `TxCtrlr.TxStopPoint`'s `ParkingBayOccupancy` names (CV2.2).
+    ParkingBayVacated,
+    /// Current started flowing through this connector, as measured rather than inferred from
+    /// the contactor closing - the moment `TxCtrlr.TxStartPoint`'s `EnergyTransfer` names
+    /// (CV2.2). Pushed in by the hardware binding alongside, not instead of,
+    /// [`Self::ContactorClosed`], and declared through
+    /// [`Capabilities::energy_transfer_detection`](crate::hardware::Capabilities::energy_transfer_detection).
+    EnergyTransferStarted,
+    /// Current stopped flowing through this connector, whether the session stopped or either side
+    /// suspended it - the moment `TxCtrlr.TxStopPoint`'s `EnergyTransfer` names (CV2.2).
+    EnergyTransferStopped,
+    /// The EV is no longer communicating - ISO 15118 dropped, or the control pilot reports state
+    /// E or F. Pushed in by the hardware binding, which alone sees the pilot. Suspends a charging
+    /// connector the way [`Self::ChargingSuspendedByEv`] does, but is reported to the CSMS as the
+    /// loss it is rather than as the EV choosing to pause.
+    EvCommunicationLost,
+    /// The CSMS asked for this connector's transaction to be reported now -
+    /// `TriggerMessage(TransactionEvent)`, or 1.6J's `TriggerMessage(MeterValues)` (F06). Raised by
+    /// [`crate::remote_control::handle_trigger_message`]. A no-op without a transaction.
+    TransactionEventTriggered,
     /// Hardware sampled a meter reading. Reported to the CSMS (via the active transaction's
     /// next TransactionEvent) only while the connector is actually `Charging`; ignored
     /// otherwise. See `docs/ROADMAP.md` §10.
+    ///
+    /// A reading carrying [`MeterSample::signed`] is also `TxCtrlr`'s `DataSigned` point (CV2.2),
+    /// so a meter that signs only the readings that bound a session can start or end one.
     MeterValueSampled(MeterSample),
     /// The CSMS reserved this connector (OCPP `ReserveNow`) while it's `Available`. See
     /// `docs/ROADMAP.md` §8.
@@ -908,6 +1028,12 @@ pub enum ConnectorEvent {
 ///
 /// `PartialEq` only, not `Eq`: [`Self::SmartChargingNotification`] can carry a
 /// [`crate::state::ChargingSchedule`], whose periods carry `f64` limits.
+///
+/// [`Self::TransactionEvent`] is left unboxed although it is by far the widest variant, unlike the
+/// boxed payloads on [`ConnectorEvent`]: an effect lives only from `apply` returning to the actor
+/// routing it onto its own channel, which carries the [`TransactionEventOccurred`] itself - so
+/// nothing ever queues effects, and boxing would buy an allocation per event for no saving.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq)]
 pub enum ChargePointEffect {
     /// [`crate::state::ChargePointState`] itself changed; the actor publishes the new state to
@@ -918,6 +1044,11 @@ pub enum ChargePointEffect {
     /// A connector's OCPP-visible status changed; the Availability functional block reports
     /// this to the CSMS via StatusNotification.
     StatusNotification(ConnectorStatusChanged),
+    /// The charge point's own status changed - its [`crate::state::LifecycleState`], as
+    /// [`crate::state::ChargePointState::station_status`] reports it. Only 1.6J has a wire
+    /// message for this (`StatusNotification` on `connectorId` 0); 2.x learns of it through the
+    /// [`Self::StatusNotification`] each connector gets alongside.
+    StationStatusNotification(ConnectorStatus),
     /// A transaction started, was updated, or ended; the Transactions functional block reports
     /// this to the CSMS via TransactionEvent.
     TransactionEvent(TransactionEventOccurred),
@@ -961,6 +1092,7 @@ impl ChargePointEffect {
             Self::StateChanged { .. } => "StateChanged",
             Self::HardwareCommand { .. } => "HardwareCommand",
             Self::StatusNotification { .. } => "StatusNotification",
+            Self::StationStatusNotification { .. } => "StationStatusNotification",
             Self::TransactionEvent { .. } => "TransactionEvent",
             Self::AuthorizationRequested { .. } => "AuthorizationRequested",
             Self::SecurityEventOccurred { .. } => "SecurityEventOccurred",
@@ -1036,8 +1168,10 @@ pub struct ConnectorStatusChanged {
     pub evse_id: usize,
     /// The changed connector's index within its EVSE.
     pub connector_id: usize,
-    /// The connector's new status, collapsed to OCPP 2.x's coarser 5-value status - what most
-    /// version adapters need.
+    /// The connector's new reported status, collapsed to OCPP 2.x's coarser 5-value status - what
+    /// most version adapters need. This is the most restrictive of the connector's own status and
+    /// its EVSE's and the station's, so a connector inside an out-of-service EVSE reports
+    /// `Unavailable` while `connector_state` is still `Available`.
     pub status: ConnectorStatus,
     /// The connector's new state at its full, protocol-version-independent granularity.
     /// Versions with a richer wire status than `status` (e.g. 1.6J's `Preparing`/`Charging`/
@@ -1083,6 +1217,25 @@ pub enum TransactionUpdateReason {
     /// suspension, and each maps to its own `triggerReason`
     /// (`CostLimitReached`/`EnergyLimitReached`/`SoCLimitReached`/`TimeLimitReached`).
     LimitReached(crate::state::TransactionLimitKind),
+    /// The EV stopped talking to the station mid-session - ISO 15118 communication dropped, the
+    /// control pilot went to state E/F, or the cable left the EV under E10 - so the transaction is
+    /// reported `SuspendedEV` (or whatever it already was) without ending.
+    EvCommunicationLost,
+    /// The meter produced a signed reading during the session, reported as it arrived rather than
+    /// at the next periodic report, so the CSMS holds every signed record the meter issued.
+    SignedDataReceived,
+    /// The CSMS asked for this transaction's state with `TriggerMessage(TransactionEvent)`
+    /// (F06) - carried on 1.6J as the `MeterValues` the equivalent trigger asks for.
+    Trigger,
+    /// A `ChangeTransactionTariff` replaced the tariff pricing this transaction (I12, 2.1 only).
+    TariffChanged,
+    /// The cable reached a connector whose transaction was already running - begun by an
+    /// authorization, the bay sensor, or any other start point ahead of the cable.
+    CablePluggedIn,
+    /// The bay sensor saw a vehicle arrive at a session already running.
+    EvDetected,
+    /// The bay sensor saw the vehicle leave while the session goes on, ahead of a later stop point.
+    EvDeparted,
 }
 
 /// One in-flight transaction read back from durable storage at boot, carried by
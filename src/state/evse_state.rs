@@ -2,7 +2,8 @@ use alloc::vec;
 use alloc::vec::Vec;
 
 use crate::state::{
-    ConnectorState, EvseEvent, ExternalChargingLimit, MeterSample, Reservation, Tariff, Transaction,
+    ConnectorState, EvseEvent, ExternalChargingLimit, MeterSample, Reservation, Tariff,
+    TimestampedMeterSample, Transaction,
 };
 
 /// The internal state of one EVSE (Electric Vehicle Supply Equipment): its own availability/
@@ -88,6 +89,21 @@ pub struct EvseState {
     /// [`ConnectorEvent::MeterValueSampled`](crate::state::ConnectorEvent::MeterValueSampled) for
     /// that connector.
     pub latest_meter_samples: Vec<Option<MeterSample>>,
+    /// The readings each connector's active transaction has kept for its `Ended` event, oldest
+    /// first, indexed the same as `connectors` - one per `SampledDataCtrlr.TxEndedInterval`
+    /// sample, plus 1.6J's `StopTxnAlignedData` readings beside them when a 1.6J aligned loop
+    /// keeps them (J02).
+    ///
+    /// Held here rather than on the [`Transaction`] so that a running transaction stays small:
+    /// every `Updated` event clones it into the offline queue, and every state change re-persists
+    /// it. The series is moved onto the `Ended` event's snapshot
+    /// ([`Transaction::ended_meter_samples`]) when the transaction ends, and cleared when the next
+    /// one starts. Bounded by
+    /// [`StateLimits::max_ended_meter_samples`](crate::state::StateLimits::max_ended_meter_samples).
+    ///
+    /// Not persisted: a transaction restored after a reboot resumes with an empty series, so its
+    /// `Ended` event reports only what was kept since the restart.
+    pub ended_meter_samples: Vec<Vec<TimestampedMeterSample>>,
     /// The current limit most recently *requested* of each connector's hardware, in milliamps,
     /// indexed the same as `connectors`. `None` means no installed charging profile imposes a
     /// limit on that connector - see [`ConnectorEvent::CurrentLimitComputed`](crate::state::ConnectorEvent::CurrentLimitComputed)
@@ -162,6 +178,7 @@ impl EvseState {
             running_cost: vec![None; connector_count],
             running_cost_totals: vec![None; connector_count],
             latest_meter_samples: vec![None; connector_count],
+            ended_meter_samples: vec![Vec::new(); connector_count],
             charging_limits: vec![None; connector_count],
             honoured_reservations: vec![None; connector_count],
             pending_remote_starts: vec![None; connector_count],
@@ -0,0 +1,190 @@
+use alloc::string::String;
+
+use crate::state::{SecurityEvent, SecurityEventType};
+
+/// Why an inbound CSMS message was counted as invalid. See [`crate::invalid_messages`] for where
+/// each kind is detected, and on which connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InvalidMessageKind {
+    /// Not an OCPP-J message at all: not JSON, not an array, no integer message type id, or a
+    /// CALL whose `[2, uniqueId, action, payload]` envelope does not parse. `ocpp-client` drops
+    /// these without a reply - there is no `uniqueId` to address one to.
+    Malformed,
+    /// A CALL whose payload did not decode into the action's request type: a missing required
+    /// field, a value of the wrong type, an enumeration value the schema does not allow.
+    /// `ocpp-client` answers it with a CALLERROR before any handler runs.
+    Undecodable,
+    /// A CALL that decoded but exceeded the `ItemsPerMessage`/`BytesPerMessage` this charge point
+    /// declares (see [`crate::message_limits`]).
+    OverLimit,
+}
+
+/// One invalid inbound message, as fed into the state machine through
+/// [`crate::state::ChargePointEvent::InvalidMessageReceived`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidMessage {
+    /// What was wrong with it.
+    pub kind: InvalidMessageKind,
+    /// The OCPP action it named, where one could be told - never for
+    /// [`InvalidMessageKind::Malformed`], whose envelope is what failed.
+    pub action: Option<String>,
+}
+
+impl InvalidMessage {
+    /// The `techInfo` a message gets when it is reported on its own.
+    fn describe(&self) -> String {
+        let action = self.action.as_deref().unwrap_or("a CALL");
+        match self.kind {
+            InvalidMessageKind::Malformed => {
+                "received a frame that is not a valid OCPP-J message".into()
+            }
+            InvalidMessageKind::Undecodable => {
+                alloc::format!("{action} carried a payload that does not match its schema")
+            }
+            InvalidMessageKind::OverLimit => alloc::format!(
+                "{action} exceeded the ItemsPerMessage/BytesPerMessage this charge point declares"
+            ),
+        }
+    }
+}
+
+/// How many invalid inbound messages this charge point has seen, and how many of them the
+/// security log has not yet been told about.
+///
+/// `InvalidMessages` is the security event a remote party can raise at will, so reporting each
+/// message individually would hand it a way to flush the bounded security log (A04.FR.04) with
+/// noise. Instead the first invalid message after a quiet interval is reported at once, and
+/// anything that follows it inside the interval is folded into one summary raised when the
+/// interval ends - see [`crate::invalid_messages::run_invalid_message_summaries`], which is what
+/// ends it. Nothing is lost to the rate limit: [`Self::total`] counts every message, and is
+/// published as `SecurityCtrlr.InvalidMessages`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvalidMessageTally {
+    /// Every invalid message since boot, reported or not.
+    pub total: usize,
+    /// Whether an interval is running, i.e. the most recent report was raised during the current
+    /// one. The next message is reported at once only when it is not.
+    interval_open: bool,
+    /// Counted since the last report, by kind.
+    malformed: usize,
+    undecodable: usize,
+    over_limit: usize,
+    /// The action the most recent unreported message named, if it named one.
+    last_action: Option<String>,
+}
+
+impl InvalidMessageTally {
+    /// Counts `message`, returning the event to raise for it - `Some` only when no interval is
+    /// running, otherwise it waits for the next [`Self::summarise`].
+    pub(crate) fn record(&mut self, message: InvalidMessage) -> Option<SecurityEvent> {
+        self.total = self.total.saturating_add(1);
+        if !self.interval_open {
+            self.interval_open = true;
+            return Some(SecurityEvent {
+                event_type: SecurityEventType::InvalidMessages,
+                tech_info: Some(message.describe()),
+            });
+        }
+        let count = match message.kind {
+            InvalidMessageKind::Malformed => &mut self.malformed,
+            InvalidMessageKind::Undecodable => &mut self.undecodable,
+            InvalidMessageKind::OverLimit => &mut self.over_limit,
+        };
+        *count = count.saturating_add(1);
+        if message.action.is_some() {
+            self.last_action = message.action;
+        }
+        None
+    }
+
+    /// Ends the current interval: returns one summary of everything counted during it, or
+    /// closes it if nothing was, so the next message is reported at once.
+    pub(crate) fn summarise(&mut self) -> Option<SecurityEvent> {
+        let unreported = self.malformed + self.undecodable + self.over_limit;
+        if unreported == 0 {
+            self.interval_open = false;
+            return None;
+        }
+        let mut tech_info = alloc::format!(
+            "{unreported} more invalid messages since the last report ({} malformed, {} not \
+             matching their schema, {} over a message limit)",
+            self.malformed,
+            self.undecodable,
+            self.over_limit
+        );
+        if let Some(action) = self.last_action.take() {
+            tech_info.push_str(&alloc::format!(", most recently {action}"));
+        }
+        tech_info.push_str(&alloc::format!("; {} since boot", self.total));
+        self.malformed = 0;
+        self.undecodable = 0;
+        self.over_limit = 0;
+        Some(SecurityEvent {
+            event_type: SecurityEventType::InvalidMessages,
+            tech_info: Some(tech_info),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn undecodable(action: &str) -> InvalidMessage {
+        InvalidMessage {
+            kind: InvalidMessageKind::Undecodable,
+            action: Some(action.into()),
+        }
+    }
+
+    #[test]
+    fn the_first_message_is_reported_at_once_and_the_rest_wait_for_the_summary() {
+        let mut tally = InvalidMessageTally::default();
+
+        let first = tally.record(undecodable("GetVariables")).unwrap();
+        assert_eq!(first.event_type, SecurityEventType::InvalidMessages);
+        assert_eq!(
+            first.tech_info.as_deref(),
+            Some("GetVariables carried a payload that does not match its schema")
+        );
+
+        for _ in 0..40 {
+            assert_eq!(tally.record(undecodable("SetVariables")), None);
+        }
+        assert_eq!(
+            tally.record(InvalidMessage {
+                kind: InvalidMessageKind::Malformed,
+                action: None,
+            }),
+            None
+        );
+        assert_eq!(tally.total, 42);
+
+        let summary = tally.summarise().unwrap();
+        assert_eq!(
+            summary.tech_info.as_deref(),
+            Some(
+                "41 more invalid messages since the last report (1 malformed, 40 not matching \
+                 their schema, 0 over a message limit), most recently SetVariables; 42 since boot"
+            ),
+            "a malformed frame names no action, so it must not erase the last one that did"
+        );
+    }
+
+    #[test]
+    fn a_quiet_interval_closes_the_window_so_the_next_message_is_reported_at_once() {
+        let mut tally = InvalidMessageTally::default();
+        assert!(tally.record(undecodable("Reset")).is_some());
+
+        // Nothing arrived during the interval the first report opened: nothing to summarise, and
+        // the next message starts a fresh interval rather than waiting a whole one.
+        assert_eq!(tally.summarise(), None);
+        assert!(tally.record(undecodable("Reset")).is_some());
+
+        // A summary still leaves the interval open - the flood may not be over.
+        assert_eq!(tally.record(undecodable("Reset")), None);
+        assert!(tally.summarise().is_some());
+        assert_eq!(tally.record(undecodable("Reset")), None);
+        assert_eq!(tally.total, 4);
+    }
+}
@@ -82,6 +82,18 @@ pub const DEFAULT_MAX_PERIODIC_EVENT_STREAMS: usize = 4;
 /// `SetDERControl` refused.
 pub const DEFAULT_MAX_DER_CONTROLS: usize = 16;
 
+/// Default maximum number of readings a connector keeps for its transaction's `Ended` event (see
+/// [`StateLimits::max_ended_meter_samples`]).
+///
+/// `0`: keeping the series is opt-in. Each reading is about 170 B, held until the transaction
+/// ends and then carried by its `Ended` event through the offline queue - so the bound prices
+/// both a connector's state and every queued `Ended` event, and a station that never sets
+/// `SampledDataCtrlr.TxEndedInterval` should not pay for it. Raise it via
+/// [`StateLimits::with_max_ended_meter_samples`] to report the series: 12 is three hours of
+/// readings at a quarter-hourly interval. While it is `0`, `TxEndedInterval` (and 1.6J's
+/// `StopTxnAlignedData`) stay read-only, so a CSMS cannot set an interval nothing keeps.
+pub const DEFAULT_MAX_ENDED_METER_SAMPLES: usize = 0;
+
 /// Default maximum number of display messages the store holds - see
 /// [`crate::state::DEFAULT_MAX_DISPLAY_MESSAGES`], which documents the reasoning; this re-export
 /// exists so every bound in [`StateLimits`] has a `DEFAULT_*` constant beside it.
@@ -185,6 +197,13 @@ pub struct StateLimits {
     /// it is refused with [`DERControlRejection::TooManyControls`](crate::state::DERControlRejection::TooManyControls);
     /// replacing an already-installed control's id always succeeds. Clamped to at least 1.
     pub max_der_controls: usize,
+    /// The most readings each connector keeps for its transaction's `Ended` event - the
+    /// `SampledDataCtrlr.TxEndedInterval` samples, and 1.6J's `StopTxnAlignedData` ones beside
+    /// them (see [`crate::state::EvseState::ended_meter_samples`]). A session long enough to fill
+    /// it keeps its earliest readings and stops adding more, so the reported series is a true
+    /// prefix of the session rather than one with holes in it; the final reading is reported
+    /// regardless. `0`, the default, keeps none.
+    pub max_ended_meter_samples: usize,
 }
 
 impl StateLimits {
@@ -203,6 +222,7 @@ impl StateLimits {
             max_periodic_event_streams: DEFAULT_MAX_PERIODIC_EVENT_STREAMS,
             max_pending_battery_swaps: DEFAULT_MAX_PENDING_BATTERY_SWAPS,
             max_der_controls: DEFAULT_MAX_DER_CONTROLS,
+            max_ended_meter_samples: DEFAULT_MAX_ENDED_METER_SAMPLES,
         }
     }
 
@@ -266,6 +286,12 @@ impl StateLimits {
         self
     }
 
+    /// Overrides [`Self::max_ended_meter_samples`].
+    pub const fn with_max_ended_meter_samples(mut self, max: usize) -> Self {
+        self.max_ended_meter_samples = max;
+        self
+    }
+
     /// Overrides [`Self::max_pending_battery_swaps`].
     pub const fn with_max_pending_battery_swaps(mut self, max: usize) -> Self {
         self.max_pending_battery_swaps = max;
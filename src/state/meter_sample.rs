@@ -2,7 +2,14 @@
 /// `ConnectorEvent::MeterValueSampled`. Only `energy_wh` is required - a hardware integration
 /// that can't measure a given quantity simply leaves it `None`, and the wire adapter (see
 /// `docs/ROADMAP.md` §10) omits it from the reported `meterValue` rather than fabricating one.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+///
+/// The fields after `soc_percent` were added later and are `#[serde(default)]`, so a sample
+/// persisted before they existed still loads - with nothing measured for them, which is true.
+/// They also use the narrowest integer that holds their range rather than `i64` throughout: a
+/// sample is copied into every `TransactionEvent` snapshot and kept per EVSE, so its size is paid
+/// many times over (`docs/MEMORY.md`). [`Self::signed`] is boxed for the same reason: most meters
+/// never produce one, and an empty `Option<Box<_>>` costs a pointer rather than four strings.
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub struct MeterSample {
     /// Cumulative active energy imported by the EV, in Wh (OCPP `Energy.Active.Import.Register`).
     pub energy_wh: i64,
@@ -17,4 +24,91 @@ pub struct MeterSample {
     /// that expose it (e.g. over ISO 15118), so hardware without that capability leaves it
     /// `None`.
     pub soc_percent: Option<u8>,
+    /// Cumulative active energy exported by the EV back to the grid, in Wh (OCPP
+    /// `Energy.Active.Export.Register`) - only bidirectional (V2G) hardware has a register for it.
+    #[serde(default)]
+    pub energy_export_wh: Option<i64>,
+    /// Active power currently exported by the EV, in W (OCPP `Power.Active.Export`).
+    #[serde(default)]
+    pub power_export_w: Option<i32>,
+    /// Reactive power currently imported, in var (OCPP `Power.Reactive.Import`).
+    #[serde(default)]
+    pub reactive_power_var: Option<i32>,
+    /// Power factor in thousandths, -1000 to 1000 (OCPP `Power.Factor`) - fixed-point, like
+    /// `current_ma`, so a sample stays `Eq` and survives a persisted round trip exactly.
+    #[serde(default)]
+    pub power_factor_milli: Option<i16>,
+    /// Grid frequency, in mHz (OCPP `Frequency`).
+    #[serde(default)]
+    pub frequency_millihertz: Option<i32>,
+    /// Temperature inside the charge point, in °C (OCPP `Temperature`, location `Body`). Only
+    /// 1.6J can report it: OCPP 2.x dropped `Temperature` from `MeasurandEnumType`, so the 2.x
+    /// adapters leave it out rather than sending it under another name.
+    #[serde(default)]
+    pub temperature_c: Option<i16>,
+    /// Current imported on each phase, L1 to L3, in mA (OCPP `Current.Import` with `phase`). Sits
+    /// alongside `current_ma` rather than replacing it: hardware reporting both has both sent, the
+    /// total without a phase and each phase with one.
+    #[serde(default)]
+    pub phase_current_ma: [Option<i32>; 3],
+    /// Line-to-neutral voltage on each phase, L1-N to L3-N, in V (OCPP `Voltage` with `phase`).
+    #[serde(default)]
+    pub phase_voltage_v: [Option<i16>; 3],
+    /// The meter's own signature over this reading, for calibration-law (Eichrecht) billing -
+    /// `None` for a meter that does not sign. Passed through untouched: this crate neither
+    /// produces nor verifies the signature, only carries it to the CSMS on the transaction's
+    /// start and end events (see [`SignedMeterValue`]).
+    #[serde(default)]
+    pub signed: Option<alloc::boxed::Box<SignedMeterValue>>,
+}
+
+/// A signed meter reading exactly as the meter produced it - OCPP 2.x's `SignedMeterValueType`,
+/// and what 1.6J sends as a `SampledValue` in the `SignedData` format.
+///
+/// Opaque to this crate. The signature covers `signed_meter_data`, which already states the
+/// reading it signs (an OCMF or EDL record carries its own register value, timestamp and meter
+/// id), so nothing here is checked against the [`MeterSample`] it is attached to: a CSMS or the
+/// driver's transparency software verifies it, not the charge point that relays it.
+///
+/// Reported on `TransactionEvent(Started)`/`(Ended)` (2.x) and in `StopTransaction`'s
+/// `transactionData` (1.6J) while `SampledDataCtrlr.SignReadings` is `true` - see
+/// [`crate::meter_values::signed_readings`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub struct SignedMeterValue {
+    /// The signed record, base64 encoded, in the format `encoding_method` names.
+    pub signed_meter_data: alloc::string::String,
+    /// The signature algorithm, e.g. `ECDSA-secp256r1-SHA256` (OCPP's `SigningMethodEnumStringType`).
+    /// Empty if `signed_meter_data` already states it, which OCPP 2.1 allows; 2.0.1 has no way to
+    /// leave it out and sends the empty string.
+    pub signing_method: alloc::string::String,
+    /// How the meter encoded the record before signing it, e.g. `OCMF` or `EDL`.
+    pub encoding_method: alloc::string::String,
+    /// The meter's public key, base64 encoded - sent alongside the reading as
+    /// `SampledDataCtrlr.PublicKeyWithSignedMeterValue` directs. Empty if the integration does not
+    /// know it, in which case it is never sent.
+    pub public_key: alloc::string::String,
+}
+
+/// A [`MeterSample`] together with the wall-clock instant it was taken at - one entry in
+/// [`crate::state::Transaction::ended_meter_samples`].
+///
+/// [`MeterSample`] itself carries no timestamp, because the state machine that records it is
+/// clock-free (see [`crate::clock`]). The readings an `Ended` event reports were each taken at
+/// their own instant during the session, so something has to remember when:
+/// [`crate::transactions::run_transaction_meter_sampling`] and
+/// [`crate::meter_values::run_aligned_meter_values`] own the clock, and state the instant on the
+/// event that asks for the reading to be kept.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TimestampedMeterSample {
+    /// When the reading was taken.
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// The reading.
+    pub sample: MeterSample,
+    /// Kept on an `AlignedDataCtrlr.Interval` boundary (1.6J's `StopTxnAlignedData`) rather
+    /// than a `SampledDataCtrlr.TxEndedInterval` one.
+    ///
+    /// `#[serde(default)]` so a reading persisted before this field existed recovers as the
+    /// sampled one it was.
+    #[serde(default)]
+    pub clock_aligned: bool,
 }
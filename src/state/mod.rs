@@ -1,5 +1,6 @@
 mod authorization_cache;
 mod authorization_status;
+mod availability;
 mod battery_swap;
 mod boot_reason;
 mod charge_point_state;
@@ -13,6 +14,7 @@ mod display_message;
 mod event;
 mod evse_state;
 mod id_token;
+mod invalid_message;
 mod limits;
 mod local_authorization_list;
 mod meter_sample;
@@ -31,12 +33,15 @@ pub use self::authorization_cache::{
     AuthorizationCache, AuthorizationCacheEntry, DEFAULT_MAX_AUTHORIZATION_CACHE_ENTRIES,
 };
 pub use self::authorization_status::AuthorizationStatus;
+pub use self::availability::AvailabilityTarget;
 pub use self::battery_swap::{
     BatteryData, BatterySwapEvent, BatterySwapEventKind, BatterySwapRequestId, BatterySwapStore,
     DEFAULT_MAX_PENDING_BATTERY_SWAPS, PendingBatterySwap,
 };
 pub use self::boot_reason::BootReasonCause;
-pub use self::charge_point_state::{ChargePointState, LifecycleState, TimeSyncAnchor};
+pub use self::charge_point_state::{
+    ChargePointState, LifecycleState, MAX_CONSUMED_RESERVATIONS, TimeSyncAnchor,
+};
 pub use self::charging_profile::{
     ChargingLimitSource, ChargingProfile, ChargingProfileCriteria, ChargingProfileId,
     ChargingProfileKind, ChargingProfilePurpose, ChargingProfileQuery, ChargingProfileRejection,
@@ -70,13 +75,15 @@ pub use self::event::{
 };
 pub use self::evse_state::{EvseState, EvseStatus, PendingRemoteStart};
 pub use self::id_token::{IdToken, IdTokenKind};
+pub use self::invalid_message::{InvalidMessage, InvalidMessageKind, InvalidMessageTally};
 pub use self::limits::{
     DEFAULT_MAX_CHARGING_PROFILES, DEFAULT_MAX_DER_CONTROLS, DEFAULT_MAX_DEVICE_MODEL_VARIABLES,
-    DEFAULT_MAX_LOCAL_AUTHORIZATION_LIST_ENTRIES, DEFAULT_MAX_PERIODIC_EVENT_STREAMS,
-    DEFAULT_MAX_TARIFFS, DEFAULT_MAX_VARIABLE_MONITORS, StateLimits,
+    DEFAULT_MAX_ENDED_METER_SAMPLES, DEFAULT_MAX_LOCAL_AUTHORIZATION_LIST_ENTRIES,
+    DEFAULT_MAX_PERIODIC_EVENT_STREAMS, DEFAULT_MAX_TARIFFS, DEFAULT_MAX_VARIABLE_MONITORS,
+    StateLimits,
 };
 pub use self::local_authorization_list::{LocalAuthorizationList, LocalListEntry};
-pub use self::meter_sample::MeterSample;
+pub use self::meter_sample::{MeterSample, SignedMeterValue, TimestampedMeterSample};
 pub use self::network_profile::{
     DEFAULT_MAX_NETWORK_PROFILE_SLOTS, NetworkConnectionProfile, NetworkInterface,
     NetworkProfileSlot, NetworkProfileStore, NetworkTransport,
@@ -50,9 +50,9 @@ pub enum SecurityEventType {
     /// Someone logged in to the local maintenance interface successfully.
     ///
     /// OCPP strongly recommends `techInfo` carry the user and the origin of the attempt as
-    /// `{'user': '...', 'origin': '...'}`. This crate does not construct that string, because it
-    /// has no maintenance interface - the integrator raising the event is the only party that
-    /// knows who logged in from where.
+    /// `{'user': '...', 'origin': '...'}`, which is what [`crate::maintenance::MaintenanceSessions`]
+    /// raises. An integrator with a maintenance interface of their own can raise it directly with
+    /// the same shape.
     MaintenanceLoginAccepted,
     /// Someone failed to log in to the local maintenance interface. Same `techInfo` recommendation
     /// as [`Self::MaintenanceLoginAccepted`].
@@ -122,9 +122,11 @@ impl SecurityEventType {
 /// Raised through [`crate::security::report_security_event`] - by this crate where it can detect
 /// the event itself (a clock step, a reboot, a queue overflow, a security-parameter change), and
 /// by the integrator for everything only the hardware knows about (a tamper switch, a maintenance
-/// login). The certificate, firmware and TLS types are declared but not yet raised anywhere,
-/// because the blocks that would detect them do not exist - see `docs/PRODUCTION-ROADMAP.md`
-/// B3/B4/F2.
+/// login). A CSMS redial that fails in TLS raises the four TLS types (see `crate::trust_store`),
+/// and invalid inbound CSMS messages raise [`SecurityEventType::InvalidMessages`], rate-limited
+/// (see [`crate::invalid_messages`]);
+/// the firmware types are declared but not yet raised anywhere, because the block that would
+/// detect them does not exist - see `docs/PRODUCTION-ROADMAP.md` B3.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SecurityEvent {
     /// Which kind of security event occurred.
@@ -213,6 +213,11 @@ pub struct Transaction {
     pub seq_no: u32,
     /// The most recent meter reading reported while this transaction was `Charging`, if any -
     /// see the Meter values functional block (`docs/ROADMAP.md` §10).
+    ///
+    /// On the transaction an `Ended` event carries, the connector's reading at the stop point
+    /// instead - what 2.x reports as `Transaction.End` and 1.6J as `meterStop`. The meter keeps
+    /// counting while a session is suspended, and a register billed against the last `Charging`
+    /// reading would miss it.
     pub last_meter_sample: Option<crate::state::MeterSample>,
     /// Whether priority charging has been granted for this transaction - OCPP 2.1's
     /// `UsePriorityCharging` (`docs/PRODUCTION-ROADMAP.md` B2.6).
@@ -312,4 +317,37 @@ pub struct Transaction {
     /// re-persist every record for nothing.
     #[serde(default)]
     pub elapsed_secs: Option<i64>,
+    /// The readings sampled every `SampledDataCtrlr.TxEndedInterval` seconds while this
+    /// transaction ran, oldest first - what its `Ended` event reports ahead of the final reading
+    /// (J02, OCPP's "sampled data" on `TransactionEvent(Ended)`). Interleaved with the readings
+    /// kept on each `AlignedDataCtrlr.Interval` boundary for 1.6J's `StopTxnAlignedData`, each
+    /// marked [`clock_aligned`](crate::state::TimestampedMeterSample::clock_aligned) - only kept
+    /// when a 1.6J aligned loop is running (see
+    /// [`MeterValuesNotifier::keeps_aligned_stop_data`](crate::meter_values::MeterValuesNotifier::keeps_aligned_stop_data)),
+    /// and skipped by the 2.x adapters regardless.
+    ///
+    /// **Only ever filled on the `Ended` event's snapshot.** While the transaction runs, the series
+    /// is held per connector in [`crate::state::EvseState::ended_meter_samples`] and moved here
+    /// when it ends, so the running transaction, every `Started`/`Updated` event, every queued
+    /// copy of one and every persisted record carry an empty `Vec` rather than the whole series.
+    /// The flip side is that a transaction recovered after a power loss ends with no series: the
+    /// readings kept before the reboot went with the state that held them.
+    ///
+    /// `#[serde(default)]` so an `Ended` event persisted before this field existed recovers with
+    /// no series, which is what it had.
+    #[serde(default)]
+    pub ended_meter_samples: alloc::vec::Vec<crate::state::TimestampedMeterSample>,
+    /// The connector's latest reading at the moment this transaction started - what its
+    /// `Started` event and 1.6J's `meterStart` report, and the signed begin reading calibration
+    /// law bills against (see [`crate::state::SignedMeterValue`]).
+    ///
+    /// Taken from [`crate::state::EvseState::latest_meter_samples`] rather than waiting for the
+    /// next sample, because the reading that matters is the one *before* energy flowed: a sample
+    /// taken after the contactor closes already includes some of the session. `None` if the
+    /// hardware had not reported one yet, in which case nothing is invented in its place.
+    ///
+    /// `#[serde(default)]` so a transaction persisted before this field existed recovers without
+    /// one, which is all it can honestly claim.
+    #[serde(default)]
+    pub start_meter_sample: Option<crate::state::MeterSample>,
 }
@@ -71,6 +71,9 @@ pub async fn handle_set_default_tariff(
     if !crate::refusal::capability_present(&state.capabilities, "SetDefaultTariff") {
         return SetDefaultTariffOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "SetDefaultTariff") {
+        return SetDefaultTariffOutcome::Rejected;
+    }
     if let TariffScope::Evse(evse_id) = scope
         && evse_id >= state.evses.len()
     {
@@ -226,6 +229,9 @@ pub async fn handle_change_transaction_tariff(
     if !crate::refusal::capability_present(&state.capabilities, "ChangeTransactionTariff") {
         return ChangeTransactionTariffOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "ChangeTransactionTariff") {
+        return ChangeTransactionTariffOutcome::Rejected;
+    }
     let Some((evse_id, connector_id)) = find_transaction(&state, transaction_id) else {
         return ChangeTransactionTariffOutcome::TxNotFound;
     };
@@ -400,6 +406,9 @@ pub fn handle_get_tariffs(actor: &ChargePointActor, evse_id: Option<usize>) -> G
     if !crate::refusal::capability_present(&state.capabilities, "GetTariffs") {
         return GetTariffsOutcome::Rejected;
     }
+    if crate::command_policy::refuses(actor, "GetTariffs") {
+        return GetTariffsOutcome::Rejected;
+    }
     let mut reports: Vec<TariffReport> = state
         .tariffs
         .selected_by_evse(evse_id)
@@ -533,7 +542,7 @@ fn pricing_context(
     transaction: &Transaction,
     now: DateTime<Utc>,
 ) -> crate::pricing::PricingContext {
-    let sample = transaction.last_meter_sample;
+    let sample = transaction.last_meter_sample.as_ref();
     crate::pricing::PricingContext {
         // `MeterSample` is in Wh/W/mA; `PricingContext` wants mWh/mW/mA - see
         // `crate::state::Money::milli_from_decimal`'s docs for the same thousandths convention.
@@ -1477,6 +1486,8 @@ pub mod ocpp_2_1 {
         if !crate::refusal::capability_present(&actor.state().capabilities, "ClearTariffs") {
             return Err(crate::refusal::ocpp_2_1_not_supported("ClearTariffs"));
         }
+        crate::command_policy::screen_action(actor, "ClearTariffs")
+            .map_err(|refused| crate::refusal::ocpp_2_1_refused_by_policy(&refused))?;
         let scope = request.evse_id.and_then(parse_scope);
         let ids: Option<Vec<TariffId>> = request
             .tariff_ids
@@ -56,6 +56,9 @@ impl GetTransactionStatusHandler for Ocpp2_0_1GetTransactionStatusHandler {
                 let actor = actor.clone();
                 let queue = queue.clone();
                 async move {
+                    crate::command_policy::screen_action(&actor, "GetTransactionStatus").map_err(
+                        |refused| crate::refusal::ocpp_2_0_1_refused_by_policy(&refused),
+                    )?;
                     let transaction_id = request
                         .transaction_id
                         .as_deref()
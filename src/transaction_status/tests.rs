@@ -65,6 +65,8 @@ fn queued_event(id: TransactionId) -> TransactionEventOccurred {
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         },
         offline: false,
     }
@@ -391,6 +391,174 @@ pub async fn run_transaction_time_limits<B, M>(
     }
 }
 
+/// How long [`run_transaction_meter_sampling`] sleeps before re-reading the intervals while both
+/// are off - the same trade [`crate::meter_values`] makes for clock-aligned data: a `state()` read
+/// a minute costs nothing, and a CSMS that sets an interval mid-session sees it take effect within
+/// that minute.
+const SAMPLING_DISABLED_POLL_SECS: u32 = 60;
+
+/// The two `SampledDataCtrlr` intervals [`run_transaction_meter_sampling`] drives.
+const SAMPLED_DATA_INTERVALS: [&str; 2] = ["TxUpdatedInterval", "TxEndedInterval"];
+
+/// Reads one `SampledDataCtrlr` interval, in whole seconds - `None` for absent, unparseable or
+/// `0`, which OCPP defines as "no sampled data" and which would otherwise busy-spin the loop.
+fn sampled_data_interval_secs(actor: &crate::actor::ChargePointActor, name: &str) -> Option<u32> {
+    let value = actor
+        .state()
+        .device_model
+        .get(
+            &crate::state::Component {
+                name: "SampledDataCtrlr".into(),
+                instance: None,
+                evse: None,
+            },
+            &crate::state::Variable {
+                name: name.into(),
+                instance: None,
+            },
+        )?
+        .attribute(crate::state::VariableAttributeType::Actual)?
+        .value
+        .parse::<u32>()
+        .ok()?;
+    (value != 0).then_some(value)
+}
+
+/// Makes `SampledDataCtrlr.TxUpdatedInterval` and `TxEndedInterval` writable, declaring that this
+/// station now acts on them (J02).
+///
+/// Both are registered read-only until something drives them (CV2.1, `DefaultVariable::honoured`):
+/// a CSMS that set `TxUpdatedInterval` on a station with no sampling loop would silence every
+/// periodic reading rather than pace it. `TxEndedInterval` stays read-only while
+/// [`StateLimits::max_ended_meter_samples`](crate::state::StateLimits::max_ended_meter_samples) is
+/// `0`, its default: there is nowhere to keep its readings. Called by
+/// [`ChargePointBuilder::transaction_meter_sampling`](crate::ChargePointBuilder::transaction_meter_sampling)
+/// as it spawns [`run_transaction_meter_sampling`], for the reason
+/// [`advertise_time_limit_support`] is called beside its sweep: the claim and the thing that
+/// makes it true arrive together.
+///
+/// Re-registers each variable with its characteristics and current value unchanged, so a value
+/// already restored from persistence survives. Idempotent.
+pub async fn advertise_meter_sampling_support(actor: &crate::actor::ChargePointActor) {
+    let keeps_ended_samples = actor.state().max_ended_meter_samples > 0;
+    for name in SAMPLED_DATA_INTERVALS {
+        if name == "TxEndedInterval" && !keeps_ended_samples {
+            continue;
+        }
+        crate::device_model::make_actual_writable(
+            actor,
+            crate::state::Component {
+                name: "SampledDataCtrlr".into(),
+                instance: None,
+                evse: None,
+            },
+            crate::state::Variable {
+                name: name.into(),
+                instance: None,
+            },
+        )
+        .await;
+    }
+}
+
+/// Paces transaction meter data to `SampledDataCtrlr.TxUpdatedInterval`, and samples it every
+/// `SampledDataCtrlr.TxEndedInterval` for the `Ended` event - **J02**, forever.
+///
+/// This crate never polls a meter: the binding pushes readings in via
+/// [`ConnectorEvent::MeterValueSampled`](crate::state::ConnectorEvent::MeterValueSampled), at
+/// whatever rate its hardware produces them. While `TxUpdatedInterval` is set the state machine
+/// only records each one, and this loop raises
+/// [`ConnectorEvent::TransactionMeterValuesDue`](crate::state::ConnectorEvent::TransactionMeterValuesDue)
+/// once per interval to report the latest. That keeps the throttling inside the state machine
+/// rather than in the outbound queue, which matters: dropping events on the way out would leave
+/// holes in `seqNo`, and a CSMS reads a hole as a lost message.
+///
+/// `TxEndedInterval` works the same way, except that each reading is kept rather than sent:
+/// [`ConnectorEvent::TransactionEndedSampleDue`](crate::state::ConnectorEvent::TransactionEndedSampleDue)
+/// carries `clock`'s reading, and the series reaches the CSMS on the transaction's `Ended` event,
+/// filtered by `TxEndedMeasurands` like the final reading beside it.
+///
+/// Both intervals are read fresh on every cycle, as [`crate::meter_values`] reads the aligned one,
+/// so a `SetVariables` (or 1.6J `ChangeConfiguration` of `MeterValueSampleInterval`) takes effect
+/// without a restart; a shortened interval takes effect at once, a lengthened one after the
+/// current period. The countdown is per charge point, not per transaction, so a session's first
+/// report lands anywhere up to one interval after it starts - the `Started` event already carries
+/// the opening reading.
+pub async fn run_transaction_meter_sampling<B, C>(
+    actor: &crate::actor::ChargePointActor,
+    backoff: &B,
+    clock: &C,
+) where
+    B: crate::provisioning::Backoff,
+    C: crate::clock::Clock,
+{
+    // Seconds left until each interval is next due; `None` while that interval is off.
+    let mut updated_due_in: Option<u32> = None;
+    let mut ended_due_in: Option<u32> = None;
+    loop {
+        let [updated_interval, ended_interval] =
+            SAMPLED_DATA_INTERVALS.map(|name| sampled_data_interval_secs(actor, name));
+        updated_due_in = updated_interval.map(|secs| updated_due_in.unwrap_or(secs).min(secs));
+        ended_due_in = ended_interval.map(|secs| ended_due_in.unwrap_or(secs).min(secs));
+
+        let wait = match (updated_due_in, ended_due_in) {
+            (None, None) => {
+                backoff.wait(SAMPLING_DISABLED_POLL_SECS).await;
+                continue;
+            }
+            (Some(updated), Some(ended)) => updated.min(ended),
+            (Some(due_in), None) | (None, Some(due_in)) => due_in,
+        };
+        backoff.wait(wait).await;
+        updated_due_in = updated_due_in.map(|due_in| due_in.saturating_sub(wait));
+        ended_due_in = ended_due_in.map(|due_in| due_in.saturating_sub(wait));
+
+        let report_updated = updated_due_in == Some(0);
+        let sample_ended = ended_due_in == Some(0);
+        // Due ones restart from the full interval on the next read above.
+        if report_updated {
+            updated_due_in = None;
+        }
+        if sample_ended {
+            ended_due_in = None;
+        }
+        let now = sample_ended.then(|| clock.now());
+
+        // Every connector with a transaction running. Which of them actually has something to
+        // report or keep is the state machine's call - see the two events' docs.
+        let running: alloc::vec::Vec<(usize, usize)> = actor
+            .state()
+            .evses
+            .iter()
+            .enumerate()
+            .flat_map(|(evse_id, evse)| {
+                evse.transactions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, transaction)| transaction.is_some())
+                    .map(move |(connector_id, _)| (evse_id, connector_id))
+            })
+            .collect();
+        for (evse_id, connector_id) in running {
+            let due = report_updated
+                .then_some(crate::state::ConnectorEvent::TransactionMeterValuesDue)
+                .into_iter()
+                .chain(now.map(crate::state::ConnectorEvent::TransactionEndedSampleDue));
+            for event in due {
+                let _ = actor
+                    .send(crate::state::ChargePointEvent::Evse {
+                        evse_id,
+                        event: crate::state::EvseEvent::Connector {
+                            connector_id,
+                            event,
+                        },
+                    })
+                    .await;
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{TransactionEventOutcome, TransactionNotifier, run_transaction_events};
@@ -455,6 +623,8 @@ mod tests {
             limit_reached: None,
             energy_start_wh: None,
             elapsed_secs: None,
+            ended_meter_samples: Vec::new(),
+            start_meter_sample: None,
         };
         sender.send(TransactionEventOccurred {
             evse_id: 0,
@@ -518,9 +688,19 @@ mod tests {
 
     /// Drives connector 0 of a fresh actor to `Charging` with a transaction running.
     async fn charging_actor() -> crate::actor::ChargePointActor {
+        charging_actor_with_limits(crate::state::StateLimits::default()).await
+    }
+
+    async fn charging_actor_with_limits(
+        limits: crate::state::StateLimits,
+    ) -> crate::actor::ChargePointActor {
         use crate::state::{ChargePointEvent, ConnectorEvent, EvseEvent, IdToken, IdTokenKind};
 
-        let actor = crate::actor::ChargePointActor::spawn([1], &crate::executor::TokioExecutor);
+        let actor = crate::actor::ChargePointActor::spawn_with_limits(
+            [1],
+            &crate::executor::TokioExecutor,
+            limits,
+        );
         let id_token = IdToken {
             value: "04A224B2".into(),
             kind: IdTokenKind::ISO14443,
@@ -696,6 +876,191 @@ mod tests {
         );
     }
 
+    /// A wall clock stopped at one instant, for the readings an `Ended` event keeps.
+    struct FixedClock(chrono::DateTime<chrono::Utc>);
+
+    impl crate::clock::Clock for FixedClock {
+        fn now(&self) -> chrono::DateTime<chrono::Utc> {
+            self.0
+        }
+    }
+
+    async fn set_sampled_data_interval(
+        actor: &crate::actor::ChargePointActor,
+        variable: &str,
+        secs: &str,
+    ) {
+        let _ = actor
+            .send(crate::state::ChargePointEvent::DeviceModel(
+                crate::state::DeviceModelEvent::AttributeValueSet {
+                    component: crate::state::Component {
+                        name: "SampledDataCtrlr".into(),
+                        instance: None,
+                        evse: None,
+                    },
+                    variable: crate::state::Variable {
+                        name: variable.into(),
+                        instance: None,
+                    },
+                    attribute_type: crate::state::VariableAttributeType::Actual,
+                    value: secs.into(),
+                },
+            ))
+            .await;
+    }
+
+    async fn push_sample(actor: &crate::actor::ChargePointActor, energy_wh: i64) {
+        use crate::state::{ChargePointEvent, ConnectorEvent, EvseEvent, MeterSample};
+
+        let _ = actor
+            .send(ChargePointEvent::Evse {
+                evse_id: 0,
+                event: EvseEvent::Connector {
+                    connector_id: 0,
+                    event: ConnectorEvent::MeterValueSampled(MeterSample {
+                        energy_wh,
+                        ..Default::default()
+                    }),
+                },
+            })
+            .await;
+    }
+
+    /// J02 end to end: once the loop has made the interval writable and a CSMS has set it, a 1 Hz
+    /// meter's readings stop going out one by one and the loop reports the latest instead.
+    #[tokio::test]
+    async fn the_sampling_loop_paces_readings_to_tx_updated_interval() {
+        use crate::state::{TransactionUpdateReason, VariableAttributeType, VariableMutability};
+
+        let actor = alloc::sync::Arc::new(charging_actor().await);
+        super::advertise_meter_sampling_support(&actor).await;
+        let mutability = actor
+            .state()
+            .device_model
+            .get(
+                &crate::state::Component {
+                    name: "SampledDataCtrlr".into(),
+                    instance: None,
+                    evse: None,
+                },
+                &crate::state::Variable {
+                    name: "TxUpdatedInterval".into(),
+                    instance: None,
+                },
+            )
+            .and_then(|definition| definition.attribute(VariableAttributeType::Actual))
+            .map(|attribute| attribute.mutability);
+        assert_eq!(mutability, Some(VariableMutability::ReadWrite));
+        set_sampled_data_interval(&actor, "TxUpdatedInterval", "30").await;
+        let seq_no = |actor: &crate::actor::ChargePointActor| {
+            actor.state().evses[0].transactions[0]
+                .as_ref()
+                .map(|transaction| transaction.seq_no)
+        };
+        let before = seq_no(&actor);
+
+        for energy_wh in [1_000, 1_001, 1_002] {
+            push_sample(&actor, energy_wh).await;
+        }
+        assert_eq!(seq_no(&actor), before, "nothing reported between intervals");
+
+        let mut events = actor.subscribe_transaction_events();
+        let task_actor = actor.clone();
+        let sampling = tokio::spawn(async move {
+            let clock = FixedClock(chrono::Utc::now());
+            super::run_transaction_meter_sampling(&task_actor, &ImmediateBackoff, &clock).await;
+        });
+        let reported = events.recv().await.expect("the loop reports the reading");
+        sampling.abort();
+
+        assert_eq!(
+            reported.kind,
+            TransactionEventKind::Updated(TransactionUpdateReason::MeterValuePeriodic)
+        );
+        assert_eq!(reported.transaction.seq_no, before.unwrap() + 1);
+        assert_eq!(
+            reported
+                .transaction
+                .last_meter_sample
+                .map(|sample| sample.energy_wh),
+            Some(1_002)
+        );
+    }
+
+    /// `TxEndedInterval` is only writable where its readings can be kept: with
+    /// `max_ended_meter_samples` at its default of `0`, accepting it would change nothing.
+    #[tokio::test]
+    async fn tx_ended_interval_stays_read_only_without_room_for_its_readings() {
+        use crate::state::{VariableAttributeType, VariableMutability};
+
+        let mutability = |actor: &crate::actor::ChargePointActor| {
+            actor
+                .state()
+                .device_model
+                .get(
+                    &crate::state::Component {
+                        name: "SampledDataCtrlr".into(),
+                        instance: None,
+                        evse: None,
+                    },
+                    &crate::state::Variable {
+                        name: "TxEndedInterval".into(),
+                        instance: None,
+                    },
+                )
+                .and_then(|definition| definition.attribute(VariableAttributeType::Actual))
+                .map(|attribute| attribute.mutability)
+        };
+
+        let actor = charging_actor().await;
+        super::advertise_meter_sampling_support(&actor).await;
+        assert_eq!(mutability(&actor), Some(VariableMutability::ReadOnly));
+
+        let actor = charging_actor_with_limits(
+            crate::state::StateLimits::default().with_max_ended_meter_samples(12),
+        )
+        .await;
+        super::advertise_meter_sampling_support(&actor).await;
+        assert_eq!(mutability(&actor), Some(VariableMutability::ReadWrite));
+    }
+
+    /// J02: `TxEndedInterval` readings are kept on the transaction, stamped with the loop's clock,
+    /// for the `Ended` event to carry.
+    #[tokio::test]
+    async fn the_sampling_loop_keeps_readings_for_the_ended_event() {
+        let actor = alloc::sync::Arc::new(
+            charging_actor_with_limits(
+                crate::state::StateLimits::default().with_max_ended_meter_samples(12),
+            )
+            .await,
+        );
+        super::advertise_meter_sampling_support(&actor).await;
+        set_sampled_data_interval(&actor, "TxEndedInterval", "60").await;
+        push_sample(&actor, 2_000).await;
+        let at = "2026-10-17T10:00:00Z"
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap();
+
+        let task_actor = actor.clone();
+        let sampling = tokio::spawn(async move {
+            super::run_transaction_meter_sampling(&task_actor, &ImmediateBackoff, &FixedClock(at))
+                .await;
+        });
+        for _ in 0..50 {
+            tokio::task::yield_now().await;
+        }
+        sampling.abort();
+
+        let state = actor.state();
+        assert!(state.evses[0].transactions[0].is_some(), "still charging");
+        let kept = &state.evses[0].ended_meter_samples[0];
+        assert!(!kept.is_empty());
+        assert!(
+            kept.iter()
+                .all(|sampled| sampled.timestamp == at && sampled.sample.energy_wh == 2_000)
+        );
+    }
+
     /// **E05 (CV2.5), the producer this crate was missing.** A CSMS blocklist update arrives as a
     /// rejected `idTokenInfo` on the response to an ordinary transaction event, and the charge
     /// point has to act on it rather than log it.
@@ -823,14 +1188,16 @@ mod tests {
 pub(crate) mod ocpp_2_1 {
     pub use with_clock::Ocpp2_1TransactionNotifier;
 
-    use crate::meter_values::MeasurandSet;
+    use crate::meter_values::{
+        MeasurandSet, Reading, ReadingContext, ReadingKind, ReadingPhase, readings,
+    };
     use crate::state::{
         MeterSample, StopReason, Transaction, TransactionChargingState, TransactionEventKind,
         TransactionLimitKind, TransactionUpdateReason,
     };
     use crate::wire::v21::common::{
-        ChargingStateEnum, MeasurandEnum, MeterValue, ReadingContextEnum, ReasonEnum, SampledValue,
-        TransactionEventEnum, TriggerReasonEnum,
+        ChargingStateEnum, MeasurandEnum, MeterValue, PhaseEnum, ReadingContextEnum, ReasonEnum,
+        SampledValue, SignedMeterValue, TransactionEventEnum, TriggerReasonEnum, UnitOfMeasure,
     };
     use alloc::vec;
     use alloc::vec::Vec;
@@ -880,6 +1247,13 @@ pub(crate) mod ocpp_2_1 {
             TransactionEventKind::Started if transaction.remote_start_id.is_some() => {
                 TriggerReasonEnum::RemoteStart
             }
+            // CV2.2: a transaction the bay sensor began, before any cable or identifier, was
+            // triggered by the vehicle arriving - and only a bay start begins `Idle`.
+            TransactionEventKind::Started
+                if transaction.charging_state == TransactionChargingState::Idle =>
+            {
+                TriggerReasonEnum::EVDetected
+            }
             TransactionEventKind::Started => TriggerReasonEnum::Authorized,
             TransactionEventKind::Updated(TransactionUpdateReason::ChargingStateChanged) => {
                 TriggerReasonEnum::ChargingStateChanged
@@ -895,6 +1269,30 @@ pub(crate) mod ocpp_2_1 {
             TransactionEventKind::Updated(TransactionUpdateReason::LimitSet) => {
                 TriggerReasonEnum::LimitSet
             }
+            // I12: a `ChangeTransactionTariff` took effect.
+            TransactionEventKind::Updated(TransactionUpdateReason::TariffChanged) => {
+                TriggerReasonEnum::TariffChanged
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::EvCommunicationLost) => {
+                TriggerReasonEnum::EVCommunicationLost
+            }
+            // E11: a signed reading, sent as it arrived.
+            TransactionEventKind::Updated(TransactionUpdateReason::SignedDataReceived) => {
+                TriggerReasonEnum::SignedDataReceived
+            }
+            // F06: the CSMS asked.
+            TransactionEventKind::Updated(TransactionUpdateReason::Trigger) => {
+                TriggerReasonEnum::Trigger
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::EvDetected) => {
+                TriggerReasonEnum::EVDetected
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::EvDeparted) => {
+                TriggerReasonEnum::EVDeparted
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::CablePluggedIn) => {
+                TriggerReasonEnum::CablePluggedIn
+            }
             // E16.FR.05: *which* ceiling was reached, which is the whole reason the four values
             // exist - a bare `SuspendedEVSE` cannot be told from a smart-charging suspension.
             TransactionEventKind::Updated(TransactionUpdateReason::LimitReached(kind)) => {
@@ -922,59 +1320,93 @@ pub(crate) mod ocpp_2_1 {
         }
     }
 
-    /// Builds one `sampledValue` per measurand that `sample` carries **and** `measurands` selects -
-    /// the energy register plus power/current/voltage/SoC where the hardware reported them (J01,
-    /// J02, CV2.6).
-    ///
-    /// The two conditions are separate on purpose. What the hardware reported is a fact about this
-    /// station; what `measurands` selects is what the CSMS asked to be told about. A measurand the
-    /// CSMS did not ask for is dropped even when the meter produced it, and one it did ask for is
-    /// still absent when the meter did not - inventing a reading to satisfy a configured list would
-    /// be worse than omitting it.
+    /// Builds one `sampledValue` per reading [`crate::meter_values::readings`] selects from
+    /// `sample` - see there for why what the meter reported and what the CSMS selected are separate
+    /// conditions (J01, J02, CV2.6).
     ///
     /// Shared with [`crate::meter_values`]' standalone `MeterValues` adapter through
     /// `build_meter_values`: a reading is a reading, and two mappings of the same measurands into
     /// the same wire type would be two places for a unit bug to hide.
-    fn sampled_values(sample: MeterSample, measurands: MeasurandSet) -> Vec<SampledValue> {
-        let mut values = Vec::new();
-        if measurands.energy {
-            values.push(sampled_value(
-                sample.energy_wh as f64,
-                MeasurandEnum::EnergyActiveImportRegister,
-            ));
-        }
-        if let Some(power_w) = sample.power_w.filter(|_| measurands.power) {
-            values.push(sampled_value(
-                power_w as f64,
-                MeasurandEnum::PowerActiveImport,
-            ));
-        }
-        if let Some(current_ma) = sample.current_ma.filter(|_| measurands.current) {
-            values.push(sampled_value(
-                current_ma as f64 / 1_000.0,
-                MeasurandEnum::CurrentImport,
-            ));
-        }
-        if let Some(voltage_v) = sample.voltage_v.filter(|_| measurands.voltage) {
-            values.push(sampled_value(voltage_v as f64, MeasurandEnum::Voltage));
-        }
-        if let Some(soc_percent) = sample.soc_percent.filter(|_| measurands.soc) {
-            values.push(sampled_value(soc_percent as f64, MeasurandEnum::SoC));
-        }
-        values
+    ///
+    /// `signature` goes on the energy register reading, the quantity an OCMF or EDL record signs;
+    /// with that measurand deselected there is nothing for it to accompany, and it is left out.
+    fn sampled_values(
+        sample: &MeterSample,
+        measurands: MeasurandSet,
+        context: ReadingContext,
+        signature: Option<(&crate::state::SignedMeterValue, bool)>,
+    ) -> Vec<SampledValue> {
+        readings(sample, measurands)
+            .into_iter()
+            .filter_map(|reading| {
+                let mut value = sampled_value(reading, context)?;
+                if reading.kind == ReadingKind::EnergyImport && reading.phase.is_none() {
+                    value.signed_meter_value = signature.and_then(signed_meter_value);
+                }
+                Some(value)
+            })
+            .collect()
+    }
+
+    /// The wire form of a meter's signature - `None` if `encodingMethod` or `signingMethod` is
+    /// longer than the 50 characters OCPP allows, since a truncated method name would make the
+    /// signature unverifiable anyway. An empty `signingMethod` is omitted, as 2.1 permits.
+    fn signed_meter_value(
+        (signed, with_public_key): (&crate::state::SignedMeterValue, bool),
+    ) -> Option<SignedMeterValue> {
+        Some(SignedMeterValue {
+            custom_data: None,
+            encoding_method: heapless::String::try_from(signed.encoding_method.as_str()).ok()?,
+            public_key: with_public_key.then(|| signed.public_key.clone()),
+            signed_meter_data: signed.signed_meter_data.clone(),
+            signing_method: match signed.signing_method.as_str() {
+                "" => None,
+                method => Some(heapless::String::try_from(method).ok()?),
+            },
+        })
     }
 
-    fn sampled_value(value: f64, measurand: MeasurandEnum) -> SampledValue {
-        SampledValue {
-            value,
+    /// `None` for `Temperature`, which 2.x's `MeasurandEnumType` no longer has.
+    fn sampled_value(reading: Reading, context: ReadingContext) -> Option<SampledValue> {
+        let measurand = match reading.kind {
+            ReadingKind::EnergyImport => MeasurandEnum::EnergyActiveImportRegister,
+            ReadingKind::PowerImport => MeasurandEnum::PowerActiveImport,
+            ReadingKind::CurrentImport => MeasurandEnum::CurrentImport,
+            ReadingKind::Voltage => MeasurandEnum::Voltage,
+            ReadingKind::Soc => MeasurandEnum::SoC,
+            ReadingKind::EnergyExport => MeasurandEnum::EnergyActiveExportRegister,
+            ReadingKind::PowerExport => MeasurandEnum::PowerActiveExport,
+            ReadingKind::ReactivePowerImport => MeasurandEnum::PowerReactiveImport,
+            ReadingKind::PowerFactor => MeasurandEnum::PowerFactor,
+            ReadingKind::Frequency => MeasurandEnum::Frequency,
+            ReadingKind::Temperature => return None,
+        };
+        Some(SampledValue {
+            value: reading.value,
             measurand: Some(measurand),
-            context: Some(ReadingContextEnum::SamplePeriodic),
-            phase: None,
+            context: Some(match context {
+                ReadingContext::SamplePeriodic => ReadingContextEnum::SamplePeriodic,
+                ReadingContext::Trigger => ReadingContextEnum::Trigger,
+                ReadingContext::TransactionBegin => ReadingContextEnum::TransactionBegin,
+                ReadingContext::TransactionEnd => ReadingContextEnum::TransactionEnd,
+            }),
+            phase: reading.phase.map(|phase| match phase {
+                ReadingPhase::L1 => PhaseEnum::L1,
+                ReadingPhase::L2 => PhaseEnum::L2,
+                ReadingPhase::L3 => PhaseEnum::L3,
+                ReadingPhase::L1N => PhaseEnum::L1N,
+                ReadingPhase::L2N => PhaseEnum::L2N,
+                ReadingPhase::L3N => PhaseEnum::L3N,
+            }),
             location: None,
             signed_meter_value: None,
-            unit_of_measure: None,
+            unit_of_measure: reading.kind.unit().map(|unit| UnitOfMeasure {
+                unit: heapless::String::try_from(unit).ok(),
+                multiplier: None,
+                custom_data: None,
+            }),
             custom_data: None,
-        }
+        })
     }
 
     /// Builds the TransactionEvent `meterValue` list from a transaction's most recent sample -
@@ -987,14 +1419,32 @@ pub(crate) mod ocpp_2_1 {
     /// Staying a pure function of its arguments is what lets the caller read the configuration
     /// fresh per message without this function needing to know a device model exists.
     pub(crate) fn build_meter_values(
-        sample: Option<MeterSample>,
+        sample: Option<&MeterSample>,
         timestamp: DateTime<Utc>,
         measurands: MeasurandSet,
+    ) -> Vec<MeterValue> {
+        build_meter_values_with(
+            sample,
+            timestamp,
+            measurands,
+            ReadingContext::SamplePeriodic,
+            None,
+        )
+    }
+
+    /// [`build_meter_values`] for a reading taken for a particular reason, optionally carrying
+    /// the meter's signature - the begin and end readings of a transaction (E11.FR.08).
+    pub(super) fn build_meter_values_with(
+        sample: Option<&MeterSample>,
+        timestamp: DateTime<Utc>,
+        measurands: MeasurandSet,
+        context: ReadingContext,
+        signature: Option<(&crate::state::SignedMeterValue, bool)>,
     ) -> Vec<MeterValue> {
         let Some(sample) = sample else {
             return Vec::new();
         };
-        let sampled_value = sampled_values(sample, measurands);
+        let sampled_value = sampled_values(sample, measurands, context, signature);
         if sampled_value.is_empty() {
             return Vec::new();
         }
@@ -1010,12 +1460,15 @@ pub(crate) mod ocpp_2_1 {
     // as-is, warn, never fabricate or drop it).
     mod with_clock {
         use super::{
-            build_meter_values, map_charging_state, map_event_type, map_stop_reason,
-            trigger_reason_for,
+            build_meter_values, build_meter_values_with, map_charging_state, map_event_type,
+            map_stop_reason, trigger_reason_for,
         };
         use crate::actor::ChargePointActor;
         use crate::clock::{Clock, is_synchronized};
-        use crate::meter_values::{MeasurandSet, transaction_event_measurands};
+        use crate::meter_values::{
+            MeasurandSet, ReadingContext, SignedReadings, signed_readings,
+            transaction_event_measurands,
+        };
         use crate::pricing::{DimensionCost, TotalCostKind, TransactionCost};
         use crate::state::{Tariff, Transaction, TransactionEventKind, TransactionUpdateReason};
         use crate::tariff::advance_running_cost;
@@ -1135,6 +1588,10 @@ pub(crate) mod ocpp_2_1 {
         /// `None` when no tariff currently prices it, in which case `costDetails` and
         /// `transactionInfo.tariffId` are both left unset rather than reporting stale figures
         /// (I07, I08, I11, I12).
+        ///
+        /// `signing` decides whether the reading a `Started` or `Ended` event - or a
+        /// `SignedDataReceived` update - reports carries the meter's signature (E11.FR.08) - see
+        /// [`SignedReadings`].
         #[allow(clippy::too_many_arguments)]
         fn build_transaction_event_request(
             now: DateTime<Utc>,
@@ -1144,6 +1601,7 @@ pub(crate) mod ocpp_2_1 {
             transaction: Transaction,
             offline: bool,
             measurands: MeasurandSet,
+            signing: SignedReadings,
             cost: Option<(TransactionCost, Tariff)>,
         ) -> TransactionEventRequest {
             if !is_synchronized(&now) {
@@ -1152,7 +1610,56 @@ pub(crate) mod ocpp_2_1 {
                     "TransactionEvent timestamp sourced from an unsynchronized clock"
                 );
             }
-            let meter_value = build_meter_values(transaction.last_meter_sample, now, measurands);
+            // J02: an `Ended` event reports the readings sampled every `TxEndedInterval` ahead of
+            // the final one, each at the instant it was taken; both are filtered by
+            // `TxEndedMeasurands` through `measurands`. The clock-aligned ones kept beside them are
+            // 1.6J's alone - see `Transaction::ended_meter_samples`.
+            let mut meter_value: Vec<_> = match kind {
+                TransactionEventKind::Ended => transaction
+                    .ended_meter_samples
+                    .iter()
+                    .filter(|sampled| !sampled.clock_aligned)
+                    .flat_map(|sampled| {
+                        build_meter_values(Some(&sampled.sample), sampled.timestamp, measurands)
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            // `Started` reports the reading the transaction began from, `Ended` the one it
+            // finished on; only those two are signed, being what calibration law bills between -
+            // and a `SignedDataReceived` update, whose signature is the news it carries.
+            let (sample, context) = match kind {
+                TransactionEventKind::Started => (
+                    transaction.start_meter_sample.as_ref(),
+                    ReadingContext::TransactionBegin,
+                ),
+                TransactionEventKind::Updated(TransactionUpdateReason::Trigger) => (
+                    transaction.last_meter_sample.as_ref(),
+                    ReadingContext::Trigger,
+                ),
+                TransactionEventKind::Updated(_) => (
+                    transaction.last_meter_sample.as_ref(),
+                    ReadingContext::SamplePeriodic,
+                ),
+                TransactionEventKind::Ended => (
+                    transaction.last_meter_sample.as_ref(),
+                    ReadingContext::TransactionEnd,
+                ),
+            };
+            let signature = sample
+                .filter(|_| {
+                    matches!(
+                        context,
+                        ReadingContext::TransactionBegin | ReadingContext::TransactionEnd
+                    ) || kind
+                        == TransactionEventKind::Updated(
+                            TransactionUpdateReason::SignedDataReceived,
+                        )
+                })
+                .and_then(|sample| signing.signature(sample, context, &transaction));
+            meter_value.extend(build_meter_values_with(
+                sample, now, measurands, context, signature,
+            ));
             let cost_details = cost
                 .as_ref()
                 .and_then(|(cost, tariff)| build_cost_details(cost, tariff));
@@ -1236,6 +1743,7 @@ pub(crate) mod ocpp_2_1 {
             transaction: Transaction,
             offline: bool,
             measurands: MeasurandSet,
+            signing: SignedReadings,
             cost: Option<(TransactionCost, Tariff)>,
         ) -> Result<TransactionEventOutcome, ClientError<OCPP2_1Error>> {
             let request = build_transaction_event_request(
@@ -1246,6 +1754,7 @@ pub(crate) mod ocpp_2_1 {
                 transaction,
                 offline,
                 measurands,
+                signing,
                 cost,
             );
             let response = client.send_transaction_event(request).await?;
@@ -1376,6 +1885,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction,
                     offline,
                     transaction_event_measurands(&self.actor, kind),
+                    signed_readings(&self.actor),
                     cost,
                 )
                 .await
@@ -1386,10 +1896,10 @@ pub(crate) mod ocpp_2_1 {
         /// (sourcing its timestamp from [`crate::clock::SystemClock`]) so existing callers that
         /// pass a bare client - e.g. [`crate::connect::connect_and_setup`] - need no source change.
         ///
-        /// **This impl cannot honour `SampledDataCtrlr`'s measurand lists, nor I07/I08/I11/I12's
-        /// `costDetails`** and does not pretend to: a bare client has no [`ChargePointActor`] to
-        /// ask, so it reports every measurand the sample carries ([`MeasurandSet::ALL`]) and no
-        /// cost at all. A station that must honour J01/J02 or report a running cost wires
+        /// **This impl cannot honour `SampledDataCtrlr`'s measurand lists or `SignReadings`, nor
+        /// I07/I08/I11/I12's `costDetails`** and does not pretend to: a bare client has no
+        /// [`ChargePointActor`] to ask, so it reports every measurand the sample carries
+        /// ([`MeasurandSet::ALL`]), no signature and no cost at all. A station that must honour J01/J02 or report a running cost wires
         /// [`Ocpp2_1TransactionNotifier`] through [`crate::builder::ChargePointBuilder`] instead.
         #[cfg(feature = "std")]
         #[async_trait::async_trait]
@@ -1413,6 +1923,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction,
                     offline,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                     // No actor, so no tariff to price against - see this impl's docs.
                     None,
                 )
@@ -1453,6 +1964,8 @@ pub(crate) mod ocpp_2_1 {
                     limit_reached: None,
                     energy_start_wh: None,
                     elapsed_secs: None,
+                    ended_meter_samples: Vec::new(),
+                    start_meter_sample: None,
                 }
             }
 
@@ -1471,6 +1984,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction(),
                     false,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                     None,
                 );
 
@@ -1493,6 +2007,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction(),
                     false,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                     None,
                 );
 
@@ -1519,6 +2034,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction(),
                     true,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                     None,
                 );
                 assert_eq!(held.offline, Some(true));
@@ -1531,6 +2047,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction(),
                     false,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                     None,
                 );
                 assert_eq!(live.offline, None);
@@ -1560,7 +2077,7 @@ pub(crate) mod ocpp_2_1 {
                     clock.now(),
                     0,
                     0,
-                    TransactionEventKind::Started,
+                    TransactionEventKind::Updated(TransactionUpdateReason::MeterValuePeriodic),
                     transaction_with_sample(),
                     false,
                     MeasurandSet {
@@ -1568,6 +2085,7 @@ pub(crate) mod ocpp_2_1 {
                         voltage: true,
                         ..MeasurandSet::default()
                     },
+                    SignedReadings::default(),
                     None,
                 );
 
@@ -1580,6 +2098,118 @@ pub(crate) mod ocpp_2_1 {
                 );
             }
 
+            fn signed_sample(energy_wh: i64, record: &str) -> crate::state::MeterSample {
+                crate::state::MeterSample {
+                    energy_wh,
+                    signed: Some(Box::new(crate::state::SignedMeterValue {
+                        signed_meter_data: record.into(),
+                        signing_method: "ECDSA-secp256r1-SHA256".into(),
+                        encoding_method: "OCMF".into(),
+                        public_key: "MFkwEwYHKoZIzj0CAQ".into(),
+                    })),
+                    ..Default::default()
+                }
+            }
+
+            /// E11.FR.08: with `SignReadings` set, the begin and end readings carry the meter's
+            /// signature on the energy register, and `OncePerTransaction` sends the key with the
+            /// first of them only.
+            #[test]
+            fn signed_begin_and_end_readings_reach_started_and_ended() {
+                let now = DateTime::<Utc>::from_timestamp(1_800_000_000, 0).unwrap();
+                let signing = SignedReadings {
+                    enabled: true,
+                    public_key:
+                        crate::meter_values::PublicKeyWithSignedMeterValue::OncePerTransaction,
+                };
+                let signed = Transaction {
+                    start_meter_sample: Some(signed_sample(1_000, "OCMF|begin")),
+                    last_meter_sample: Some(signed_sample(9_000, "OCMF|end")),
+                    ..transaction()
+                };
+
+                let reading = |kind| {
+                    let request = build_transaction_event_request(
+                        now,
+                        0,
+                        0,
+                        kind,
+                        signed.clone(),
+                        false,
+                        MeasurandSet::ALL,
+                        signing,
+                        None,
+                    );
+                    request.meter_value.expect("a reading")[0].sampled_value[0].clone()
+                };
+                let started = reading(TransactionEventKind::Started);
+                let ended = reading(TransactionEventKind::Ended);
+
+                assert_eq!(started.value, 1_000.0);
+                assert_eq!(
+                    started.context,
+                    Some(crate::wire::v21::common::ReadingContextEnum::TransactionBegin)
+                );
+                let begin = started.signed_meter_value.expect("signed begin reading");
+                assert_eq!(begin.signed_meter_data, "OCMF|begin");
+                assert_eq!(begin.encoding_method.as_str(), "OCMF");
+                assert_eq!(begin.public_key.as_deref(), Some("MFkwEwYHKoZIzj0CAQ"));
+
+                assert_eq!(
+                    ended.context,
+                    Some(crate::wire::v21::common::ReadingContextEnum::TransactionEnd)
+                );
+                let end = ended.signed_meter_value.expect("signed end reading");
+                assert_eq!(end.signed_meter_data, "OCMF|end");
+                assert_eq!(end.public_key, None, "the key went with the begin reading");
+            }
+
+            /// A meter that signs does not make the CSMS receive signatures it did not ask for,
+            /// and a periodic reading is never signed.
+            #[test]
+            fn a_signature_is_only_sent_while_sign_readings_is_set() {
+                let now = DateTime::<Utc>::from_timestamp(1_800_000_000, 0).unwrap();
+                let signed = Transaction {
+                    start_meter_sample: Some(signed_sample(1_000, "OCMF|begin")),
+                    last_meter_sample: Some(signed_sample(2_000, "OCMF|update")),
+                    ..transaction()
+                };
+                let first_value = |kind, signing| {
+                    build_transaction_event_request(
+                        now,
+                        0,
+                        0,
+                        kind,
+                        signed.clone(),
+                        false,
+                        MeasurandSet::ALL,
+                        signing,
+                        None,
+                    )
+                    .meter_value
+                    .expect("a reading")[0]
+                        .sampled_value[0]
+                        .clone()
+                };
+
+                assert_eq!(
+                    first_value(TransactionEventKind::Started, SignedReadings::default())
+                        .signed_meter_value,
+                    None
+                );
+                assert_eq!(
+                    first_value(
+                        TransactionEventKind::Updated(TransactionUpdateReason::MeterValuePeriodic),
+                        SignedReadings {
+                            enabled: true,
+                            ..SignedReadings::default()
+                        },
+                    )
+                    .signed_meter_value,
+                    None
+                );
+            }
+
             /// CV15 (E16.FR.01/.03): the confirmation rides on the event whose trigger reason is
             /// `LimitSet`, and on no other. Both halves asserted together, because "once" is the
             /// requirement and a test of only the positive case would pass on a build that sent
@@ -1604,6 +2234,7 @@ pub(crate) mod ocpp_2_1 {
                         limited.clone(),
                         false,
                         MeasurandSet::default(),
+                        SignedReadings::default(),
                         None,
                     )
                 };
@@ -1681,6 +2312,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction_with_sample(),
                     false,
                     MeasurandSet::default(),
+                    SignedReadings::default(),
                     None,
                 );
 
@@ -1692,6 +2324,64 @@ pub(crate) mod ocpp_2_1 {
                 );
             }
 
+            /// J02: an `Ended` event reports the `TxEndedInterval` readings, each at its own
+            /// instant and oldest first, ahead of the final reading stamped now. The clock-aligned
+            /// readings kept for 1.6J are not among them.
+            #[test]
+            fn an_ended_event_reports_the_sampled_readings_before_the_final_one() {
+                let now = DateTime::<Utc>::from_timestamp(1_800_000_000, 0).unwrap();
+                let earlier = DateTime::<Utc>::from_timestamp(1_799_999_000, 0).unwrap();
+                let aligned = DateTime::<Utc>::from_timestamp(1_799_999_100, 0).unwrap();
+                let ended = Transaction {
+                    ended_meter_samples: alloc::vec![
+                        crate::state::TimestampedMeterSample {
+                            timestamp: earlier,
+                            sample: crate::state::MeterSample {
+                                energy_wh: 3_000,
+                                ..Default::default()
+                            },
+                            clock_aligned: false,
+                        },
+                        crate::state::TimestampedMeterSample {
+                            timestamp: aligned,
+                            sample: crate::state::MeterSample {
+                                energy_wh: 3_100,
+                                ..Default::default()
+                            },
+                            clock_aligned: true,
+                        },
+                    ],
+                    ..transaction_with_sample()
+                };
+
+                let request = build_transaction_event_request(
+                    now,
+                    0,
+                    0,
+                    TransactionEventKind::Ended,
+                    ended,
+                    false,
+                    MeasurandSet {
+                        energy: true,
+                        ..MeasurandSet::default()
+                    },
+                    SignedReadings::default(),
+                    None,
+                );
+
+                let meter_value = request.meter_value.expect("two readings");
+                assert_eq!(
+                    meter_value
+                        .iter()
+                        .map(|value| (
+                            DateTime::<Utc>::from(value.timestamp),
+                            value.sampled_value[0].value
+                        ))
+                        .collect::<Vec<_>>(),
+                    alloc::vec![(earlier, 3_000.0), (now, 4_200.0)]
+                );
+            }
+
             fn priced_tariff() -> Tariff {
                 let mut tariff = Tariff::new(crate::state::TariffId("t1".into()), "EUR");
                 tariff.energy = Some(crate::state::EnergyComponent {
@@ -1735,6 +2425,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction(),
                     false,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                     Some((cost, tariff)),
                 );
 
@@ -1758,6 +2449,7 @@ pub(crate) mod ocpp_2_1 {
                     transaction(),
                     false,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                     None,
                 );
 
@@ -1782,7 +2474,7 @@ pub(crate) mod ocpp_2_1 {
                 .unwrap()
                 .with_timezone(&Utc);
 
-            let values = build_meter_values(Some(sample), timestamp, MeasurandSet::ALL);
+            let values = build_meter_values(Some(&sample), timestamp, MeasurandSet::ALL);
 
             assert_eq!(values.len(), 1);
             assert_eq!(values[0].sampled_value.len(), 1);
@@ -1801,12 +2493,13 @@ pub(crate) mod ocpp_2_1 {
                 current_ma: Some(32_000),
                 voltage_v: Some(230),
                 soc_percent: Some(42),
+                ..Default::default()
             };
             let timestamp = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
                 .unwrap()
                 .with_timezone(&Utc);
 
-            let values = build_meter_values(Some(sample), timestamp, MeasurandSet::ALL);
+            let values = build_meter_values(Some(&sample), timestamp, MeasurandSet::ALL);
 
             assert_eq!(values.len(), 1);
             let sampled = &values[0].sampled_value;
@@ -1828,6 +2521,53 @@ pub(crate) mod ocpp_2_1 {
                 .any(|value| value.measurand == Some(MeasurandEnum::SoC) && value.value == 42.0));
         }
 
+        #[test]
+        fn per_phase_and_export_readings_carry_their_phase_and_unit() {
+            let sample = MeterSample {
+                energy_wh: 1_500,
+                power_export_w: Some(3_700),
+                phase_current_ma: [Some(16_000), None, None],
+                phase_voltage_v: [None, None, Some(229)],
+                power_factor_milli: Some(-950),
+                temperature_c: Some(41),
+                ..Default::default()
+            };
+
+            let values = build_meter_values(Some(&sample), Utc::now(), MeasurandSet::ALL);
+
+            let sampled = &values[0].sampled_value;
+            fn unit(value: &SampledValue) -> Option<&str> {
+                value.unit_of_measure.as_ref()?.unit.as_deref()
+            }
+            let l1 = sampled
+                .iter()
+                .find(|value| value.phase == Some(PhaseEnum::L1))
+                .expect("L1 current reported");
+            assert_eq!(l1.measurand, Some(MeasurandEnum::CurrentImport));
+            assert_eq!(l1.value, 16.0);
+            assert_eq!(unit(l1), Some("A"));
+            let l3n = sampled
+                .iter()
+                .find(|value| value.phase == Some(PhaseEnum::L3N))
+                .expect("L3-N voltage reported");
+            assert_eq!(l3n.measurand, Some(MeasurandEnum::Voltage));
+            assert_eq!(unit(l3n), Some("V"));
+            let export = sampled
+                .iter()
+                .find(|value| value.measurand == Some(MeasurandEnum::PowerActiveExport))
+                .expect("export power reported");
+            assert_eq!(export.value, 3_700.0);
+            assert_eq!(unit(export), Some("W"));
+            let power_factor = sampled
+                .iter()
+                .find(|value| value.measurand == Some(MeasurandEnum::PowerFactor))
+                .expect("power factor reported");
+            assert_eq!(power_factor.value, -0.95);
+            assert_eq!(power_factor.unit_of_measure, None);
+            // Energy, L1, L3-N, export, power factor - and no temperature, which 2.x cannot name.
+            assert_eq!(sampled.len(), 5);
+        }
+
         #[test]
         fn no_sample_reports_no_meter_values() {
             let timestamp = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
@@ -1900,6 +2640,8 @@ pub(crate) mod ocpp_2_1 {
                 limit_reached: None,
                 energy_start_wh: None,
                 elapsed_secs: None,
+                ended_meter_samples: Vec::new(),
+                start_meter_sample: None,
             };
 
             assert_eq!(
@@ -1922,6 +2664,70 @@ pub(crate) mod ocpp_2_1 {
             );
         }
 
+        /// Each update is reported under the reason for what was observed, and a
+        /// transaction the bay sensor began - the only kind that begins `Idle` - was triggered by
+        /// the vehicle arriving.
+        #[test]
+        fn each_observed_update_reports_its_own_trigger_reason() {
+            let mut transaction = Transaction {
+                id: crate::state::TransactionId(0),
+                id_token: None,
+                charging_state: TransactionChargingState::Charging,
+                stop_reason: None,
+                seq_no: 0,
+                last_meter_sample: None,
+                priority_charging: false,
+                remote_start_id: None,
+                reservation_id: None,
+                stop_at_energy_wh: None,
+                limit: None,
+                csms_limit: None,
+                limit_reached: None,
+                energy_start_wh: None,
+                elapsed_secs: None,
+                ended_meter_samples: Vec::new(),
+                start_meter_sample: None,
+            };
+            for (reason, expected) in [
+                (
+                    TransactionUpdateReason::EvCommunicationLost,
+                    TriggerReasonEnum::EVCommunicationLost,
+                ),
+                (
+                    TransactionUpdateReason::SignedDataReceived,
+                    TriggerReasonEnum::SignedDataReceived,
+                ),
+                (TransactionUpdateReason::Trigger, TriggerReasonEnum::Trigger),
+                (
+                    TransactionUpdateReason::TariffChanged,
+                    TriggerReasonEnum::TariffChanged,
+                ),
+                (
+                    TransactionUpdateReason::EvDetected,
+                    TriggerReasonEnum::EVDetected,
+                ),
+                (
+                    TransactionUpdateReason::EvDeparted,
+                    TriggerReasonEnum::EVDeparted,
+                ),
+                (
+                    TransactionUpdateReason::CablePluggedIn,
+                    TriggerReasonEnum::CablePluggedIn,
+                ),
+            ] {
+                assert_eq!(
+                    trigger_reason_for(TransactionEventKind::Updated(reason), &transaction),
+                    expected
+                );
+            }
+
+            transaction.charging_state = TransactionChargingState::Idle;
+            assert_eq!(
+                trigger_reason_for(TransactionEventKind::Started, &transaction),
+                TriggerReasonEnum::EVDetected
+            );
+        }
+
         /// CV6: F01.FR.19/F02.FR.21 - a transaction the CSMS asked for reports `RemoteStart`, not
         /// the `Authorized` a locally presented card produces. The `remoteStartId`'s presence is
         /// the discriminator, since only `handle_request_start_transaction` ever sets one.
@@ -1943,6 +2749,8 @@ pub(crate) mod ocpp_2_1 {
                 limit_reached: None,
                 energy_start_wh: None,
                 elapsed_secs: None,
+                ended_meter_samples: Vec::new(),
+                start_meter_sample: None,
             };
 
             assert_eq!(
@@ -1977,6 +2785,8 @@ pub(crate) mod ocpp_2_1 {
                 limit_reached: None,
                 energy_start_wh: None,
                 elapsed_secs: None,
+                ended_meter_samples: Vec::new(),
+                start_meter_sample: None,
             };
 
             assert_eq!(
@@ -2039,15 +2849,17 @@ pub(crate) mod ocpp_2_1 {
 pub(crate) mod ocpp_2_0_1 {
     pub use with_clock::Ocpp2_0_1TransactionNotifier;
 
-    use crate::meter_values::MeasurandSet;
+    use crate::meter_values::{
+        MeasurandSet, Reading, ReadingContext, ReadingKind, ReadingPhase, readings,
+    };
 
     use crate::state::{
         MeterSample, StopReason, Transaction, TransactionChargingState, TransactionEventKind,
         TransactionLimitKind, TransactionUpdateReason,
     };
     use crate::wire::v201::common::{
-        ChargingStateEnum, MeasurandEnum, MeterValue, ReadingContextEnum, ReasonEnum, SampledValue,
-        TransactionEventEnum, TriggerReasonEnum,
+        ChargingStateEnum, MeasurandEnum, MeterValue, PhaseEnum, ReadingContextEnum, ReasonEnum,
+        SampledValue, SignedMeterValue, TransactionEventEnum, TriggerReasonEnum, UnitOfMeasure,
     };
     use alloc::vec;
     use alloc::vec::Vec;
@@ -2095,6 +2907,13 @@ pub(crate) mod ocpp_2_0_1 {
             TransactionEventKind::Started if transaction.remote_start_id.is_some() => {
                 TriggerReasonEnum::RemoteStart
             }
+            // CV2.2: a transaction the bay sensor began, before any cable or identifier, was
+            // triggered by the vehicle arriving - and only a bay start begins `Idle`.
+            TransactionEventKind::Started
+                if transaction.charging_state == TransactionChargingState::Idle =>
+            {
+                TriggerReasonEnum::EVDetected
+            }
             TransactionEventKind::Started => TriggerReasonEnum::Authorized,
             TransactionEventKind::Updated(TransactionUpdateReason::ChargingStateChanged) => {
                 TriggerReasonEnum::ChargingStateChanged
@@ -2111,9 +2930,30 @@ pub(crate) mod ocpp_2_0_1 {
             // it does have, so those two are exact; the other two report the charging-state
             // change that accompanies them, which is true (the connector really did move to
             // `SuspendedEVSE`) even though it does not say why. A `LimitSet` confirmation has no
-            // honest 2.0.1 spelling at all and is not sent - see `notify_transaction_event`.
-            TransactionEventKind::Updated(TransactionUpdateReason::LimitSet) => {
-                TriggerReasonEnum::ChargingStateChanged
+            // honest 2.0.1 spelling at all and is not sent - see `notify_transaction_event`. Nor
+            // does a `TariffChanged`, which only 2.1's `ChangeTransactionTariff` can cause.
+            TransactionEventKind::Updated(
+                TransactionUpdateReason::LimitSet | TransactionUpdateReason::TariffChanged,
+            ) => TriggerReasonEnum::ChargingStateChanged,
+            TransactionEventKind::Updated(TransactionUpdateReason::EvCommunicationLost) => {
+                TriggerReasonEnum::EVCommunicationLost
+            }
+            // E11: a signed reading, sent as it arrived.
+            TransactionEventKind::Updated(TransactionUpdateReason::SignedDataReceived) => {
+                TriggerReasonEnum::SignedDataReceived
+            }
+            // F06: the CSMS asked.
+            TransactionEventKind::Updated(TransactionUpdateReason::Trigger) => {
+                TriggerReasonEnum::Trigger
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::EvDetected) => {
+                TriggerReasonEnum::EVDetected
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::EvDeparted) => {
+                TriggerReasonEnum::EVDeparted
+            }
+            TransactionEventKind::Updated(TransactionUpdateReason::CablePluggedIn) => {
+                TriggerReasonEnum::CablePluggedIn
             }
             TransactionEventKind::Updated(TransactionUpdateReason::LimitReached(kind)) => {
                 match kind {
@@ -2142,60 +2982,116 @@ pub(crate) mod ocpp_2_0_1 {
     }
 
     /// Mirrors [`super::ocpp_2_1::sampled_values`], including its CV2.6 measurand filter - see
-    /// there for why what the meter reported and what the CSMS selected are separate conditions.
-    fn sampled_values(sample: MeterSample, measurands: MeasurandSet) -> Vec<SampledValue> {
-        let mut values = Vec::new();
-        if measurands.energy {
-            values.push(sampled_value(
-                sample.energy_wh as f64,
-                MeasurandEnum::EnergyActiveImportRegister,
-            ));
-        }
-        if let Some(power_w) = sample.power_w.filter(|_| measurands.power) {
-            values.push(sampled_value(
-                power_w as f64,
-                MeasurandEnum::PowerActiveImport,
-            ));
-        }
-        if let Some(current_ma) = sample.current_ma.filter(|_| measurands.current) {
-            values.push(sampled_value(
-                current_ma as f64 / 1_000.0,
-                MeasurandEnum::CurrentImport,
-            ));
-        }
-        if let Some(voltage_v) = sample.voltage_v.filter(|_| measurands.voltage) {
-            values.push(sampled_value(voltage_v as f64, MeasurandEnum::Voltage));
-        }
-        if let Some(soc_percent) = sample.soc_percent.filter(|_| measurands.soc) {
-            values.push(sampled_value(soc_percent as f64, MeasurandEnum::SoC));
-        }
-        values
+    /// [`crate::meter_values::readings`] for why what the meter reported and what the CSMS selected
+    /// are separate conditions.
+    fn sampled_values(
+        sample: &MeterSample,
+        measurands: MeasurandSet,
+        context: ReadingContext,
+        signature: Option<(&crate::state::SignedMeterValue, bool)>,
+    ) -> Vec<SampledValue> {
+        readings(sample, measurands)
+            .into_iter()
+            .filter_map(|reading| {
+                let mut value = sampled_value(reading, context)?;
+                if reading.kind == ReadingKind::EnergyImport && reading.phase.is_none() {
+                    value.signed_meter_value = signature.and_then(signed_meter_value);
+                }
+                Some(value)
+            })
+            .collect()
     }
 
-    fn sampled_value(value: f64, measurand: MeasurandEnum) -> SampledValue {
-        SampledValue {
-            value,
+    /// Mirrors [`super::ocpp_2_1::signed_meter_value`], except that 2.0.1 makes `publicKey` and
+    /// `signingMethod` mandatory: a key `PublicKeyWithSignedMeterValue` withholds, or a method
+    /// the record already states, goes out as the empty string.
+    fn signed_meter_value(
+        (signed, with_public_key): (&crate::state::SignedMeterValue, bool),
+    ) -> Option<SignedMeterValue> {
+        Some(SignedMeterValue {
+            custom_data: None,
+            encoding_method: heapless::String::try_from(signed.encoding_method.as_str()).ok()?,
+            public_key: if with_public_key {
+                signed.public_key.clone()
+            } else {
+                alloc::string::String::new()
+            },
+            signed_meter_data: signed.signed_meter_data.clone(),
+            signing_method: heapless::String::try_from(signed.signing_method.as_str()).ok()?,
+        })
+    }
+
+    /// `None` for `Temperature`, which 2.0.1's `MeasurandEnumType` does not have either.
+    fn sampled_value(reading: Reading, context: ReadingContext) -> Option<SampledValue> {
+        let measurand = match reading.kind {
+            ReadingKind::EnergyImport => MeasurandEnum::EnergyActiveImportRegister,
+            ReadingKind::PowerImport => MeasurandEnum::PowerActiveImport,
+            ReadingKind::CurrentImport => MeasurandEnum::CurrentImport,
+            ReadingKind::Voltage => MeasurandEnum::Voltage,
+            ReadingKind::Soc => MeasurandEnum::SoC,
+            ReadingKind::EnergyExport => MeasurandEnum::EnergyActiveExportRegister,
+            ReadingKind::PowerExport => MeasurandEnum::PowerActiveExport,
+            ReadingKind::ReactivePowerImport => MeasurandEnum::PowerReactiveImport,
+            ReadingKind::PowerFactor => MeasurandEnum::PowerFactor,
+            ReadingKind::Frequency => MeasurandEnum::Frequency,
+            ReadingKind::Temperature => return None,
+        };
+        Some(SampledValue {
+            value: reading.value,
             measurand: Some(measurand),
-            context: Some(ReadingContextEnum::SamplePeriodic),
-            phase: None,
+            context: Some(match context {
+                ReadingContext::SamplePeriodic => ReadingContextEnum::SamplePeriodic,
+                ReadingContext::Trigger => ReadingContextEnum::Trigger,
+                ReadingContext::TransactionBegin => ReadingContextEnum::TransactionBegin,
+                ReadingContext::TransactionEnd => ReadingContextEnum::TransactionEnd,
+            }),
+            phase: reading.phase.map(|phase| match phase {
+                ReadingPhase::L1 => PhaseEnum::L1,
+                ReadingPhase::L2 => PhaseEnum::L2,
+                ReadingPhase::L3 => PhaseEnum::L3,
+                ReadingPhase::L1N => PhaseEnum::L1N,
+                ReadingPhase::L2N => PhaseEnum::L2N,
+                ReadingPhase::L3N => PhaseEnum::L3N,
+            }),
             location: None,
             signed_meter_value: None,
-            unit_of_measure: None,
+            unit_of_measure: reading.kind.unit().map(|unit| UnitOfMeasure {
+                unit: heapless::String::try_from(unit).ok(),
+                multiplier: None,
+                custom_data: None,
+            }),
             custom_data: None,
-        }
+        })
     }
 
     /// Mirrors [`super::ocpp_2_1::build_meter_values`], including its CV2.6 rule that a measurand
     /// set selecting nothing produces no `MeterValue` at all.
     pub(crate) fn build_meter_values(
-        sample: Option<MeterSample>,
+        sample: Option<&MeterSample>,
         timestamp: DateTime<Utc>,
         measurands: MeasurandSet,
+    ) -> Vec<MeterValue> {
+        build_meter_values_with(
+            sample,
+            timestamp,
+            measurands,
+            ReadingContext::SamplePeriodic,
+            None,
+        )
+    }
+
+    /// Mirrors [`super::ocpp_2_1::build_meter_values_with`].
+    pub(super) fn build_meter_values_with(
+        sample: Option<&MeterSample>,
+        timestamp: DateTime<Utc>,
+        measurands: MeasurandSet,
+        context: ReadingContext,
+        signature: Option<(&crate::state::SignedMeterValue, bool)>,
     ) -> Vec<MeterValue> {
         let Some(sample) = sample else {
             return Vec::new();
         };
-        let sampled_value = sampled_values(sample, measurands);
+        let sampled_value = sampled_values(sample, measurands, context, signature);
         if sampled_value.is_empty() {
             return Vec::new();
         }
@@ -2211,25 +3107,31 @@ pub(crate) mod ocpp_2_0_1 {
     // as-is, warn, never fabricate or drop it). Mirrors `super::ocpp_2_1::with_clock`.
     mod with_clock {
         use super::{
-            build_meter_values, map_charging_state, map_event_type, map_stop_reason,
-            trigger_reason_for,
+            build_meter_values, build_meter_values_with, map_charging_state, map_event_type,
+            map_stop_reason, trigger_reason_for,
         };
         use crate::actor::ChargePointActor;
         use crate::clock::{Clock, is_synchronized};
-        use crate::meter_values::{MeasurandSet, transaction_event_measurands};
-        use crate::state::{Transaction, TransactionEventKind};
+        use crate::meter_values::{
+            MeasurandSet, ReadingContext, SignedReadings, signed_readings,
+            transaction_event_measurands,
+        };
+        use crate::state::{Transaction, TransactionEventKind, TransactionUpdateReason};
         use crate::transactions::{TransactionEventOutcome, TransactionNotifier};
         use crate::wire::v201::TransactionEventRequest;
         use crate::wire::v201::common::{
             AuthorizationStatusEnum, EVSE, Transaction as WireTransaction,
         };
         use alloc::boxed::Box;
+        use alloc::vec::Vec;
         use ocpp_client::ClientError;
         use ocpp_client::ocpp_2_0_1::{OCPP2_0_1Client, OCPP2_0_1Error};
 
         /// Mirrors `super::ocpp_2_1::with_clock::build_transaction_event_request` - pure, so a
         /// fixed [`Clock`] fake can assert the exact timestamp reaching the wire request, and
-        /// carrying only the measurands `measurands` selects (CV2.6).
+        /// carrying only the measurands `measurands` selects (CV2.6) and, on `Started`/`Ended` or a
+        /// `SignedDataReceived` update, the signature `signing` allows.
+        #[allow(clippy::too_many_arguments)]
         fn build_transaction_event_request<C: Clock>(
             clock: &C,
             evse_id: usize,
@@ -2238,6 +3140,7 @@ pub(crate) mod ocpp_2_0_1 {
             transaction: Transaction,
             offline: bool,
             measurands: MeasurandSet,
+            signing: SignedReadings,
         ) -> TransactionEventRequest {
             let now = clock.now();
             if !is_synchronized(&now) {
@@ -2246,7 +3149,53 @@ pub(crate) mod ocpp_2_0_1 {
                     "TransactionEvent timestamp sourced from an unsynchronized clock"
                 );
             }
-            let meter_value = build_meter_values(transaction.last_meter_sample, now, measurands);
+            // J02: an `Ended` event reports the readings sampled every `TxEndedInterval` ahead of
+            // the final one, each at the instant it was taken; both are filtered by
+            // `TxEndedMeasurands` through `measurands`. The clock-aligned ones kept beside them are
+            // 1.6J's alone - see `Transaction::ended_meter_samples`.
+            let mut meter_value: Vec<_> = match kind {
+                TransactionEventKind::Ended => transaction
+                    .ended_meter_samples
+                    .iter()
+                    .filter(|sampled| !sampled.clock_aligned)
+                    .flat_map(|sampled| {
+                        build_meter_values(Some(&sampled.sample), sampled.timestamp, measurands)
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            let (sample, context) = match kind {
+                TransactionEventKind::Started => (
+                    transaction.start_meter_sample.as_ref(),
+                    ReadingContext::TransactionBegin,
+                ),
+                TransactionEventKind::Updated(TransactionUpdateReason::Trigger) => (
+                    transaction.last_meter_sample.as_ref(),
+                    ReadingContext::Trigger,
+                ),
+                TransactionEventKind::Updated(_) => (
+                    transaction.last_meter_sample.as_ref(),
+                    ReadingContext::SamplePeriodic,
+                ),
+                TransactionEventKind::Ended => (
+                    transaction.last_meter_sample.as_ref(),
+                    ReadingContext::TransactionEnd,
+                ),
+            };
+            let signature = sample
+                .filter(|_| {
+                    matches!(
+                        context,
+                        ReadingContext::TransactionBegin | ReadingContext::TransactionEnd
+                    ) || kind
+                        == TransactionEventKind::Updated(
+                            TransactionUpdateReason::SignedDataReceived,
+                        )
+                })
+                .and_then(|sample| signing.signature(sample, context, &transaction));
+            meter_value.extend(build_meter_values_with(
+                sample, now, measurands, context, signature,
+            ));
             TransactionEventRequest {
                 custom_data: None,
                 event_type: map_event_type(kind),
@@ -2299,6 +3248,7 @@ pub(crate) mod ocpp_2_0_1 {
             transaction: Transaction,
             offline: bool,
             measurands: MeasurandSet,
+            signing: SignedReadings,
         ) -> Result<TransactionEventOutcome, ClientError<OCPP2_0_1Error>> {
             let request = build_transaction_event_request(
                 clock,
@@ -2308,6 +3258,7 @@ pub(crate) mod ocpp_2_0_1 {
                 transaction,
                 offline,
                 measurands,
+                signing,
             );
             let response = client.send_transaction_event(request).await?;
             Ok(read_outcome(&response))
@@ -2402,6 +3353,7 @@ pub(crate) mod ocpp_2_0_1 {
                     transaction,
                     offline,
                     transaction_event_measurands(&self.actor, kind),
+                    signed_readings(&self.actor),
                 )
                 .await
             }
@@ -2434,6 +3386,7 @@ pub(crate) mod ocpp_2_0_1 {
                     transaction,
                     offline,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                 )
                 .await
             }
@@ -2471,6 +3424,8 @@ pub(crate) mod ocpp_2_0_1 {
                     limit_reached: None,
                     energy_start_wh: None,
                     elapsed_secs: None,
+                    ended_meter_samples: Vec::new(),
+                    start_meter_sample: None,
                 }
             }
 
@@ -2489,6 +3444,7 @@ pub(crate) mod ocpp_2_0_1 {
                     transaction(),
                     false,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                 );
 
                 assert_eq!(request.timestamp, crate::wire::OcppTimestamp::from(fixed));
@@ -2507,6 +3463,7 @@ pub(crate) mod ocpp_2_0_1 {
                     transaction(),
                     false,
                     MeasurandSet::ALL,
+                    SignedReadings::default(),
                 );
 
                 assert_eq!(
@@ -2514,6 +3471,46 @@ pub(crate) mod ocpp_2_0_1 {
                     crate::wire::OcppTimestamp::from(unset_rtc.0)
                 );
             }
+
+            /// 2.0.1 cannot omit `publicKey` or `signingMethod`, so a withheld key is the empty
+            /// string rather than a missing field.
+            #[test]
+            fn a_withheld_public_key_is_sent_empty() {
+                let clock = FixedClock(DateTime::<Utc>::from_timestamp(1_800_000_000, 0).unwrap());
+                let signed = Transaction {
+                    start_meter_sample: Some(crate::state::MeterSample {
+                        energy_wh: 1_000,
+                        signed: Some(Box::new(crate::state::SignedMeterValue {
+                            signed_meter_data: "OCMF|begin".into(),
+                            signing_method: "ECDSA-secp256r1-SHA256".into(),
+                            encoding_method: "OCMF".into(),
+                            public_key: "MFkwEwYHKoZIzj0CAQ".into(),
+                        })),
+                        ..Default::default()
+                    }),
+                    ..transaction()
+                };
+
+                let request = build_transaction_event_request(
+                    &clock,
+                    0,
+                    0,
+                    TransactionEventKind::Started,
+                    signed,
+                    false,
+                    MeasurandSet::ALL,
+                    SignedReadings {
+                        enabled: true,
+                        ..SignedReadings::default()
+                    },
+                );
+
+                let value = &request.meter_value.expect("a reading")[0].sampled_value[0];
+                let signature = value.signed_meter_value.as_ref().expect("signed");
+                assert_eq!(signature.signed_meter_data, "OCMF|begin");
+                assert_eq!(signature.signing_method.as_str(), "ECDSA-secp256r1-SHA256");
+                assert_eq!(signature.public_key, "");
+            }
         }
     }
 
@@ -2532,7 +3529,7 @@ pub(crate) mod ocpp_2_0_1 {
                 .unwrap()
                 .with_timezone(&Utc);
 
-            let values = build_meter_values(Some(sample), timestamp, MeasurandSet::ALL);
+            let values = build_meter_values(Some(&sample), timestamp, MeasurandSet::ALL);
 
             assert_eq!(values.len(), 1);
             assert_eq!(values[0].sampled_value.len(), 1);
@@ -2615,6 +3612,8 @@ pub(crate) mod ocpp_2_0_1 {
                 limit_reached: None,
                 energy_start_wh: None,
                 elapsed_secs: None,
+                ended_meter_samples: Vec::new(),
+                start_meter_sample: None,
             };
 
             assert_eq!(
@@ -2637,6 +3636,71 @@ pub(crate) mod ocpp_2_0_1 {
             );
         }
 
+        /// Each update is reported under the reason for what was observed, and a
+        /// transaction the bay sensor began - the only kind that begins `Idle` - was triggered by
+        /// the vehicle arriving.
+        #[test]
+        fn each_observed_update_reports_its_own_trigger_reason() {
+            let mut transaction = Transaction {
+                id: crate::state::TransactionId(0),
+                id_token: None,
+                charging_state: TransactionChargingState::Charging,
+                stop_reason: None,
+                seq_no: 0,
+                last_meter_sample: None,
+                priority_charging: false,
+                remote_start_id: None,
+                reservation_id: None,
+                stop_at_energy_wh: None,
+                limit: None,
+                csms_limit: None,
+                limit_reached: None,
+                energy_start_wh: None,
+                elapsed_secs: None,
+                ended_meter_samples: Vec::new(),
+                start_meter_sample: None,
+            };
+            for (reason, expected) in [
+                (
+                    TransactionUpdateReason::EvCommunicationLost,
+                    TriggerReasonEnum::EVCommunicationLost,
+                ),
+                (
+                    TransactionUpdateReason::SignedDataReceived,
+                    TriggerReasonEnum::SignedDataReceived,
+                ),
+                (TransactionUpdateReason::Trigger, TriggerReasonEnum::Trigger),
+                // 2.1-only, and unreachable here - see `trigger_reason_for`.
+                (
+                    TransactionUpdateReason::TariffChanged,
+                    TriggerReasonEnum::ChargingStateChanged,
+                ),
+                (
+                    TransactionUpdateReason::EvDetected,
+                    TriggerReasonEnum::EVDetected,
+                ),
+                (
+                    TransactionUpdateReason::EvDeparted,
+                    TriggerReasonEnum::EVDeparted,
+                ),
+                (
+                    TransactionUpdateReason::CablePluggedIn,
+                    TriggerReasonEnum::CablePluggedIn,
+                ),
+            ] {
+                assert_eq!(
+                    trigger_reason_for(TransactionEventKind::Updated(reason), &transaction),
+                    expected
+                );
+            }
+
+            transaction.charging_state = TransactionChargingState::Idle;
+            assert_eq!(
+                trigger_reason_for(TransactionEventKind::Started, &transaction),
+                TriggerReasonEnum::EVDetected
+            );
+        }
+
         #[test]
         fn ended_derives_its_trigger_reason_from_the_stop_reason() {
             let base = Transaction {
@@ -2655,6 +3719,8 @@ pub(crate) mod ocpp_2_0_1 {
                 limit_reached: None,
                 energy_start_wh: None,
                 elapsed_secs: None,
+                ended_meter_samples: Vec::new(),
+                start_meter_sample: None,
             };
 
             assert_eq!(
@@ -2717,18 +3783,24 @@ pub(crate) mod ocpp_2_0_1 {
 /// `TransactionId` to the CSMS-assigned one, populated when `StartTransaction.conf` returns and
 /// consulted (then, on `Ended`, removed) for every later call on that transaction.
 ///
-/// Two real gaps, both inherited from the internal model rather than introduced here: 1.6J's
-/// `StartTransaction.req` needs `idTag` and `meterStart` - `idTag` now has a real source
-/// ([`crate::state::Transaction::id_token`]), but `meterStart` doesn't; this crate's
-/// [`crate::state::MeterSample`] is only ever recorded once charging is already under way (see
-/// `docs/ROADMAP.md` §10), so there's no reading captured *at* `Started` to report - this falls
-/// back to `0` (documented, not silently wrong). `idTag` mapping itself (including truncation
-/// for identifiers exceeding 1.6J's 20-byte bound) lives in [`crate::id_tag`], shared with every
-/// other 1.6J adapter that sends an identifier on the wire.
+/// 1.6J's `StartTransaction.req` needs `idTag` and `meterStart`. `idTag` comes from
+/// [`crate::state::Transaction::id_token`]; `meterStart` from
+/// [`crate::state::Transaction::start_meter_sample`], the connector's reading when the transaction
+/// began - and `0` only when the hardware had reported none yet, since the field is mandatory
+/// (documented, not silently wrong). `idTag` mapping itself (including truncation for identifiers
+/// exceeding 1.6J's 20-byte bound) lives in [`crate::id_tag`], shared with every other 1.6J
+/// adapter that sends an identifier on the wire.
 #[cfg(feature = "ocpp_1_6")]
 pub(crate) mod ocpp_1_6 {
-    use crate::state::{MeterSample, StopReason, TransactionId};
-    use crate::wire::v16::common::{Measurand, MeterValueItem, Reason, SampledValueItem};
+    use crate::actor::ChargePointActor;
+    use crate::meter_values::{
+        MeasurandSet, Reading, ReadingContext, ReadingKind, ReadingPhase, readings,
+    };
+    use crate::state::{MeterSample, SignedMeterValue, StopReason, TransactionId};
+    use crate::wire::v16::common::{
+        Context, Format, Location, Measurand, MeterValueItem, Phase, Reason, SampledValueItem,
+        SampledValueItemUnit,
+    };
     use alloc::collections::BTreeMap;
     use alloc::string::ToString;
     use alloc::vec;
@@ -2755,55 +3827,105 @@ pub(crate) mod ocpp_1_6 {
         }
     }
 
-    /// Builds one `sampledValue` per measurand present in `sample`, mirroring
-    /// [`super::ocpp_2_1::sampled_values`] but for 1.6J's string-valued `SampledValueItem`.
     /// Builds the 1.6J `meterValue` body for `sample`, shared with [`crate::meter_values`]'
-    /// standalone `MeterValues` adapter for the same reason the 2.x builders are.
+    /// standalone `MeterValues` adapter for the same reason the 2.x builders are - and, like them,
+    /// empty rather than a reading-free `MeterValueItem` when `measurands` selects nothing
+    /// present.
     pub(crate) fn build_meter_values(
-        sample: MeterSample,
+        sample: &MeterSample,
         timestamp: chrono::DateTime<chrono::Utc>,
+        measurands: MeasurandSet,
     ) -> Vec<MeterValueItem> {
+        let sampled_value = sampled_values(sample, measurands);
+        if sampled_value.is_empty() {
+            return Vec::new();
+        }
         vec![MeterValueItem {
             timestamp: timestamp.into(),
-            sampled_value: sampled_values(sample),
+            sampled_value,
         }]
     }
 
-    fn sampled_values(sample: MeterSample) -> Vec<SampledValueItem> {
-        let mut values = vec![sampled_value(
-            sample.energy_wh.to_string(),
-            Measurand::EnergyActiveImportRegister,
-        )];
-        if let Some(power_w) = sample.power_w {
-            values.push(sampled_value(
-                power_w.to_string(),
-                Measurand::PowerActiveImport,
-            ));
-        }
-        if let Some(current_ma) = sample.current_ma {
-            values.push(sampled_value(
-                (current_ma as f64 / 1_000.0).to_string(),
-                Measurand::CurrentImport,
-            ));
-        }
-        if let Some(voltage_v) = sample.voltage_v {
-            values.push(sampled_value(voltage_v.to_string(), Measurand::Voltage));
-        }
-        if let Some(soc_percent) = sample.soc_percent {
-            values.push(sampled_value(soc_percent.to_string(), Measurand::SoC));
-        }
-        values
+    /// Mirrors [`super::ocpp_2_1::sampled_values`] for 1.6J's string-valued `SampledValueItem`.
+    /// 1.6J's measurand lists are the same device-model variables under their 1.6J key names
+    /// (`MeterValuesSampledData` is `SampledDataCtrlr.TxUpdatedMeasurands`, and so on), so the
+    /// filter is the same one.
+    fn sampled_values(sample: &MeterSample, measurands: MeasurandSet) -> Vec<SampledValueItem> {
+        readings(sample, measurands)
+            .into_iter()
+            .map(sampled_value)
+            .collect()
     }
 
-    fn sampled_value(value: alloc::string::String, measurand: Measurand) -> SampledValueItem {
+    fn sampled_value(reading: Reading) -> SampledValueItem {
+        let measurand = match reading.kind {
+            ReadingKind::EnergyImport => Measurand::EnergyActiveImportRegister,
+            ReadingKind::PowerImport => Measurand::PowerActiveImport,
+            ReadingKind::CurrentImport => Measurand::CurrentImport,
+            ReadingKind::Voltage => Measurand::Voltage,
+            ReadingKind::Soc => Measurand::SoC,
+            ReadingKind::EnergyExport => Measurand::EnergyActiveExportRegister,
+            ReadingKind::PowerExport => Measurand::PowerActiveExport,
+            ReadingKind::ReactivePowerImport => Measurand::PowerReactiveImport,
+            ReadingKind::PowerFactor => Measurand::PowerFactor,
+            ReadingKind::Frequency => Measurand::Frequency,
+            ReadingKind::Temperature => Measurand::Temperature,
+        };
+        // 1.6J's unit enum is closed, and has no `Hz`: a frequency goes out without one, the
+        // same as a power factor, rather than under a unit it is not in.
+        let unit = match reading.kind {
+            ReadingKind::EnergyImport | ReadingKind::EnergyExport => Some(SampledValueItemUnit::Wh),
+            ReadingKind::PowerImport | ReadingKind::PowerExport => Some(SampledValueItemUnit::W),
+            ReadingKind::ReactivePowerImport => Some(SampledValueItemUnit::Var),
+            ReadingKind::CurrentImport => Some(SampledValueItemUnit::A),
+            ReadingKind::Voltage => Some(SampledValueItemUnit::V),
+            ReadingKind::Soc => Some(SampledValueItemUnit::Percent),
+            ReadingKind::Temperature => Some(SampledValueItemUnit::Celsius),
+            ReadingKind::PowerFactor | ReadingKind::Frequency => None,
+        };
         SampledValueItem {
             context: None,
             format: None,
-            location: None,
+            // 1.6J's default location is `Outlet`; the temperature is the charge point's own.
+            location: (reading.kind == ReadingKind::Temperature).then_some(Location::Body),
             measurand: Some(measurand),
+            phase: reading.phase.map(|phase| match phase {
+                ReadingPhase::L1 => Phase::L1,
+                ReadingPhase::L2 => Phase::L2,
+                ReadingPhase::L3 => Phase::L3,
+                ReadingPhase::L1N => Phase::L1N,
+                ReadingPhase::L2N => Phase::L2N,
+                ReadingPhase::L3N => Phase::L3N,
+            }),
+            unit,
+            value: reading.value.to_string(),
+        }
+    }
+
+    /// 1.6J's spelling of a signed reading: a `SampledValue` in the `SignedData` format whose
+    /// `value` *is* the signed record - the convention calibration-law deployments settled on,
+    /// since 1.6J predates `SignedMeterValueType`. Reported as the energy register it signs.
+    ///
+    /// Only `signed_meter_data` fits. 1.6J has nowhere for the signing or encoding method (an
+    /// OCMF or EDL record states both itself) nor for the public key, which a 1.6J CSMS has to
+    /// obtain out of band - `PublicKeyWithSignedMeterValue` has no effect here.
+    pub(super) fn signed_sampled_value(
+        signed: &SignedMeterValue,
+        context: ReadingContext,
+    ) -> SampledValueItem {
+        SampledValueItem {
+            context: Some(match context {
+                ReadingContext::SamplePeriodic => Context::SamplePeriodic,
+                ReadingContext::Trigger => Context::Trigger,
+                ReadingContext::TransactionBegin => Context::TransactionBegin,
+                ReadingContext::TransactionEnd => Context::TransactionEnd,
+            }),
+            format: Some(Format::SignedData),
+            location: None,
+            measurand: Some(Measurand::EnergyActiveImportRegister),
             phase: None,
             unit: None,
-            value,
+            value: signed.signed_meter_data.clone(),
         }
     }
 
@@ -2813,28 +3935,36 @@ pub(crate) mod ocpp_1_6 {
     /// docs. `C` defaults to nothing in particular; embedded/no_std callers construct with
     /// [`Self::with_clock`] and an RTC-backed `Clock`, while `std` callers keep using
     /// [`Self::new`] (sourcing the timestamp from [`crate::clock::SystemClock`]) unchanged.
+    ///
+    /// Holds the actor for the same reason the 2.x notifiers do: `MeterValuesSampledData` and
+    /// `StopTxnSampledData` are read per message, so a `ChangeConfiguration` narrowing either
+    /// takes effect on the next reading.
     pub struct Ocpp1_6TransactionNotifier<C> {
         client: OCPP1_6Client,
         connector_counts: Vec<usize>,
         csms_transaction_ids:
             BlockingMutex<CriticalSectionRawMutex, RefCell<BTreeMap<TransactionId, i64>>>,
         clock: C,
+        actor: ChargePointActor,
     }
 
     impl<C: crate::clock::Clock> Ocpp1_6TransactionNotifier<C> {
         /// Wraps `client`, capturing `connector_counts` (each EVSE's connector count, in
         /// `evse_id` order) for translating connector addresses, starting with no cached
-        /// CSMS-assigned transaction ids, and sourcing every wire timestamp from `clock`.
+        /// CSMS-assigned transaction ids, sourcing every wire timestamp from `clock` and every
+        /// reading's measurand selection from `actor`'s device model.
         pub fn with_clock(
             client: OCPP1_6Client,
             connector_counts: impl IntoIterator<Item = usize>,
             clock: C,
+            actor: ChargePointActor,
         ) -> Self {
             Self {
                 client,
                 connector_counts: connector_counts.into_iter().collect(),
                 csms_transaction_ids: BlockingMutex::new(RefCell::new(BTreeMap::new())),
                 clock,
+                actor,
             }
         }
     }
@@ -2843,13 +3973,14 @@ pub(crate) mod ocpp_1_6 {
     impl Ocpp1_6TransactionNotifier<crate::clock::SystemClock> {
         /// Wraps `client`, capturing `connector_counts` (each EVSE's connector count, in
         /// `evse_id` order) for translating connector addresses, starting with no cached
-        /// CSMS-assigned transaction ids, and sourcing every wire timestamp from
-        /// [`crate::clock::SystemClock`].
+        /// CSMS-assigned transaction ids, sourcing every wire timestamp from
+        /// [`crate::clock::SystemClock`] and its measurand selection from `actor`.
         pub fn new(
             client: OCPP1_6Client,
             connector_counts: impl IntoIterator<Item = usize>,
+            actor: ChargePointActor,
         ) -> Self {
-            Self::with_clock(client, connector_counts, crate::clock::SystemClock)
+            Self::with_clock(client, connector_counts, crate::clock::SystemClock, actor)
         }
     }
 
@@ -2857,13 +3988,19 @@ pub(crate) mod ocpp_1_6 {
     // produced from `self.clock` - see `crate::clock::is_synchronized`'s docs for the policy on
     // an unsynchronized reading (send it as-is, warn, never fabricate or drop it).
     mod with_clock {
-        use super::{Ocpp1_6TransactionNotifier, map_stop_reason, sampled_values};
+        use super::{
+            Ocpp1_6TransactionNotifier, build_meter_values, map_stop_reason, sampled_values,
+            signed_sampled_value,
+        };
         use crate::clock::{Clock, is_synchronized};
         use crate::id_tag::map_id_tag;
-        use crate::state::{Transaction, TransactionEventKind};
+        use crate::meter_values::{
+            ReadingContext, aligned_ended_measurands, signed_readings, transaction_event_measurands,
+        };
+        use crate::state::{Transaction, TransactionEventKind, TransactionUpdateReason};
         use crate::topology::flatten_ocpp_1_6_connector_id;
         use crate::transactions::{TransactionEventOutcome, TransactionNotifier};
-        use crate::wire::v16::common::MeterValueItem;
+        use crate::wire::v16::common::{Context, MeterValueItem, TransactionDataItem};
         use crate::wire::v16::{
             MeterValuesRequest, StartTransactionRequest, StopTransactionRequest,
         };
@@ -2922,10 +4059,18 @@ pub(crate) mod ocpp_1_6 {
                         "1.6J transaction timestamp sourced from an unsynchronized clock"
                     );
                 }
-                let meter_reading = transaction
+                // `meterStart`/`meterStop` are mandatory integers, so a reading this charge point
+                // never had is reported as `0` - the wire leaves no way to say "unknown". Both are
+                // the register at the exact start and stop points - see
+                // `Transaction::last_meter_sample` for the second.
+                let meter_start = transaction
+                    .start_meter_sample
+                    .as_ref()
+                    .map_or(0, |s| s.energy_wh);
+                let meter_stop = transaction
                     .last_meter_sample
-                    .map(|s| s.energy_wh)
-                    .unwrap_or(0);
+                    .as_ref()
+                    .map_or(0, |s| s.energy_wh);
 
                 match kind {
                     TransactionEventKind::Started => {
@@ -2934,8 +4079,10 @@ pub(crate) mod ocpp_1_6 {
                             .send_start_transaction(StartTransactionRequest {
                                 connector_id,
                                 id_tag: map_id_tag(transaction.id_token.as_ref()),
-                                meter_start: meter_reading,
-                                reservation_id: None,
+                                meter_start,
+                                // The reservation this session consumed, so the CSMS can close it
+                                // out against the transaction that used it.
+                                reservation_id: transaction.reservation_id,
                                 timestamp: now.into(),
                             })
                             .await?;
@@ -2951,22 +4098,64 @@ pub(crate) mod ocpp_1_6 {
                         ));
                     }
                     TransactionEventKind::Updated(_) => {
-                        let Some(sample) = transaction.last_meter_sample else {
+                        let Some(sample) = &transaction.last_meter_sample else {
                             // Nothing sampled yet - a `MeterValues` with no `sampledValue` at all
                             // isn't meaningful, unlike 2.1's `TransactionEvent`, which reports
                             // regardless (see `super::ocpp_2_1`'s `Updated` handling).
                             return Ok(TransactionEventOutcome::acknowledged());
                         };
+                        let mut meter_value = build_meter_values(
+                            sample,
+                            now,
+                            transaction_event_measurands(&self.actor, kind),
+                        );
+                        match kind {
+                            // F06: 1.6J's answer to a triggered report is this `MeterValues`,
+                            // and it says so on every reading it carries.
+                            TransactionEventKind::Updated(TransactionUpdateReason::Trigger) => {
+                                for item in &mut meter_value {
+                                    for value in &mut item.sampled_value {
+                                        value.context = Some(Context::Trigger);
+                                    }
+                                }
+                            }
+                            // A signed reading travels in 1.6J's one place for it, beside the
+                            // readings it signs - see `signed_sampled_value`.
+                            TransactionEventKind::Updated(
+                                TransactionUpdateReason::SignedDataReceived,
+                            ) => {
+                                if let Some((signed, _)) = signed_readings(&self.actor).signature(
+                                    sample,
+                                    ReadingContext::SamplePeriodic,
+                                    &transaction,
+                                ) {
+                                    let signed = signed_sampled_value(
+                                        signed,
+                                        ReadingContext::SamplePeriodic,
+                                    );
+                                    match meter_value.first_mut() {
+                                        Some(item) => item.sampled_value.push(signed),
+                                        None => meter_value.push(MeterValueItem {
+                                            timestamp: now.into(),
+                                            sampled_value: alloc::vec![signed],
+                                        }),
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
+                        if meter_value.is_empty() {
+                            // `MeterValuesSampledData` selects nothing this meter reports - the
+                            // same "no message rather than an empty one" rule as the 2.x adapters.
+                            return Ok(TransactionEventOutcome::acknowledged());
+                        }
                         let csms_transaction_id = self
                             .csms_transaction_ids
                             .lock(|cache| cache.borrow().get(&transaction.id).copied());
                         self.client
                             .send_meter_values(MeterValuesRequest {
                                 connector_id,
-                                meter_value: Vec::from([MeterValueItem {
-                                    sampled_value: sampled_values(sample),
-                                    timestamp: now.into(),
-                                }]),
+                                meter_value,
                                 transaction_id: csms_transaction_id,
                             })
                             .await?;
@@ -2981,14 +4170,70 @@ pub(crate) mod ocpp_1_6 {
                             // transaction), but there's nothing to stop without the CSMS's id.
                             return Ok(TransactionEventOutcome::acknowledged());
                         };
+                        let ended_measurands =
+                            transaction_event_measurands(&self.actor, TransactionEventKind::Ended);
+                        let aligned_measurands = aligned_ended_measurands(&self.actor);
+                        // J02 then E11.FR.08: the `StopTxnSampledData` and `StopTxnAlignedData`
+                        // readings in the order they were taken, then - while `SignReadings` is
+                        // set - the signed begin and end readings together. 1.6J has no
+                        // `meterValue` on `StartTransaction`, so the begin reading can only
+                        // travel here. Both share one entry stamped `now`: the signed records
+                        // carry the meter's own timestamps, which are what a transparency tool
+                        // checks.
+                        let signing = signed_readings(&self.actor);
+                        let signed: Vec<_> = [
+                            (
+                                transaction.start_meter_sample.as_ref(),
+                                ReadingContext::TransactionBegin,
+                            ),
+                            (
+                                transaction.last_meter_sample.as_ref(),
+                                ReadingContext::TransactionEnd,
+                            ),
+                        ]
+                        .into_iter()
+                        .filter_map(|(sample, context)| {
+                            let (signed, _) = signing.signature(sample?, context, &transaction)?;
+                            Some(signed_sampled_value(signed, context))
+                        })
+                        .collect();
+                        let mut transaction_data: Vec<_> = transaction
+                            .ended_meter_samples
+                            .iter()
+                            .map(|kept| {
+                                // A clock-aligned reading says so; a sampled one leaves `context`
+                                // at 1.6J's default, `Sample.Periodic`.
+                                let (measurands, context) = if kept.clock_aligned {
+                                    (aligned_measurands, Some(Context::SampleClock))
+                                } else {
+                                    (ended_measurands, None)
+                                };
+                                let mut sampled_value = sampled_values(&kept.sample, measurands);
+                                for value in &mut sampled_value {
+                                    value.context = context.clone();
+                                }
+                                TransactionDataItem {
+                                    sampled_value,
+                                    timestamp: kept.timestamp.into(),
+                                }
+                            })
+                            .filter(|item| !item.sampled_value.is_empty())
+                            .collect();
+                        if !signed.is_empty() {
+                            transaction_data.push(TransactionDataItem {
+                                sampled_value: signed,
+                                timestamp: now.into(),
+                            });
+                        }
                         let response = self
                             .client
                             .send_stop_transaction(StopTransactionRequest {
                                 id_tag: Some(map_id_tag(transaction.id_token.as_ref())),
-                                meter_stop: meter_reading,
+                                meter_stop,
                                 reason: transaction.stop_reason.map(map_stop_reason),
                                 timestamp: now.into(),
-                                transaction_data: None,
+                                transaction_data: (!transaction_data.is_empty())
+                                    .then_some(transaction_data),
                                 transaction_id: csms_transaction_id,
                             })
                             .await?;
@@ -3033,9 +4278,10 @@ pub(crate) mod ocpp_1_6 {
                 current_ma: Some(32_000),
                 voltage_v: Some(230),
                 soc_percent: Some(42),
+                ..Default::default()
             };
 
-            let values = sampled_values(sample);
+            let values = sampled_values(&sample, MeasurandSet::ALL);
 
             assert_eq!(values.len(), 5);
             assert!(values.iter().any(|value| value.measurand
@@ -3049,6 +4295,117 @@ pub(crate) mod ocpp_1_6 {
             );
         }
 
+        #[test]
+        fn phases_export_and_temperature_reach_the_wire_with_their_phase_location_and_unit() {
+            let sample = MeterSample {
+                energy_wh: 1_500,
+                energy_export_wh: Some(250),
+                phase_current_ma: [Some(16_000), Some(15_500), None],
+                phase_voltage_v: [Some(230), Some(231), Some(229)],
+                frequency_millihertz: Some(49_980),
+                temperature_c: Some(41),
+                ..Default::default()
+            };
+
+            let values = sampled_values(&sample, MeasurandSet::ALL);
+
+            let l2 = values
+                .iter()
+                .find(|value| {
+                    value.measurand == Some(Measurand::CurrentImport)
+                        && value.phase == Some(Phase::L2)
+                })
+                .expect("L2 current reported");
+            assert_eq!(l2.value, "15.5");
+            assert_eq!(l2.unit, Some(SampledValueItemUnit::A));
+            assert!(
+                !values.iter().any(|value| value.phase == Some(Phase::L3)),
+                "a phase the meter did not measure is omitted, not zeroed"
+            );
+            assert_eq!(
+                values
+                    .iter()
+                    .filter(|value| value.measurand == Some(Measurand::Voltage))
+                    .map(|value| value.phase.clone())
+                    .collect::<Vec<_>>(),
+                vec![Some(Phase::L1N), Some(Phase::L2N), Some(Phase::L3N)]
+            );
+            let temperature = values
+                .iter()
+                .find(|value| value.measurand == Some(Measurand::Temperature))
+                .expect("temperature reported on 1.6J");
+            assert_eq!(temperature.location, Some(Location::Body));
+            assert_eq!(temperature.unit, Some(SampledValueItemUnit::Celsius));
+            let frequency = values
+                .iter()
+                .find(|value| value.measurand == Some(Measurand::Frequency))
+                .expect("frequency reported");
+            assert_eq!(frequency.value, "49.98");
+            assert_eq!(frequency.unit, None, "1.6J has no Hz unit to state");
+            assert!(values.iter().any(|value| value.measurand
+                == Some(Measurand::EnergyActiveExportRegister)
+                && value.value == "250"
+                && value.unit == Some(SampledValueItemUnit::Wh)));
+        }
+
+        #[test]
+        fn a_sample_reports_only_the_configured_measurands() {
+            let sample = MeterSample {
+                energy_wh: 1_500,
+                power_w: Some(7_400),
+                phase_current_ma: [Some(16_000), Some(16_000), Some(16_000)],
+                temperature_c: Some(41),
+                ..Default::default()
+            };
+
+            let values = build_meter_values(
+                &sample,
+                chrono::DateTime::UNIX_EPOCH,
+                MeasurandSet::from_member_list("Energy.Active.Import.Register,Temperature"),
+            );
+
+            assert_eq!(values.len(), 1);
+            assert_eq!(
+                values[0]
+                    .sampled_value
+                    .iter()
+                    .map(|value| value.measurand.clone())
+                    .collect::<Vec<_>>(),
+                vec![
+                    Some(Measurand::EnergyActiveImportRegister),
+                    Some(Measurand::Temperature)
+                ]
+            );
+            assert!(
+                build_meter_values(
+                    &sample,
+                    chrono::DateTime::UNIX_EPOCH,
+                    MeasurandSet::from_member_list("SoC"),
+                )
+                .is_empty(),
+                "nothing selected that the meter reports sends no MeterValueItem at all"
+            );
+        }
+
+        /// 1.6J's convention for a signed reading: the record itself as the value, in the
+        /// `SignedData` format, under the energy register it signs.
+        #[test]
+        fn a_signed_reading_is_sent_as_signed_data() {
+            let signed = SignedMeterValue {
+                signed_meter_data: "OCMF|{\"RD\":[{\"RV\":9.0}]}".into(),
+                signing_method: "ECDSA-secp256r1-SHA256".into(),
+                encoding_method: "OCMF".into(),
+                public_key: "MFkwEwYHKoZIzj0CAQ".into(),
+            };
+
+            let value = signed_sampled_value(&signed, ReadingContext::TransactionEnd);
+
+            assert_eq!(value.format, Some(Format::SignedData));
+            assert_eq!(value.context, Some(Context::TransactionEnd));
+            assert_eq!(value.measurand, Some(Measurand::EnergyActiveImportRegister));
+            assert_eq!(value.value, signed.signed_meter_data);
+        }
+
         #[test]
         fn ocpp1_6_transaction_notifier_can_be_constructed_from_a_client_and_topology() {
             // Compile-level check that `Ocpp1_6TransactionNotifier::new` accepts the same
@@ -3058,6 +4415,7 @@ pub(crate) mod ocpp_1_6 {
                 F: Fn(
                     OCPP1_6Client,
                     [usize; 2],
+                    ChargePointActor,
                 ) -> Ocpp1_6TransactionNotifier<crate::clock::SystemClock>,
             >(
                 _: F,
@@ -3082,7 +4440,12 @@ pub(crate) mod ocpp_1_6 {
             // actual timestamp behavior is exercised end-to-end via
             // `with_clock`'s tests (only reachable with a live `OCPP1_6Client`).
             fn assert_ctor<
-                F: Fn(OCPP1_6Client, [usize; 2], FixedClock) -> Ocpp1_6TransactionNotifier<FixedClock>,
+                F: Fn(
+                    OCPP1_6Client,
+                    [usize; 2],
+                    FixedClock,
+                    ChargePointActor,
+                ) -> Ocpp1_6TransactionNotifier<FixedClock>,
             >(
                 _: F,
             ) {
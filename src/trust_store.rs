@@ -62,13 +62,58 @@
 //! function the integrator's own connection-setup code calls rather than something wired
 //! automatically - this crate does not assume every charge point wants a live TLS trust update
 //! pushed through immediately, only that the primitive to do so exists and rolls back safely.
+//!
+//! # Reporting what a failed handshake amounts to
+//!
+//! OCPP names four security events for a CSMS connection that fails in TLS rather than in the
+//! network: `InvalidCsmsCertificate` (the server certificate did not validate - expired, issued
+//! for another host, by an unknown CA), `CsmsFailedToAuthenticate` (it validated, but the server
+//! could not prove it holds the certificate's key), `InvalidTlsVersion` and
+//! `InvalidTlsCipherSuite` (the server only speaks a protocol version or cipher suite this charge
+//! point will not). Two pieces tell them apart:
+//!
+//! - [`CsmsCertVerifier`], the `ServerCertVerifier` every configuration this crate builds
+//!   ([`server_only_client_config`], [`crate::mutual_tls::client_config`]) verifies the CSMS
+//!   with. It delegates to rustls's own WebPKI verifier and changes one thing: a handshake
+//!   signature that fails to verify comes back marked as such, because rustls reports it with the
+//!   same `BadSignature` a badly-signed certificate gets, and the two are different events.
+//! - [`tls_failure_event`], the handshake observer: given the error a dial failed with, it finds
+//!   the rustls error underneath the WebSocket and I/O layers and says which event, if any, it
+//!   amounts to. Errors that are not TLS failures at all - a refused TCP connection, an HTTP 401
+//!   on the upgrade - amount to none, and so does a TLS failure that names no cause: a server's
+//!   generic `handshake_failure` alert fails the dial like any other error rather than being
+//!   guessed into a cipher-suite event. `InvalidTlsCipherSuite` is only raised where rustls itself
+//!   found no suite in common, or caught the server choosing one it may not.
+//!
+//! [`crate::network_switch::ConnectionTarget`] runs every failed redial through the observer and
+//! raises the result through [`crate::security::report_security_event`], once per kind of failure
+//! until a dial succeeds again, so a CSMS serving an expired certificate does not flood the
+//! security log once per back-off. The initial dial in `connect_and_setup` happens before there
+//! is an actor to raise anything on; its error carries the classified event instead
+//! (`ConnectAndSetupError::security_event`) for the caller to raise once a session is up.
+//! A configuration an integrator built without [`CsmsCertVerifier`] is still classified - it only
+//! loses the `CsmsFailedToAuthenticate` distinction, which then reads as `InvalidCsmsCertificate`.
 
+use alloc::string::ToString;
 use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
 
-use ocpp_client::rustls::{ClientConfig, RootCertStore};
+use ocpp_client::rustls::client::WantsClientCert;
+use ocpp_client::rustls::client::WebPkiServerVerifier;
+use ocpp_client::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use ocpp_client::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use ocpp_client::rustls::{
+    AlertDescription, CertificateError, ClientConfig, ConfigBuilder, DigitallySignedStruct,
+    DistinguishedName, Error as TlsError, OtherError, PeerIncompatible, PeerMisbehaved,
+    RootCertStore, SignatureScheme,
+};
 
 use crate::hardware::{CertificateStore, CertificateUse};
 use crate::mutual_tls::decode_pem_certificates;
+use crate::state::{SecurityEvent, SecurityEventType};
 
 /// Builds a [`RootCertStore`] from every [`CertificateUse::CsmsRoot`] chain `certificate_store`
 /// holds.
@@ -107,11 +152,200 @@ pub async fn build_root_cert_store<C: CertificateStore + Sync>(
 /// this reason (see that function's docs); this crate does not duplicate that assembly for a
 /// second, client-cert-bearing config.
 pub fn server_only_client_config(root_store: Arc<RootCertStore>) -> Arc<ClientConfig> {
-    Arc::new(
-        ClientConfig::builder()
-            .with_root_certificates(root_store)
-            .with_no_client_auth(),
-    )
+    Arc::new(csms_verifying_config_builder(root_store).with_no_client_auth())
+}
+
+/// A `ClientConfig` builder that verifies the CSMS against `root_store` through a
+/// [`CsmsCertVerifier`], for [`server_only_client_config`] and
+/// [`crate::mutual_tls::client_config`] to finish with their own client-certificate choice.
+pub(crate) fn csms_verifying_config_builder(
+    root_store: Arc<RootCertStore>,
+) -> ConfigBuilder<ClientConfig, WantsClientCert> {
+    match WebPkiServerVerifier::builder(root_store.clone()).build() {
+        Ok(verifier) => ClientConfig::builder()
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(CsmsCertVerifier::new(verifier))),
+        // Only an empty store fails to build a verifier. rustls's own configuration for one
+        // accepts no server at all, which is the fail-safe the module docs ask for, and every
+        // failure it reports is a certificate one `tls_failure_event` already classifies.
+        Err(_) => ClientConfig::builder().with_root_certificates(root_store),
+    }
+}
+
+/// A [`ServerCertVerifier`] that verifies the CSMS exactly as the verifier it wraps does, but
+/// reports a handshake signature that fails to verify in a form [`tls_failure_event`] can tell
+/// apart from an invalid certificate - see the module docs.
+///
+/// The one visible side effect: rustls answers such a failure with a `certificate_unknown`
+/// alert rather than `decrypt_error`, since the marked error is a certificate error of rustls's
+/// `Other` kind. The connection fails the same way either way.
+#[derive(Debug)]
+pub struct CsmsCertVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+}
+
+impl CsmsCertVerifier {
+    /// Wraps `inner`, typically a `WebPkiServerVerifier` built from [`build_root_cert_store`]'s
+    /// result.
+    pub fn new(inner: Arc<dyn ServerCertVerifier>) -> Self {
+        Self { inner }
+    }
+}
+
+impl ServerCertVerifier for CsmsCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, TlsError> {
+        self.inner
+            .verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        self.inner
+            .verify_tls12_signature(message, cert, dss)
+            .map_err(signature_rejected)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, TlsError> {
+        self.inner
+            .verify_tls13_signature(message, cert, dss)
+            .map_err(signature_rejected)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        self.inner.requires_raw_public_keys()
+    }
+
+    fn root_hint_subjects(&self) -> Option<&[DistinguishedName]> {
+        self.inner.root_hint_subjects()
+    }
+}
+
+/// The CSMS's handshake signature did not verify against the certificate it presented: it does
+/// not hold that certificate's key. Carried inside the rustls error [`CsmsCertVerifier`] returns.
+#[derive(Debug)]
+struct SignatureRejected(TlsError);
+
+impl fmt::Display for SignatureRejected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the CSMS's handshake signature does not match its certificate: {}",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for SignatureRejected {}
+
+fn signature_rejected(error: TlsError) -> TlsError {
+    TlsError::InvalidCertificate(CertificateError::Other(OtherError(Arc::new(
+        SignatureRejected(error),
+    ))))
+}
+
+/// OCPP's `techInfo` bound, in bytes.
+const MAX_TECH_INFO_BYTES: usize = 255;
+
+/// The security event a failed CSMS dial amounts to, if it failed in TLS - see the module docs
+/// for the four events and for why `error` is searched rather than matched.
+///
+/// `techInfo` is rustls's own description of the failure (the certificate's validity window, the
+/// name it was issued for), cut to OCPP's 255 bytes.
+pub fn tls_failure_event(error: &(dyn core::error::Error + 'static)) -> Option<SecurityEvent> {
+    let mut next = Some(error);
+    while let Some(current) = next {
+        if let Some(tls) = as_tls_error(current) {
+            let event_type = tls_failure_type(tls)?;
+            let mut tech_info = match signature_rejection(tls) {
+                Some(rejected) => rejected.to_string(),
+                None => tls.to_string(),
+            };
+            if tech_info.len() > MAX_TECH_INFO_BYTES {
+                let mut end = MAX_TECH_INFO_BYTES;
+                while !tech_info.is_char_boundary(end) {
+                    end -= 1;
+                }
+                tech_info.truncate(end);
+            }
+            return Some(SecurityEvent {
+                event_type,
+                tech_info: Some(tech_info),
+            });
+        }
+        next = current.source();
+    }
+    None
+}
+
+/// `error` as a rustls error, looking inside an `io::Error` too: that is how `tokio-rustls`
+/// hands one up, and `io::Error::source` skips past it.
+fn as_tls_error<'a>(error: &'a (dyn core::error::Error + 'static)) -> Option<&'a TlsError> {
+    error.downcast_ref::<TlsError>().or_else(|| {
+        error
+            .downcast_ref::<std::io::Error>()?
+            .get_ref()?
+            .downcast_ref::<TlsError>()
+    })
+}
+
+fn signature_rejection(error: &TlsError) -> Option<&SignatureRejected> {
+    match error {
+        TlsError::InvalidCertificate(CertificateError::Other(other)) => {
+            other.0.downcast_ref::<SignatureRejected>()
+        }
+        _ => None,
+    }
+}
+
+fn tls_failure_type(error: &TlsError) -> Option<SecurityEventType> {
+    if signature_rejection(error).is_some() {
+        return Some(SecurityEventType::CsmsFailedToAuthenticate);
+    }
+    match error {
+        TlsError::InvalidCertificate(_) | TlsError::InvalidCertRevocationList(_) => {
+            Some(SecurityEventType::InvalidCsmsCertificate)
+        }
+        TlsError::PeerIncompatible(
+            PeerIncompatible::ServerDoesNotSupportTls12Or13
+            | PeerIncompatible::ServerTlsVersionIsDisabledByOurConfig
+            | PeerIncompatible::SupportedVersionsExtensionRequired
+            | PeerIncompatible::Tls12NotOffered
+            | PeerIncompatible::Tls12NotOfferedOrEnabled,
+        )
+        | TlsError::AlertReceived(AlertDescription::ProtocolVersion) => {
+            Some(SecurityEventType::InvalidTlsVersion)
+        }
+        TlsError::PeerIncompatible(PeerIncompatible::NoCipherSuitesInCommon)
+        | TlsError::PeerMisbehaved(
+            PeerMisbehaved::SelectedUnofferedCipherSuite
+            | PeerMisbehaved::SelectedUnusableCipherSuiteForVersion,
+        ) => Some(SecurityEventType::InvalidTlsCipherSuite),
+        // `handshake_failure` and `insufficient_security` say only that the server gave up, not
+        // over what - a cipher suite, a curve, a missing client certificate. Naming one of the
+        // specific events for them would be a guess, so they fail the dial like any other TLS
+        // error.
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -220,6 +454,62 @@ Nw9pJl5k7A/A\n\
         assert_eq!(root_store.len(), 0);
     }
 
+    #[test]
+    fn a_tls_error_is_found_under_the_websocket_and_io_layers_that_carry_it() {
+        // The shape a failed dial's error has by the time it reaches a reconnector.
+        let error: ocpp_client::TransportError = Box::new(
+            tokio_tungstenite::tungstenite::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                TlsError::InvalidCertificate(CertificateError::UnknownIssuer),
+            )),
+        );
+
+        let event = tls_failure_event(&*error).expect("a certificate failure is an event");
+
+        assert_eq!(event.event_type, SecurityEventType::InvalidCsmsCertificate);
+    }
+
+    #[test]
+    fn a_failure_outside_tls_is_no_security_event() {
+        let refused: ocpp_client::TransportError =
+            Box::new(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+        // Nor is every TLS error: a connection cut mid-handshake says nothing about the CSMS.
+        let cut: ocpp_client::TransportError = Box::new(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            TlsError::AlertReceived(AlertDescription::CloseNotify),
+        ));
+
+        assert_eq!(tls_failure_event(&*refused), None);
+        assert_eq!(tls_failure_event(&*cut), None);
+    }
+
+    #[test]
+    fn only_a_cipher_suite_failure_rustls_itself_identifies_is_an_invalid_cipher_suite() {
+        let tls = |error: TlsError| -> ocpp_client::TransportError {
+            Box::new(std::io::Error::new(std::io::ErrorKind::InvalidData, error))
+        };
+
+        for error in [
+            TlsError::PeerIncompatible(PeerIncompatible::NoCipherSuitesInCommon),
+            TlsError::PeerMisbehaved(PeerMisbehaved::SelectedUnofferedCipherSuite),
+        ] {
+            assert_eq!(
+                tls_failure_event(&*tls(error)).map(|event| event.event_type),
+                Some(SecurityEventType::InvalidTlsCipherSuite)
+            );
+        }
+        // A generic alert names no cause, so it is not reported as one.
+        for alert in [
+            AlertDescription::HandshakeFailure,
+            AlertDescription::InsufficientSecurity,
+        ] {
+            assert_eq!(
+                tls_failure_event(&*tls(TlsError::AlertReceived(alert))),
+                None
+            );
+        }
+    }
+
     #[test]
     fn server_only_config_carries_no_client_certificate() {
         let config = server_only_client_config(Arc::new(RootCertStore::empty()));
@@ -84,7 +84,8 @@ pub enum SetMonitorOutcome {
     /// monitor's `value` (its interval in seconds) wasn't positive; or the store is at its
     /// configured maximum and this monitor doesn't replace an existing one (see
     /// [`crate::state::SetMonitorRejection::TooManyMonitors`] - OCPP's `SetMonitoringStatusEnum`
-    /// has no separate "out of room" status, so this is the closest honest fit).
+    /// has no separate "out of room" status, so this is the closest honest fit); or the
+    /// [`crate::command_policy`] refused the whole request.
     Rejected,
     /// A monitor already watches this exact `(component, variable, monitor_type)` combination,
     /// and the request named no id to replace it with.
@@ -101,6 +102,9 @@ pub async fn handle_set_variable_monitoring(
     actor: &ChargePointActor,
     requests: Vec<SetMonitorRequest>,
 ) -> Vec<SetMonitorOutcome> {
+    if crate::command_policy::refuses(actor, "SetVariableMonitoring") {
+        return alloc::vec![SetMonitorOutcome::Rejected; requests.len()];
+    }
     let mut outcomes = Vec::with_capacity(requests.len());
     for request in requests {
         outcomes.push(resolve_and_apply_set(actor, request).await);
@@ -177,15 +181,16 @@ async fn resolve_and_apply_set(
     SetMonitorOutcome::Accepted { id, severity }
 }
 
-/// The outcome of clearing one monitor by id, matching OCPP's `ClearMonitoringStatusEnum` (minus
-/// `Rejected`, which this crate never returns - clearing an installed monitor can't fail once
-/// it's been found).
+/// The outcome of clearing one monitor by id, matching OCPP's `ClearMonitoringStatusEnum`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClearMonitorOutcome {
     /// The monitor was removed.
     Accepted,
     /// No monitor with this id was installed.
     NotFound,
+    /// The [`crate::command_policy`] refused the whole request. Clearing an installed monitor
+    /// can't otherwise fail once it's been found.
+    Rejected,
 }
 
 /// Handles a CSMS-initiated `ClearVariableMonitoring` request: resolves and clears every id
@@ -197,6 +202,12 @@ pub async fn handle_clear_variable_monitoring(
     actor: &ChargePointActor,
     ids: Vec<VariableMonitorId>,
 ) -> Vec<(VariableMonitorId, ClearMonitorOutcome)> {
+    if crate::command_policy::refuses(actor, "ClearVariableMonitoring") {
+        return ids
+            .into_iter()
+            .map(|id| (id, ClearMonitorOutcome::Rejected))
+            .collect();
+    }
     let mut outcomes = Vec::with_capacity(ids.len());
     for id in ids {
         let installed = actor.state().variable_monitors.get(id).is_some();
@@ -235,7 +246,8 @@ pub async fn handle_set_monitoring_base(actor: &ChargePointActor, base: Monitori
 pub enum SetMonitoringLevelOutcome {
     /// `severity` was in OCPP's `0..=9` range and is now [`crate::state::VariableMonitorStore::level`].
     Accepted,
-    /// `severity` was outside OCPP's `0..=9` range.
+    /// `severity` was outside OCPP's `0..=9` range, or the [`crate::command_policy`] refused the
+    /// command.
     Rejected,
 }
 
@@ -251,6 +263,9 @@ pub async fn handle_set_monitoring_level(
     actor: &ChargePointActor,
     severity: i64,
 ) -> SetMonitoringLevelOutcome {
+    if crate::command_policy::refuses(actor, "SetMonitoringLevel") {
+        return SetMonitoringLevelOutcome::Rejected;
+    }
     let Ok(severity) = u8::try_from(severity) else {
         return SetMonitoringLevelOutcome::Rejected;
     };
@@ -335,6 +350,8 @@ pub enum MonitoringReportOutcome {
     /// The filter (criteria and/or component/variable list) matched no installed monitor at all -
     /// including the degenerate case of no filter at all against an empty store.
     EmptyResultSet,
+    /// The [`crate::command_policy`] refused the command.
+    Rejected,
 }
 
 /// Whether monitor `component`/`variable`/`monitor_type` matches `criteria`/`component_variable` -
@@ -416,6 +433,9 @@ pub fn handle_get_monitoring_report(
     criteria: &[MonitoringCriterion],
     component_variable: &[ReportComponentVariable],
 ) -> MonitoringReportOutcome {
+    if crate::command_policy::refuses(actor, "GetMonitoringReport") {
+        return MonitoringReportOutcome::Rejected;
+    }
     let state = actor.state();
     let entries = filter_monitoring_entries(&state.variable_monitors, criteria, component_variable);
     if entries.is_empty() {
@@ -1607,6 +1627,7 @@ mod ocpp_2_1 {
         match outcome {
             ClearMonitorOutcome::Accepted => ClearMonitoringStatusEnum::Accepted,
             ClearMonitorOutcome::NotFound => ClearMonitoringStatusEnum::NotFound,
+            ClearMonitorOutcome::Rejected => ClearMonitoringStatusEnum::Rejected,
         }
     }
 
@@ -1680,9 +1701,11 @@ mod ocpp_2_1 {
                         &request,
                     ) {
                         return Err(crate::message_limits::ocpp_2_1_too_large(
+                            &actor,
                             "SetVariableMonitoring",
                             violation,
-                        ));
+                        )
+                        .await);
                     }
                     let parsed: Vec<SetMonitorRequest> = request
                         .set_monitoring_data
@@ -1748,6 +1771,15 @@ mod ocpp_2_1 {
             self.on_set_monitoring_base(move |request, _client| {
                 let actor = actor.clone();
                 async move {
+                    // Screened here: applying a base can't otherwise fail, so
+                    // `handle_set_monitoring_base` has no outcome to carry a refusal in.
+                    if crate::command_policy::refuses(&actor, "SetMonitoringBase") {
+                        return Ok(SetMonitoringBaseResponse {
+                            custom_data: None,
+                            status: GenericDeviceModelStatusEnum::Rejected,
+                            status_info: None,
+                        });
+                    }
                     handle_set_monitoring_base(
                         &actor,
                         map_monitoring_base(&request.monitoring_base),
@@ -1813,6 +1845,7 @@ mod ocpp_2_1 {
         match outcome {
             MonitoringReportOutcome::Accepted(_) => GenericDeviceModelStatusEnum::Accepted,
             MonitoringReportOutcome::EmptyResultSet => GenericDeviceModelStatusEnum::EmptyResultSet,
+            MonitoringReportOutcome::Rejected => GenericDeviceModelStatusEnum::Rejected,
         }
     }
 
@@ -2391,6 +2424,7 @@ mod ocpp_2_0_1 {
         match outcome {
             ClearMonitorOutcome::Accepted => ClearMonitoringStatusEnum::Accepted,
             ClearMonitorOutcome::NotFound => ClearMonitoringStatusEnum::NotFound,
+            ClearMonitorOutcome::Rejected => ClearMonitoringStatusEnum::Rejected,
         }
     }
 
@@ -2459,9 +2493,11 @@ mod ocpp_2_0_1 {
                         &request,
                     ) {
                         return Err(crate::message_limits::ocpp_2_0_1_too_large(
+                            &actor,
                             "SetVariableMonitoring",
                             violation,
-                        ));
+                        )
+                        .await);
                     }
                     let parsed: Vec<SetMonitorRequest> = request
                         .set_monitoring_data
@@ -2527,6 +2563,15 @@ mod ocpp_2_0_1 {
             self.on_set_monitoring_base(move |request, _client| {
                 let actor = actor.clone();
                 async move {
+                    // Screened here: applying a base can't otherwise fail, so
+                    // `handle_set_monitoring_base` has no outcome to carry a refusal in.
+                    if crate::command_policy::refuses(&actor, "SetMonitoringBase") {
+                        return Ok(SetMonitoringBaseResponse {
+                            custom_data: None,
+                            status: GenericDeviceModelStatusEnum::Rejected,
+                            status_info: None,
+                        });
+                    }
                     handle_set_monitoring_base(
                         &actor,
                         map_monitoring_base(&request.monitoring_base),
@@ -2592,6 +2637,7 @@ mod ocpp_2_0_1 {
         match outcome {
             MonitoringReportOutcome::Accepted(_) => GenericDeviceModelStatusEnum::Accepted,
             MonitoringReportOutcome::EmptyResultSet => GenericDeviceModelStatusEnum::EmptyResultSet,
+            MonitoringReportOutcome::Rejected => GenericDeviceModelStatusEnum::Rejected,
         }
     }
 
@@ -7,10 +7,11 @@ use ocpp_charge_point::connect_and_setup;
 use ocpp_charge_point::executor::TokioExecutor;
 use ocpp_charge_point::hardware::{ChargePoint, Connector, Evse};
 use ocpp_charge_point::provisioning::TokioBackoff;
-use ocpp_charge_point::state::RegistrationStatus;
+use ocpp_charge_point::state::{RegistrationStatus, SecurityEventType};
 use serde_json::{Value, json};
 use std::sync::Arc;
-use tokio::net::TcpListener;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::WebSocketStream;
 use tokio_tungstenite::tungstenite::Message;
 
 struct TestChargePoint {
@@ -90,34 +91,59 @@ impl Connector for TestConnector {
     }
 }
 
-#[tokio::test]
-async fn connect_and_setup_completes_boot_notification_over_a_real_websocket() {
-    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
-    let addr = listener.local_addr().unwrap();
-
-    let server = tokio::spawn(async move {
+/// Accepts one connection negotiating `subprotocol`, returning it and its first frame.
+///
+/// A handshake that does not offer `subprotocol` is refused, as a CSMS that does not speak the
+/// version would, and the next one is awaited: `connect_and_setup` offers its versions one at a
+/// time, so only the accepted dial becomes a session and the CSMS never sees a second connection
+/// under the station's identity at startup.
+async fn accept_session(
+    listener: &TcpListener,
+    subprotocol: &'static str,
+) -> (WebSocketStream<TcpStream>, String) {
+    let mut ws = loop {
         let (tcp, _) = listener.accept().await.unwrap();
-        let mut ws = tokio_tungstenite::accept_hdr_async(
+        let accepted = tokio_tungstenite::accept_hdr_async(
             tcp,
-            // The closure's `Result` type is dictated by tungstenite's `Callback` trait, which
-            // we don't control - boxing the `Err` variant would require wrapping every call
-            // site instead of just this test helper, for no behavioural benefit.
             #[allow(clippy::result_large_err)]
-            |_req: &tokio_tungstenite::tungstenite::handshake::server::Request,
+            |req: &tokio_tungstenite::tungstenite::handshake::server::Request,
              mut response: tokio_tungstenite::tungstenite::handshake::server::Response| {
+                let offered = req
+                    .headers()
+                    .get("Sec-WebSocket-Protocol")
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default();
+                if !offered.split(',').any(|offer| offer.trim() == subprotocol) {
+                    return Err(tokio_tungstenite::tungstenite::http::Response::builder()
+                        .status(400)
+                        .body(None)
+                        .unwrap());
+                }
                 response
                     .headers_mut()
-                    .insert("Sec-WebSocket-Protocol", "ocpp2.1".parse().unwrap());
+                    .insert("Sec-WebSocket-Protocol", subprotocol.parse().unwrap());
                 Ok(response)
             },
         )
-        .await
-        .unwrap();
+        .await;
+        if let Ok(ws) = accepted {
+            break ws;
+        }
+    };
+    let frame = match ws.next().await.unwrap().unwrap() {
+        Message::Text(text) => text.to_string(),
+        other => panic!("expected a text frame, got {other:?}"),
+    };
+    (ws, frame)
+}
 
-        let frame = match ws.next().await.unwrap().unwrap() {
-            Message::Text(text) => text.to_string(),
-            other => panic!("expected a text frame, got {other:?}"),
-        };
+#[tokio::test]
+async fn connect_and_setup_completes_boot_notification_over_a_real_websocket() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let server = tokio::spawn(async move {
+        let (mut ws, frame) = accept_session(&listener, "ocpp2.1").await;
         let call: Value = serde_json::from_str(&frame).unwrap();
         assert_eq!(call[2], "BootNotification");
         assert_eq!(call[3]["chargingStation"]["vendorName"], "Acme");
@@ -163,7 +189,8 @@ async fn connect_and_setup_completes_boot_notification_over_a_real_websocket() {
 }
 
 /// A9/A1's payoff, and the test that used to assert the opposite: negotiating **1.6J** now runs a
-/// real 1.6J session rather than being refused with `UnsupportedNegotiatedVersion`.
+/// real 1.6J session rather than being refused with `UnsupportedNegotiatedVersion`. The CSMS
+/// refuses the 2.1 and 2.0.1 offers made ahead of it.
 ///
 /// The mock CSMS answers 1.6J's own `BootNotification` shape - `chargePointVendor`/
 /// `chargePointModel` flattened at the top level, where 2.x nests them under `chargingStation` -
@@ -175,25 +202,7 @@ async fn a_negotiated_1_6j_connection_runs_a_1_6j_session() {
     let addr = listener.local_addr().unwrap();
 
     let server = tokio::spawn(async move {
-        let (tcp, _) = listener.accept().await.unwrap();
-        let mut ws = tokio_tungstenite::accept_hdr_async(
-            tcp,
-            #[allow(clippy::result_large_err)]
-            |_req: &tokio_tungstenite::tungstenite::handshake::server::Request,
-             mut response: tokio_tungstenite::tungstenite::handshake::server::Response| {
-                response
-                    .headers_mut()
-                    .insert("Sec-WebSocket-Protocol", "ocpp1.6".parse().unwrap());
-                Ok(response)
-            },
-        )
-        .await
-        .unwrap();
-
-        let frame = match ws.next().await.unwrap().unwrap() {
-            Message::Text(text) => text.to_string(),
-            other => panic!("expected a text frame, got {other:?}"),
-        };
+        let (mut ws, frame) = accept_session(&listener, "ocpp1.6").await;
         let call: Value = serde_json::from_str(&frame).unwrap();
         assert_eq!(call[2], "BootNotification");
         // 1.6J's flat shape, not 2.x's nested `chargingStation` - the version-specific
@@ -446,3 +455,128 @@ async fn an_unhandled_action_is_answered_with_a_not_implemented_call_error() {
 
     server.await.unwrap();
 }
+
+/// Offering every version, the first connection is deduplicated like every redial
+/// (`crate::call_dedup`): a CSMS reusing a message id for a different `GetVariables` gets the
+/// first answer again, and the reuse is reported as `AttemptedReplayAttacks`.
+#[tokio::test]
+async fn a_reused_call_id_on_the_first_connection_is_answered_from_cache() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (ready_tx, ready_rx) = tokio::sync::oneshot::channel::<()>();
+
+    let server = tokio::spawn(async move {
+        let (mut ws, frame) = accept_session(&listener, "ocpp2.1").await;
+        let boot: Value = serde_json::from_str(&frame).unwrap();
+        assert_eq!(boot[2], "BootNotification");
+        ws.send(Message::text(
+            serde_json::to_string(&json!([
+                3,
+                boot[1],
+                {
+                    "currentTime": "2024-01-01T00:00:00Z",
+                    "interval": 300,
+                    "status": "Accepted"
+                }
+            ]))
+            .unwrap(),
+        ))
+        .await
+        .unwrap();
+        // Only once `setup` has returned is there an actor to report the replay to.
+        ready_rx.await.unwrap();
+
+        let mut answers = Vec::new();
+        for variable in ["HeartbeatInterval", "NetworkConfigurationPriority"] {
+            ws.send(Message::text(
+                serde_json::to_string(&json!([
+                    2,
+                    "reused-1",
+                    "GetVariables",
+                    {
+                        "getVariableData": [{
+                            "component": { "name": "OCPPCommCtrlr" },
+                            "variable": { "name": variable }
+                        }]
+                    }
+                ]))
+                .unwrap(),
+            ))
+            .await
+            .unwrap();
+            let answer = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+                loop {
+                    let frame = match ws.next().await.unwrap().unwrap() {
+                        Message::Text(text) => text.to_string(),
+                        _ => continue,
+                    };
+                    let message: Value = serde_json::from_str(&frame).unwrap();
+                    if message[0] == 2 {
+                        ws.send(Message::text(
+                            serde_json::to_string(&json!([3, message[1], {}])).unwrap(),
+                        ))
+                        .await
+                        .unwrap();
+                        continue;
+                    }
+                    if message[1] == "reused-1" {
+                        break message;
+                    }
+                }
+            })
+            .await
+            .expect("the charge point never answered GetVariables");
+            answers.push(answer);
+        }
+        assert_eq!(
+            answers[0], answers[1],
+            "the reused id must get the first answer again"
+        );
+        assert_eq!(
+            answers[0][2]["getVariableResult"][0]["variable"]["name"],
+            "HeartbeatInterval"
+        );
+    });
+
+    let runtime = connect_and_setup(
+        TestChargePoint {
+            evses: [TestEvse {
+                connectors: [TestConnector],
+            }],
+        },
+        &format!("ws://{addr}"),
+        None,
+        None,
+        None,
+        TokioExecutor,
+        TokioBackoff,
+    )
+    .await
+    .unwrap();
+    let mut security_events = runtime.subscribe_security_events();
+    ready_tx.send(()).unwrap();
+
+    tokio::time::timeout(std::time::Duration::from_secs(10), server)
+        .await
+        .expect("the CSMS side of the test timed out")
+        .unwrap();
+
+    let replay = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            let event = security_events.recv().await.unwrap();
+            if event.event_type == SecurityEventType::AttemptedReplayAttacks {
+                break event;
+            }
+        }
+    })
+    .await
+    .expect("the reused id was never reported");
+    assert!(
+        replay
+            .tech_info
+            .as_deref()
+            .is_some_and(|info| info.contains("reused-1")),
+        "{:?}",
+        replay.tech_info
+    );
+}
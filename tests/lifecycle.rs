@@ -26,6 +26,7 @@ use std::sync::Arc;
 
 struct TestChargePoint {
     evses: [TestEvse; 1],
+    capabilities: Capabilities,
 }
 struct TestEvse {
     connectors: [TestConnector; 1],
@@ -45,7 +46,7 @@ impl ChargePoint<TestEvse, TestConnector> for TestChargePoint {
         &self.evses
     }
     fn capabilities(&self) -> Capabilities {
-        Capabilities::default()
+        self.capabilities
     }
     async fn start(
         self: Arc<Self>,
@@ -92,6 +93,7 @@ fn charge_point() -> TestChargePoint {
         evses: [TestEvse {
             connectors: [TestConnector],
         }],
+        capabilities: Capabilities::default(),
     }
 }
 
@@ -326,3 +328,103 @@ async fn a_csms_that_disappears_mid_transaction_loses_nothing() {
     );
     assert!(before > 0, "the CSMS should have seen the session start");
 }
+
+/// What a 1.6J CSMS bills from: `StartTransaction` quotes the reservation the session consumed,
+/// and `meterStart`/`meterStop` are the register at the exact start and stop points - including
+/// the energy counted while the EV had paused, which the last `Charging` reading misses.
+#[tokio::test]
+async fn a_1_6j_transaction_record_carries_its_reservation_and_the_register_at_each_end() {
+    let csms = MockCsms::start(
+        "ocpp1.6",
+        vec![
+            (
+                "BootNotification",
+                serde_json::json!({
+                    "currentTime": "2026-01-01T00:00:00Z",
+                    "interval": 300,
+                    "status": "Accepted"
+                }),
+            ),
+            (
+                "Authorize",
+                serde_json::json!({ "idTagInfo": { "status": "Accepted" } }),
+            ),
+            (
+                "StartTransaction",
+                serde_json::json!({ "transactionId": 42, "idTagInfo": { "status": "Accepted" } }),
+            ),
+        ],
+    )
+    .await;
+    let runtime = connect_and_setup(
+        TestChargePoint {
+            capabilities: Capabilities::default().with_reservation(true),
+            ..charge_point()
+        },
+        csms.address(),
+        Some(&[OcppVersion::V1_6]),
+        None,
+        None,
+        TokioExecutor,
+        ocpp_charge_point::provisioning::TokioBackoff,
+    )
+    .await
+    .expect("the session should come up");
+    csms.wait_for("BootNotification").await;
+
+    csms.send_call(
+        "reserve-1",
+        "ReserveNow",
+        serde_json::json!({
+            "connectorId": 1,
+            "expiryDate": "2099-01-01T00:00:00Z",
+            "idTag": "04A224B2",
+            "reservationId": 7
+        }),
+    );
+    for _ in 0..200 {
+        if runtime.state().evses[0].reservations[0].is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+
+    let reading = |energy_wh| {
+        ConnectorEvent::MeterValueSampled(ocpp_charge_point::state::MeterSample {
+            energy_wh,
+            ..Default::default()
+        })
+    };
+    for event in [
+        reading(1_000),
+        ConnectorEvent::CableConnected,
+        ConnectorEvent::LockConfirmed,
+        ConnectorEvent::IdTokenPresented(id_token()),
+        ConnectorEvent::ChargingAuthorized(id_token()),
+        ConnectorEvent::ContactorClosed,
+        reading(1_500),
+        ConnectorEvent::ChargingSuspendedByEv,
+        reading(1_520),
+        ConnectorEvent::ChargingStopped(ocpp_charge_point::state::StopReason::Local),
+        ConnectorEvent::ContactorOpened,
+        ConnectorEvent::UnlockConfirmed,
+        ConnectorEvent::CableDisconnected,
+    ] {
+        let _ = runtime
+            .send(ChargePointEvent::Evse {
+                evse_id: 0,
+                event: EvseEvent::Connector {
+                    connector_id: 0,
+                    event,
+                },
+            })
+            .await;
+    }
+
+    let start = csms.wait_for("StartTransaction").await;
+    assert_eq!(start.payload["reservationId"], 7);
+    assert_eq!(start.payload["meterStart"], 1_000);
+    let stop = csms.wait_for("StopTransaction").await;
+    assert_eq!(stop.payload["transactionId"], 42);
+    assert_eq!(stop.payload["meterStop"], 1_520);
+}
@@ -15,12 +15,18 @@
 //! with **no** CALLERROR at all - there's no `uniqueId` to reply to it with, and no hook exists to
 //! change that from this crate. It still doesn't crash anything, which the second test below
 //! covers.
+//!
+//! Both kinds are also *counted* (`ocpp_charge_point::invalid_messages`): the third test checks
+//! that a CSMS reading `SecurityCtrlr.InvalidMessages` sees them, and that they raise
+//! `InvalidMessages`. Like `payload_size_limit.rs`, it sends them on a *redial* - the first
+//! connection is built inside `ocpp_client::connect`, whose wire this crate cannot watch.
 
 use futures::{SinkExt, StreamExt};
 use ocpp_charge_point::connect_and_setup;
 use ocpp_charge_point::executor::TokioExecutor;
 use ocpp_charge_point::hardware::{ChargePoint, Connector, Evse};
 use ocpp_charge_point::provisioning::TokioBackoff;
+use ocpp_charge_point::state::SecurityEventType;
 use serde_json::{Value, json};
 use std::sync::Arc;
 use tokio::net::TcpListener;
@@ -319,3 +325,94 @@ async fn a_frame_that_is_not_even_valid_json_does_not_take_the_connection_down()
         .expect("the CSMS side of the test timed out")
         .unwrap();
 }
+
+#[tokio::test]
+async fn on_a_redial_malformed_and_undecodable_calls_are_counted_and_reported() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let csms = tokio::spawn(async move {
+        // First connection: boot, then drop it so the charge point redials through the
+        // transport this crate wraps.
+        let mut socket = accept(&listener).await;
+        let boot = next_call(&mut socket, "BootNotification").await;
+        accept_boot(&mut socket, &boot).await;
+        tokio::time::sleep(core::time::Duration::from_millis(500)).await;
+        socket.close(None).await.ok();
+        drop(socket);
+
+        let mut socket = accept(&listener).await;
+        let boot = next_call(&mut socket, "BootNotification").await;
+        accept_boot(&mut socket, &boot).await;
+
+        socket
+            .send(Message::text("not json at all { [ garbage"))
+            .await
+            .unwrap();
+        let undecodable = json!([2, "undecodable-1", "GetVariables", {}]);
+        socket
+            .send(Message::text(serde_json::to_string(&undecodable).unwrap()))
+            .await
+            .unwrap();
+        let reply = next_reply_to(&mut socket, "undecodable-1").await;
+        assert_eq!(reply[0], 4, "unexpected reply: {reply}");
+
+        // The operator's view: one `GetVariables`, no security log pull needed.
+        let read_counter = json!([
+            2,
+            "read-counter",
+            "GetVariables",
+            {
+                "getVariableData": [
+                    { "component": { "name": "SecurityCtrlr" }, "variable": { "name": "InvalidMessages" } }
+                ]
+            }
+        ]);
+        socket
+            .send(Message::text(serde_json::to_string(&read_counter).unwrap()))
+            .await
+            .unwrap();
+        let reply = next_reply_to(&mut socket, "read-counter").await;
+        assert_eq!(
+            reply[2]["getVariableResult"][0]["attributeValue"], "2",
+            "the garbage frame and the undecodable CALL must both be counted: {reply}"
+        );
+    });
+
+    let runtime = connect_and_setup(
+        charge_point(),
+        &format!("ws://{addr}"),
+        None,
+        None,
+        None,
+        TokioExecutor,
+        TokioBackoff,
+    )
+    .await
+    .unwrap();
+    let mut security_events = runtime.subscribe_security_events();
+
+    tokio::time::timeout(core::time::Duration::from_secs(10), csms)
+        .await
+        .expect("the CSMS side of the test timed out")
+        .unwrap();
+
+    // The first of the two is reported at once; the second waits for the interval's summary.
+    let mut reported = Vec::new();
+    while let Ok(Ok(event)) = tokio::time::timeout(
+        core::time::Duration::from_millis(200),
+        security_events.recv(),
+    )
+    .await
+    {
+        if event.event_type == SecurityEventType::InvalidMessages {
+            reported.push(event.tech_info);
+        }
+    }
+    assert_eq!(
+        reported,
+        vec![Some(
+            "received a frame that is not a valid OCPP-J message".to_string()
+        )]
+    );
+}
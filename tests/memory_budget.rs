@@ -41,10 +41,10 @@ use ocpp_charge_point::state::{
     ChargingProfilePurpose, ChargingProfileScope, ChargingRateUnit, ChargingSchedule,
     ChargingSchedulePeriod, Component, ConnectorState, ConnectorStatus, ConnectorStatusChanged,
     DeviceModelEvent, IdToken, IdTokenKind, LocalListEntry, MeterSample, Reservation,
-    ReservationId, SecurityEvent, SecurityEventType, StateLimits, Transaction,
-    TransactionChargingState, TransactionEventKind, TransactionEventOccurred, TransactionId,
-    Variable, VariableAttribute, VariableAttributeType, VariableCharacteristics, VariableDataType,
-    VariableMutability,
+    ReservationId, SecurityEvent, SecurityEventType, StateLimits, TimestampedMeterSample,
+    Transaction, TransactionChargingState, TransactionEventKind, TransactionEventOccurred,
+    TransactionId, Variable, VariableAttribute, VariableAttributeType, VariableCharacteristics,
+    VariableDataType, VariableMutability,
 };
 use ocpp_charge_point::state::{ChargePointState, DeviceModel, EvseState, VariableDefinition};
 
@@ -154,21 +154,41 @@ fn full_local_list(entries: usize) -> Vec<LocalListEntry> {
         .collect()
 }
 
-/// A worst-case active transaction: an id token plus a meter sample with every measurand present.
+/// A meter sample with every measurand present. Unsigned: a signed reading adds whatever its
+/// meter's record weighs, which is the meter's business rather than this crate's
+/// (`docs/MEMORY.md`).
+fn full_sample() -> MeterSample {
+    MeterSample {
+        energy_wh: 12_345,
+        power_w: Some(7_400),
+        current_ma: Some(32_000),
+        voltage_v: Some(230),
+        soc_percent: Some(64),
+        energy_export_wh: Some(1_234),
+        power_export_w: Some(3_700),
+        reactive_power_var: Some(800),
+        power_factor_milli: Some(980),
+        frequency_millihertz: Some(50_000),
+        temperature_c: Some(41),
+        phase_current_ma: [Some(32_000); 3],
+        phase_voltage_v: [Some(230); 3],
+        signed: None,
+    }
+}
+
+/// A worst-case active transaction: an id token plus start and latest meter samples with every
+/// measurand present. Its `ended_meter_samples` is empty, as a running transaction's always is -
+/// the series is held against the connector until the transaction ends (see
+/// [`full_ended_series`]).
 fn full_transaction(index: usize) -> Transaction {
+    let sample = full_sample();
     Transaction {
         id: TransactionId(index as u64),
         id_token: Some(id_token(index)),
         charging_state: TransactionChargingState::Charging,
         stop_reason: None,
         seq_no: 42,
-        last_meter_sample: Some(MeterSample {
-            energy_wh: 12_345,
-            power_w: Some(7_400),
-            current_ma: Some(32_000),
-            voltage_v: Some(230),
-            soc_percent: Some(64),
-        }),
+        last_meter_sample: Some(sample.clone()),
         priority_charging: true,
         remote_start_id: None,
         reservation_id: None,
@@ -178,9 +198,24 @@ fn full_transaction(index: usize) -> Transaction {
         limit_reached: None,
         energy_start_wh: None,
         elapsed_secs: None,
+        ended_meter_samples: Vec::new(),
+        start_meter_sample: Some(sample),
     }
 }
 
+/// A worst-case `Ended` series: `len` full samples - `StateLimits::max_ended_meter_samples`
+/// of them is as many as a connector keeps, and as many as its `Ended` event then carries.
+fn full_ended_series(len: usize) -> Vec<TimestampedMeterSample> {
+    let timestamp = chrono::DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+    (0..len)
+        .map(|_| TimestampedMeterSample {
+            timestamp,
+            sample: full_sample(),
+            clock_aligned: false,
+        })
+        .collect()
+}
+
 /// Registers `variables` device model variables through the documented event path, spread
 /// `per_component` variables to a component, so what's measured is what a hardware binding's
 /// registrations actually cost. Returns how many the model accepted - fewer than asked for means
@@ -231,12 +266,12 @@ fn fill_device_model(
     state.device_model.len()
 }
 
-/// Fills every connector with an active transaction and a held reservation - the state a fully busy
-/// charge point holds. Constructed directly rather than driven through the connector state machine:
+/// Fills every connector with an active transaction, a held reservation and a full `Ended`
+/// series: the state a fully busy charge point holds. Constructed directly rather than driven through the connector state machine:
 /// this measures what the collections cost when occupied, and a connector can't hold both a
 /// transaction and a reservation through the live path (which is exactly why measuring the
 /// worst case needs the fields set directly).
-fn fill_connectors(state: &mut ChargePointState) {
+fn fill_connectors(state: &mut ChargePointState, ended_samples: usize) {
     for (evse_id, evse) in state.evses.iter_mut().enumerate() {
         for connector_id in 0..evse.connectors.len() {
             let index = evse_id * 100 + connector_id;
@@ -250,6 +285,7 @@ fn fill_connectors(state: &mut ChargePointState) {
                 expires_at: None,
             });
             evse.running_costs[connector_id] = Some(12.75);
+            evse.ended_meter_samples[connector_id] = full_ended_series(ended_samples);
         }
     }
 }
@@ -270,19 +306,24 @@ fn full_status_queue(capacity: usize) -> OfflineQueue<ConnectorStatusChanged> {
     queue
 }
 
-/// A worst-case transaction queue backlog: `capacity` transaction events, each carrying an id token
-/// and a full meter sample.
-fn full_transaction_queue(capacity: usize) -> OfflineQueue<TransactionEventOccurred> {
+/// A worst-case transaction queue backlog: `capacity` transaction events, each carrying an id token,
+/// full meter samples and a full `Ended` series. Every slot an `Ended` event is the worst a
+/// backlog can hold - one for each of `capacity` sessions that ended while the CSMS was away.
+fn full_transaction_queue(
+    capacity: usize,
+    ended_samples: usize,
+) -> OfflineQueue<TransactionEventOccurred> {
     let queue = OfflineQueue::with_capacity(capacity);
     queue.restore_backlog(
         (0..capacity)
             .map(|index| TransactionEventOccurred {
                 evse_id: 0,
                 connector_id: 0,
-                kind: TransactionEventKind::Updated(
-                    ocpp_charge_point::state::TransactionUpdateReason::MeterValuePeriodic,
-                ),
-                transaction: full_transaction(index),
+                kind: TransactionEventKind::Ended,
+                transaction: Transaction {
+                    ended_meter_samples: full_ended_series(ended_samples),
+                    ..full_transaction(index)
+                },
                 offline: false,
             })
             .collect(),
@@ -425,7 +466,8 @@ fn measure(configuration: &Configuration) -> Measurement {
         );
     });
 
-    let (connectors, _) = retained(|| fill_connectors(&mut state));
+    let ended_samples = configuration.limits.max_ended_meter_samples;
+    let (connectors, _) = retained(|| fill_connectors(&mut state, ended_samples));
 
     let mut installed_profiles = 0;
     let (charging_profiles, _) = retained(|| {
@@ -438,7 +480,7 @@ fn measure(configuration: &Configuration) -> Measurement {
 
     let (status_queue, status) = retained(|| full_status_queue(configuration.queue_capacity));
     let (transaction_queue, transactions) =
-        retained(|| full_transaction_queue(configuration.queue_capacity));
+        retained(|| full_transaction_queue(configuration.queue_capacity, ended_samples));
     let (security_queue, security) = retained(|| full_security_queue(configuration.queue_capacity));
     let (security_log, log) = retained(|| full_security_log(configuration.security_log_capacity));
 
@@ -521,7 +563,7 @@ fn device_model_shape_comparison(variables: usize, shapes: &[usize]) -> Vec<(usi
 /// above the measured figure, so ordinary drift doesn't fail the build but a change that
 /// meaningfully grows retained state does - the point of measuring at all (G2.3). Raise a ceiling
 /// only together with `docs/MEMORY.md`'s table.
-const CEILINGS: [usize; 3] = [79_000, 230_000, 505_000];
+const CEILINGS: [usize; 3] = [90_000, 268_000, 595_000];
 
 #[test]
 fn retained_heap_per_configuration_stays_within_its_documented_budget() {
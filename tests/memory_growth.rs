@@ -267,6 +267,7 @@ async fn run_transaction(harness: &mut Harness, evse_id: usize, connector_id: us
                     current_ma: Some(32_000),
                     voltage_v: Some(230),
                     soc_percent: Some(40 + sample),
+                    ..Default::default()
                 }),
             )
             .await;
@@ -682,7 +682,7 @@ async fn mailbox_backpressure_refuses_and_preserves_fifo_order() {
         .lock()
         .unwrap()
         .iter()
-        .filter_map(|occurred| occurred.transaction.last_meter_sample)
+        .filter_map(|occurred| occurred.transaction.last_meter_sample.clone())
         .map(|sample| sample.energy_wh)
         .filter(|energy| (10_000..10_000 + FLOOD as i64).contains(energy))
         .collect();
@@ -24,8 +24,11 @@
 //! # What this does not cover
 //!
 //! A cut *mid-write* (a torn record) is `AtomicStorage`'s concern and is tested directly in
-//! `src/hardware/storage.rs`; `InMemoryStorage` here is atomic by construction, so this sweep
-//! exercises cut-between-writes, which is the case the write *policy* governs.
+//! `src/hardware/storage.rs` (and, over real files, `src/hardware/file_storage.rs`);
+//! `InMemoryStorage` here is atomic by construction, so this sweep exercises cut-between-writes,
+//! which is the case the write *policy* governs. The first sweep is repeated over `FileStorage`,
+//! so the backend std integrators actually ship is held to the same invariants, with the reboot
+//! reopening the directory rather than reusing a handle.
 //!
 //! The other three E2 rows that survive a reboot - the local authorization list, reservations and
 //! the device model - are not swept here: none of them is written *during* a transaction, so a
@@ -40,7 +43,7 @@ use std::sync::Arc;
 use ocpp_charge_point::actor::ChargePointActor;
 use ocpp_charge_point::clock::SystemClock;
 use ocpp_charge_point::executor::TokioExecutor;
-use ocpp_charge_point::hardware::InMemoryStorage;
+use ocpp_charge_point::hardware::{FileStorage, InMemoryStorage, Storage};
 use ocpp_charge_point::persistence::{
     DEFAULT_METER_WRITE_THRESHOLD_WH, TransactionStore, restore_transactions,
     run_transaction_persistence,
@@ -170,8 +173,8 @@ struct Recovery {
 /// Returns what the reboot recovered. The pre-cut charge point is dropped *before* the new one is
 /// spawned, so nothing in RAM can leak across the boundary - the only channel between them is
 /// `storage`.
-async fn cut_after(
-    storage: &Arc<InMemoryStorage>,
+async fn cut_after<S: Storage + Send + Sync + 'static>(
+    storage: &Arc<S>,
     steps: &[Step],
     cut_after: usize,
     write_threshold_wh: i64,
@@ -482,6 +485,7 @@ async fn at_every_cut_point_the_csms_eventually_learns_the_delivered_energy() {
             ended[0]
                 .transaction
                 .last_meter_sample
+                .as_ref()
                 .map(|sample| sample.energy_wh),
             delivered_energy_wh(&steps, run),
             "cutting after {run} steps ({where_it_cut}): the CSMS was never told about the energy"
@@ -534,3 +538,57 @@ async fn a_transaction_id_is_never_reused_across_repeated_cuts_on_the_same_stora
         "the sweep must actually have recovered some transactions, or it proves nothing"
     );
 }
+
+/// A fresh, empty directory under the system temp dir, removed again on drop.
+struct TempDir(std::path::PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("ocpp-cp-power-cut-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        Self(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+#[tokio::test]
+async fn every_cut_point_recovers_exactly_what_was_delivered_from_file_storage() {
+    let steps = lifecycle(&[1_000, 2_500, 4_200]);
+
+    for run in 0..=steps.len() {
+        let dir = TempDir::new(&format!("sweep-{run}"));
+        let storage = Arc::new(FileStorage::open(&dir.0).unwrap());
+        let recovery = cut_after(&storage, &steps, run, 0).await;
+
+        // Nothing survives in RAM but the files: the next boot opens the directory afresh.
+        drop(storage);
+        let reopened = Arc::new(FileStorage::open(&dir.0).unwrap());
+        let second_boot = ChargePointActor::spawn([1], &TokioExecutor);
+        assert_eq!(
+            restore_transactions(&second_boot, &TransactionStore::new(reopened)).await,
+            0,
+            "a reopened directory must not bring back a transaction that was already recovered"
+        );
+
+        let expected_in_flight = run > 0 && steps[run - 1].transaction_in_flight;
+        assert_eq!(
+            recovery.count,
+            usize::from(expected_in_flight),
+            "cutting after {run} steps should recover {} transaction(s) from files",
+            usize::from(expected_in_flight)
+        );
+        if expected_in_flight {
+            assert_eq!(
+                recovery.billable_energy_wh,
+                delivered_energy_wh(&steps, run),
+                "cutting after {run} steps lost billable energy on file storage"
+            );
+        }
+    }
+}